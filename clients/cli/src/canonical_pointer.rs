@@ -0,0 +1,269 @@
+use {
+    crate::{
+        common::{parse_pubkey, parse_token_program},
+        config::Config,
+        output::format_output,
+        CommandResult, Error,
+    },
+    clap::Args,
+    serde_derive::{Deserialize, Serialize},
+    serde_with::{serde_as, DisplayFromStr},
+    solana_cli_output::{display::writeln_name_value, QuietDisplay, VerboseDisplay},
+    solana_client::nonblocking::rpc_client::RpcClient,
+    solana_pubkey::Pubkey,
+    spl_token_wrap::{
+        get_canonical_pointer_address, get_pending_canonical_pointer_address,
+        get_scoped_canonical_pointer_address, get_scoped_pending_canonical_pointer_address,
+        state::{CanonicalDeploymentPointer, PendingCanonicalPointer, VersionedState},
+    },
+    std::fmt::{Display, Formatter},
+};
+
+#[derive(Clone, Debug, Args)]
+pub struct CanonicalPointerArgs {
+    /// The address of the unwrapped mint
+    #[clap(value_parser = parse_pubkey)]
+    pub unwrapped_mint: Pubkey,
+
+    /// Also show the pointer scoped to the wrapped mint of this token program,
+    /// which takes precedence over the unscoped pointer
+    #[clap(long, value_parser = parse_token_program)]
+    pub wrapped_token_program: Option<Pubkey>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PointerOutput {
+    #[serde_as(as = "DisplayFromStr")]
+    pub program_id: Pubkey,
+    pub enforce_on_wrap: bool,
+    pub change_delay: u64,
+}
+
+impl From<&CanonicalDeploymentPointer> for PointerOutput {
+    fn from(pointer: &CanonicalDeploymentPointer) -> Self {
+        Self {
+            program_id: pointer.program_id,
+            enforce_on_wrap: pointer.enforce_on_wrap.into(),
+            change_delay: pointer.change_delay.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingPointerOutput {
+    #[serde(flatten)]
+    pub pointer: PointerOutput,
+    pub proposed_slot: u64,
+    pub proposed_timestamp: i64,
+    pub activation_timestamp: i64,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CanonicalPointerOutput {
+    #[serde_as(as = "DisplayFromStr")]
+    pub canonical_pointer_address: Pubkey,
+    pub canonical_pointer: Option<PointerOutput>,
+    #[serde_as(as = "DisplayFromStr")]
+    pub pending_canonical_pointer_address: Pubkey,
+    pub pending_canonical_pointer: Option<PendingPointerOutput>,
+    pub scoped: Option<ScopedCanonicalPointerOutput>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScopedCanonicalPointerOutput {
+    #[serde_as(as = "DisplayFromStr")]
+    pub wrapped_token_program: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub canonical_pointer_address: Pubkey,
+    pub canonical_pointer: Option<PointerOutput>,
+    #[serde_as(as = "DisplayFromStr")]
+    pub pending_canonical_pointer_address: Pubkey,
+    pub pending_canonical_pointer: Option<PendingPointerOutput>,
+}
+
+fn writeln_pointer(f: &mut Formatter<'_>, pointer: &PointerOutput) -> std::fmt::Result {
+    writeln_name_value(f, "  Program id:", &pointer.program_id.to_string())?;
+    writeln_name_value(
+        f,
+        "  Enforced on wrap:",
+        &pointer.enforce_on_wrap.to_string(),
+    )?;
+    writeln_name_value(
+        f,
+        "  Change delay (seconds):",
+        &pointer.change_delay.to_string(),
+    )
+}
+
+fn writeln_pending_pointer(
+    f: &mut Formatter<'_>,
+    pending_canonical_pointer: &Option<PendingPointerOutput>,
+) -> std::fmt::Result {
+    match pending_canonical_pointer {
+        Some(pending) => {
+            writeln!(f, "  WARNING: a canonical pointer change has been proposed")?;
+            writeln_pointer(f, &pending.pointer)?;
+            writeln_name_value(f, "  Proposed slot:", &pending.proposed_slot.to_string())?;
+            writeln_name_value(
+                f,
+                "  Proposed at (unix timestamp):",
+                &pending.proposed_timestamp.to_string(),
+            )?;
+            writeln_name_value(
+                f,
+                "  Can be accepted from (unix timestamp):",
+                &pending.activation_timestamp.to_string(),
+            )
+        }
+        None => writeln!(f, "  No change proposed"),
+    }
+}
+
+impl Display for CanonicalPointerOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln_name_value(
+            f,
+            "Canonical pointer address:",
+            &self.canonical_pointer_address.to_string(),
+        )?;
+        match &self.canonical_pointer {
+            Some(pointer) => writeln_pointer(f, pointer)?,
+            None => writeln!(f, "  Not set, every deployment is canonical")?,
+        }
+
+        writeln_name_value(
+            f,
+            "Pending canonical pointer address:",
+            &self.pending_canonical_pointer_address.to_string(),
+        )?;
+        writeln_pending_pointer(f, &self.pending_canonical_pointer)?;
+
+        if let Some(scoped) = &self.scoped {
+            writeln!(f)?;
+            writeln_name_value(
+                f,
+                "Wrapped token program:",
+                &scoped.wrapped_token_program.to_string(),
+            )?;
+            writeln_name_value(
+                f,
+                "Scoped canonical pointer address:",
+                &scoped.canonical_pointer_address.to_string(),
+            )?;
+            match &scoped.canonical_pointer {
+                Some(pointer) => writeln_pointer(f, pointer)?,
+                None => writeln!(f, "  Not set, the unscoped pointer applies")?,
+            }
+
+            writeln_name_value(
+                f,
+                "Scoped pending canonical pointer address:",
+                &scoped.pending_canonical_pointer_address.to_string(),
+            )?;
+            writeln_pending_pointer(f, &scoped.pending_canonical_pointer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl QuietDisplay for CanonicalPointerOutput {
+    fn write_str(&self, _: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        Ok(())
+    }
+}
+impl VerboseDisplay for CanonicalPointerOutput {}
+
+async fn get_pointer(
+    rpc_client: &RpcClient,
+    address: &Pubkey,
+) -> Result<Option<PointerOutput>, Error> {
+    match rpc_client
+        .get_account_with_commitment(address, rpc_client.commitment())
+        .await?
+        .value
+    {
+        Some(account) => Ok(Some(PointerOutput::from(
+            &CanonicalDeploymentPointer::unpack(&account.data)?,
+        ))),
+        None => Ok(None),
+    }
+}
+
+async fn get_pending_pointer(
+    rpc_client: &RpcClient,
+    address: &Pubkey,
+) -> Result<Option<PendingPointerOutput>, Error> {
+    match rpc_client
+        .get_account_with_commitment(address, rpc_client.commitment())
+        .await?
+        .value
+    {
+        Some(account) => {
+            let pending = PendingCanonicalPointer::unpack_versioned(&account.data)?;
+            Ok(Some(PendingPointerOutput {
+                pointer: PointerOutput::from(&pending.pointer),
+                proposed_slot: pending.proposed_slot.into(),
+                proposed_timestamp: pending.proposed_timestamp.into(),
+                activation_timestamp: pending.activation_timestamp.into(),
+            }))
+        }
+        None => Ok(None),
+    }
+}
+
+pub async fn command_canonical_pointer(
+    config: &Config,
+    args: CanonicalPointerArgs,
+) -> CommandResult {
+    let rpc_client = &config.rpc_client;
+
+    let canonical_pointer_address = get_canonical_pointer_address(&args.unwrapped_mint);
+    let canonical_pointer = get_pointer(rpc_client, &canonical_pointer_address).await?;
+
+    let pending_canonical_pointer_address =
+        get_pending_canonical_pointer_address(&args.unwrapped_mint);
+    let pending_canonical_pointer =
+        get_pending_pointer(rpc_client, &pending_canonical_pointer_address).await?;
+
+    let scoped = match args.wrapped_token_program {
+        Some(wrapped_token_program) => {
+            let canonical_pointer_address =
+                get_scoped_canonical_pointer_address(&args.unwrapped_mint, &wrapped_token_program);
+            let pending_canonical_pointer_address = get_scoped_pending_canonical_pointer_address(
+                &args.unwrapped_mint,
+                &wrapped_token_program,
+            );
+            Some(ScopedCanonicalPointerOutput {
+                wrapped_token_program,
+                canonical_pointer_address,
+                canonical_pointer: get_pointer(rpc_client, &canonical_pointer_address).await?,
+                pending_canonical_pointer_address,
+                pending_canonical_pointer: get_pending_pointer(
+                    rpc_client,
+                    &pending_canonical_pointer_address,
+                )
+                .await?,
+            })
+        }
+        None => None,
+    };
+
+    Ok(format_output(
+        config,
+        CanonicalPointerOutput {
+            canonical_pointer_address,
+            canonical_pointer,
+            pending_canonical_pointer_address,
+            pending_canonical_pointer,
+            scoped,
+        },
+    ))
+}
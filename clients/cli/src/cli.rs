@@ -1,11 +1,25 @@
 use {
     crate::{
+        canonical_pointer::{command_canonical_pointer, CanonicalPointerArgs},
         close_stuck_escrow::{command_close_stuck_escrow, CloseStuckEscrowArgs},
+        close_wrapped_mint::{command_close_wrapped_mint, CloseWrappedMintArgs},
         config::Config,
         create_escrow_account::{command_create_escrow_account, CreateEscrowAccountArgs},
         create_mint::{command_create_mint, CreateMintArgs},
         find_pdas::{command_get_pdas, FindPdasArgs},
+        list_wrapped_mints::{command_list_wrapped_mints, ListWrappedMintsArgs},
         output::parse_output_format,
+        reconcile_escrow::{command_reconcile_escrow, ReconcileEscrowArgs},
+        register_wrapped_mint::{command_register_wrapped_mint, RegisterWrappedMintArgs},
+        repair_escrow::{command_repair_escrow, RepairEscrowArgs},
+        set_allowlist_mode::{command_set_allowlist_mode, SetAllowlistModeArgs},
+        set_flash_mint_fee::{command_set_flash_mint_fee, SetFlashMintFeeArgs},
+        set_protocol_fee_config::{command_set_protocol_fee_config, SetProtocolFeeConfigArgs},
+        set_protocol_fee_override::{
+            command_set_protocol_fee_override, SetProtocolFeeOverrideArgs,
+        },
+        set_wrap_limits::{command_set_wrap_limits, SetWrapLimitsArgs},
+        stats::{command_stats, StatsArgs},
         sync_metadata_to_spl_token::{
             command_sync_metadata_to_spl_token, SyncMetadataToSplTokenArgs,
         },
@@ -13,7 +27,9 @@ use {
             command_sync_metadata_to_token2022, SyncMetadataToToken2022Args,
         },
         unwrap::{command_unwrap, UnwrapArgs},
+        update_allowlist::{command_update_allowlist, UpdateAllowlistArgs},
         wrap::{command_wrap, WrapArgs},
+        wrap_limits::{command_wrap_limits, WrapLimitsArgs},
         CommandResult,
     },
     clap::{
@@ -107,11 +123,49 @@ pub enum Command {
     /// Close a stuck escrow account when a mint has closed and re-created with
     /// different mint extensions
     CloseStuckEscrow(CloseStuckEscrowArgs),
+    /// Close a retired wrapped Token-2022 mint and its backpointer once its
+    /// unwrapped mint is closed and its supply is zero
+    CloseWrappedMint(CloseWrappedMintArgs),
+    /// Reallocate an escrow account in place to add the account extensions
+    /// its unwrapped mint requires, without moving the escrowed tokens
+    RepairEscrow(RepairEscrowArgs),
+    /// Apply the escrow surplus policy to unwrapped tokens sent straight to
+    /// the escrow, beyond the wrapped supply
+    ReconcileEscrow(ReconcileEscrowArgs),
     /// Sync metadata from unwrapped mint to wrapped SPL Token mint's `Metaplex`
     /// metadata account
     SyncMetadataToSplToken(SyncMetadataToSplTokenArgs),
     /// Sync metadata from unwrapped mint to wrapped Token-2022 mint
     SyncMetadataToToken2022(SyncMetadataToToken2022Args),
+    /// Show the canonical deployment pointer of an unwrapped mint and any
+    /// pending change to it
+    CanonicalPointer(CanonicalPointerArgs),
+    /// List the wrapped mints recorded in the on-chain registry
+    ListWrappedMints(ListWrappedMintsArgs),
+    /// Record a wrapped mint created before the registry existed in the
+    /// on-chain registry
+    RegisterWrappedMint(RegisterWrappedMintArgs),
+    /// Show the wrap and unwrap statistics recorded for a wrapped mint
+    Stats(StatsArgs),
+    /// Set the limits on wrapping an unwrapped mint: caps on the wrapped
+    /// supply and on the amount wrapped per epoch, or unwrap-only mode
+    SetWrapLimits(SetWrapLimitsArgs),
+    /// Show the wrap limits of an unwrapped mint
+    WrapLimits(WrapLimitsArgs),
+    /// Enable or disable the allowlist of an unwrapped mint, restricting
+    /// wrapping and unwrapping to allowlisted wallets
+    SetAllowlistMode(SetAllowlistModeArgs),
+    /// Add wallets to or remove them from the allowlist of an unwrapped mint
+    UpdateAllowlist(UpdateAllowlistArgs),
+    /// Set the protocol fee charged on wraps and unwraps by this deployment,
+    /// as the program upgrade authority
+    SetProtocolFeeConfig(SetProtocolFeeConfigArgs),
+    /// Override the protocol fee of an unwrapped mint, as the program upgrade
+    /// authority
+    SetProtocolFeeOverride(SetProtocolFeeOverrideArgs),
+    /// Set the fee burned on top of flash mints of a wrapped mint, in basis
+    /// points
+    SetFlashMintFee(SetFlashMintFeeArgs),
 }
 
 impl Command {
@@ -128,12 +182,38 @@ impl Command {
             Command::Unwrap(args) => command_unwrap(config, args, matches, wallet_manager).await,
             Command::CreateEscrowAccount(args) => command_create_escrow_account(config, args).await,
             Command::CloseStuckEscrow(args) => command_close_stuck_escrow(config, args).await,
+            Command::CloseWrappedMint(args) => command_close_wrapped_mint(config, args).await,
+            Command::RepairEscrow(args) => command_repair_escrow(config, args).await,
+            Command::ReconcileEscrow(args) => command_reconcile_escrow(config, args).await,
             Command::SyncMetadataToSplToken(args) => {
                 command_sync_metadata_to_spl_token(config, args, matches, wallet_manager).await
             }
             Command::SyncMetadataToToken2022(args) => {
                 command_sync_metadata_to_token2022(config, args, matches, wallet_manager).await
             }
+            Command::CanonicalPointer(args) => command_canonical_pointer(config, args).await,
+            Command::ListWrappedMints(args) => command_list_wrapped_mints(config, args).await,
+            Command::RegisterWrappedMint(args) => command_register_wrapped_mint(config, args).await,
+            Command::Stats(args) => command_stats(config, args).await,
+            Command::SetWrapLimits(args) => {
+                command_set_wrap_limits(config, args, matches, wallet_manager).await
+            }
+            Command::WrapLimits(args) => command_wrap_limits(config, args).await,
+            Command::SetAllowlistMode(args) => {
+                command_set_allowlist_mode(config, args, matches, wallet_manager).await
+            }
+            Command::UpdateAllowlist(args) => {
+                command_update_allowlist(config, args, matches, wallet_manager).await
+            }
+            Command::SetProtocolFeeConfig(args) => {
+                command_set_protocol_fee_config(config, args, matches, wallet_manager).await
+            }
+            Command::SetProtocolFeeOverride(args) => {
+                command_set_protocol_fee_override(config, args, matches, wallet_manager).await
+            }
+            Command::SetFlashMintFee(args) => {
+                command_set_flash_mint_fee(config, args, matches, wallet_manager).await
+            }
         }
     }
 }
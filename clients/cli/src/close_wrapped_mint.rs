@@ -0,0 +1,145 @@
+use {
+    crate::{
+        common::{parse_pubkey, parse_token_program, process_transaction},
+        config::Config,
+        output::{format_output, println_display},
+        CommandResult,
+    },
+    clap::Args,
+    serde_derive::{Deserialize, Serialize},
+    serde_with::{serde_as, DisplayFromStr},
+    solana_cli_output::{display::writeln_name_value, QuietDisplay, VerboseDisplay},
+    solana_pubkey::Pubkey,
+    solana_signature::Signature,
+    solana_signer::Signer,
+    solana_transaction::Transaction,
+    spl_token_wrap::{
+        get_escrow_address, get_wrapped_mint_authority, get_wrapped_mint_backpointer_address,
+        instruction::close_wrapped_mint, state::Backpointer,
+    },
+    std::fmt::{Display, Formatter},
+};
+
+#[derive(Clone, Debug, Args)]
+pub struct CloseWrappedMintArgs {
+    /// The address of the wrapped mint to close
+    #[clap(value_parser = parse_pubkey)]
+    pub wrapped_mint: Pubkey,
+
+    /// The address of the account to send lamports to
+    #[clap(value_parser = parse_pubkey)]
+    pub destination: Pubkey,
+
+    /// The address of the token program of the closed unwrapped mint.
+    /// Only required if the backpointer predates provenance tracking.
+    #[clap(long, value_parser = parse_token_program)]
+    pub unwrapped_token_program: Option<Pubkey>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloseWrappedMintOutput {
+    #[serde_as(as = "DisplayFromStr")]
+    pub wrapped_mint: Pubkey,
+
+    #[serde_as(as = "DisplayFromStr")]
+    pub unwrapped_mint: Pubkey,
+
+    #[serde_as(as = "DisplayFromStr")]
+    pub backpointer: Pubkey,
+
+    pub signatures: Vec<Signature>,
+}
+
+impl Display for CloseWrappedMintOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln_name_value(f, "Wrapped mint:", &self.wrapped_mint.to_string())?;
+        writeln_name_value(f, "Unwrapped mint:", &self.unwrapped_mint.to_string())?;
+        writeln_name_value(f, "Backpointer:", &self.backpointer.to_string())?;
+
+        writeln!(f, "Signers:")?;
+        for signature in &self.signatures {
+            writeln!(f, "  {signature}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl QuietDisplay for CloseWrappedMintOutput {
+    fn write_str(&self, _: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        Ok(())
+    }
+}
+impl VerboseDisplay for CloseWrappedMintOutput {}
+
+pub async fn command_close_wrapped_mint(
+    config: &Config,
+    args: CloseWrappedMintArgs,
+) -> CommandResult {
+    let backpointer_address = get_wrapped_mint_backpointer_address(&args.wrapped_mint);
+    let backpointer_account = config
+        .rpc_client
+        .get_account(&backpointer_address)
+        .await
+        .map_err(|_| format!("Backpointer {backpointer_address} not found"))?;
+    let backpointer = Backpointer::unpack(&backpointer_account.data)?;
+
+    let unwrapped_token_program = if backpointer.has_provenance() {
+        backpointer.unwrapped_token_program
+    } else {
+        args.unwrapped_token_program.ok_or(
+            "Backpointer does not record the unwrapped token program, pass \
+             --unwrapped-token-program",
+        )?
+    };
+
+    let escrow = get_escrow_address(
+        &backpointer.unwrapped_mint,
+        &unwrapped_token_program,
+        &spl_token_2022_interface::id(),
+    );
+
+    if !config.dry_run {
+        println_display(
+            config,
+            format!(
+                "Closing wrapped mint {} of unwrapped mint {}",
+                args.wrapped_mint, backpointer.unwrapped_mint,
+            ),
+        );
+    }
+
+    let instruction = close_wrapped_mint(
+        &spl_token_wrap::id(),
+        &args.wrapped_mint,
+        &backpointer_address,
+        &args.destination,
+        &backpointer.unwrapped_mint,
+        &escrow,
+        &get_wrapped_mint_authority(&args.wrapped_mint),
+        &unwrapped_token_program,
+    );
+
+    let latest_blockhash = config.rpc_client.get_latest_blockhash().await?;
+    let payer = config.fee_payer()?;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[&*payer],
+        latest_blockhash,
+    );
+
+    process_transaction(config, transaction.clone()).await?;
+
+    let output = CloseWrappedMintOutput {
+        wrapped_mint: args.wrapped_mint,
+        unwrapped_mint: backpointer.unwrapped_mint,
+        backpointer: backpointer_address,
+        signatures: transaction.signatures,
+    };
+
+    Ok(format_output(config, output))
+}
@@ -7,11 +7,16 @@ use {
     solana_pubkey::Pubkey,
     solana_signature::Signature,
     solana_transaction::Transaction,
+    spl_associated_token_account_interface::address::get_associated_token_address_with_program_id,
     spl_token_2022_interface::{
         extension::{PodStateWithExtensions, StateWithExtensions},
-        pod::PodAccount,
+        pod::{PodAccount, PodMint},
         state::Mint,
     },
+    spl_token_wrap::{
+        get_protocol_fee_config_address,
+        state::{ProtocolFeeConfig, VersionedState},
+    },
     std::str::FromStr,
 };
 
@@ -87,6 +92,28 @@ pub async fn get_mint_for_token_account(
     Ok(unpacked_account.base.mint)
 }
 
+pub async fn get_token_account_owner(
+    rpc_client: &RpcClient,
+    token_account_address: &Pubkey,
+) -> Result<Pubkey, Error> {
+    let token_account_info = rpc_client.get_account(token_account_address).await?;
+    let unpacked_account = PodStateWithExtensions::<PodAccount>::unpack(&token_account_info.data)?;
+    Ok(unpacked_account.base.owner)
+}
+
+/// Returns the balance of an escrow and the supply of its wrapped mint
+pub async fn get_escrow_collateral(
+    rpc_client: &RpcClient,
+    escrow_address: &Pubkey,
+    wrapped_mint_address: &Pubkey,
+) -> Result<(u64, u64), Error> {
+    let escrow_info = rpc_client.get_account(escrow_address).await?;
+    let escrow = PodStateWithExtensions::<PodAccount>::unpack(&escrow_info.data)?;
+    let wrapped_mint_info = rpc_client.get_account(wrapped_mint_address).await?;
+    let wrapped_mint = PodStateWithExtensions::<PodMint>::unpack(&wrapped_mint_info.data)?;
+    Ok((escrow.base.amount.into(), wrapped_mint.base.supply.into()))
+}
+
 pub async fn get_account_owner(rpc_client: &RpcClient, account: &Pubkey) -> Result<Pubkey, Error> {
     let owner = rpc_client.get_account(account).await?.owner;
     Ok(owner)
@@ -115,3 +142,47 @@ pub async fn assert_mint_account(
 
     Ok(())
 }
+
+/// Returns the lamports an account is missing to be rent-exempt, with the
+/// required space computed from its current data
+pub async fn get_rent_shortfall(
+    rpc_client: &RpcClient,
+    address: &Pubkey,
+    space: impl FnOnce(Option<&[u8]>) -> Result<usize, Error>,
+) -> Result<u64, Error> {
+    let account = rpc_client
+        .get_account_with_commitment(address, rpc_client.commitment())
+        .await?
+        .value;
+    let space = space(account.as_ref().map(|account| account.data.as_slice()))?;
+    let rent = rpc_client
+        .get_minimum_balance_for_rent_exemption(space)
+        .await?;
+    Ok(rent.saturating_sub(account.map(|account| account.lamports).unwrap_or(0)))
+}
+
+/// Returns the protocol fee vault for an unwrapped mint: the associated token
+/// account of the fee destination if the deployment charges a protocol fee,
+/// otherwise the fallback, as the vault is only checked when a fee applies
+pub async fn get_protocol_fee_vault(
+    rpc_client: &RpcClient,
+    unwrapped_mint: &Pubkey,
+    unwrapped_token_program: &Pubkey,
+    fallback: &Pubkey,
+) -> Result<Pubkey, Error> {
+    let config = rpc_client
+        .get_account_with_commitment(&get_protocol_fee_config_address(), rpc_client.commitment())
+        .await?
+        .value;
+    match config {
+        Some(account) if account.owner == spl_token_wrap::id() => {
+            let config = ProtocolFeeConfig::unpack_versioned(&account.data)?;
+            Ok(get_associated_token_address_with_program_id(
+                &config.fee_destination,
+                unwrapped_mint,
+                unwrapped_token_program,
+            ))
+        }
+        _ => Ok(*fallback),
+    }
+}
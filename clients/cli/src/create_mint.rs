@@ -1,14 +1,15 @@
 use {
     crate::{
-        common::{parse_pubkey, parse_token_program, process_transaction},
+        common::{get_rent_shortfall, parse_pubkey, parse_token_program, process_transaction},
         config::Config,
         output::{format_output, println_display},
-        CommandResult,
+        CommandResult, Error,
     },
     clap::Args,
     serde_derive::{Deserialize, Serialize},
     serde_with::{serde_as, DisplayFromStr},
     solana_cli_output::{display::writeln_name_value, QuietDisplay, VerboseDisplay},
+    solana_client::nonblocking::rpc_client::RpcClient,
     solana_instruction::Instruction,
     solana_pubkey::Pubkey,
     solana_signature::Signature,
@@ -16,11 +17,18 @@ use {
     solana_transaction::Transaction,
     spl_token::solana_program::program_pack::Pack,
     spl_token_wrap::{
-        get_wrapped_mint_address, get_wrapped_mint_backpointer_address, id,
+        extension_policy::{analyze_mint_extensions, ExtensionRisk},
+        get_wrapped_mint_address, get_wrapped_mint_backpointer_address,
+        get_wrapped_mint_index_address, get_wrapped_mint_registry_address,
+        get_wrapped_mint_registry_page_address, id,
         instruction::create_mint,
         mint_customizer::{
             default_token_2022::DefaultToken2022Customizer, interface::MintCustomizer,
         },
+        state::{
+            Backpointer, VersionedListEntry, VersionedState, WrappedMintIndexEntry,
+            WrappedMintRegistry, WrappedMintRegistryEntry,
+        },
     },
     std::fmt::{Display, Formatter},
 };
@@ -38,6 +46,26 @@ pub struct CreateMintArgs {
     /// Do not err if account already created
     #[clap(long)]
     pub idempotent: bool,
+
+    /// Accept an unwrapped mint with extensions that let its authorities move
+    /// or lock escrowed tokens, like a permanent delegate
+    #[clap(long)]
+    pub acknowledge_risky_extensions: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtensionFindingOutput {
+    pub extension: String,
+    pub rejected: bool,
+    pub description: String,
+}
+
+impl Display for ExtensionFindingOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let risk = if self.rejected { "rejected" } else { "risky" };
+        write!(f, "{} ({}): {}", self.extension, risk, self.description)
+    }
 }
 
 #[serde_as]
@@ -52,6 +80,8 @@ pub struct CreateMintOutput {
     pub wrapped_backpointer_address: Pubkey,
     pub funded_wrapped_mint_lamports: u64,
     pub funded_backpointer_lamports: u64,
+    pub funded_registry_lamports: u64,
+    pub extension_findings: Vec<ExtensionFindingOutput>,
     #[serde_as(as = "Option<DisplayFromStr>")]
     pub signature: Option<Signature>,
 }
@@ -83,6 +113,18 @@ impl Display for CreateMintOutput {
             "Funded backpointer lamports:",
             &self.funded_backpointer_lamports.to_string(),
         )?;
+        writeln_name_value(
+            f,
+            "Funded registry lamports:",
+            &self.funded_registry_lamports.to_string(),
+        )?;
+
+        if !self.extension_findings.is_empty() {
+            writeln!(f, "Unwrapped mint extensions:")?;
+            for finding in &self.extension_findings {
+                writeln!(f, "  {finding}")?;
+            }
+        }
 
         if let Some(signature) = self.signature {
             writeln_name_value(f, "Signature:", &signature.to_string())?;
@@ -99,6 +141,70 @@ impl QuietDisplay for CreateMintOutput {
 }
 impl VerboseDisplay for CreateMintOutput {}
 
+/// Returns the registry accounts a wrapped mint is appended to, with the
+/// lamports each of them is missing to grow by one entry
+pub async fn get_registry_accounts(
+    rpc_client: &RpcClient,
+    unwrapped_mint: &Pubkey,
+) -> Result<[(Pubkey, u64); 3], Error> {
+    let registry_address = get_wrapped_mint_registry_address();
+    let mut next_page_index = 0;
+    let registry_shortfall = get_rent_shortfall(rpc_client, &registry_address, |data| {
+        if let Some(data) = data {
+            next_page_index = WrappedMintRegistry::unpack_versioned(data)?.next_page_index();
+        }
+        Ok(WrappedMintRegistry::LEN)
+    })
+    .await?;
+
+    let page_address = get_wrapped_mint_registry_page_address(next_page_index);
+    let page_shortfall = get_rent_shortfall(rpc_client, &page_address, |data| {
+        let count = match data {
+            Some(data) => WrappedMintRegistryEntry::unpack_list(data)?.len(),
+            None => 0,
+        };
+        Ok(WrappedMintRegistryEntry::list_len(count.saturating_add(1)))
+    })
+    .await?;
+
+    let index_address = get_wrapped_mint_index_address(unwrapped_mint);
+    let index_shortfall = get_rent_shortfall(rpc_client, &index_address, |data| {
+        let count = match data {
+            Some(data) => WrappedMintIndexEntry::unpack_list(data)?.len(),
+            None => 0,
+        };
+        Ok(WrappedMintIndexEntry::list_len(count.saturating_add(1)))
+    })
+    .await?;
+
+    Ok([
+        (registry_address, registry_shortfall),
+        (page_address, page_shortfall),
+        (index_address, index_shortfall),
+    ])
+}
+
+/// Returns the extensions of the unwrapped mint that the `CreateMint`
+/// extension policy flags
+async fn get_extension_findings(
+    rpc_client: &RpcClient,
+    unwrapped_mint: &Pubkey,
+) -> Result<Vec<ExtensionFindingOutput>, Error> {
+    let account = rpc_client.get_account(unwrapped_mint).await?;
+    if account.owner != spl_token_2022_interface::id() {
+        return Ok(vec![]);
+    }
+
+    Ok(analyze_mint_extensions(&account.data)?
+        .into_iter()
+        .map(|finding| ExtensionFindingOutput {
+            extension: format!("{:?}", finding.extension_type),
+            rejected: matches!(finding.risk, ExtensionRisk::Rejected(_)),
+            description: finding.description().to_string(),
+        })
+        .collect())
+}
+
 pub async fn command_create_mint(config: &Config, args: CreateMintArgs) -> CommandResult {
     let payer = config.fee_payer()?;
     let rpc_client = config.rpc_client.clone();
@@ -112,13 +218,18 @@ pub async fn command_create_mint(config: &Config, args: CreateMintArgs) -> Comma
         format!("Creating wrapped mint for {}", args.unwrapped_mint),
     );
 
+    let extension_findings = get_extension_findings(&rpc_client, &args.unwrapped_mint).await?;
+    for finding in &extension_findings {
+        println_display(config, format!("Unwrapped mint extension {finding}"));
+    }
+
     let mut instructions: Vec<Instruction> = Vec::new();
 
     // Fund the wrapped mint account if it doesn't exist or is insufficiently funded
     let wrapped_mint_account = rpc_client.get_account(&wrapped_mint_address).await;
-    let wrapped_mint_lamports = match wrapped_mint_account {
-        Ok(account) => account.lamports,
-        Err(_) => 0,
+    let (wrapped_mint_lamports, wrapped_mint_exists) = match wrapped_mint_account {
+        Ok(account) => (account.lamports, !account.data.is_empty()),
+        Err(_) => (0, false),
     };
 
     let mint_size = if args.wrapped_token_program == spl_token_2022_interface::id() {
@@ -154,9 +265,7 @@ pub async fn command_create_mint(config: &Config, args: CreateMintArgs) -> Comma
     };
 
     let backpointer_rent = rpc_client
-        .get_minimum_balance_for_rent_exemption(std::mem::size_of::<
-            spl_token_wrap::state::Backpointer,
-        >())
+        .get_minimum_balance_for_rent_exemption(Backpointer::LEN)
         .await?;
 
     let funded_backpointer_lamports = backpointer_rent.saturating_sub(backpointer_lamports);
@@ -175,6 +284,44 @@ pub async fn command_create_mint(config: &Config, args: CreateMintArgs) -> Comma
         ));
     }
 
+    // Apply the same extension policy as the program, which skips it for an
+    // existing wrapped mint
+    if !wrapped_mint_exists {
+        if extension_findings.iter().any(|finding| finding.rejected) {
+            return Err(format!(
+                "Unwrapped mint {} has extensions that cannot be wrapped",
+                args.unwrapped_mint
+            )
+            .into());
+        }
+        if !extension_findings.is_empty() && !args.acknowledge_risky_extensions {
+            return Err(format!(
+                "Unwrapped mint {} has risky extensions, pass --acknowledge-risky-extensions \
+                 to wrap it anyway",
+                args.unwrapped_mint
+            )
+            .into());
+        }
+    }
+
+    // Fund the growth of the registry accounts the new wrapped mint is appended to
+    let registry_accounts = get_registry_accounts(&rpc_client, &args.unwrapped_mint).await?;
+    let mut funded_registry_lamports: u64 = 0;
+    if !wrapped_mint_exists {
+        for (address, lamports) in registry_accounts {
+            if lamports > 0 {
+                println_display(
+                    config,
+                    format!("Funding registry account {address} with {lamports} lamports for rent"),
+                );
+                instructions.push(transfer(&payer.pubkey(), &address, lamports));
+                funded_registry_lamports = funded_registry_lamports.saturating_add(lamports);
+            }
+        }
+    }
+    let [(registry_address, _), (registry_page_address, _), (wrapped_mint_index_address, _)] =
+        registry_accounts;
+
     // Add the create_mint instruction
     instructions.push(create_mint(
         &id(),
@@ -182,7 +329,11 @@ pub async fn command_create_mint(config: &Config, args: CreateMintArgs) -> Comma
         &wrapped_backpointer_address,
         &args.unwrapped_mint,
         &args.wrapped_token_program,
+        &registry_address,
+        &registry_page_address,
+        &wrapped_mint_index_address,
         args.idempotent,
+        args.acknowledge_risky_extensions,
     ));
 
     let latest_blockhash = rpc_client.get_latest_blockhash().await?;
@@ -203,6 +354,8 @@ pub async fn command_create_mint(config: &Config, args: CreateMintArgs) -> Comma
             wrapped_backpointer_address,
             funded_wrapped_mint_lamports,
             funded_backpointer_lamports,
+            funded_registry_lamports,
+            extension_findings,
             signature,
         },
     ))
@@ -3,16 +3,17 @@ use {
         common::{get_account_owner, parse_pubkey, parse_token_program},
         config::Config,
         output::format_output,
-        CommandResult,
+        CommandResult, Error,
     },
     clap::Args,
     serde_derive::{Deserialize, Serialize},
     serde_with::{serde_as, DisplayFromStr},
     solana_cli_output::{display::writeln_name_value, QuietDisplay, VerboseDisplay},
+    solana_client::nonblocking::rpc_client::RpcClient,
     solana_pubkey::Pubkey,
     spl_token_wrap::{
         get_escrow_address, get_wrapped_mint_address, get_wrapped_mint_authority,
-        get_wrapped_mint_backpointer_address,
+        get_wrapped_mint_backpointer_address, state::Backpointer,
     },
     std::fmt::{Display, Formatter},
 };
@@ -40,6 +41,114 @@ pub struct PdasOutput {
     pub wrapped_backpointer_address: Pubkey,
     #[serde_as(as = "DisplayFromStr")]
     pub unwrapped_escrow: Pubkey,
+    pub backpointer: Option<BackpointerOutput>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackpointerOutput {
+    #[serde_as(as = "DisplayFromStr")]
+    pub unwrapped_mint: Pubkey,
+    pub provenance: Option<ProvenanceOutput>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenanceOutput {
+    #[serde_as(as = "DisplayFromStr")]
+    pub unwrapped_token_program: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub wrapped_token_program: Pubkey,
+    pub customizer_id: Option<String>,
+    pub creation_slot: Option<u64>,
+    pub program_version: Option<String>,
+    pub wrapped_mint_bump: u8,
+    pub wrapped_mint_authority_bump: u8,
+    pub backpointer_bump: u8,
+}
+
+impl From<&Backpointer> for BackpointerOutput {
+    fn from(backpointer: &Backpointer) -> Self {
+        // Fields that are unknown for backfilled backpointers are zeroed
+        let provenance = backpointer.has_provenance().then(|| {
+            let customizer_id = backpointer
+                .customizer_id
+                .iter()
+                .rposition(|byte| *byte != 0)
+                .map(|last| String::from_utf8_lossy(&backpointer.customizer_id[..=last]).into());
+            let [major, minor, patch] = backpointer.program_version;
+            ProvenanceOutput {
+                unwrapped_token_program: backpointer.unwrapped_token_program,
+                wrapped_token_program: backpointer.wrapped_token_program,
+                customizer_id,
+                creation_slot: Some(u64::from(backpointer.creation_slot)).filter(|slot| *slot != 0),
+                program_version: (backpointer.program_version != [0; 3])
+                    .then(|| format!("{major}.{minor}.{patch}")),
+                wrapped_mint_bump: backpointer.wrapped_mint_bump,
+                wrapped_mint_authority_bump: backpointer.wrapped_mint_authority_bump,
+                backpointer_bump: backpointer.backpointer_bump,
+            }
+        });
+        Self {
+            unwrapped_mint: backpointer.unwrapped_mint,
+            provenance,
+        }
+    }
+}
+
+fn writeln_backpointer(f: &mut Formatter<'_>, backpointer: &BackpointerOutput) -> std::fmt::Result {
+    writeln_name_value(
+        f,
+        "  Unwrapped mint:",
+        &backpointer.unwrapped_mint.to_string(),
+    )?;
+    let Some(provenance) = &backpointer.provenance else {
+        return writeln!(
+            f,
+            "  No provenance recorded, backfill it with the MigrateState instruction"
+        );
+    };
+    let unknown = || "unknown".to_string();
+    writeln_name_value(
+        f,
+        "  Unwrapped token program:",
+        &provenance.unwrapped_token_program.to_string(),
+    )?;
+    writeln_name_value(
+        f,
+        "  Wrapped token program:",
+        &provenance.wrapped_token_program.to_string(),
+    )?;
+    writeln_name_value(
+        f,
+        "  Mint customizer:",
+        &provenance.customizer_id.clone().unwrap_or_else(unknown),
+    )?;
+    writeln_name_value(
+        f,
+        "  Creation slot:",
+        &provenance
+            .creation_slot
+            .map(|slot| slot.to_string())
+            .unwrap_or_else(unknown),
+    )?;
+    writeln_name_value(
+        f,
+        "  Program version:",
+        &provenance.program_version.clone().unwrap_or_else(unknown),
+    )?;
+    writeln_name_value(
+        f,
+        "  Bumps (mint, authority, backpointer):",
+        &format!(
+            "{}, {}, {}",
+            provenance.wrapped_mint_bump,
+            provenance.wrapped_mint_authority_bump,
+            provenance.backpointer_bump
+        ),
+    )
 }
 
 impl Display for PdasOutput {
@@ -59,6 +168,10 @@ impl Display for PdasOutput {
             "Wrapped backpointer address:",
             &self.wrapped_backpointer_address.to_string(),
         )?;
+        match &self.backpointer {
+            Some(backpointer) => writeln_backpointer(f, backpointer)?,
+            None => writeln!(f, "  Not created, the mint has not been wrapped")?,
+        }
         writeln_name_value(
             f,
             "Unwrapped escrow address:",
@@ -76,6 +189,22 @@ impl QuietDisplay for PdasOutput {
 }
 impl VerboseDisplay for PdasOutput {}
 
+async fn get_backpointer(
+    rpc_client: &RpcClient,
+    address: &Pubkey,
+) -> Result<Option<BackpointerOutput>, Error> {
+    match rpc_client
+        .get_account_with_commitment(address, rpc_client.commitment())
+        .await?
+        .value
+    {
+        Some(account) => Ok(Some(BackpointerOutput::from(&Backpointer::unpack(
+            &account.data,
+        )?))),
+        None => Ok(None),
+    }
+}
+
 pub async fn command_get_pdas(config: &Config, args: FindPdasArgs) -> CommandResult {
     let wrapped_mint_address =
         get_wrapped_mint_address(&args.unwrapped_mint, &args.wrapped_token_program);
@@ -91,6 +220,8 @@ pub async fn command_get_pdas(config: &Config, args: FindPdasArgs) -> CommandRes
         &args.wrapped_token_program,
     );
 
+    let backpointer = get_backpointer(&config.rpc_client, &wrapped_backpointer_address).await?;
+
     Ok(format_output(
         config,
         PdasOutput {
@@ -98,6 +229,7 @@ pub async fn command_get_pdas(config: &Config, args: FindPdasArgs) -> CommandRes
             wrapped_mint_authority,
             wrapped_backpointer_address,
             unwrapped_escrow,
+            backpointer,
         },
     ))
 }
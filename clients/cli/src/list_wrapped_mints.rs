@@ -0,0 +1,176 @@
+use {
+    crate::{common::parse_pubkey, config::Config, output::format_output, CommandResult, Error},
+    clap::Args,
+    serde_derive::{Deserialize, Serialize},
+    serde_with::{serde_as, DisplayFromStr},
+    solana_cli_output::{display::writeln_name_value, QuietDisplay, VerboseDisplay},
+    solana_client::nonblocking::rpc_client::RpcClient,
+    solana_pubkey::Pubkey,
+    spl_token_wrap::{
+        get_wrapped_mint_index_address, get_wrapped_mint_registry_address,
+        get_wrapped_mint_registry_page_address,
+        state::{
+            VersionedListEntry, VersionedState, WrappedMintIndexEntry, WrappedMintRegistry,
+            WrappedMintRegistryEntry,
+        },
+    },
+    std::fmt::{Display, Formatter},
+};
+
+#[derive(Clone, Debug, Args)]
+pub struct ListWrappedMintsArgs {
+    /// Only list the wrapped mints of this unwrapped mint
+    #[clap(long, value_parser = parse_pubkey, conflicts_with = "page")]
+    pub unwrapped_mint: Option<Pubkey>,
+
+    /// Only list the wrapped mints in this page of the registry
+    #[clap(long)]
+    pub page: Option<u64>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WrappedMintOutput {
+    #[serde_as(as = "DisplayFromStr")]
+    pub unwrapped_mint: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub wrapped_mint: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub wrapped_token_program: Pubkey,
+}
+
+impl From<&WrappedMintRegistryEntry> for WrappedMintOutput {
+    fn from(entry: &WrappedMintRegistryEntry) -> Self {
+        Self {
+            unwrapped_mint: entry.unwrapped_mint,
+            wrapped_mint: entry.wrapped_mint,
+            wrapped_token_program: entry.wrapped_token_program,
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWrappedMintsOutput {
+    #[serde_as(as = "DisplayFromStr")]
+    pub registry_address: Pubkey,
+    pub total_wrapped_mints: u64,
+    pub page_count: u64,
+    pub wrapped_mints: Vec<WrappedMintOutput>,
+}
+
+impl Display for ListWrappedMintsOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln_name_value(f, "Registry address:", &self.registry_address.to_string())?;
+        writeln_name_value(
+            f,
+            "Total wrapped mints:",
+            &self.total_wrapped_mints.to_string(),
+        )?;
+        writeln_name_value(f, "Registry pages:", &self.page_count.to_string())?;
+
+        for wrapped_mint in &self.wrapped_mints {
+            writeln!(f)?;
+            writeln_name_value(f, "Wrapped mint:", &wrapped_mint.wrapped_mint.to_string())?;
+            writeln_name_value(
+                f,
+                "  Unwrapped mint:",
+                &wrapped_mint.unwrapped_mint.to_string(),
+            )?;
+            writeln_name_value(
+                f,
+                "  Wrapped token program:",
+                &wrapped_mint.wrapped_token_program.to_string(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl QuietDisplay for ListWrappedMintsOutput {
+    fn write_str(&self, _: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        Ok(())
+    }
+}
+impl VerboseDisplay for ListWrappedMintsOutput {}
+
+async fn get_account_data(
+    rpc_client: &RpcClient,
+    address: &Pubkey,
+) -> Result<Option<Vec<u8>>, Error> {
+    Ok(rpc_client
+        .get_account_with_commitment(address, rpc_client.commitment())
+        .await?
+        .value
+        .map(|account| account.data))
+}
+
+async fn get_registry_page(
+    rpc_client: &RpcClient,
+    page_index: u64,
+) -> Result<Vec<WrappedMintOutput>, Error> {
+    let page_address = get_wrapped_mint_registry_page_address(page_index);
+    match get_account_data(rpc_client, &page_address).await? {
+        Some(data) => Ok(WrappedMintRegistryEntry::unpack_list(&data)?
+            .iter()
+            .map(WrappedMintOutput::from)
+            .collect()),
+        None => Ok(vec![]),
+    }
+}
+
+pub async fn command_list_wrapped_mints(
+    config: &Config,
+    args: ListWrappedMintsArgs,
+) -> CommandResult {
+    let rpc_client = &config.rpc_client;
+
+    let registry_address = get_wrapped_mint_registry_address();
+    let (total_wrapped_mints, page_count) =
+        match get_account_data(rpc_client, &registry_address).await? {
+            Some(data) => {
+                let registry = WrappedMintRegistry::unpack_versioned(&data)?;
+                (u64::from(registry.entry_count), registry.page_count())
+            }
+            None => (0, 0),
+        };
+
+    let wrapped_mints = if let Some(unwrapped_mint) = args.unwrapped_mint {
+        let index_address = get_wrapped_mint_index_address(&unwrapped_mint);
+        match get_account_data(rpc_client, &index_address).await? {
+            Some(data) => WrappedMintIndexEntry::unpack_list(&data)?
+                .iter()
+                .map(|entry| WrappedMintOutput {
+                    unwrapped_mint,
+                    wrapped_mint: entry.wrapped_mint,
+                    wrapped_token_program: entry.wrapped_token_program,
+                })
+                .collect(),
+            None => vec![],
+        }
+    } else if let Some(page_index) = args.page {
+        if page_index >= page_count {
+            return Err(format!("Registry only has {page_count} page(s)").into());
+        }
+        get_registry_page(rpc_client, page_index).await?
+    } else {
+        let mut wrapped_mints = vec![];
+        for page_index in 0..page_count {
+            wrapped_mints.extend(get_registry_page(rpc_client, page_index).await?);
+        }
+        wrapped_mints
+    };
+
+    Ok(format_output(
+        config,
+        ListWrappedMintsOutput {
+            registry_address,
+            total_wrapped_mints,
+            page_count,
+            wrapped_mints,
+        },
+    ))
+}
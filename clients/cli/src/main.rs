@@ -1,15 +1,29 @@
+mod canonical_pointer;
 mod cli;
 mod close_stuck_escrow;
+mod close_wrapped_mint;
 mod common;
 mod config;
 mod create_escrow_account;
 mod create_mint;
 mod find_pdas;
+mod list_wrapped_mints;
 mod output;
+mod reconcile_escrow;
+mod register_wrapped_mint;
+mod repair_escrow;
+mod set_allowlist_mode;
+mod set_flash_mint_fee;
+mod set_protocol_fee_config;
+mod set_protocol_fee_override;
+mod set_wrap_limits;
+mod stats;
 mod sync_metadata_to_spl_token;
 mod sync_metadata_to_token2022;
 mod unwrap;
+mod update_allowlist;
 mod wrap;
+mod wrap_limits;
 
 use {
     crate::{cli::Cli, config::Config},
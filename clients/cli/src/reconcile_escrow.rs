@@ -0,0 +1,188 @@
+use {
+    crate::{
+        common::{
+            get_account_owner, get_escrow_collateral, parse_pubkey, parse_token_program,
+            process_transaction,
+        },
+        config::Config,
+        output::{format_output, println_display},
+        CommandResult,
+    },
+    clap::Args,
+    serde_derive::{Deserialize, Serialize},
+    serde_with::{serde_as, DisplayFromStr},
+    solana_cli_output::{display::writeln_name_value, QuietDisplay, VerboseDisplay},
+    solana_pubkey::Pubkey,
+    solana_signature::Signature,
+    solana_signer::Signer,
+    solana_transaction::Transaction,
+    spl_token_wrap::{
+        get_escrow_address, get_escrow_surplus_policy_address, get_wrap_limits_address,
+        get_wrapped_mint_address, get_wrapped_mint_authority,
+        instruction::reconcile_escrow,
+        state::{EscrowSurplusPolicy, SurplusAction, VersionedState},
+    },
+    std::fmt::{Display, Formatter},
+};
+
+#[derive(Clone, Debug, Args)]
+pub struct ReconcileEscrowArgs {
+    /// The address of the unwrapped mint
+    #[clap(value_parser = parse_pubkey)]
+    pub unwrapped_mint: Pubkey,
+
+    /// The address of the token program for the wrapped mint
+    #[clap(value_parser = parse_token_program)]
+    pub wrapped_token_program: Pubkey,
+
+    /// The unwrapped token account to receive the surplus. Required if the
+    /// policy returns the surplus, in which case the fee payer must be the
+    /// claimant.
+    #[clap(long, value_parser = parse_pubkey)]
+    pub destination: Option<Pubkey>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconcileEscrowOutput {
+    #[serde_as(as = "DisplayFromStr")]
+    pub unwrapped_mint: Pubkey,
+
+    #[serde_as(as = "DisplayFromStr")]
+    pub wrapped_mint: Pubkey,
+
+    #[serde_as(as = "DisplayFromStr")]
+    pub escrow_account: Pubkey,
+
+    pub returned: bool,
+
+    #[serde_as(as = "DisplayFromStr")]
+    pub destination: Pubkey,
+
+    pub surplus: u64,
+
+    pub signatures: Vec<Signature>,
+}
+
+impl Display for ReconcileEscrowOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln_name_value(f, "Unwrapped mint:", &self.unwrapped_mint.to_string())?;
+        writeln_name_value(f, "Wrapped mint:", &self.wrapped_mint.to_string())?;
+        writeln_name_value(f, "Escrow account:", &self.escrow_account.to_string())?;
+        if self.returned {
+            writeln_name_value(f, "Surplus returned:", &self.surplus.to_string())?;
+        } else {
+            writeln_name_value(f, "Surplus minted:", &self.surplus.to_string())?;
+        }
+        writeln_name_value(f, "Destination:", &self.destination.to_string())?;
+
+        writeln!(f, "Signers:")?;
+        for signature in &self.signatures {
+            writeln!(f, "  {signature}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl QuietDisplay for ReconcileEscrowOutput {
+    fn write_str(&self, _: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        Ok(())
+    }
+}
+impl VerboseDisplay for ReconcileEscrowOutput {}
+
+pub async fn command_reconcile_escrow(config: &Config, args: ReconcileEscrowArgs) -> CommandResult {
+    let unwrapped_token_program =
+        get_account_owner(&config.rpc_client, &args.unwrapped_mint).await?;
+    let wrapped_mint = get_wrapped_mint_address(&args.unwrapped_mint, &args.wrapped_token_program);
+    let wrapped_mint_authority = get_wrapped_mint_authority(&wrapped_mint);
+    let escrow_account = get_escrow_address(
+        &args.unwrapped_mint,
+        &unwrapped_token_program,
+        &args.wrapped_token_program,
+    );
+
+    let policy_address = get_escrow_surplus_policy_address(&wrapped_mint);
+    let policy_account = config
+        .rpc_client
+        .get_account(&policy_address)
+        .await
+        .map_err(|_| format!("No escrow surplus policy set for wrapped mint {wrapped_mint}"))?;
+    let policy = EscrowSurplusPolicy::unpack_versioned(&policy_account.data)?;
+
+    let (escrow_balance, wrapped_supply) =
+        get_escrow_collateral(&config.rpc_client, &escrow_account, &wrapped_mint).await?;
+    let surplus = escrow_balance
+        .checked_sub(wrapped_supply)
+        .filter(|surplus| *surplus > 0)
+        .ok_or_else(|| {
+            format!(
+                "Escrow {escrow_account} holds {escrow_balance} unwrapped tokens for a wrapped \
+                 supply of {wrapped_supply}, there is no surplus"
+            )
+        })?;
+
+    let payer = config.fee_payer()?;
+    let action = policy.action()?;
+    let (destination, claimant) = match action {
+        SurplusAction::MintToRecipient => (policy.target, None),
+        SurplusAction::ReturnToClaimant => {
+            if payer.pubkey() != policy.target {
+                return Err(format!(
+                    "The surplus is returned to claimant {}, which must be the fee payer",
+                    policy.target
+                )
+                .into());
+            }
+            let destination = args
+                .destination
+                .ok_or("The policy returns the surplus, pass --destination")?;
+            (destination, Some(policy.target))
+        }
+    };
+
+    if !config.dry_run {
+        println_display(
+            config,
+            format!("Reconciling surplus of {surplus} in escrow account {escrow_account}"),
+        );
+    }
+
+    let instruction = reconcile_escrow(
+        &spl_token_wrap::id(),
+        &escrow_account,
+        &args.unwrapped_mint,
+        &wrapped_mint,
+        &wrapped_mint_authority,
+        &policy_address,
+        &destination,
+        &unwrapped_token_program,
+        &args.wrapped_token_program,
+        &get_wrap_limits_address(&args.unwrapped_mint),
+        claimant.as_ref(),
+    );
+
+    let latest_blockhash = config.rpc_client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[&*payer],
+        latest_blockhash,
+    );
+
+    process_transaction(config, transaction.clone()).await?;
+
+    let output = ReconcileEscrowOutput {
+        unwrapped_mint: args.unwrapped_mint,
+        wrapped_mint,
+        escrow_account,
+        returned: action == SurplusAction::ReturnToClaimant,
+        destination,
+        surplus,
+        signatures: transaction.signatures,
+    };
+
+    Ok(format_output(config, output))
+}
@@ -0,0 +1,143 @@
+use {
+    crate::{
+        common::{parse_pubkey, parse_token_program, process_transaction},
+        config::Config,
+        create_mint::get_registry_accounts,
+        output::{format_output, println_display},
+        CommandResult,
+    },
+    clap::Args,
+    serde_derive::{Deserialize, Serialize},
+    serde_with::{serde_as, DisplayFromStr},
+    solana_cli_output::{display::writeln_name_value, QuietDisplay, VerboseDisplay},
+    solana_pubkey::Pubkey,
+    solana_signature::Signature,
+    solana_signer::Signer,
+    solana_system_interface::instruction::transfer,
+    solana_transaction::Transaction,
+    spl_token_wrap::{
+        get_wrapped_mint_address, get_wrapped_mint_backpointer_address, id,
+        instruction::register_wrapped_mint,
+    },
+    std::fmt::{Display, Formatter},
+};
+
+#[derive(Clone, Debug, Args)]
+pub struct RegisterWrappedMintArgs {
+    /// The address of the unwrapped mint
+    #[clap(value_parser = parse_pubkey)]
+    pub unwrapped_mint: Pubkey,
+
+    /// The address of the token program for the wrapped mint
+    #[clap(value_parser = parse_token_program)]
+    pub wrapped_token_program: Pubkey,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterWrappedMintOutput {
+    #[serde_as(as = "DisplayFromStr")]
+    pub unwrapped_mint_address: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub wrapped_mint_address: Pubkey,
+    pub funded_registry_lamports: u64,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub signature: Option<Signature>,
+}
+
+impl Display for RegisterWrappedMintOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln_name_value(
+            f,
+            "Unwrapped mint address:",
+            &self.unwrapped_mint_address.to_string(),
+        )?;
+        writeln_name_value(
+            f,
+            "Wrapped mint address:",
+            &self.wrapped_mint_address.to_string(),
+        )?;
+        writeln_name_value(
+            f,
+            "Funded registry lamports:",
+            &self.funded_registry_lamports.to_string(),
+        )?;
+        if let Some(signature) = self.signature {
+            writeln_name_value(f, "Signature:", &signature.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+impl QuietDisplay for RegisterWrappedMintOutput {
+    fn write_str(&self, _: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        Ok(())
+    }
+}
+impl VerboseDisplay for RegisterWrappedMintOutput {}
+
+pub async fn command_register_wrapped_mint(
+    config: &Config,
+    args: RegisterWrappedMintArgs,
+) -> CommandResult {
+    let payer = config.fee_payer()?;
+    let rpc_client = config.rpc_client.clone();
+
+    let wrapped_mint_address =
+        get_wrapped_mint_address(&args.unwrapped_mint, &args.wrapped_token_program);
+    let wrapped_backpointer_address = get_wrapped_mint_backpointer_address(&wrapped_mint_address);
+
+    println_display(
+        config,
+        format!("Registering wrapped mint {wrapped_mint_address}"),
+    );
+
+    // Fund the growth of the registry accounts the wrapped mint is appended to
+    let registry_accounts = get_registry_accounts(&rpc_client, &args.unwrapped_mint).await?;
+    let mut instructions = Vec::new();
+    let mut funded_registry_lamports: u64 = 0;
+    for (address, lamports) in registry_accounts {
+        if lamports > 0 {
+            println_display(
+                config,
+                format!("Funding registry account {address} with {lamports} lamports for rent"),
+            );
+            instructions.push(transfer(&payer.pubkey(), &address, lamports));
+            funded_registry_lamports = funded_registry_lamports.saturating_add(lamports);
+        }
+    }
+    let [(registry_address, _), (registry_page_address, _), (wrapped_mint_index_address, _)] =
+        registry_accounts;
+
+    instructions.push(register_wrapped_mint(
+        &id(),
+        &wrapped_mint_address,
+        &wrapped_backpointer_address,
+        &args.unwrapped_mint,
+        &args.wrapped_token_program,
+        &registry_address,
+        &registry_page_address,
+        &wrapped_mint_index_address,
+    ));
+
+    let latest_blockhash = rpc_client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &[&*payer],
+        latest_blockhash,
+    );
+
+    let signature = process_transaction(config, transaction).await?;
+
+    Ok(format_output(
+        config,
+        RegisterWrappedMintOutput {
+            unwrapped_mint_address: args.unwrapped_mint,
+            wrapped_mint_address,
+            funded_registry_lamports,
+            signature,
+        },
+    ))
+}
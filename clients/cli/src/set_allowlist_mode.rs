@@ -0,0 +1,161 @@
+use {
+    crate::{
+        common::{get_rent_shortfall, parse_pubkey, process_transaction},
+        config::Config,
+        output::{format_output, println_display},
+        CommandResult,
+    },
+    clap::Args,
+    serde_derive::{Deserialize, Serialize},
+    serde_with::{serde_as, DisplayFromStr},
+    solana_clap_v3_utils::{
+        input_parsers::signer::{SignerSource, SignerSourceParserBuilder},
+        keypair::signer_from_source,
+    },
+    solana_cli_output::{display::writeln_name_value, QuietDisplay, VerboseDisplay},
+    solana_pubkey::Pubkey,
+    solana_remote_wallet::remote_wallet::RemoteWalletManager,
+    solana_signature::Signature,
+    solana_signer::Signer,
+    solana_system_interface::instruction::transfer,
+    solana_transaction::Transaction,
+    spl_token_wrap::{
+        get_allowlist_address,
+        instruction::set_allowlist_mode,
+        state::{Allowlist, VersionedState},
+    },
+    std::{
+        fmt::{Display, Formatter},
+        rc::Rc,
+        sync::Arc,
+    },
+};
+
+#[derive(Clone, Debug, Args)]
+pub struct SetAllowlistModeArgs {
+    /// The address of the unwrapped mint
+    #[clap(value_parser = parse_pubkey)]
+    pub unwrapped_mint: Pubkey,
+
+    /// Disable the allowlist, letting any wallet wrap and unwrap
+    #[clap(long)]
+    pub disable: bool,
+
+    /// Signer source of the unwrapped mint authority if different from fee
+    /// payer
+    #[clap(
+        long,
+        value_parser = SignerSourceParserBuilder::default().allow_all().build()
+    )]
+    pub mint_authority: Option<SignerSource>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetAllowlistModeOutput {
+    #[serde_as(as = "DisplayFromStr")]
+    pub unwrapped_mint: Pubkey,
+
+    #[serde_as(as = "DisplayFromStr")]
+    pub allowlist_address: Pubkey,
+
+    pub enabled: bool,
+
+    pub signatures: Vec<Signature>,
+}
+
+impl Display for SetAllowlistModeOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln_name_value(f, "Unwrapped mint:", &self.unwrapped_mint.to_string())?;
+        writeln_name_value(f, "Allowlist address:", &self.allowlist_address.to_string())?;
+        writeln_name_value(f, "Enabled:", &self.enabled.to_string())?;
+
+        writeln!(f, "Signers:")?;
+        for signature in &self.signatures {
+            writeln!(f, "  {signature}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl QuietDisplay for SetAllowlistModeOutput {
+    fn write_str(&self, _: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        Ok(())
+    }
+}
+impl VerboseDisplay for SetAllowlistModeOutput {}
+
+pub async fn command_set_allowlist_mode(
+    config: &Config,
+    args: SetAllowlistModeArgs,
+    matches: &clap::ArgMatches,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> CommandResult {
+    let payer = config.fee_payer()?;
+
+    // If mint_authority is provided, use it as a signer,
+    // else default to fee payer
+    let mint_authority_signer = if let Some(authority_source) = &args.mint_authority {
+        let signer =
+            signer_from_source(matches, authority_source, "mint_authority", wallet_manager)
+                .map_err(|e| e.to_string())?;
+        Arc::from(signer)
+    } else {
+        payer.clone()
+    };
+
+    let allowlist_address = get_allowlist_address(&args.unwrapped_mint);
+    let enabled = !args.disable;
+
+    let mut instructions = vec![];
+    let lamports = get_rent_shortfall(&config.rpc_client, &allowlist_address, |_| {
+        Ok(Allowlist::LEN)
+    })
+    .await?;
+    if lamports > 0 {
+        println_display(
+            config,
+            format!(
+                "Funding allowlist account {allowlist_address} with {lamports} lamports for rent"
+            ),
+        );
+        instructions.push(transfer(&payer.pubkey(), &allowlist_address, lamports));
+    }
+
+    instructions.push(set_allowlist_mode(
+        &spl_token_wrap::id(),
+        &mint_authority_signer.pubkey(),
+        &allowlist_address,
+        &args.unwrapped_mint,
+        enabled,
+        None,
+        None,
+        &[],
+    ));
+
+    let mut signers = vec![payer.clone()];
+    if payer.pubkey() != mint_authority_signer.pubkey() {
+        signers.push(mint_authority_signer);
+    }
+
+    let latest_blockhash = config.rpc_client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &signers,
+        latest_blockhash,
+    );
+
+    process_transaction(config, transaction.clone()).await?;
+
+    let output = SetAllowlistModeOutput {
+        unwrapped_mint: args.unwrapped_mint,
+        allowlist_address,
+        enabled,
+        signatures: transaction.signatures,
+    };
+
+    Ok(format_output(config, output))
+}
@@ -0,0 +1,179 @@
+use {
+    crate::{
+        common::{get_rent_shortfall, parse_pubkey, parse_token_program, process_transaction},
+        config::Config,
+        output::{format_output, println_display},
+        CommandResult,
+    },
+    clap::{value_parser, Args},
+    serde_derive::{Deserialize, Serialize},
+    serde_with::{serde_as, DisplayFromStr},
+    solana_clap_v3_utils::{
+        input_parsers::signer::{SignerSource, SignerSourceParserBuilder},
+        keypair::signer_from_source,
+    },
+    solana_cli_output::{display::writeln_name_value, QuietDisplay, VerboseDisplay},
+    solana_pubkey::Pubkey,
+    solana_remote_wallet::remote_wallet::RemoteWalletManager,
+    solana_signature::Signature,
+    solana_signer::Signer,
+    solana_system_interface::instruction::transfer,
+    solana_transaction::Transaction,
+    spl_token_wrap::{
+        get_flash_mint_guard_address, get_wrapped_mint_address,
+        instruction::set_flash_mint_fee,
+        state::{FlashMintGuard, VersionedState, MAX_FEE_BASIS_POINTS},
+    },
+    std::{
+        fmt::{Display, Formatter},
+        rc::Rc,
+        sync::Arc,
+    },
+};
+
+#[derive(Clone, Debug, Args)]
+pub struct SetFlashMintFeeArgs {
+    /// The address of the unwrapped mint
+    #[clap(value_parser = parse_pubkey)]
+    pub unwrapped_mint: Pubkey,
+
+    /// The address of the token program for the wrapped mint
+    #[clap(value_parser = parse_token_program)]
+    pub wrapped_token_program: Pubkey,
+
+    /// Fee charged on flash mints of the wrapped mint, in basis points
+    #[clap(value_parser = value_parser!(u16).range(0..=i64::from(MAX_FEE_BASIS_POINTS)))]
+    pub fee_basis_points: u16,
+
+    /// Signer source of the unwrapped mint authority if different from fee
+    /// payer
+    #[clap(
+        long,
+        value_parser = SignerSourceParserBuilder::default().allow_all().build()
+    )]
+    pub mint_authority: Option<SignerSource>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetFlashMintFeeOutput {
+    #[serde_as(as = "DisplayFromStr")]
+    pub wrapped_mint: Pubkey,
+
+    #[serde_as(as = "DisplayFromStr")]
+    pub flash_mint_guard_address: Pubkey,
+
+    pub fee_basis_points: u16,
+
+    pub signatures: Vec<Signature>,
+}
+
+impl Display for SetFlashMintFeeOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln_name_value(f, "Wrapped mint:", &self.wrapped_mint.to_string())?;
+        writeln_name_value(
+            f,
+            "Flash mint guard address:",
+            &self.flash_mint_guard_address.to_string(),
+        )?;
+        writeln_name_value(
+            f,
+            "Flash mint fee (bps):",
+            &self.fee_basis_points.to_string(),
+        )?;
+
+        writeln!(f, "Signers:")?;
+        for signature in &self.signatures {
+            writeln!(f, "  {signature}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl QuietDisplay for SetFlashMintFeeOutput {
+    fn write_str(&self, _: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        Ok(())
+    }
+}
+impl VerboseDisplay for SetFlashMintFeeOutput {}
+
+pub async fn command_set_flash_mint_fee(
+    config: &Config,
+    args: SetFlashMintFeeArgs,
+    matches: &clap::ArgMatches,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> CommandResult {
+    let payer = config.fee_payer()?;
+
+    // If mint_authority is provided, use it as a signer,
+    // else default to fee payer
+    let mint_authority_signer = if let Some(authority_source) = &args.mint_authority {
+        let signer =
+            signer_from_source(matches, authority_source, "mint_authority", wallet_manager)
+                .map_err(|e| e.to_string())?;
+        Arc::from(signer)
+    } else {
+        payer.clone()
+    };
+
+    let wrapped_mint = get_wrapped_mint_address(&args.unwrapped_mint, &args.wrapped_token_program);
+    let flash_mint_guard_address = get_flash_mint_guard_address(&wrapped_mint);
+
+    let mut instructions = vec![];
+    let lamports = get_rent_shortfall(&config.rpc_client, &flash_mint_guard_address, |_| {
+        Ok(FlashMintGuard::LEN)
+    })
+    .await?;
+    if lamports > 0 {
+        println_display(
+            config,
+            format!(
+                "Funding flash mint guard account {flash_mint_guard_address} with {lamports} \
+                 lamports for rent"
+            ),
+        );
+        instructions.push(transfer(
+            &payer.pubkey(),
+            &flash_mint_guard_address,
+            lamports,
+        ));
+    }
+
+    instructions.push(set_flash_mint_fee(
+        &spl_token_wrap::id(),
+        &mint_authority_signer.pubkey(),
+        &flash_mint_guard_address,
+        &args.unwrapped_mint,
+        &wrapped_mint,
+        args.fee_basis_points,
+        None,
+        None,
+        &[],
+    ));
+
+    let mut signers = vec![payer.clone()];
+    if payer.pubkey() != mint_authority_signer.pubkey() {
+        signers.push(mint_authority_signer);
+    }
+
+    let latest_blockhash = config.rpc_client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &signers,
+        latest_blockhash,
+    );
+
+    process_transaction(config, transaction.clone()).await?;
+
+    let output = SetFlashMintFeeOutput {
+        wrapped_mint,
+        flash_mint_guard_address,
+        fee_basis_points: args.fee_basis_points,
+        signatures: transaction.signatures,
+    };
+
+    Ok(format_output(config, output))
+}
@@ -0,0 +1,198 @@
+use {
+    crate::{
+        common::{get_rent_shortfall, parse_pubkey, process_transaction},
+        config::Config,
+        output::{format_output, println_display},
+        CommandResult,
+    },
+    clap::{value_parser, Args},
+    serde_derive::{Deserialize, Serialize},
+    serde_with::{serde_as, DisplayFromStr},
+    solana_clap_v3_utils::{
+        input_parsers::signer::{SignerSource, SignerSourceParserBuilder},
+        keypair::signer_from_source,
+    },
+    solana_cli_output::{display::writeln_name_value, QuietDisplay, VerboseDisplay},
+    solana_loader_v3_interface::get_program_data_address,
+    solana_pubkey::Pubkey,
+    solana_remote_wallet::remote_wallet::RemoteWalletManager,
+    solana_signature::Signature,
+    solana_signer::Signer,
+    solana_system_interface::instruction::transfer,
+    solana_transaction::Transaction,
+    spl_token_wrap::{
+        get_protocol_fee_config_address,
+        instruction::set_protocol_fee_config,
+        state::{ProtocolFeeConfig, VersionedState, MAX_FEE_BASIS_POINTS},
+    },
+    std::{
+        fmt::{Display, Formatter},
+        rc::Rc,
+        sync::Arc,
+    },
+};
+
+#[derive(Clone, Debug, Args)]
+pub struct SetProtocolFeeConfigArgs {
+    /// The owner of the token accounts receiving the protocol fee. Wraps and
+    /// unwraps default to its associated token account of the unwrapped mint.
+    #[clap(value_parser = parse_pubkey)]
+    pub fee_destination: Pubkey,
+
+    /// The protocol fee charged on wraps, in basis points
+    #[clap(
+        long,
+        value_parser = value_parser!(u16).range(0..=i64::from(MAX_FEE_BASIS_POINTS)),
+        default_value_t = 0
+    )]
+    pub wrap_fee_basis_points: u16,
+
+    /// The protocol fee charged on unwraps, in basis points
+    #[clap(
+        long,
+        value_parser = value_parser!(u16).range(0..=i64::from(MAX_FEE_BASIS_POINTS)),
+        default_value_t = 0
+    )]
+    pub unwrap_fee_basis_points: u16,
+
+    /// Signer source of the program upgrade authority if different from fee
+    /// payer
+    #[clap(
+        long,
+        value_parser = SignerSourceParserBuilder::default().allow_all().build()
+    )]
+    pub upgrade_authority: Option<SignerSource>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetProtocolFeeConfigOutput {
+    #[serde_as(as = "DisplayFromStr")]
+    pub protocol_fee_config_address: Pubkey,
+
+    #[serde_as(as = "DisplayFromStr")]
+    pub fee_destination: Pubkey,
+
+    pub wrap_fee_basis_points: u16,
+
+    pub unwrap_fee_basis_points: u16,
+
+    pub signatures: Vec<Signature>,
+}
+
+impl Display for SetProtocolFeeConfigOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln_name_value(
+            f,
+            "Protocol fee config address:",
+            &self.protocol_fee_config_address.to_string(),
+        )?;
+        writeln_name_value(f, "Fee destination:", &self.fee_destination.to_string())?;
+        writeln_name_value(
+            f,
+            "Wrap fee (bps):",
+            &self.wrap_fee_basis_points.to_string(),
+        )?;
+        writeln_name_value(
+            f,
+            "Unwrap fee (bps):",
+            &self.unwrap_fee_basis_points.to_string(),
+        )?;
+
+        writeln!(f, "Signers:")?;
+        for signature in &self.signatures {
+            writeln!(f, "  {signature}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl QuietDisplay for SetProtocolFeeConfigOutput {
+    fn write_str(&self, _: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        Ok(())
+    }
+}
+impl VerboseDisplay for SetProtocolFeeConfigOutput {}
+
+pub async fn command_set_protocol_fee_config(
+    config: &Config,
+    args: SetProtocolFeeConfigArgs,
+    matches: &clap::ArgMatches,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> CommandResult {
+    let payer = config.fee_payer()?;
+
+    // If upgrade_authority is provided, use it as a signer,
+    // else default to fee payer
+    let upgrade_authority_signer = if let Some(authority_source) = &args.upgrade_authority {
+        let signer = signer_from_source(
+            matches,
+            authority_source,
+            "upgrade_authority",
+            wallet_manager,
+        )
+        .map_err(|e| e.to_string())?;
+        Arc::from(signer)
+    } else {
+        payer.clone()
+    };
+
+    let protocol_fee_config_address = get_protocol_fee_config_address();
+
+    let mut instructions = vec![];
+    let lamports = get_rent_shortfall(&config.rpc_client, &protocol_fee_config_address, |_| {
+        Ok(ProtocolFeeConfig::LEN)
+    })
+    .await?;
+    if lamports > 0 {
+        println_display(
+            config,
+            format!(
+                "Funding protocol fee config account {protocol_fee_config_address} with \
+                 {lamports} lamports for rent"
+            ),
+        );
+        instructions.push(transfer(
+            &payer.pubkey(),
+            &protocol_fee_config_address,
+            lamports,
+        ));
+    }
+
+    instructions.push(set_protocol_fee_config(
+        &spl_token_wrap::id(),
+        &upgrade_authority_signer.pubkey(),
+        &protocol_fee_config_address,
+        &get_program_data_address(&spl_token_wrap::id()),
+        args.wrap_fee_basis_points,
+        args.unwrap_fee_basis_points,
+        &args.fee_destination,
+    ));
+
+    let mut signers = vec![payer.clone()];
+    if payer.pubkey() != upgrade_authority_signer.pubkey() {
+        signers.push(upgrade_authority_signer);
+    }
+
+    let latest_blockhash = config.rpc_client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &signers,
+        latest_blockhash,
+    );
+
+    process_transaction(config, transaction.clone()).await?;
+
+    let output = SetProtocolFeeConfigOutput {
+        protocol_fee_config_address,
+        fee_destination: args.fee_destination,
+        wrap_fee_basis_points: args.wrap_fee_basis_points,
+        unwrap_fee_basis_points: args.unwrap_fee_basis_points,
+        signatures: transaction.signatures,
+    };
+
+    Ok(format_output(config, output))
+}
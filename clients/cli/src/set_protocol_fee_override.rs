@@ -0,0 +1,213 @@
+use {
+    crate::{
+        common::{get_rent_shortfall, parse_pubkey, process_transaction},
+        config::Config,
+        output::{format_output, println_display},
+        CommandResult,
+    },
+    clap::{value_parser, Args},
+    serde_derive::{Deserialize, Serialize},
+    serde_with::{serde_as, DisplayFromStr},
+    solana_clap_v3_utils::{
+        input_parsers::signer::{SignerSource, SignerSourceParserBuilder},
+        keypair::signer_from_source,
+    },
+    solana_cli_output::{display::writeln_name_value, QuietDisplay, VerboseDisplay},
+    solana_loader_v3_interface::get_program_data_address,
+    solana_pubkey::Pubkey,
+    solana_remote_wallet::remote_wallet::RemoteWalletManager,
+    solana_signature::Signature,
+    solana_signer::Signer,
+    solana_system_interface::instruction::transfer,
+    solana_transaction::Transaction,
+    spl_token_wrap::{
+        get_protocol_fee_override_address,
+        instruction::set_protocol_fee_override,
+        state::{ProtocolFeeOverride, VersionedState, MAX_FEE_BASIS_POINTS},
+    },
+    std::{
+        fmt::{Display, Formatter},
+        rc::Rc,
+        sync::Arc,
+    },
+};
+
+#[derive(Clone, Debug, Args)]
+pub struct SetProtocolFeeOverrideArgs {
+    /// The address of the unwrapped mint
+    #[clap(value_parser = parse_pubkey)]
+    pub unwrapped_mint: Pubkey,
+
+    /// The protocol fee charged on wraps of the mint, in basis points
+    #[clap(
+        long,
+        value_parser = value_parser!(u16).range(0..=i64::from(MAX_FEE_BASIS_POINTS)),
+        default_value_t = 0
+    )]
+    pub wrap_fee_basis_points: u16,
+
+    /// The protocol fee charged on unwraps of the mint, in basis points
+    #[clap(
+        long,
+        value_parser = value_parser!(u16).range(0..=i64::from(MAX_FEE_BASIS_POINTS)),
+        default_value_t = 0
+    )]
+    pub unwrap_fee_basis_points: u16,
+
+    /// Disable the override, falling back to the protocol fee of the
+    /// deployment
+    #[clap(
+        long,
+        conflicts_with_all = &["wrap-fee-basis-points", "unwrap-fee-basis-points"]
+    )]
+    pub disable: bool,
+
+    /// Signer source of the program upgrade authority if different from fee
+    /// payer
+    #[clap(
+        long,
+        value_parser = SignerSourceParserBuilder::default().allow_all().build()
+    )]
+    pub upgrade_authority: Option<SignerSource>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetProtocolFeeOverrideOutput {
+    #[serde_as(as = "DisplayFromStr")]
+    pub unwrapped_mint: Pubkey,
+
+    #[serde_as(as = "DisplayFromStr")]
+    pub protocol_fee_override_address: Pubkey,
+
+    pub enabled: bool,
+
+    pub wrap_fee_basis_points: u16,
+
+    pub unwrap_fee_basis_points: u16,
+
+    pub signatures: Vec<Signature>,
+}
+
+impl Display for SetProtocolFeeOverrideOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln_name_value(f, "Unwrapped mint:", &self.unwrapped_mint.to_string())?;
+        writeln_name_value(
+            f,
+            "Protocol fee override address:",
+            &self.protocol_fee_override_address.to_string(),
+        )?;
+        writeln_name_value(f, "Enabled:", &self.enabled.to_string())?;
+        if self.enabled {
+            writeln_name_value(
+                f,
+                "Wrap fee (bps):",
+                &self.wrap_fee_basis_points.to_string(),
+            )?;
+            writeln_name_value(
+                f,
+                "Unwrap fee (bps):",
+                &self.unwrap_fee_basis_points.to_string(),
+            )?;
+        }
+
+        writeln!(f, "Signers:")?;
+        for signature in &self.signatures {
+            writeln!(f, "  {signature}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl QuietDisplay for SetProtocolFeeOverrideOutput {
+    fn write_str(&self, _: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        Ok(())
+    }
+}
+impl VerboseDisplay for SetProtocolFeeOverrideOutput {}
+
+pub async fn command_set_protocol_fee_override(
+    config: &Config,
+    args: SetProtocolFeeOverrideArgs,
+    matches: &clap::ArgMatches,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> CommandResult {
+    let payer = config.fee_payer()?;
+
+    // If upgrade_authority is provided, use it as a signer,
+    // else default to fee payer
+    let upgrade_authority_signer = if let Some(authority_source) = &args.upgrade_authority {
+        let signer = signer_from_source(
+            matches,
+            authority_source,
+            "upgrade_authority",
+            wallet_manager,
+        )
+        .map_err(|e| e.to_string())?;
+        Arc::from(signer)
+    } else {
+        payer.clone()
+    };
+
+    let protocol_fee_override_address = get_protocol_fee_override_address(&args.unwrapped_mint);
+    let enabled = !args.disable;
+
+    let mut instructions = vec![];
+    let lamports = get_rent_shortfall(&config.rpc_client, &protocol_fee_override_address, |_| {
+        Ok(ProtocolFeeOverride::LEN)
+    })
+    .await?;
+    if lamports > 0 {
+        println_display(
+            config,
+            format!(
+                "Funding protocol fee override account {protocol_fee_override_address} with \
+                 {lamports} lamports for rent"
+            ),
+        );
+        instructions.push(transfer(
+            &payer.pubkey(),
+            &protocol_fee_override_address,
+            lamports,
+        ));
+    }
+
+    instructions.push(set_protocol_fee_override(
+        &spl_token_wrap::id(),
+        &upgrade_authority_signer.pubkey(),
+        &protocol_fee_override_address,
+        &args.unwrapped_mint,
+        &get_program_data_address(&spl_token_wrap::id()),
+        enabled,
+        args.wrap_fee_basis_points,
+        args.unwrap_fee_basis_points,
+    ));
+
+    let mut signers = vec![payer.clone()];
+    if payer.pubkey() != upgrade_authority_signer.pubkey() {
+        signers.push(upgrade_authority_signer);
+    }
+
+    let latest_blockhash = config.rpc_client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &signers,
+        latest_blockhash,
+    );
+
+    process_transaction(config, transaction.clone()).await?;
+
+    let output = SetProtocolFeeOverrideOutput {
+        unwrapped_mint: args.unwrapped_mint,
+        protocol_fee_override_address,
+        enabled,
+        wrap_fee_basis_points: args.wrap_fee_basis_points,
+        unwrap_fee_basis_points: args.unwrap_fee_basis_points,
+        signatures: transaction.signatures,
+    };
+
+    Ok(format_output(config, output))
+}
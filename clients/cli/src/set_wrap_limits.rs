@@ -0,0 +1,196 @@
+use {
+    crate::{
+        common::{get_rent_shortfall, parse_pubkey, process_transaction},
+        config::Config,
+        output::{format_output, println_display},
+        CommandResult,
+    },
+    clap::Args,
+    serde_derive::{Deserialize, Serialize},
+    serde_with::{serde_as, DisplayFromStr},
+    solana_clap_v3_utils::{
+        input_parsers::signer::{SignerSource, SignerSourceParserBuilder},
+        keypair::signer_from_source,
+    },
+    solana_cli_output::{display::writeln_name_value, QuietDisplay, VerboseDisplay},
+    solana_pubkey::Pubkey,
+    solana_remote_wallet::remote_wallet::RemoteWalletManager,
+    solana_signature::Signature,
+    solana_signer::Signer,
+    solana_system_interface::instruction::transfer,
+    solana_transaction::Transaction,
+    spl_token_wrap::{
+        get_wrap_limits_address, get_wrapped_mint_address,
+        instruction::set_wrap_limits,
+        state::{VersionedState, WrapLimits},
+    },
+    std::{
+        fmt::{Display, Formatter},
+        rc::Rc,
+        sync::Arc,
+    },
+};
+
+#[derive(Clone, Debug, Args)]
+pub struct SetWrapLimitsArgs {
+    /// The address of the unwrapped mint
+    #[clap(value_parser = parse_pubkey)]
+    pub unwrapped_mint: Pubkey,
+
+    /// Maximum total amount wrapped across all wrapped mints of the unwrapped
+    /// mint, no cap if not provided
+    #[clap(long)]
+    pub max_wrapped_supply: Option<u64>,
+
+    /// Maximum amount wrapped per epoch, no cap if not provided
+    #[clap(long)]
+    pub max_wrapped_per_epoch: Option<u64>,
+
+    /// Reject new wraps, only allowing unwraps
+    #[clap(long)]
+    pub unwrap_only: bool,
+
+    /// Signer source of the unwrapped mint authority if different from fee
+    /// payer
+    #[clap(
+        long,
+        value_parser = SignerSourceParserBuilder::default().allow_all().build()
+    )]
+    pub mint_authority: Option<SignerSource>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetWrapLimitsOutput {
+    #[serde_as(as = "DisplayFromStr")]
+    pub unwrapped_mint: Pubkey,
+
+    #[serde_as(as = "DisplayFromStr")]
+    pub wrap_limits_address: Pubkey,
+
+    pub unwrap_only: bool,
+
+    pub max_wrapped_supply: Option<u64>,
+
+    pub max_wrapped_per_epoch: Option<u64>,
+
+    pub signatures: Vec<Signature>,
+}
+
+impl Display for SetWrapLimitsOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let format_cap = |cap: Option<u64>| cap.map_or("none".to_string(), |cap| cap.to_string());
+
+        writeln_name_value(f, "Unwrapped mint:", &self.unwrapped_mint.to_string())?;
+        writeln_name_value(
+            f,
+            "Wrap limits address:",
+            &self.wrap_limits_address.to_string(),
+        )?;
+        writeln_name_value(f, "Unwrap only:", &self.unwrap_only.to_string())?;
+        writeln_name_value(
+            f,
+            "Max total wrapped:",
+            &format_cap(self.max_wrapped_supply),
+        )?;
+        writeln_name_value(
+            f,
+            "Max wrapped per epoch:",
+            &format_cap(self.max_wrapped_per_epoch),
+        )?;
+
+        writeln!(f, "Signers:")?;
+        for signature in &self.signatures {
+            writeln!(f, "  {signature}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl QuietDisplay for SetWrapLimitsOutput {
+    fn write_str(&self, _: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        Ok(())
+    }
+}
+impl VerboseDisplay for SetWrapLimitsOutput {}
+
+pub async fn command_set_wrap_limits(
+    config: &Config,
+    args: SetWrapLimitsArgs,
+    matches: &clap::ArgMatches,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> CommandResult {
+    let payer = config.fee_payer()?;
+
+    // If mint_authority is provided, use it as a signer,
+    // else default to fee payer
+    let mint_authority_signer = if let Some(authority_source) = &args.mint_authority {
+        let signer =
+            signer_from_source(matches, authority_source, "mint_authority", wallet_manager)
+                .map_err(|e| e.to_string())?;
+        Arc::from(signer)
+    } else {
+        payer.clone()
+    };
+
+    let wrap_limits_address = get_wrap_limits_address(&args.unwrapped_mint);
+
+    let mut instructions = vec![];
+    let lamports = get_rent_shortfall(&config.rpc_client, &wrap_limits_address, |_| {
+        Ok(WrapLimits::LEN)
+    })
+    .await?;
+    if lamports > 0 {
+        println_display(
+            config,
+            format!(
+                "Funding wrap limits account {wrap_limits_address} with {lamports} lamports for \
+                 rent"
+            ),
+        );
+        instructions.push(transfer(&payer.pubkey(), &wrap_limits_address, lamports));
+    }
+
+    instructions.push(set_wrap_limits(
+        &spl_token_wrap::id(),
+        &mint_authority_signer.pubkey(),
+        &wrap_limits_address,
+        &args.unwrapped_mint,
+        &get_wrapped_mint_address(&args.unwrapped_mint, &spl_token::id()),
+        &get_wrapped_mint_address(&args.unwrapped_mint, &spl_token_2022_interface::id()),
+        args.unwrap_only,
+        args.max_wrapped_supply.unwrap_or(0),
+        args.max_wrapped_per_epoch.unwrap_or(0),
+        None,
+        None,
+        &[],
+    ));
+
+    let mut signers = vec![payer.clone()];
+    if payer.pubkey() != mint_authority_signer.pubkey() {
+        signers.push(mint_authority_signer);
+    }
+
+    let latest_blockhash = config.rpc_client.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &signers,
+        latest_blockhash,
+    );
+
+    process_transaction(config, transaction.clone()).await?;
+
+    let output = SetWrapLimitsOutput {
+        unwrapped_mint: args.unwrapped_mint,
+        wrap_limits_address,
+        unwrap_only: args.unwrap_only,
+        max_wrapped_supply: args.max_wrapped_supply,
+        max_wrapped_per_epoch: args.max_wrapped_per_epoch,
+        signatures: transaction.signatures,
+    };
+
+    Ok(format_output(config, output))
+}
@@ -0,0 +1,104 @@
+use {
+    crate::{common::parse_pubkey, config::Config, output::format_output, CommandResult},
+    clap::Args,
+    serde_derive::{Deserialize, Serialize},
+    serde_with::{serde_as, DisplayFromStr},
+    solana_cli_output::{display::writeln_name_value, QuietDisplay, VerboseDisplay},
+    solana_pubkey::Pubkey,
+    spl_token_wrap::{
+        get_wrap_stats_address,
+        state::{VersionedState, WrapStats},
+    },
+    std::fmt::{Display, Formatter},
+};
+
+#[derive(Clone, Debug, Args)]
+pub struct StatsArgs {
+    /// The address of the wrapped mint
+    #[clap(value_parser = parse_pubkey)]
+    pub wrapped_mint: Pubkey,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsOutput {
+    #[serde_as(as = "DisplayFromStr")]
+    pub wrapped_mint: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub stats_address: Pubkey,
+    pub initialized: bool,
+    #[serde_as(as = "DisplayFromStr")]
+    pub total_wrapped: u128,
+    #[serde_as(as = "DisplayFromStr")]
+    pub total_unwrapped: u128,
+    #[serde_as(as = "DisplayFromStr")]
+    pub total_wrap_fees: u128,
+    pub wrap_count: u64,
+    pub unwrap_count: u64,
+    pub last_activity_slot: u64,
+}
+
+impl Display for StatsOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln_name_value(f, "Wrapped mint:", &self.wrapped_mint.to_string())?;
+        writeln_name_value(f, "Stats address:", &self.stats_address.to_string())?;
+        if !self.initialized {
+            writeln!(f, "No statistics recorded")?;
+            return Ok(());
+        }
+        writeln_name_value(f, "Total wrapped:", &self.total_wrapped.to_string())?;
+        writeln_name_value(f, "Total unwrapped:", &self.total_unwrapped.to_string())?;
+        writeln_name_value(f, "Total wrap fees:", &self.total_wrap_fees.to_string())?;
+        writeln_name_value(f, "Wrap count:", &self.wrap_count.to_string())?;
+        writeln_name_value(f, "Unwrap count:", &self.unwrap_count.to_string())?;
+        writeln_name_value(
+            f,
+            "Last activity slot:",
+            &self.last_activity_slot.to_string(),
+        )?;
+        Ok(())
+    }
+}
+
+impl QuietDisplay for StatsOutput {
+    fn write_str(&self, _: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        Ok(())
+    }
+}
+impl VerboseDisplay for StatsOutput {}
+
+pub async fn command_stats(config: &Config, args: StatsArgs) -> CommandResult {
+    let stats_address = get_wrap_stats_address(&args.wrapped_mint);
+    let account = config
+        .rpc_client
+        .get_account_with_commitment(&stats_address, config.rpc_client.commitment())
+        .await?
+        .value;
+
+    let stats = account
+        .map(|account| WrapStats::unpack_versioned(&account.data))
+        .transpose()?;
+
+    let mut output = StatsOutput {
+        wrapped_mint: args.wrapped_mint,
+        stats_address,
+        initialized: stats.is_some(),
+        total_wrapped: 0,
+        total_unwrapped: 0,
+        total_wrap_fees: 0,
+        wrap_count: 0,
+        unwrap_count: 0,
+        last_activity_slot: 0,
+    };
+    if let Some(stats) = stats {
+        output.total_wrapped = stats.total_wrapped.into();
+        output.total_unwrapped = stats.total_unwrapped.into();
+        output.total_wrap_fees = stats.total_wrap_fees.into();
+        output.wrap_count = stats.wrap_count.into();
+        output.unwrap_count = stats.unwrap_count.into();
+        output.last_activity_slot = stats.last_activity_slot.into();
+    }
+
+    Ok(format_output(config, output))
+}
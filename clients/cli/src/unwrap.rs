@@ -1,8 +1,9 @@
 use {
     crate::{
         common::{
-            get_account_owner, get_mint_for_token_account, parse_presigner, parse_pubkey,
-            parse_token_program, process_transaction,
+            get_account_owner, get_escrow_collateral, get_mint_for_token_account,
+            get_protocol_fee_vault, get_rent_shortfall, get_token_account_owner, parse_presigner,
+            parse_pubkey, parse_token_program, process_transaction,
         },
         config::Config,
         output::{format_output, println_display},
@@ -20,18 +21,56 @@ use {
         ReturnSignersConfig, VerboseDisplay,
     },
     solana_hash::Hash,
+    solana_instruction::Instruction,
+    solana_keypair::Keypair,
     solana_presigner::Presigner,
     solana_pubkey::Pubkey,
     solana_remote_wallet::remote_wallet::RemoteWalletManager,
     solana_signature::Signature,
     solana_signer::Signer,
+    solana_system_interface::instruction::{create_account, transfer},
     solana_transaction::Transaction,
+    solana_zk_sdk::encryption::{
+        auth_encryption::{AeCiphertext, AeKey},
+        elgamal::{ElGamalCiphertext, ElGamalKeypair},
+        pod::{
+            auth_encryption::PodAeCiphertext as SdkPodAeCiphertext,
+            elgamal::{
+                PodElGamalCiphertext as SdkPodElGamalCiphertext,
+                PodElGamalPubkey as SdkPodElGamalPubkey,
+            },
+        },
+    },
+    solana_zk_sdk_pod::encryption::auth_encryption::PodAeCiphertext,
+    spl_token_2022_interface::{
+        extension::{
+            confidential_transfer::ConfidentialTransferAccount, BaseStateWithExtensions,
+            PodStateWithExtensions,
+        },
+        pod::PodAccount,
+        solana_zk_elgamal_proof_interface::{
+            self,
+            instruction::{close_context_state, ContextStateInfo, ProofInstruction},
+            proof_data::{
+                BatchedRangeProofContext, BatchedRangeProofU64Data,
+                CiphertextCommitmentEqualityProofContext, CiphertextCommitmentEqualityProofData,
+            },
+            state::ProofContextState,
+        },
+    },
+    spl_token_confidential_transfer_proof_generation::withdraw::withdraw_proof_data,
     spl_token_wrap::{
-        get_escrow_address, get_wrapped_mint_address, get_wrapped_mint_authority,
-        instruction::unwrap,
+        get_allowlist_address, get_allowlist_entry_address, get_escrow_address,
+        get_flash_mint_guard_address, get_pro_rata_redemption_address,
+        get_protocol_fee_config_address, get_protocol_fee_override_address,
+        get_wrap_limits_address, get_wrap_stats_address, get_wrapped_mint_address,
+        get_wrapped_mint_authority,
+        instruction::{unwrap, unwrap_confidential},
+        state::{ProRataRedemption, VersionedState, WrapStats},
     },
     std::{
         fmt::{Display, Formatter},
+        mem::size_of,
         rc::Rc,
         sync::Arc,
     },
@@ -73,6 +112,12 @@ pub struct UnwrapArgs {
     #[clap(long, value_parser = parse_token_program)]
     pub unwrapped_token_program: Option<Pubkey>,
 
+    /// The token account receiving the protocol fee. Defaults to the
+    /// associated token account of the fee destination of the deployment, if
+    /// it charges a protocol fee.
+    #[clap(long, value_parser = parse_pubkey)]
+    pub protocol_fee_vault: Option<Pubkey>,
+
     /// Member signer of a multisig account.
     /// Use this argument multiple times for each signer.
     #[clap(
@@ -99,6 +144,23 @@ pub struct UnwrapArgs {
     /// Do not broadcast signed transaction, just sign
     #[clap(long)]
     pub sign_only: bool,
+
+    /// Record the unwrap in the statistics account of the wrapped mint.
+    /// Funds the rent of the account if it doesn't exist yet, unless
+    /// signing only.
+    #[clap(long)]
+    pub record_stats: bool,
+
+    /// Unwrap from the confidential available balance of the wrapped token
+    /// account. The withdraw proofs are generated locally with the ElGamal
+    /// and AES keys derived from the transfer authority, and verified into
+    /// proof context accounts funded by the fee payer, which are closed again
+    /// by the unwrap transaction.
+    #[clap(
+        long,
+        conflicts_with_all = &["record-stats", "sign-only", "multisig-signer"]
+    )]
+    pub confidential: bool,
 }
 
 #[serde_as]
@@ -123,6 +185,12 @@ pub struct UnwrapOutput {
     #[serde_as(as = "DisplayFromStr")]
     pub recipient_token_account: Pubkey,
 
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub wrap_stats_account: Option<Pubkey>,
+
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub pro_rata_redemption_account: Option<Pubkey>,
+
     pub amount: u64,
 
     pub signatures: Vec<Signature>,
@@ -158,6 +226,16 @@ impl Display for UnwrapOutput {
             "Recipient unwrapped token account:",
             &self.recipient_token_account.to_string(),
         )?;
+        if let Some(wrap_stats_account) = &self.wrap_stats_account {
+            writeln_name_value(f, "Stats account:", &wrap_stats_account.to_string())?;
+        }
+        if let Some(pro_rata_redemption_account) = &self.pro_rata_redemption_account {
+            writeln_name_value(
+                f,
+                "Pro-rata redemption account:",
+                &pro_rata_redemption_account.to_string(),
+            )?;
+        }
         writeln_name_value(f, "Amount unwrapped:", &self.amount.to_string())?;
 
         if let Some(data) = &self.sign_only_data {
@@ -193,6 +271,8 @@ pub async fn command_unwrap(
         wrapped_mint_authority_address,
         unwrapped_token_program,
         escrow_account,
+        allowlist_entry,
+        protocol_fee_vault,
         transfer_authority_signer,
     } = resolve_addresses(config, &args, matches, wallet_manager).await?;
 
@@ -218,20 +298,150 @@ pub async fn command_unwrap(
         .map(|s| s.pubkey())
         .collect::<Vec<Pubkey>>();
 
-    let instruction = unwrap(
-        &spl_token_wrap::id(),
-        &escrow_account,
-        &args.unwrapped_token_recipient,
-        &wrapped_mint_authority_address,
-        &unwrapped_mint_address,
-        &wrapped_token_program,
-        &unwrapped_token_program,
-        &args.wrapped_token_account,
-        &wrapped_mint_address,
-        &transfer_authority_signer.pubkey(),
-        &multisig_pubkeys.iter().collect::<Vec<&Pubkey>>(),
-        args.amount,
-    );
+    let payer = config.fee_payer()?;
+    let mut instructions = vec![];
+
+    if args.confidential {
+        if wrapped_token_program != spl_token_2022_interface::id() {
+            return Err("Confidential unwraps require a Token-2022 wrapped mint".into());
+        }
+        if config.dry_run {
+            return Err("Confidential unwraps cannot be simulated".into());
+        }
+    }
+
+    let wrap_stats_account = args
+        .record_stats
+        .then(|| get_wrap_stats_address(&wrapped_mint_address));
+    if let (Some(wrap_stats_account), false) = (wrap_stats_account, args.sign_only) {
+        let lamports = get_rent_shortfall(&config.rpc_client, &wrap_stats_account, |_| {
+            Ok(WrapStats::LEN)
+        })
+        .await?;
+        if lamports > 0 {
+            println_display(
+                config,
+                format!(
+                    "Funding stats account {wrap_stats_account} with {lamports} lamports for rent"
+                ),
+            );
+            instructions.push(transfer(&payer.pubkey(), &wrap_stats_account, lamports));
+        }
+    }
+
+    // An under-collateralized escrow redeems pro rata, which is recorded in
+    // the pro-rata redemption account
+    let (escrow_balance, wrapped_supply) =
+        get_escrow_collateral(&config.rpc_client, &escrow_account, &wrapped_mint_address).await?;
+    let pro_rata_redemption_account = (escrow_balance < wrapped_supply)
+        .then(|| get_pro_rata_redemption_address(&wrapped_mint_address));
+    if let Some(pro_rata_redemption_account) = pro_rata_redemption_account {
+        if args.confidential {
+            return Err(format!(
+                "Escrow {escrow_account} is under-collateralized, confidential unwraps require a \
+                 fully collateralized escrow"
+            )
+            .into());
+        }
+        println_display(
+            config,
+            format!(
+                "Escrow {escrow_account} is under-collateralized: {escrow_balance} unwrapped \
+                 tokens for a wrapped supply of {wrapped_supply}, redeeming pro rata"
+            ),
+        );
+        if !args.sign_only {
+            let lamports =
+                get_rent_shortfall(&config.rpc_client, &pro_rata_redemption_account, |_| {
+                    Ok(ProRataRedemption::LEN)
+                })
+                .await?;
+            if lamports > 0 {
+                println_display(
+                    config,
+                    format!(
+                        "Funding pro-rata redemption account {pro_rata_redemption_account} \
+                         with {lamports} lamports for rent"
+                    ),
+                );
+                instructions.push(transfer(
+                    &payer.pubkey(),
+                    &pro_rata_redemption_account,
+                    lamports,
+                ));
+            }
+        }
+    }
+
+    if args.confidential {
+        let withdraw = prepare_confidential_withdraw(
+            config,
+            &args.wrapped_token_account,
+            transfer_authority_signer.as_ref(),
+            &payer,
+            args.amount,
+        )
+        .await?;
+        instructions.push(unwrap_confidential(
+            &spl_token_wrap::id(),
+            &escrow_account,
+            &args.unwrapped_token_recipient,
+            &wrapped_mint_authority_address,
+            &unwrapped_mint_address,
+            &unwrapped_token_program,
+            &args.wrapped_token_account,
+            &wrapped_mint_address,
+            &transfer_authority_signer.pubkey(),
+            &get_wrap_limits_address(&unwrapped_mint_address),
+            &get_allowlist_address(&unwrapped_mint_address),
+            &allowlist_entry,
+            &get_protocol_fee_config_address(),
+            &get_protocol_fee_override_address(&unwrapped_mint_address),
+            &protocol_fee_vault,
+            &get_flash_mint_guard_address(&wrapped_mint_address),
+            &withdraw.equality_proof_context,
+            &withdraw.range_proof_context,
+            &[],
+            args.amount,
+            withdraw.new_decryptable_available_balance,
+        ));
+        for context_state_account in [
+            withdraw.equality_proof_context,
+            withdraw.range_proof_context,
+        ] {
+            instructions.push(close_context_state(
+                ContextStateInfo {
+                    context_state_account: &context_state_account,
+                    context_state_authority: &payer.pubkey(),
+                },
+                &payer.pubkey(),
+            ));
+        }
+    } else {
+        instructions.push(unwrap(
+            &spl_token_wrap::id(),
+            &escrow_account,
+            &args.unwrapped_token_recipient,
+            &wrapped_mint_authority_address,
+            &unwrapped_mint_address,
+            &wrapped_token_program,
+            &unwrapped_token_program,
+            &args.wrapped_token_account,
+            &wrapped_mint_address,
+            &transfer_authority_signer.pubkey(),
+            &get_wrap_limits_address(&unwrapped_mint_address),
+            &get_allowlist_address(&unwrapped_mint_address),
+            &allowlist_entry,
+            &get_protocol_fee_config_address(),
+            &get_protocol_fee_override_address(&unwrapped_mint_address),
+            &protocol_fee_vault,
+            &get_flash_mint_guard_address(&wrapped_mint_address),
+            wrap_stats_account.as_ref(),
+            pro_rata_redemption_account.as_ref(),
+            &multisig_pubkeys.iter().collect::<Vec<&Pubkey>>(),
+            args.amount,
+        ));
+    }
 
     let blockhash = if let Some(hash) = args.blockhash {
         hash
@@ -239,8 +449,6 @@ pub async fn command_unwrap(
         config.rpc_client.get_latest_blockhash().await?
     };
 
-    let payer = config.fee_payer()?;
-
     // Payer will always be a signer
     let mut signers = vec![payer.clone()];
 
@@ -261,7 +469,7 @@ pub async fn command_unwrap(
         }
     }
 
-    let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
     transaction.partial_sign(&signers, blockhash);
 
     if !args.sign_only {
@@ -284,6 +492,8 @@ pub async fn command_unwrap(
         escrow_account,
         wrapped_token_account: args.wrapped_token_account,
         recipient_token_account: args.unwrapped_token_recipient,
+        wrap_stats_account,
+        pro_rata_redemption_account,
         amount: args.amount,
         signatures: transaction.signatures,
         sign_only_data,
@@ -292,6 +502,178 @@ pub async fn command_unwrap(
     Ok(format_output(config, output))
 }
 
+/// Proof context state accounts of a confidential withdraw, verified on chain
+struct ConfidentialWithdraw {
+    equality_proof_context: Pubkey,
+    range_proof_context: Pubkey,
+    new_decryptable_available_balance: PodAeCiphertext,
+}
+
+/// Generates the proofs of a confidential withdraw from the wrapped token
+/// account and verifies them into new proof context state accounts, with the
+/// fee payer as their authority
+async fn prepare_confidential_withdraw(
+    config: &Config,
+    wrapped_token_account: &Pubkey,
+    transfer_authority: &dyn Signer,
+    payer: &Arc<dyn Signer>,
+    amount: u64,
+) -> Result<ConfidentialWithdraw, Error> {
+    let account = config.rpc_client.get_account(wrapped_token_account).await?;
+    let state = PodStateWithExtensions::<PodAccount>::unpack(&account.data)?;
+    let extension = state
+        .get_extension::<ConfidentialTransferAccount>()
+        .map_err(|_| {
+            format!(
+                "Wrapped token account {wrapped_token_account} is not configured for \
+                 confidential transfers"
+            )
+        })?;
+
+    // The keys are derived from the transfer authority the same way as
+    // `spl-token` derives them
+    let seed = wrapped_token_account.to_bytes();
+    let elgamal_keypair =
+        ElGamalKeypair::new_from_signer(transfer_authority, &seed).map_err(|e| e.to_string())?;
+    let aes_key = AeKey::new_from_signer(transfer_authority, &seed).map_err(|e| e.to_string())?;
+    if bytemuck::cast::<_, SdkPodElGamalPubkey>(extension.elgamal_pubkey)
+        != SdkPodElGamalPubkey::from(*elgamal_keypair.pubkey())
+    {
+        return Err(format!(
+            "ElGamal public key of wrapped token account {wrapped_token_account} was not derived \
+             from the transfer authority"
+        )
+        .into());
+    }
+
+    let decryptable_available_balance: AeCiphertext =
+        bytemuck::cast::<_, SdkPodAeCiphertext>(extension.decryptable_available_balance)
+            .try_into()
+            .map_err(|_| "Invalid decryptable available balance")?;
+    let available_balance = aes_key
+        .decrypt(&decryptable_available_balance)
+        .ok_or("Failed to decrypt the confidential available balance")?;
+    let remaining_balance = available_balance.checked_sub(amount).ok_or_else(|| {
+        format!("Confidential available balance {available_balance} is less than {amount}")
+    })?;
+    let available_balance_ciphertext: ElGamalCiphertext =
+        bytemuck::cast::<_, SdkPodElGamalCiphertext>(extension.available_balance)
+            .try_into()
+            .map_err(|_| "Invalid available balance ciphertext")?;
+
+    let proof_data = withdraw_proof_data(
+        &available_balance_ciphertext,
+        available_balance,
+        amount,
+        &elgamal_keypair,
+    )
+    .map_err(|e| e.to_string())?;
+    let equality_proof_data: CiphertextCommitmentEqualityProofData =
+        bytemuck::cast(proof_data.equality_proof_data);
+    let range_proof_data: BatchedRangeProofU64Data = bytemuck::cast(proof_data.range_proof_data);
+
+    // The range proof fills a transaction on its own, so the context state
+    // accounts are created first and each proof is verified separately
+
+    let equality_proof_context = Keypair::new();
+    let range_proof_context = Keypair::new();
+
+    let mut instructions = vec![];
+    for (context_state_account, space) in [
+        (
+            equality_proof_context.pubkey(),
+            size_of::<ProofContextState<CiphertextCommitmentEqualityProofContext>>(),
+        ),
+        (
+            range_proof_context.pubkey(),
+            size_of::<ProofContextState<BatchedRangeProofContext>>(),
+        ),
+    ] {
+        let lamports = config
+            .rpc_client
+            .get_minimum_balance_for_rent_exemption(space)
+            .await?;
+        instructions.push(create_account(
+            &payer.pubkey(),
+            &context_state_account,
+            lamports,
+            space as u64,
+            &solana_zk_elgamal_proof_interface::id(),
+        ));
+    }
+    let equality_proof_context_address = equality_proof_context.pubkey();
+    let range_proof_context_address = range_proof_context.pubkey();
+    send_instructions(
+        config,
+        &instructions,
+        vec![
+            payer.clone(),
+            Arc::new(equality_proof_context),
+            Arc::new(range_proof_context),
+        ],
+    )
+    .await?;
+
+    send_instructions(
+        config,
+        &[
+            ProofInstruction::VerifyCiphertextCommitmentEquality.encode_verify_proof(
+                Some(ContextStateInfo {
+                    context_state_account: &equality_proof_context_address,
+                    context_state_authority: &payer.pubkey(),
+                }),
+                &equality_proof_data,
+            ),
+        ],
+        vec![payer.clone()],
+    )
+    .await?;
+
+    send_instructions(
+        config,
+        &[
+            ProofInstruction::VerifyBatchedRangeProofU64.encode_verify_proof(
+                Some(ContextStateInfo {
+                    context_state_account: &range_proof_context_address,
+                    context_state_authority: &payer.pubkey(),
+                }),
+                &range_proof_data,
+            ),
+        ],
+        vec![payer.clone()],
+    )
+    .await?;
+
+    println_display(
+        config,
+        format!(
+            "Verified withdraw proofs into context accounts {equality_proof_context_address} and \
+             {range_proof_context_address}"
+        ),
+    );
+
+    Ok(ConfidentialWithdraw {
+        equality_proof_context: equality_proof_context_address,
+        range_proof_context: range_proof_context_address,
+        new_decryptable_available_balance: bytemuck::cast(SdkPodAeCiphertext::from(
+            aes_key.encrypt(remaining_balance),
+        )),
+    })
+}
+
+/// Sends the instructions in a transaction paid by the first signer
+async fn send_instructions(
+    config: &Config,
+    instructions: &[Instruction],
+    signers: Vec<Arc<dyn Signer>>,
+) -> Result<(), Error> {
+    let blockhash = config.rpc_client.get_latest_blockhash().await?;
+    let mut transaction = Transaction::new_with_payer(instructions, Some(&signers[0].pubkey()));
+    transaction.try_sign(&signers, blockhash)?;
+    process_transaction(config, transaction).await?;
+    Ok(())
+}
+
 struct ResolvedAddrs {
     wrapped_token_program: Pubkey,
     unwrapped_mint_address: Pubkey,
@@ -299,6 +681,8 @@ struct ResolvedAddrs {
     wrapped_mint_authority_address: Pubkey,
     unwrapped_token_program: Pubkey,
     escrow_account: Pubkey,
+    allowlist_entry: Pubkey,
+    protocol_fee_vault: Pubkey,
     transfer_authority_signer: Arc<dyn Signer>,
 }
 
@@ -371,6 +755,24 @@ async fn resolve_addresses(
         &wrapped_token_program,
     );
 
+    // The allowlist is checked against the owner of the wrapped token account
+    let wrapped_token_owner =
+        get_token_account_owner(&config.rpc_client, &args.wrapped_token_account).await?;
+    let allowlist_entry =
+        get_allowlist_entry_address(&unwrapped_mint_address, &wrapped_token_owner);
+
+    let protocol_fee_vault = if let Some(protocol_fee_vault) = args.protocol_fee_vault {
+        protocol_fee_vault
+    } else {
+        get_protocol_fee_vault(
+            &config.rpc_client,
+            &unwrapped_mint_address,
+            &unwrapped_token_program,
+            &escrow_account,
+        )
+        .await?
+    };
+
     if !config.dry_run {
         println_display(
             config,
@@ -404,6 +806,8 @@ async fn resolve_addresses(
         wrapped_mint_authority_address,
         unwrapped_token_program,
         escrow_account,
+        allowlist_entry,
+        protocol_fee_vault,
         transfer_authority_signer,
     })
 }
@@ -0,0 +1,215 @@
+use {
+    crate::{
+        common::{get_rent_shortfall, parse_pubkey, process_transaction},
+        config::Config,
+        output::{format_output, println_display},
+        CommandResult, Error,
+    },
+    clap::Args,
+    serde_derive::{Deserialize, Serialize},
+    serde_with::{serde_as, DisplayFromStr},
+    solana_clap_v3_utils::{
+        input_parsers::signer::{SignerSource, SignerSourceParserBuilder},
+        keypair::signer_from_source,
+    },
+    solana_cli_output::{display::writeln_name_value, QuietDisplay, VerboseDisplay},
+    solana_pubkey::Pubkey,
+    solana_remote_wallet::remote_wallet::RemoteWalletManager,
+    solana_signature::Signature,
+    solana_signer::Signer,
+    solana_system_interface::instruction::transfer,
+    solana_transaction::Transaction,
+    spl_token_wrap::{
+        get_allowlist_entry_address,
+        instruction::update_allowlist,
+        state::{AllowlistEntry, VersionedState},
+    },
+    std::{
+        fmt::{Display, Formatter},
+        path::PathBuf,
+        rc::Rc,
+        str::FromStr,
+        sync::Arc,
+    },
+};
+
+/// Wallets updated per transaction, keeping the entry accounts, the wallets
+/// in the instruction data and the rent transfers within the transaction size
+/// limit
+const MAX_WALLETS_PER_TRANSACTION: usize = 8;
+
+#[derive(Clone, Debug, Args)]
+pub struct UpdateAllowlistArgs {
+    /// The address of the unwrapped mint
+    #[clap(value_parser = parse_pubkey)]
+    pub unwrapped_mint: Pubkey,
+
+    /// The wallets to add to or remove from the allowlist
+    #[clap(
+        value_parser = parse_pubkey,
+        multiple = true,
+        required_unless_present = "wallets_file"
+    )]
+    pub wallets: Vec<Pubkey>,
+
+    /// File listing more wallets, one address per line
+    #[clap(long, value_parser)]
+    pub wallets_file: Option<PathBuf>,
+
+    /// Remove the wallets from the allowlist instead of adding them
+    #[clap(long)]
+    pub remove: bool,
+
+    /// Signer source of the unwrapped mint authority if different from fee
+    /// payer
+    #[clap(
+        long,
+        value_parser = SignerSourceParserBuilder::default().allow_all().build()
+    )]
+    pub mint_authority: Option<SignerSource>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateAllowlistOutput {
+    #[serde_as(as = "DisplayFromStr")]
+    pub unwrapped_mint: Pubkey,
+
+    pub allowed: bool,
+
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub wallets: Vec<Pubkey>,
+
+    pub signatures: Vec<Signature>,
+}
+
+impl Display for UpdateAllowlistOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln_name_value(f, "Unwrapped mint:", &self.unwrapped_mint.to_string())?;
+        writeln_name_value(f, "Allowed:", &self.allowed.to_string())?;
+
+        writeln!(f, "Wallets:")?;
+        for wallet in &self.wallets {
+            writeln!(f, "  {wallet}")?;
+        }
+
+        writeln!(f, "Signers:")?;
+        for signature in &self.signatures {
+            writeln!(f, "  {signature}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl QuietDisplay for UpdateAllowlistOutput {
+    fn write_str(&self, _: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        Ok(())
+    }
+}
+impl VerboseDisplay for UpdateAllowlistOutput {}
+
+fn read_wallets_file(path: &PathBuf) -> Result<Vec<Pubkey>, Error> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read wallets file {}: {e}", path.display()))?;
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            Pubkey::from_str(line).map_err(|_| format!("Invalid wallet address {line}").into())
+        })
+        .collect()
+}
+
+pub async fn command_update_allowlist(
+    config: &Config,
+    args: UpdateAllowlistArgs,
+    matches: &clap::ArgMatches,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> CommandResult {
+    let payer = config.fee_payer()?;
+
+    // If mint_authority is provided, use it as a signer,
+    // else default to fee payer
+    let mint_authority_signer = if let Some(authority_source) = &args.mint_authority {
+        let signer =
+            signer_from_source(matches, authority_source, "mint_authority", wallet_manager)
+                .map_err(|e| e.to_string())?;
+        Arc::from(signer)
+    } else {
+        payer.clone()
+    };
+
+    let mut wallets = args.wallets.clone();
+    if let Some(path) = &args.wallets_file {
+        wallets.extend(read_wallets_file(path)?);
+    }
+    wallets.sort();
+    wallets.dedup();
+    if wallets.is_empty() {
+        return Err("No wallets to update".into());
+    }
+
+    let allowed = !args.remove;
+    let mut signatures = vec![];
+    for chunk in wallets.chunks(MAX_WALLETS_PER_TRANSACTION) {
+        let mut instructions = vec![];
+        let mut entries = vec![];
+        for wallet in chunk {
+            let entry_address = get_allowlist_entry_address(&args.unwrapped_mint, wallet);
+            let lamports = get_rent_shortfall(&config.rpc_client, &entry_address, |_| {
+                Ok(AllowlistEntry::LEN)
+            })
+            .await?;
+            if lamports > 0 {
+                println_display(
+                    config,
+                    format!(
+                        "Funding allowlist entry {entry_address} of {wallet} with {lamports} \
+                         lamports for rent"
+                    ),
+                );
+                instructions.push(transfer(&payer.pubkey(), &entry_address, lamports));
+            }
+            entries.push((*wallet, entry_address));
+        }
+
+        instructions.push(update_allowlist(
+            &spl_token_wrap::id(),
+            &mint_authority_signer.pubkey(),
+            &args.unwrapped_mint,
+            allowed,
+            &entries,
+            None,
+            None,
+            &[],
+        ));
+
+        let mut signers = vec![payer.clone()];
+        if payer.pubkey() != mint_authority_signer.pubkey() {
+            signers.push(mint_authority_signer.clone());
+        }
+
+        let latest_blockhash = config.rpc_client.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &signers,
+            latest_blockhash,
+        );
+
+        process_transaction(config, transaction.clone()).await?;
+        signatures.extend(transaction.signatures);
+    }
+
+    let output = UpdateAllowlistOutput {
+        unwrapped_mint: args.unwrapped_mint,
+        allowed,
+        wallets,
+        signatures,
+    };
+
+    Ok(format_output(config, output))
+}
@@ -1,12 +1,13 @@
 use {
     crate::{
         common::{
-            get_account_owner, get_mint_for_token_account, parse_presigner, parse_pubkey,
+            get_account_owner, get_mint_for_token_account, get_protocol_fee_vault,
+            get_rent_shortfall, get_token_account_owner, parse_presigner, parse_pubkey,
             parse_token_program, process_transaction,
         },
         config::Config,
         output::{format_output, println_display},
-        CommandResult,
+        CommandResult, Error,
     },
     clap::{value_parser, Args},
     serde_derive::{Deserialize, Serialize},
@@ -25,10 +26,23 @@ use {
     solana_remote_wallet::remote_wallet::RemoteWalletManager,
     solana_signature::Signature,
     solana_signer::Signer,
+    solana_system_interface::instruction::transfer,
     solana_transaction::Transaction,
     spl_associated_token_account_interface::address::get_associated_token_address_with_program_id,
+    spl_token_2022_interface::{
+        extension::{
+            confidential_transfer::ConfidentialTransferAccount, BaseStateWithExtensions,
+            PodStateWithExtensions,
+        },
+        pod::PodAccount,
+    },
     spl_token_wrap::{
-        get_escrow_address, get_wrapped_mint_address, get_wrapped_mint_authority, instruction::wrap,
+        get_allowlist_address, get_allowlist_entry_address, get_canonical_pointer_address,
+        get_escrow_address, get_protocol_fee_config_address, get_protocol_fee_override_address,
+        get_scoped_canonical_pointer_address, get_wrap_limits_address, get_wrap_stats_address,
+        get_wrapped_mint_address, get_wrapped_mint_authority,
+        instruction::{wrap, wrap_confidential, wrap_sol},
+        state::{VersionedState, WrapStats},
     },
     std::{
         fmt::{Display, Formatter},
@@ -39,7 +53,9 @@ use {
 
 #[derive(Clone, Debug, Args)]
 pub struct WrapArgs {
-    /// The address of the unwrapped token account to wrap from
+    /// The address of the unwrapped token account to wrap from.
+    /// With `--sol`, the system account to wrap lamports from, which must be
+    /// the transfer authority.
     #[clap(value_parser = parse_pubkey)]
     pub unwrapped_token_account: Pubkey,
 
@@ -59,7 +75,7 @@ pub struct WrapArgs {
     pub transfer_authority: Option<SignerSource>,
 
     /// The address of the mint to wrap, queried if not provided
-    #[clap(long, value_parser = parse_pubkey)]
+    #[clap(long, value_parser = parse_pubkey, conflicts_with = "sol")]
     pub unwrapped_mint: Option<Pubkey>,
 
     /// The address of the token account to receive wrapped tokens.
@@ -67,8 +83,21 @@ pub struct WrapArgs {
     #[clap(long, value_parser = parse_pubkey)]
     pub recipient_token_account: Option<Pubkey>,
 
+    /// The owner of the recipient token account, checked against the
+    /// allowlist of the unwrapped mint. Queried if `recipient_token_account`
+    /// is provided, defaults to the fee payer otherwise.
+    #[clap(long, value_parser = parse_pubkey)]
+    pub recipient_owner: Option<Pubkey>,
+
+    /// The token account receiving the protocol fee. Defaults to the
+    /// associated token account of the fee destination of the deployment, if
+    /// it charges a protocol fee.
+    #[clap(long, value_parser = parse_pubkey)]
+    pub protocol_fee_vault: Option<Pubkey>,
+
     /// The address of the token program that the unwrapped mint belongs to.
-    /// Queries account for `unwrapped_token_account` if not provided.
+    /// Queries account for `unwrapped_token_account` if not provided, or
+    /// defaults to SPL Token with `--sol`.
     #[clap(long, value_parser = parse_token_program)]
     pub unwrapped_token_program: Option<Pubkey>,
 
@@ -78,7 +107,8 @@ pub struct WrapArgs {
         long,
         multiple = true,
         value_parser = SignerSourceParserBuilder::default().allow_all().build(),
-        requires = "blockhash"
+        requires = "blockhash",
+        conflicts_with = "sol"
     )]
     pub multisig_signer: Option<Vec<SignerSource>>,
 
@@ -98,6 +128,23 @@ pub struct WrapArgs {
     /// Do not broadcast signed transaction, just sign
     #[clap(long)]
     pub sign_only: bool,
+
+    /// Record the wrap in the statistics account of the wrapped mint.
+    /// Funds the rent of the account if it doesn't exist yet, unless
+    /// signing only.
+    #[clap(long, conflicts_with = "sol")]
+    pub record_stats: bool,
+
+    /// Wrap native SOL directly from lamports into the wrapped native mint,
+    /// instead of wrapping tokens from a token account
+    #[clap(long)]
+    pub sol: bool,
+
+    /// Deposit the wrapped tokens into the confidential pending balance of
+    /// the recipient. The recipient must be a Token-2022 account configured
+    /// for confidential transfers and owned by the fee payer.
+    #[clap(long, conflicts_with_all = &["sol", "record-stats"])]
+    pub confidential: bool,
 }
 
 #[serde_as]
@@ -119,6 +166,9 @@ pub struct WrapOutput {
     #[serde_as(as = "DisplayFromStr")]
     pub escrow_account: Pubkey,
 
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub wrap_stats_account: Option<Pubkey>,
+
     pub amount: u64,
 
     pub signatures: Vec<Signature>,
@@ -149,6 +199,9 @@ impl Display for WrapOutput {
             &self.recipient_token_account.to_string(),
         )?;
         writeln_name_value(f, "Escrow account:", &self.escrow_account.to_string())?;
+        if let Some(wrap_stats_account) = &self.wrap_stats_account {
+            writeln_name_value(f, "Stats account:", &wrap_stats_account.to_string())?;
+        }
         writeln_name_value(f, "Amount:", &self.amount.to_string())?;
 
         if let Some(data) = &self.sign_only_data {
@@ -179,20 +232,38 @@ pub async fn command_wrap(
 ) -> CommandResult {
     let payer = config.fee_payer()?;
 
-    let unwrapped_mint = if let Some(mint) = args.unwrapped_mint {
+    let unwrapped_token_program = if let Some(pubkey) = args.unwrapped_token_program {
+        pubkey
+    } else if args.sol {
+        spl_token::id()
+    } else {
+        get_account_owner(&config.rpc_client, &args.unwrapped_token_account).await?
+    };
+
+    let unwrapped_mint = if args.sol {
+        if unwrapped_token_program == spl_token_2022_interface::id() {
+            spl_token_2022_interface::native_mint::id()
+        } else {
+            spl_token::native_mint::id()
+        }
+    } else if let Some(mint) = args.unwrapped_mint {
         mint
     } else {
         get_mint_for_token_account(&config.rpc_client, &args.unwrapped_token_account).await?
     };
 
     if !args.sign_only {
-        println_display(
-            config,
-            format!(
-                "Wrapping {} tokens from mint {}",
-                args.amount, unwrapped_mint
-            ),
-        );
+        if args.sol {
+            println_display(config, format!("Wrapping {} lamports", args.amount));
+        } else {
+            println_display(
+                config,
+                format!(
+                    "Wrapping {} tokens from mint {}",
+                    args.amount, unwrapped_mint
+                ),
+            );
+        }
     }
 
     // Derive wrapped mint address and mint authority
@@ -209,6 +280,15 @@ pub async fn command_wrap(
         )
     });
 
+    let recipient_owner = if let Some(owner) = args.recipient_owner {
+        owner
+    } else if args.recipient_token_account.is_some() {
+        get_token_account_owner(&config.rpc_client, &recipient_token_account).await?
+    } else {
+        payer.pubkey()
+    };
+    let allowlist_entry = get_allowlist_entry_address(&unwrapped_mint, &recipient_owner);
+
     // NullSigner used for multisig scenarios
     let parse_config = SignerFromPathConfig {
         allow_null_signer: true,
@@ -250,11 +330,13 @@ pub async fn command_wrap(
         .map(|s| s.pubkey())
         .collect::<Vec<Pubkey>>();
 
-    let unwrapped_token_program = if let Some(pubkey) = args.unwrapped_token_program {
-        pubkey
-    } else {
-        get_account_owner(&config.rpc_client, &args.unwrapped_token_account).await?
-    };
+    if args.sol && args.unwrapped_token_account != transfer_authority_signer.pubkey() {
+        return Err(format!(
+            "With --sol, lamports are wrapped from the transfer authority {}",
+            transfer_authority_signer.pubkey()
+        )
+        .into());
+    }
 
     let escrow_account = get_escrow_address(
         &unwrapped_mint,
@@ -262,20 +344,114 @@ pub async fn command_wrap(
         &args.wrapped_token_program,
     );
 
-    let instruction = wrap(
-        &spl_token_wrap::id(),
-        &recipient_token_account,
-        &wrapped_mint_address,
-        &wrapped_mint_authority,
-        &unwrapped_token_program,
-        &args.wrapped_token_program,
-        &args.unwrapped_token_account,
-        &unwrapped_mint,
-        &escrow_account,
-        &transfer_authority_signer.pubkey(),
-        &multisig_pubkeys.iter().collect::<Vec<&Pubkey>>(),
-        args.amount,
-    );
+    let protocol_fee_vault = if let Some(protocol_fee_vault) = args.protocol_fee_vault {
+        protocol_fee_vault
+    } else {
+        get_protocol_fee_vault(
+            &config.rpc_client,
+            &unwrapped_mint,
+            &unwrapped_token_program,
+            &escrow_account,
+        )
+        .await?
+    };
+
+    let mut instructions = vec![];
+
+    let wrap_stats_account = args
+        .record_stats
+        .then(|| get_wrap_stats_address(&wrapped_mint_address));
+    if let (Some(wrap_stats_account), false) = (wrap_stats_account, args.sign_only) {
+        let lamports = get_rent_shortfall(&config.rpc_client, &wrap_stats_account, |_| {
+            Ok(WrapStats::LEN)
+        })
+        .await?;
+        if lamports > 0 {
+            println_display(
+                config,
+                format!(
+                    "Funding stats account {wrap_stats_account} with {lamports} lamports for rent"
+                ),
+            );
+            instructions.push(transfer(&payer.pubkey(), &wrap_stats_account, lamports));
+        }
+    }
+
+    if args.confidential {
+        if args.wrapped_token_program != spl_token_2022_interface::id() {
+            return Err("Confidential wraps require a Token-2022 wrapped mint".into());
+        }
+        if !args.sign_only {
+            check_confidential_recipient(config, &recipient_token_account, &payer.pubkey()).await?;
+        }
+        instructions.push(wrap_confidential(
+            &spl_token_wrap::id(),
+            &recipient_token_account,
+            &payer.pubkey(),
+            &wrapped_mint_address,
+            &wrapped_mint_authority,
+            &unwrapped_token_program,
+            &args.unwrapped_token_account,
+            &unwrapped_mint,
+            &escrow_account,
+            &get_canonical_pointer_address(&unwrapped_mint),
+            &get_scoped_canonical_pointer_address(&unwrapped_mint, &args.wrapped_token_program),
+            &get_wrap_limits_address(&unwrapped_mint),
+            &get_allowlist_address(&unwrapped_mint),
+            &allowlist_entry,
+            &get_protocol_fee_config_address(),
+            &get_protocol_fee_override_address(&unwrapped_mint),
+            &protocol_fee_vault,
+            &transfer_authority_signer.pubkey(),
+            &multisig_pubkeys.iter().collect::<Vec<&Pubkey>>(),
+            args.amount,
+        ));
+    } else if args.sol {
+        instructions.push(wrap_sol(
+            &spl_token_wrap::id(),
+            &recipient_token_account,
+            &wrapped_mint_address,
+            &wrapped_mint_authority,
+            &unwrapped_token_program,
+            &args.wrapped_token_program,
+            &unwrapped_mint,
+            &escrow_account,
+            &get_canonical_pointer_address(&unwrapped_mint),
+            &get_scoped_canonical_pointer_address(&unwrapped_mint, &args.wrapped_token_program),
+            &get_wrap_limits_address(&unwrapped_mint),
+            &get_allowlist_address(&unwrapped_mint),
+            &allowlist_entry,
+            &get_protocol_fee_config_address(),
+            &get_protocol_fee_override_address(&unwrapped_mint),
+            &protocol_fee_vault,
+            &transfer_authority_signer.pubkey(),
+            args.amount,
+        ));
+    } else {
+        instructions.push(wrap(
+            &spl_token_wrap::id(),
+            &recipient_token_account,
+            &wrapped_mint_address,
+            &wrapped_mint_authority,
+            &unwrapped_token_program,
+            &args.wrapped_token_program,
+            &args.unwrapped_token_account,
+            &unwrapped_mint,
+            &escrow_account,
+            &get_canonical_pointer_address(&unwrapped_mint),
+            &get_scoped_canonical_pointer_address(&unwrapped_mint, &args.wrapped_token_program),
+            &get_wrap_limits_address(&unwrapped_mint),
+            &get_allowlist_address(&unwrapped_mint),
+            &allowlist_entry,
+            &get_protocol_fee_config_address(),
+            &get_protocol_fee_override_address(&unwrapped_mint),
+            &protocol_fee_vault,
+            &transfer_authority_signer.pubkey(),
+            wrap_stats_account.as_ref(),
+            &multisig_pubkeys.iter().collect::<Vec<&Pubkey>>(),
+            args.amount,
+        ));
+    }
 
     let blockhash = if let Some(hash) = args.blockhash {
         hash
@@ -303,7 +479,7 @@ pub async fn command_wrap(
         }
     }
 
-    let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
     transaction.partial_sign(&signers, blockhash);
 
     if !args.sign_only {
@@ -325,6 +501,7 @@ pub async fn command_wrap(
         unwrapped_token_account: args.unwrapped_token_account,
         recipient_token_account,
         escrow_account,
+        wrap_stats_account,
         amount: args.amount,
         signatures: transaction.signatures,
         sign_only_data,
@@ -332,3 +509,34 @@ pub async fn command_wrap(
 
     Ok(format_output(config, output))
 }
+
+/// Checks that the recipient is owned by the given owner and can receive
+/// confidential deposits
+async fn check_confidential_recipient(
+    config: &Config,
+    recipient_token_account: &Pubkey,
+    owner: &Pubkey,
+) -> Result<(), Error> {
+    let account = config
+        .rpc_client
+        .get_account(recipient_token_account)
+        .await
+        .map_err(|_| format!("Recipient token account {recipient_token_account} not found"))?;
+    let state = PodStateWithExtensions::<PodAccount>::unpack(&account.data)?;
+    if state.base.owner != *owner {
+        return Err(format!(
+            "Recipient token account {recipient_token_account} must be owned by the fee payer {owner}"
+        )
+        .into());
+    }
+    state
+        .get_extension::<ConfidentialTransferAccount>()
+        .and_then(|extension| extension.valid_as_destination())
+        .map_err(|_| {
+            format!(
+                "Recipient token account {recipient_token_account} is not configured for \
+                 confidential transfers"
+            )
+        })?;
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use {
+    crate::{common::parse_pubkey, config::Config, output::format_output, CommandResult},
+    clap::Args,
+    serde_derive::{Deserialize, Serialize},
+    serde_with::{serde_as, DisplayFromStr},
+    solana_cli_output::{display::writeln_name_value, QuietDisplay, VerboseDisplay},
+    solana_pubkey::Pubkey,
+    spl_token_wrap::{
+        get_wrap_limits_address,
+        state::{VersionedState, WrapLimits},
+    },
+    std::fmt::{Display, Formatter},
+};
+
+#[derive(Clone, Debug, Args)]
+pub struct WrapLimitsArgs {
+    /// The address of the unwrapped mint
+    #[clap(value_parser = parse_pubkey)]
+    pub unwrapped_mint: Pubkey,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WrapLimitsOutput {
+    #[serde_as(as = "DisplayFromStr")]
+    pub unwrapped_mint: Pubkey,
+    #[serde_as(as = "DisplayFromStr")]
+    pub wrap_limits_address: Pubkey,
+    pub initialized: bool,
+    pub unwrap_only: bool,
+    pub max_wrapped_supply: Option<u64>,
+    pub max_wrapped_per_epoch: Option<u64>,
+    pub epoch: u64,
+    pub wrapped_in_epoch: u64,
+    pub total_wrapped: u64,
+}
+
+impl Display for WrapLimitsOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let format_cap = |cap: Option<u64>| cap.map_or("none".to_string(), |cap| cap.to_string());
+
+        writeln_name_value(f, "Unwrapped mint:", &self.unwrapped_mint.to_string())?;
+        writeln_name_value(
+            f,
+            "Wrap limits address:",
+            &self.wrap_limits_address.to_string(),
+        )?;
+        if !self.initialized {
+            writeln!(f, "No wrap limits set")?;
+            return Ok(());
+        }
+        writeln_name_value(f, "Unwrap only:", &self.unwrap_only.to_string())?;
+        writeln_name_value(
+            f,
+            "Max total wrapped:",
+            &format_cap(self.max_wrapped_supply),
+        )?;
+        writeln_name_value(
+            f,
+            "Max wrapped per epoch:",
+            &format_cap(self.max_wrapped_per_epoch),
+        )?;
+        writeln_name_value(
+            f,
+            "Wrapped in epoch:",
+            &format!("{} (epoch {})", self.wrapped_in_epoch, self.epoch),
+        )?;
+        writeln_name_value(f, "Total wrapped:", &self.total_wrapped.to_string())?;
+        Ok(())
+    }
+}
+
+impl QuietDisplay for WrapLimitsOutput {
+    fn write_str(&self, _: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        Ok(())
+    }
+}
+impl VerboseDisplay for WrapLimitsOutput {}
+
+pub async fn command_wrap_limits(config: &Config, args: WrapLimitsArgs) -> CommandResult {
+    let wrap_limits_address = get_wrap_limits_address(&args.unwrapped_mint);
+    let account = config
+        .rpc_client
+        .get_account_with_commitment(&wrap_limits_address, config.rpc_client.commitment())
+        .await?
+        .value;
+
+    let limits = account
+        .map(|account| WrapLimits::unpack_versioned(&account.data))
+        .transpose()?;
+
+    let mut output = WrapLimitsOutput {
+        unwrapped_mint: args.unwrapped_mint,
+        wrap_limits_address,
+        initialized: limits.is_some(),
+        unwrap_only: false,
+        max_wrapped_supply: None,
+        max_wrapped_per_epoch: None,
+        epoch: 0,
+        wrapped_in_epoch: 0,
+        total_wrapped: 0,
+    };
+    if let Some(limits) = limits {
+        let cap = |cap: u64| (cap != 0).then_some(cap);
+        output.unwrap_only = limits.is_unwrap_only();
+        output.max_wrapped_supply = cap(limits.max_wrapped_supply.into());
+        output.max_wrapped_per_epoch = cap(limits.max_wrapped_per_epoch.into());
+        output.epoch = limits.epoch.into();
+        output.wrapped_in_epoch = limits.wrapped_in_epoch.into();
+        output.total_wrapped = limits.total_wrapped.into();
+    }
+
+    Ok(format_output(config, output))
+}
@@ -1,10 +1,17 @@
 pub mod helpers;
+pub mod test_allowlist;
+pub mod test_canonical_pointer;
 pub mod test_close_stuck_escrow;
 pub mod test_confidential_transfers;
 pub mod test_create_escrow_account;
 pub mod test_create_mint;
+pub mod test_flash_mint;
+pub mod test_list_wrapped_mints;
 pub mod test_pdas;
+pub mod test_protocol_fee;
+pub mod test_stats;
 pub mod test_sync_metadata_to_spl_token;
 pub mod test_sync_metadata_to_token2022;
 pub mod test_unwrap;
 pub mod test_wrap;
+pub mod test_wrap_limits;
@@ -0,0 +1,140 @@
+use {
+    crate::common::helpers::{
+        create_associated_token_account, create_token_account, create_unwrapped_mint,
+        execute_create_mint, mint_to, TestEnv, TOKEN_WRAP_CLI_BIN,
+    },
+    serde_json::Value,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    spl_token_wrap::{
+        get_allowlist_entry_address, get_wrapped_mint_address, get_wrapped_mint_authority,
+        state::{AllowlistEntry, VersionedState},
+    },
+    std::process::Command,
+};
+
+pub async fn test_allowlist(env: &TestEnv) {
+    let unwrapped_token_program = spl_token::id();
+    let wrapped_token_program = spl_token_2022_interface::id();
+    let unwrapped_mint = create_unwrapped_mint(env, &unwrapped_token_program).await;
+    execute_create_mint(env, &unwrapped_mint, &wrapped_token_program).await;
+
+    let unwrapped_token_account = create_token_account(
+        env,
+        &unwrapped_token_program,
+        &unwrapped_mint,
+        &env.payer.pubkey(),
+    )
+    .await;
+    mint_to(
+        env,
+        &unwrapped_token_program,
+        &unwrapped_mint,
+        &unwrapped_token_account,
+        100,
+    )
+    .await;
+
+    let wrapped_mint = get_wrapped_mint_address(&unwrapped_mint, &wrapped_token_program);
+    let recipient_account = create_associated_token_account(
+        env,
+        &wrapped_token_program,
+        &wrapped_mint,
+        &env.payer.pubkey(),
+    )
+    .await;
+    create_associated_token_account(
+        env,
+        &unwrapped_token_program,
+        &unwrapped_mint,
+        &get_wrapped_mint_authority(&wrapped_mint),
+    )
+    .await;
+
+    let wrap = |amount: u64| {
+        Command::new(TOKEN_WRAP_CLI_BIN)
+            .args([
+                "wrap".to_string(),
+                "-C".to_string(),
+                env.config_file_path.clone(),
+                unwrapped_token_account.to_string(),
+                wrapped_token_program.to_string(),
+                amount.to_string(),
+            ])
+            .status()
+            .unwrap()
+            .success()
+    };
+    let unwrap = |amount: u64| {
+        Command::new(TOKEN_WRAP_CLI_BIN)
+            .args([
+                "unwrap".to_string(),
+                "-C".to_string(),
+                env.config_file_path.clone(),
+                recipient_account.to_string(),
+                unwrapped_token_account.to_string(),
+                amount.to_string(),
+            ])
+            .status()
+            .unwrap()
+            .success()
+    };
+    let run = |command: &str, args: &[String]| -> Value {
+        let output = Command::new(TOKEN_WRAP_CLI_BIN)
+            .args([
+                command,
+                "-C",
+                &env.config_file_path,
+                &unwrapped_mint.to_string(),
+                "--output",
+                "json",
+            ])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        serde_json::from_slice(&output.stdout).unwrap()
+    };
+
+    // The payer is the unwrapped mint authority
+    let json_result = run("set-allowlist-mode", &[]);
+    assert!(json_result["enabled"].as_bool().unwrap());
+    assert!(!wrap(10));
+
+    // Allow the payer along with enough other wallets to span transactions
+    let mut wallets = (0..10)
+        .map(|_| Pubkey::new_unique().to_string())
+        .collect::<Vec<_>>();
+    wallets.push(env.payer.pubkey().to_string());
+    let json_result = run("update-allowlist", &wallets);
+    assert!(json_result["allowed"].as_bool().unwrap());
+    assert_eq!(json_result["wallets"].as_array().unwrap().len(), 11);
+    assert_eq!(json_result["signatures"].as_array().unwrap().len(), 2);
+
+    let entry = env
+        .rpc_client
+        .get_account(&get_allowlist_entry_address(
+            &unwrapped_mint,
+            &env.payer.pubkey(),
+        ))
+        .await
+        .unwrap();
+    assert!(AllowlistEntry::unpack_versioned(&entry.data)
+        .unwrap()
+        .is_allowed());
+
+    assert!(wrap(10));
+    assert!(unwrap(5));
+
+    // Removed wallets can no longer unwrap
+    run(
+        "update-allowlist",
+        &[env.payer.pubkey().to_string(), "--remove".to_string()],
+    );
+    assert!(!unwrap(5));
+
+    // Disabling the allowlist lets any wallet through
+    let json_result = run("set-allowlist-mode", &["--disable".to_string()]);
+    assert!(!json_result["enabled"].as_bool().unwrap());
+    assert!(unwrap(5));
+}
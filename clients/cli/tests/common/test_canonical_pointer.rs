@@ -0,0 +1,142 @@
+use {
+    crate::common::helpers::{create_unwrapped_mint, TestEnv, TOKEN_WRAP_CLI_BIN},
+    serde_json::Value,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    solana_system_interface::instruction::transfer,
+    solana_transaction::Transaction,
+    spl_token_wrap::{
+        get_canonical_pointer_address, get_pending_canonical_pointer_address,
+        get_scoped_canonical_pointer_address,
+        instruction::{propose_canonical_pointer, set_canonical_pointer},
+        state::{CanonicalDeploymentPointer, PendingCanonicalPointer, VersionedState},
+    },
+    std::process::Command,
+};
+
+fn run_canonical_pointer(env: &TestEnv, unwrapped_mint: &Pubkey, extra_args: &[&str]) -> Value {
+    let output = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args([
+            "canonical-pointer",
+            "-C",
+            &env.config_file_path,
+            &unwrapped_mint.to_string(),
+            "--output",
+            "json",
+        ])
+        .args(extra_args)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+pub async fn test_canonical_pointer_with_pending_change(env: &TestEnv) {
+    let unwrapped_mint = create_unwrapped_mint(env, &spl_token::id()).await;
+    let pointer_address = get_canonical_pointer_address(&unwrapped_mint);
+    let pending_address = get_pending_canonical_pointer_address(&unwrapped_mint);
+
+    let json_result = run_canonical_pointer(env, &unwrapped_mint, &[]);
+    assert_eq!(
+        json_result["canonicalPointerAddress"].as_str().unwrap(),
+        pointer_address.to_string(),
+    );
+    assert!(json_result["canonicalPointer"].is_null());
+    assert!(json_result["pendingCanonicalPointer"].is_null());
+    assert!(json_result["scoped"].is_null());
+
+    // Set a timelocked pointer, then propose a change to it
+    let current_program_id = Pubkey::new_unique();
+    let proposed_program_id = Pubkey::new_unique();
+    let change_delay = 3_600;
+    let pointer_rent = env
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(CanonicalDeploymentPointer::LEN)
+        .await
+        .unwrap();
+    let pending_rent = env
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(PendingCanonicalPointer::LEN)
+        .await
+        .unwrap();
+
+    let blockhash = env.rpc_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            transfer(&env.payer.pubkey(), &pointer_address, pointer_rent),
+            set_canonical_pointer(
+                &spl_token_wrap::id(),
+                &env.payer.pubkey(),
+                &pointer_address,
+                &unwrapped_mint,
+                None,
+                None,
+                &current_program_id,
+                true,
+                change_delay,
+                None,
+                None,
+                &[],
+            ),
+            transfer(&env.payer.pubkey(), &pending_address, pending_rent),
+            propose_canonical_pointer(
+                &spl_token_wrap::id(),
+                &env.payer.pubkey(),
+                &pointer_address,
+                &pending_address,
+                &unwrapped_mint,
+                None,
+                None,
+                &proposed_program_id,
+                false,
+                0,
+                None,
+                None,
+                &[],
+            ),
+        ],
+        Some(&env.payer.pubkey()),
+        &[env.payer.insecure_clone()],
+        blockhash,
+    );
+    env.rpc_client
+        .send_and_confirm_transaction(&transaction)
+        .await
+        .unwrap();
+
+    let json_result = run_canonical_pointer(env, &unwrapped_mint, &[]);
+    let pointer = &json_result["canonicalPointer"];
+    assert_eq!(
+        pointer["programId"].as_str().unwrap(),
+        current_program_id.to_string()
+    );
+    assert!(pointer["enforceOnWrap"].as_bool().unwrap());
+    assert_eq!(pointer["changeDelay"].as_u64().unwrap(), change_delay);
+
+    let pending = &json_result["pendingCanonicalPointer"];
+    assert_eq!(
+        pending["programId"].as_str().unwrap(),
+        proposed_program_id.to_string()
+    );
+    assert!(!pending["enforceOnWrap"].as_bool().unwrap());
+    assert_eq!(
+        pending["activationTimestamp"].as_i64().unwrap(),
+        pending["proposedTimestamp"].as_i64().unwrap() + change_delay as i64,
+    );
+
+    // The scoped pointer is unset, so the unscoped pointer above applies
+    let wrapped_token_program = spl_token_2022_interface::id().to_string();
+    let json_result = run_canonical_pointer(
+        env,
+        &unwrapped_mint,
+        &["--wrapped-token-program", &wrapped_token_program],
+    );
+    let scoped = &json_result["scoped"];
+    assert_eq!(
+        scoped["canonicalPointerAddress"].as_str().unwrap(),
+        get_scoped_canonical_pointer_address(&unwrapped_mint, &spl_token_2022_interface::id())
+            .to_string(),
+    );
+    assert!(scoped["canonicalPointer"].is_null());
+    assert!(scoped["pendingCanonicalPointer"].is_null());
+}
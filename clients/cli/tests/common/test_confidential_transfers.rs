@@ -1,17 +1,91 @@
 use {
-    crate::common::helpers::{create_unwrapped_mint, execute_create_mint, TestEnv},
+    crate::common::helpers::{
+        create_associated_token_account, create_token_account, create_unwrapped_mint,
+        execute_create_mint, extract_signers, mint_to, TestEnv, TOKEN_WRAP_CLI_BIN,
+    },
     solana_nullable::MaybeNull,
     solana_pubkey::Pubkey,
-    solana_zk_sdk_pod::encryption::elgamal::PodElGamalPubkey,
+    solana_signer::Signer,
+    solana_transaction::Transaction,
+    solana_zk_sdk::{
+        encryption::{
+            auth_encryption::{AeCiphertext, AeKey},
+            elgamal::ElGamalKeypair,
+            pod::auth_encryption::PodAeCiphertext as SdkPodAeCiphertext,
+        },
+        zk_elgamal_proof_program::proof_data::PubkeyValidityProofData as SdkPubkeyValidityProofData,
+    },
+    solana_zk_sdk_pod::encryption::{auth_encryption::PodAeCiphertext, elgamal::PodElGamalPubkey},
     spl_token_2022_interface::{
         extension::{
-            confidential_transfer::ConfidentialTransferMint, BaseStateWithExtensions,
-            PodStateWithExtensions,
+            confidential_transfer::{
+                instruction::{apply_pending_balance, configure_account, PubkeyValidityProofData},
+                ConfidentialTransferAccount, ConfidentialTransferMint,
+                DEFAULT_MAXIMUM_PENDING_BALANCE_CREDIT_COUNTER,
+            },
+            BaseStateWithExtensions, ExtensionType, PodStateWithExtensions,
         },
-        pod::PodMint,
+        instruction::reallocate,
+        pod::{PodAccount, PodMint},
     },
+    spl_token_confidential_transfer_proof_extraction::instruction::ProofLocation,
+    spl_token_wrap::{get_wrapped_mint_address, get_wrapped_mint_authority},
+    std::{num::NonZeroI8, process::Command},
 };
 
+fn encrypt_balance(aes_key: &AeKey, amount: u64) -> PodAeCiphertext {
+    bytemuck::cast(SdkPodAeCiphertext::from(aes_key.encrypt(amount)))
+}
+
+/// Configures a wrapped token account of the payer for confidential
+/// transfers, with keys derived from the payer like the CLI derives them
+async fn configure_confidential_account(
+    env: &TestEnv,
+    token_account: &Pubkey,
+    mint: &Pubkey,
+) -> (ElGamalKeypair, AeKey) {
+    let seed = token_account.to_bytes();
+    let elgamal_keypair = ElGamalKeypair::new_from_signer(env.payer.as_ref(), &seed).unwrap();
+    let aes_key = AeKey::new_from_signer(env.payer.as_ref(), &seed).unwrap();
+    let proof_data: PubkeyValidityProofData =
+        bytemuck::cast(SdkPubkeyValidityProofData::new(&elgamal_keypair).unwrap());
+
+    let mut instructions = vec![reallocate(
+        &spl_token_2022_interface::id(),
+        token_account,
+        &env.payer.pubkey(),
+        &env.payer.pubkey(),
+        &[],
+        &[ExtensionType::ConfidentialTransferAccount],
+    )
+    .unwrap()];
+    instructions.extend(
+        configure_account(
+            &spl_token_2022_interface::id(),
+            token_account,
+            mint,
+            &encrypt_balance(&aes_key, 0),
+            DEFAULT_MAXIMUM_PENDING_BALANCE_CREDIT_COUNTER,
+            &env.payer.pubkey(),
+            &[],
+            ProofLocation::InstructionOffset(NonZeroI8::new(1).unwrap(), &proof_data),
+        )
+        .unwrap(),
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&env.payer.pubkey()),
+        &[&env.payer],
+        env.rpc_client.get_latest_blockhash().await.unwrap(),
+    );
+    env.rpc_client
+        .send_and_confirm_transaction(&tx)
+        .await
+        .unwrap();
+
+    (elgamal_keypair, aes_key)
+}
+
 pub async fn test_confidential_transfer_with_wrap_and_deposit(env: &TestEnv) {
     let unwrapped_token_program = spl_token_2022_interface::id();
     let wrapped_token_program = spl_token_2022_interface::id();
@@ -40,3 +114,235 @@ pub async fn test_confidential_transfer_with_wrap_and_deposit(env: &TestEnv) {
         MaybeNull::<PodElGamalPubkey>::default()
     );
 }
+
+pub async fn test_wrap_confidential(env: &TestEnv) {
+    let unwrapped_token_program = spl_token::id();
+    let wrapped_token_program = spl_token_2022_interface::id();
+    let unwrapped_mint = create_unwrapped_mint(env, &unwrapped_token_program).await;
+    execute_create_mint(env, &unwrapped_mint, &wrapped_token_program).await;
+
+    let unwrapped_token_account = create_token_account(
+        env,
+        &unwrapped_token_program,
+        &unwrapped_mint,
+        &env.payer.pubkey(),
+    )
+    .await;
+    let starting_amount = 100;
+    mint_to(
+        env,
+        &unwrapped_token_program,
+        &unwrapped_mint,
+        &unwrapped_token_account,
+        starting_amount,
+    )
+    .await;
+
+    // The recipient is not configured for confidential transfers
+    let wrapped_mint = get_wrapped_mint_address(&unwrapped_mint, &wrapped_token_program);
+    let recipient_account = create_associated_token_account(
+        env,
+        &wrapped_token_program,
+        &wrapped_mint,
+        &env.payer.pubkey(),
+    )
+    .await;
+    create_associated_token_account(
+        env,
+        &unwrapped_token_program,
+        &unwrapped_mint,
+        &get_wrapped_mint_authority(&wrapped_mint),
+    )
+    .await;
+
+    let wrap_amount = 50;
+    let wrap_args = vec![
+        "wrap".to_string(),
+        "-C".to_string(),
+        env.config_file_path.clone(),
+        unwrapped_token_account.to_string(),
+        wrapped_token_program.to_string(),
+        wrap_amount.to_string(),
+        "--confidential".to_string(),
+    ];
+
+    let output = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args(&wrap_args)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("is not configured for confidential transfers"));
+
+    // Nothing was wrapped
+    let recipient_data = env
+        .rpc_client
+        .get_account_data(&recipient_account)
+        .await
+        .unwrap();
+    let recipient_state = PodStateWithExtensions::<PodAccount>::unpack(&recipient_data).unwrap();
+    assert_eq!(u64::from(recipient_state.base.amount), 0);
+
+    // Signing only skips the recipient check
+    let blockhash = env.rpc_client.get_latest_blockhash().await.unwrap();
+    let output = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args(&wrap_args)
+        .args([
+            "--blockhash".to_string(),
+            blockhash.to_string(),
+            "--sign-only".to_string(),
+            "--output".to_string(),
+            "json".to_string(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(extract_signers(&output.stdout).len(), 1);
+
+    // Confidential wraps need a Token-2022 wrapped mint
+    let status = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args(vec![
+            "wrap".to_string(),
+            "-C".to_string(),
+            env.config_file_path.clone(),
+            unwrapped_token_account.to_string(),
+            spl_token::id().to_string(),
+            wrap_amount.to_string(),
+            "--confidential".to_string(),
+        ])
+        .status()
+        .unwrap();
+    assert!(!status.success());
+}
+
+pub async fn test_unwrap_confidential(env: &TestEnv) {
+    let unwrapped_token_program = spl_token::id();
+    let wrapped_token_program = spl_token_2022_interface::id();
+    let unwrapped_mint = create_unwrapped_mint(env, &unwrapped_token_program).await;
+    execute_create_mint(env, &unwrapped_mint, &wrapped_token_program).await;
+    let wrapped_mint = get_wrapped_mint_address(&unwrapped_mint, &wrapped_token_program);
+
+    let unwrapped_token_account = create_token_account(
+        env,
+        &unwrapped_token_program,
+        &unwrapped_mint,
+        &env.payer.pubkey(),
+    )
+    .await;
+    mint_to(
+        env,
+        &unwrapped_token_program,
+        &unwrapped_mint,
+        &unwrapped_token_account,
+        100,
+    )
+    .await;
+    create_associated_token_account(
+        env,
+        &unwrapped_token_program,
+        &unwrapped_mint,
+        &get_wrapped_mint_authority(&wrapped_mint),
+    )
+    .await;
+
+    let wrapped_token_account = create_associated_token_account(
+        env,
+        &wrapped_token_program,
+        &wrapped_mint,
+        &env.payer.pubkey(),
+    )
+    .await;
+    let (_, aes_key) =
+        configure_confidential_account(env, &wrapped_token_account, &wrapped_mint).await;
+
+    // Wrap into the pending balance and apply it
+    let wrap_amount = 50;
+    let status = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args([
+            "wrap".to_string(),
+            "-C".to_string(),
+            env.config_file_path.clone(),
+            unwrapped_token_account.to_string(),
+            wrapped_token_program.to_string(),
+            wrap_amount.to_string(),
+            "--confidential".to_string(),
+        ])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let tx = Transaction::new_signed_with_payer(
+        &[apply_pending_balance(
+            &wrapped_token_program,
+            &wrapped_token_account,
+            1,
+            &encrypt_balance(&aes_key, wrap_amount),
+            &env.payer.pubkey(),
+            &[],
+        )
+        .unwrap()],
+        Some(&env.payer.pubkey()),
+        &[&env.payer],
+        env.rpc_client.get_latest_blockhash().await.unwrap(),
+    );
+    env.rpc_client
+        .send_and_confirm_transaction(&tx)
+        .await
+        .unwrap();
+
+    // Unwrap part of the available balance
+    let recipient = create_token_account(
+        env,
+        &unwrapped_token_program,
+        &unwrapped_mint,
+        &env.payer.pubkey(),
+    )
+    .await;
+    let unwrap_amount = 20;
+    let unwrap_args = vec![
+        "unwrap".to_string(),
+        "-C".to_string(),
+        env.config_file_path.clone(),
+        wrapped_token_account.to_string(),
+        recipient.to_string(),
+        unwrap_amount.to_string(),
+        "--confidential".to_string(),
+    ];
+    let status = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args(&unwrap_args)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let recipient_data = env.rpc_client.get_account_data(&recipient).await.unwrap();
+    let recipient_state = PodStateWithExtensions::<PodAccount>::unpack(&recipient_data).unwrap();
+    assert_eq!(u64::from(recipient_state.base.amount), unwrap_amount);
+
+    let wrapped_data = env
+        .rpc_client
+        .get_account_data(&wrapped_token_account)
+        .await
+        .unwrap();
+    let wrapped_state = PodStateWithExtensions::<PodAccount>::unpack(&wrapped_data).unwrap();
+    assert_eq!(u64::from(wrapped_state.base.amount), 0);
+    let confidential_account = wrapped_state
+        .get_extension::<ConfidentialTransferAccount>()
+        .unwrap();
+    let decryptable_balance: AeCiphertext =
+        bytemuck::cast::<_, SdkPodAeCiphertext>(confidential_account.decryptable_available_balance)
+            .try_into()
+            .unwrap();
+    assert_eq!(
+        aes_key.decrypt(&decryptable_balance),
+        Some(wrap_amount - unwrap_amount)
+    );
+
+    // Unwrapping more than the available balance fails before sending
+    let output = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args(&unwrap_args[..5])
+        .args(["100".to_string(), "--confidential".to_string()])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("is less than 100"));
+}
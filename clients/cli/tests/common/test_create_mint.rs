@@ -1,19 +1,28 @@
 use {
-    crate::common::helpers::{create_unwrapped_mint, execute_create_mint, TestEnv},
+    crate::common::helpers::{
+        create_unwrapped_mint, execute_create_mint, TestEnv, TOKEN_WRAP_CLI_BIN,
+    },
+    solana_keypair::Keypair,
     solana_program_pack::Pack,
     solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    solana_system_interface::instruction::create_account,
+    solana_transaction::Transaction,
     spl_token::{self, state::Mint as SplTokenMint},
     spl_token_2022_interface::{
         extension::{
             confidential_transfer::ConfidentialTransferMint, metadata_pointer::MetadataPointer,
-            BaseStateWithExtensions, PodStateWithExtensions,
+            mint_close_authority::MintCloseAuthority, BaseStateWithExtensions, ExtensionType,
+            PodStateWithExtensions,
         },
+        instruction::{initialize_mint2, initialize_permanent_delegate},
         pod::PodMint,
     },
     spl_token_wrap::{
         self, get_wrapped_mint_address, get_wrapped_mint_authority,
         get_wrapped_mint_backpointer_address, state::Backpointer,
     },
+    std::process::Command,
 };
 
 pub async fn test_create_mint(env: &TestEnv) {
@@ -53,11 +62,13 @@ pub async fn test_create_mint(env: &TestEnv) {
     );
 
     // Verify backpointer data
-    let backpointer = *bytemuck::from_bytes::<Backpointer>(&backpointer_account.data);
+    let backpointer = Backpointer::unpack(&backpointer_account.data).unwrap();
     assert_eq!(backpointer.unwrapped_mint, unwrapped_mint);
+    assert_eq!(backpointer.unwrapped_token_program, unwrapped_token_program);
+    assert_eq!(backpointer.wrapped_token_program, wrapped_token_program);
 
     // Verify extension state
-    assert_eq!(wrapped_mint_state.get_extension_types().unwrap().len(), 2);
+    assert_eq!(wrapped_mint_state.get_extension_types().unwrap().len(), 3);
 
     assert!(wrapped_mint_state
         .get_extension::<ConfidentialTransferMint>()
@@ -76,4 +87,97 @@ pub async fn test_create_mint(env: &TestEnv) {
         Option::<Pubkey>::from(pointer_ext.metadata_address).unwrap(),
         wrapped_mint_address
     );
+
+    // Verify MintCloseAuthority content
+    let close_authority_ext = wrapped_mint_state
+        .get_extension::<MintCloseAuthority>()
+        .unwrap();
+    assert_eq!(
+        Option::<Pubkey>::from(close_authority_ext.close_authority).unwrap(),
+        expected_mint_authority
+    );
+}
+
+pub async fn test_create_mint_risky_extension_requires_acknowledgement(env: &TestEnv) {
+    let unwrapped_token_program = spl_token_2022_interface::id();
+    let wrapped_token_program = spl_token_2022_interface::id();
+    let unwrapped_mint_keypair = Keypair::new();
+    let unwrapped_mint = unwrapped_mint_keypair.pubkey();
+
+    // Create an unwrapped mint with a permanent delegate
+    let space =
+        ExtensionType::try_calculate_account_len::<PodMint>(&[ExtensionType::PermanentDelegate])
+            .unwrap();
+    let rent = env
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(space)
+        .await
+        .unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            create_account(
+                &env.payer.pubkey(),
+                &unwrapped_mint,
+                rent,
+                space as u64,
+                &unwrapped_token_program,
+            ),
+            initialize_permanent_delegate(
+                &unwrapped_token_program,
+                &unwrapped_mint,
+                &env.payer.pubkey(),
+            )
+            .unwrap(),
+            initialize_mint2(
+                &unwrapped_token_program,
+                &unwrapped_mint,
+                &env.payer.pubkey(),
+                None,
+                0,
+            )
+            .unwrap(),
+        ],
+        Some(&env.payer.pubkey()),
+        &[&env.payer, &unwrapped_mint_keypair],
+        env.rpc_client.get_latest_blockhash().await.unwrap(),
+    );
+    env.rpc_client
+        .send_and_confirm_transaction(&tx)
+        .await
+        .unwrap();
+
+    let create_mint_args = [
+        "create-mint".to_string(),
+        "-C".to_string(),
+        env.config_file_path.clone(),
+        unwrapped_mint.to_string(),
+        wrapped_token_program.to_string(),
+    ];
+
+    // Fails without acknowledgement
+    let output = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args(&create_mint_args)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("PermanentDelegate"));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("--acknowledge-risky-extensions"));
+
+    // Succeeds once acknowledged
+    let status = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args(&create_mint_args)
+        .arg("--acknowledge-risky-extensions")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let wrapped_mint_address = get_wrapped_mint_address(&unwrapped_mint, &wrapped_token_program);
+    let wrapped_mint_account = env
+        .rpc_client
+        .get_account(&wrapped_mint_address)
+        .await
+        .unwrap();
+    assert_eq!(wrapped_mint_account.owner, wrapped_token_program);
 }
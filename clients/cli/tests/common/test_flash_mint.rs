@@ -0,0 +1,55 @@
+use {
+    crate::common::helpers::{
+        create_unwrapped_mint, execute_create_mint, TestEnv, TOKEN_WRAP_CLI_BIN,
+    },
+    spl_token_wrap::{
+        get_flash_mint_guard_address, get_wrapped_mint_address,
+        state::{FlashMintGuard, VersionedState},
+    },
+    std::process::Command,
+};
+
+pub async fn test_set_flash_mint_fee(env: &TestEnv) {
+    let unwrapped_token_program = spl_token::id();
+    let wrapped_token_program = spl_token_2022_interface::id();
+    let unwrapped_mint = create_unwrapped_mint(env, &unwrapped_token_program).await;
+    execute_create_mint(env, &unwrapped_mint, &wrapped_token_program).await;
+
+    let status = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args([
+            "set-flash-mint-fee",
+            "-C",
+            &env.config_file_path,
+            &unwrapped_mint.to_string(),
+            &wrapped_token_program.to_string(),
+            "30",
+        ])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let wrapped_mint = get_wrapped_mint_address(&unwrapped_mint, &wrapped_token_program);
+    let guard_account = env
+        .rpc_client
+        .get_account(&get_flash_mint_guard_address(&wrapped_mint))
+        .await
+        .unwrap();
+    assert_eq!(guard_account.owner, spl_token_wrap::id());
+    let guard = FlashMintGuard::unpack_versioned(&guard_account.data).unwrap();
+    assert_eq!(u16::from(guard.fee_basis_points), 30);
+    assert!(!guard.is_active());
+
+    // Fees above 100% are rejected
+    let status = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args([
+            "set-flash-mint-fee",
+            "-C",
+            &env.config_file_path,
+            &unwrapped_mint.to_string(),
+            &wrapped_token_program.to_string(),
+            "10001",
+        ])
+        .status()
+        .unwrap();
+    assert!(!status.success());
+}
@@ -0,0 +1,74 @@
+use {
+    crate::common::helpers::{
+        create_unwrapped_mint, execute_create_mint, TestEnv, TOKEN_WRAP_CLI_BIN,
+    },
+    serde_json::Value,
+    spl_token_wrap::get_wrapped_mint_address,
+    std::process::Command,
+};
+
+fn run_list_wrapped_mints(env: &TestEnv, extra_args: &[&str]) -> Value {
+    let output = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args([
+            "list-wrapped-mints",
+            "-C",
+            &env.config_file_path,
+            "--output",
+            "json",
+        ])
+        .args(extra_args)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn wrapped_mints(json_result: &Value) -> Vec<String> {
+    json_result["wrappedMints"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["wrappedMint"].as_str().unwrap().to_string())
+        .collect()
+}
+
+pub async fn test_list_wrapped_mints(env: &TestEnv) {
+    let unwrapped_mint = create_unwrapped_mint(env, &spl_token_2022_interface::id()).await;
+    execute_create_mint(env, &unwrapped_mint, &spl_token::id()).await;
+    execute_create_mint(env, &unwrapped_mint, &spl_token_2022_interface::id()).await;
+
+    let spl_token_wrapped_mint = get_wrapped_mint_address(&unwrapped_mint, &spl_token::id());
+    let token_2022_wrapped_mint =
+        get_wrapped_mint_address(&unwrapped_mint, &spl_token_2022_interface::id());
+
+    // Variants of the unwrapped mint, read from its index
+    let json_result =
+        run_list_wrapped_mints(env, &["--unwrapped-mint", &unwrapped_mint.to_string()]);
+    assert_eq!(
+        wrapped_mints(&json_result),
+        vec![
+            spl_token_wrapped_mint.to_string(),
+            token_2022_wrapped_mint.to_string()
+        ]
+    );
+    assert_eq!(
+        json_result["wrappedMints"][1]["wrappedTokenProgram"]
+            .as_str()
+            .unwrap(),
+        spl_token_2022_interface::id().to_string()
+    );
+
+    // Every page of the registry. Other tests share the validator, so only
+    // check that both wrapped mints are included.
+    let json_result = run_list_wrapped_mints(env, &[]);
+    let total = json_result["totalWrappedMints"].as_u64().unwrap();
+    assert!(total >= 2);
+    let listed = wrapped_mints(&json_result);
+    assert_eq!(listed.len() as u64, total);
+    assert!(listed.contains(&spl_token_wrapped_mint.to_string()));
+    assert!(listed.contains(&token_2022_wrapped_mint.to_string()));
+
+    // A single page
+    let json_result = run_list_wrapped_mints(env, &["--page", "0"]);
+    assert!(!wrapped_mints(&json_result).is_empty());
+}
@@ -63,4 +63,7 @@ pub async fn test_pdas(env: &TestEnv) {
         json_result["wrappedBackpointerAddress"].as_str().unwrap(),
         expected_backpointer.to_string(),
     );
+
+    // The mint has not been wrapped yet
+    assert!(json_result["backpointer"].is_null());
 }
@@ -0,0 +1,43 @@
+use {
+    crate::common::helpers::{TestEnv, TOKEN_WRAP_CLI_BIN},
+    solana_pubkey::Pubkey,
+    spl_token_wrap::get_protocol_fee_config_address,
+    std::process::Command,
+};
+
+pub async fn test_set_protocol_fee_config_requires_upgrade_authority(env: &TestEnv) {
+    // The test validator deploys the program without a usable upgrade
+    // authority, so the fee payer cannot set the protocol fee
+    let output = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args([
+            "set-protocol-fee-config".to_string(),
+            "-C".to_string(),
+            env.config_file_path.clone(),
+            Pubkey::new_unique().to_string(),
+            "--wrap-fee-basis-points".to_string(),
+            "100".to_string(),
+        ])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+
+    let config_account = env
+        .rpc_client
+        .get_account(&get_protocol_fee_config_address())
+        .await;
+    assert!(config_account.is_err());
+
+    // Fees above 100% are rejected before sending
+    let output = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args([
+            "set-protocol-fee-config".to_string(),
+            "-C".to_string(),
+            env.config_file_path.clone(),
+            Pubkey::new_unique().to_string(),
+            "--unwrap-fee-basis-points".to_string(),
+            "10001".to_string(),
+        ])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
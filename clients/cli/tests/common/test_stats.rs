@@ -0,0 +1,122 @@
+use {
+    crate::common::helpers::{
+        create_associated_token_account, create_token_account, create_unwrapped_mint,
+        execute_create_mint, mint_to, TestEnv, TOKEN_WRAP_CLI_BIN,
+    },
+    serde_json::Value,
+    solana_signer::Signer,
+    spl_token_wrap::{
+        get_wrap_stats_address, get_wrapped_mint_address, get_wrapped_mint_authority,
+    },
+    std::process::Command,
+};
+
+fn run_stats(env: &TestEnv, wrapped_mint: &str) -> Value {
+    let output = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args([
+            "stats",
+            "-C",
+            &env.config_file_path,
+            wrapped_mint,
+            "--output",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+pub async fn test_stats_recorded_on_opt_in(env: &TestEnv) {
+    let unwrapped_token_program = spl_token::id();
+    let wrapped_token_program = spl_token_2022_interface::id();
+    let unwrapped_mint = create_unwrapped_mint(env, &unwrapped_token_program).await;
+    execute_create_mint(env, &unwrapped_mint, &wrapped_token_program).await;
+
+    let unwrapped_token_account = create_token_account(
+        env,
+        &unwrapped_token_program,
+        &unwrapped_mint,
+        &env.payer.pubkey(),
+    )
+    .await;
+    mint_to(
+        env,
+        &unwrapped_token_program,
+        &unwrapped_mint,
+        &unwrapped_token_account,
+        100,
+    )
+    .await;
+
+    let wrapped_mint = get_wrapped_mint_address(&unwrapped_mint, &wrapped_token_program);
+    let recipient_account = create_associated_token_account(
+        env,
+        &wrapped_token_program,
+        &wrapped_mint,
+        &env.payer.pubkey(),
+    )
+    .await;
+    create_associated_token_account(
+        env,
+        &unwrapped_token_program,
+        &unwrapped_mint,
+        &get_wrapped_mint_authority(&wrapped_mint),
+    )
+    .await;
+
+    // Nothing recorded yet
+    let json_result = run_stats(env, &wrapped_mint.to_string());
+    assert_eq!(
+        json_result["statsAddress"].as_str().unwrap(),
+        get_wrap_stats_address(&wrapped_mint).to_string()
+    );
+    assert!(!json_result["initialized"].as_bool().unwrap());
+
+    // Wraps that don't opt in leave the statistics untouched
+    let wrap = |amount: u64, record_stats: bool| {
+        let mut command = Command::new(TOKEN_WRAP_CLI_BIN);
+        command.args([
+            "wrap".to_string(),
+            "-C".to_string(),
+            env.config_file_path.clone(),
+            unwrapped_token_account.to_string(),
+            wrapped_token_program.to_string(),
+            amount.to_string(),
+        ]);
+        if record_stats {
+            command.arg("--record-stats");
+        }
+        assert!(command.status().unwrap().success());
+    };
+    wrap(10, false);
+    assert!(!run_stats(env, &wrapped_mint.to_string())["initialized"]
+        .as_bool()
+        .unwrap());
+
+    wrap(30, true);
+    wrap(20, true);
+
+    let status = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args([
+            "unwrap".to_string(),
+            "-C".to_string(),
+            env.config_file_path.clone(),
+            recipient_account.to_string(),
+            unwrapped_token_account.to_string(),
+            "15".to_string(),
+            "--record-stats".to_string(),
+        ])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let json_result = run_stats(env, &wrapped_mint.to_string());
+    assert!(json_result["initialized"].as_bool().unwrap());
+    assert_eq!(json_result["totalWrapped"].as_str().unwrap(), "50");
+    assert_eq!(json_result["totalUnwrapped"].as_str().unwrap(), "15");
+    assert_eq!(json_result["totalWrapFees"].as_str().unwrap(), "0");
+    assert_eq!(json_result["wrapCount"].as_u64().unwrap(), 2);
+    assert_eq!(json_result["unwrapCount"].as_u64().unwrap(), 1);
+    assert!(json_result["lastActivitySlot"].as_u64().unwrap() > 0);
+}
@@ -171,6 +171,97 @@ pub async fn test_wrap_single_signer_with_optional_flags(env: &TestEnv) {
     .await;
 }
 
+pub async fn test_wrap_sol(env: &TestEnv) {
+    let unwrapped_token_program = spl_token::id();
+    let wrapped_token_program = spl_token_2022_interface::id();
+    let native_mint = spl_token::native_mint::id();
+    execute_create_mint(env, &native_mint, &wrapped_token_program).await;
+
+    let wrapped_mint = get_wrapped_mint_address(&native_mint, &wrapped_token_program);
+    let recipient_account = create_associated_token_account(
+        env,
+        &wrapped_token_program,
+        &wrapped_mint,
+        &env.payer.pubkey(),
+    )
+    .await;
+
+    let wrapped_mint_authority = get_wrapped_mint_authority(&wrapped_mint);
+    let escrow_account = create_associated_token_account(
+        env,
+        &unwrapped_token_program,
+        &native_mint,
+        &wrapped_mint_authority,
+    )
+    .await;
+
+    let payer_lamports = env
+        .rpc_client
+        .get_balance(&env.payer.pubkey())
+        .await
+        .unwrap();
+
+    // Wrap lamports of the fee payer
+    let wrap_amount = 1_000_000;
+
+    let status = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args(vec![
+            "wrap".to_string(),
+            "-C".to_string(),
+            env.config_file_path.clone(),
+            env.payer.pubkey().to_string(),
+            wrapped_token_program.to_string(),
+            wrap_amount.to_string(),
+            "--sol".to_string(),
+        ])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    // The escrow holds the lamports as native tokens
+    let escrow_account_data = env
+        .rpc_client
+        .get_account_data(&escrow_account)
+        .await
+        .unwrap();
+    let escrow_token_state =
+        PodStateWithExtensions::<PodAccount>::unpack(&escrow_account_data).unwrap();
+    assert_eq!(u64::from(escrow_token_state.base.amount), wrap_amount);
+
+    // Recipient should have wrapped tokens
+    let wrapped_account_data = env
+        .rpc_client
+        .get_account_data(&recipient_account)
+        .await
+        .unwrap();
+    let wrapped_token_state =
+        PodStateWithExtensions::<PodAccount>::unpack(&wrapped_account_data).unwrap();
+    assert_eq!(u64::from(wrapped_token_state.base.amount), wrap_amount);
+
+    // Payer paid the lamports and the transaction fee
+    let payer_lamports_after = env
+        .rpc_client
+        .get_balance(&env.payer.pubkey())
+        .await
+        .unwrap();
+    assert!(payer_lamports_after <= payer_lamports - wrap_amount);
+
+    // Wrapping lamports from an account other than the transfer authority fails
+    let status = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args(vec![
+            "wrap".to_string(),
+            "-C".to_string(),
+            env.config_file_path.clone(),
+            Pubkey::new_unique().to_string(),
+            wrapped_token_program.to_string(),
+            wrap_amount.to_string(),
+            "--sol".to_string(),
+        ])
+        .status()
+        .unwrap();
+    assert!(!status.success());
+}
+
 async fn assert_result(
     env: &TestEnv,
     unwrapped_token_account: &Pubkey,
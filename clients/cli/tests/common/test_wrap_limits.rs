@@ -0,0 +1,135 @@
+use {
+    crate::common::helpers::{
+        create_associated_token_account, create_token_account, create_unwrapped_mint,
+        execute_create_mint, mint_to, TestEnv, TOKEN_WRAP_CLI_BIN,
+    },
+    serde_json::Value,
+    solana_signer::Signer,
+    spl_token_wrap::{
+        get_wrap_limits_address, get_wrapped_mint_address, get_wrapped_mint_authority,
+    },
+    std::process::Command,
+};
+
+fn run_wrap_limits(env: &TestEnv, unwrapped_mint: &str) -> Value {
+    let output = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args([
+            "wrap-limits",
+            "-C",
+            &env.config_file_path,
+            unwrapped_mint,
+            "--output",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+pub async fn test_wrap_limits(env: &TestEnv) {
+    let unwrapped_token_program = spl_token::id();
+    let wrapped_token_program = spl_token_2022_interface::id();
+    let unwrapped_mint = create_unwrapped_mint(env, &unwrapped_token_program).await;
+    execute_create_mint(env, &unwrapped_mint, &wrapped_token_program).await;
+
+    let unwrapped_token_account = create_token_account(
+        env,
+        &unwrapped_token_program,
+        &unwrapped_mint,
+        &env.payer.pubkey(),
+    )
+    .await;
+    mint_to(
+        env,
+        &unwrapped_token_program,
+        &unwrapped_mint,
+        &unwrapped_token_account,
+        100,
+    )
+    .await;
+
+    let wrapped_mint = get_wrapped_mint_address(&unwrapped_mint, &wrapped_token_program);
+    let recipient_account = create_associated_token_account(
+        env,
+        &wrapped_token_program,
+        &wrapped_mint,
+        &env.payer.pubkey(),
+    )
+    .await;
+    create_associated_token_account(
+        env,
+        &unwrapped_token_program,
+        &unwrapped_mint,
+        &get_wrapped_mint_authority(&wrapped_mint),
+    )
+    .await;
+
+    // No limits set yet
+    let json_result = run_wrap_limits(env, &unwrapped_mint.to_string());
+    assert_eq!(
+        json_result["wrapLimitsAddress"].as_str().unwrap(),
+        get_wrap_limits_address(&unwrapped_mint).to_string()
+    );
+    assert!(!json_result["initialized"].as_bool().unwrap());
+
+    let wrap = |amount: u64| {
+        Command::new(TOKEN_WRAP_CLI_BIN)
+            .args([
+                "wrap".to_string(),
+                "-C".to_string(),
+                env.config_file_path.clone(),
+                unwrapped_token_account.to_string(),
+                wrapped_token_program.to_string(),
+                amount.to_string(),
+            ])
+            .status()
+            .unwrap()
+            .success()
+    };
+    let set_wrap_limits = |args: &[&str]| {
+        let status = Command::new(TOKEN_WRAP_CLI_BIN)
+            .args([
+                "set-wrap-limits",
+                "-C",
+                &env.config_file_path,
+                &unwrapped_mint.to_string(),
+            ])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    };
+
+    // The payer is the unwrapped mint authority
+    set_wrap_limits(&["--max-wrapped-supply", "50"]);
+    assert!(wrap(40));
+    assert!(!wrap(20));
+
+    let json_result = run_wrap_limits(env, &unwrapped_mint.to_string());
+    assert!(json_result["initialized"].as_bool().unwrap());
+    assert!(!json_result["unwrapOnly"].as_bool().unwrap());
+    assert_eq!(json_result["maxWrappedSupply"].as_u64().unwrap(), 50);
+    assert!(json_result["maxWrappedPerEpoch"].is_null());
+    assert_eq!(json_result["wrappedInEpoch"].as_u64().unwrap(), 40);
+
+    // Unwrap-only mode stops wraps but not unwraps
+    set_wrap_limits(&["--unwrap-only"]);
+    assert!(!wrap(1));
+    let status = Command::new(TOKEN_WRAP_CLI_BIN)
+        .args([
+            "unwrap".to_string(),
+            "-C".to_string(),
+            env.config_file_path.clone(),
+            recipient_account.to_string(),
+            unwrapped_token_account.to_string(),
+            "15".to_string(),
+        ])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let json_result = run_wrap_limits(env, &unwrapped_mint.to_string());
+    assert!(json_result["unwrapOnly"].as_bool().unwrap());
+    assert!(json_result["maxWrappedSupply"].is_null());
+}
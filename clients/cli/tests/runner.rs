@@ -1,9 +1,10 @@
 use {
     crate::common::{
-        helpers::setup_test_env, test_close_stuck_escrow::*, test_confidential_transfers::*,
-        test_create_escrow_account::*, test_create_mint::*, test_pdas::*,
-        test_sync_metadata_to_spl_token::*, test_sync_metadata_to_token2022::*, test_unwrap::*,
-        test_wrap::*,
+        helpers::setup_test_env, test_allowlist::*, test_canonical_pointer::*,
+        test_close_stuck_escrow::*, test_confidential_transfers::*, test_create_escrow_account::*,
+        test_create_mint::*, test_flash_mint::*, test_list_wrapped_mints::*, test_pdas::*,
+        test_protocol_fee::*, test_stats::*, test_sync_metadata_to_spl_token::*,
+        test_sync_metadata_to_token2022::*, test_unwrap::*, test_wrap::*, test_wrap_limits::*,
     },
     libtest_mimic::{Arguments, Trial},
 };
@@ -29,10 +30,13 @@ async fn main() {
 
     // maybe come up with a way to do this through a some macro tag on the function?
     let tests = vec![
+        async_trial!(test_canonical_pointer_with_pending_change, env),
         async_trial!(test_only_token_2022_allowed, env),
         async_trial!(test_create_mint_close_stuck_escrow_fails, env),
         async_trial!(test_successful_close, env),
         async_trial!(test_confidential_transfer_with_wrap_and_deposit, env),
+        async_trial!(test_wrap_confidential, env),
+        async_trial!(test_unwrap_confidential, env),
         async_trial!(test_create_ata_escrow_account_for_spl_token_mint, env),
         async_trial!(test_create_ata_escrow_account_for_token2022_mint, env),
         async_trial!(test_create_escrow_account_with_signer, env),
@@ -41,6 +45,12 @@ async fn main() {
         async_trial!(test_create_escrow_account_with_wrong_mint_owner, env),
         async_trial!(test_create_escrow_account_with_wrong_account_type, env),
         async_trial!(test_create_mint, env),
+        async_trial!(
+            test_create_mint_risky_extension_requires_acknowledgement,
+            env
+        ),
+        async_trial!(test_list_wrapped_mints, env),
+        async_trial!(test_stats_recorded_on_opt_in, env),
         async_trial!(test_pdas, env),
         async_trial!(test_sync_metadata_from_token2022_to_spl_token, env),
         async_trial!(test_sync_metadata_from_spl_token_to_spl_token, env),
@@ -58,6 +68,11 @@ async fn main() {
         async_trial!(test_wrap_single_signer_with_defaults, env),
         async_trial!(test_wrap_single_signer_with_optional_flags, env),
         async_trial!(test_wrap_with_multisig, env),
+        async_trial!(test_wrap_sol, env),
+        async_trial!(test_wrap_limits, env),
+        async_trial!(test_allowlist, env),
+        async_trial!(test_set_protocol_fee_config_requires_upgrade_authority, env),
+        async_trial!(test_set_flash_mint_fee, env),
     ];
 
     libtest_mimic::run(&args, tests).exit();
@@ -3,13 +3,22 @@ import {
     fetchEncodedAccount,
     GetAccountInfoApi,
     GetMinimumBalanceForRentExemptionApi,
+    getU64Decoder,
     Instruction,
     KeyPairSigner,
     Rpc,
 } from '@solana/kit';
 import { getMintSize, TOKEN_2022_PROGRAM_ADDRESS, extension } from '@solana-program/token-2022';
 import { getTransferSolInstruction } from '@solana-program/system';
-import { findBackpointerPda, findWrappedMintPda, getBackpointerSize, getCreateMintInstruction } from './generated';
+import {
+    findBackpointerPda,
+    findRegistryPagePda,
+    findRegistryPda,
+    findWrappedMintIndexPda,
+    findWrappedMintPda,
+    getBackpointerSize,
+    getCreateMintInstruction,
+} from './generated';
 
 export interface CreateMintArgs {
     rpc: Rpc<GetAccountInfoApi & GetMinimumBalanceForRentExemptionApi>;
@@ -17,6 +26,7 @@ export interface CreateMintArgs {
     wrappedTokenProgram: Address;
     payer: KeyPairSigner;
     idempotent?: boolean;
+    acknowledgeRiskyExtensions?: boolean;
 }
 
 export interface CreateMintResult {
@@ -24,6 +34,7 @@ export interface CreateMintResult {
     backpointer: Address;
     fundedWrappedMintLamports: bigint;
     fundedBackpointerLamports: bigint;
+    fundedRegistryLamports: bigint;
     ixs: Instruction[];
 }
 
@@ -43,12 +54,20 @@ const DEFAULT_EXTENSIONS = [
     }),
 ];
 
+// Layout of the wrapped mint registry accounts, each behind a 2-byte header
+const ACCOUNT_HEADER_SIZE = 2;
+const REGISTRY_SIZE = ACCOUNT_HEADER_SIZE + 8;
+const REGISTRY_ENTRY_SIZE = 96;
+const WRAPPED_MINT_INDEX_ENTRY_SIZE = 64;
+const REGISTRY_ENTRIES_PER_PAGE = 100n;
+
 export async function createMint({
     rpc,
     unwrappedMint,
     wrappedTokenProgram,
     payer,
     idempotent = false,
+    acknowledgeRiskyExtensions = false,
 }: CreateMintArgs): Promise<CreateMintResult> {
     const [wrappedMint] = await findWrappedMintPda({
         unwrappedMint,
@@ -104,6 +123,51 @@ export async function createMint({
         );
     }
 
+    // Fund the growth of the registry accounts, unless the wrapped mint already exists
+    const [registry] = await findRegistryPda();
+    const [wrappedMintIndex] = await findWrappedMintIndexPda({ unwrappedMint });
+    const registryAccount = await fetchEncodedAccount(rpc, registry);
+    const entryCount = registryAccount.exists
+        ? getU64Decoder().decode(registryAccount.data, ACCOUNT_HEADER_SIZE)
+        : 0n;
+    const [registryPage] = await findRegistryPagePda({ pageIndex: entryCount / REGISTRY_ENTRIES_PER_PAGE });
+
+    let fundedRegistryLamports = 0n;
+
+    const wrappedMintCreated = wrappedMintAccount.exists && wrappedMintAccount.data.length > 0;
+    if (!wrappedMintCreated) {
+        const [registryPageAccount, wrappedMintIndexAccount] = await Promise.all([
+            fetchEncodedAccount(rpc, registryPage),
+            fetchEncodedAccount(rpc, wrappedMintIndex),
+        ]);
+        // Lists start with their header and grow by one entry
+        const listSize = (account: typeof registryPageAccount, entrySize: number) =>
+            (account.exists && account.data.length > 0 ? account.data.length : ACCOUNT_HEADER_SIZE) + entrySize;
+        const grownAccounts = [
+            { address: registry, account: registryAccount, size: REGISTRY_SIZE },
+            { address: registryPage, account: registryPageAccount, size: listSize(registryPageAccount, REGISTRY_ENTRY_SIZE) },
+            {
+                address: wrappedMintIndex,
+                account: wrappedMintIndexAccount,
+                size: listSize(wrappedMintIndexAccount, WRAPPED_MINT_INDEX_ENTRY_SIZE),
+            },
+        ];
+        for (const { address, account, size } of grownAccounts) {
+            const rent = await rpc.getMinimumBalanceForRentExemption(BigInt(size)).send();
+            const lamports = account.exists ? account.lamports : 0n;
+            if (lamports < rent) {
+                fundedRegistryLamports += rent - lamports;
+                instructions.push(
+                    getTransferSolInstruction({
+                        source: payer,
+                        destination: address,
+                        amount: rent - lamports,
+                    }),
+                );
+            }
+        }
+    }
+
     // Add create_mint instruction
     instructions.push(
         getCreateMintInstruction({
@@ -111,7 +175,11 @@ export async function createMint({
             backpointer,
             unwrappedMint,
             wrappedTokenProgram,
+            registry,
+            registryPage,
+            wrappedMintIndex,
             idempotent,
+            acknowledgeRiskyExtensions,
         }),
     );
 
@@ -121,5 +189,6 @@ export async function createMint({
         ixs: instructions,
         fundedWrappedMintLamports,
         fundedBackpointerLamports,
+        fundedRegistryLamports,
     };
 }
@@ -87,6 +87,7 @@ async function main() {
     console.log('Backpointer:', createMintHelper.backpointer);
     console.log('Funded wrapped mint lamports:', createMintHelper.fundedWrappedMintLamports);
     console.log('Funded backpointer lamports:', createMintHelper.fundedBackpointerLamports);
+    console.log('Funded registry lamports:', createMintHelper.fundedRegistryLamports);
     console.log('Signature:', createMintSignature);
 
     // === Setup accounts needed for wrap ===
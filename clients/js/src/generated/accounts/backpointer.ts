@@ -13,10 +13,21 @@ import {
     decodeAccount,
     fetchEncodedAccount,
     fetchEncodedAccounts,
+    fixDecoderSize,
+    fixEncoderSize,
     getAddressDecoder,
     getAddressEncoder,
+    getArrayDecoder,
+    getArrayEncoder,
+    getBytesDecoder,
+    getBytesEncoder,
     getStructDecoder,
     getStructEncoder,
+    getU64Decoder,
+    getU64Encoder,
+    getU8Decoder,
+    getU8Encoder,
+    transformEncoder,
     type Account,
     type Address,
     type EncodedAccount,
@@ -27,22 +38,88 @@ import {
     type FixedSizeEncoder,
     type MaybeAccount,
     type MaybeEncodedAccount,
+    type ReadonlyUint8Array,
 } from '@solana/kit';
 import { BackpointerSeeds, findBackpointerPda } from '../pdas';
 
-/** Account to store the address of the unwrapped mint. */
-export type Backpointer = { unwrappedMint: Address };
+/**
+ * Account to store the address of the unwrapped mint and how the
+ * wrapped mint was created. Backpointers in the original 32-byte layout
+ * only store the unwrapped mint until migrated with `MigrateState`.
+ */
+export type Backpointer = {
+    discriminator: number;
+    version: number;
+    /** Address that the wrapped mint is wrapping */
+    unwrappedMint: Address;
+    /** Token program that owns the unwrapped mint */
+    unwrappedTokenProgram: Address;
+    /** Token program that owns the wrapped mint */
+    wrappedTokenProgram: Address;
+    /** ID of the mint customizer, zeroed if unknown */
+    customizerId: ReadonlyUint8Array;
+    /** Slot in which the wrapped mint was created, zero if unknown */
+    creationSlot: bigint;
+    wrappedMintBump: number;
+    wrappedMintAuthorityBump: number;
+    backpointerBump: number;
+    /** Program version as major, minor and patch, zeroed if unknown */
+    programVersion: Array<number>;
+};
 
-export type BackpointerArgs = Backpointer;
+export type BackpointerArgs = {
+    /** Address that the wrapped mint is wrapping */
+    unwrappedMint: Address;
+    /** Token program that owns the unwrapped mint */
+    unwrappedTokenProgram: Address;
+    /** Token program that owns the wrapped mint */
+    wrappedTokenProgram: Address;
+    /** ID of the mint customizer, zeroed if unknown */
+    customizerId: ReadonlyUint8Array;
+    /** Slot in which the wrapped mint was created, zero if unknown */
+    creationSlot: number | bigint;
+    wrappedMintBump: number;
+    wrappedMintAuthorityBump: number;
+    backpointerBump: number;
+    /** Program version as major, minor and patch, zeroed if unknown */
+    programVersion: Array<number>;
+};
 
 /** Gets the encoder for {@link BackpointerArgs} account data. */
 export function getBackpointerEncoder(): FixedSizeEncoder<BackpointerArgs> {
-    return getStructEncoder([['unwrappedMint', getAddressEncoder()]]);
+    return transformEncoder(
+        getStructEncoder([
+            ['discriminator', getU8Encoder()],
+            ['version', getU8Encoder()],
+            ['unwrappedMint', getAddressEncoder()],
+            ['unwrappedTokenProgram', getAddressEncoder()],
+            ['wrappedTokenProgram', getAddressEncoder()],
+            ['customizerId', fixEncoderSize(getBytesEncoder(), 16)],
+            ['creationSlot', getU64Encoder()],
+            ['wrappedMintBump', getU8Encoder()],
+            ['wrappedMintAuthorityBump', getU8Encoder()],
+            ['backpointerBump', getU8Encoder()],
+            ['programVersion', getArrayEncoder(getU8Encoder(), { size: 3 })],
+        ]),
+        value => ({ ...value, discriminator: 1, version: 1 }),
+    );
 }
 
 /** Gets the decoder for {@link Backpointer} account data. */
 export function getBackpointerDecoder(): FixedSizeDecoder<Backpointer> {
-    return getStructDecoder([['unwrappedMint', getAddressDecoder()]]);
+    return getStructDecoder([
+        ['discriminator', getU8Decoder()],
+        ['version', getU8Decoder()],
+        ['unwrappedMint', getAddressDecoder()],
+        ['unwrappedTokenProgram', getAddressDecoder()],
+        ['wrappedTokenProgram', getAddressDecoder()],
+        ['customizerId', fixDecoderSize(getBytesDecoder(), 16)],
+        ['creationSlot', getU64Decoder()],
+        ['wrappedMintBump', getU8Decoder()],
+        ['wrappedMintAuthorityBump', getU8Decoder()],
+        ['backpointerBump', getU8Decoder()],
+        ['programVersion', getArrayDecoder(getU8Decoder(), { size: 3 })],
+    ]);
 }
 
 /** Gets the codec for {@link Backpointer} account data. */
@@ -101,7 +178,7 @@ export async function fetchAllMaybeBackpointer(
 }
 
 export function getBackpointerSize(): number {
-    return 32;
+    return 128;
 }
 
 export async function fetchBackpointerFromSeeds(
@@ -45,6 +45,9 @@ export type CreateMintInstruction<
     TAccountUnwrappedMint extends string | AccountMeta<string> = string,
     TAccountSystemProgram extends string | AccountMeta<string> = '11111111111111111111111111111111',
     TAccountWrappedTokenProgram extends string | AccountMeta<string> = string,
+    TAccountRegistry extends string | AccountMeta<string> = string,
+    TAccountRegistryPage extends string | AccountMeta<string> = string,
+    TAccountWrappedMintIndex extends string | AccountMeta<string> = string,
     TRemainingAccounts extends readonly AccountMeta<string>[] = [],
 > = Instruction<TProgram> &
     InstructionWithData<ReadonlyUint8Array> &
@@ -57,6 +60,11 @@ export type CreateMintInstruction<
             TAccountWrappedTokenProgram extends string
                 ? ReadonlyAccount<TAccountWrappedTokenProgram>
                 : TAccountWrappedTokenProgram,
+            TAccountRegistry extends string ? WritableAccount<TAccountRegistry> : TAccountRegistry,
+            TAccountRegistryPage extends string ? WritableAccount<TAccountRegistryPage> : TAccountRegistryPage,
+            TAccountWrappedMintIndex extends string
+                ? WritableAccount<TAccountWrappedMintIndex>
+                : TAccountWrappedMintIndex,
             ...TRemainingAccounts,
         ]
     >;
@@ -65,11 +73,15 @@ export type CreateMintInstructionData = {
     discriminator: number;
     /** Whether the creation should fail if the wrapped mint already exists. */
     idempotent: boolean;
+    /** Whether to allow unwrapped mints with extensions that can put the escrow at risk. */
+    acknowledgeRiskyExtensions: boolean;
 };
 
 export type CreateMintInstructionDataArgs = {
     /** Whether the creation should fail if the wrapped mint already exists. */
     idempotent?: boolean;
+    /** Whether to allow unwrapped mints with extensions that can put the escrow at risk. */
+    acknowledgeRiskyExtensions?: boolean;
 };
 
 export function getCreateMintInstructionDataEncoder(): FixedSizeEncoder<CreateMintInstructionDataArgs> {
@@ -77,8 +89,14 @@ export function getCreateMintInstructionDataEncoder(): FixedSizeEncoder<CreateMi
         getStructEncoder([
             ['discriminator', getU8Encoder()],
             ['idempotent', getBooleanEncoder()],
+            ['acknowledgeRiskyExtensions', getBooleanEncoder()],
         ]),
-        value => ({ ...value, discriminator: CREATE_MINT_DISCRIMINATOR, idempotent: value.idempotent ?? false }),
+        value => ({
+            ...value,
+            discriminator: CREATE_MINT_DISCRIMINATOR,
+            idempotent: value.idempotent ?? false,
+            acknowledgeRiskyExtensions: value.acknowledgeRiskyExtensions ?? false,
+        }),
     );
 }
 
@@ -86,6 +104,7 @@ export function getCreateMintInstructionDataDecoder(): FixedSizeDecoder<CreateMi
     return getStructDecoder([
         ['discriminator', getU8Decoder()],
         ['idempotent', getBooleanDecoder()],
+        ['acknowledgeRiskyExtensions', getBooleanDecoder()],
     ]);
 }
 
@@ -102,6 +121,9 @@ export type CreateMintInput<
     TAccountUnwrappedMint extends string = string,
     TAccountSystemProgram extends string = string,
     TAccountWrappedTokenProgram extends string = string,
+    TAccountRegistry extends string = string,
+    TAccountRegistryPage extends string = string,
+    TAccountWrappedMintIndex extends string = string,
 > = {
     /**
      *  Unallocated wrapped mint account to create (PDA), address must be:
@@ -119,7 +141,23 @@ export type CreateMintInput<
     systemProgram?: Address<TAccountSystemProgram>;
     /** The token program used to create the wrapped mint */
     wrappedTokenProgram: Address<TAccountWrappedTokenProgram>;
+    /**
+     * Wrapped mint registry PDA, address must be:
+     * `get_wrapped_mint_registry_address()`
+     */
+    registry: Address<TAccountRegistry>;
+    /**
+     * Registry page the entry is appended to, address must be:
+     * `get_wrapped_mint_registry_page_address(page_index)`
+     */
+    registryPage: Address<TAccountRegistryPage>;
+    /**
+     * Wrapped mint index PDA of the unwrapped mint, address must be:
+     * `get_wrapped_mint_index_address(unwrapped_mint_address)`
+     */
+    wrappedMintIndex: Address<TAccountWrappedMintIndex>;
     idempotent?: CreateMintInstructionDataArgs['idempotent'];
+    acknowledgeRiskyExtensions?: CreateMintInstructionDataArgs['acknowledgeRiskyExtensions'];
 };
 
 export function getCreateMintInstruction<
@@ -128,6 +166,9 @@ export function getCreateMintInstruction<
     TAccountUnwrappedMint extends string,
     TAccountSystemProgram extends string,
     TAccountWrappedTokenProgram extends string,
+    TAccountRegistry extends string,
+    TAccountRegistryPage extends string,
+    TAccountWrappedMintIndex extends string,
     TProgramAddress extends Address = typeof TOKEN_WRAP_PROGRAM_ADDRESS,
 >(
     input: CreateMintInput<
@@ -135,7 +176,10 @@ export function getCreateMintInstruction<
         TAccountBackpointer,
         TAccountUnwrappedMint,
         TAccountSystemProgram,
-        TAccountWrappedTokenProgram
+        TAccountWrappedTokenProgram,
+        TAccountRegistry,
+        TAccountRegistryPage,
+        TAccountWrappedMintIndex
     >,
     config?: { programAddress?: TProgramAddress },
 ): CreateMintInstruction<
@@ -144,7 +188,10 @@ export function getCreateMintInstruction<
     TAccountBackpointer,
     TAccountUnwrappedMint,
     TAccountSystemProgram,
-    TAccountWrappedTokenProgram
+    TAccountWrappedTokenProgram,
+    TAccountRegistry,
+    TAccountRegistryPage,
+    TAccountWrappedMintIndex
 > {
     // Program address.
     const programAddress = config?.programAddress ?? TOKEN_WRAP_PROGRAM_ADDRESS;
@@ -156,6 +203,9 @@ export function getCreateMintInstruction<
         unwrappedMint: { value: input.unwrappedMint ?? null, isWritable: false },
         systemProgram: { value: input.systemProgram ?? null, isWritable: false },
         wrappedTokenProgram: { value: input.wrappedTokenProgram ?? null, isWritable: false },
+        registry: { value: input.registry ?? null, isWritable: true },
+        registryPage: { value: input.registryPage ?? null, isWritable: true },
+        wrappedMintIndex: { value: input.wrappedMintIndex ?? null, isWritable: true },
     };
     const accounts = originalAccounts as Record<keyof typeof originalAccounts, ResolvedInstructionAccount>;
 
@@ -176,6 +226,9 @@ export function getCreateMintInstruction<
             getAccountMeta('unwrappedMint', accounts.unwrappedMint),
             getAccountMeta('systemProgram', accounts.systemProgram),
             getAccountMeta('wrappedTokenProgram', accounts.wrappedTokenProgram),
+            getAccountMeta('registry', accounts.registry),
+            getAccountMeta('registryPage', accounts.registryPage),
+            getAccountMeta('wrappedMintIndex', accounts.wrappedMintIndex),
         ],
         data: getCreateMintInstructionDataEncoder().encode(args as CreateMintInstructionDataArgs),
         programAddress,
@@ -185,7 +238,10 @@ export function getCreateMintInstruction<
         TAccountBackpointer,
         TAccountUnwrappedMint,
         TAccountSystemProgram,
-        TAccountWrappedTokenProgram
+        TAccountWrappedTokenProgram,
+        TAccountRegistry,
+        TAccountRegistryPage,
+        TAccountWrappedMintIndex
     >);
 }
 
@@ -211,6 +267,21 @@ export type ParsedCreateMintInstruction<
         systemProgram?: TAccountMetas[3] | undefined;
         /** The token program used to create the wrapped mint */
         wrappedTokenProgram: TAccountMetas[4];
+        /**
+         * Wrapped mint registry PDA, address must be:
+         * `get_wrapped_mint_registry_address()`
+         */
+        registry: TAccountMetas[5];
+        /**
+         * Registry page the entry is appended to, address must be:
+         * `get_wrapped_mint_registry_page_address(page_index)`
+         */
+        registryPage: TAccountMetas[6];
+        /**
+         * Wrapped mint index PDA of the unwrapped mint, address must be:
+         * `get_wrapped_mint_index_address(unwrapped_mint_address)`
+         */
+        wrappedMintIndex: TAccountMetas[7];
     };
     data: CreateMintInstructionData;
 };
@@ -220,10 +291,10 @@ export function parseCreateMintInstruction<TProgram extends string, TAccountMeta
         InstructionWithAccounts<TAccountMetas> &
         InstructionWithData<ReadonlyUint8Array>,
 ): ParsedCreateMintInstruction<TProgram, TAccountMetas> {
-    if (instruction.accounts.length < 5) {
+    if (instruction.accounts.length < 8) {
         throw new SolanaError(SOLANA_ERROR__PROGRAM_CLIENTS__INSUFFICIENT_ACCOUNT_METAS, {
             actualAccountMetas: instruction.accounts.length,
-            expectedAccountMetas: 5,
+            expectedAccountMetas: 8,
         });
     }
     let accountIndex = 0;
@@ -244,6 +315,9 @@ export function parseCreateMintInstruction<TProgram extends string, TAccountMeta
             unwrappedMint: getNextAccount(),
             systemProgram: getNextOptionalAccount(),
             wrappedTokenProgram: getNextAccount(),
+            registry: getNextAccount(),
+            registryPage: getNextAccount(),
+            wrappedMintIndex: getNextAccount(),
         },
         data: getCreateMintInstructionDataDecoder().decode(instruction.data),
     };
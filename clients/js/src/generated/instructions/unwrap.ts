@@ -53,6 +53,13 @@ export type UnwrapInstruction<
     TAccountWrappedTokenAccount extends string | AccountMeta<string> = string,
     TAccountWrappedMint extends string | AccountMeta<string> = string,
     TAccountTransferAuthority extends string | AccountMeta<string> = string,
+    TAccountWrapLimits extends string | AccountMeta<string> = string,
+    TAccountAllowlist extends string | AccountMeta<string> = string,
+    TAccountAllowlistEntry extends string | AccountMeta<string> = string,
+    TAccountProtocolFeeConfig extends string | AccountMeta<string> = string,
+    TAccountProtocolFeeOverride extends string | AccountMeta<string> = string,
+    TAccountProtocolFeeVault extends string | AccountMeta<string> = string,
+    TAccountFlashMintGuard extends string | AccountMeta<string> = string,
     TRemainingAccounts extends readonly AccountMeta<string>[] = [],
 > = Instruction<TProgram> &
     InstructionWithData<ReadonlyUint8Array> &
@@ -79,6 +86,19 @@ export type UnwrapInstruction<
             TAccountTransferAuthority extends string
                 ? ReadonlyAccount<TAccountTransferAuthority>
                 : TAccountTransferAuthority,
+            TAccountWrapLimits extends string ? WritableAccount<TAccountWrapLimits> : TAccountWrapLimits,
+            TAccountAllowlist extends string ? ReadonlyAccount<TAccountAllowlist> : TAccountAllowlist,
+            TAccountAllowlistEntry extends string ? ReadonlyAccount<TAccountAllowlistEntry> : TAccountAllowlistEntry,
+            TAccountProtocolFeeConfig extends string
+                ? ReadonlyAccount<TAccountProtocolFeeConfig>
+                : TAccountProtocolFeeConfig,
+            TAccountProtocolFeeOverride extends string
+                ? ReadonlyAccount<TAccountProtocolFeeOverride>
+                : TAccountProtocolFeeOverride,
+            TAccountProtocolFeeVault extends string
+                ? WritableAccount<TAccountProtocolFeeVault>
+                : TAccountProtocolFeeVault,
+            TAccountFlashMintGuard extends string ? ReadonlyAccount<TAccountFlashMintGuard> : TAccountFlashMintGuard,
             ...TRemainingAccounts,
         ]
     >;
@@ -125,6 +145,13 @@ export type UnwrapInput<
     TAccountWrappedTokenAccount extends string = string,
     TAccountWrappedMint extends string = string,
     TAccountTransferAuthority extends string = string,
+    TAccountWrapLimits extends string = string,
+    TAccountAllowlist extends string = string,
+    TAccountAllowlistEntry extends string = string,
+    TAccountProtocolFeeConfig extends string = string,
+    TAccountProtocolFeeOverride extends string = string,
+    TAccountProtocolFeeVault extends string = string,
+    TAccountFlashMintGuard extends string = string,
 > = {
     /**
      * The escrow account holding the unwrapped tokens.
@@ -153,6 +180,42 @@ export type UnwrapInput<
     wrappedMint: Address<TAccountWrappedMint>;
     /** The authority to burn the wrapped tokens. */
     transferAuthority: Address<TAccountTransferAuthority> | TransactionSigner<TAccountTransferAuthority>;
+    /**
+     * Wrap limits PDA, address must be:
+     * `get_wrap_limits_address(unwrapped_mint_address)`. May be uninitialized.
+     * The unwrapped amount is subtracted from its total wrapped.
+     */
+    wrapLimits: Address<TAccountWrapLimits>;
+    /**
+     * Allowlist PDA, address must be: `get_allowlist_address(unwrapped_mint_address)`.
+     * May be uninitialized, in which case the allowlist mode is disabled.
+     */
+    allowlist: Address<TAccountAllowlist>;
+    /**
+     * Allowlist entry PDA of the owner of the wrapped token account, address must be:
+     * `get_allowlist_entry_address(unwrapped_mint_address, owner)`. May be uninitialized.
+     */
+    allowlistEntry: Address<TAccountAllowlistEntry>;
+    /**
+     * Protocol fee config PDA, address must be: `get_protocol_fee_config_address()`.
+     * May be uninitialized, in which case no protocol fee is charged.
+     */
+    protocolFeeConfig: Address<TAccountProtocolFeeConfig>;
+    /**
+     * Protocol fee override PDA, address must be:
+     * `get_protocol_fee_override_address(unwrapped_mint_address)`. May be uninitialized.
+     */
+    protocolFeeOverride: Address<TAccountProtocolFeeOverride>;
+    /**
+     * Protocol fee vault, a token account of the unwrapped mint owned by the fee destination
+     * of the protocol fee config. Only checked if a protocol fee is charged.
+     */
+    protocolFeeVault: Address<TAccountProtocolFeeVault>;
+    /**
+     * Flash mint guard PDA, address must be: `get_flash_mint_guard_address(wrapped_mint_address)`.
+     * May be uninitialized. Unwrapping is rejected while a flash mint is active.
+     */
+    flashMintGuard: Address<TAccountFlashMintGuard>;
     amount: UnwrapInstructionDataArgs['amount'];
     multiSigners?: Array<TransactionSigner>;
 };
@@ -167,6 +230,13 @@ export function getUnwrapInstruction<
     TAccountWrappedTokenAccount extends string,
     TAccountWrappedMint extends string,
     TAccountTransferAuthority extends string,
+    TAccountWrapLimits extends string,
+    TAccountAllowlist extends string,
+    TAccountAllowlistEntry extends string,
+    TAccountProtocolFeeConfig extends string,
+    TAccountProtocolFeeOverride extends string,
+    TAccountProtocolFeeVault extends string,
+    TAccountFlashMintGuard extends string,
     TProgramAddress extends Address = typeof TOKEN_WRAP_PROGRAM_ADDRESS,
 >(
     input: UnwrapInput<
@@ -178,7 +248,14 @@ export function getUnwrapInstruction<
         TAccountUnwrappedTokenProgram,
         TAccountWrappedTokenAccount,
         TAccountWrappedMint,
-        TAccountTransferAuthority
+        TAccountTransferAuthority,
+        TAccountWrapLimits,
+        TAccountAllowlist,
+        TAccountAllowlistEntry,
+        TAccountProtocolFeeConfig,
+        TAccountProtocolFeeOverride,
+        TAccountProtocolFeeVault,
+        TAccountFlashMintGuard
     >,
     config?: { programAddress?: TProgramAddress },
 ): UnwrapInstruction<
@@ -193,7 +270,14 @@ export function getUnwrapInstruction<
     TAccountWrappedMint,
     (typeof input)['transferAuthority'] extends TransactionSigner<TAccountTransferAuthority>
         ? ReadonlySignerAccount<TAccountTransferAuthority> & AccountSignerMeta<TAccountTransferAuthority>
-        : TAccountTransferAuthority
+        : TAccountTransferAuthority,
+    TAccountWrapLimits,
+    TAccountAllowlist,
+    TAccountAllowlistEntry,
+    TAccountProtocolFeeConfig,
+    TAccountProtocolFeeOverride,
+    TAccountProtocolFeeVault,
+    TAccountFlashMintGuard
 > {
     // Program address.
     const programAddress = config?.programAddress ?? TOKEN_WRAP_PROGRAM_ADDRESS;
@@ -209,6 +293,13 @@ export function getUnwrapInstruction<
         wrappedTokenAccount: { value: input.wrappedTokenAccount ?? null, isWritable: true },
         wrappedMint: { value: input.wrappedMint ?? null, isWritable: true },
         transferAuthority: { value: input.transferAuthority ?? null, isWritable: false },
+        wrapLimits: { value: input.wrapLimits ?? null, isWritable: true },
+        allowlist: { value: input.allowlist ?? null, isWritable: false },
+        allowlistEntry: { value: input.allowlistEntry ?? null, isWritable: false },
+        protocolFeeConfig: { value: input.protocolFeeConfig ?? null, isWritable: false },
+        protocolFeeOverride: { value: input.protocolFeeOverride ?? null, isWritable: false },
+        protocolFeeVault: { value: input.protocolFeeVault ?? null, isWritable: true },
+        flashMintGuard: { value: input.flashMintGuard ?? null, isWritable: false },
     };
     const accounts = originalAccounts as Record<keyof typeof originalAccounts, ResolvedInstructionAccount>;
 
@@ -234,6 +325,13 @@ export function getUnwrapInstruction<
             getAccountMeta('wrappedTokenAccount', accounts.wrappedTokenAccount),
             getAccountMeta('wrappedMint', accounts.wrappedMint),
             getAccountMeta('transferAuthority', accounts.transferAuthority),
+            getAccountMeta('wrapLimits', accounts.wrapLimits),
+            getAccountMeta('allowlist', accounts.allowlist),
+            getAccountMeta('allowlistEntry', accounts.allowlistEntry),
+            getAccountMeta('protocolFeeConfig', accounts.protocolFeeConfig),
+            getAccountMeta('protocolFeeOverride', accounts.protocolFeeOverride),
+            getAccountMeta('protocolFeeVault', accounts.protocolFeeVault),
+            getAccountMeta('flashMintGuard', accounts.flashMintGuard),
             ...remainingAccounts,
         ],
         data: getUnwrapInstructionDataEncoder().encode(args as UnwrapInstructionDataArgs),
@@ -250,7 +348,14 @@ export function getUnwrapInstruction<
         TAccountWrappedMint,
         (typeof input)['transferAuthority'] extends TransactionSigner<TAccountTransferAuthority>
             ? ReadonlySignerAccount<TAccountTransferAuthority> & AccountSignerMeta<TAccountTransferAuthority>
-            : TAccountTransferAuthority
+            : TAccountTransferAuthority,
+        TAccountWrapLimits,
+        TAccountAllowlist,
+        TAccountAllowlistEntry,
+        TAccountProtocolFeeConfig,
+        TAccountProtocolFeeOverride,
+        TAccountProtocolFeeVault,
+        TAccountFlashMintGuard
     >);
 }
 
@@ -287,6 +392,42 @@ export type ParsedUnwrapInstruction<
         wrappedMint: TAccountMetas[7];
         /** The authority to burn the wrapped tokens. */
         transferAuthority: TAccountMetas[8];
+        /**
+         * Wrap limits PDA, address must be:
+         * `get_wrap_limits_address(unwrapped_mint_address)`. May be uninitialized.
+         * The unwrapped amount is subtracted from its total wrapped.
+         */
+        wrapLimits: TAccountMetas[9];
+        /**
+         * Allowlist PDA, address must be: `get_allowlist_address(unwrapped_mint_address)`.
+         * May be uninitialized, in which case the allowlist mode is disabled.
+         */
+        allowlist: TAccountMetas[10];
+        /**
+         * Allowlist entry PDA of the owner of the wrapped token account, address must be:
+         * `get_allowlist_entry_address(unwrapped_mint_address, owner)`. May be uninitialized.
+         */
+        allowlistEntry: TAccountMetas[11];
+        /**
+         * Protocol fee config PDA, address must be: `get_protocol_fee_config_address()`.
+         * May be uninitialized, in which case no protocol fee is charged.
+         */
+        protocolFeeConfig: TAccountMetas[12];
+        /**
+         * Protocol fee override PDA, address must be:
+         * `get_protocol_fee_override_address(unwrapped_mint_address)`. May be uninitialized.
+         */
+        protocolFeeOverride: TAccountMetas[13];
+        /**
+         * Protocol fee vault, a token account of the unwrapped mint owned by the fee destination
+         * of the protocol fee config. Only checked if a protocol fee is charged.
+         */
+        protocolFeeVault: TAccountMetas[14];
+        /**
+         * Flash mint guard PDA, address must be: `get_flash_mint_guard_address(wrapped_mint_address)`.
+         * May be uninitialized. Unwrapping is rejected while a flash mint is active.
+         */
+        flashMintGuard: TAccountMetas[15];
     };
     data: UnwrapInstructionData;
 };
@@ -296,10 +437,10 @@ export function parseUnwrapInstruction<TProgram extends string, TAccountMetas ex
         InstructionWithAccounts<TAccountMetas> &
         InstructionWithData<ReadonlyUint8Array>,
 ): ParsedUnwrapInstruction<TProgram, TAccountMetas> {
-    if (instruction.accounts.length < 9) {
+    if (instruction.accounts.length < 16) {
         throw new SolanaError(SOLANA_ERROR__PROGRAM_CLIENTS__INSUFFICIENT_ACCOUNT_METAS, {
             actualAccountMetas: instruction.accounts.length,
-            expectedAccountMetas: 9,
+            expectedAccountMetas: 16,
         });
     }
     let accountIndex = 0;
@@ -320,6 +461,13 @@ export function parseUnwrapInstruction<TProgram extends string, TAccountMetas ex
             wrappedTokenAccount: getNextAccount(),
             wrappedMint: getNextAccount(),
             transferAuthority: getNextAccount(),
+            wrapLimits: getNextAccount(),
+            allowlist: getNextAccount(),
+            allowlistEntry: getNextAccount(),
+            protocolFeeConfig: getNextAccount(),
+            protocolFeeOverride: getNextAccount(),
+            protocolFeeVault: getNextAccount(),
+            flashMintGuard: getNextAccount(),
         },
         data: getUnwrapInstructionDataDecoder().decode(instruction.data),
     };
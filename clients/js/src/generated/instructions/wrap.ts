@@ -53,6 +53,14 @@ export type WrapInstruction<
     TAccountUnwrappedMint extends string | AccountMeta<string> = string,
     TAccountUnwrappedEscrow extends string | AccountMeta<string> = string,
     TAccountTransferAuthority extends string | AccountMeta<string> = string,
+    TAccountCanonicalPointer extends string | AccountMeta<string> = string,
+    TAccountScopedCanonicalPointer extends string | AccountMeta<string> = string,
+    TAccountWrapLimits extends string | AccountMeta<string> = string,
+    TAccountAllowlist extends string | AccountMeta<string> = string,
+    TAccountAllowlistEntry extends string | AccountMeta<string> = string,
+    TAccountProtocolFeeConfig extends string | AccountMeta<string> = string,
+    TAccountProtocolFeeOverride extends string | AccountMeta<string> = string,
+    TAccountProtocolFeeVault extends string | AccountMeta<string> = string,
     TRemainingAccounts extends readonly AccountMeta<string>[] = [],
 > = Instruction<TProgram> &
     InstructionWithData<ReadonlyUint8Array> &
@@ -79,6 +87,24 @@ export type WrapInstruction<
             TAccountTransferAuthority extends string
                 ? ReadonlyAccount<TAccountTransferAuthority>
                 : TAccountTransferAuthority,
+            TAccountCanonicalPointer extends string
+                ? ReadonlyAccount<TAccountCanonicalPointer>
+                : TAccountCanonicalPointer,
+            TAccountScopedCanonicalPointer extends string
+                ? ReadonlyAccount<TAccountScopedCanonicalPointer>
+                : TAccountScopedCanonicalPointer,
+            TAccountWrapLimits extends string ? WritableAccount<TAccountWrapLimits> : TAccountWrapLimits,
+            TAccountAllowlist extends string ? ReadonlyAccount<TAccountAllowlist> : TAccountAllowlist,
+            TAccountAllowlistEntry extends string ? ReadonlyAccount<TAccountAllowlistEntry> : TAccountAllowlistEntry,
+            TAccountProtocolFeeConfig extends string
+                ? ReadonlyAccount<TAccountProtocolFeeConfig>
+                : TAccountProtocolFeeConfig,
+            TAccountProtocolFeeOverride extends string
+                ? ReadonlyAccount<TAccountProtocolFeeOverride>
+                : TAccountProtocolFeeOverride,
+            TAccountProtocolFeeVault extends string
+                ? WritableAccount<TAccountProtocolFeeVault>
+                : TAccountProtocolFeeVault,
             ...TRemainingAccounts,
         ]
     >;
@@ -125,6 +151,14 @@ export type WrapInput<
     TAccountUnwrappedMint extends string = string,
     TAccountUnwrappedEscrow extends string = string,
     TAccountTransferAuthority extends string = string,
+    TAccountCanonicalPointer extends string = string,
+    TAccountScopedCanonicalPointer extends string = string,
+    TAccountWrapLimits extends string = string,
+    TAccountAllowlist extends string = string,
+    TAccountAllowlistEntry extends string = string,
+    TAccountProtocolFeeConfig extends string = string,
+    TAccountProtocolFeeOverride extends string = string,
+    TAccountProtocolFeeVault extends string = string,
 > = {
     /** The token account to receive the wrapped tokens. */
     recipientWrappedTokenAccount: Address<TAccountRecipientWrappedTokenAccount>;
@@ -153,6 +187,48 @@ export type WrapInput<
     unwrappedEscrow: Address<TAccountUnwrappedEscrow>;
     /** The authority to transfer the unwrapped tokens. */
     transferAuthority: Address<TAccountTransferAuthority> | TransactionSigner<TAccountTransferAuthority>;
+    /**
+     * Canonical pointer PDA, address must be:
+     * `get_canonical_pointer_address(unwrapped_mint_address)`. May be uninitialized.
+     */
+    canonicalPointer: Address<TAccountCanonicalPointer>;
+    /**
+     * Scoped canonical pointer PDA, address must be:
+     * `get_scoped_canonical_pointer_address(unwrapped_mint_address, wrapped_token_program_id)`.
+     * May be uninitialized, in which case the unscoped pointer applies.
+     */
+    scopedCanonicalPointer: Address<TAccountScopedCanonicalPointer>;
+    /**
+     * Wrap limits PDA, address must be:
+     * `get_wrap_limits_address(unwrapped_mint_address)`. May be uninitialized,
+     * in which case wrapping is not limited.
+     */
+    wrapLimits: Address<TAccountWrapLimits>;
+    /**
+     * Allowlist PDA, address must be: `get_allowlist_address(unwrapped_mint_address)`.
+     * May be uninitialized, in which case the allowlist mode is disabled.
+     */
+    allowlist: Address<TAccountAllowlist>;
+    /**
+     * Allowlist entry PDA of the owner of the recipient wrapped token account, address must be:
+     * `get_allowlist_entry_address(unwrapped_mint_address, owner)`. May be uninitialized.
+     */
+    allowlistEntry: Address<TAccountAllowlistEntry>;
+    /**
+     * Protocol fee config PDA, address must be: `get_protocol_fee_config_address()`.
+     * May be uninitialized, in which case no protocol fee is charged.
+     */
+    protocolFeeConfig: Address<TAccountProtocolFeeConfig>;
+    /**
+     * Protocol fee override PDA, address must be:
+     * `get_protocol_fee_override_address(unwrapped_mint_address)`. May be uninitialized.
+     */
+    protocolFeeOverride: Address<TAccountProtocolFeeOverride>;
+    /**
+     * Protocol fee vault, a token account of the unwrapped mint owned by the fee destination
+     * of the protocol fee config. Only checked if a protocol fee is charged.
+     */
+    protocolFeeVault: Address<TAccountProtocolFeeVault>;
     amount: WrapInstructionDataArgs['amount'];
     multiSigners?: Array<TransactionSigner>;
 };
@@ -167,6 +243,14 @@ export function getWrapInstruction<
     TAccountUnwrappedMint extends string,
     TAccountUnwrappedEscrow extends string,
     TAccountTransferAuthority extends string,
+    TAccountCanonicalPointer extends string,
+    TAccountScopedCanonicalPointer extends string,
+    TAccountWrapLimits extends string,
+    TAccountAllowlist extends string,
+    TAccountAllowlistEntry extends string,
+    TAccountProtocolFeeConfig extends string,
+    TAccountProtocolFeeOverride extends string,
+    TAccountProtocolFeeVault extends string,
     TProgramAddress extends Address = typeof TOKEN_WRAP_PROGRAM_ADDRESS,
 >(
     input: WrapInput<
@@ -178,7 +262,15 @@ export function getWrapInstruction<
         TAccountUnwrappedTokenAccount,
         TAccountUnwrappedMint,
         TAccountUnwrappedEscrow,
-        TAccountTransferAuthority
+        TAccountTransferAuthority,
+        TAccountCanonicalPointer,
+        TAccountScopedCanonicalPointer,
+        TAccountWrapLimits,
+        TAccountAllowlist,
+        TAccountAllowlistEntry,
+        TAccountProtocolFeeConfig,
+        TAccountProtocolFeeOverride,
+        TAccountProtocolFeeVault
     >,
     config?: { programAddress?: TProgramAddress },
 ): WrapInstruction<
@@ -193,7 +285,15 @@ export function getWrapInstruction<
     TAccountUnwrappedEscrow,
     (typeof input)['transferAuthority'] extends TransactionSigner<TAccountTransferAuthority>
         ? ReadonlySignerAccount<TAccountTransferAuthority> & AccountSignerMeta<TAccountTransferAuthority>
-        : TAccountTransferAuthority
+        : TAccountTransferAuthority,
+    TAccountCanonicalPointer,
+    TAccountScopedCanonicalPointer,
+    TAccountWrapLimits,
+    TAccountAllowlist,
+    TAccountAllowlistEntry,
+    TAccountProtocolFeeConfig,
+    TAccountProtocolFeeOverride,
+    TAccountProtocolFeeVault
 > {
     // Program address.
     const programAddress = config?.programAddress ?? TOKEN_WRAP_PROGRAM_ADDRESS;
@@ -209,6 +309,14 @@ export function getWrapInstruction<
         unwrappedMint: { value: input.unwrappedMint ?? null, isWritable: false },
         unwrappedEscrow: { value: input.unwrappedEscrow ?? null, isWritable: true },
         transferAuthority: { value: input.transferAuthority ?? null, isWritable: false },
+        canonicalPointer: { value: input.canonicalPointer ?? null, isWritable: false },
+        scopedCanonicalPointer: { value: input.scopedCanonicalPointer ?? null, isWritable: false },
+        wrapLimits: { value: input.wrapLimits ?? null, isWritable: true },
+        allowlist: { value: input.allowlist ?? null, isWritable: false },
+        allowlistEntry: { value: input.allowlistEntry ?? null, isWritable: false },
+        protocolFeeConfig: { value: input.protocolFeeConfig ?? null, isWritable: false },
+        protocolFeeOverride: { value: input.protocolFeeOverride ?? null, isWritable: false },
+        protocolFeeVault: { value: input.protocolFeeVault ?? null, isWritable: true },
     };
     const accounts = originalAccounts as Record<keyof typeof originalAccounts, ResolvedInstructionAccount>;
 
@@ -234,6 +342,14 @@ export function getWrapInstruction<
             getAccountMeta('unwrappedMint', accounts.unwrappedMint),
             getAccountMeta('unwrappedEscrow', accounts.unwrappedEscrow),
             getAccountMeta('transferAuthority', accounts.transferAuthority),
+            getAccountMeta('canonicalPointer', accounts.canonicalPointer),
+            getAccountMeta('scopedCanonicalPointer', accounts.scopedCanonicalPointer),
+            getAccountMeta('wrapLimits', accounts.wrapLimits),
+            getAccountMeta('allowlist', accounts.allowlist),
+            getAccountMeta('allowlistEntry', accounts.allowlistEntry),
+            getAccountMeta('protocolFeeConfig', accounts.protocolFeeConfig),
+            getAccountMeta('protocolFeeOverride', accounts.protocolFeeOverride),
+            getAccountMeta('protocolFeeVault', accounts.protocolFeeVault),
             ...remainingAccounts,
         ],
         data: getWrapInstructionDataEncoder().encode(args as WrapInstructionDataArgs),
@@ -250,7 +366,15 @@ export function getWrapInstruction<
         TAccountUnwrappedEscrow,
         (typeof input)['transferAuthority'] extends TransactionSigner<TAccountTransferAuthority>
             ? ReadonlySignerAccount<TAccountTransferAuthority> & AccountSignerMeta<TAccountTransferAuthority>
-            : TAccountTransferAuthority
+            : TAccountTransferAuthority,
+        TAccountCanonicalPointer,
+        TAccountScopedCanonicalPointer,
+        TAccountWrapLimits,
+        TAccountAllowlist,
+        TAccountAllowlistEntry,
+        TAccountProtocolFeeConfig,
+        TAccountProtocolFeeOverride,
+        TAccountProtocolFeeVault
     >);
 }
 
@@ -287,6 +411,48 @@ export type ParsedWrapInstruction<
         unwrappedEscrow: TAccountMetas[7];
         /** The authority to transfer the unwrapped tokens. */
         transferAuthority: TAccountMetas[8];
+        /**
+         * Canonical pointer PDA, address must be:
+         * `get_canonical_pointer_address(unwrapped_mint_address)`. May be uninitialized.
+         */
+        canonicalPointer: TAccountMetas[9];
+        /**
+         * Scoped canonical pointer PDA, address must be:
+         * `get_scoped_canonical_pointer_address(unwrapped_mint_address, wrapped_token_program_id)`.
+         * May be uninitialized, in which case the unscoped pointer applies.
+         */
+        scopedCanonicalPointer: TAccountMetas[10];
+        /**
+         * Wrap limits PDA, address must be:
+         * `get_wrap_limits_address(unwrapped_mint_address)`. May be uninitialized,
+         * in which case wrapping is not limited.
+         */
+        wrapLimits: TAccountMetas[11];
+        /**
+         * Allowlist PDA, address must be: `get_allowlist_address(unwrapped_mint_address)`.
+         * May be uninitialized, in which case the allowlist mode is disabled.
+         */
+        allowlist: TAccountMetas[12];
+        /**
+         * Allowlist entry PDA of the owner of the recipient wrapped token account, address must be:
+         * `get_allowlist_entry_address(unwrapped_mint_address, owner)`. May be uninitialized.
+         */
+        allowlistEntry: TAccountMetas[13];
+        /**
+         * Protocol fee config PDA, address must be: `get_protocol_fee_config_address()`.
+         * May be uninitialized, in which case no protocol fee is charged.
+         */
+        protocolFeeConfig: TAccountMetas[14];
+        /**
+         * Protocol fee override PDA, address must be:
+         * `get_protocol_fee_override_address(unwrapped_mint_address)`. May be uninitialized.
+         */
+        protocolFeeOverride: TAccountMetas[15];
+        /**
+         * Protocol fee vault, a token account of the unwrapped mint owned by the fee destination
+         * of the protocol fee config. Only checked if a protocol fee is charged.
+         */
+        protocolFeeVault: TAccountMetas[16];
     };
     data: WrapInstructionData;
 };
@@ -296,10 +462,10 @@ export function parseWrapInstruction<TProgram extends string, TAccountMetas exte
         InstructionWithAccounts<TAccountMetas> &
         InstructionWithData<ReadonlyUint8Array>,
 ): ParsedWrapInstruction<TProgram, TAccountMetas> {
-    if (instruction.accounts.length < 9) {
+    if (instruction.accounts.length < 17) {
         throw new SolanaError(SOLANA_ERROR__PROGRAM_CLIENTS__INSUFFICIENT_ACCOUNT_METAS, {
             actualAccountMetas: instruction.accounts.length,
-            expectedAccountMetas: 9,
+            expectedAccountMetas: 17,
         });
     }
     let accountIndex = 0;
@@ -320,6 +486,14 @@ export function parseWrapInstruction<TProgram extends string, TAccountMetas exte
             unwrappedMint: getNextAccount(),
             unwrappedEscrow: getNextAccount(),
             transferAuthority: getNextAccount(),
+            canonicalPointer: getNextAccount(),
+            scopedCanonicalPointer: getNextAccount(),
+            wrapLimits: getNextAccount(),
+            allowlist: getNextAccount(),
+            allowlistEntry: getNextAccount(),
+            protocolFeeConfig: getNextAccount(),
+            protocolFeeOverride: getNextAccount(),
+            protocolFeeVault: getNextAccount(),
         },
         data: getWrapInstructionDataDecoder().decode(instruction.data),
     };
@@ -9,3 +9,14 @@
 export * from './backpointer';
 export * from './wrappedMint';
 export * from './wrappedMintAuthority';
+export * from './canonicalPointer';
+export * from './scopedCanonicalPointer';
+export * from './wrapLimits';
+export * from './allowlist';
+export * from './allowlistEntry';
+export * from './protocolFeeConfig';
+export * from './protocolFeeOverride';
+export * from './flashMintGuard';
+export * from './registry';
+export * from './registryPage';
+export * from './wrappedMintIndex';
@@ -20,4 +20,7 @@ export {
     type CreateEscrowAccountResult,
     combinedMultisigTx,
     type MultiSigCombineArgs,
+    findPolicyAccounts,
+    type PolicyAccounts,
+    getProtocolFeeVault,
 } from './utilities';
@@ -16,8 +16,8 @@ import {
     TransactionMessageWithFeePayerSigner,
     TransactionSigner,
 } from '@solana/kit';
-import { findWrappedMintAuthorityPda, getUnwrapInstruction, UnwrapInput } from './generated';
-import { getMintFromTokenAccount, getOwnerFromAccount } from './utilities';
+import { findFlashMintGuardPda, findWrappedMintAuthorityPda, getUnwrapInstruction, UnwrapInput } from './generated';
+import { findPolicyAccounts, getMintFromTokenAccount, getOwnerFromAccount, getProtocolFeeVault } from './utilities';
 
 export interface SingleSignerUnwrapArgs {
     rpc: Rpc<GetAccountInfoApi>;
@@ -61,14 +61,28 @@ async function resolveUnwrapAddrs({
     if (!wrappedAccountInfo.exists) {
         throw new Error(`Wrapped token account ${wrappedTokenAccount} not found.`);
     }
-    const wrappedMint = getTokenDecoder().decode(wrappedAccountInfo.data).mint;
+    const { mint: wrappedMint, owner: allowlistWallet } = getTokenDecoder().decode(wrappedAccountInfo.data);
 
     const [wrappedMintAuthority] = await findWrappedMintAuthorityPda({ wrappedMint });
 
     // Default transfer authority to payer if not provided
     const transferAuthority = inputTransferAuthority ?? payer;
 
+    const [unwrappedEscrow] = await findAssociatedTokenPda({
+        owner: wrappedMintAuthority,
+        mint: unwrappedMint,
+        tokenProgram: unwrappedTokenProgram,
+    });
+    const protocolFeeVault = await getProtocolFeeVault({
+        rpc,
+        unwrappedMint,
+        unwrappedTokenProgram,
+        fallback: unwrappedEscrow,
+    });
+
     return {
+        allowlistWallet,
+        protocolFeeVault,
         unwrappedMint,
         wrappedMint,
         wrappedMintAuthority,
@@ -89,6 +103,8 @@ interface UnwrapTxBuilderArgs {
     wrappedTokenProgram: Address;
     transferAuthority: Address | TransactionSigner;
     multiSigners?: TransactionSigner[];
+    allowlistWallet?: Address; // Owner of the wrapped token account, defaults to the transfer authority
+    protocolFeeVault?: Address; // Defaults to the escrow, only checked if a protocol fee is charged
 }
 
 async function buildUnwrapTransaction({
@@ -102,12 +118,21 @@ async function buildUnwrapTransaction({
     transferAuthority,
     amount,
     multiSigners = [],
+    allowlistWallet,
+    protocolFeeVault,
 }: UnwrapTxBuilderArgs): Promise<Instruction> {
     const [unwrappedEscrow] = await findAssociatedTokenPda({
         owner: wrappedMintAuthority,
         mint: unwrappedMint,
         tokenProgram: unwrappedTokenProgram,
     });
+    const { wrapLimits, allowlist, allowlistEntry, protocolFeeConfig, protocolFeeOverride } = await findPolicyAccounts({
+        unwrappedMint,
+        wrappedTokenProgram,
+        allowlistWallet:
+            allowlistWallet ?? (typeof transferAuthority === 'string' ? transferAuthority : transferAuthority.address),
+    });
+    const [flashMintGuard] = await findFlashMintGuardPda({ wrappedMint });
 
     const unwrapInstructionInput: UnwrapInput = {
         unwrappedEscrow,
@@ -119,6 +144,13 @@ async function buildUnwrapTransaction({
         wrappedTokenAccount,
         wrappedMint,
         transferAuthority,
+        wrapLimits,
+        allowlist,
+        allowlistEntry,
+        protocolFeeConfig,
+        protocolFeeOverride,
+        protocolFeeVault: protocolFeeVault ?? unwrappedEscrow,
+        flashMintGuard,
         amount: BigInt(amount),
         multiSigners,
     };
@@ -154,6 +186,8 @@ export async function singleSignerUnwrap({
         unwrappedTokenProgram,
         unwrappedMint,
         wrappedTokenProgram,
+        allowlistWallet,
+        protocolFeeVault,
     } = await resolveUnwrapAddrs({
         rpc,
         payer,
@@ -175,6 +209,8 @@ export async function singleSignerUnwrap({
         wrappedMint,
         transferAuthority,
         amount,
+        allowlistWallet,
+        protocolFeeVault,
     });
 
     return {
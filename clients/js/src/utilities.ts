@@ -20,6 +20,7 @@ import {
     fetchEncodedAccount,
     FullySignedTransaction,
     generateKeyPairSigner,
+    getAddressDecoder,
     GetAccountInfoApi,
     GetMinimumBalanceForRentExemptionApi,
     Instruction,
@@ -30,7 +31,18 @@ import {
     TransactionWithBlockhashLifetime,
     TransactionWithinSizeLimit,
 } from '@solana/kit';
-import { findWrappedMintAuthorityPda, findWrappedMintPda } from './generated';
+import {
+    findAllowlistEntryPda,
+    findAllowlistPda,
+    findCanonicalPointerPda,
+    findProtocolFeeConfigPda,
+    findProtocolFeeOverridePda,
+    findScopedCanonicalPointerPda,
+    findWrapLimitsPda,
+    findWrappedMintAuthorityPda,
+    findWrappedMintPda,
+    TOKEN_WRAP_PROGRAM_ADDRESS,
+} from './generated';
 
 function getInitializeTokenFn(tokenProgram: Address): (input: InitializeAccountInput) => Instruction {
     if (tokenProgram === TOKEN_PROGRAM_ADDRESS) return initializeToken;
@@ -142,6 +154,86 @@ export async function getMintFromTokenAccount(
     return getTokenDecoder().decode(account.data).mint;
 }
 
+export interface PolicyAccounts {
+    canonicalPointer: Address;
+    scopedCanonicalPointer: Address;
+    wrapLimits: Address;
+    allowlist: Address;
+    allowlistEntry: Address;
+    protocolFeeConfig: Address;
+    protocolFeeOverride: Address;
+}
+
+// Derives the policy accounts that `Wrap` and `Unwrap` require. The allowlist entry is the one of the wallet owning
+// the wrapped token account that is minted to or burned from.
+export async function findPolicyAccounts({
+    unwrappedMint,
+    wrappedTokenProgram,
+    allowlistWallet,
+}: {
+    unwrappedMint: Address;
+    wrappedTokenProgram: Address;
+    allowlistWallet: Address;
+}): Promise<PolicyAccounts> {
+    const [
+        [canonicalPointer],
+        [scopedCanonicalPointer],
+        [wrapLimits],
+        [allowlist],
+        [allowlistEntry],
+        [protocolFeeConfig],
+        [protocolFeeOverride],
+    ] = await Promise.all([
+        findCanonicalPointerPda({ unwrappedMint }),
+        findScopedCanonicalPointerPda({ unwrappedMint, wrappedTokenProgram }),
+        findWrapLimitsPda({ unwrappedMint }),
+        findAllowlistPda({ unwrappedMint }),
+        findAllowlistEntryPda({ unwrappedMint, wallet: allowlistWallet }),
+        findProtocolFeeConfigPda(),
+        findProtocolFeeOverridePda({ unwrappedMint }),
+    ]);
+    return {
+        canonicalPointer,
+        scopedCanonicalPointer,
+        wrapLimits,
+        allowlist,
+        allowlistEntry,
+        protocolFeeConfig,
+        protocolFeeOverride,
+    };
+}
+
+// Size of the discriminator and version header of program-owned state
+const ACCOUNT_HEADER_SIZE = 2;
+
+// Returns the protocol fee vault for an unwrapped mint: the associated token account of the fee destination if the
+// deployment charges a protocol fee, otherwise the fallback, as the vault is only checked when a fee applies.
+export async function getProtocolFeeVault({
+    rpc,
+    unwrappedMint,
+    unwrappedTokenProgram,
+    fallback,
+}: {
+    rpc: Rpc<GetAccountInfoApi>;
+    unwrappedMint: Address;
+    unwrappedTokenProgram: Address;
+    fallback: Address;
+}): Promise<Address> {
+    const [protocolFeeConfig] = await findProtocolFeeConfigPda();
+    const account = await fetchEncodedAccount(rpc, protocolFeeConfig);
+    if (!account.exists || account.programAddress !== TOKEN_WRAP_PROGRAM_ADDRESS) {
+        return fallback;
+    }
+    // The fee destination is the first field after the header
+    const feeDestination = getAddressDecoder().decode(account.data, ACCOUNT_HEADER_SIZE);
+    const [protocolFeeVault] = await findAssociatedTokenPda({
+        owner: feeDestination,
+        mint: unwrappedMint,
+        tokenProgram: unwrappedTokenProgram,
+    });
+    return protocolFeeVault;
+}
+
 function messageBytesEqual(results: (Transaction & TransactionWithBlockhashLifetime)[]): boolean {
     // If array has only one element, return true
     if (results.length === 1) {
@@ -1,4 +1,4 @@
-import { findAssociatedTokenPda } from '@solana-program/token-2022';
+import { fetchMaybeToken, findAssociatedTokenPda } from '@solana-program/token-2022';
 import {
     Address,
     appendTransactionMessageInstructions,
@@ -16,7 +16,7 @@ import {
     TransactionSigner,
 } from '@solana/kit';
 import { findWrappedMintAuthorityPda, findWrappedMintPda, getWrapInstruction, WrapInput } from './generated';
-import { getMintFromTokenAccount, getOwnerFromAccount } from './utilities';
+import { findPolicyAccounts, getMintFromTokenAccount, getOwnerFromAccount, getProtocolFeeVault } from './utilities';
 
 interface IxBuilderArgs {
     unwrappedTokenAccount: Address;
@@ -29,6 +29,8 @@ interface IxBuilderArgs {
     recipientWrappedTokenAccount: Address;
     unwrappedTokenProgram: Address;
     multiSigners?: TransactionSigner[];
+    allowlistWallet?: Address; // Owner of the recipient wrapped token account, defaults to the transfer authority
+    protocolFeeVault?: Address; // Defaults to the escrow, only checked if a protocol fee is charged
 }
 
 export interface MultiSignerWrapIxBuilderArgs extends IxBuilderArgs {
@@ -92,6 +94,8 @@ export async function singleSignerWrap({
         recipientWrappedTokenAccount,
         transferAuthority,
         unwrappedEscrow,
+        allowlistWallet,
+        protocolFeeVault,
     } = await resolveAddrs({
         rpc,
         payer,
@@ -113,6 +117,8 @@ export async function singleSignerWrap({
         wrappedMintAuthority,
         recipientWrappedTokenAccount,
         unwrappedTokenProgram,
+        allowlistWallet,
+        protocolFeeVault,
     });
 
     return {
@@ -164,8 +170,21 @@ async function resolveAddrs({
 
     const transferAuthority = inputTransferAuthority ?? payer;
 
+    // The allowlist applies to the owner of the recipient, which is the payer by default
+    const recipient = inputRecipientTokenAccount ? await fetchMaybeToken(rpc, inputRecipientTokenAccount) : null;
+    const allowlistWallet = recipient?.exists ? recipient.data.owner : payer.address;
+
+    const protocolFeeVault = await getProtocolFeeVault({
+        rpc,
+        unwrappedMint,
+        unwrappedTokenProgram,
+        fallback: unwrappedEscrow,
+    });
+
     return {
         unwrappedEscrow,
+        allowlistWallet,
+        protocolFeeVault,
         transferAuthority,
         unwrappedMint,
         unwrappedTokenProgram,
@@ -186,12 +205,20 @@ async function buildWrapIx({
     wrappedMint,
     wrappedMintAuthority,
     multiSigners = [],
+    allowlistWallet,
+    protocolFeeVault,
 }: IxBuilderArgs): Promise<Instruction> {
     const [unwrappedEscrow] = await findAssociatedTokenPda({
         owner: wrappedMintAuthority,
         mint: unwrappedMint,
         tokenProgram: unwrappedTokenProgram,
     });
+    const policyAccounts = await findPolicyAccounts({
+        unwrappedMint,
+        wrappedTokenProgram,
+        allowlistWallet:
+            allowlistWallet ?? (typeof transferAuthority === 'string' ? transferAuthority : transferAuthority.address),
+    });
 
     const wrapInstructionInput: WrapInput = {
         recipientWrappedTokenAccount,
@@ -203,6 +230,8 @@ async function buildWrapIx({
         unwrappedMint,
         unwrappedEscrow,
         transferAuthority,
+        ...policyAccounts,
+        protocolFeeVault: protocolFeeVault ?? unwrappedEscrow,
         amount: BigInt(amount),
         multiSigners,
     };
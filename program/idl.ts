@@ -1,12 +1,16 @@
 import {
   accountNode,
   argumentValueNode,
+  arrayTypeNode,
   booleanTypeNode,
   booleanValueNode,
+  bytesTypeNode,
   constantPdaSeedNodeFromString,
   createFromRoot,
   errorNode,
   fieldDiscriminatorNode,
+  fixedCountNode,
+  fixedSizeTypeNode,
   identityValueNode,
   instructionAccountNode,
   instructionArgumentNode,
@@ -39,15 +43,69 @@ const codama = createFromRoot(
       accounts: [
         accountNode({
           name: "backpointer",
-          docs: "Account to store the address of the unwrapped mint.",
+          docs: [
+            "Account to store the address of the unwrapped mint and how the",
+            "wrapped mint was created. Backpointers in the original 32-byte layout",
+            "only store the unwrapped mint until migrated with `MigrateState`.",
+          ],
           data: structTypeNode([
+            structFieldTypeNode({
+              name: "discriminator",
+              type: numberTypeNode("u8"),
+              defaultValue: numberValueNode(1),
+              defaultValueStrategy: "omitted",
+            }),
+            structFieldTypeNode({
+              name: "version",
+              type: numberTypeNode("u8"),
+              defaultValue: numberValueNode(1),
+              defaultValueStrategy: "omitted",
+            }),
             structFieldTypeNode({
               name: "unwrappedMint",
+              docs: "Address that the wrapped mint is wrapping",
               type: publicKeyTypeNode(),
             }),
+            structFieldTypeNode({
+              name: "unwrappedTokenProgram",
+              docs: "Token program that owns the unwrapped mint",
+              type: publicKeyTypeNode(),
+            }),
+            structFieldTypeNode({
+              name: "wrappedTokenProgram",
+              docs: "Token program that owns the wrapped mint",
+              type: publicKeyTypeNode(),
+            }),
+            structFieldTypeNode({
+              name: "customizerId",
+              docs: "ID of the mint customizer, zeroed if unknown",
+              type: fixedSizeTypeNode(bytesTypeNode(), 16),
+            }),
+            structFieldTypeNode({
+              name: "creationSlot",
+              docs: "Slot in which the wrapped mint was created, zero if unknown",
+              type: numberTypeNode("u64"),
+            }),
+            structFieldTypeNode({
+              name: "wrappedMintBump",
+              type: numberTypeNode("u8"),
+            }),
+            structFieldTypeNode({
+              name: "wrappedMintAuthorityBump",
+              type: numberTypeNode("u8"),
+            }),
+            structFieldTypeNode({
+              name: "backpointerBump",
+              type: numberTypeNode("u8"),
+            }),
+            structFieldTypeNode({
+              name: "programVersion",
+              docs: "Program version as major, minor and patch, zeroed if unknown",
+              type: arrayTypeNode(numberTypeNode("u8"), fixedCountNode(3)),
+            }),
           ]),
           pda: pdaLinkNode("backpointer"),
-          size: 32,
+          size: 128,
         }),
       ],
       instructions: [
@@ -99,6 +157,33 @@ const codama = createFromRoot(
               isSigner: false,
               isWritable: false,
             }),
+            instructionAccountNode({
+              name: "registry",
+              docs: [
+                "Wrapped mint registry PDA, address must be:",
+                "`get_wrapped_mint_registry_address()`",
+              ],
+              isSigner: false,
+              isWritable: true,
+            }),
+            instructionAccountNode({
+              name: "registryPage",
+              docs: [
+                "Registry page the entry is appended to, address must be:",
+                "`get_wrapped_mint_registry_page_address(page_index)`",
+              ],
+              isSigner: false,
+              isWritable: true,
+            }),
+            instructionAccountNode({
+              name: "wrappedMintIndex",
+              docs: [
+                "Wrapped mint index PDA of the unwrapped mint, address must be:",
+                "`get_wrapped_mint_index_address(unwrapped_mint_address)`",
+              ],
+              isSigner: false,
+              isWritable: true,
+            }),
           ],
           discriminators: [fieldDiscriminatorNode("discriminator", 0)],
           arguments: [
@@ -115,6 +200,13 @@ const codama = createFromRoot(
               defaultValue: booleanValueNode(false),
               defaultValueStrategy: "optional",
             }),
+            instructionArgumentNode({
+              name: "acknowledgeRiskyExtensions",
+              docs: "Whether to allow unwrapped mints with extensions that can put the escrow at risk.",
+              type: booleanTypeNode(),
+              defaultValue: booleanValueNode(false),
+              defaultValueStrategy: "optional",
+            }),
           ],
         }),
         instructionNode({
@@ -188,6 +280,80 @@ const codama = createFromRoot(
               isWritable: false,
               defaultValue: identityValueNode(),
             }),
+            instructionAccountNode({
+              name: "canonicalPointer",
+              docs: [
+                "Canonical pointer PDA, address must be:",
+                "`get_canonical_pointer_address(unwrapped_mint_address)`. May be uninitialized.",
+              ],
+              isSigner: false,
+              isWritable: false,
+            }),
+            instructionAccountNode({
+              name: "scopedCanonicalPointer",
+              docs: [
+                "Scoped canonical pointer PDA, address must be:",
+                "`get_scoped_canonical_pointer_address(unwrapped_mint_address, wrapped_token_program_id)`.",
+                "May be uninitialized, in which case the unscoped pointer applies.",
+              ],
+              isSigner: false,
+              isWritable: false,
+            }),
+            instructionAccountNode({
+              name: "wrapLimits",
+              docs: [
+                "Wrap limits PDA, address must be:",
+                "`get_wrap_limits_address(unwrapped_mint_address)`. May be uninitialized,",
+                "in which case wrapping is not limited.",
+              ],
+              isSigner: false,
+              isWritable: true,
+            }),
+            instructionAccountNode({
+              name: "allowlist",
+              docs: [
+                "Allowlist PDA, address must be: `get_allowlist_address(unwrapped_mint_address)`.",
+                "May be uninitialized, in which case the allowlist mode is disabled.",
+              ],
+              isSigner: false,
+              isWritable: false,
+            }),
+            instructionAccountNode({
+              name: "allowlistEntry",
+              docs: [
+                "Allowlist entry PDA of the owner of the recipient wrapped token account, address must be:",
+                "`get_allowlist_entry_address(unwrapped_mint_address, owner)`. May be uninitialized.",
+              ],
+              isSigner: false,
+              isWritable: false,
+            }),
+            instructionAccountNode({
+              name: "protocolFeeConfig",
+              docs: [
+                "Protocol fee config PDA, address must be: `get_protocol_fee_config_address()`.",
+                "May be uninitialized, in which case no protocol fee is charged.",
+              ],
+              isSigner: false,
+              isWritable: false,
+            }),
+            instructionAccountNode({
+              name: "protocolFeeOverride",
+              docs: [
+                "Protocol fee override PDA, address must be:",
+                "`get_protocol_fee_override_address(unwrapped_mint_address)`. May be uninitialized.",
+              ],
+              isSigner: false,
+              isWritable: false,
+            }),
+            instructionAccountNode({
+              name: "protocolFeeVault",
+              docs: [
+                "Protocol fee vault, a token account of the unwrapped mint owned by the fee destination",
+                "of the protocol fee config. Only checked if a protocol fee is charged.",
+              ],
+              isSigner: false,
+              isWritable: true,
+            }),
           ],
           remainingAccounts: [
             instructionRemainingAccountsNode(
@@ -278,6 +444,70 @@ const codama = createFromRoot(
               isWritable: false,
               defaultValue: identityValueNode(),
             }),
+            instructionAccountNode({
+              name: "wrapLimits",
+              docs: [
+                "Wrap limits PDA, address must be:",
+                "`get_wrap_limits_address(unwrapped_mint_address)`. May be uninitialized.",
+                "The unwrapped amount is subtracted from its total wrapped.",
+              ],
+              isSigner: false,
+              isWritable: true,
+            }),
+            instructionAccountNode({
+              name: "allowlist",
+              docs: [
+                "Allowlist PDA, address must be: `get_allowlist_address(unwrapped_mint_address)`.",
+                "May be uninitialized, in which case the allowlist mode is disabled.",
+              ],
+              isSigner: false,
+              isWritable: false,
+            }),
+            instructionAccountNode({
+              name: "allowlistEntry",
+              docs: [
+                "Allowlist entry PDA of the owner of the wrapped token account, address must be:",
+                "`get_allowlist_entry_address(unwrapped_mint_address, owner)`. May be uninitialized.",
+              ],
+              isSigner: false,
+              isWritable: false,
+            }),
+            instructionAccountNode({
+              name: "protocolFeeConfig",
+              docs: [
+                "Protocol fee config PDA, address must be: `get_protocol_fee_config_address()`.",
+                "May be uninitialized, in which case no protocol fee is charged.",
+              ],
+              isSigner: false,
+              isWritable: false,
+            }),
+            instructionAccountNode({
+              name: "protocolFeeOverride",
+              docs: [
+                "Protocol fee override PDA, address must be:",
+                "`get_protocol_fee_override_address(unwrapped_mint_address)`. May be uninitialized.",
+              ],
+              isSigner: false,
+              isWritable: false,
+            }),
+            instructionAccountNode({
+              name: "protocolFeeVault",
+              docs: [
+                "Protocol fee vault, a token account of the unwrapped mint owned by the fee destination",
+                "of the protocol fee config. Only checked if a protocol fee is charged.",
+              ],
+              isSigner: false,
+              isWritable: true,
+            }),
+            instructionAccountNode({
+              name: "flashMintGuard",
+              docs: [
+                "Flash mint guard PDA, address must be: `get_flash_mint_guard_address(wrapped_mint_address)`.",
+                "May be uninitialized. Unwrapping is rejected while a flash mint is active.",
+              ],
+              isSigner: false,
+              isWritable: false,
+            }),
           ],
           remainingAccounts: [
             instructionRemainingAccountsNode(
@@ -567,6 +797,83 @@ const codama = createFromRoot(
             variablePdaSeedNode("wrappedMint", publicKeyTypeNode()),
           ],
         }),
+        pdaNode({
+          name: "canonicalPointer",
+          seeds: [
+            constantPdaSeedNodeFromString("utf8", "canonical_pointer"),
+            variablePdaSeedNode("unwrappedMint", publicKeyTypeNode()),
+          ],
+        }),
+        pdaNode({
+          name: "scopedCanonicalPointer",
+          seeds: [
+            constantPdaSeedNodeFromString("utf8", "canonical_pointer"),
+            variablePdaSeedNode("unwrappedMint", publicKeyTypeNode()),
+            variablePdaSeedNode("wrappedTokenProgram", publicKeyTypeNode()),
+          ],
+        }),
+        pdaNode({
+          name: "wrapLimits",
+          seeds: [
+            constantPdaSeedNodeFromString("utf8", "wrap_limits"),
+            variablePdaSeedNode("unwrappedMint", publicKeyTypeNode()),
+          ],
+        }),
+        pdaNode({
+          name: "allowlist",
+          seeds: [
+            constantPdaSeedNodeFromString("utf8", "allowlist"),
+            variablePdaSeedNode("unwrappedMint", publicKeyTypeNode()),
+          ],
+        }),
+        pdaNode({
+          name: "allowlistEntry",
+          seeds: [
+            constantPdaSeedNodeFromString("utf8", "allowlist_entry"),
+            variablePdaSeedNode("unwrappedMint", publicKeyTypeNode()),
+            variablePdaSeedNode("wallet", publicKeyTypeNode()),
+          ],
+        }),
+        pdaNode({
+          name: "protocolFeeConfig",
+          seeds: [
+            constantPdaSeedNodeFromString("utf8", "protocol_fee_config"),
+          ],
+        }),
+        pdaNode({
+          name: "protocolFeeOverride",
+          seeds: [
+            constantPdaSeedNodeFromString("utf8", "protocol_fee_override"),
+            variablePdaSeedNode("unwrappedMint", publicKeyTypeNode()),
+          ],
+        }),
+        pdaNode({
+          name: "flashMintGuard",
+          seeds: [
+            constantPdaSeedNodeFromString("utf8", "flash_mint_guard"),
+            variablePdaSeedNode("wrappedMint", publicKeyTypeNode()),
+          ],
+        }),
+        pdaNode({
+          name: "registry",
+          seeds: [
+            constantPdaSeedNodeFromString("utf8", "registry"),
+          ],
+        }),
+        pdaNode({
+          name: "registryPage",
+          seeds: [
+            constantPdaSeedNodeFromString("utf8", "registry_page"),
+            variablePdaSeedNode("pageIndex", numberTypeNode("u64")),
+          ],
+        }),
+        pdaNode({
+          name: "wrappedMintIndex",
+          seeds: [
+            constantPdaSeedNodeFromString("utf8", "wrapped_mint_index"),
+            variablePdaSeedNode("unwrappedMint", publicKeyTypeNode()),
+          ],
+        }),
       ],
       errors: [
         errorNode({
@@ -67,6 +67,196 @@ pub enum TokenWrapError {
     /// Instruction can only be used with spl-token wrapped mints
     #[error("Instruction can only be used with spl-token wrapped mints")]
     NoSyncingToToken2022,
+    /// Wrapping is disabled because the canonical pointer enforces another
+    /// deployment
+    #[error("Wrapping is disabled because the canonical pointer enforces another deployment")]
+    NonCanonicalDeployment,
+    /// Canonical pointer has a change delay and must be changed through a
+    /// proposal
+    #[error("Canonical pointer has a change delay and must be changed through a proposal")]
+    CanonicalPointerTimelocked,
+    /// Pending canonical pointer change has not reached its activation time
+    #[error("Pending canonical pointer change has not reached its activation time")]
+    PendingCanonicalPointerNotActive,
+    /// Unwrapped mint does not match the backpointer
+    #[error("Unwrapped mint does not match the backpointer")]
+    UnwrappedMintMismatch,
+
+    // 20
+    /// Wrapped mint registry address does not match expected PDA
+    #[error("Wrapped mint registry address does not match expected PDA")]
+    WrappedMintRegistryMismatch,
+    /// Wrapped mint registry page is not the page the next entry is appended to
+    #[error("Wrapped mint registry page is not the page the next entry is appended to")]
+    WrappedMintRegistryPageMismatch,
+    /// Wrapped mint index address does not match expected PDA
+    #[error("Wrapped mint index address does not match expected PDA")]
+    WrappedMintIndexMismatch,
+    /// Wrap statistics address does not match expected PDA
+    #[error("Wrap statistics address does not match expected PDA")]
+    WrapStatsMismatch,
+    /// Unwrapped mint must be closed before its wrapped mint can be closed
+    #[error("Unwrapped mint must be closed before its wrapped mint can be closed")]
+    UnwrappedMintNotClosed,
+
+    // 25
+    /// Wrapped mint still has supply outstanding
+    #[error("Wrapped mint still has supply outstanding")]
+    WrappedMintHasSupply,
+    /// Escrow still holds unwrapped tokens
+    #[error("Escrow still holds unwrapped tokens")]
+    EscrowNotEmpty,
+    /// Wrapped mint does not have the wrapped mint authority as close authority
+    #[error("Wrapped mint does not have the wrapped mint authority as close authority")]
+    WrappedMintNotClosable,
+    /// Unwrapped mint is non-transferable and cannot be escrowed
+    #[error("Unwrapped mint is non-transferable and cannot be escrowed")]
+    UnwrappedMintNonTransferable,
+    /// Unwrapped mint freezes new accounts by default, the escrow would start
+    /// frozen
+    #[error("Unwrapped mint freezes new accounts by default, the escrow would start frozen")]
+    UnwrappedMintDefaultFrozen,
+
+    // 30
+    /// Unwrapped mint has extensions that put the escrow at risk, which must be
+    /// acknowledged
+    #[error(
+        "Unwrapped mint has extensions that put the escrow at risk, which must be acknowledged"
+    )]
+    RiskyExtensionNotAcknowledged,
+    /// Escrow is under-collateralized, the pro-rata redemption account must be
+    /// provided
+    #[error("Escrow is under-collateralized, the pro-rata redemption account must be provided")]
+    ProRataRedemptionRequired,
+    /// Pro-rata redemption account address does not match expected PDA
+    #[error("Pro-rata redemption account address does not match expected PDA")]
+    ProRataRedemptionMismatch,
+    /// Unwrap amount is too small to redeem any unwrapped tokens pro rata
+    #[error("Unwrap amount is too small to redeem any unwrapped tokens pro rata")]
+    RedemptionAmountZero,
+    /// Escrow surplus policy address does not match expected derivation
+    #[error("Escrow surplus policy address does not match expected derivation")]
+    EscrowSurplusPolicyMismatch,
+
+    // 35
+    /// Escrow does not hold more unwrapped tokens than the wrapped supply
+    #[error("Escrow does not hold more unwrapped tokens than the wrapped supply")]
+    NoEscrowSurplus,
+    /// Recipient or claimant does not match the escrow surplus policy
+    #[error("Recipient or claimant does not match the escrow surplus policy")]
+    SurplusRecipientMismatch,
+    /// Escrow would hold fewer unwrapped tokens than the wrapped supply
+    #[error("Escrow would hold fewer unwrapped tokens than the wrapped supply")]
+    EscrowBackingViolated,
+    /// Unwrapped mint is not the native mint of its token program
+    #[error("Unwrapped mint is not the native mint of its token program")]
+    NotNativeMint,
+    /// The temporary account for native unwraps does not match the expected
+    /// derivation
+    #[error("The temporary account for native unwraps does not match the expected derivation")]
+    NativeUnwrapAccountMismatch,
+
+    // 40
+    /// Recipient wrapped token account is not configured to receive
+    /// confidential deposits
+    #[error("Recipient wrapped token account is not configured to receive confidential deposits")]
+    RecipientNotConfidential,
+    /// The confidential transfer settings of the wrapped mint are not governed
+    /// by the wrapped mint authority
+    #[error("Wrapped mint confidential transfer authority is not the wrapped mint authority")]
+    ConfidentialConfigNotGoverned,
+    /// Wrapped mint admin address does not match expected PDA
+    #[error("Wrapped mint admin address does not match expected PDA")]
+    WrappedMintAdminMismatch,
+    /// Wrap limits account address does not match expected PDA
+    #[error("Wrap limits account address does not match expected PDA")]
+    WrapLimitsMismatch,
+    /// Unwrapped mint only allows unwrapping
+    #[error("Unwrapped mint only allows unwrapping")]
+    UnwrapOnly,
+
+    // 45
+    /// Wrap would exceed the maximum wrapped supply
+    #[error("Wrap would exceed the maximum wrapped supply")]
+    WrappedSupplyCapExceeded,
+    /// Wrap would exceed the maximum amount wrapped per epoch
+    #[error("Wrap would exceed the maximum amount wrapped per epoch")]
+    EpochWrapCapExceeded,
+    /// Allowlist address does not match expected PDA
+    #[error("Allowlist address does not match expected PDA")]
+    AllowlistMismatch,
+    /// Allowlist entry address does not match expected PDA
+    #[error("Allowlist entry address does not match expected PDA")]
+    AllowlistEntryMismatch,
+    /// Wallet is not on the allowlist of the unwrapped mint
+    #[error("Wallet is not on the allowlist of the unwrapped mint")]
+    NotAllowlisted,
+
+    // 50
+    /// Protocol fee config address does not match expected PDA
+    #[error("Protocol fee config address does not match expected PDA")]
+    ProtocolFeeConfigMismatch,
+    /// Protocol fee override address does not match expected PDA
+    #[error("Protocol fee override address does not match expected PDA")]
+    ProtocolFeeOverrideMismatch,
+    /// Fee vault is not a token account of the unwrapped mint owned by the fee
+    /// destination
+    #[error("Fee vault is not a token account of the unwrapped mint owned by the fee destination")]
+    FeeVaultMismatch,
+    /// Protocol fee exceeds 10,000 basis points
+    #[error("Protocol fee exceeds 10,000 basis points")]
+    InvalidFeeBasisPoints,
+    /// Program data address does not match the program
+    #[error("Program data address does not match the program")]
+    ProgramDataMismatch,
+
+    // 55
+    /// Flash mint guard address does not match expected PDA
+    #[error("Flash mint guard address does not match expected PDA")]
+    FlashMintGuardMismatch,
+    /// A flash mint of the wrapped mint is already awaiting repayment
+    #[error("A flash mint of the wrapped mint is already awaiting repayment")]
+    FlashMintActive,
+    /// The transaction has no later FlashRepay for the flash mint
+    #[error("The transaction has no later FlashRepay for the flash mint")]
+    FlashRepayMissing,
+    /// No flash mint of the wrapped mint awaits repayment
+    #[error("No flash mint of the wrapped mint awaits repayment")]
+    FlashMintNotActive,
+    /// Permit delegate address does not match expected PDA
+    #[error("Permit delegate address does not match expected PDA")]
+    PermitDelegateMismatch,
+
+    // 60
+    /// Permit nonce address does not match expected PDA
+    #[error("Permit nonce address does not match expected PDA")]
+    PermitNonceMismatch,
+    /// Token account owner is not the permit signer
+    #[error("Token account owner is not the permit signer")]
+    PermitOwnerMismatch,
+    /// Permit nonce does not match the next nonce of the owner
+    #[error("Permit nonce does not match the next nonce of the owner")]
+    InvalidPermitNonce,
+    /// Permit expired
+    #[error("Permit expired")]
+    PermitExpired,
+    /// Permit signature not found in the transaction
+    #[error("Permit signature not found in the transaction")]
+    PermitSignatureMissing,
+
+    // 65
+    /// Crank wrap delegate address does not match expected PDA
+    #[error("Crank wrap delegate address does not match expected PDA")]
+    CrankWrapDelegateMismatch,
+    /// Token account has not approved the crank wrap delegate
+    #[error("Token account has not approved the crank wrap delegate")]
+    CrankWrapNotApproved,
+    /// Recipient is not the one designated to the crank wrap delegate
+    #[error("Recipient is not the one designated to the crank wrap delegate")]
+    CrankWrapRecipientMismatch,
+    /// Wrapped mint is already recorded in the wrapped mint registry
+    #[error("Wrapped mint is already recorded in the wrapped mint registry")]
+    WrappedMintAlreadyRegistered,
 }
 
 impl From<TokenWrapError> for ProgramError {
@@ -102,6 +292,63 @@ impl ToStr for TokenWrapError {
             TokenWrapError::MetadataPointerMismatch => "Error: MetadataPointerMismatch",
             TokenWrapError::ExternalProgramReturnedNoData => "Error: ExternalProgramReturnedNoData",
             TokenWrapError::NoSyncingToToken2022 => "Error: NoSyncingToToken2022",
+            TokenWrapError::NonCanonicalDeployment => "Error: NonCanonicalDeployment",
+            TokenWrapError::CanonicalPointerTimelocked => "Error: CanonicalPointerTimelocked",
+            TokenWrapError::PendingCanonicalPointerNotActive => {
+                "Error: PendingCanonicalPointerNotActive"
+            }
+            TokenWrapError::UnwrappedMintMismatch => "Error: UnwrappedMintMismatch",
+            TokenWrapError::WrappedMintRegistryMismatch => "Error: WrappedMintRegistryMismatch",
+            TokenWrapError::WrappedMintRegistryPageMismatch => {
+                "Error: WrappedMintRegistryPageMismatch"
+            }
+            TokenWrapError::WrappedMintIndexMismatch => "Error: WrappedMintIndexMismatch",
+            TokenWrapError::WrapStatsMismatch => "Error: WrapStatsMismatch",
+            TokenWrapError::UnwrappedMintNotClosed => "Error: UnwrappedMintNotClosed",
+            TokenWrapError::WrappedMintHasSupply => "Error: WrappedMintHasSupply",
+            TokenWrapError::EscrowNotEmpty => "Error: EscrowNotEmpty",
+            TokenWrapError::WrappedMintNotClosable => "Error: WrappedMintNotClosable",
+            TokenWrapError::UnwrappedMintNonTransferable => "Error: UnwrappedMintNonTransferable",
+            TokenWrapError::UnwrappedMintDefaultFrozen => "Error: UnwrappedMintDefaultFrozen",
+            TokenWrapError::RiskyExtensionNotAcknowledged => "Error: RiskyExtensionNotAcknowledged",
+            TokenWrapError::ProRataRedemptionRequired => "Error: ProRataRedemptionRequired",
+            TokenWrapError::ProRataRedemptionMismatch => "Error: ProRataRedemptionMismatch",
+            TokenWrapError::RedemptionAmountZero => "Error: RedemptionAmountZero",
+            TokenWrapError::EscrowSurplusPolicyMismatch => "Error: EscrowSurplusPolicyMismatch",
+            TokenWrapError::NoEscrowSurplus => "Error: NoEscrowSurplus",
+            TokenWrapError::SurplusRecipientMismatch => "Error: SurplusRecipientMismatch",
+            TokenWrapError::EscrowBackingViolated => "Error: EscrowBackingViolated",
+            TokenWrapError::NotNativeMint => "Error: NotNativeMint",
+            TokenWrapError::NativeUnwrapAccountMismatch => "Error: NativeUnwrapAccountMismatch",
+            TokenWrapError::RecipientNotConfidential => "Error: RecipientNotConfidential",
+            TokenWrapError::ConfidentialConfigNotGoverned => "Error: ConfidentialConfigNotGoverned",
+            TokenWrapError::WrappedMintAdminMismatch => "Error: WrappedMintAdminMismatch",
+            TokenWrapError::WrapLimitsMismatch => "Error: WrapLimitsMismatch",
+            TokenWrapError::UnwrapOnly => "Error: UnwrapOnly",
+            TokenWrapError::WrappedSupplyCapExceeded => "Error: WrappedSupplyCapExceeded",
+            TokenWrapError::EpochWrapCapExceeded => "Error: EpochWrapCapExceeded",
+            TokenWrapError::AllowlistMismatch => "Error: AllowlistMismatch",
+            TokenWrapError::AllowlistEntryMismatch => "Error: AllowlistEntryMismatch",
+            TokenWrapError::NotAllowlisted => "Error: NotAllowlisted",
+            TokenWrapError::ProtocolFeeConfigMismatch => "Error: ProtocolFeeConfigMismatch",
+            TokenWrapError::ProtocolFeeOverrideMismatch => "Error: ProtocolFeeOverrideMismatch",
+            TokenWrapError::FeeVaultMismatch => "Error: FeeVaultMismatch",
+            TokenWrapError::InvalidFeeBasisPoints => "Error: InvalidFeeBasisPoints",
+            TokenWrapError::ProgramDataMismatch => "Error: ProgramDataMismatch",
+            TokenWrapError::FlashMintGuardMismatch => "Error: FlashMintGuardMismatch",
+            TokenWrapError::FlashMintActive => "Error: FlashMintActive",
+            TokenWrapError::FlashRepayMissing => "Error: FlashRepayMissing",
+            TokenWrapError::FlashMintNotActive => "Error: FlashMintNotActive",
+            TokenWrapError::PermitDelegateMismatch => "Error: PermitDelegateMismatch",
+            TokenWrapError::PermitNonceMismatch => "Error: PermitNonceMismatch",
+            TokenWrapError::PermitOwnerMismatch => "Error: PermitOwnerMismatch",
+            TokenWrapError::InvalidPermitNonce => "Error: InvalidPermitNonce",
+            TokenWrapError::PermitExpired => "Error: PermitExpired",
+            TokenWrapError::PermitSignatureMissing => "Error: PermitSignatureMissing",
+            TokenWrapError::CrankWrapDelegateMismatch => "Error: CrankWrapDelegateMismatch",
+            TokenWrapError::CrankWrapNotApproved => "Error: CrankWrapNotApproved",
+            TokenWrapError::CrankWrapRecipientMismatch => "Error: CrankWrapRecipientMismatch",
+            TokenWrapError::WrappedMintAlreadyRegistered => "Error: WrappedMintAlreadyRegistered",
         }
     }
 }
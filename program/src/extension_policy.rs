@@ -0,0 +1,106 @@
+//! Policy for the extensions of unwrapped mints accepted by `CreateMint`
+
+use {
+    crate::error::TokenWrapError,
+    solana_msg::msg,
+    solana_program_error::ProgramError,
+    spl_token_2022_interface::{
+        extension::{
+            default_account_state::DefaultAccountState, BaseStateWithExtensions, ExtensionType,
+            PodStateWithExtensions,
+        },
+        pod::PodMint,
+        state::AccountState,
+    },
+};
+
+/// How an unwrapped mint extension affects the escrow of a wrapped mint
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExtensionRisk {
+    /// The escrow can hold and release tokens, but an authority of the
+    /// unwrapped mint can move or lock them. Creating the wrapped mint must be
+    /// acknowledged.
+    Risky,
+    /// The escrow cannot work with this mint, creating the wrapped mint is
+    /// rejected with the given error
+    Rejected(TokenWrapError),
+}
+
+/// An extension of an unwrapped mint that is not plainly allowed
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtensionFinding {
+    /// The extension of the unwrapped mint
+    pub extension_type: ExtensionType,
+    /// The risk it poses to the escrow
+    pub risk: ExtensionRisk,
+}
+
+impl ExtensionFinding {
+    /// Describes the effect of the extension on the escrow
+    pub fn description(&self) -> &'static str {
+        match self.extension_type {
+            ExtensionType::PermanentDelegate => {
+                "the permanent delegate can transfer or burn escrowed tokens"
+            }
+            ExtensionType::TransferHook => "the transfer hook program can block wraps and unwraps",
+            ExtensionType::Pausable => "the pause authority can block wraps and unwraps",
+            ExtensionType::NonTransferable => "tokens cannot be transferred into the escrow",
+            ExtensionType::DefaultAccountState => "the escrow would be created frozen",
+            _ => "unsupported extension",
+        }
+    }
+}
+
+/// Returns the extensions of an unwrapped mint that are risky or rejected.
+/// SPL Token mints have no extensions and yield no findings.
+pub fn analyze_mint_extensions(mint_data: &[u8]) -> Result<Vec<ExtensionFinding>, ProgramError> {
+    let mint = PodStateWithExtensions::<PodMint>::unpack(mint_data)?;
+
+    let mut findings = vec![];
+    for extension_type in mint.get_extension_types()? {
+        let risk = match extension_type {
+            ExtensionType::PermanentDelegate
+            | ExtensionType::TransferHook
+            | ExtensionType::Pausable => ExtensionRisk::Risky,
+            ExtensionType::NonTransferable => {
+                ExtensionRisk::Rejected(TokenWrapError::UnwrappedMintNonTransferable)
+            }
+            ExtensionType::DefaultAccountState => {
+                let extension = mint.get_extension::<DefaultAccountState>()?;
+                if extension.state != AccountState::Frozen as u8 {
+                    continue;
+                }
+                ExtensionRisk::Rejected(TokenWrapError::UnwrappedMintDefaultFrozen)
+            }
+            _ => continue,
+        };
+        findings.push(ExtensionFinding {
+            extension_type,
+            risk,
+        });
+    }
+    Ok(findings)
+}
+
+/// Checks the extensions of an unwrapped mint against the policy. Rejected
+/// extensions always fail, risky ones fail unless acknowledged.
+pub fn check_mint_extensions(
+    mint_data: &[u8],
+    acknowledge_risky_extensions: bool,
+) -> Result<(), ProgramError> {
+    for finding in analyze_mint_extensions(mint_data)? {
+        msg!(
+            "Unwrapped mint extension {:?}: {}",
+            finding.extension_type,
+            finding.description()
+        );
+        match finding.risk {
+            ExtensionRisk::Rejected(error) => Err(error)?,
+            ExtensionRisk::Risky if !acknowledge_risky_extensions => {
+                Err(TokenWrapError::RiskyExtensionNotAcknowledged)?
+            }
+            ExtensionRisk::Risky => {}
+        }
+    }
+    Ok(())
+}
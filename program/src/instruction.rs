@@ -1,9 +1,15 @@
 //! Program instructions
 
 use {
+    crate::state::SurplusAction,
     solana_instruction::{AccountMeta, Instruction},
     solana_program_error::ProgramError,
     solana_pubkey::Pubkey,
+    solana_zk_sdk_pod::encryption::{
+        auth_encryption::PodAeCiphertext, elgamal::PodElGamalPubkey, AE_CIPHERTEXT_LEN,
+        ELGAMAL_PUBKEY_LEN,
+    },
+    spl_token_metadata_interface::state::Field,
     std::convert::TryInto,
 };
 
@@ -17,6 +23,15 @@ pub enum TokenWrapInstruction {
     /// - token-2022 to spl-token
     /// - token-2022 to token-2022 w/ new extensions
     ///
+    /// Unwrapped mints with extensions the escrow cannot work with, like
+    /// `NonTransferable`, are rejected. Extensions that let an authority of the
+    /// unwrapped mint move or lock escrowed tokens, like `PermanentDelegate`,
+    /// must be acknowledged.
+    ///
+    /// The new wrapped mint is appended to the wrapped mint registry and to the
+    /// index of the unwrapped mint. These accounts grow by one entry, so the
+    /// caller must also pre-fund them for their new size.
+    ///
     /// Accounts expected by this instruction:
     ///
     /// 0. `[w]` Unallocated wrapped mint account to create (PDA), address must
@@ -27,10 +42,21 @@ pub enum TokenWrapInstruction {
     /// 2. `[]` Existing unwrapped mint
     /// 3. `[]` System program
     /// 4. `[]` SPL Token program for wrapped mint
+    /// 5. `[w]` Wrapped mint registry (PDA)
+    ///    `get_wrapped_mint_registry_address()`
+    /// 6. `[w]` Last page of the registry (PDA)
+    ///    `get_wrapped_mint_registry_page_address(registry.next_page_index())`
+    /// 7. `[w]` Wrapped mint index of the unwrapped mint (PDA)
+    ///    `get_wrapped_mint_index_address(unwrapped_mint_address)`
     CreateMint {
         /// If true, idempotent creation. If false, fail if the mint already
         /// exists.
         idempotent: bool,
+        /// If true, accept an unwrapped mint with extensions that put the
+        /// escrow at risk, see
+        /// [`extension_policy`](crate::extension_policy). Encoded as an
+        /// optional trailing byte, omitted when false.
+        acknowledge_risky_extensions: bool,
     },
 
     /// Wrap tokens
@@ -38,6 +64,11 @@ pub enum TokenWrapInstruction {
     /// Move a user's unwrapped tokens into an escrow account and mint the same
     /// number of wrapped tokens into the provided account.
     ///
+    /// If the deployment charges a protocol fee, the fee portion of the
+    /// unwrapped tokens is moved from the escrow to the protocol fee vault and
+    /// only the rest is minted. The split is logged and set as the return
+    /// data, as a `ProtocolFeeSplit`.
+    ///
     /// Accounts expected by this instruction:
     ///
     /// 0. `[w]` Recipient wrapped token account
@@ -56,11 +87,44 @@ pub enum TokenWrapInstruction {
     ///    wrapped_token_program)`
     /// 8. `[s]` Transfer authority on unwrapped token account. Not required to
     ///    be a signer if it's a multisig.
-    /// 9. `..8+M` `[s]` (Optional) M multisig signers on unwrapped token
-    ///    account.
+    /// 9. `[]` Canonical pointer PDA, address must be:
+    ///    `get_canonical_pointer_address(unwrapped_mint_address)`. May be
+    ///    uninitialized.
+    /// 10. `[]` Scoped canonical pointer PDA, address must be:
+    ///     `get_scoped_canonical_pointer_address(unwrapped_mint_address,
+    ///     wrapped_token_program_id)`. May be uninitialized, in which case the
+    ///     unscoped pointer applies.
+    /// 11. `[w]` Wrap limits PDA, address must be:
+    ///     `get_wrap_limits_address(unwrapped_mint_address)`. May be
+    ///     uninitialized, in which case wrapping is not limited.
+    /// 12. `[]` Allowlist PDA, address must be:
+    ///     `get_allowlist_address(unwrapped_mint_address)`. May be
+    ///     uninitialized, in which case the allowlist mode is disabled.
+    /// 13. `[]` Allowlist entry PDA of the owner of the recipient
+    ///     wrapped token account, address must be:
+    ///     `get_allowlist_entry_address(unwrapped_mint_address, owner)`. May
+    ///     be uninitialized.
+    /// 14. `[]` Protocol fee config PDA, address must be:
+    ///     `get_protocol_fee_config_address()`. May be uninitialized, in which
+    ///     case no protocol fee is charged.
+    /// 15. `[]` Protocol fee override PDA, address must be:
+    ///     `get_protocol_fee_override_address(unwrapped_mint_address)`. May be
+    ///     uninitialized.
+    /// 16. `[w]` Protocol fee vault, a token account of the unwrapped mint
+    ///     owned by the fee destination of the protocol fee config. Only
+    ///     checked if a protocol fee is charged.
+    /// 17. `[w]` (Optional) Wrap statistics PDA, only present if
+    ///     `record_stats` is set. Address must be:
+    ///     `get_wrap_stats_address(wrapped_mint)`. Created if uninitialized,
+    ///     in which case it must be pre-funded for rent.
+    /// 18. `..18+M` `[s]` (Optional) M multisig signers on unwrapped token
+    ///     account. Starting after the last optional account present.
     Wrap {
         /// little-endian `u64` representing the amount to wrap
         amount: u64,
+        /// If true, record the operation in the wrap statistics account.
+        /// Encoded as an optional trailing byte, omitted when false.
+        record_stats: bool,
     },
 
     /// Unwrap tokens
@@ -68,6 +132,19 @@ pub enum TokenWrapInstruction {
     /// Burn user wrapped tokens and transfer the same amount of unwrapped
     /// tokens from the escrow account to the provided account.
     ///
+    /// If the escrow holds fewer unwrapped tokens than the wrapped mint
+    /// supply, the escrow is under-collateralized and the unwrap switches to
+    /// pro-rata redemption: `amount * escrow_balance / wrapped_supply`
+    /// unwrapped tokens are transferred for the burned wrapped tokens, and the
+    /// redemption is recorded in the pro-rata redemption account, which must
+    /// then be provided. Unwrapping is rejected while a `FlashMint` of the
+    /// wrapped mint awaits repayment, as the loan is not backed by the escrow.
+    ///
+    /// If the deployment charges a protocol fee, the fee portion of the
+    /// released unwrapped tokens is transferred to the protocol fee vault and
+    /// the rest to the recipient. The split is logged and set as the return
+    /// data, as a `ProtocolFeeSplit`.
+    ///
     /// Accounts expected by this instruction:
     /// 0. `[w]` Escrow of unwrapped tokens, address must be an `ATA`:
     ///    `get_escrow_address(unwrapped_mint, unwrapped_token_program,
@@ -83,10 +160,50 @@ pub enum TokenWrapInstruction {
     ///    `get_wrapped_mint_address(unwrapped_mint_address,
     ///    wrapped_token_program_id)`
     /// 8. `[s]` Transfer authority on wrapped token account
-    /// 9. `..8+M` `[s]` (Optional) M multisig signers on wrapped token account
+    /// 9. `[w]` Wrap limits PDA, address must be:
+    ///    `get_wrap_limits_address(unwrapped_mint_address)`. May be
+    ///    uninitialized. The unwrapped amount is subtracted from its total
+    ///    wrapped.
+    /// 10. `[]` Allowlist PDA, address must be:
+    ///     `get_allowlist_address(unwrapped_mint_address)`. May be
+    ///     uninitialized, in which case the allowlist mode is disabled.
+    /// 11. `[]` Allowlist entry PDA of the owner of the wrapped
+    ///     token account, address must be:
+    ///     `get_allowlist_entry_address(unwrapped_mint_address, owner)`. May
+    ///     be uninitialized.
+    /// 12. `[]` Protocol fee config PDA, address must be:
+    ///     `get_protocol_fee_config_address()`. May be uninitialized, in which
+    ///     case no protocol fee is charged.
+    /// 13. `[]` Protocol fee override PDA, address must be:
+    ///     `get_protocol_fee_override_address(unwrapped_mint_address)`. May be
+    ///     uninitialized.
+    /// 14. `[w]` Protocol fee vault, a token account of the unwrapped mint
+    ///     owned by the fee destination of the protocol fee config. Only
+    ///     checked if a protocol fee is charged.
+    /// 15. `[]` Flash mint guard PDA, address must be:
+    ///     `get_flash_mint_guard_address(wrapped_mint)`. May be
+    ///     uninitialized.
+    /// 16. `[w]` (Optional) Wrap statistics PDA, only present if
+    ///     `record_stats` is set. Address must be:
+    ///     `get_wrap_stats_address(wrapped_mint)`. Created if uninitialized,
+    ///     in which case it must be pre-funded for rent.
+    /// 17. `[w]` (Optional) Pro-rata redemption PDA, only present if
+    ///     `pro_rata` is set. Address must be:
+    ///     `get_pro_rata_redemption_address(wrapped_mint)`. Created if
+    ///     uninitialized, in which case it must be pre-funded for rent.
+    /// 18. `..18+M` `[s]` (Optional) M multisig signers on wrapped token
+    ///     account. Each optional account starts right after the last
+    ///     optional account present.
     Unwrap {
         /// little-endian `u64` representing the amount to unwrap
         amount: u64,
+        /// If true, record the operation in the wrap statistics account.
+        /// Encoded as an optional trailing byte, omitted when false.
+        record_stats: bool,
+        /// If true, the pro-rata redemption account is provided and the
+        /// unwrap may redeem pro rata. Encoded as a second optional trailing
+        /// byte, omitted when false.
+        pro_rata: bool,
     },
 
     /// Closes a stuck escrow `ATA`. This is for the edge case where an
@@ -181,220 +298,2701 @@ pub enum TokenWrapInstruction {
     /// A `CanonicalPointer` PDA allows a mint authority to signal on-chain
     /// another Token Wrap deployment is the "canonical" one for the mint.
     ///
+    /// If `enforce_on_wrap` is set, `Wrap` will be rejected by every deployment
+    /// other than the canonical one. `Unwrap` is never restricted.
+    ///
+    /// If `change_delay` is non-zero, later changes to the pointer, including
+    /// lowering the delay or closing it, can only be made through
+    /// `ProposeCanonicalPointer` and `AcceptCanonicalPointer`. This
+    /// instruction fails on such a pointer.
+    ///
+    /// If `wrapped_token_program` is set, the pointer only applies to the
+    /// wrapped mint for that token program and is stored at
+    /// `get_scoped_canonical_pointer_address`. A scoped pointer takes
+    /// precedence over the unscoped one; clients and `Wrap` fall back to the
+    /// unscoped pointer when no scoped pointer exists. While the unscoped
+    /// pointer has a `change_delay`, this instruction also fails on every
+    /// scoped pointer.
+    ///
     /// If calling for the first time, the client is responsible for pre-funding
-    /// the rent for the PDA that will be initialized.
+    /// the rent for the PDA that will be initialized. Pointers created with an
+    /// older, smaller layout are resized on update, so the client must top up
+    /// the rent for the additional space.
     ///
-    /// If no mint authority exists on the unwrapped mint, this instruction will
-    /// fail.
+    /// The pointer authority is the mint authority of the unwrapped mint. If the
+    /// mint authority has been revoked, the update authority of the mint's
+    /// `TokenMetadata` or `Metaplex` metadata is used instead. In either case,
+    /// the authority may be an SPL Token multisig.
+    ///
+    /// If scoped, the unscoped `CanonicalPointer` PDA account, which may be
+    /// uninitialized, follows account 3 and shifts the optional accounts by
+    /// one. Its address must be:
+    /// `get_canonical_pointer_address(unwrapped_mint_address)`.
     ///
     /// Accounts expected:
-    /// 0. `[s]` Unwrapped mint authority
+    /// 0. `[s]` Pointer authority, the unwrapped mint authority or, if the mint
+    ///    has none, the metadata update authority. Not a signer if multisig.
     /// 1. `[w]` `CanonicalPointer` PDA account to create or update, address
-    ///    must be: `get_canonical_pointer_address(unwrapped_mint_address)`
+    ///    must be: `get_canonical_pointer_address(unwrapped_mint_address)` or,
+    ///    if scoped, `get_scoped_canonical_pointer_address(
+    ///    unwrapped_mint_address, wrapped_token_program)`
     /// 2. `[]` Unwrapped mint
     /// 3. `[]` System program
+    /// 4. `[]` (Optional) Source metadata account. Required if the unwrapped
+    ///    mint has no mint authority. If a Token-2022 mint holds its own
+    ///    metadata, pass the mint.
+    /// 5. `[]` (Optional) Owner program. Required when the metadata account is
+    ///    owned by a third-party program.
+    /// 6. `..6+M` `[s]` (Optional) M multisig signer accounts
     SetCanonicalPointer {
         /// The program ID to set as canonical
         program_id: Pubkey,
+        /// If true, non-canonical deployments reject `Wrap`. False in the
+        /// original instruction data, which only holds the program ID.
+        enforce_on_wrap: bool,
+        /// Minimum number of seconds between proposing and accepting later
+        /// changes. Zero in the original instruction data.
+        change_delay: u64,
+        /// If set, the pointer only applies to wrapped mints of this token
+        /// program. Only encoded after all other fields.
+        wrapped_token_program: Option<Pubkey>,
     },
-}
 
-impl TokenWrapInstruction {
-    /// Packs a [`TokenWrapInstruction`](enum.TokenWrapInstruction.html) into a
-    /// byte array.
-    pub fn pack(&self) -> Vec<u8> {
-        let mut buf = Vec::new();
-        match self {
-            TokenWrapInstruction::CreateMint { idempotent } => {
-                buf.push(0);
-                buf.push(if *idempotent { 1 } else { 0 });
-            }
+    /// Closes the canonical program pointer for a mint, returning its rent to
+    /// the destination account.
+    ///
+    /// Once closed, clients should treat every deployment as canonical for the
+    /// mint, the same as if a pointer had never been set. The pointer can be
+    /// re-created later with `SetCanonicalPointer`.
+    ///
+    /// Fails if the pointer has a `change_delay`, or if it is scoped and the
+    /// unscoped pointer has one. The delay must first be lowered to zero
+    /// through a proposal.
+    ///
+    /// The pointer authority is resolved the same way as in
+    /// `SetCanonicalPointer`.
+    ///
+    /// If scoped, the unscoped `CanonicalPointer` PDA account, which may be
+    /// uninitialized, follows account 3 and shifts the optional accounts by
+    /// one. Its address must be:
+    /// `get_canonical_pointer_address(unwrapped_mint_address)`.
+    ///
+    /// Accounts expected:
+    /// 0. `[s]` Pointer authority, the unwrapped mint authority or, if the mint
+    ///    has none, the metadata update authority. Not a signer if multisig.
+    /// 1. `[w]` `CanonicalPointer` PDA account to close, address must be:
+    ///    `get_canonical_pointer_address(unwrapped_mint_address)` or, if
+    ///    scoped, `get_scoped_canonical_pointer_address(
+    ///    unwrapped_mint_address, wrapped_token_program)`
+    /// 2. `[]` Unwrapped mint
+    /// 3. `[w]` Destination account for the reclaimed lamports
+    /// 4. `[]` (Optional) Source metadata account. Required if the unwrapped
+    ///    mint has no mint authority.
+    /// 5. `[]` (Optional) Owner program. Required when the metadata account is
+    ///    owned by a third-party program.
+    /// 6. `..6+M` `[s]` (Optional) M multisig signer accounts
+    CloseCanonicalPointer {
+        /// The wrapped token program the pointer is scoped to, if any
+        wrapped_token_program: Option<Pubkey>,
+    },
 
-            TokenWrapInstruction::Wrap { amount } => {
-                buf.push(1);
-                buf.extend_from_slice(&amount.to_le_bytes());
-            }
-            TokenWrapInstruction::Unwrap { amount } => {
-                buf.push(2);
-                buf.extend_from_slice(&amount.to_le_bytes());
-            }
-            TokenWrapInstruction::CloseStuckEscrow => {
-                buf.push(3);
-            }
-            TokenWrapInstruction::SyncMetadataToToken2022 => {
-                buf.push(4);
-            }
-            TokenWrapInstruction::SyncMetadataToSplToken => {
-                buf.push(5);
-            }
-            TokenWrapInstruction::SetCanonicalPointer { program_id } => {
-                buf.push(6);
-                buf.extend_from_slice(program_id.as_ref());
-            }
-        }
-        buf
-    }
+    /// Proposes a change to the canonical program pointer for a mint.
+    ///
+    /// The proposal is stored in the `PendingCanonicalPointer` PDA along with
+    /// the current slot and the timestamp from which it can be accepted, which
+    /// is the current time plus the `change_delay` of the existing pointer.
+    /// Proposing again replaces the previous proposal and restarts the delay.
+    ///
+    /// A scoped pointer is held to at least the `change_delay` of the
+    /// unscoped pointer, both for the proposal itself and for the delay of the
+    /// proposed pointer.
+    ///
+    /// If calling for the first time, the client is responsible for pre-funding
+    /// the rent for the pending PDA. On acceptance, these lamports move to the
+    /// pointer account and cover its rent.
+    ///
+    /// The pointer authority is resolved the same way as in
+    /// `SetCanonicalPointer`.
+    ///
+    /// If scoped, the unscoped `CanonicalPointer` PDA account, which may be
+    /// uninitialized, follows account 4 and shifts the optional accounts by
+    /// one. Its address must be:
+    /// `get_canonical_pointer_address(unwrapped_mint_address)`.
+    ///
+    /// Accounts expected:
+    /// 0. `[s]` Pointer authority, the unwrapped mint authority or, if the mint
+    ///    has none, the metadata update authority. Not a signer if multisig.
+    /// 1. `[]` `CanonicalPointer` PDA account, may be uninitialized. Address
+    ///    must be: `get_canonical_pointer_address(unwrapped_mint_address)`
+    ///    or, if scoped, the scoped pointer address
+    /// 2. `[w]` `PendingCanonicalPointer` PDA account to create or update,
+    ///    address must be:
+    ///    `get_pending_canonical_pointer_address(unwrapped_mint_address)`
+    ///    or, if scoped, the scoped pending pointer address
+    /// 3. `[]` Unwrapped mint
+    /// 4. `[]` System program
+    /// 5. `[]` (Optional) Source metadata account. Required if the unwrapped
+    ///    mint has no mint authority.
+    /// 6. `[]` (Optional) Owner program. Required when the metadata account is
+    ///    owned by a third-party program.
+    /// 7. `..7+M` `[s]` (Optional) M multisig signer accounts
+    ProposeCanonicalPointer {
+        /// The program ID to set as canonical
+        program_id: Pubkey,
+        /// If true, non-canonical deployments reject `Wrap`
+        enforce_on_wrap: bool,
+        /// Minimum number of seconds between proposing and accepting later
+        /// changes
+        change_delay: u64,
+        /// If set, the proposal targets the pointer scoped to this wrapped
+        /// token program. Only encoded after all other fields.
+        wrapped_token_program: Option<Pubkey>,
+    },
 
-    /// Unpacks a byte array into a
-    /// [`TokenWrapInstruction`](enum.TokenWrapInstruction.html).
-    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
-        match input.split_first() {
-            Some((&0, rest)) if rest.len() == 1 => {
-                let idempotent = match rest[0] {
-                    0 => false,
-                    1 => true,
-                    _ => return Err(ProgramError::InvalidInstructionData),
-                };
-                Ok(TokenWrapInstruction::CreateMint { idempotent })
-            }
-            Some((&1, rest)) if rest.len() == 8 => {
-                let amount = u64::from_le_bytes(rest.try_into().unwrap());
-                Ok(TokenWrapInstruction::Wrap { amount })
-            }
-            Some((&2, rest)) if rest.len() == 8 => {
-                let amount = u64::from_le_bytes(rest.try_into().unwrap());
-                Ok(TokenWrapInstruction::Unwrap { amount })
-            }
-            Some((&3, [])) => Ok(TokenWrapInstruction::CloseStuckEscrow),
-            Some((&4, [])) => Ok(TokenWrapInstruction::SyncMetadataToToken2022),
-            Some((&5, [])) => Ok(TokenWrapInstruction::SyncMetadataToSplToken),
-            Some((&6, rest)) if rest.len() == 32 => {
-                let program_id = Pubkey::new_from_array(rest.try_into().unwrap());
-                Ok(TokenWrapInstruction::SetCanonicalPointer { program_id })
-            }
-            _ => Err(ProgramError::InvalidInstructionData),
-        }
-    }
-}
+    /// Applies a proposed canonical pointer change once its activation time
+    /// has passed. Anyone may call this instruction.
+    ///
+    /// The `PendingCanonicalPointer` PDA is closed and its lamports are moved
+    /// to the `CanonicalPointer` PDA, which is created if needed.
+    ///
+    /// Accounts expected:
+    /// 0. `[w]` `CanonicalPointer` PDA account to create or update
+    /// 1. `[w]` `PendingCanonicalPointer` PDA account to apply and close
+    /// 2. `[]` Unwrapped mint
+    /// 3. `[]` System program
+    AcceptCanonicalPointer {
+        /// The wrapped token program the pointer is scoped to, if any
+        wrapped_token_program: Option<Pubkey>,
+    },
 
-/// Creates `CreateMint` instruction.
-pub fn create_mint(
-    program_id: &Pubkey,
-    wrapped_mint_address: &Pubkey,
-    wrapped_backpointer_address: &Pubkey,
-    unwrapped_mint_address: &Pubkey,
-    wrapped_token_program_id: &Pubkey,
-    idempotent: bool,
-) -> Instruction {
-    let accounts = vec![
-        AccountMeta::new(*wrapped_mint_address, false),
-        AccountMeta::new(*wrapped_backpointer_address, false),
-        AccountMeta::new_readonly(*unwrapped_mint_address, false),
-        AccountMeta::new_readonly(solana_system_interface::program::id(), false),
-        AccountMeta::new_readonly(*wrapped_token_program_id, false),
-    ];
-    let data = TokenWrapInstruction::CreateMint { idempotent }.pack();
-    Instruction::new_with_bytes(*program_id, &data, accounts)
-}
+    /// Withdraws a proposed canonical pointer change, returning the rent of the
+    /// `PendingCanonicalPointer` PDA to the destination account.
+    ///
+    /// The pointer authority is resolved the same way as in
+    /// `SetCanonicalPointer`.
+    ///
+    /// Accounts expected:
+    /// 0. `[s]` Pointer authority, the unwrapped mint authority or, if the mint
+    ///    has none, the metadata update authority. Not a signer if multisig.
+    /// 1. `[w]` `PendingCanonicalPointer` PDA account to close
+    /// 2. `[]` Unwrapped mint
+    /// 3. `[w]` Destination account for the reclaimed lamports
+    /// 4. `[]` (Optional) Source metadata account. Required if the unwrapped
+    ///    mint has no mint authority.
+    /// 5. `[]` (Optional) Owner program. Required when the metadata account is
+    ///    owned by a third-party program.
+    /// 6. `..6+M` `[s]` (Optional) M multisig signer accounts
+    CancelCanonicalPointer {
+        /// The wrapped token program the pointer is scoped to, if any
+        wrapped_token_program: Option<Pubkey>,
+    },
 
-/// Creates `Wrap` instruction.
-#[allow(clippy::too_many_arguments)]
-pub fn wrap(
-    program_id: &Pubkey,
-    recipient_wrapped_token_account_address: &Pubkey,
-    wrapped_mint_address: &Pubkey,
-    wrapped_mint_authority_address: &Pubkey,
-    unwrapped_token_program_id: &Pubkey,
-    wrapped_token_program_id: &Pubkey,
-    unwrapped_token_account_address: &Pubkey,
-    unwrapped_mint_address: &Pubkey,
-    unwrapped_escrow_address: &Pubkey,
-    transfer_authority_address: &Pubkey,
-    multisig_signer_pubkeys: &[&Pubkey],
-    amount: u64,
-) -> Instruction {
-    let mut accounts = vec![
-        AccountMeta::new(*recipient_wrapped_token_account_address, false),
-        AccountMeta::new(*wrapped_mint_address, false),
-        AccountMeta::new_readonly(*wrapped_mint_authority_address, false),
-        AccountMeta::new_readonly(*unwrapped_token_program_id, false),
-        AccountMeta::new_readonly(*wrapped_token_program_id, false),
-        AccountMeta::new(*unwrapped_token_account_address, false),
-        AccountMeta::new_readonly(*unwrapped_mint_address, false),
-        AccountMeta::new(*unwrapped_escrow_address, false),
-        AccountMeta::new_readonly(
-            *transfer_authority_address,
-            multisig_signer_pubkeys.is_empty(),
-        ),
-    ];
-    for signer_pubkey in multisig_signer_pubkeys.iter() {
-        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
-    }
+    /// Rewrites a program-owned state account created with an older layout
+    /// into the current, versioned layout. Anyone may call this instruction.
+    /// Accounts already in the current layout are left untouched.
+    ///
+    /// The type of the account is determined from its address, so the account
+    /// it is derived from must be provided.
+    ///
+    /// Backpointers are backfilled with the provenance data that can be read
+    /// from the mints. The customizer, creation slot and program version of
+    /// existing wrapped mints are unknown and left zeroed.
+    ///
+    /// The current layout is larger, so the client is responsible for topping
+    /// up the rent of the account before calling this instruction.
+    ///
+    /// Accounts expected:
+    /// 0. `[w]` `Backpointer` or unscoped `CanonicalPointer` PDA account to
+    ///    migrate
+    /// 1. `[]` Wrapped mint for a `Backpointer`, otherwise the unwrapped mint
+    /// 2. `[]` Unwrapped mint, only for a `Backpointer`
+    MigrateState,
 
-    let data = TokenWrapInstruction::Wrap { amount }.pack();
-    Instruction::new_with_bytes(*program_id, &data, accounts)
-}
+    /// Closes a retired wrapped mint and its backpointer, sending their
+    /// lamports to the destination account. Anyone may call this instruction.
+    ///
+    /// The unwrapped mint must have been closed, the wrapped mint must have no
+    /// supply, and the escrow must be empty or closed. Only Token-2022 wrapped
+    /// mints whose `MintCloseAuthority` is the wrapped mint authority can be
+    /// closed, like the ones created with the default mint customizer.
+    ///
+    /// The wrapped mint stays listed in the registry.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    /// 0. `[w]` Wrapped mint to close
+    /// 1. `[w]` Backpointer of the wrapped mint, address must be:
+    ///    `get_wrapped_mint_backpointer_address(wrapped_mint_address)`
+    /// 2. `[w]` Destination for lamports from the closed accounts
+    /// 3. `[]` Unwrapped mint, must be closed
+    /// 4. `[]` Escrow of unwrapped tokens, address must be:
+    ///    `get_escrow_address(unwrapped_mint, unwrapped_token_program,
+    ///    wrapped_token_program)`. May be closed.
+    /// 5. `[]` Wrapped mint authority (PDA)
+    /// 6. `[]` SPL Token program for the unwrapped mint
+    /// 7. `[]` Token-2022 program
+    CloseWrappedMint,
 
-/// Creates `Unwrap` instruction.
-#[allow(clippy::too_many_arguments)]
-pub fn unwrap(
-    program_id: &Pubkey,
-    unwrapped_escrow_address: &Pubkey,
-    recipient_unwrapped_token_account_address: &Pubkey,
-    wrapped_mint_authority_address: &Pubkey,
-    unwrapped_mint_address: &Pubkey,
-    wrapped_token_program_id: &Pubkey,
-    unwrapped_token_program_id: &Pubkey,
-    wrapped_token_account_address: &Pubkey,
-    wrapped_mint_address: &Pubkey,
-    transfer_authority_address: &Pubkey,
-    multisig_signer_pubkeys: &[&Pubkey],
-    amount: u64,
-) -> Instruction {
-    let mut accounts = vec![
-        AccountMeta::new(*unwrapped_escrow_address, false),
-        AccountMeta::new(*recipient_unwrapped_token_account_address, false),
-        AccountMeta::new_readonly(*wrapped_mint_authority_address, false),
-        AccountMeta::new_readonly(*unwrapped_mint_address, false),
-        AccountMeta::new_readonly(*wrapped_token_program_id, false),
-        AccountMeta::new_readonly(*unwrapped_token_program_id, false),
-        AccountMeta::new(*wrapped_token_account_address, false),
-        AccountMeta::new(*wrapped_mint_address, false),
-        AccountMeta::new_readonly(
-            *transfer_authority_address,
-            multisig_signer_pubkeys.is_empty(),
-        ),
-    ];
-    for signer_pubkey in multisig_signer_pubkeys.iter() {
-        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
-    }
+    /// Repairs the escrow `ATA` of a Token-2022 unwrapped mint in place. This
+    /// is for the case where the unwrapped mint now requires account
+    /// extensions the escrow `ATA` does not have, e.g. after being closed and
+    /// re-created at the same address with different extensions.
+    ///
+    /// Uses the Token-2022 `Reallocate` instruction, signed by the wrapped
+    /// mint authority, to add the missing account extensions. Unlike
+    /// `CloseStuckEscrow`, this works while the escrow holds tokens. The payer
+    /// funds the additional rent. Note that `Reallocate` allocates the space
+    /// for the extensions but does not initialize their state.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    /// 0. `[w]` Escrow account to repair (`ATA`)
+    /// 1. `[w, s]` Payer of the additional rent
+    /// 2. `[]` Unwrapped mint
+    /// 3. `[]` Wrapped mint
+    /// 4. `[]` Wrapped mint authority (PDA)
+    /// 5. `[]` System program
+    /// 6. `[]` Token-2022 program
+    RepairEscrow,
 
-    let data = TokenWrapInstruction::Unwrap { amount }.pack();
-    Instruction::new_with_bytes(*program_id, &data, accounts)
-}
+    /// Sets the policy `ReconcileEscrow` applies to surplus unwrapped tokens
+    /// in the escrow of a wrapped mint, i.e. tokens sent straight to the
+    /// escrow that are not backed by wrapped supply.
+    ///
+    /// If calling for the first time, the client is responsible for pre-funding
+    /// the rent for the PDA that will be initialized.
+    ///
+    /// The policy authority is the mint authority of the unwrapped mint, with
+    /// the same metadata and multisig fallbacks as `SetCanonicalPointer`.
+    ///
+    /// Accounts expected:
+    /// 0. `[s]` Policy authority, the unwrapped mint authority or, if the mint
+    ///    has none, the metadata update authority. Not a signer if multisig.
+    /// 1. `[w]` `EscrowSurplusPolicy` PDA account to create or update, address
+    ///    must be: `get_escrow_surplus_policy_address(wrapped_mint_address)`
+    /// 2. `[]` Unwrapped mint
+    /// 3. `[]` Wrapped mint
+    /// 4. `[]` System program
+    /// 5. `[]` (Optional) Source metadata account. Required if the unwrapped
+    ///    mint has no mint authority.
+    /// 6. `[]` (Optional) Owner program. Required when the metadata account is
+    ///    owned by a third-party program.
+    /// 7. `..7+M` `[s]` (Optional) M multisig signer accounts
+    SetEscrowSurplusPolicy {
+        /// What to do with the surplus
+        action: SurplusAction,
+        /// Wrapped token account receiving minted surplus, or the claimant
+        /// authority that must sign for returned surplus
+        target: Pubkey,
+    },
 
-/// Creates `CloseStuckEscrow` instruction.
-pub fn close_stuck_escrow(
-    program_id: &Pubkey,
-    escrow_address: &Pubkey,
-    destination_address: &Pubkey,
-    unwrapped_mint_address: &Pubkey,
-    wrapped_mint_address: &Pubkey,
-    wrapped_mint_authority_address: &Pubkey,
-) -> Instruction {
-    let accounts = vec![
-        AccountMeta::new(*escrow_address, false),
-        AccountMeta::new(*destination_address, false),
-        AccountMeta::new_readonly(*unwrapped_mint_address, false),
-        AccountMeta::new_readonly(*wrapped_mint_address, false),
-        AccountMeta::new_readonly(*wrapped_mint_authority_address, false),
-        AccountMeta::new_readonly(spl_token_2022_interface::id(), false),
-    ];
-    let data = TokenWrapInstruction::CloseStuckEscrow.pack();
-    Instruction::new_with_bytes(*program_id, &data, accounts)
-}
+    /// Applies the escrow surplus policy to the unwrapped tokens the escrow
+    /// holds beyond the wrapped supply. The surplus is either minted as
+    /// wrapped tokens to the recipient of the policy, or transferred to a
+    /// token account of the claimant of the policy, who must sign. Afterwards
+    /// the escrow must still fully back the wrapped supply.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[w]` Escrow of unwrapped tokens, address must be an `ATA`:
+    ///    `get_escrow_address(unwrapped_mint, unwrapped_token_program,
+    ///    wrapped_token_program)`
+    /// 1. `[]` Unwrapped mint
+    /// 2. `[w]` Wrapped mint, address must be:
+    ///    `get_wrapped_mint_address(unwrapped_mint_address,
+    ///    wrapped_token_program_id)`
+    /// 3. `[]` Wrapped mint authority, address must be:
+    ///    `get_wrapped_mint_authority(wrapped_mint)`
+    /// 4. `[]` `EscrowSurplusPolicy` PDA, address must be:
+    ///    `get_escrow_surplus_policy_address(wrapped_mint)`
+    /// 5. `[w]` Destination: the recipient wrapped token account of the policy
+    ///    when minting, or an unwrapped token account when returning
+    /// 6. `[]` SPL Token program for unwrapped mint
+    /// 7. `[]` SPL Token program for wrapped mint
+    /// 8. `[w]` Wrap limits PDA, address must be:
+    ///    `get_wrap_limits_address(unwrapped_mint_address)`. May be
+    ///    uninitialized. Minting the surplus is subject to the limits.
+    /// 9. `[s]` (Optional) Claimant authority of the policy, only present if
+    ///    the policy returns the surplus
+    /// 10. `..10+M` `[]` (Optional) Additional accounts required by the
+    ///     transfer hook of the unwrapped mint when returning
+    ReconcileEscrow,
 
-/// Creates `SyncMetadataToToken2022` instruction.
-pub fn sync_metadata_to_token_2022(
-    program_id: &Pubkey,
-    wrapped_mint: &Pubkey,
-    wrapped_mint_authority: &Pubkey,
-    unwrapped_mint: &Pubkey,
-    source_metadata: Option<&Pubkey>,
-    owner_program: Option<&Pubkey>,
-) -> Instruction {
-    let mut accounts = vec![
-        AccountMeta::new(*wrapped_mint, false),
+    /// Wrap native SOL directly from lamports
+    ///
+    /// Transfer lamports from a funding account into the escrow of the native
+    /// mint, sync its token balance with `SyncNative`, and mint the same
+    /// amount of wrapped tokens to the recipient.
+    ///
+    /// The protocol fee is charged like in `Wrap`: its portion of the
+    /// deposited lamports is moved from the escrow to the protocol fee vault
+    /// and only the rest is minted. The split is logged and set as the return
+    /// data, as a `ProtocolFeeSplit`.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[w]` Recipient wrapped token account
+    /// 1. `[w]` Wrapped mint, address must be:
+    ///    `get_wrapped_mint_address(native_mint_address,
+    ///    wrapped_token_program_id)`
+    /// 2. `[]` Wrapped mint authority, address must be:
+    ///    `get_wrapped_mint_authority(wrapped_mint)`
+    /// 3. `[]` SPL Token program for the native mint
+    /// 4. `[]` SPL Token program for wrapped mint
+    /// 5. `[]` Native mint of the unwrapped token program
+    /// 6. `[w]` Escrow of the native mint, address must be an `ATA`:
+    ///    `get_escrow_address(native_mint, unwrapped_token_program,
+    ///    wrapped_token_program)`
+    /// 7. `[]` Canonical pointer PDA, address must be:
+    ///    `get_canonical_pointer_address(native_mint_address)`. May be
+    ///    uninitialized.
+    /// 8. `[]` Scoped canonical pointer PDA, address must be:
+    ///    `get_scoped_canonical_pointer_address(native_mint_address,
+    ///    wrapped_token_program_id)`. May be uninitialized.
+    /// 9. `[w]` Wrap limits PDA, address must be:
+    ///    `get_wrap_limits_address(native_mint_address)`. May be
+    ///    uninitialized.
+    /// 10. `[]` Allowlist PDA, address must be:
+    ///     `get_allowlist_address(native_mint_address)`. May be
+    ///     uninitialized.
+    /// 11. `[]` Allowlist entry PDA of the owner of the recipient wrapped
+    ///     token account, address must be:
+    ///     `get_allowlist_entry_address(native_mint_address, owner)`. May be
+    ///     uninitialized.
+    /// 12. `[]` Protocol fee config PDA, address must be:
+    ///     `get_protocol_fee_config_address()`. May be uninitialized, in which
+    ///     case no protocol fee is charged.
+    /// 13. `[]` Protocol fee override PDA, address must be:
+    ///     `get_protocol_fee_override_address(native_mint_address)`. May be
+    ///     uninitialized.
+    /// 14. `[w]` Protocol fee vault, a native token account owned by the fee
+    ///     destination of the protocol fee config. Only checked if a protocol
+    ///     fee is charged.
+    /// 15. `[ws]` Funding account, lamports are transferred from it
+    /// 16. `[]` System program
+    WrapSol {
+        /// little-endian `u64` representing the lamports to wrap
+        amount: u64,
+    },
+
+    /// Unwrap tokens into native SOL
+    ///
+    /// Burn user wrapped tokens, transfer the same amount from the escrow of
+    /// the native mint to a temporary native token account and close it,
+    /// paying out the lamports. The temporary account is created and closed
+    /// within the instruction. Its rent is fronted by the payer, who receives
+    /// the lamports of the closed account and forwards the unwrapped amount to
+    /// the recipient.
+    ///
+    /// As in `Unwrap`, an under-collateralized escrow switches to pro-rata
+    /// redemption, which requires the pro-rata redemption account, and
+    /// unwrapping is rejected while a `FlashMint` awaits repayment.
+    ///
+    /// The protocol fee is charged like in `Unwrap`: its portion of the
+    /// released lamports stays in the protocol fee vault as wrapped SOL and
+    /// only the rest is paid out. The split is logged and set as the return
+    /// data, as a `ProtocolFeeSplit`.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[w]` Escrow of the native mint, address must be an `ATA`:
+    ///    `get_escrow_address(native_mint, unwrapped_token_program,
+    ///    wrapped_token_program)`
+    /// 1. `[w]` Recipient of the unwrapped lamports
+    /// 2. `[]` Wrapped mint authority, address must be:
+    ///    `get_wrapped_mint_authority(wrapped_mint)`
+    /// 3. `[]` Native mint of the unwrapped token program
+    /// 4. `[]` SPL Token program for wrapped mint
+    /// 5. `[]` SPL Token program for the native mint
+    /// 6. `[w]` Wrapped token account to unwrap
+    /// 7. `[w]` Wrapped mint, address must be:
+    ///    `get_wrapped_mint_address(native_mint_address,
+    ///    wrapped_token_program_id)`
+    /// 8. `[s]` Transfer authority on wrapped token account
+    /// 9. `[w]` Wrap limits PDA, address must be:
+    ///    `get_wrap_limits_address(native_mint_address)`. May be
+    ///    uninitialized.
+    /// 10. `[]` Allowlist PDA, address must be:
+    ///     `get_allowlist_address(native_mint_address)`. May be
+    ///     uninitialized.
+    /// 11. `[]` Allowlist entry PDA of the owner of the wrapped token account,
+    ///     address must be:
+    ///     `get_allowlist_entry_address(native_mint_address, owner)`. May be
+    ///     uninitialized.
+    /// 12. `[]` Protocol fee config PDA, address must be:
+    ///     `get_protocol_fee_config_address()`. May be uninitialized, in which
+    ///     case no protocol fee is charged.
+    /// 13. `[]` Protocol fee override PDA, address must be:
+    ///     `get_protocol_fee_override_address(native_mint_address)`. May be
+    ///     uninitialized.
+    /// 14. `[w]` Protocol fee vault, a native token account owned by the fee
+    ///     destination of the protocol fee config. Only checked if a protocol
+    ///     fee is charged.
+    /// 15. `[]` Flash mint guard PDA, address must be:
+    ///     `get_flash_mint_guard_address(wrapped_mint)`. May be
+    ///     uninitialized.
+    /// 16. `[w]` Temporary native token account, address must be:
+    ///     `get_native_unwrap_account_address(wrapped_mint)`
+    /// 17. `[ws]` Payer of the rent of the temporary account
+    /// 18. `[]` System program
+    /// 19. `[w]` (Optional) Pro-rata redemption PDA, only present if
+    ///     `pro_rata` is set. Address must be:
+    ///     `get_pro_rata_redemption_address(wrapped_mint)`. Created if
+    ///     uninitialized, in which case it must be pre-funded for rent.
+    /// 20. `..20+M` `[s]` (Optional) M multisig signers on wrapped token
+    ///     account, starting right after the pro-rata redemption PDA if
+    ///     present
+    UnwrapSol {
+        /// little-endian `u64` representing the amount to unwrap
+        amount: u64,
+        /// If true, the pro-rata redemption account is provided and the
+        /// unwrap may redeem pro rata. Encoded as an optional trailing byte,
+        /// omitted when false.
+        pro_rata: bool,
+    },
+
+    /// Wrap tokens into the confidential pending balance of the recipient
+    ///
+    /// Performs a `Wrap` into a Token-2022 wrapped mint and deposits the
+    /// minted tokens into the confidential pending balance of the recipient
+    /// with a confidential transfer `Deposit`. The recipient account must be
+    /// configured to receive confidential deposits, and its owner must sign.
+    /// The owner still applies the pending balance with
+    /// `ApplyPendingBalance`. The wrapped amount remains visible in the
+    /// instruction data.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `..17` `[]` Accounts 0 through 16 of `Wrap`, with the policy accounts
+    /// 17. `[s]` Owner of the recipient wrapped token account
+    /// 18. `..18+M` `[s]` (Optional) M multisig signers on unwrapped token
+    ///     account, as for `Wrap`
+    WrapConfidential {
+        /// little-endian `u64` representing the amount to wrap
+        amount: u64,
+    },
+
+    /// Unwrap tokens from a confidential available balance
+    ///
+    /// Withdraws the amount from the confidential available balance of a
+    /// Token-2022 wrapped token account with a confidential transfer
+    /// `Withdraw`, then performs an `Unwrap` of the same amount. The
+    /// equality and range proofs of the withdraw must already be verified
+    /// into context state accounts. The wrap statistics are not recorded,
+    /// and the unwrap fails if the escrow is under-collateralized.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `..16` `[]` Accounts 0 through 15 of `Unwrap`, with the policy
+    ///    accounts and the flash mint guard
+    /// 16. `[]` Ciphertext-commitment equality proof context state account
+    /// 17. `[]` Batched range proof `U64` context state account
+    /// 18. `..18+M` `[s]` (Optional) M multisig signers on wrapped token
+    ///     account
+    UnwrapConfidential {
+        /// little-endian `u64` representing the amount to unwrap
+        amount: u64,
+        /// The new decryptable available balance of the wrapped token
+        /// account after the withdraw
+        new_decryptable_available_balance: PodAeCiphertext,
+    },
+
+    /// Updates the confidential transfer settings of a Token-2022 wrapped
+    /// mint with the Token-2022 confidential transfer `UpdateMint`
+    /// instruction, signed by the wrapped mint authority.
+    ///
+    /// Only wrapped mints whose confidential transfer authority is the wrapped
+    /// mint authority can be updated, i.e. mints created with the
+    /// `GovernedConfidentialMintCustomizer`. The config authority is the mint
+    /// authority of the unwrapped mint, with the same metadata and multisig
+    /// fallbacks as `SetCanonicalPointer`.
+    ///
+    /// Accounts expected:
+    /// 0. `[s]` Config authority, the unwrapped mint authority or, if the mint
+    ///    has none, the metadata update authority. Not a signer if multisig.
+    /// 1. `[w]` Wrapped mint, must be owned by Token-2022
+    /// 2. `[]` Wrapped mint authority (PDA)
+    /// 3. `[]` Unwrapped mint
+    /// 4. `[]` Token-2022 program
+    /// 5. `[]` (Optional) Source metadata account. Required if the unwrapped
+    ///    mint has no mint authority.
+    /// 6. `[]` (Optional) Owner program. Required when the metadata account is
+    ///    owned by a third-party program.
+    /// 7. `..7+M` `[s]` (Optional) M multisig signer accounts
+    UpdateConfidentialConfig {
+        /// Whether new accounts are approved to use confidential transfers
+        /// without the authority
+        auto_approve_new_accounts: bool,
+        /// The new auditor `ElGamal` public key, or none to remove the auditor
+        auditor_elgamal_pubkey: Option<PodElGamalPubkey>,
+    },
+
+    /// Appoints the admin of a wrapped mint, who may run `ExecuteAdminAction`
+    /// alongside the unwrapped mint authority. Setting the default pubkey
+    /// removes the appointed admin.
+    ///
+    /// If calling for the first time, the client is responsible for pre-funding
+    /// the rent for the PDA that will be initialized.
+    ///
+    /// The authority is the mint authority of the unwrapped mint, with the same
+    /// metadata and multisig fallbacks as `SetCanonicalPointer`.
+    ///
+    /// Accounts expected:
+    /// 0. `[s]` Unwrapped mint authority or, if the mint has none, the
+    ///    metadata update authority. Not a signer if multisig.
+    /// 1. `[w]` `WrappedMintAdmin` PDA account to create or update, address
+    ///    must be: `get_wrapped_mint_admin_address(wrapped_mint_address)`
+    /// 2. `[]` Unwrapped mint
+    /// 3. `[]` Wrapped mint
+    /// 4. `[]` System program
+    /// 5. `[]` (Optional) Source metadata account. Required if the unwrapped
+    ///    mint has no mint authority.
+    /// 6. `[]` (Optional) Owner program. Required when the metadata account is
+    ///    owned by a third-party program.
+    /// 7. `..7+M` `[s]` (Optional) M multisig signer accounts
+    SetWrappedMintAdmin {
+        /// The new admin, or the default pubkey for none
+        admin: Pubkey,
+    },
+
+    /// Runs an administrative Token-2022 action on a wrapped mint, signed by
+    /// the wrapped mint authority. The action only succeeds if the wrapped
+    /// mint authority holds the matching authority on the wrapped mint, e.g.
+    /// the freeze authority for `FreezeAccount`, which depends on the mint
+    /// customizer. The `AdministeredMintCustomizer` gives the wrapped mint
+    /// authority every authority these actions need.
+    ///
+    /// The admin is either the admin appointed with `SetWrappedMintAdmin` or
+    /// the unwrapped mint authority, with the same metadata and multisig
+    /// fallbacks as `SetCanonicalPointer`. For `UpdateMetadataField`, the
+    /// client is responsible for pre-funding any additional rent of the
+    /// wrapped mint.
+    ///
+    /// Accounts expected:
+    /// 0. `[s]` Admin. Not a signer if multisig.
+    /// 1. `[w]` Wrapped mint
+    /// 2. `[]` Wrapped mint authority (PDA)
+    /// 3. `[]` Unwrapped mint
+    /// 4. `[]` `WrappedMintAdmin` PDA account, address must be:
+    ///    `get_wrapped_mint_admin_address(wrapped_mint_address)`. Need not
+    ///    exist.
+    /// 5. `[]` Wrapped token program
+    /// 6. `[w]` (`FreezeAccount` and `ThawAccount` only) Wrapped token account
+    /// 7. `[]` (Optional) Source metadata account. Required if the admin is the
+    ///    metadata update authority of the unwrapped mint.
+    /// 8. `[]` (Optional) Owner program. Required when the metadata account is
+    ///    owned by a third-party program.
+    /// 9. `..9+M` `[s]` (Optional) M multisig signer accounts
+    ExecuteAdminAction {
+        /// The action to run
+        action: AdminAction,
+    },
+
+    /// Sets the limits on wrapping an unwrapped mint, enforced by every
+    /// instruction minting wrapped tokens. Setting `unwrap_only` stops new
+    /// wraps while unwraps remain available. Updating the limits keeps the
+    /// amount already wrapped in the current epoch, and resets the total
+    /// wrapped to the supply of both wrapped mints of the unwrapped mint.
+    ///
+    /// If calling for the first time, the client is responsible for pre-funding
+    /// the rent for the PDA that will be initialized.
+    ///
+    /// The authority is the mint authority of the unwrapped mint, with the same
+    /// metadata and multisig fallbacks as `SetCanonicalPointer`.
+    ///
+    /// Accounts expected:
+    /// 0. `[s]` Unwrapped mint authority or, if the mint has none, the
+    ///    metadata update authority. Not a signer if multisig.
+    /// 1. `[w]` `WrapLimits` PDA account to create or update, address must be:
+    ///    `get_wrap_limits_address(unwrapped_mint_address)`
+    /// 2. `[]` Unwrapped mint
+    /// 3. `[]` System program
+    /// 4. `[]` Wrapped mint under SPL Token, address must be:
+    ///    `get_wrapped_mint_address(unwrapped_mint_address, spl_token_id)`.
+    ///    May be uninitialized.
+    /// 5. `[]` Wrapped mint under Token-2022, address must be:
+    ///    `get_wrapped_mint_address(unwrapped_mint_address, token_2022_id)`.
+    ///    May be uninitialized.
+    /// 6. `[]` (Optional) Source metadata account. Required if the unwrapped
+    ///    mint has no mint authority.
+    /// 7. `[]` (Optional) Owner program. Required when the metadata account is
+    ///    owned by a third-party program.
+    /// 8. `..8+M` `[s]` (Optional) M multisig signer accounts
+    SetWrapLimits {
+        /// Whether `Wrap` is rejected entirely
+        unwrap_only: bool,
+        /// little-endian `u64` maximum total amount wrapped across all wrapped
+        /// mints of the unwrapped mint, or 0 for no cap
+        max_wrapped_supply: u64,
+        /// little-endian `u64` maximum amount wrapped per epoch, or 0 for no
+        /// cap
+        max_wrapped_per_epoch: u64,
+    },
+
+    /// Enables or disables the allowlist mode of an unwrapped mint. While it is
+    /// enabled, `Wrap` and `Unwrap` and their variants only succeed for wallets
+    /// allowed with `UpdateAllowlist`.
+    ///
+    /// If calling for the first time, the client is responsible for pre-funding
+    /// the rent for the PDA that will be initialized.
+    ///
+    /// The authority is the mint authority of the unwrapped mint, with the same
+    /// metadata and multisig fallbacks as `SetCanonicalPointer`.
+    ///
+    /// Accounts expected:
+    /// 0. `[s]` Unwrapped mint authority or, if the mint has none, the
+    ///    metadata update authority. Not a signer if multisig.
+    /// 1. `[w]` `Allowlist` PDA account to create or update, address must be:
+    ///    `get_allowlist_address(unwrapped_mint_address)`
+    /// 2. `[]` Unwrapped mint
+    /// 3. `[]` System program
+    /// 4. `[]` (Optional) Source metadata account. Required if the unwrapped
+    ///    mint has no mint authority.
+    /// 5. `[]` (Optional) Owner program. Required when the metadata account is
+    ///    owned by a third-party program.
+    /// 6. `..6+M` `[s]` (Optional) M multisig signer accounts
+    SetAllowlistMode {
+        /// Whether the allowlist mode is enabled
+        enabled: bool,
+    },
+
+    /// Allows or disallows a batch of wallets on the allowlist of an unwrapped
+    /// mint.
+    ///
+    /// The client is responsible for pre-funding the rent for the entry PDAs
+    /// that will be initialized.
+    ///
+    /// The authority is the mint authority of the unwrapped mint, with the same
+    /// metadata and multisig fallbacks as `SetCanonicalPointer`.
+    ///
+    /// Accounts expected:
+    /// 0. `[s]` Unwrapped mint authority or, if the mint has none, the
+    ///    metadata update authority. Not a signer if multisig.
+    /// 1. `[]` Unwrapped mint
+    /// 2. `[]` System program
+    /// 3. `..3+N` `[w]` `AllowlistEntry` PDA accounts to create or update, one
+    ///    per wallet and in the same order, addresses must be:
+    ///    `get_allowlist_entry_address(unwrapped_mint_address, wallet)`
+    /// 4. `[]` (Optional) Source metadata account, at index `3+N`. Required if
+    ///    the unwrapped mint has no mint authority.
+    /// 5. `[]` (Optional) Owner program, at index `4+N`. Required when the
+    ///    metadata account is owned by a third-party program.
+    /// 6. `..M` `[s]` (Optional) M multisig signer accounts, from index `5+N`
+    UpdateAllowlist {
+        /// Whether the wallets are allowed
+        allowed: bool,
+        /// The wallets to update, packed as consecutive 32-byte keys
+        wallets: Vec<Pubkey>,
+    },
+
+    /// Sets the deployment-wide protocol fee charged by `Wrap` and `Unwrap`
+    /// on the unwrapped tokens, in basis points, and the wallet receiving it.
+    /// Setting both fees to zero stops charging the fee.
+    ///
+    /// The authority is the upgrade authority of the program. The client is
+    /// responsible for pre-funding the rent of the config PDA if it is not yet
+    /// initialized.
+    ///
+    /// Accounts expected:
+    /// 0. `[s]` Program upgrade authority
+    /// 1. `[w]` Protocol fee config PDA, address must be:
+    ///    `get_protocol_fee_config_address()`
+    /// 2. `[]` Program data account of the program
+    /// 3. `[]` System program
+    SetProtocolFeeConfig {
+        /// Protocol fee charged on wraps, in basis points
+        wrap_fee_basis_points: u16,
+        /// Protocol fee charged on unwraps, in basis points
+        unwrap_fee_basis_points: u16,
+        /// Owner of the token accounts receiving the protocol fee
+        fee_destination: Pubkey,
+    },
+
+    /// Overrides the protocol fee for a single unwrapped mint. A disabled
+    /// override falls back to the deployment-wide fee.
+    ///
+    /// The authority is the upgrade authority of the program. The client is
+    /// responsible for pre-funding the rent of the override PDA if it is not
+    /// yet initialized.
+    ///
+    /// Accounts expected:
+    /// 0. `[s]` Program upgrade authority
+    /// 1. `[w]` Protocol fee override PDA, address must be:
+    ///    `get_protocol_fee_override_address(unwrapped_mint_address)`
+    /// 2. `[]` Unwrapped mint
+    /// 3. `[]` Program data account of the program
+    /// 4. `[]` System program
+    SetProtocolFeeOverride {
+        /// Whether the override applies
+        enabled: bool,
+        /// Protocol fee charged on wraps of the mint, in basis points
+        wrap_fee_basis_points: u16,
+        /// Protocol fee charged on unwraps of the mint, in basis points
+        unwrap_fee_basis_points: u16,
+    },
+
+    /// Mints wrapped tokens without escrowing unwrapped tokens, to be burned
+    /// again by a `FlashRepay` later in the same transaction.
+    ///
+    /// The instructions sysvar must show a later top-level `FlashRepay` of the
+    /// same flash mint guard, and the guard rejects another flash mint of the
+    /// wrapped mint until then. The repayment is the amount plus the fee set
+    /// with `SetFlashMintFee`, rounded up.
+    ///
+    /// As with `Wrap`, the canonical pointer, wrap limits and allowlist of the
+    /// unwrapped mint apply, the allowlist to the owner of the recipient
+    /// wrapped token account. The loan is checked against the wrap limits but
+    /// not recorded in them, as it is burned again in the same transaction.
+    /// Unwrapping the wrapped mint is rejected until the loan is repaid.
+    ///
+    /// If calling for the first time on a wrapped mint, the client is
+    /// responsible for pre-funding the rent for the guard PDA that will be
+    /// initialized.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[w]` Recipient wrapped token account
+    /// 1. `[w]` Wrapped mint, must be initialized, address must be:
+    ///    `get_wrapped_mint_address(unwrapped_mint_address,
+    ///    wrapped_token_program_id)`
+    /// 2. `[]` Wrapped mint authority, address must be:
+    ///    `get_wrapped_mint_authority(wrapped_mint)`
+    /// 3. `[]` Unwrapped token mint
+    /// 4. `[]` Escrow of unwrapped tokens, must be owned by:
+    ///    `get_wrapped_mint_authority(wrapped_mint_address)`
+    /// 5. `[w]` `FlashMintGuard` PDA account, address must be:
+    ///    `get_flash_mint_guard_address(wrapped_mint_address)`
+    /// 6. `[]` Wrapped token program
+    /// 7. `[]` Unwrapped token program
+    /// 8. `[]` Instructions sysvar
+    /// 9. `[]` System program
+    /// 10. `[]` Canonical pointer PDA account, address must be:
+    ///     `get_canonical_pointer_address(unwrapped_mint_address)`
+    /// 11. `[]` Scoped canonical pointer PDA account, address must be:
+    ///     `get_scoped_canonical_pointer_address(unwrapped_mint_address,
+    ///     wrapped_token_program_id)`
+    /// 12. `[]` `WrapLimits` PDA account, address must be:
+    ///     `get_wrap_limits_address(unwrapped_mint_address)`
+    /// 13. `[]` `Allowlist` PDA account, address must be:
+    ///     `get_allowlist_address(unwrapped_mint_address)`
+    /// 14. `[]` `AllowlistEntry` PDA account of the recipient's owner, address
+    ///     must be: `get_allowlist_entry_address(unwrapped_mint_address,
+    ///     owner)`
+    FlashMint {
+        /// little-endian `u64` representing the amount to flash mint
+        amount: u64,
+    },
+
+    /// Repays the active flash mint of a wrapped mint by burning its amount
+    /// plus its fee, then checks that the escrow backs the wrapped supply at
+    /// least as well as before the flash mint.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[w]` Wrapped token account to burn from
+    /// 1. `[w]` Wrapped mint
+    /// 2. `[]` Escrow of unwrapped tokens backing the wrapped mint
+    /// 3. `[w]` `FlashMintGuard` PDA account, address must be:
+    ///    `get_flash_mint_guard_address(wrapped_mint_address)`
+    /// 4. `[]` Wrapped token program, must own the wrapped mint
+    /// 5. `[s]` Transfer authority on wrapped token account. Not a signer if
+    ///    it's a multisig.
+    /// 6. `..6+M` `[s]` (Optional) M multisig signers on wrapped token account
+    FlashRepay,
+
+    /// Sets the fee charged on flash mints of a wrapped mint, in basis points.
+    /// Flash mints already awaiting repayment keep their fee.
+    ///
+    /// If calling for the first time, the client is responsible for pre-funding
+    /// the rent for the PDA that will be initialized.
+    ///
+    /// The authority is the mint authority of the unwrapped mint, with the same
+    /// metadata and multisig fallbacks as `SetCanonicalPointer`.
+    ///
+    /// Accounts expected:
+    /// 0. `[s]` Unwrapped mint authority or, if the mint has none, the
+    ///    metadata update authority. Not a signer if multisig.
+    /// 1. `[w]` `FlashMintGuard` PDA account to create or update, address must
+    ///    be: `get_flash_mint_guard_address(wrapped_mint_address)`
+    /// 2. `[]` Unwrapped mint
+    /// 3. `[]` Wrapped mint
+    /// 4. `[]` System program
+    /// 5. `[]` (Optional) Source metadata account. Required if the unwrapped
+    ///    mint has no mint authority.
+    /// 6. `[]` (Optional) Owner program. Required when the metadata account is
+    ///    owned by a third-party program.
+    /// 7. `..7+M` `[s]` (Optional) M multisig signer accounts
+    SetFlashMintFee {
+        /// Fee charged on flash mints, in basis points
+        fee_basis_points: u16,
+    },
+
+    /// Wraps tokens on behalf of their owner, who authorized it off-chain by
+    /// signing a `Permit`, so that a relayer can pay for the transaction.
+    ///
+    /// The owner signs `Permit::message` for `PermitAction::Wrap` with the
+    /// unwrapped mint, the amount, the unwrapped token account spent, the
+    /// recipient wrapped token account, their next nonce and an expiry. The transaction must verify that signature
+    /// with an instruction of the ed25519 program holding the public key,
+    /// signature and message in its own data. The owner must have approved
+    /// the permit delegate on the unwrapped token account, which then
+    /// transfers the tokens.
+    ///
+    /// Each permit consumes the next nonce of the owner, which is stored in a
+    /// nonce PDA. If calling for the first time for an owner, the client is
+    /// responsible for pre-funding the rent for the nonce PDA that will be
+    /// initialized.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `..17` Accounts 0 through 16 of `Wrap`, with the policy accounts and
+    ///    the transfer authority at index 8 being the permit delegate, address
+    ///    must be: `get_permit_delegate_address()`. Wrap statistics are not
+    ///    recorded.
+    /// 17. `[]` Owner of the unwrapped token account, the permit signer
+    /// 18. `[w]` `PermitNonce` PDA account, address must be:
+    ///     `get_permit_nonce_address(owner)`
+    /// 19. `[]` Instructions sysvar
+    /// 20. `[]` System program
+    /// 21. `..21+M` `[]` (Optional) Additional accounts of the transfer, e.g.
+    ///     transfer hook accounts
+    WrapWithPermit {
+        /// little-endian `u64` representing the amount to wrap
+        amount: u64,
+        /// little-endian `u64` representing the nonce of the permit
+        nonce: u64,
+        /// little-endian `i64` representing the unix timestamp after which the
+        /// permit expires
+        expiry: i64,
+    },
+
+    /// Unwraps tokens on behalf of their owner, who authorized it off-chain by
+    /// signing a `Permit`, so that a relayer can pay for the transaction.
+    ///
+    /// The permit is verified like in `WrapWithPermit`, for
+    /// `PermitAction::Unwrap` with the wrapped mint, the wrapped token account
+    /// spent and the recipient unwrapped token account. The owner must have approved the permit
+    /// delegate on the wrapped token account, which then burns the tokens.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `..16` Accounts 0 through 15 of `Unwrap`, with the policy accounts,
+    ///    the flash mint guard and the transfer authority at index 8 being
+    ///    the permit delegate,
+    ///    address must be:
+    ///    `get_permit_delegate_address()`. Wrap statistics are not recorded
+    ///    and pro-rata redemption is not accepted.
+    /// 16. `[]` Owner of the wrapped token account, the permit signer
+    /// 17. `[w]` `PermitNonce` PDA account, address must be:
+    ///     `get_permit_nonce_address(owner)`
+    /// 18. `[]` Instructions sysvar
+    /// 19. `[]` System program
+    /// 20. `..20+M` `[]` (Optional) Additional accounts of the transfer, e.g.
+    ///     transfer hook accounts
+    UnwrapWithPermit {
+        /// little-endian `u64` representing the amount to unwrap
+        amount: u64,
+        /// little-endian `u64` representing the nonce of the permit
+        nonce: u64,
+        /// little-endian `i64` representing the unix timestamp after which the
+        /// permit expires
+        expiry: i64,
+    },
+
+    /// Designates the wrapped token account receiving the cranked wraps of
+    /// the owner's tokens of an unwrapped mint, see `CrankWrap`.
+    ///
+    /// If calling for the first time, the client is responsible for pre-funding
+    /// the rent for the PDA that will be initialized.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[s]` Owner of the unwrapped tokens
+    /// 1. `[w]` `CrankWrapDelegate` PDA account to create or update, address
+    ///    must be: `get_crank_wrap_delegate_address(owner, unwrapped_mint)`
+    /// 2. `[]` Unwrapped mint
+    /// 3. `[]` System program
+    SetCrankWrapRecipient {
+        /// Wrapped token account receiving the cranked wraps
+        recipient: Pubkey,
+    },
+
+    /// Wraps the tokens an owner delegated to their crank wrap delegate, into
+    /// the recipient designated with `SetCrankWrapRecipient`. Anyone can call
+    /// it, so that automation services can wrap recurring inflows.
+    ///
+    /// The owner approves the delegate on the unwrapped token account in a
+    /// top-level instruction, which is allowed even if the account has
+    /// `CpiGuard` enabled, unlike the transfer by the owner in `Wrap`. The
+    /// wrapped amount is the delegated amount, capped by the balance of the
+    /// unwrapped token account.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `..17` Accounts 0 through 16 of `Wrap`, with the policy accounts, the
+    ///    recipient at index 0 being the designated recipient and the
+    ///    transfer authority at index 8 being the crank wrap delegate, address
+    ///    must be: `get_crank_wrap_delegate_address(owner, unwrapped_mint)`.
+    ///    Wrap statistics are not recorded.
+    /// 17. `..17+M` `[]` (Optional) Additional accounts of the transfer, e.g.
+    ///     transfer hook accounts
+    CrankWrap,
+
+    /// Records a wrapped mint created before the wrapped mint registry existed
+    /// in the registry and in the index of its unwrapped mint. Anyone can call
+    /// it. Fails if the wrapped mint is already in the index.
+    ///
+    /// As in `CreateMint`, the client is responsible for pre-funding the rent
+    /// of the registry accounts that are created or grown.
+    ///
+    /// Accounts expected by this instruction:
+    /// 0. `[]` Wrapped mint, must be initialized, address must be:
+    ///    `get_wrapped_mint_address(unwrapped_mint_address,
+    ///    wrapped_token_program_id)`
+    /// 1. `[]` Wrapped backpointer account, address must be:
+    ///    `get_wrapped_mint_backpointer_address(wrapped_mint_address)`
+    /// 2. `[]` Unwrapped mint
+    /// 3. `[]` System program
+    /// 4. `[]` The token program used to create the wrapped mint
+    /// 5. `[w]` Wrapped mint registry PDA, address must be:
+    ///    `get_wrapped_mint_registry_address()`
+    /// 6. `[w]` Registry page the entry is appended to, address must be:
+    ///    `get_wrapped_mint_registry_page_address(page_index)`
+    /// 7. `[w]` Wrapped mint index PDA of the unwrapped mint, address must be:
+    ///    `get_wrapped_mint_index_address(unwrapped_mint_address)`
+    RegisterWrappedMint,
+}
+
+/// Administrative actions `ExecuteAdminAction` can run on a wrapped mint
+#[derive(Clone, Debug, PartialEq)]
+pub enum AdminAction {
+    /// Freeze a wrapped token account
+    FreezeAccount,
+    /// Thaw a frozen wrapped token account
+    ThawAccount,
+    /// Pause transfers, mints and burns of a `Pausable` wrapped mint
+    Pause,
+    /// Resume a paused wrapped mint
+    Resume,
+    /// Update a field of the `TokenMetadata` of the wrapped mint
+    UpdateMetadataField {
+        /// Field to update
+        field: Field,
+        /// Value to write
+        value: String,
+    },
+    /// Set the transfer fee of a `TransferFeeConfig` wrapped mint
+    SetTransferFee {
+        /// Transfer fee in basis points of the transfer amount
+        transfer_fee_basis_points: u16,
+        /// Maximum fee per transfer
+        maximum_fee: u64,
+    },
+}
+
+impl AdminAction {
+    fn pack(&self, buf: &mut Vec<u8>) {
+        match self {
+            AdminAction::FreezeAccount => buf.push(0),
+            AdminAction::ThawAccount => buf.push(1),
+            AdminAction::Pause => buf.push(2),
+            AdminAction::Resume => buf.push(3),
+            AdminAction::UpdateMetadataField { field, value } => {
+                buf.push(4);
+                match field {
+                    Field::Name => buf.push(0),
+                    Field::Symbol => buf.push(1),
+                    Field::Uri => buf.push(2),
+                    Field::Key(key) => {
+                        buf.push(3);
+                        pack_string(buf, key);
+                    }
+                }
+                pack_string(buf, value);
+            }
+            AdminAction::SetTransferFee {
+                transfer_fee_basis_points,
+                maximum_fee,
+            } => {
+                buf.push(5);
+                buf.extend_from_slice(&transfer_fee_basis_points.to_le_bytes());
+                buf.extend_from_slice(&maximum_fee.to_le_bytes());
+            }
+        }
+    }
+
+    fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        match input.split_first() {
+            Some((&0, [])) => Ok(AdminAction::FreezeAccount),
+            Some((&1, [])) => Ok(AdminAction::ThawAccount),
+            Some((&2, [])) => Ok(AdminAction::Pause),
+            Some((&3, [])) => Ok(AdminAction::Resume),
+            Some((&4, rest)) => {
+                let (field, rest) = match rest.split_first() {
+                    Some((&0, rest)) => (Field::Name, rest),
+                    Some((&1, rest)) => (Field::Symbol, rest),
+                    Some((&2, rest)) => (Field::Uri, rest),
+                    Some((&3, rest)) => {
+                        let (key, rest) = unpack_string(rest)?;
+                        (Field::Key(key), rest)
+                    }
+                    _ => return Err(ProgramError::InvalidInstructionData),
+                };
+                let (value, rest) = unpack_string(rest)?;
+                if !rest.is_empty() {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                Ok(AdminAction::UpdateMetadataField { field, value })
+            }
+            Some((&5, rest)) if rest.len() == 10 => {
+                let (transfer_fee_basis_points, maximum_fee) = rest.split_at(2);
+                Ok(AdminAction::SetTransferFee {
+                    transfer_fee_basis_points: u16::from_le_bytes(
+                        transfer_fee_basis_points.try_into().unwrap(),
+                    ),
+                    maximum_fee: u64::from_le_bytes(maximum_fee.try_into().unwrap()),
+                })
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// Packs a string as its little-endian `u32` length followed by its bytes
+fn pack_string(buf: &mut Vec<u8>, value: &str) {
+    buf.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    buf.extend_from_slice(value.as_bytes());
+}
+
+/// Unpacks a string packed by `pack_string`, returning the remaining input
+fn unpack_string(input: &[u8]) -> Result<(String, &[u8]), ProgramError> {
+    let (len, rest) = input
+        .split_first_chunk::<4>()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let len = u32::from_le_bytes(*len) as usize;
+    if rest.len() < len {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (value, rest) = rest.split_at(len);
+    let value =
+        String::from_utf8(value.to_vec()).map_err(|_| ProgramError::InvalidInstructionData)?;
+    Ok((value, rest))
+}
+
+impl TokenWrapInstruction {
+    /// Packs a [`TokenWrapInstruction`](enum.TokenWrapInstruction.html) into a
+    /// byte array.
+    pub fn pack(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        match self {
+            TokenWrapInstruction::CreateMint {
+                idempotent,
+                acknowledge_risky_extensions,
+            } => {
+                buf.push(0);
+                buf.push(if *idempotent { 1 } else { 0 });
+                if *acknowledge_risky_extensions {
+                    buf.push(1);
+                }
+            }
+
+            TokenWrapInstruction::Wrap {
+                amount,
+                record_stats,
+            } => {
+                buf.push(1);
+                buf.extend_from_slice(&amount.to_le_bytes());
+                if *record_stats {
+                    buf.push(1);
+                }
+            }
+            TokenWrapInstruction::Unwrap {
+                amount,
+                record_stats,
+                pro_rata,
+            } => {
+                buf.push(2);
+                buf.extend_from_slice(&amount.to_le_bytes());
+                if *record_stats || *pro_rata {
+                    buf.push(*record_stats as u8);
+                }
+                if *pro_rata {
+                    buf.push(1);
+                }
+            }
+            TokenWrapInstruction::CloseStuckEscrow => {
+                buf.push(3);
+            }
+            TokenWrapInstruction::SyncMetadataToToken2022 => {
+                buf.push(4);
+            }
+            TokenWrapInstruction::SyncMetadataToSplToken => {
+                buf.push(5);
+            }
+            TokenWrapInstruction::SetCanonicalPointer {
+                program_id,
+                enforce_on_wrap,
+                change_delay,
+                wrapped_token_program,
+            } => {
+                buf.push(6);
+                buf.extend_from_slice(program_id.as_ref());
+                buf.push(if *enforce_on_wrap { 1 } else { 0 });
+                buf.extend_from_slice(&change_delay.to_le_bytes());
+                pack_pointer_scope(&mut buf, wrapped_token_program);
+            }
+            TokenWrapInstruction::CloseCanonicalPointer {
+                wrapped_token_program,
+            } => {
+                buf.push(7);
+                pack_pointer_scope(&mut buf, wrapped_token_program);
+            }
+            TokenWrapInstruction::ProposeCanonicalPointer {
+                program_id,
+                enforce_on_wrap,
+                change_delay,
+                wrapped_token_program,
+            } => {
+                buf.push(8);
+                buf.extend_from_slice(program_id.as_ref());
+                buf.push(if *enforce_on_wrap { 1 } else { 0 });
+                buf.extend_from_slice(&change_delay.to_le_bytes());
+                pack_pointer_scope(&mut buf, wrapped_token_program);
+            }
+            TokenWrapInstruction::AcceptCanonicalPointer {
+                wrapped_token_program,
+            } => {
+                buf.push(9);
+                pack_pointer_scope(&mut buf, wrapped_token_program);
+            }
+            TokenWrapInstruction::CancelCanonicalPointer {
+                wrapped_token_program,
+            } => {
+                buf.push(10);
+                pack_pointer_scope(&mut buf, wrapped_token_program);
+            }
+            TokenWrapInstruction::MigrateState => {
+                buf.push(11);
+            }
+            TokenWrapInstruction::CloseWrappedMint => {
+                buf.push(12);
+            }
+            TokenWrapInstruction::RepairEscrow => {
+                buf.push(13);
+            }
+            TokenWrapInstruction::SetEscrowSurplusPolicy { action, target } => {
+                buf.push(14);
+                buf.push(*action as u8);
+                buf.extend_from_slice(target.as_ref());
+            }
+            TokenWrapInstruction::ReconcileEscrow => {
+                buf.push(15);
+            }
+            TokenWrapInstruction::WrapSol { amount } => {
+                buf.push(16);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            }
+            TokenWrapInstruction::UnwrapSol { amount, pro_rata } => {
+                buf.push(17);
+                buf.extend_from_slice(&amount.to_le_bytes());
+                if *pro_rata {
+                    buf.push(1);
+                }
+            }
+            TokenWrapInstruction::WrapConfidential { amount } => {
+                buf.push(18);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            }
+            TokenWrapInstruction::UnwrapConfidential {
+                amount,
+                new_decryptable_available_balance,
+            } => {
+                buf.push(19);
+                buf.extend_from_slice(&amount.to_le_bytes());
+                buf.extend_from_slice(&new_decryptable_available_balance.0);
+            }
+            TokenWrapInstruction::UpdateConfidentialConfig {
+                auto_approve_new_accounts,
+                auditor_elgamal_pubkey,
+            } => {
+                buf.push(20);
+                buf.push(if *auto_approve_new_accounts { 1 } else { 0 });
+                // An all-zero key encodes no auditor, as in Token-2022
+                buf.extend_from_slice(&auditor_elgamal_pubkey.unwrap_or_default().0);
+            }
+            TokenWrapInstruction::SetWrappedMintAdmin { admin } => {
+                buf.push(21);
+                buf.extend_from_slice(admin.as_ref());
+            }
+            TokenWrapInstruction::ExecuteAdminAction { action } => {
+                buf.push(22);
+                action.pack(&mut buf);
+            }
+            TokenWrapInstruction::SetWrapLimits {
+                unwrap_only,
+                max_wrapped_supply,
+                max_wrapped_per_epoch,
+            } => {
+                buf.push(23);
+                buf.push(if *unwrap_only { 1 } else { 0 });
+                buf.extend_from_slice(&max_wrapped_supply.to_le_bytes());
+                buf.extend_from_slice(&max_wrapped_per_epoch.to_le_bytes());
+            }
+            TokenWrapInstruction::SetAllowlistMode { enabled } => {
+                buf.push(24);
+                buf.push(if *enabled { 1 } else { 0 });
+            }
+            TokenWrapInstruction::UpdateAllowlist { allowed, wallets } => {
+                buf.push(25);
+                buf.push(if *allowed { 1 } else { 0 });
+                for wallet in wallets {
+                    buf.extend_from_slice(wallet.as_ref());
+                }
+            }
+            TokenWrapInstruction::SetProtocolFeeConfig {
+                wrap_fee_basis_points,
+                unwrap_fee_basis_points,
+                fee_destination,
+            } => {
+                buf.push(26);
+                buf.extend_from_slice(&wrap_fee_basis_points.to_le_bytes());
+                buf.extend_from_slice(&unwrap_fee_basis_points.to_le_bytes());
+                buf.extend_from_slice(fee_destination.as_ref());
+            }
+            TokenWrapInstruction::SetProtocolFeeOverride {
+                enabled,
+                wrap_fee_basis_points,
+                unwrap_fee_basis_points,
+            } => {
+                buf.push(27);
+                buf.push(if *enabled { 1 } else { 0 });
+                buf.extend_from_slice(&wrap_fee_basis_points.to_le_bytes());
+                buf.extend_from_slice(&unwrap_fee_basis_points.to_le_bytes());
+            }
+            TokenWrapInstruction::FlashMint { amount } => {
+                buf.push(28);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            }
+            TokenWrapInstruction::FlashRepay => {
+                buf.push(29);
+            }
+            TokenWrapInstruction::SetFlashMintFee { fee_basis_points } => {
+                buf.push(30);
+                buf.extend_from_slice(&fee_basis_points.to_le_bytes());
+            }
+            TokenWrapInstruction::WrapWithPermit {
+                amount,
+                nonce,
+                expiry,
+            } => {
+                buf.push(31);
+                pack_permit_args(&mut buf, *amount, *nonce, *expiry);
+            }
+            TokenWrapInstruction::UnwrapWithPermit {
+                amount,
+                nonce,
+                expiry,
+            } => {
+                buf.push(32);
+                pack_permit_args(&mut buf, *amount, *nonce, *expiry);
+            }
+            TokenWrapInstruction::SetCrankWrapRecipient { recipient } => {
+                buf.push(33);
+                buf.extend_from_slice(recipient.as_ref());
+            }
+            TokenWrapInstruction::CrankWrap => {
+                buf.push(34);
+            }
+            TokenWrapInstruction::RegisterWrappedMint => {
+                buf.push(35);
+            }
+        }
+        buf
+    }
+
+    /// Unpacks a byte array into a
+    /// [`TokenWrapInstruction`](enum.TokenWrapInstruction.html).
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        match input.split_first() {
+            Some((&0, rest)) if matches!(rest.len(), 1 | 2) => {
+                let idempotent = match rest[0] {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(ProgramError::InvalidInstructionData),
+                };
+                let acknowledge_risky_extensions = match rest.get(1) {
+                    None | Some(0) => false,
+                    Some(1) => true,
+                    _ => return Err(ProgramError::InvalidInstructionData),
+                };
+                Ok(TokenWrapInstruction::CreateMint {
+                    idempotent,
+                    acknowledge_risky_extensions,
+                })
+            }
+            Some((&1, rest)) if matches!(rest.len(), 8..=9) => {
+                let (amount, [record_stats]) = unpack_amount_args(rest)?;
+                Ok(TokenWrapInstruction::Wrap {
+                    amount,
+                    record_stats,
+                })
+            }
+            Some((&2, rest)) if matches!(rest.len(), 8..=10) => {
+                let (amount, [record_stats, pro_rata]) = unpack_amount_args(rest)?;
+                Ok(TokenWrapInstruction::Unwrap {
+                    amount,
+                    record_stats,
+                    pro_rata,
+                })
+            }
+            Some((&3, [])) => Ok(TokenWrapInstruction::CloseStuckEscrow),
+            Some((&4, [])) => Ok(TokenWrapInstruction::SyncMetadataToToken2022),
+            Some((&5, [])) => Ok(TokenWrapInstruction::SyncMetadataToSplToken),
+            Some((&6, rest)) if matches!(rest.len(), 32 | 41 | 73) => {
+                let (args, scope) = rest.split_at(rest.len().min(41));
+                let (program_id, enforce_on_wrap, change_delay) = unpack_pointer_args(args)?;
+                Ok(TokenWrapInstruction::SetCanonicalPointer {
+                    program_id,
+                    enforce_on_wrap,
+                    change_delay,
+                    wrapped_token_program: unpack_pointer_scope(scope)?,
+                })
+            }
+            Some((&7, rest)) => Ok(TokenWrapInstruction::CloseCanonicalPointer {
+                wrapped_token_program: unpack_pointer_scope(rest)?,
+            }),
+            Some((&8, rest)) if matches!(rest.len(), 41 | 73) => {
+                let (args, scope) = rest.split_at(41);
+                let (program_id, enforce_on_wrap, change_delay) = unpack_pointer_args(args)?;
+                Ok(TokenWrapInstruction::ProposeCanonicalPointer {
+                    program_id,
+                    enforce_on_wrap,
+                    change_delay,
+                    wrapped_token_program: unpack_pointer_scope(scope)?,
+                })
+            }
+            Some((&9, rest)) => Ok(TokenWrapInstruction::AcceptCanonicalPointer {
+                wrapped_token_program: unpack_pointer_scope(rest)?,
+            }),
+            Some((&10, rest)) => Ok(TokenWrapInstruction::CancelCanonicalPointer {
+                wrapped_token_program: unpack_pointer_scope(rest)?,
+            }),
+            Some((&11, [])) => Ok(TokenWrapInstruction::MigrateState),
+            Some((&12, [])) => Ok(TokenWrapInstruction::CloseWrappedMint),
+            Some((&13, [])) => Ok(TokenWrapInstruction::RepairEscrow),
+            Some((&14, [action, target @ ..])) if target.len() == 32 => {
+                Ok(TokenWrapInstruction::SetEscrowSurplusPolicy {
+                    action: SurplusAction::try_from(*action)
+                        .map_err(|_| ProgramError::InvalidInstructionData)?,
+                    target: Pubkey::new_from_array(target.try_into().unwrap()),
+                })
+            }
+            Some((&15, [])) => Ok(TokenWrapInstruction::ReconcileEscrow),
+            Some((&16, rest)) if rest.len() == 8 => Ok(TokenWrapInstruction::WrapSol {
+                amount: u64::from_le_bytes(rest.try_into().unwrap()),
+            }),
+            Some((&17, rest)) if matches!(rest.len(), 8..=9) => {
+                let (amount, [pro_rata]) = unpack_amount_args(rest)?;
+                Ok(TokenWrapInstruction::UnwrapSol { amount, pro_rata })
+            }
+            Some((&18, rest)) if rest.len() == 8 => Ok(TokenWrapInstruction::WrapConfidential {
+                amount: u64::from_le_bytes(rest.try_into().unwrap()),
+            }),
+            Some((&19, rest)) if rest.len() == 8 + AE_CIPHERTEXT_LEN => {
+                let (amount, balance) = rest.split_at(8);
+                Ok(TokenWrapInstruction::UnwrapConfidential {
+                    amount: u64::from_le_bytes(amount.try_into().unwrap()),
+                    new_decryptable_available_balance: PodAeCiphertext(balance.try_into().unwrap()),
+                })
+            }
+            Some((&20, [auto_approve_new_accounts, auditor @ ..]))
+                if auditor.len() == ELGAMAL_PUBKEY_LEN =>
+            {
+                let auto_approve_new_accounts = match auto_approve_new_accounts {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(ProgramError::InvalidInstructionData),
+                };
+                let auditor_elgamal_pubkey = PodElGamalPubkey(auditor.try_into().unwrap());
+                Ok(TokenWrapInstruction::UpdateConfidentialConfig {
+                    auto_approve_new_accounts,
+                    auditor_elgamal_pubkey: (auditor_elgamal_pubkey != PodElGamalPubkey::default())
+                        .then_some(auditor_elgamal_pubkey),
+                })
+            }
+            Some((&21, rest)) if rest.len() == 32 => {
+                Ok(TokenWrapInstruction::SetWrappedMintAdmin {
+                    admin: Pubkey::new_from_array(rest.try_into().unwrap()),
+                })
+            }
+            Some((&22, rest)) => Ok(TokenWrapInstruction::ExecuteAdminAction {
+                action: AdminAction::unpack(rest)?,
+            }),
+            Some((&23, [unwrap_only, rest @ ..])) if rest.len() == 16 => {
+                let unwrap_only = match unwrap_only {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(ProgramError::InvalidInstructionData),
+                };
+                let (max_wrapped_supply, max_wrapped_per_epoch) = rest.split_at(8);
+                Ok(TokenWrapInstruction::SetWrapLimits {
+                    unwrap_only,
+                    max_wrapped_supply: u64::from_le_bytes(max_wrapped_supply.try_into().unwrap()),
+                    max_wrapped_per_epoch: u64::from_le_bytes(
+                        max_wrapped_per_epoch.try_into().unwrap(),
+                    ),
+                })
+            }
+            Some((&24, [enabled])) => Ok(TokenWrapInstruction::SetAllowlistMode {
+                enabled: match enabled {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(ProgramError::InvalidInstructionData),
+                },
+            }),
+            Some((&25, [allowed, wallets @ ..]))
+                if !wallets.is_empty() && wallets.len() % 32 == 0 =>
+            {
+                let allowed = match allowed {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(ProgramError::InvalidInstructionData),
+                };
+                Ok(TokenWrapInstruction::UpdateAllowlist {
+                    allowed,
+                    wallets: wallets
+                        .chunks_exact(32)
+                        .map(|wallet| Pubkey::new_from_array(wallet.try_into().unwrap()))
+                        .collect(),
+                })
+            }
+            Some((&26, rest)) if rest.len() == 36 => {
+                let (fees, fee_destination) = rest.split_at(4);
+                let (wrap_fee_basis_points, unwrap_fee_basis_points) = unpack_fee_args(fees);
+                Ok(TokenWrapInstruction::SetProtocolFeeConfig {
+                    wrap_fee_basis_points,
+                    unwrap_fee_basis_points,
+                    fee_destination: Pubkey::new_from_array(fee_destination.try_into().unwrap()),
+                })
+            }
+            Some((&27, [enabled, fees @ ..])) if fees.len() == 4 => {
+                let enabled = match enabled {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(ProgramError::InvalidInstructionData),
+                };
+                let (wrap_fee_basis_points, unwrap_fee_basis_points) = unpack_fee_args(fees);
+                Ok(TokenWrapInstruction::SetProtocolFeeOverride {
+                    enabled,
+                    wrap_fee_basis_points,
+                    unwrap_fee_basis_points,
+                })
+            }
+            Some((&28, rest)) if rest.len() == 8 => Ok(TokenWrapInstruction::FlashMint {
+                amount: u64::from_le_bytes(rest.try_into().unwrap()),
+            }),
+            Some((&29, [])) => Ok(TokenWrapInstruction::FlashRepay),
+            Some((&30, rest)) if rest.len() == 2 => Ok(TokenWrapInstruction::SetFlashMintFee {
+                fee_basis_points: u16::from_le_bytes(rest.try_into().unwrap()),
+            }),
+            Some((&31, rest)) if rest.len() == 24 => {
+                let (amount, nonce, expiry) = unpack_permit_args(rest);
+                Ok(TokenWrapInstruction::WrapWithPermit {
+                    amount,
+                    nonce,
+                    expiry,
+                })
+            }
+            Some((&32, rest)) if rest.len() == 24 => {
+                let (amount, nonce, expiry) = unpack_permit_args(rest);
+                Ok(TokenWrapInstruction::UnwrapWithPermit {
+                    amount,
+                    nonce,
+                    expiry,
+                })
+            }
+            Some((&33, rest)) if rest.len() == 32 => {
+                Ok(TokenWrapInstruction::SetCrankWrapRecipient {
+                    recipient: Pubkey::new_from_array(rest.try_into().unwrap()),
+                })
+            }
+            Some((&34, [])) => Ok(TokenWrapInstruction::CrankWrap),
+            Some((&35, [])) => Ok(TokenWrapInstruction::RegisterWrappedMint),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// Unpacks the program id, enforcement flag and change delay of a pointer.
+/// Trailing fields may be omitted and default to false and 0.
+fn unpack_pointer_args(input: &[u8]) -> Result<(Pubkey, bool, u64), ProgramError> {
+    let (program_id, rest) = input.split_at(32);
+    let program_id = Pubkey::new_from_array(program_id.try_into().unwrap());
+    let (enforce_on_wrap, change_delay) = match rest {
+        [] => return Ok((program_id, false, 0)),
+        [0, change_delay @ ..] => (false, change_delay),
+        [1, change_delay @ ..] => (true, change_delay),
+        _ => return Err(ProgramError::InvalidInstructionData),
+    };
+    let change_delay = u64::from_le_bytes(
+        change_delay
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
+    Ok((program_id, enforce_on_wrap, change_delay))
+}
+
+/// Unpacks the amount of `Wrap` and `Unwrap` with their `N` optional
+/// trailing flags, which default to false when omitted.
+fn unpack_amount_args<const N: usize>(input: &[u8]) -> Result<(u64, [bool; N]), ProgramError> {
+    let (amount, rest) = input.split_at(8);
+    let amount = u64::from_le_bytes(amount.try_into().unwrap());
+    let mut flags = [false; N];
+    for (flag, byte) in flags.iter_mut().zip(rest) {
+        *flag = match byte {
+            0 => false,
+            1 => true,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+    }
+    Ok((amount, flags))
+}
+
+/// Unpacks the wrap and unwrap fees, in basis points, from 4 bytes.
+fn unpack_fee_args(input: &[u8]) -> (u16, u16) {
+    let (wrap_fee_basis_points, unwrap_fee_basis_points) = input.split_at(2);
+    (
+        u16::from_le_bytes(wrap_fee_basis_points.try_into().unwrap()),
+        u16::from_le_bytes(unwrap_fee_basis_points.try_into().unwrap()),
+    )
+}
+
+/// Appends the amount, nonce and expiry of a permit instruction.
+fn pack_permit_args(buf: &mut Vec<u8>, amount: u64, nonce: u64, expiry: i64) {
+    buf.extend_from_slice(&amount.to_le_bytes());
+    buf.extend_from_slice(&nonce.to_le_bytes());
+    buf.extend_from_slice(&expiry.to_le_bytes());
+}
+
+/// Unpacks the amount, nonce and expiry of a permit instruction from 24
+/// bytes.
+fn unpack_permit_args(input: &[u8]) -> (u64, u64, i64) {
+    let (amount, rest) = input.split_at(8);
+    let (nonce, expiry) = rest.split_at(8);
+    (
+        u64::from_le_bytes(amount.try_into().unwrap()),
+        u64::from_le_bytes(nonce.try_into().unwrap()),
+        i64::from_le_bytes(expiry.try_into().unwrap()),
+    )
+}
+
+/// Appends the wrapped token program a pointer is scoped to, if any.
+fn pack_pointer_scope(buf: &mut Vec<u8>, wrapped_token_program: &Option<Pubkey>) {
+    if let Some(wrapped_token_program) = wrapped_token_program {
+        buf.extend_from_slice(wrapped_token_program.as_ref());
+    }
+}
+
+/// Unpacks the optional wrapped token program a pointer is scoped to.
+fn unpack_pointer_scope(input: &[u8]) -> Result<Option<Pubkey>, ProgramError> {
+    match input {
+        [] => Ok(None),
+        _ => Ok(Some(Pubkey::new_from_array(
+            input
+                .try_into()
+                .map_err(|_| ProgramError::InvalidInstructionData)?,
+        ))),
+    }
+}
+
+/// Creates `CreateMint` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn create_mint(
+    program_id: &Pubkey,
+    wrapped_mint_address: &Pubkey,
+    wrapped_backpointer_address: &Pubkey,
+    unwrapped_mint_address: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+    registry_address: &Pubkey,
+    registry_page_address: &Pubkey,
+    wrapped_mint_index_address: &Pubkey,
+    idempotent: bool,
+    acknowledge_risky_extensions: bool,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*wrapped_mint_address, false),
+        AccountMeta::new(*wrapped_backpointer_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint_address, false),
+        AccountMeta::new_readonly(solana_system_interface::program::id(), false),
+        AccountMeta::new_readonly(*wrapped_token_program_id, false),
+        AccountMeta::new(*registry_address, false),
+        AccountMeta::new(*registry_page_address, false),
+        AccountMeta::new(*wrapped_mint_index_address, false),
+    ];
+    let data = TokenWrapInstruction::CreateMint {
+        idempotent,
+        acknowledge_risky_extensions,
+    }
+    .pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `Wrap` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn wrap(
+    program_id: &Pubkey,
+    recipient_wrapped_token_account_address: &Pubkey,
+    wrapped_mint_address: &Pubkey,
+    wrapped_mint_authority_address: &Pubkey,
+    unwrapped_token_program_id: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+    unwrapped_token_account_address: &Pubkey,
+    unwrapped_mint_address: &Pubkey,
+    unwrapped_escrow_address: &Pubkey,
+    canonical_pointer_address: &Pubkey,
+    scoped_canonical_pointer_address: &Pubkey,
+    wrap_limits_address: &Pubkey,
+    allowlist_address: &Pubkey,
+    allowlist_entry_address: &Pubkey,
+    protocol_fee_config_address: &Pubkey,
+    protocol_fee_override_address: &Pubkey,
+    protocol_fee_vault_address: &Pubkey,
+    transfer_authority_address: &Pubkey,
+    wrap_stats_address: Option<&Pubkey>,
+    multisig_signer_pubkeys: &[&Pubkey],
+    amount: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*recipient_wrapped_token_account_address, false),
+        AccountMeta::new(*wrapped_mint_address, false),
+        AccountMeta::new_readonly(*wrapped_mint_authority_address, false),
+        AccountMeta::new_readonly(*unwrapped_token_program_id, false),
+        AccountMeta::new_readonly(*wrapped_token_program_id, false),
+        AccountMeta::new(*unwrapped_token_account_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint_address, false),
+        AccountMeta::new(*unwrapped_escrow_address, false),
+        AccountMeta::new_readonly(
+            *transfer_authority_address,
+            multisig_signer_pubkeys.is_empty(),
+        ),
+        AccountMeta::new_readonly(*canonical_pointer_address, false),
+        AccountMeta::new_readonly(*scoped_canonical_pointer_address, false),
+        AccountMeta::new(*wrap_limits_address, false),
+        AccountMeta::new_readonly(*allowlist_address, false),
+        AccountMeta::new_readonly(*allowlist_entry_address, false),
+        AccountMeta::new_readonly(*protocol_fee_config_address, false),
+        AccountMeta::new_readonly(*protocol_fee_override_address, false),
+        AccountMeta::new(*protocol_fee_vault_address, false),
+    ];
+    if let Some(wrap_stats_address) = wrap_stats_address {
+        accounts.push(AccountMeta::new(*wrap_stats_address, false));
+    }
+    for signer_pubkey in multisig_signer_pubkeys.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+
+    let data = TokenWrapInstruction::Wrap {
+        amount,
+        record_stats: wrap_stats_address.is_some(),
+    }
+    .pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `WrapConfidential` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn wrap_confidential(
+    program_id: &Pubkey,
+    recipient_wrapped_token_account_address: &Pubkey,
+    recipient_owner_address: &Pubkey,
+    wrapped_mint_address: &Pubkey,
+    wrapped_mint_authority_address: &Pubkey,
+    unwrapped_token_program_id: &Pubkey,
+    unwrapped_token_account_address: &Pubkey,
+    unwrapped_mint_address: &Pubkey,
+    unwrapped_escrow_address: &Pubkey,
+    canonical_pointer_address: &Pubkey,
+    scoped_canonical_pointer_address: &Pubkey,
+    wrap_limits_address: &Pubkey,
+    allowlist_address: &Pubkey,
+    allowlist_entry_address: &Pubkey,
+    protocol_fee_config_address: &Pubkey,
+    protocol_fee_override_address: &Pubkey,
+    protocol_fee_vault_address: &Pubkey,
+    transfer_authority_address: &Pubkey,
+    multisig_signer_pubkeys: &[&Pubkey],
+    amount: u64,
+) -> Instruction {
+    let mut instruction = wrap(
+        program_id,
+        recipient_wrapped_token_account_address,
+        wrapped_mint_address,
+        wrapped_mint_authority_address,
+        unwrapped_token_program_id,
+        &spl_token_2022_interface::id(),
+        unwrapped_token_account_address,
+        unwrapped_mint_address,
+        unwrapped_escrow_address,
+        canonical_pointer_address,
+        scoped_canonical_pointer_address,
+        wrap_limits_address,
+        allowlist_address,
+        allowlist_entry_address,
+        protocol_fee_config_address,
+        protocol_fee_override_address,
+        protocol_fee_vault_address,
+        transfer_authority_address,
+        None,
+        multisig_signer_pubkeys,
+        amount,
+    );
+    instruction.accounts.insert(
+        17,
+        AccountMeta::new_readonly(*recipient_owner_address, true),
+    );
+    instruction.data = TokenWrapInstruction::WrapConfidential { amount }.pack();
+    instruction
+}
+
+/// Creates `Unwrap` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn unwrap(
+    program_id: &Pubkey,
+    unwrapped_escrow_address: &Pubkey,
+    recipient_unwrapped_token_account_address: &Pubkey,
+    wrapped_mint_authority_address: &Pubkey,
+    unwrapped_mint_address: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+    unwrapped_token_program_id: &Pubkey,
+    wrapped_token_account_address: &Pubkey,
+    wrapped_mint_address: &Pubkey,
+    transfer_authority_address: &Pubkey,
+    wrap_limits_address: &Pubkey,
+    allowlist_address: &Pubkey,
+    allowlist_entry_address: &Pubkey,
+    protocol_fee_config_address: &Pubkey,
+    protocol_fee_override_address: &Pubkey,
+    protocol_fee_vault_address: &Pubkey,
+    flash_mint_guard_address: &Pubkey,
+    wrap_stats_address: Option<&Pubkey>,
+    pro_rata_redemption_address: Option<&Pubkey>,
+    multisig_signer_pubkeys: &[&Pubkey],
+    amount: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*unwrapped_escrow_address, false),
+        AccountMeta::new(*recipient_unwrapped_token_account_address, false),
+        AccountMeta::new_readonly(*wrapped_mint_authority_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint_address, false),
+        AccountMeta::new_readonly(*wrapped_token_program_id, false),
+        AccountMeta::new_readonly(*unwrapped_token_program_id, false),
+        AccountMeta::new(*wrapped_token_account_address, false),
+        AccountMeta::new(*wrapped_mint_address, false),
+        AccountMeta::new_readonly(
+            *transfer_authority_address,
+            multisig_signer_pubkeys.is_empty(),
+        ),
+        AccountMeta::new(*wrap_limits_address, false),
+        AccountMeta::new_readonly(*allowlist_address, false),
+        AccountMeta::new_readonly(*allowlist_entry_address, false),
+        AccountMeta::new_readonly(*protocol_fee_config_address, false),
+        AccountMeta::new_readonly(*protocol_fee_override_address, false),
+        AccountMeta::new(*protocol_fee_vault_address, false),
+        AccountMeta::new_readonly(*flash_mint_guard_address, false),
+    ];
+    if let Some(wrap_stats_address) = wrap_stats_address {
+        accounts.push(AccountMeta::new(*wrap_stats_address, false));
+    }
+    if let Some(pro_rata_redemption_address) = pro_rata_redemption_address {
+        accounts.push(AccountMeta::new(*pro_rata_redemption_address, false));
+    }
+    for signer_pubkey in multisig_signer_pubkeys.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+
+    let data = TokenWrapInstruction::Unwrap {
+        amount,
+        record_stats: wrap_stats_address.is_some(),
+        pro_rata: pro_rata_redemption_address.is_some(),
+    }
+    .pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `UnwrapConfidential` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn unwrap_confidential(
+    program_id: &Pubkey,
+    unwrapped_escrow_address: &Pubkey,
+    recipient_unwrapped_token_account_address: &Pubkey,
+    wrapped_mint_authority_address: &Pubkey,
+    unwrapped_mint_address: &Pubkey,
+    unwrapped_token_program_id: &Pubkey,
+    wrapped_token_account_address: &Pubkey,
+    wrapped_mint_address: &Pubkey,
+    transfer_authority_address: &Pubkey,
+    wrap_limits_address: &Pubkey,
+    allowlist_address: &Pubkey,
+    allowlist_entry_address: &Pubkey,
+    protocol_fee_config_address: &Pubkey,
+    protocol_fee_override_address: &Pubkey,
+    protocol_fee_vault_address: &Pubkey,
+    flash_mint_guard_address: &Pubkey,
+    equality_proof_context_address: &Pubkey,
+    range_proof_context_address: &Pubkey,
+    multisig_signer_pubkeys: &[&Pubkey],
+    amount: u64,
+    new_decryptable_available_balance: PodAeCiphertext,
+) -> Instruction {
+    let mut instruction = unwrap(
+        program_id,
+        unwrapped_escrow_address,
+        recipient_unwrapped_token_account_address,
+        wrapped_mint_authority_address,
+        unwrapped_mint_address,
+        &spl_token_2022_interface::id(),
+        unwrapped_token_program_id,
+        wrapped_token_account_address,
+        wrapped_mint_address,
+        transfer_authority_address,
+        wrap_limits_address,
+        allowlist_address,
+        allowlist_entry_address,
+        protocol_fee_config_address,
+        protocol_fee_override_address,
+        protocol_fee_vault_address,
+        flash_mint_guard_address,
+        None,
+        None,
+        multisig_signer_pubkeys,
+        amount,
+    );
+    instruction.accounts.splice(
+        16..16,
+        [
+            AccountMeta::new_readonly(*equality_proof_context_address, false),
+            AccountMeta::new_readonly(*range_proof_context_address, false),
+        ],
+    );
+    instruction.data = TokenWrapInstruction::UnwrapConfidential {
+        amount,
+        new_decryptable_available_balance,
+    }
+    .pack();
+    instruction
+}
+
+/// Creates `WrapSol` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn wrap_sol(
+    program_id: &Pubkey,
+    recipient_wrapped_token_account_address: &Pubkey,
+    wrapped_mint_address: &Pubkey,
+    wrapped_mint_authority_address: &Pubkey,
+    unwrapped_token_program_id: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+    native_mint_address: &Pubkey,
+    unwrapped_escrow_address: &Pubkey,
+    canonical_pointer_address: &Pubkey,
+    scoped_canonical_pointer_address: &Pubkey,
+    wrap_limits_address: &Pubkey,
+    allowlist_address: &Pubkey,
+    allowlist_entry_address: &Pubkey,
+    protocol_fee_config_address: &Pubkey,
+    protocol_fee_override_address: &Pubkey,
+    protocol_fee_vault_address: &Pubkey,
+    funding_address: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*recipient_wrapped_token_account_address, false),
+        AccountMeta::new(*wrapped_mint_address, false),
+        AccountMeta::new_readonly(*wrapped_mint_authority_address, false),
+        AccountMeta::new_readonly(*unwrapped_token_program_id, false),
+        AccountMeta::new_readonly(*wrapped_token_program_id, false),
+        AccountMeta::new_readonly(*native_mint_address, false),
+        AccountMeta::new(*unwrapped_escrow_address, false),
+        AccountMeta::new_readonly(*canonical_pointer_address, false),
+        AccountMeta::new_readonly(*scoped_canonical_pointer_address, false),
+        AccountMeta::new(*wrap_limits_address, false),
+        AccountMeta::new_readonly(*allowlist_address, false),
+        AccountMeta::new_readonly(*allowlist_entry_address, false),
+        AccountMeta::new_readonly(*protocol_fee_config_address, false),
+        AccountMeta::new_readonly(*protocol_fee_override_address, false),
+        AccountMeta::new(*protocol_fee_vault_address, false),
+        AccountMeta::new(*funding_address, true),
+        AccountMeta::new_readonly(solana_system_interface::program::id(), false),
+    ];
+    let data = TokenWrapInstruction::WrapSol { amount }.pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `UnwrapSol` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn unwrap_sol(
+    program_id: &Pubkey,
+    unwrapped_escrow_address: &Pubkey,
+    recipient_address: &Pubkey,
+    wrapped_mint_authority_address: &Pubkey,
+    native_mint_address: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+    unwrapped_token_program_id: &Pubkey,
+    wrapped_token_account_address: &Pubkey,
+    wrapped_mint_address: &Pubkey,
+    transfer_authority_address: &Pubkey,
+    wrap_limits_address: &Pubkey,
+    allowlist_address: &Pubkey,
+    allowlist_entry_address: &Pubkey,
+    protocol_fee_config_address: &Pubkey,
+    protocol_fee_override_address: &Pubkey,
+    protocol_fee_vault_address: &Pubkey,
+    flash_mint_guard_address: &Pubkey,
+    native_unwrap_account_address: &Pubkey,
+    payer_address: &Pubkey,
+    pro_rata_redemption_address: Option<&Pubkey>,
+    multisig_signer_pubkeys: &[&Pubkey],
+    amount: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*unwrapped_escrow_address, false),
+        AccountMeta::new(*recipient_address, false),
+        AccountMeta::new_readonly(*wrapped_mint_authority_address, false),
+        AccountMeta::new_readonly(*native_mint_address, false),
+        AccountMeta::new_readonly(*wrapped_token_program_id, false),
+        AccountMeta::new_readonly(*unwrapped_token_program_id, false),
+        AccountMeta::new(*wrapped_token_account_address, false),
+        AccountMeta::new(*wrapped_mint_address, false),
+        AccountMeta::new_readonly(
+            *transfer_authority_address,
+            multisig_signer_pubkeys.is_empty(),
+        ),
+        AccountMeta::new(*wrap_limits_address, false),
+        AccountMeta::new_readonly(*allowlist_address, false),
+        AccountMeta::new_readonly(*allowlist_entry_address, false),
+        AccountMeta::new_readonly(*protocol_fee_config_address, false),
+        AccountMeta::new_readonly(*protocol_fee_override_address, false),
+        AccountMeta::new(*protocol_fee_vault_address, false),
+        AccountMeta::new_readonly(*flash_mint_guard_address, false),
+        AccountMeta::new(*native_unwrap_account_address, false),
+        AccountMeta::new(*payer_address, true),
+        AccountMeta::new_readonly(solana_system_interface::program::id(), false),
+    ];
+    if let Some(pro_rata_redemption_address) = pro_rata_redemption_address {
+        accounts.push(AccountMeta::new(*pro_rata_redemption_address, false));
+    }
+    for signer_pubkey in multisig_signer_pubkeys.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+
+    let data = TokenWrapInstruction::UnwrapSol {
+        amount,
+        pro_rata: pro_rata_redemption_address.is_some(),
+    }
+    .pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `CloseStuckEscrow` instruction.
+pub fn close_stuck_escrow(
+    program_id: &Pubkey,
+    escrow_address: &Pubkey,
+    destination_address: &Pubkey,
+    unwrapped_mint_address: &Pubkey,
+    wrapped_mint_address: &Pubkey,
+    wrapped_mint_authority_address: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*escrow_address, false),
+        AccountMeta::new(*destination_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint_address, false),
+        AccountMeta::new_readonly(*wrapped_mint_address, false),
+        AccountMeta::new_readonly(*wrapped_mint_authority_address, false),
+        AccountMeta::new_readonly(spl_token_2022_interface::id(), false),
+    ];
+    let data = TokenWrapInstruction::CloseStuckEscrow.pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `RepairEscrow` instruction.
+pub fn repair_escrow(
+    program_id: &Pubkey,
+    escrow_address: &Pubkey,
+    payer_address: &Pubkey,
+    unwrapped_mint_address: &Pubkey,
+    wrapped_mint_address: &Pubkey,
+    wrapped_mint_authority_address: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*escrow_address, false),
+        AccountMeta::new(*payer_address, true),
+        AccountMeta::new_readonly(*unwrapped_mint_address, false),
+        AccountMeta::new_readonly(*wrapped_mint_address, false),
+        AccountMeta::new_readonly(*wrapped_mint_authority_address, false),
+        AccountMeta::new_readonly(solana_system_interface::program::id(), false),
+        AccountMeta::new_readonly(spl_token_2022_interface::id(), false),
+    ];
+    let data = TokenWrapInstruction::RepairEscrow.pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `SetEscrowSurplusPolicy` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn set_escrow_surplus_policy(
+    program_id: &Pubkey,
+    policy_authority: &Pubkey,
+    policy_address: &Pubkey,
+    unwrapped_mint: &Pubkey,
+    wrapped_mint: &Pubkey,
+    action: SurplusAction,
+    target: &Pubkey,
+    source_metadata: Option<&Pubkey>,
+    owner_program: Option<&Pubkey>,
+    multisig_signer_pubkeys: &[&Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*policy_authority, multisig_signer_pubkeys.is_empty()),
+        AccountMeta::new(*policy_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint, false),
+        AccountMeta::new_readonly(*wrapped_mint, false),
+        AccountMeta::new_readonly(solana_system_interface::program::id(), false),
+    ];
+    push_pointer_authority_accounts(
+        &mut accounts,
+        source_metadata,
+        owner_program,
+        multisig_signer_pubkeys,
+    );
+    let data = TokenWrapInstruction::SetEscrowSurplusPolicy {
+        action,
+        target: *target,
+    }
+    .pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `UpdateConfidentialConfig` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn update_confidential_config(
+    program_id: &Pubkey,
+    config_authority: &Pubkey,
+    wrapped_mint: &Pubkey,
+    wrapped_mint_authority: &Pubkey,
+    unwrapped_mint: &Pubkey,
+    auto_approve_new_accounts: bool,
+    auditor_elgamal_pubkey: Option<PodElGamalPubkey>,
+    source_metadata: Option<&Pubkey>,
+    owner_program: Option<&Pubkey>,
+    multisig_signer_pubkeys: &[&Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*config_authority, multisig_signer_pubkeys.is_empty()),
+        AccountMeta::new(*wrapped_mint, false),
+        AccountMeta::new_readonly(*wrapped_mint_authority, false),
+        AccountMeta::new_readonly(*unwrapped_mint, false),
+        AccountMeta::new_readonly(spl_token_2022_interface::id(), false),
+    ];
+    push_pointer_authority_accounts(
+        &mut accounts,
+        source_metadata,
+        owner_program,
+        multisig_signer_pubkeys,
+    );
+    let data = TokenWrapInstruction::UpdateConfidentialConfig {
+        auto_approve_new_accounts,
+        auditor_elgamal_pubkey,
+    }
+    .pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `SetWrappedMintAdmin` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn set_wrapped_mint_admin(
+    program_id: &Pubkey,
+    unwrapped_mint_authority: &Pubkey,
+    admin_address: &Pubkey,
+    unwrapped_mint: &Pubkey,
+    wrapped_mint: &Pubkey,
+    admin: &Pubkey,
+    source_metadata: Option<&Pubkey>,
+    owner_program: Option<&Pubkey>,
+    multisig_signer_pubkeys: &[&Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(
+            *unwrapped_mint_authority,
+            multisig_signer_pubkeys.is_empty(),
+        ),
+        AccountMeta::new(*admin_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint, false),
+        AccountMeta::new_readonly(*wrapped_mint, false),
+        AccountMeta::new_readonly(solana_system_interface::program::id(), false),
+    ];
+    push_pointer_authority_accounts(
+        &mut accounts,
+        source_metadata,
+        owner_program,
+        multisig_signer_pubkeys,
+    );
+    let data = TokenWrapInstruction::SetWrappedMintAdmin { admin: *admin }.pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `ExecuteAdminAction` instruction. The wrapped token account is
+/// required for `FreezeAccount` and `ThawAccount`.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_admin_action(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    wrapped_mint: &Pubkey,
+    wrapped_mint_authority: &Pubkey,
+    unwrapped_mint: &Pubkey,
+    admin_address: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+    wrapped_token_account: Option<&Pubkey>,
+    action: AdminAction,
+    source_metadata: Option<&Pubkey>,
+    owner_program: Option<&Pubkey>,
+    multisig_signer_pubkeys: &[&Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*admin, multisig_signer_pubkeys.is_empty()),
+        AccountMeta::new(*wrapped_mint, false),
+        AccountMeta::new_readonly(*wrapped_mint_authority, false),
+        AccountMeta::new_readonly(*unwrapped_mint, false),
+        AccountMeta::new_readonly(*admin_address, false),
+        AccountMeta::new_readonly(*wrapped_token_program_id, false),
+    ];
+    if let Some(wrapped_token_account) = wrapped_token_account {
+        accounts.push(AccountMeta::new(*wrapped_token_account, false));
+    }
+    push_pointer_authority_accounts(
+        &mut accounts,
+        source_metadata,
+        owner_program,
+        multisig_signer_pubkeys,
+    );
+    let data = TokenWrapInstruction::ExecuteAdminAction { action }.pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `SetWrapLimits` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn set_wrap_limits(
+    program_id: &Pubkey,
+    unwrapped_mint_authority: &Pubkey,
+    wrap_limits_address: &Pubkey,
+    unwrapped_mint: &Pubkey,
+    spl_token_wrapped_mint: &Pubkey,
+    token_2022_wrapped_mint: &Pubkey,
+    unwrap_only: bool,
+    max_wrapped_supply: u64,
+    max_wrapped_per_epoch: u64,
+    source_metadata: Option<&Pubkey>,
+    owner_program: Option<&Pubkey>,
+    multisig_signer_pubkeys: &[&Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(
+            *unwrapped_mint_authority,
+            multisig_signer_pubkeys.is_empty(),
+        ),
+        AccountMeta::new(*wrap_limits_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint, false),
+        AccountMeta::new_readonly(solana_system_interface::program::id(), false),
+        AccountMeta::new_readonly(*spl_token_wrapped_mint, false),
+        AccountMeta::new_readonly(*token_2022_wrapped_mint, false),
+    ];
+    push_pointer_authority_accounts(
+        &mut accounts,
+        source_metadata,
+        owner_program,
+        multisig_signer_pubkeys,
+    );
+    let data = TokenWrapInstruction::SetWrapLimits {
+        unwrap_only,
+        max_wrapped_supply,
+        max_wrapped_per_epoch,
+    }
+    .pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `SetAllowlistMode` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn set_allowlist_mode(
+    program_id: &Pubkey,
+    unwrapped_mint_authority: &Pubkey,
+    allowlist_address: &Pubkey,
+    unwrapped_mint: &Pubkey,
+    enabled: bool,
+    source_metadata: Option<&Pubkey>,
+    owner_program: Option<&Pubkey>,
+    multisig_signer_pubkeys: &[&Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(
+            *unwrapped_mint_authority,
+            multisig_signer_pubkeys.is_empty(),
+        ),
+        AccountMeta::new(*allowlist_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint, false),
+        AccountMeta::new_readonly(solana_system_interface::program::id(), false),
+    ];
+    push_pointer_authority_accounts(
+        &mut accounts,
+        source_metadata,
+        owner_program,
+        multisig_signer_pubkeys,
+    );
+    let data = TokenWrapInstruction::SetAllowlistMode { enabled }.pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `UpdateAllowlist` instruction. Each wallet is paired with the
+/// address of its allowlist entry.
+#[allow(clippy::too_many_arguments)]
+pub fn update_allowlist(
+    program_id: &Pubkey,
+    unwrapped_mint_authority: &Pubkey,
+    unwrapped_mint: &Pubkey,
+    allowed: bool,
+    wallets: &[(Pubkey, Pubkey)],
+    source_metadata: Option<&Pubkey>,
+    owner_program: Option<&Pubkey>,
+    multisig_signer_pubkeys: &[&Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(
+            *unwrapped_mint_authority,
+            multisig_signer_pubkeys.is_empty(),
+        ),
+        AccountMeta::new_readonly(*unwrapped_mint, false),
+        AccountMeta::new_readonly(solana_system_interface::program::id(), false),
+    ];
+    accounts.extend(
+        wallets
+            .iter()
+            .map(|(_, allowlist_entry_address)| AccountMeta::new(*allowlist_entry_address, false)),
+    );
+    push_pointer_authority_accounts(
+        &mut accounts,
+        source_metadata,
+        owner_program,
+        multisig_signer_pubkeys,
+    );
+    let data = TokenWrapInstruction::UpdateAllowlist {
+        allowed,
+        wallets: wallets.iter().map(|(wallet, _)| *wallet).collect(),
+    }
+    .pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `SetProtocolFeeConfig` instruction.
+pub fn set_protocol_fee_config(
+    program_id: &Pubkey,
+    upgrade_authority: &Pubkey,
+    protocol_fee_config_address: &Pubkey,
+    program_data_address: &Pubkey,
+    wrap_fee_basis_points: u16,
+    unwrap_fee_basis_points: u16,
+    fee_destination: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*upgrade_authority, true),
+        AccountMeta::new(*protocol_fee_config_address, false),
+        AccountMeta::new_readonly(*program_data_address, false),
+        AccountMeta::new_readonly(solana_system_interface::program::id(), false),
+    ];
+    let data = TokenWrapInstruction::SetProtocolFeeConfig {
+        wrap_fee_basis_points,
+        unwrap_fee_basis_points,
+        fee_destination: *fee_destination,
+    }
+    .pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `SetProtocolFeeOverride` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn set_protocol_fee_override(
+    program_id: &Pubkey,
+    upgrade_authority: &Pubkey,
+    protocol_fee_override_address: &Pubkey,
+    unwrapped_mint: &Pubkey,
+    program_data_address: &Pubkey,
+    enabled: bool,
+    wrap_fee_basis_points: u16,
+    unwrap_fee_basis_points: u16,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*upgrade_authority, true),
+        AccountMeta::new(*protocol_fee_override_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint, false),
+        AccountMeta::new_readonly(*program_data_address, false),
+        AccountMeta::new_readonly(solana_system_interface::program::id(), false),
+    ];
+    let data = TokenWrapInstruction::SetProtocolFeeOverride {
+        enabled,
+        wrap_fee_basis_points,
+        unwrap_fee_basis_points,
+    }
+    .pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `FlashMint` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn flash_mint(
+    program_id: &Pubkey,
+    recipient_wrapped_token_account_address: &Pubkey,
+    wrapped_mint_address: &Pubkey,
+    wrapped_mint_authority_address: &Pubkey,
+    unwrapped_mint_address: &Pubkey,
+    unwrapped_escrow_address: &Pubkey,
+    flash_mint_guard_address: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+    unwrapped_token_program_id: &Pubkey,
+    canonical_pointer_address: &Pubkey,
+    scoped_canonical_pointer_address: &Pubkey,
+    wrap_limits_address: &Pubkey,
+    allowlist_address: &Pubkey,
+    allowlist_entry_address: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*recipient_wrapped_token_account_address, false),
+        AccountMeta::new(*wrapped_mint_address, false),
+        AccountMeta::new_readonly(*wrapped_mint_authority_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint_address, false),
+        AccountMeta::new_readonly(*unwrapped_escrow_address, false),
+        AccountMeta::new(*flash_mint_guard_address, false),
+        AccountMeta::new_readonly(*wrapped_token_program_id, false),
+        AccountMeta::new_readonly(*unwrapped_token_program_id, false),
+        AccountMeta::new_readonly(solana_instructions_sysvar::id(), false),
+        AccountMeta::new_readonly(solana_system_interface::program::id(), false),
+        AccountMeta::new_readonly(*canonical_pointer_address, false),
+        AccountMeta::new_readonly(*scoped_canonical_pointer_address, false),
+        AccountMeta::new_readonly(*wrap_limits_address, false),
+        AccountMeta::new_readonly(*allowlist_address, false),
+        AccountMeta::new_readonly(*allowlist_entry_address, false),
+    ];
+    let data = TokenWrapInstruction::FlashMint { amount }.pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `FlashRepay` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn flash_repay(
+    program_id: &Pubkey,
+    wrapped_token_account_address: &Pubkey,
+    wrapped_mint_address: &Pubkey,
+    unwrapped_escrow_address: &Pubkey,
+    flash_mint_guard_address: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+    transfer_authority_address: &Pubkey,
+    multisig_signer_pubkeys: &[&Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*wrapped_token_account_address, false),
+        AccountMeta::new(*wrapped_mint_address, false),
+        AccountMeta::new_readonly(*unwrapped_escrow_address, false),
+        AccountMeta::new(*flash_mint_guard_address, false),
+        AccountMeta::new_readonly(*wrapped_token_program_id, false),
+        AccountMeta::new_readonly(
+            *transfer_authority_address,
+            multisig_signer_pubkeys.is_empty(),
+        ),
+    ];
+    for signer_pubkey in multisig_signer_pubkeys.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+    let data = TokenWrapInstruction::FlashRepay.pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `SetFlashMintFee` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn set_flash_mint_fee(
+    program_id: &Pubkey,
+    unwrapped_mint_authority: &Pubkey,
+    flash_mint_guard_address: &Pubkey,
+    unwrapped_mint: &Pubkey,
+    wrapped_mint: &Pubkey,
+    fee_basis_points: u16,
+    source_metadata: Option<&Pubkey>,
+    owner_program: Option<&Pubkey>,
+    multisig_signer_pubkeys: &[&Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(
+            *unwrapped_mint_authority,
+            multisig_signer_pubkeys.is_empty(),
+        ),
+        AccountMeta::new(*flash_mint_guard_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint, false),
+        AccountMeta::new_readonly(*wrapped_mint, false),
+        AccountMeta::new_readonly(solana_system_interface::program::id(), false),
+    ];
+    push_pointer_authority_accounts(
+        &mut accounts,
+        source_metadata,
+        owner_program,
+        multisig_signer_pubkeys,
+    );
+    let data = TokenWrapInstruction::SetFlashMintFee { fee_basis_points }.pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `WrapWithPermit` instruction.
+///
+/// The transaction must also include an instruction of the ed25519 program
+/// verifying the signature of the permit, such as one created by
+/// `solana_ed25519_program::new_ed25519_instruction_with_signature`.
+#[allow(clippy::too_many_arguments)]
+pub fn wrap_with_permit(
+    program_id: &Pubkey,
+    recipient_wrapped_token_account_address: &Pubkey,
+    wrapped_mint_address: &Pubkey,
+    wrapped_mint_authority_address: &Pubkey,
+    unwrapped_token_program_id: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+    unwrapped_token_account_address: &Pubkey,
+    unwrapped_mint_address: &Pubkey,
+    unwrapped_escrow_address: &Pubkey,
+    canonical_pointer_address: &Pubkey,
+    scoped_canonical_pointer_address: &Pubkey,
+    wrap_limits_address: &Pubkey,
+    allowlist_address: &Pubkey,
+    allowlist_entry_address: &Pubkey,
+    protocol_fee_config_address: &Pubkey,
+    protocol_fee_override_address: &Pubkey,
+    protocol_fee_vault_address: &Pubkey,
+    permit_delegate_address: &Pubkey,
+    owner_address: &Pubkey,
+    permit_nonce_address: &Pubkey,
+    amount: u64,
+    nonce: u64,
+    expiry: i64,
+) -> Instruction {
+    let mut instruction = wrap(
+        program_id,
+        recipient_wrapped_token_account_address,
+        wrapped_mint_address,
+        wrapped_mint_authority_address,
+        unwrapped_token_program_id,
+        wrapped_token_program_id,
+        unwrapped_token_account_address,
+        unwrapped_mint_address,
+        unwrapped_escrow_address,
+        canonical_pointer_address,
+        scoped_canonical_pointer_address,
+        wrap_limits_address,
+        allowlist_address,
+        allowlist_entry_address,
+        protocol_fee_config_address,
+        protocol_fee_override_address,
+        protocol_fee_vault_address,
+        permit_delegate_address,
+        None,
+        &[],
+        amount,
+    );
+    instruction.accounts[8] = AccountMeta::new_readonly(*permit_delegate_address, false);
+    instruction
+        .accounts
+        .extend(permit_accounts(owner_address, permit_nonce_address));
+    instruction.data = TokenWrapInstruction::WrapWithPermit {
+        amount,
+        nonce,
+        expiry,
+    }
+    .pack();
+    instruction
+}
+
+/// Creates `UnwrapWithPermit` instruction.
+///
+/// The transaction must also include an instruction of the ed25519 program
+/// verifying the signature of the permit, such as one created by
+/// `solana_ed25519_program::new_ed25519_instruction_with_signature`.
+#[allow(clippy::too_many_arguments)]
+pub fn unwrap_with_permit(
+    program_id: &Pubkey,
+    unwrapped_escrow_address: &Pubkey,
+    recipient_unwrapped_token_account_address: &Pubkey,
+    wrapped_mint_authority_address: &Pubkey,
+    unwrapped_mint_address: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+    unwrapped_token_program_id: &Pubkey,
+    wrapped_token_account_address: &Pubkey,
+    wrapped_mint_address: &Pubkey,
+    wrap_limits_address: &Pubkey,
+    allowlist_address: &Pubkey,
+    allowlist_entry_address: &Pubkey,
+    protocol_fee_config_address: &Pubkey,
+    protocol_fee_override_address: &Pubkey,
+    protocol_fee_vault_address: &Pubkey,
+    flash_mint_guard_address: &Pubkey,
+    permit_delegate_address: &Pubkey,
+    owner_address: &Pubkey,
+    permit_nonce_address: &Pubkey,
+    amount: u64,
+    nonce: u64,
+    expiry: i64,
+) -> Instruction {
+    let mut instruction = unwrap(
+        program_id,
+        unwrapped_escrow_address,
+        recipient_unwrapped_token_account_address,
+        wrapped_mint_authority_address,
+        unwrapped_mint_address,
+        wrapped_token_program_id,
+        unwrapped_token_program_id,
+        wrapped_token_account_address,
+        wrapped_mint_address,
+        permit_delegate_address,
+        wrap_limits_address,
+        allowlist_address,
+        allowlist_entry_address,
+        protocol_fee_config_address,
+        protocol_fee_override_address,
+        protocol_fee_vault_address,
+        flash_mint_guard_address,
+        None,
+        None,
+        &[],
+        amount,
+    );
+    instruction.accounts[8] = AccountMeta::new_readonly(*permit_delegate_address, false);
+    instruction
+        .accounts
+        .extend(permit_accounts(owner_address, permit_nonce_address));
+    instruction.data = TokenWrapInstruction::UnwrapWithPermit {
+        amount,
+        nonce,
+        expiry,
+    }
+    .pack();
+    instruction
+}
+
+/// Accounts following the fixed accounts of `Wrap` or `Unwrap` in their
+/// permit variants
+fn permit_accounts(owner_address: &Pubkey, permit_nonce_address: &Pubkey) -> [AccountMeta; 4] {
+    [
+        AccountMeta::new_readonly(*owner_address, false),
+        AccountMeta::new(*permit_nonce_address, false),
+        AccountMeta::new_readonly(solana_instructions_sysvar::id(), false),
+        AccountMeta::new_readonly(solana_system_interface::program::id(), false),
+    ]
+}
+
+/// Creates `SetCrankWrapRecipient` instruction.
+pub fn set_crank_wrap_recipient(
+    program_id: &Pubkey,
+    owner_address: &Pubkey,
+    crank_wrap_delegate_address: &Pubkey,
+    unwrapped_mint_address: &Pubkey,
+    recipient: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*owner_address, true),
+        AccountMeta::new(*crank_wrap_delegate_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint_address, false),
+        AccountMeta::new_readonly(solana_system_interface::program::id(), false),
+    ];
+    let data = TokenWrapInstruction::SetCrankWrapRecipient {
+        recipient: *recipient,
+    }
+    .pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `CrankWrap` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn crank_wrap(
+    program_id: &Pubkey,
+    recipient_wrapped_token_account_address: &Pubkey,
+    wrapped_mint_address: &Pubkey,
+    wrapped_mint_authority_address: &Pubkey,
+    unwrapped_token_program_id: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+    unwrapped_token_account_address: &Pubkey,
+    unwrapped_mint_address: &Pubkey,
+    unwrapped_escrow_address: &Pubkey,
+    canonical_pointer_address: &Pubkey,
+    scoped_canonical_pointer_address: &Pubkey,
+    wrap_limits_address: &Pubkey,
+    allowlist_address: &Pubkey,
+    allowlist_entry_address: &Pubkey,
+    protocol_fee_config_address: &Pubkey,
+    protocol_fee_override_address: &Pubkey,
+    protocol_fee_vault_address: &Pubkey,
+    crank_wrap_delegate_address: &Pubkey,
+) -> Instruction {
+    let mut instruction = wrap(
+        program_id,
+        recipient_wrapped_token_account_address,
+        wrapped_mint_address,
+        wrapped_mint_authority_address,
+        unwrapped_token_program_id,
+        wrapped_token_program_id,
+        unwrapped_token_account_address,
+        unwrapped_mint_address,
+        unwrapped_escrow_address,
+        canonical_pointer_address,
+        scoped_canonical_pointer_address,
+        wrap_limits_address,
+        allowlist_address,
+        allowlist_entry_address,
+        protocol_fee_config_address,
+        protocol_fee_override_address,
+        protocol_fee_vault_address,
+        crank_wrap_delegate_address,
+        None,
+        &[],
+        0,
+    );
+    instruction.accounts[8] = AccountMeta::new_readonly(*crank_wrap_delegate_address, false);
+    instruction.data = TokenWrapInstruction::CrankWrap.pack();
+    instruction
+}
+
+/// Creates `ReconcileEscrow` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn reconcile_escrow(
+    program_id: &Pubkey,
+    escrow_address: &Pubkey,
+    unwrapped_mint_address: &Pubkey,
+    wrapped_mint_address: &Pubkey,
+    wrapped_mint_authority_address: &Pubkey,
+    policy_address: &Pubkey,
+    destination_address: &Pubkey,
+    unwrapped_token_program_id: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+    wrap_limits_address: &Pubkey,
+    claimant_address: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*escrow_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint_address, false),
+        AccountMeta::new(*wrapped_mint_address, false),
+        AccountMeta::new_readonly(*wrapped_mint_authority_address, false),
+        AccountMeta::new_readonly(*policy_address, false),
+        AccountMeta::new(*destination_address, false),
+        AccountMeta::new_readonly(*unwrapped_token_program_id, false),
+        AccountMeta::new_readonly(*wrapped_token_program_id, false),
+        AccountMeta::new(*wrap_limits_address, false),
+    ];
+    if let Some(claimant_address) = claimant_address {
+        accounts.push(AccountMeta::new_readonly(*claimant_address, true));
+    }
+    let data = TokenWrapInstruction::ReconcileEscrow.pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `SyncMetadataToToken2022` instruction.
+pub fn sync_metadata_to_token_2022(
+    program_id: &Pubkey,
+    wrapped_mint: &Pubkey,
+    wrapped_mint_authority: &Pubkey,
+    unwrapped_mint: &Pubkey,
+    source_metadata: Option<&Pubkey>,
+    owner_program: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*wrapped_mint, false),
         AccountMeta::new_readonly(*wrapped_mint_authority, false),
         AccountMeta::new_readonly(*unwrapped_mint, false),
         AccountMeta::new_readonly(spl_token_2022_interface::id(), false),
@@ -444,23 +3042,277 @@ pub fn sync_metadata_to_spl_token(
     Instruction::new_with_bytes(*program_id, &data, accounts)
 }
 
+fn push_pointer_authority_accounts(
+    accounts: &mut Vec<AccountMeta>,
+    source_metadata: Option<&Pubkey>,
+    owner_program: Option<&Pubkey>,
+    multisig_signer_pubkeys: &[&Pubkey],
+) {
+    if let Some(pubkey) = source_metadata {
+        accounts.push(AccountMeta::new_readonly(*pubkey, false));
+    }
+
+    if let Some(owner) = owner_program {
+        accounts.push(AccountMeta::new_readonly(*owner, false));
+    }
+
+    for signer_pubkey in multisig_signer_pubkeys.iter() {
+        accounts.push(AccountMeta::new_readonly(**signer_pubkey, true));
+    }
+}
+
 /// Creates `SetCanonicalPointer` instruction.
+#[allow(clippy::too_many_arguments)]
 pub fn set_canonical_pointer(
     program_id: &Pubkey,
-    mint_authority: &Pubkey,
+    pointer_authority: &Pubkey,
     pointer_address: &Pubkey,
     unwrapped_mint: &Pubkey,
+    wrapped_token_program: Option<&Pubkey>,
+    unscoped_pointer_address: Option<&Pubkey>,
     canonical_program_id: &Pubkey,
+    enforce_on_wrap: bool,
+    change_delay: u64,
+    source_metadata: Option<&Pubkey>,
+    owner_program: Option<&Pubkey>,
+    multisig_signer_pubkeys: &[&Pubkey],
 ) -> Instruction {
-    let accounts = vec![
-        AccountMeta::new_readonly(*mint_authority, true),
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*pointer_authority, multisig_signer_pubkeys.is_empty()),
         AccountMeta::new(*pointer_address, false),
         AccountMeta::new_readonly(*unwrapped_mint, false),
         AccountMeta::new_readonly(solana_system_interface::program::id(), false),
     ];
+    if let Some(unscoped_pointer_address) = unscoped_pointer_address {
+        accounts.push(AccountMeta::new_readonly(*unscoped_pointer_address, false));
+    }
+    push_pointer_authority_accounts(
+        &mut accounts,
+        source_metadata,
+        owner_program,
+        multisig_signer_pubkeys,
+    );
     let data = TokenWrapInstruction::SetCanonicalPointer {
         program_id: *canonical_program_id,
+        enforce_on_wrap,
+        change_delay,
+        wrapped_token_program: wrapped_token_program.copied(),
+    }
+    .pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `CloseCanonicalPointer` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn close_canonical_pointer(
+    program_id: &Pubkey,
+    pointer_authority: &Pubkey,
+    pointer_address: &Pubkey,
+    unwrapped_mint: &Pubkey,
+    wrapped_token_program: Option<&Pubkey>,
+    unscoped_pointer_address: Option<&Pubkey>,
+    destination: &Pubkey,
+    source_metadata: Option<&Pubkey>,
+    owner_program: Option<&Pubkey>,
+    multisig_signer_pubkeys: &[&Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*pointer_authority, multisig_signer_pubkeys.is_empty()),
+        AccountMeta::new(*pointer_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint, false),
+        AccountMeta::new(*destination, false),
+    ];
+    if let Some(unscoped_pointer_address) = unscoped_pointer_address {
+        accounts.push(AccountMeta::new_readonly(*unscoped_pointer_address, false));
+    }
+    push_pointer_authority_accounts(
+        &mut accounts,
+        source_metadata,
+        owner_program,
+        multisig_signer_pubkeys,
+    );
+    let data = TokenWrapInstruction::CloseCanonicalPointer {
+        wrapped_token_program: wrapped_token_program.copied(),
+    }
+    .pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `ProposeCanonicalPointer` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn propose_canonical_pointer(
+    program_id: &Pubkey,
+    pointer_authority: &Pubkey,
+    pointer_address: &Pubkey,
+    pending_pointer_address: &Pubkey,
+    unwrapped_mint: &Pubkey,
+    wrapped_token_program: Option<&Pubkey>,
+    unscoped_pointer_address: Option<&Pubkey>,
+    canonical_program_id: &Pubkey,
+    enforce_on_wrap: bool,
+    change_delay: u64,
+    source_metadata: Option<&Pubkey>,
+    owner_program: Option<&Pubkey>,
+    multisig_signer_pubkeys: &[&Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*pointer_authority, multisig_signer_pubkeys.is_empty()),
+        AccountMeta::new_readonly(*pointer_address, false),
+        AccountMeta::new(*pending_pointer_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint, false),
+        AccountMeta::new_readonly(solana_system_interface::program::id(), false),
+    ];
+    if let Some(unscoped_pointer_address) = unscoped_pointer_address {
+        accounts.push(AccountMeta::new_readonly(*unscoped_pointer_address, false));
+    }
+    push_pointer_authority_accounts(
+        &mut accounts,
+        source_metadata,
+        owner_program,
+        multisig_signer_pubkeys,
+    );
+    let data = TokenWrapInstruction::ProposeCanonicalPointer {
+        program_id: *canonical_program_id,
+        enforce_on_wrap,
+        change_delay,
+        wrapped_token_program: wrapped_token_program.copied(),
+    }
+    .pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `AcceptCanonicalPointer` instruction.
+pub fn accept_canonical_pointer(
+    program_id: &Pubkey,
+    pointer_address: &Pubkey,
+    pending_pointer_address: &Pubkey,
+    unwrapped_mint: &Pubkey,
+    wrapped_token_program: Option<&Pubkey>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*pointer_address, false),
+        AccountMeta::new(*pending_pointer_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint, false),
+        AccountMeta::new_readonly(solana_system_interface::program::id(), false),
+    ];
+    let data = TokenWrapInstruction::AcceptCanonicalPointer {
+        wrapped_token_program: wrapped_token_program.copied(),
+    }
+    .pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `CancelCanonicalPointer` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn cancel_canonical_pointer(
+    program_id: &Pubkey,
+    pointer_authority: &Pubkey,
+    pending_pointer_address: &Pubkey,
+    unwrapped_mint: &Pubkey,
+    wrapped_token_program: Option<&Pubkey>,
+    destination: &Pubkey,
+    source_metadata: Option<&Pubkey>,
+    owner_program: Option<&Pubkey>,
+    multisig_signer_pubkeys: &[&Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*pointer_authority, multisig_signer_pubkeys.is_empty()),
+        AccountMeta::new(*pending_pointer_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint, false),
+        AccountMeta::new(*destination, false),
+    ];
+    push_pointer_authority_accounts(
+        &mut accounts,
+        source_metadata,
+        owner_program,
+        multisig_signer_pubkeys,
+    );
+    let data = TokenWrapInstruction::CancelCanonicalPointer {
+        wrapped_token_program: wrapped_token_program.copied(),
     }
     .pack();
     Instruction::new_with_bytes(*program_id, &data, accounts)
 }
+
+/// Creates `MigrateState` instruction.
+pub fn migrate_state(
+    program_id: &Pubkey,
+    state_address: &Pubkey,
+    mint_address: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*state_address, false),
+        AccountMeta::new_readonly(*mint_address, false),
+    ];
+    let data = TokenWrapInstruction::MigrateState.pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `MigrateState` instruction for a backpointer, backfilling its
+/// provenance data.
+pub fn migrate_backpointer(
+    program_id: &Pubkey,
+    backpointer_address: &Pubkey,
+    wrapped_mint_address: &Pubkey,
+    unwrapped_mint_address: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*backpointer_address, false),
+        AccountMeta::new_readonly(*wrapped_mint_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint_address, false),
+    ];
+    let data = TokenWrapInstruction::MigrateState.pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `CloseWrappedMint` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn close_wrapped_mint(
+    program_id: &Pubkey,
+    wrapped_mint_address: &Pubkey,
+    backpointer_address: &Pubkey,
+    destination_address: &Pubkey,
+    unwrapped_mint_address: &Pubkey,
+    escrow_address: &Pubkey,
+    wrapped_mint_authority_address: &Pubkey,
+    unwrapped_token_program_id: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*wrapped_mint_address, false),
+        AccountMeta::new(*backpointer_address, false),
+        AccountMeta::new(*destination_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint_address, false),
+        AccountMeta::new_readonly(*escrow_address, false),
+        AccountMeta::new_readonly(*wrapped_mint_authority_address, false),
+        AccountMeta::new_readonly(*unwrapped_token_program_id, false),
+        AccountMeta::new_readonly(spl_token_2022_interface::id(), false),
+    ];
+    let data = TokenWrapInstruction::CloseWrappedMint.pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
+
+/// Creates `RegisterWrappedMint` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn register_wrapped_mint(
+    program_id: &Pubkey,
+    wrapped_mint_address: &Pubkey,
+    wrapped_backpointer_address: &Pubkey,
+    unwrapped_mint_address: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+    registry_address: &Pubkey,
+    registry_page_address: &Pubkey,
+    wrapped_mint_index_address: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*wrapped_mint_address, false),
+        AccountMeta::new_readonly(*wrapped_backpointer_address, false),
+        AccountMeta::new_readonly(*unwrapped_mint_address, false),
+        AccountMeta::new_readonly(solana_system_interface::program::id(), false),
+        AccountMeta::new_readonly(*wrapped_token_program_id, false),
+        AccountMeta::new(*registry_address, false),
+        AccountMeta::new(*registry_page_address, false),
+        AccountMeta::new(*wrapped_mint_index_address, false),
+    ];
+    let data = TokenWrapInstruction::RegisterWrappedMint.pack();
+    Instruction::new_with_bytes(*program_id, &data, accounts)
+}
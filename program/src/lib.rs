@@ -4,10 +4,12 @@
 
 mod entrypoint;
 pub mod error;
+pub mod extension_policy;
 pub mod instruction;
 pub mod metadata;
 pub mod metaplex;
 pub mod mint_customizer;
+pub mod permit;
 pub mod processor;
 pub mod state;
 
@@ -18,6 +20,29 @@ use {
 
 solana_pubkey::declare_id!("TwRapQCDhWkZRrDaHfZGuHxkZ91gHDRkyuzNqeU5MgR");
 
+const fn parse_version_component(component: &str) -> u8 {
+    let mut digits = component.as_bytes();
+    let mut value: u8 = 0;
+    while let [digit, rest @ ..] = digits {
+        assert!(digit.is_ascii_digit(), "version component is not a number");
+        value = value
+            .checked_mul(10)
+            .expect("version component is too large")
+            .checked_add(digit.abs_diff(b'0'))
+            .expect("version component is too large");
+        digits = rest;
+    }
+    value
+}
+
+/// Version of the program as major, minor and patch, recorded in the
+/// backpointer of every wrapped mint it creates
+pub const PROGRAM_VERSION: [u8; 3] = [
+    parse_version_component(env!("CARGO_PKG_VERSION_MAJOR")),
+    parse_version_component(env!("CARGO_PKG_VERSION_MINOR")),
+    parse_version_component(env!("CARGO_PKG_VERSION_PATCH")),
+];
+
 const WRAPPED_MINT_SEED: &[u8] = br"mint";
 
 pub(crate) fn get_wrapped_mint_address_with_seed(
@@ -245,3 +270,862 @@ pub fn get_canonical_pointer_address_for_program(
 ) -> Pubkey {
     get_canonical_pointer_address_with_seed_for_program(unwrapped_mint, program_id).0
 }
+
+/// Derives the canonical pointer address and bump seed scoped to a wrapped
+/// token program for a specific Token Wrap program deployment.
+pub(crate) fn get_scoped_canonical_pointer_address_with_seed_for_program(
+    unwrapped_mint: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            CANONICAL_POINTER_SEED,
+            unwrapped_mint.as_ref(),
+            wrapped_token_program_id.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+pub(crate) fn get_scoped_canonical_pointer_address_signer_seeds<'a>(
+    unwrapped_mint: &'a Pubkey,
+    wrapped_token_program_id: &'a Pubkey,
+    bump_seed: &'a [u8],
+) -> [&'a [u8]; 4] {
+    [
+        CANONICAL_POINTER_SEED,
+        unwrapped_mint.as_ref(),
+        wrapped_token_program_id.as_ref(),
+        bump_seed,
+    ]
+}
+
+/// Derives the canonical pointer address and bump seed scoped to a wrapped
+/// token program.
+pub(crate) fn get_scoped_canonical_pointer_address_with_seed(
+    unwrapped_mint: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    get_scoped_canonical_pointer_address_with_seed_for_program(
+        unwrapped_mint,
+        wrapped_token_program_id,
+        &id(),
+    )
+}
+
+/// Derives the canonical pointer address for an unwrapped mint scoped to a
+/// wrapped token program. If no account exists at this address, clients
+/// should fall back to `get_canonical_pointer_address`.
+pub fn get_scoped_canonical_pointer_address(
+    unwrapped_mint: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+) -> Pubkey {
+    get_scoped_canonical_pointer_address_for_program(
+        unwrapped_mint,
+        wrapped_token_program_id,
+        &id(),
+    )
+}
+
+/// Derives the canonical pointer address for an unwrapped mint scoped to a
+/// wrapped token program for a specific Token Wrap program deployment.
+pub fn get_scoped_canonical_pointer_address_for_program(
+    unwrapped_mint: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    get_scoped_canonical_pointer_address_with_seed_for_program(
+        unwrapped_mint,
+        wrapped_token_program_id,
+        program_id,
+    )
+    .0
+}
+
+const PENDING_CANONICAL_POINTER_SEED: &[u8] = br"pending_canonical_pointer";
+
+/// Derives the pending canonical pointer address and bump seed for a specific
+/// Token Wrap program deployment.
+pub(crate) fn get_pending_canonical_pointer_address_with_seed_for_program(
+    unwrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PENDING_CANONICAL_POINTER_SEED, unwrapped_mint.as_ref()],
+        program_id,
+    )
+}
+
+pub(crate) fn get_pending_canonical_pointer_address_signer_seeds<'a>(
+    unwrapped_mint: &'a Pubkey,
+    bump_seed: &'a [u8],
+) -> [&'a [u8]; 3] {
+    [
+        PENDING_CANONICAL_POINTER_SEED,
+        unwrapped_mint.as_ref(),
+        bump_seed,
+    ]
+}
+
+/// Derives the pending canonical pointer address and bump seed.
+pub(crate) fn get_pending_canonical_pointer_address_with_seed(
+    unwrapped_mint: &Pubkey,
+) -> (Pubkey, u8) {
+    get_pending_canonical_pointer_address_with_seed_for_program(unwrapped_mint, &id())
+}
+
+/// Derives the address of the proposed, not yet accepted, canonical pointer
+/// change for an unwrapped mint.
+pub fn get_pending_canonical_pointer_address(unwrapped_mint: &Pubkey) -> Pubkey {
+    get_pending_canonical_pointer_address_for_program(unwrapped_mint, &id())
+}
+
+/// Derives the pending canonical pointer address for an unwrapped mint for a
+/// specific Token Wrap program deployment.
+pub fn get_pending_canonical_pointer_address_for_program(
+    unwrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    get_pending_canonical_pointer_address_with_seed_for_program(unwrapped_mint, program_id).0
+}
+
+/// Derives the scoped pending canonical pointer address and bump seed for a
+/// specific Token Wrap program deployment.
+pub(crate) fn get_scoped_pending_canonical_pointer_address_with_seed_for_program(
+    unwrapped_mint: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PENDING_CANONICAL_POINTER_SEED,
+            unwrapped_mint.as_ref(),
+            wrapped_token_program_id.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+pub(crate) fn get_scoped_pending_canonical_pointer_address_signer_seeds<'a>(
+    unwrapped_mint: &'a Pubkey,
+    wrapped_token_program_id: &'a Pubkey,
+    bump_seed: &'a [u8],
+) -> [&'a [u8]; 4] {
+    [
+        PENDING_CANONICAL_POINTER_SEED,
+        unwrapped_mint.as_ref(),
+        wrapped_token_program_id.as_ref(),
+        bump_seed,
+    ]
+}
+
+/// Derives the scoped pending canonical pointer address and bump seed.
+pub(crate) fn get_scoped_pending_canonical_pointer_address_with_seed(
+    unwrapped_mint: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    get_scoped_pending_canonical_pointer_address_with_seed_for_program(
+        unwrapped_mint,
+        wrapped_token_program_id,
+        &id(),
+    )
+}
+
+/// Derives the address of the proposed change to the canonical pointer scoped
+/// to a wrapped token program.
+pub fn get_scoped_pending_canonical_pointer_address(
+    unwrapped_mint: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+) -> Pubkey {
+    get_scoped_pending_canonical_pointer_address_for_program(
+        unwrapped_mint,
+        wrapped_token_program_id,
+        &id(),
+    )
+}
+
+/// Derives the scoped pending canonical pointer address for a specific Token
+/// Wrap program deployment.
+pub fn get_scoped_pending_canonical_pointer_address_for_program(
+    unwrapped_mint: &Pubkey,
+    wrapped_token_program_id: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    get_scoped_pending_canonical_pointer_address_with_seed_for_program(
+        unwrapped_mint,
+        wrapped_token_program_id,
+        program_id,
+    )
+    .0
+}
+
+const WRAPPED_MINT_REGISTRY_SEED: &[u8] = br"registry";
+
+/// Derives the wrapped mint registry address and bump seed for a specific
+/// Token Wrap program deployment.
+pub(crate) fn get_wrapped_mint_registry_address_with_seed_for_program(
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WRAPPED_MINT_REGISTRY_SEED], program_id)
+}
+
+pub(crate) fn get_wrapped_mint_registry_address_signer_seeds(bump_seed: &[u8]) -> [&[u8]; 2] {
+    [WRAPPED_MINT_REGISTRY_SEED, bump_seed]
+}
+
+/// Derives the wrapped mint registry address and bump seed.
+pub(crate) fn get_wrapped_mint_registry_address_with_seed() -> (Pubkey, u8) {
+    get_wrapped_mint_registry_address_with_seed_for_program(&id())
+}
+
+/// Derives the address of the registry counting every wrapped mint created by
+/// the program.
+pub fn get_wrapped_mint_registry_address() -> Pubkey {
+    get_wrapped_mint_registry_address_for_program(&id())
+}
+
+/// Derives the wrapped mint registry address for a specific Token Wrap
+/// program deployment.
+pub fn get_wrapped_mint_registry_address_for_program(program_id: &Pubkey) -> Pubkey {
+    get_wrapped_mint_registry_address_with_seed_for_program(program_id).0
+}
+
+const WRAPPED_MINT_REGISTRY_PAGE_SEED: &[u8] = br"registry_page";
+
+/// Derives the registry page address and bump seed for a specific Token Wrap
+/// program deployment.
+pub(crate) fn get_wrapped_mint_registry_page_address_with_seed_for_program(
+    page_index: u64,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[WRAPPED_MINT_REGISTRY_PAGE_SEED, &page_index.to_le_bytes()],
+        program_id,
+    )
+}
+
+pub(crate) fn get_wrapped_mint_registry_page_address_signer_seeds<'a>(
+    page_index_bytes: &'a [u8; 8],
+    bump_seed: &'a [u8],
+) -> [&'a [u8]; 3] {
+    [WRAPPED_MINT_REGISTRY_PAGE_SEED, page_index_bytes, bump_seed]
+}
+
+/// Derives the registry page address and bump seed.
+pub(crate) fn get_wrapped_mint_registry_page_address_with_seed(page_index: u64) -> (Pubkey, u8) {
+    get_wrapped_mint_registry_page_address_with_seed_for_program(page_index, &id())
+}
+
+/// Derives the address of a page of the wrapped mint registry.
+pub fn get_wrapped_mint_registry_page_address(page_index: u64) -> Pubkey {
+    get_wrapped_mint_registry_page_address_for_program(page_index, &id())
+}
+
+/// Derives the registry page address for a specific Token Wrap program
+/// deployment.
+pub fn get_wrapped_mint_registry_page_address_for_program(
+    page_index: u64,
+    program_id: &Pubkey,
+) -> Pubkey {
+    get_wrapped_mint_registry_page_address_with_seed_for_program(page_index, program_id).0
+}
+
+const WRAPPED_MINT_INDEX_SEED: &[u8] = br"wrapped_mint_index";
+
+/// Derives the wrapped mint index address and bump seed for a specific Token
+/// Wrap program deployment.
+pub(crate) fn get_wrapped_mint_index_address_with_seed_for_program(
+    unwrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[WRAPPED_MINT_INDEX_SEED, unwrapped_mint.as_ref()],
+        program_id,
+    )
+}
+
+pub(crate) fn get_wrapped_mint_index_address_signer_seeds<'a>(
+    unwrapped_mint: &'a Pubkey,
+    bump_seed: &'a [u8],
+) -> [&'a [u8]; 3] {
+    [WRAPPED_MINT_INDEX_SEED, unwrapped_mint.as_ref(), bump_seed]
+}
+
+/// Derives the wrapped mint index address and bump seed.
+pub(crate) fn get_wrapped_mint_index_address_with_seed(unwrapped_mint: &Pubkey) -> (Pubkey, u8) {
+    get_wrapped_mint_index_address_with_seed_for_program(unwrapped_mint, &id())
+}
+
+/// Derives the address of the index listing every wrapped mint of an
+/// unwrapped mint.
+pub fn get_wrapped_mint_index_address(unwrapped_mint: &Pubkey) -> Pubkey {
+    get_wrapped_mint_index_address_for_program(unwrapped_mint, &id())
+}
+
+/// Derives the wrapped mint index address for a specific Token Wrap program
+/// deployment.
+pub fn get_wrapped_mint_index_address_for_program(
+    unwrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    get_wrapped_mint_index_address_with_seed_for_program(unwrapped_mint, program_id).0
+}
+
+const WRAP_STATS_SEED: &[u8] = br"stats";
+
+/// Derives the wrap statistics address and bump seed for a specific Token
+/// Wrap program deployment.
+pub(crate) fn get_wrap_stats_address_with_seed_for_program(
+    wrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WRAP_STATS_SEED, wrapped_mint.as_ref()], program_id)
+}
+
+pub(crate) fn get_wrap_stats_address_signer_seeds<'a>(
+    wrapped_mint: &'a Pubkey,
+    bump_seed: &'a [u8],
+) -> [&'a [u8]; 3] {
+    [WRAP_STATS_SEED, wrapped_mint.as_ref(), bump_seed]
+}
+
+/// Derives the wrap statistics address and bump seed.
+pub(crate) fn get_wrap_stats_address_with_seed(wrapped_mint: &Pubkey) -> (Pubkey, u8) {
+    get_wrap_stats_address_with_seed_for_program(wrapped_mint, &id())
+}
+
+/// Derives the address of the statistics account of a wrapped mint.
+pub fn get_wrap_stats_address(wrapped_mint: &Pubkey) -> Pubkey {
+    get_wrap_stats_address_for_program(wrapped_mint, &id())
+}
+
+/// Derives the wrap statistics address for a specific Token Wrap program
+/// deployment.
+pub fn get_wrap_stats_address_for_program(wrapped_mint: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    get_wrap_stats_address_with_seed_for_program(wrapped_mint, program_id).0
+}
+
+const PRO_RATA_REDEMPTION_SEED: &[u8] = br"pro_rata";
+
+/// Derives the pro-rata redemption address and bump seed for a specific Token
+/// Wrap program deployment.
+pub(crate) fn get_pro_rata_redemption_address_with_seed_for_program(
+    wrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PRO_RATA_REDEMPTION_SEED, wrapped_mint.as_ref()],
+        program_id,
+    )
+}
+
+pub(crate) fn get_pro_rata_redemption_address_signer_seeds<'a>(
+    wrapped_mint: &'a Pubkey,
+    bump_seed: &'a [u8],
+) -> [&'a [u8]; 3] {
+    [PRO_RATA_REDEMPTION_SEED, wrapped_mint.as_ref(), bump_seed]
+}
+
+/// Derives the pro-rata redemption address and bump seed.
+pub(crate) fn get_pro_rata_redemption_address_with_seed(wrapped_mint: &Pubkey) -> (Pubkey, u8) {
+    get_pro_rata_redemption_address_with_seed_for_program(wrapped_mint, &id())
+}
+
+/// Derives the address of the account recording the pro-rata redemptions of a
+/// wrapped mint.
+pub fn get_pro_rata_redemption_address(wrapped_mint: &Pubkey) -> Pubkey {
+    get_pro_rata_redemption_address_for_program(wrapped_mint, &id())
+}
+
+/// Derives the pro-rata redemption address for a specific Token Wrap program
+/// deployment.
+pub fn get_pro_rata_redemption_address_for_program(
+    wrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    get_pro_rata_redemption_address_with_seed_for_program(wrapped_mint, program_id).0
+}
+
+const ESCROW_SURPLUS_POLICY_SEED: &[u8] = br"surplus_policy";
+
+/// Derives the escrow surplus policy address and bump seed for a specific
+/// Token Wrap program deployment.
+pub(crate) fn get_escrow_surplus_policy_address_with_seed_for_program(
+    wrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ESCROW_SURPLUS_POLICY_SEED, wrapped_mint.as_ref()],
+        program_id,
+    )
+}
+
+pub(crate) fn get_escrow_surplus_policy_address_signer_seeds<'a>(
+    wrapped_mint: &'a Pubkey,
+    bump_seed: &'a [u8],
+) -> [&'a [u8]; 3] {
+    [ESCROW_SURPLUS_POLICY_SEED, wrapped_mint.as_ref(), bump_seed]
+}
+
+/// Derives the escrow surplus policy address and bump seed.
+pub(crate) fn get_escrow_surplus_policy_address_with_seed(wrapped_mint: &Pubkey) -> (Pubkey, u8) {
+    get_escrow_surplus_policy_address_with_seed_for_program(wrapped_mint, &id())
+}
+
+/// Derives the address of the policy deciding what happens to surplus
+/// unwrapped tokens in the escrow of a wrapped mint.
+pub fn get_escrow_surplus_policy_address(wrapped_mint: &Pubkey) -> Pubkey {
+    get_escrow_surplus_policy_address_for_program(wrapped_mint, &id())
+}
+
+/// Derives the escrow surplus policy address for a specific Token Wrap program
+/// deployment.
+pub fn get_escrow_surplus_policy_address_for_program(
+    wrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    get_escrow_surplus_policy_address_with_seed_for_program(wrapped_mint, program_id).0
+}
+
+const NATIVE_UNWRAP_SEED: &[u8] = br"native_unwrap";
+
+/// Derives the native unwrap account address and bump seed for a specific
+/// Token Wrap program deployment.
+pub(crate) fn get_native_unwrap_account_address_with_seed_for_program(
+    wrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NATIVE_UNWRAP_SEED, wrapped_mint.as_ref()], program_id)
+}
+
+pub(crate) fn get_native_unwrap_account_address_signer_seeds<'a>(
+    wrapped_mint: &'a Pubkey,
+    bump_seed: &'a [u8],
+) -> [&'a [u8]; 3] {
+    [NATIVE_UNWRAP_SEED, wrapped_mint.as_ref(), bump_seed]
+}
+
+/// Derives the native unwrap account address and bump seed.
+pub(crate) fn get_native_unwrap_account_address_with_seed(wrapped_mint: &Pubkey) -> (Pubkey, u8) {
+    get_native_unwrap_account_address_with_seed_for_program(wrapped_mint, &id())
+}
+
+/// Derives the address of the temporary native token account that pays out
+/// the lamports of `UnwrapSol` for a wrapped mint. The account only exists
+/// during the instruction.
+pub fn get_native_unwrap_account_address(wrapped_mint: &Pubkey) -> Pubkey {
+    get_native_unwrap_account_address_for_program(wrapped_mint, &id())
+}
+
+/// Derives the native unwrap account address for a specific Token Wrap
+/// program deployment.
+pub fn get_native_unwrap_account_address_for_program(
+    wrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    get_native_unwrap_account_address_with_seed_for_program(wrapped_mint, program_id).0
+}
+
+const WRAPPED_MINT_ADMIN_SEED: &[u8] = br"admin";
+
+/// Derives the wrapped mint admin address and bump seed for a specific Token
+/// Wrap program deployment.
+pub(crate) fn get_wrapped_mint_admin_address_with_seed_for_program(
+    wrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[WRAPPED_MINT_ADMIN_SEED, wrapped_mint.as_ref()],
+        program_id,
+    )
+}
+
+pub(crate) fn get_wrapped_mint_admin_address_signer_seeds<'a>(
+    wrapped_mint: &'a Pubkey,
+    bump_seed: &'a [u8],
+) -> [&'a [u8]; 3] {
+    [WRAPPED_MINT_ADMIN_SEED, wrapped_mint.as_ref(), bump_seed]
+}
+
+/// Derives the wrapped mint admin address and bump seed.
+pub(crate) fn get_wrapped_mint_admin_address_with_seed(wrapped_mint: &Pubkey) -> (Pubkey, u8) {
+    get_wrapped_mint_admin_address_with_seed_for_program(wrapped_mint, &id())
+}
+
+/// Derives the address of the account holding the admin appointed by the
+/// unwrapped mint authority to run administrative actions on a wrapped mint.
+pub fn get_wrapped_mint_admin_address(wrapped_mint: &Pubkey) -> Pubkey {
+    get_wrapped_mint_admin_address_for_program(wrapped_mint, &id())
+}
+
+/// Derives the wrapped mint admin address for a specific Token Wrap program
+/// deployment.
+pub fn get_wrapped_mint_admin_address_for_program(
+    wrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    get_wrapped_mint_admin_address_with_seed_for_program(wrapped_mint, program_id).0
+}
+
+const WRAP_LIMITS_SEED: &[u8] = br"wrap_limits";
+
+/// Derives the wrap limits address and bump seed for a specific Token Wrap
+/// program deployment.
+pub(crate) fn get_wrap_limits_address_with_seed_for_program(
+    unwrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WRAP_LIMITS_SEED, unwrapped_mint.as_ref()], program_id)
+}
+
+pub(crate) fn get_wrap_limits_address_signer_seeds<'a>(
+    unwrapped_mint: &'a Pubkey,
+    bump_seed: &'a [u8],
+) -> [&'a [u8]; 3] {
+    [WRAP_LIMITS_SEED, unwrapped_mint.as_ref(), bump_seed]
+}
+
+/// Derives the wrap limits address and bump seed.
+pub(crate) fn get_wrap_limits_address_with_seed(unwrapped_mint: &Pubkey) -> (Pubkey, u8) {
+    get_wrap_limits_address_with_seed_for_program(unwrapped_mint, &id())
+}
+
+/// Derives the address of the account holding the limits set by the unwrapped
+/// mint authority on wrapping an unwrapped mint.
+pub fn get_wrap_limits_address(unwrapped_mint: &Pubkey) -> Pubkey {
+    get_wrap_limits_address_for_program(unwrapped_mint, &id())
+}
+
+/// Derives the wrap limits address for a specific Token Wrap program
+/// deployment.
+pub fn get_wrap_limits_address_for_program(unwrapped_mint: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    get_wrap_limits_address_with_seed_for_program(unwrapped_mint, program_id).0
+}
+
+const ALLOWLIST_SEED: &[u8] = br"allowlist";
+
+/// Derives the allowlist address and bump seed for a specific Token Wrap
+/// program deployment.
+pub(crate) fn get_allowlist_address_with_seed_for_program(
+    unwrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ALLOWLIST_SEED, unwrapped_mint.as_ref()], program_id)
+}
+
+pub(crate) fn get_allowlist_address_signer_seeds<'a>(
+    unwrapped_mint: &'a Pubkey,
+    bump_seed: &'a [u8],
+) -> [&'a [u8]; 3] {
+    [ALLOWLIST_SEED, unwrapped_mint.as_ref(), bump_seed]
+}
+
+/// Derives the allowlist address and bump seed.
+pub(crate) fn get_allowlist_address_with_seed(unwrapped_mint: &Pubkey) -> (Pubkey, u8) {
+    get_allowlist_address_with_seed_for_program(unwrapped_mint, &id())
+}
+
+/// Derives the address of the account holding the allowlist mode of an
+/// unwrapped mint.
+pub fn get_allowlist_address(unwrapped_mint: &Pubkey) -> Pubkey {
+    get_allowlist_address_for_program(unwrapped_mint, &id())
+}
+
+/// Derives the allowlist address for a specific Token Wrap program
+/// deployment.
+pub fn get_allowlist_address_for_program(unwrapped_mint: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    get_allowlist_address_with_seed_for_program(unwrapped_mint, program_id).0
+}
+
+const ALLOWLIST_ENTRY_SEED: &[u8] = br"allowlist_entry";
+
+/// Derives the allowlist entry address and bump seed for a specific Token Wrap
+/// program deployment.
+pub(crate) fn get_allowlist_entry_address_with_seed_for_program(
+    unwrapped_mint: &Pubkey,
+    wallet: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ALLOWLIST_ENTRY_SEED,
+            unwrapped_mint.as_ref(),
+            wallet.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+pub(crate) fn get_allowlist_entry_address_signer_seeds<'a>(
+    unwrapped_mint: &'a Pubkey,
+    wallet: &'a Pubkey,
+    bump_seed: &'a [u8],
+) -> [&'a [u8]; 4] {
+    [
+        ALLOWLIST_ENTRY_SEED,
+        unwrapped_mint.as_ref(),
+        wallet.as_ref(),
+        bump_seed,
+    ]
+}
+
+/// Derives the allowlist entry address and bump seed.
+pub(crate) fn get_allowlist_entry_address_with_seed(
+    unwrapped_mint: &Pubkey,
+    wallet: &Pubkey,
+) -> (Pubkey, u8) {
+    get_allowlist_entry_address_with_seed_for_program(unwrapped_mint, wallet, &id())
+}
+
+/// Derives the address of the account recording whether a wallet is on the
+/// allowlist of an unwrapped mint.
+pub fn get_allowlist_entry_address(unwrapped_mint: &Pubkey, wallet: &Pubkey) -> Pubkey {
+    get_allowlist_entry_address_for_program(unwrapped_mint, wallet, &id())
+}
+
+/// Derives the allowlist entry address for a specific Token Wrap program
+/// deployment.
+pub fn get_allowlist_entry_address_for_program(
+    unwrapped_mint: &Pubkey,
+    wallet: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    get_allowlist_entry_address_with_seed_for_program(unwrapped_mint, wallet, program_id).0
+}
+
+const PROTOCOL_FEE_CONFIG_SEED: &[u8] = br"protocol_fee_config";
+
+/// Derives the protocol fee config address and bump seed for a specific Token
+/// Wrap program deployment.
+pub(crate) fn get_protocol_fee_config_address_with_seed_for_program(
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROTOCOL_FEE_CONFIG_SEED], program_id)
+}
+
+pub(crate) fn get_protocol_fee_config_address_signer_seeds(bump_seed: &[u8]) -> [&[u8]; 2] {
+    [PROTOCOL_FEE_CONFIG_SEED, bump_seed]
+}
+
+/// Derives the protocol fee config address and bump seed.
+pub(crate) fn get_protocol_fee_config_address_with_seed() -> (Pubkey, u8) {
+    get_protocol_fee_config_address_with_seed_for_program(&id())
+}
+
+/// Derives the address of the account configuring the protocol fee charged
+/// by the deployment on wraps and unwraps.
+pub fn get_protocol_fee_config_address() -> Pubkey {
+    get_protocol_fee_config_address_for_program(&id())
+}
+
+/// Derives the protocol fee config address for a specific Token Wrap program
+/// deployment.
+pub fn get_protocol_fee_config_address_for_program(program_id: &Pubkey) -> Pubkey {
+    get_protocol_fee_config_address_with_seed_for_program(program_id).0
+}
+
+const PROTOCOL_FEE_OVERRIDE_SEED: &[u8] = br"protocol_fee_override";
+
+/// Derives the protocol fee override address and bump seed for a specific
+/// Token Wrap program deployment.
+pub(crate) fn get_protocol_fee_override_address_with_seed_for_program(
+    unwrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PROTOCOL_FEE_OVERRIDE_SEED, unwrapped_mint.as_ref()],
+        program_id,
+    )
+}
+
+pub(crate) fn get_protocol_fee_override_address_signer_seeds<'a>(
+    unwrapped_mint: &'a Pubkey,
+    bump_seed: &'a [u8],
+) -> [&'a [u8]; 3] {
+    [
+        PROTOCOL_FEE_OVERRIDE_SEED,
+        unwrapped_mint.as_ref(),
+        bump_seed,
+    ]
+}
+
+/// Derives the protocol fee override address and bump seed.
+pub(crate) fn get_protocol_fee_override_address_with_seed(unwrapped_mint: &Pubkey) -> (Pubkey, u8) {
+    get_protocol_fee_override_address_with_seed_for_program(unwrapped_mint, &id())
+}
+
+/// Derives the address of the account overriding the protocol fee for an
+/// unwrapped mint.
+pub fn get_protocol_fee_override_address(unwrapped_mint: &Pubkey) -> Pubkey {
+    get_protocol_fee_override_address_for_program(unwrapped_mint, &id())
+}
+
+/// Derives the protocol fee override address for a specific Token Wrap
+/// program deployment.
+pub fn get_protocol_fee_override_address_for_program(
+    unwrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    get_protocol_fee_override_address_with_seed_for_program(unwrapped_mint, program_id).0
+}
+
+const FLASH_MINT_GUARD_SEED: &[u8] = br"flash_mint_guard";
+
+/// Derives the flash mint guard address and bump seed for a specific Token
+/// Wrap program deployment.
+pub(crate) fn get_flash_mint_guard_address_with_seed_for_program(
+    wrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FLASH_MINT_GUARD_SEED, wrapped_mint.as_ref()], program_id)
+}
+
+pub(crate) fn get_flash_mint_guard_address_signer_seeds<'a>(
+    wrapped_mint: &'a Pubkey,
+    bump_seed: &'a [u8],
+) -> [&'a [u8]; 3] {
+    [FLASH_MINT_GUARD_SEED, wrapped_mint.as_ref(), bump_seed]
+}
+
+/// Derives the flash mint guard address and bump seed.
+pub(crate) fn get_flash_mint_guard_address_with_seed(wrapped_mint: &Pubkey) -> (Pubkey, u8) {
+    get_flash_mint_guard_address_with_seed_for_program(wrapped_mint, &id())
+}
+
+/// Derives the address of the account guarding flash mints of a wrapped mint
+/// and holding their fee.
+pub fn get_flash_mint_guard_address(wrapped_mint: &Pubkey) -> Pubkey {
+    get_flash_mint_guard_address_for_program(wrapped_mint, &id())
+}
+
+/// Derives the flash mint guard address for a specific Token Wrap program
+/// deployment.
+pub fn get_flash_mint_guard_address_for_program(
+    wrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    get_flash_mint_guard_address_with_seed_for_program(wrapped_mint, program_id).0
+}
+
+const PERMIT_DELEGATE_SEED: &[u8] = br"permit_delegate";
+
+/// Derives the permit delegate address and bump seed for a specific Token
+/// Wrap program deployment.
+pub(crate) fn get_permit_delegate_address_with_seed_for_program(
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PERMIT_DELEGATE_SEED], program_id)
+}
+
+pub(crate) fn get_permit_delegate_address_signer_seeds(bump_seed: &[u8]) -> [&[u8]; 2] {
+    [PERMIT_DELEGATE_SEED, bump_seed]
+}
+
+/// Derives the permit delegate address and bump seed.
+pub(crate) fn get_permit_delegate_address_with_seed() -> (Pubkey, u8) {
+    get_permit_delegate_address_with_seed_for_program(&id())
+}
+
+/// Derives the address of the delegate that token owners approve to have
+/// their permits executed.
+pub fn get_permit_delegate_address() -> Pubkey {
+    get_permit_delegate_address_for_program(&id())
+}
+
+/// Derives the permit delegate address for a specific Token Wrap program
+/// deployment.
+pub fn get_permit_delegate_address_for_program(program_id: &Pubkey) -> Pubkey {
+    get_permit_delegate_address_with_seed_for_program(program_id).0
+}
+
+const PERMIT_NONCE_SEED: &[u8] = br"permit_nonce";
+
+/// Derives the permit nonce address and bump seed for a specific Token Wrap
+/// program deployment.
+pub(crate) fn get_permit_nonce_address_with_seed_for_program(
+    owner: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PERMIT_NONCE_SEED, owner.as_ref()], program_id)
+}
+
+pub(crate) fn get_permit_nonce_address_signer_seeds<'a>(
+    owner: &'a Pubkey,
+    bump_seed: &'a [u8],
+) -> [&'a [u8]; 3] {
+    [PERMIT_NONCE_SEED, owner.as_ref(), bump_seed]
+}
+
+/// Derives the permit nonce address and bump seed.
+pub(crate) fn get_permit_nonce_address_with_seed(owner: &Pubkey) -> (Pubkey, u8) {
+    get_permit_nonce_address_with_seed_for_program(owner, &id())
+}
+
+/// Derives the address of the account holding the next permit nonce of an
+/// owner.
+pub fn get_permit_nonce_address(owner: &Pubkey) -> Pubkey {
+    get_permit_nonce_address_for_program(owner, &id())
+}
+
+/// Derives the permit nonce address for a specific Token Wrap program
+/// deployment.
+pub fn get_permit_nonce_address_for_program(owner: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    get_permit_nonce_address_with_seed_for_program(owner, program_id).0
+}
+
+const CRANK_WRAP_DELEGATE_SEED: &[u8] = br"crank_wrap_delegate";
+
+/// Derives the crank wrap delegate address and bump seed for a specific Token
+/// Wrap program deployment.
+pub(crate) fn get_crank_wrap_delegate_address_with_seed_for_program(
+    owner: &Pubkey,
+    unwrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            CRANK_WRAP_DELEGATE_SEED,
+            owner.as_ref(),
+            unwrapped_mint.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+pub(crate) fn get_crank_wrap_delegate_address_signer_seeds<'a>(
+    owner: &'a Pubkey,
+    unwrapped_mint: &'a Pubkey,
+    bump_seed: &'a [u8],
+) -> [&'a [u8]; 4] {
+    [
+        CRANK_WRAP_DELEGATE_SEED,
+        owner.as_ref(),
+        unwrapped_mint.as_ref(),
+        bump_seed,
+    ]
+}
+
+/// Derives the crank wrap delegate address and bump seed.
+pub(crate) fn get_crank_wrap_delegate_address_with_seed(
+    owner: &Pubkey,
+    unwrapped_mint: &Pubkey,
+) -> (Pubkey, u8) {
+    get_crank_wrap_delegate_address_with_seed_for_program(owner, unwrapped_mint, &id())
+}
+
+/// Derives the address of the delegate an owner approves on their token
+/// account of an unwrapped mint to have its tokens wrapped by `CrankWrap`.
+pub fn get_crank_wrap_delegate_address(owner: &Pubkey, unwrapped_mint: &Pubkey) -> Pubkey {
+    get_crank_wrap_delegate_address_for_program(owner, unwrapped_mint, &id())
+}
+
+/// Derives the crank wrap delegate address for a specific Token Wrap program
+/// deployment.
+pub fn get_crank_wrap_delegate_address_for_program(
+    owner: &Pubkey,
+    unwrapped_mint: &Pubkey,
+    program_id: &Pubkey,
+) -> Pubkey {
+    get_crank_wrap_delegate_address_with_seed_for_program(owner, unwrapped_mint, program_id).0
+}
@@ -0,0 +1,117 @@
+use {
+    crate::{
+        get_wrapped_mint_address, get_wrapped_mint_authority,
+        mint_customizer::interface::{customizer_id, MintCustomizer, CUSTOMIZER_ID_LEN},
+    },
+    solana_account_info::AccountInfo,
+    solana_cpi::invoke,
+    solana_program_error::{ProgramError, ProgramResult},
+    solana_pubkey::Pubkey,
+    spl_token_2022_interface::{
+        extension::{
+            metadata_pointer::instruction::initialize as initialize_metadata_pointer,
+            pausable::instruction::initialize as initialize_pausable,
+            transfer_fee::instruction::initialize_transfer_fee_config, ExtensionType,
+            PodStateWithExtensions,
+        },
+        instruction::initialize_mint_close_authority,
+        pod::PodMint,
+        state::Mint,
+    },
+};
+
+/// A mint `customizer` for issuers that administer their wrapped mint through
+/// `ExecuteAdminAction`. The wrapped mint authority PDA holds every authority
+/// that instruction uses:
+/// - The freeze authority, for `FreezeAccount` and `ThawAccount`
+/// - The `Pausable` authority, for `Pause` and `Resume`
+/// - The `TransferFeeConfig` authorities, for `SetTransferFee`, starting at
+///   no fee
+/// - The `MetadataPointer` authority and the close authority, as in
+///   [`DefaultToken2022Customizer`](super::default_token_2022::DefaultToken2022Customizer)
+///
+/// Confidential transfers are left out, since Token-2022 only combines them
+/// with transfer fees through a confidential fee withdraw key.
+pub struct AdministeredMintCustomizer;
+
+impl MintCustomizer for AdministeredMintCustomizer {
+    const ID: [u8; CUSTOMIZER_ID_LEN] = customizer_id("administered");
+
+    fn get_token_2022_mint_space() -> Result<usize, ProgramError> {
+        // The TokenMetadata extension is initialized *after* the base mint and
+        // handles its own reallocation
+        ExtensionType::try_calculate_account_len::<Mint>(&[
+            ExtensionType::MetadataPointer,
+            ExtensionType::MintCloseAuthority,
+            ExtensionType::Pausable,
+            ExtensionType::TransferFeeConfig,
+        ])
+    }
+
+    fn initialize_extensions(
+        wrapped_mint_account: &AccountInfo,
+        wrapped_token_program_account: &AccountInfo,
+    ) -> ProgramResult {
+        let wrapped_mint_authority = get_wrapped_mint_authority(wrapped_mint_account.key);
+
+        invoke(
+            &initialize_metadata_pointer(
+                wrapped_token_program_account.key,
+                wrapped_mint_account.key,
+                Some(wrapped_mint_authority),
+                Some(*wrapped_mint_account.key),
+            )?,
+            core::slice::from_ref(wrapped_mint_account),
+        )?;
+
+        invoke(
+            &initialize_mint_close_authority(
+                wrapped_token_program_account.key,
+                wrapped_mint_account.key,
+                Some(&wrapped_mint_authority),
+            )?,
+            core::slice::from_ref(wrapped_mint_account),
+        )?;
+
+        invoke(
+            &initialize_pausable(
+                wrapped_token_program_account.key,
+                wrapped_mint_account.key,
+                &wrapped_mint_authority,
+            )?,
+            core::slice::from_ref(wrapped_mint_account),
+        )?;
+
+        // No fee until the admin sets one with `SetTransferFee`
+        invoke(
+            &initialize_transfer_fee_config(
+                wrapped_token_program_account.key,
+                wrapped_mint_account.key,
+                Some(&wrapped_mint_authority),
+                Some(&wrapped_mint_authority),
+                0,
+                0,
+            )?,
+            core::slice::from_ref(wrapped_mint_account),
+        )?;
+
+        Ok(())
+    }
+
+    fn get_freeze_auth_and_decimals(
+        unwrapped_mint_account: &AccountInfo,
+    ) -> Result<(Option<Pubkey>, u8), ProgramError> {
+        // Copy decimals from the original unwrapped mint.
+        let unwrapped_mint_data = unwrapped_mint_account.try_borrow_data()?;
+        let pod_mint = PodStateWithExtensions::<PodMint>::unpack(&unwrapped_mint_data)?.base;
+        let decimals = pod_mint.decimals;
+
+        // Customizers only apply to Token-2022 wrapped mints
+        let wrapped_mint_address =
+            get_wrapped_mint_address(unwrapped_mint_account.key, &spl_token_2022_interface::id());
+        Ok((
+            Some(get_wrapped_mint_authority(&wrapped_mint_address)),
+            decimals,
+        ))
+    }
+}
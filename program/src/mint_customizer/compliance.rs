@@ -1,5 +1,5 @@
 use {
-    crate::mint_customizer::interface::MintCustomizer,
+    crate::mint_customizer::interface::{customizer_id, MintCustomizer, CUSTOMIZER_ID_LEN},
     solana_account_info::AccountInfo,
     solana_cpi::invoke,
     solana_program_error::{ProgramError, ProgramResult},
@@ -48,6 +48,8 @@ pub const PAUSE_AUTHORITY: Pubkey =
     solana_pubkey::pubkey!("pauySfjziLCpPMoaeFsWgvBCe7ygHKr6wXCyvTNZyGv");
 
 impl MintCustomizer for ComplianceMintCustomizer {
+    const ID: [u8; CUSTOMIZER_ID_LEN] = customizer_id("compliance");
+
     fn get_token_2022_mint_space() -> Result<usize, ProgramError> {
         ExtensionType::try_calculate_account_len::<Mint>(&[
             ExtensionType::PermanentDelegate,
@@ -1,5 +1,8 @@
 use {
-    crate::{get_wrapped_mint_authority, mint_customizer::interface::MintCustomizer},
+    crate::{
+        get_wrapped_mint_authority,
+        mint_customizer::interface::{customizer_id, MintCustomizer, CUSTOMIZER_ID_LEN},
+    },
     solana_account_info::AccountInfo,
     solana_cpi::invoke,
     solana_program_error::{ProgramError, ProgramResult},
@@ -11,16 +14,21 @@ use {
             ExtensionType::{self},
             PodStateWithExtensions,
         },
+        instruction::initialize_mint_close_authority,
         pod::PodMint,
         state::Mint,
     },
 };
 
-/// This implementation adds the `ConfidentialTransferMint` & `MetadataPointer`
-/// extensions by default.
+/// This implementation adds the `ConfidentialTransferMint`, `MetadataPointer` &
+/// `MintCloseAuthority` extensions by default. The close authority is the
+/// wrapped mint authority, so a retired wrapped mint can be closed with
+/// `CloseWrappedMint`.
 pub struct DefaultToken2022Customizer;
 
 impl MintCustomizer for DefaultToken2022Customizer {
+    const ID: [u8; CUSTOMIZER_ID_LEN] = customizer_id("default");
+
     fn get_token_2022_mint_space() -> Result<usize, ProgramError> {
         // Calculate space for all extensions that are initialized *before* the base
         // mint. The TokenMetadata extension is initialized *after* and its
@@ -28,6 +36,7 @@ impl MintCustomizer for DefaultToken2022Customizer {
         ExtensionType::try_calculate_account_len::<Mint>(&[
             ExtensionType::ConfidentialTransferMint,
             ExtensionType::MetadataPointer,
+            ExtensionType::MintCloseAuthority,
         ])
     }
 
@@ -35,31 +44,8 @@ impl MintCustomizer for DefaultToken2022Customizer {
         wrapped_mint_account: &AccountInfo,
         wrapped_token_program_account: &AccountInfo,
     ) -> ProgramResult {
-        // Initialize confidential transfer ext
-        invoke(
-            &initialize_confidential_transfer_mint(
-                wrapped_token_program_account.key,
-                wrapped_mint_account.key,
-                None, // Immutable. No one can later change privacy settings.
-                true, // No approvals necessary to use.
-                None, // No auditor can decrypt transaction amounts.
-            )?,
-            core::slice::from_ref(wrapped_mint_account),
-        )?;
-
-        // Initialize metadata pointer
-        let wrapped_mint_authority = get_wrapped_mint_authority(wrapped_mint_account.key);
-        invoke(
-            &initialize_metadata_pointer(
-                wrapped_token_program_account.key,
-                wrapped_mint_account.key,
-                Some(wrapped_mint_authority),
-                Some(*wrapped_mint_account.key),
-            )?,
-            core::slice::from_ref(wrapped_mint_account),
-        )?;
-
-        Ok(())
+        // Immutable. No one can later change privacy settings.
+        initialize_default_extensions(wrapped_mint_account, wrapped_token_program_account, None)
     }
 
     fn get_freeze_auth_and_decimals(
@@ -73,3 +59,47 @@ impl MintCustomizer for DefaultToken2022Customizer {
         Ok((freeze_authority, decimals))
     }
 }
+
+/// Initializes the extensions of the default customizer, with the given
+/// authority over the confidential transfer settings
+pub(crate) fn initialize_default_extensions(
+    wrapped_mint_account: &AccountInfo,
+    wrapped_token_program_account: &AccountInfo,
+    confidential_transfer_authority: Option<Pubkey>,
+) -> ProgramResult {
+    // Initialize confidential transfer ext
+    invoke(
+        &initialize_confidential_transfer_mint(
+            wrapped_token_program_account.key,
+            wrapped_mint_account.key,
+            confidential_transfer_authority,
+            true, // No approvals necessary to use.
+            None, // No auditor can decrypt transaction amounts.
+        )?,
+        core::slice::from_ref(wrapped_mint_account),
+    )?;
+
+    // Initialize metadata pointer
+    let wrapped_mint_authority = get_wrapped_mint_authority(wrapped_mint_account.key);
+    invoke(
+        &initialize_metadata_pointer(
+            wrapped_token_program_account.key,
+            wrapped_mint_account.key,
+            Some(wrapped_mint_authority),
+            Some(*wrapped_mint_account.key),
+        )?,
+        core::slice::from_ref(wrapped_mint_account),
+    )?;
+
+    // Initialize close authority
+    invoke(
+        &initialize_mint_close_authority(
+            wrapped_token_program_account.key,
+            wrapped_mint_account.key,
+            Some(&wrapped_mint_authority),
+        )?,
+        core::slice::from_ref(wrapped_mint_account),
+    )?;
+
+    Ok(())
+}
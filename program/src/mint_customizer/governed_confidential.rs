@@ -0,0 +1,44 @@
+use {
+    crate::{
+        get_wrapped_mint_authority,
+        mint_customizer::{
+            default_token_2022::{initialize_default_extensions, DefaultToken2022Customizer},
+            interface::{customizer_id, MintCustomizer, CUSTOMIZER_ID_LEN},
+        },
+    },
+    solana_account_info::AccountInfo,
+    solana_program_error::{ProgramError, ProgramResult},
+    solana_pubkey::Pubkey,
+};
+
+/// Adds the same extensions as [`DefaultToken2022Customizer`], but the
+/// authority over the confidential transfer settings is the wrapped mint
+/// authority PDA instead of none. The unwrapped mint authority can then
+/// rotate the auditor `ElGamal` key or toggle auto-approval through
+/// `UpdateConfidentialConfig`.
+pub struct GovernedConfidentialMintCustomizer;
+
+impl MintCustomizer for GovernedConfidentialMintCustomizer {
+    const ID: [u8; CUSTOMIZER_ID_LEN] = customizer_id("governed-ct");
+
+    fn get_token_2022_mint_space() -> Result<usize, ProgramError> {
+        DefaultToken2022Customizer::get_token_2022_mint_space()
+    }
+
+    fn initialize_extensions(
+        wrapped_mint_account: &AccountInfo,
+        wrapped_token_program_account: &AccountInfo,
+    ) -> ProgramResult {
+        initialize_default_extensions(
+            wrapped_mint_account,
+            wrapped_token_program_account,
+            Some(get_wrapped_mint_authority(wrapped_mint_account.key)),
+        )
+    }
+
+    fn get_freeze_auth_and_decimals(
+        unwrapped_mint_account: &AccountInfo,
+    ) -> Result<(Option<Pubkey>, u8), ProgramError> {
+        DefaultToken2022Customizer::get_freeze_auth_and_decimals(unwrapped_mint_account)
+    }
+}
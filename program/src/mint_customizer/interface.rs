@@ -4,8 +4,28 @@ use {
     solana_pubkey::Pubkey,
 };
 
+/// Size of a [`MintCustomizer::ID`]
+pub const CUSTOMIZER_ID_LEN: usize = 16;
+
+/// Builds a [`MintCustomizer::ID`] from a name, padding it with zeroes
+pub const fn customizer_id(name: &str) -> [u8; CUSTOMIZER_ID_LEN] {
+    let name = name.as_bytes();
+    assert!(
+        name.len() <= CUSTOMIZER_ID_LEN,
+        "customizer name is too long"
+    );
+    let mut id = [0; CUSTOMIZER_ID_LEN];
+    id.split_at_mut(name.len()).0.copy_from_slice(name);
+    id
+}
+
 /// The interface for customizing attributes of the new wrapped mint.
 pub trait MintCustomizer {
+    /// Identifier recorded in the backpointer of every wrapped mint created
+    /// with this customizer, see [`customizer_id`]. Defaults to zeroes, which
+    /// clients show as an unknown customizer.
+    const ID: [u8; CUSTOMIZER_ID_LEN] = [0; CUSTOMIZER_ID_LEN];
+
     /// Calculates the space required for a new spl-token-2022 mint
     /// account, including any custom extensions
     fn get_token_2022_mint_space() -> Result<usize, ProgramError>;
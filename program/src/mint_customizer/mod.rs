@@ -1,9 +1,13 @@
 //! Mint `customizer` interface and implementations
 
+/// Token 2022 mint variant administered by the wrapped mint authority
+pub mod administered;
 /// Compliance-focused mint variant
 pub mod compliance;
 /// Default token 2022 mint variant
 pub mod default_token_2022;
+/// Default token 2022 mint variant with governed confidential transfer settings
+pub mod governed_confidential;
 /// `MintCustomizer` trait definition
 pub mod interface;
 /// No extensions version of the mint
@@ -1,5 +1,5 @@
 use {
-    crate::mint_customizer::interface::MintCustomizer,
+    crate::mint_customizer::interface::{customizer_id, MintCustomizer, CUSTOMIZER_ID_LEN},
     solana_account_info::AccountInfo,
     solana_program_error::ProgramError,
     solana_pubkey::Pubkey,
@@ -14,6 +14,8 @@ use {
 pub struct NoExtensionCustomizer;
 
 impl MintCustomizer for NoExtensionCustomizer {
+    const ID: [u8; CUSTOMIZER_ID_LEN] = customizer_id("no_extensions");
+
     fn get_token_2022_mint_space() -> Result<usize, ProgramError> {
         let extensions = vec![];
         ExtensionType::try_calculate_account_len::<Mint>(&extensions)
@@ -0,0 +1,128 @@
+//! Off-chain permits authorizing `WrapWithPermit` and `UnwrapWithPermit`
+
+use {
+    bytemuck::{Pod, Zeroable},
+    solana_account_info::AccountInfo,
+    solana_ed25519_program::{
+        Ed25519SignatureOffsets, PUBKEY_SERIALIZED_SIZE, SIGNATURE_OFFSETS_SERIALIZED_SIZE,
+        SIGNATURE_OFFSETS_START,
+    },
+    solana_instructions_sysvar::load_instruction_at_checked,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_zero_copy::unaligned::{I64, U64},
+};
+
+/// Prefix of every permit message, so that a permit signature cannot be
+/// mistaken for the signature of any other message
+pub const PERMIT_MESSAGE_PREFIX: &[u8] = b"token-wrap permit\0";
+
+/// Operation authorized by a permit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum PermitAction {
+    /// `WrapWithPermit`
+    Wrap = 0,
+    /// `UnwrapWithPermit`
+    Unwrap = 1,
+}
+
+/// Terms of a permit, signed off-chain by the owner of the spent token
+/// account.
+///
+/// The owner signs [`Permit::message`] with their ed25519 key, and the
+/// transaction carries the signature in an instruction of the ed25519
+/// program.
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct Permit {
+    /// Token Wrap program deployment accepting the permit
+    pub program_id: Pubkey,
+    /// Operation authorized, see [`PermitAction`]
+    pub action: u8,
+    /// Mint of the spent tokens: the unwrapped mint for a wrap, the wrapped
+    /// mint for an unwrap
+    pub mint: Pubkey,
+    /// Amount of tokens spent
+    pub amount: U64,
+    /// Token account of the owner the tokens are spent from
+    pub source: Pubkey,
+    /// Token account receiving the output of the operation
+    pub recipient: Pubkey,
+    /// Nonce of the owner the permit consumes
+    pub nonce: U64,
+    /// Unix timestamp after which the permit is rejected
+    pub expiry: I64,
+}
+
+impl Permit {
+    /// Returns the message the owner signs
+    pub fn message(&self) -> Vec<u8> {
+        [PERMIT_MESSAGE_PREFIX, bytemuck::bytes_of(self)].concat()
+    }
+}
+
+/// Returns true if an ed25519 program instruction of the transaction verifies
+/// a signature of `message` by `signer`.
+///
+/// Only signatures whose public key and message are stored in the ed25519
+/// instruction itself are considered.
+pub fn has_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<bool, ProgramError> {
+    if !solana_instructions_sysvar::check_id(instructions_sysvar.key) {
+        return Err(ProgramError::UnsupportedSysvar);
+    }
+    let mut index = 0;
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions_sysvar) {
+        if instruction.program_id == solana_sdk_ids::ed25519_program::id()
+            && ed25519_instruction_verifies(&instruction.data, signer, message)
+        {
+            return Ok(true);
+        }
+        index = index.saturating_add(1);
+    }
+    Ok(false)
+}
+
+fn ed25519_instruction_verifies(data: &[u8], signer: &Pubkey, message: &[u8]) -> bool {
+    let Some(&num_signatures) = data.first() else {
+        return false;
+    };
+    (0..usize::from(num_signatures)).any(|i| {
+        // Offsets that overflow cannot point at a verified signature
+        let Some(offsets) = i
+            .checked_mul(SIGNATURE_OFFSETS_SERIALIZED_SIZE)
+            .and_then(|offset| offset.checked_add(SIGNATURE_OFFSETS_START))
+            .and_then(|start| data_at(data, start, SIGNATURE_OFFSETS_SERIALIZED_SIZE))
+            .and_then(|bytes| {
+                bytemuck::try_pod_read_unaligned::<Ed25519SignatureOffsets>(bytes).ok()
+            })
+        else {
+            return false;
+        };
+        if offsets.signature_instruction_index != u16::MAX
+            || offsets.public_key_instruction_index != u16::MAX
+            || offsets.message_instruction_index != u16::MAX
+        {
+            return false;
+        }
+        data_at(
+            data,
+            usize::from(offsets.public_key_offset),
+            PUBKEY_SERIALIZED_SIZE,
+        ) == Some(signer.as_ref())
+            && data_at(
+                data,
+                usize::from(offsets.message_data_offset),
+                usize::from(offsets.message_data_size),
+            ) == Some(message)
+    })
+}
+
+/// Returns `len` bytes of `data` starting at `offset`, if they are in bounds
+fn data_at(data: &[u8], offset: usize, len: usize) -> Option<&[u8]> {
+    data.get(offset..offset.checked_add(len)?)
+}
@@ -3,19 +3,56 @@
 use {
     crate::{
         error::TokenWrapError,
+        extension_policy::check_mint_extensions,
+        get_allowlist_address_signer_seeds, get_allowlist_address_with_seed,
+        get_allowlist_entry_address_signer_seeds, get_allowlist_entry_address_with_seed,
         get_canonical_pointer_address_signer_seeds, get_canonical_pointer_address_with_seed,
-        get_wrapped_mint_address, get_wrapped_mint_address_with_seed, get_wrapped_mint_authority,
-        get_wrapped_mint_authority_signer_seeds, get_wrapped_mint_authority_with_seed,
-        get_wrapped_mint_backpointer_address_signer_seeds,
-        get_wrapped_mint_backpointer_address_with_seed, get_wrapped_mint_signer_seeds,
-        instruction::TokenWrapInstruction,
+        get_crank_wrap_delegate_address_signer_seeds, get_crank_wrap_delegate_address_with_seed,
+        get_escrow_surplus_policy_address_signer_seeds,
+        get_escrow_surplus_policy_address_with_seed, get_flash_mint_guard_address_signer_seeds,
+        get_flash_mint_guard_address_with_seed, get_native_unwrap_account_address_signer_seeds,
+        get_native_unwrap_account_address_with_seed,
+        get_pending_canonical_pointer_address_signer_seeds,
+        get_pending_canonical_pointer_address_with_seed, get_permit_delegate_address_signer_seeds,
+        get_permit_delegate_address_with_seed, get_permit_nonce_address_signer_seeds,
+        get_permit_nonce_address_with_seed, get_pro_rata_redemption_address_signer_seeds,
+        get_pro_rata_redemption_address_with_seed, get_protocol_fee_config_address_signer_seeds,
+        get_protocol_fee_config_address_with_seed, get_protocol_fee_override_address_signer_seeds,
+        get_protocol_fee_override_address_with_seed,
+        get_scoped_canonical_pointer_address_signer_seeds,
+        get_scoped_canonical_pointer_address_with_seed,
+        get_scoped_pending_canonical_pointer_address_signer_seeds,
+        get_scoped_pending_canonical_pointer_address_with_seed,
+        get_wrap_limits_address_signer_seeds, get_wrap_limits_address_with_seed,
+        get_wrap_stats_address_signer_seeds, get_wrap_stats_address_with_seed,
+        get_wrapped_mint_address, get_wrapped_mint_address_with_seed,
+        get_wrapped_mint_admin_address_signer_seeds, get_wrapped_mint_admin_address_with_seed,
+        get_wrapped_mint_authority, get_wrapped_mint_authority_signer_seeds,
+        get_wrapped_mint_authority_with_seed, get_wrapped_mint_backpointer_address_signer_seeds,
+        get_wrapped_mint_backpointer_address_with_seed,
+        get_wrapped_mint_index_address_signer_seeds, get_wrapped_mint_index_address_with_seed,
+        get_wrapped_mint_registry_address_signer_seeds,
+        get_wrapped_mint_registry_address_with_seed,
+        get_wrapped_mint_registry_page_address_signer_seeds,
+        get_wrapped_mint_registry_page_address_with_seed, get_wrapped_mint_signer_seeds,
+        instruction::{AdminAction, TokenWrapInstruction},
         metadata::extract_token_metadata,
         metaplex::token_2022_metadata_to_metaplex,
         mint_customizer::{
             default_token_2022::DefaultToken2022Customizer, interface::MintCustomizer,
         },
-        state::{Backpointer, CanonicalDeploymentPointer},
+        permit::{has_ed25519_signature, Permit, PermitAction},
+        state::{
+            Allowlist, AllowlistEntry, Backpointer, CanonicalDeploymentPointer, CrankWrapDelegate,
+            EscrowSurplusPolicy, FlashMintGuard, PendingCanonicalPointer, PermitNonce,
+            ProRataRedemption, ProtocolFeeConfig, ProtocolFeeOverride, ProtocolFeeSplit,
+            SurplusAction, VersionedListEntry, VersionedState, WrapLimits, WrapStats,
+            WrappedMintAdmin, WrappedMintIndexEntry, WrappedMintRegistry, WrappedMintRegistryEntry,
+            MAX_FEE_BASIS_POINTS,
+        },
+        PROGRAM_VERSION,
     },
+    bytemuck::Zeroable,
     mpl_token_metadata::{
         accounts::Metadata as MetaplexMetadata,
         instructions::{
@@ -24,14 +61,18 @@ use {
         },
     },
     solana_account_info::{next_account_info, AccountInfo},
-    solana_cpi::{invoke, invoke_signed},
+    solana_cpi::{invoke, invoke_signed, set_return_data},
+    solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked},
+    solana_loader_v3_interface::{get_program_data_address, state::UpgradeableLoaderState},
     solana_msg::msg,
     solana_program_error::{ProgramError, ProgramResult},
     solana_program_pack::Pack,
     solana_pubkey::Pubkey,
     solana_rent::Rent,
-    solana_system_interface::instruction::{allocate, assign},
+    solana_sdk_ids::bpf_loader_upgradeable,
+    solana_system_interface::instruction::{allocate, assign, transfer},
     solana_sysvar::{clock::Clock, Sysvar},
+    solana_zk_sdk_pod::encryption::{auth_encryption::PodAeCiphertext, elgamal::PodElGamalPubkey},
     spl_associated_token_account_interface::address::get_associated_token_address_with_program_id,
     spl_token_2022::onchain::{
         extract_multisig_accounts, invoke_transfer_checked, invoke_transfer_checked_with_fee,
@@ -39,18 +80,30 @@ use {
     spl_token_2022_interface::{
         extension::{
             account_len::try_for_each_required_init_account_extension,
-            transfer_fee::TransferFeeConfig, BaseStateWithExtensions, ExtensionType,
-            PodStateWithExtensions,
+            confidential_transfer::{
+                instruction::{
+                    deposit, inner_withdraw, update_mint as update_confidential_transfer_mint,
+                },
+                ConfidentialTransferAccount, ConfidentialTransferMint,
+            },
+            mint_close_authority::MintCloseAuthority,
+            pausable::instruction::{pause, resume},
+            transfer_fee::{instruction::set_transfer_fee, TransferFeeConfig},
+            BaseStateWithExtensions, ExtensionType, PodStateWithExtensions,
+        },
+        instruction::{
+            close_account, freeze_account, initialize_account3, initialize_mint2, reallocate,
+            sync_native, thaw_account, MAX_SIGNERS,
         },
-        instruction::initialize_mint2,
         pod::{PodAccount, PodMint},
-        state::AccountState,
+        state::{Account, AccountState, Multisig},
     },
+    spl_token_confidential_transfer_proof_extraction::instruction::ProofLocation,
     spl_token_metadata_interface::{
         instruction::{initialize as initialize_token_metadata, remove_key, update_field},
         state::{Field, TokenMetadata},
     },
-    std::{collections::HashMap, mem},
+    std::collections::HashMap,
 };
 
 /// Processes [`CreateMint`](enum.TokenWrapInstruction.html) instruction.
@@ -58,6 +111,7 @@ pub fn process_create_mint<M: MintCustomizer>(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     idempotent: bool,
+    acknowledge_risky_extensions: bool,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
 
@@ -66,6 +120,9 @@ pub fn process_create_mint<M: MintCustomizer>(
     let unwrapped_mint_account = next_account_info(account_info_iter)?;
     let _system_program_account = next_account_info(account_info_iter)?;
     let wrapped_token_program_account = next_account_info(account_info_iter)?;
+    let registry_account = next_account_info(account_info_iter)?;
+    let registry_page_account = next_account_info(account_info_iter)?;
+    let wrapped_mint_index_account = next_account_info(account_info_iter)?;
 
     let (wrapped_mint_address, mint_bump) = get_wrapped_mint_address_with_seed(
         unwrapped_mint_account.key,
@@ -108,6 +165,15 @@ pub fn process_create_mint<M: MintCustomizer>(
         return Ok(());
     }
 
+    // Extension policy of the unwrapped mint
+
+    if *unwrapped_mint_account.owner == spl_token_2022_interface::id() {
+        check_mint_extensions(
+            &unwrapped_mint_account.try_borrow_data()?,
+            acknowledge_risky_extensions,
+        )?;
+    }
+
     // Initialize wrapped mint PDA
 
     let bump_seed = [mint_bump];
@@ -166,7 +232,7 @@ pub fn process_create_mint<M: MintCustomizer>(
 
     // Initialize backpointer PDA
 
-    let backpointer_space = std::mem::size_of::<Backpointer>();
+    let backpointer_space = Backpointer::LEN;
     let backpointer_rent_required = rent.minimum_balance(backpointer_space);
     if wrapped_backpointer_account.lamports() < backpointer_rent_required {
         msg!(
@@ -192,15 +258,505 @@ pub fn process_create_mint<M: MintCustomizer>(
 
     // Set data within backpointer PDA
 
-    let mut backpointer_account_data = wrapped_backpointer_account.try_borrow_mut_data()?;
-    let backpointer = bytemuck::from_bytes_mut::<Backpointer>(&mut backpointer_account_data[..]);
-    backpointer.unwrapped_mint = *unwrapped_mint_account.key;
+    let (_, wrapped_mint_authority_bump) =
+        get_wrapped_mint_authority_with_seed(wrapped_mint_account.key);
+    Backpointer {
+        unwrapped_mint: *unwrapped_mint_account.key,
+        unwrapped_token_program: *unwrapped_mint_account.owner,
+        wrapped_token_program: *wrapped_token_program_account.key,
+        customizer_id: M::ID,
+        creation_slot: Clock::get()?.slot.into(),
+        wrapped_mint_bump: mint_bump,
+        wrapped_mint_authority_bump,
+        backpointer_bump,
+        program_version: PROGRAM_VERSION,
+    }
+    .pack_versioned(&mut wrapped_backpointer_account.try_borrow_mut_data()?)?;
+
+    // Record the wrapped mint in the registry
+
+    register_wrapped_mint(
+        program_id,
+        registry_account,
+        registry_page_account,
+        wrapped_mint_index_account,
+        WrappedMintRegistryEntry {
+            unwrapped_mint: *unwrapped_mint_account.key,
+            wrapped_mint: *wrapped_mint_account.key,
+            wrapped_token_program: *wrapped_token_program_account.key,
+        },
+    )
+}
+
+/// Grows a list account by one entry and writes the entry at the end
+fn append_list_entry<T: VersionedListEntry>(
+    program_id: &Pubkey,
+    account_info: &AccountInfo,
+    entry: &T,
+    signer_seeds: &[&[u8]],
+) -> Result<usize, ProgramError> {
+    let index = if account_info.data_is_empty() {
+        0
+    } else {
+        if account_info.owner != program_id {
+            Err(ProgramError::InvalidAccountOwner)?
+        }
+        T::unpack_list(&account_info.try_borrow_data()?)?.len()
+    };
+
+    create_or_resize_pda(
+        program_id,
+        account_info,
+        T::list_len(index.saturating_add(1)),
+        signer_seeds,
+    )?;
+    entry.pack_list_entry(&mut account_info.try_borrow_mut_data()?, index)?;
+    Ok(index)
+}
+
+/// Appends a new wrapped mint to the registry and to the index of its
+/// unwrapped mint
+fn register_wrapped_mint(
+    program_id: &Pubkey,
+    registry_info: &AccountInfo,
+    registry_page_info: &AccountInfo,
+    wrapped_mint_index_info: &AccountInfo,
+    entry: WrappedMintRegistryEntry,
+) -> ProgramResult {
+    let (registry_address, registry_bump) = get_wrapped_mint_registry_address_with_seed();
+    if *registry_info.key != registry_address {
+        Err(TokenWrapError::WrappedMintRegistryMismatch)?
+    }
+
+    let mut registry = if registry_info.data_is_empty() {
+        WrappedMintRegistry::zeroed()
+    } else {
+        if registry_info.owner != program_id {
+            Err(ProgramError::InvalidAccountOwner)?
+        }
+        WrappedMintRegistry::unpack_versioned(&registry_info.try_borrow_data()?)?
+    };
+
+    // Append to the last page
+
+    let page_index = registry.next_page_index();
+    let (page_address, page_bump) = get_wrapped_mint_registry_page_address_with_seed(page_index);
+    if *registry_page_info.key != page_address {
+        msg!("Error: the next entry is appended to page {}", page_index);
+        Err(TokenWrapError::WrappedMintRegistryPageMismatch)?
+    }
+
+    let page_index_bytes = page_index.to_le_bytes();
+    let bump_seed = [page_bump];
+    let entry_index = append_list_entry(
+        program_id,
+        registry_page_info,
+        &entry,
+        &get_wrapped_mint_registry_page_address_signer_seeds(&page_index_bytes, &bump_seed),
+    )?;
+    if entry_index as u64 != u64::from(registry.entry_count) % WrappedMintRegistry::ENTRIES_PER_PAGE
+    {
+        Err(ProgramError::InvalidAccountData)?
+    }
+
+    // Append to the index of the unwrapped mint
+
+    let (index_address, index_bump) =
+        get_wrapped_mint_index_address_with_seed(&entry.unwrapped_mint);
+    if *wrapped_mint_index_info.key != index_address {
+        Err(TokenWrapError::WrappedMintIndexMismatch)?
+    }
+
+    let bump_seed = [index_bump];
+    append_list_entry(
+        program_id,
+        wrapped_mint_index_info,
+        &WrappedMintIndexEntry {
+            wrapped_mint: entry.wrapped_mint,
+            wrapped_token_program: entry.wrapped_token_program,
+        },
+        &get_wrapped_mint_index_address_signer_seeds(&entry.unwrapped_mint, &bump_seed),
+    )?;
+
+    // Count the new entry
+
+    registry.entry_count = u64::from(registry.entry_count)
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        .into();
+    let bump_seed = [registry_bump];
+    create_or_resize_pda(
+        program_id,
+        registry_info,
+        WrappedMintRegistry::LEN,
+        &get_wrapped_mint_registry_address_signer_seeds(&bump_seed),
+    )?;
+    registry.pack_versioned(&mut registry_info.try_borrow_mut_data()?)
+}
+
+/// Processes [`RegisterWrappedMint`](enum.TokenWrapInstruction.html)
+/// instruction.
+pub fn process_register_wrapped_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let wrapped_mint_account = next_account_info(account_info_iter)?;
+    let wrapped_backpointer_account = next_account_info(account_info_iter)?;
+    let unwrapped_mint_account = next_account_info(account_info_iter)?;
+    let _system_program_account = next_account_info(account_info_iter)?;
+    let wrapped_token_program_account = next_account_info(account_info_iter)?;
+    let registry_account = next_account_info(account_info_iter)?;
+    let registry_page_account = next_account_info(account_info_iter)?;
+    let wrapped_mint_index_account = next_account_info(account_info_iter)?;
+
+    if *wrapped_token_program_account.key != spl_token::id()
+        && *wrapped_token_program_account.key != spl_token_2022_interface::id()
+    {
+        Err(ProgramError::IncorrectProgramId)?
+    }
+
+    // Validate the wrapped mint was created by `CreateMint`
+
+    let wrapped_mint_address = get_wrapped_mint_address(
+        unwrapped_mint_account.key,
+        wrapped_token_program_account.key,
+    );
+    if *wrapped_mint_account.key != wrapped_mint_address {
+        Err(TokenWrapError::WrappedMintMismatch)?
+    }
+    if wrapped_mint_account.data_is_empty() {
+        Err(ProgramError::UninitializedAccount)?
+    }
+    if wrapped_mint_account.owner != wrapped_token_program_account.key {
+        Err(TokenWrapError::InvalidWrappedMintOwner)?
+    }
+
+    let (wrapped_backpointer_address, _) =
+        get_wrapped_mint_backpointer_address_with_seed(wrapped_mint_account.key);
+    if *wrapped_backpointer_account.key != wrapped_backpointer_address {
+        Err(TokenWrapError::BackpointerMismatch)?
+    }
+    if wrapped_backpointer_account.owner != program_id {
+        Err(TokenWrapError::InvalidBackpointerOwner)?
+    }
+    let backpointer = Backpointer::unpack(&wrapped_backpointer_account.try_borrow_data()?)?;
+    if backpointer.unwrapped_mint != *unwrapped_mint_account.key {
+        Err(TokenWrapError::UnwrappedMintMismatch)?
+    }
+
+    // Mints created since the registry exists are already recorded
+
+    let (index_address, _) = get_wrapped_mint_index_address_with_seed(unwrapped_mint_account.key);
+    if *wrapped_mint_index_account.key != index_address {
+        Err(TokenWrapError::WrappedMintIndexMismatch)?
+    }
+    if !wrapped_mint_index_account.data_is_empty() {
+        if wrapped_mint_index_account.owner != program_id {
+            Err(ProgramError::InvalidAccountOwner)?
+        }
+        let index_data = wrapped_mint_index_account.try_borrow_data()?;
+        if WrappedMintIndexEntry::unpack_list(&index_data)?
+            .iter()
+            .any(|entry| entry.wrapped_mint == *wrapped_mint_account.key)
+        {
+            Err(TokenWrapError::WrappedMintAlreadyRegistered)?
+        }
+    }
+
+    register_wrapped_mint(
+        program_id,
+        registry_account,
+        registry_page_account,
+        wrapped_mint_index_account,
+        WrappedMintRegistryEntry {
+            unwrapped_mint: *unwrapped_mint_account.key,
+            wrapped_mint: *wrapped_mint_account.key,
+            wrapped_token_program: *wrapped_token_program_account.key,
+        },
+    )
+}
+
+/// Rejects the operation if the canonical pointer for the wrapped token
+/// program enforces a deployment other than this one. The scoped pointer takes
+/// precedence, falling back to the unscoped pointer if it is uninitialized. If
+/// neither is initialized, no deployment has been designated.
+fn check_canonical_pointer(
+    program_id: &Pubkey,
+    canonical_pointer_info: &AccountInfo,
+    scoped_canonical_pointer_info: &AccountInfo,
+    unwrapped_mint: &Pubkey,
+    wrapped_token_program: &Pubkey,
+) -> ProgramResult {
+    check_canonical_pointer_address(canonical_pointer_info, unwrapped_mint, None)?;
+    check_canonical_pointer_address(
+        scoped_canonical_pointer_info,
+        unwrapped_mint,
+        Some(wrapped_token_program),
+    )?;
+
+    let pointer = match read_canonical_pointer(program_id, scoped_canonical_pointer_info)? {
+        Some(pointer) => pointer,
+        None => match read_canonical_pointer(program_id, canonical_pointer_info)? {
+            Some(pointer) => pointer,
+            None => return Ok(()),
+        },
+    };
+
+    if pointer.blocks_wrap_in(program_id) {
+        msg!(
+            "Canonical deployment for this mint is {}",
+            pointer.program_id
+        );
+        Err(TokenWrapError::NonCanonicalDeployment)?
+    }
+
+    Ok(())
+}
+
+/// Rejects the operation if it breaks the wrap limits of the unwrapped mint,
+/// and otherwise records the wrapped amount in the current epoch. If the wrap
+/// limits account is uninitialized, wrapping is not limited.
+fn enforce_wrap_limits(
+    program_id: &Pubkey,
+    wrap_limits_info: &AccountInfo,
+    unwrapped_mint: &Pubkey,
+    amount: u64,
+    epoch: u64,
+) -> ProgramResult {
+    match check_wrap_limits(program_id, wrap_limits_info, unwrapped_mint, amount, epoch)? {
+        Some(limits) => limits.pack_versioned(&mut wrap_limits_info.try_borrow_mut_data()?),
+        None => Ok(()),
+    }
+}
+
+/// Rejects the operation if it breaks the wrap limits of the unwrapped mint,
+/// and otherwise returns the limits with the wrapped amount recorded, without
+/// storing them. Returns `None` if the wrap limits account is uninitialized, in
+/// which case wrapping is not limited.
+fn check_wrap_limits(
+    program_id: &Pubkey,
+    wrap_limits_info: &AccountInfo,
+    unwrapped_mint: &Pubkey,
+    amount: u64,
+    epoch: u64,
+) -> Result<Option<WrapLimits>, ProgramError> {
+    let (expected_address, _) = get_wrap_limits_address_with_seed(unwrapped_mint);
+    if *wrap_limits_info.key != expected_address {
+        Err(TokenWrapError::WrapLimitsMismatch)?
+    }
+    if wrap_limits_info.data_is_empty() {
+        return Ok(None);
+    }
+    if wrap_limits_info.owner != program_id {
+        Err(ProgramError::InvalidAccountOwner)?
+    }
+
+    let mut limits = WrapLimits::unpack_versioned(&wrap_limits_info.try_borrow_data()?)?;
+    if limits.is_unwrap_only() {
+        Err(TokenWrapError::UnwrapOnly)?
+    }
+
+    let new_total = u64::from(limits.total_wrapped)
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if !limits.allows_total_wrapped(new_total) {
+        msg!(
+            "Total wrapped would be {}, the maximum is {}",
+            new_total,
+            u64::from(limits.max_wrapped_supply)
+        );
+        Err(TokenWrapError::WrappedSupplyCapExceeded)?
+    }
+
+    if limits.record_wrap(amount, epoch).is_none() {
+        msg!(
+            "The maximum amount wrapped per epoch is {}",
+            u64::from(limits.max_wrapped_per_epoch)
+        );
+        Err(TokenWrapError::EpochWrapCapExceeded)?
+    }
+    Ok(Some(limits))
+}
+
+/// Returns the supply of the wrapped mint of the unwrapped mint under the given
+/// token program, or 0 if the wrapped mint has not been created.
+fn read_wrapped_supply(
+    unwrapped_mint: &Pubkey,
+    wrapped_mint_info: &AccountInfo,
+    wrapped_token_program_id: &Pubkey,
+) -> Result<u64, ProgramError> {
+    if *wrapped_mint_info.key != get_wrapped_mint_address(unwrapped_mint, wrapped_token_program_id)
+    {
+        Err(TokenWrapError::WrappedMintMismatch)?
+    }
+    if wrapped_mint_info.data_is_empty() {
+        return Ok(0);
+    }
+    if wrapped_mint_info.owner != wrapped_token_program_id {
+        Err(ProgramError::InvalidAccountOwner)?
+    }
+    let wrapped_mint_data = wrapped_mint_info.try_borrow_data()?;
+    Ok(
+        PodStateWithExtensions::<PodMint>::unpack(&wrapped_mint_data)?
+            .base
+            .supply
+            .into(),
+    )
+}
+
+/// Subtracts an unwrapped amount from the total wrapped in the wrap limits of
+/// the unwrapped mint. If the account is uninitialized, no limits are set.
+fn record_unwrap_in_wrap_limits(
+    program_id: &Pubkey,
+    wrap_limits_info: &AccountInfo,
+    unwrapped_mint: &Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    let (expected_address, _) = get_wrap_limits_address_with_seed(unwrapped_mint);
+    if *wrap_limits_info.key != expected_address {
+        Err(TokenWrapError::WrapLimitsMismatch)?
+    }
+    if wrap_limits_info.data_is_empty() {
+        return Ok(());
+    }
+    if wrap_limits_info.owner != program_id {
+        Err(ProgramError::InvalidAccountOwner)?
+    }
+
+    let mut limits = WrapLimits::unpack_versioned(&wrap_limits_info.try_borrow_data()?)?;
+    limits.record_unwrap(amount);
+    limits.pack_versioned(&mut wrap_limits_info.try_borrow_mut_data()?)
+}
+
+/// Rejects the operation if the allowlist mode of the unwrapped mint is
+/// enabled and the owner of the token account is not allowed. If the allowlist
+/// account is uninitialized, the mode is disabled.
+fn check_allowlist(
+    program_id: &Pubkey,
+    allowlist_info: &AccountInfo,
+    allowlist_entry_info: &AccountInfo,
+    unwrapped_mint: &Pubkey,
+    token_account_info: &AccountInfo,
+) -> ProgramResult {
+    let (expected_allowlist, _) = get_allowlist_address_with_seed(unwrapped_mint);
+    if *allowlist_info.key != expected_allowlist {
+        Err(TokenWrapError::AllowlistMismatch)?
+    }
+    if allowlist_info.data_is_empty() {
+        return Ok(());
+    }
+    if allowlist_info.owner != program_id {
+        Err(ProgramError::InvalidAccountOwner)?
+    }
+    if !Allowlist::unpack_versioned(&allowlist_info.try_borrow_data()?)?.is_enabled() {
+        return Ok(());
+    }
 
+    let wallet = {
+        let token_account_data = token_account_info.try_borrow_data()?;
+        PodStateWithExtensions::<PodAccount>::unpack(&token_account_data)?
+            .base
+            .owner
+    };
+    let (expected_entry, _) = get_allowlist_entry_address_with_seed(unwrapped_mint, &wallet);
+    if *allowlist_entry_info.key != expected_entry {
+        Err(TokenWrapError::AllowlistEntryMismatch)?
+    }
+    let allowed = !allowlist_entry_info.data_is_empty()
+        && allowlist_entry_info.owner == program_id
+        && AllowlistEntry::unpack_versioned(&allowlist_entry_info.try_borrow_data()?)?.is_allowed();
+    if !allowed {
+        msg!("Wallet {} is not on the allowlist", wallet);
+        Err(TokenWrapError::NotAllowlisted)?
+    }
     Ok(())
 }
 
+/// Splits the unwrapped tokens of an operation into the protocol fee and the
+/// rest. The override of the unwrapped mint replaces the fee of the config if
+/// enabled, and `fee_of` selects the fee of the operation. If a fee is charged,
+/// the fee vault must be a token account of the unwrapped mint owned by the fee
+/// destination. If the config account is uninitialized, no fee is charged.
+#[allow(clippy::too_many_arguments)]
+fn split_protocol_fee(
+    program_id: &Pubkey,
+    protocol_fee_config_info: &AccountInfo,
+    protocol_fee_override_info: &AccountInfo,
+    protocol_fee_vault_info: &AccountInfo,
+    unwrapped_mint: &Pubkey,
+    amount: u64,
+    fee_of: impl Fn(&ProtocolFeeConfig) -> u16,
+) -> Result<ProtocolFeeSplit, ProgramError> {
+    let (expected_config, _) = get_protocol_fee_config_address_with_seed();
+    if *protocol_fee_config_info.key != expected_config {
+        Err(TokenWrapError::ProtocolFeeConfigMismatch)?
+    }
+    let (expected_override, _) = get_protocol_fee_override_address_with_seed(unwrapped_mint);
+    if *protocol_fee_override_info.key != expected_override {
+        Err(TokenWrapError::ProtocolFeeOverrideMismatch)?
+    }
+    if protocol_fee_config_info.data_is_empty() {
+        return ProtocolFeeSplit::new(amount, 0).ok_or(ProgramError::ArithmeticOverflow);
+    }
+    if protocol_fee_config_info.owner != program_id {
+        Err(ProgramError::InvalidAccountOwner)?
+    }
+
+    let mut fee =
+        ProtocolFeeConfig::unpack_versioned(&protocol_fee_config_info.try_borrow_data()?)?;
+    if !protocol_fee_override_info.data_is_empty() && protocol_fee_override_info.owner == program_id
+    {
+        let fee_override =
+            ProtocolFeeOverride::unpack_versioned(&protocol_fee_override_info.try_borrow_data()?)?;
+        if fee_override.is_enabled() {
+            fee.wrap_fee_basis_points = fee_override.wrap_fee_basis_points;
+            fee.unwrap_fee_basis_points = fee_override.unwrap_fee_basis_points;
+        }
+    }
+
+    let basis_points = fee_of(&fee);
+    if basis_points > 0 {
+        let fee_vault_data = protocol_fee_vault_info.try_borrow_data()?;
+        let fee_vault = PodStateWithExtensions::<PodAccount>::unpack(&fee_vault_data)
+            .map_err(|_| TokenWrapError::FeeVaultMismatch)?;
+        if fee_vault.base.mint != *unwrapped_mint || fee_vault.base.owner != fee.fee_destination {
+            Err(TokenWrapError::FeeVaultMismatch)?
+        }
+    }
+    ProtocolFeeSplit::new(amount, basis_points).ok_or(ProgramError::ArithmeticOverflow)
+}
+
+/// Logs the protocol fee split of an operation and sets it as the return data
+fn report_protocol_fee_split(split: &ProtocolFeeSplit) {
+    msg!(
+        "Protocol fee: {}, amount: {}",
+        u64::from(split.protocol_fee),
+        u64::from(split.amount)
+    );
+    set_return_data(bytemuck::bytes_of(split));
+}
+
 /// Processes [`Wrap`](enum.TokenWrapInstruction.html) instruction.
-pub fn process_wrap(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+pub fn process_wrap(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    record_stats: bool,
+) -> ProgramResult {
+    wrap_with_authority_seeds(program_id, accounts, amount, record_stats, &[])
+}
+
+/// Wraps tokens with the accounts of `Wrap`, signing the transfer with the
+/// given seeds when the transfer authority is a PDA of the program
+fn wrap_with_authority_seeds(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    record_stats: bool,
+    transfer_authority_signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
     if amount == 0 {
         Err(TokenWrapError::ZeroWrapAmount)?
     }
@@ -216,6 +772,18 @@ pub fn process_wrap(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
     let unwrapped_mint = next_account_info(account_info_iter)?;
     let unwrapped_escrow = next_account_info(account_info_iter)?;
     let transfer_authority = next_account_info(account_info_iter)?;
+    let canonical_pointer = next_account_info(account_info_iter)?;
+    let scoped_canonical_pointer = next_account_info(account_info_iter)?;
+    let wrap_limits = next_account_info(account_info_iter)?;
+    let allowlist = next_account_info(account_info_iter)?;
+    let allowlist_entry = next_account_info(account_info_iter)?;
+    let protocol_fee_config = next_account_info(account_info_iter)?;
+    let protocol_fee_override = next_account_info(account_info_iter)?;
+    let protocol_fee_vault = next_account_info(account_info_iter)?;
+    let wrap_stats = record_stats
+        .then(|| next_account_info(account_info_iter))
+        .transpose()?;
+    let additional_accounts = account_info_iter.as_slice();
 
     // Validate accounts
 
@@ -248,6 +816,22 @@ pub fn process_wrap(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
         }
     }
 
+    check_canonical_pointer(
+        program_id,
+        canonical_pointer,
+        scoped_canonical_pointer,
+        unwrapped_mint.key,
+        wrapped_token_program.key,
+    )?;
+
+    check_allowlist(
+        program_id,
+        allowlist,
+        allowlist_entry,
+        unwrapped_mint.key,
+        recipient_wrapped_token_account,
+    )?;
+
     // Transfer unwrapped tokens from user to escrow
 
     let unwrapped_mint_data = unwrapped_mint.try_borrow_data()?;
@@ -264,6 +848,26 @@ pub fn process_wrap(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
         .checked_sub(fee)
         .ok_or(ProgramError::ArithmeticOverflow)?;
 
+    // The protocol fee is taken from the tokens deposited in the escrow
+    let split = split_protocol_fee(
+        program_id,
+        protocol_fee_config,
+        protocol_fee_override,
+        protocol_fee_vault,
+        unwrapped_mint.key,
+        net_amount,
+        |fee| fee.wrap_fee_basis_points.into(),
+    )?;
+    let mint_amount = u64::from(split.amount);
+
+    enforce_wrap_limits(
+        program_id,
+        wrap_limits,
+        unwrapped_mint.key,
+        mint_amount,
+        clock,
+    )?;
+
     if unwrapped_token_program.key == &spl_token_2022_interface::id() {
         // This invoke fn does extra validation on calculated fee
         invoke_transfer_checked_with_fee(
@@ -272,11 +876,11 @@ pub fn process_wrap(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
             unwrapped_mint.clone(),
             unwrapped_escrow.clone(),
             transfer_authority.clone(),
-            &accounts[9..],
+            additional_accounts,
             amount,
             unwrapped_mint_state.base.decimals,
             fee,
-            &[],
+            transfer_authority_signer_seeds,
         )?;
     } else {
         invoke_transfer_checked(
@@ -285,17 +889,32 @@ pub fn process_wrap(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
             unwrapped_mint.clone(),
             unwrapped_escrow.clone(),
             transfer_authority.clone(),
-            &accounts[9..],
+            additional_accounts,
             amount,
             unwrapped_mint_state.base.decimals,
-            &[],
+            transfer_authority_signer_seeds,
         )?;
     }
 
-    // Mint wrapped tokens to recipient
     let bump_seed = [bump];
     let signer_seeds = get_wrapped_mint_authority_signer_seeds(wrapped_mint.key, &bump_seed);
 
+    // Route the protocol fee from the escrow to the fee vault
+    if u64::from(split.protocol_fee) > 0 {
+        invoke_transfer_checked(
+            unwrapped_token_program.key,
+            unwrapped_escrow.clone(),
+            unwrapped_mint.clone(),
+            protocol_fee_vault.clone(),
+            wrapped_mint_authority.clone(),
+            additional_accounts,
+            split.protocol_fee.into(),
+            unwrapped_mint_state.base.decimals,
+            &[&signer_seeds],
+        )?;
+    }
+
+    // Mint wrapped tokens to recipient
     invoke_signed(
         &spl_token_2022_interface::instruction::mint_to(
             wrapped_token_program.key,
@@ -303,7 +922,7 @@ pub fn process_wrap(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
             recipient_wrapped_token_account.key,
             wrapped_mint_authority.key,
             &[],
-            net_amount,
+            mint_amount,
         )?,
         &[
             wrapped_mint.clone(),
@@ -313,575 +932,3598 @@ pub fn process_wrap(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
         &[&signer_seeds],
     )?;
 
+    if let Some(wrap_stats) = wrap_stats {
+        update_wrap_stats(program_id, wrap_stats, wrapped_mint.key, |stats, slot| {
+            stats.record_wrap(mint_amount, fee, slot)
+        })?;
+    }
+
+    report_protocol_fee_split(&split);
     Ok(())
 }
 
-/// Processes [`Unwrap`](enum.TokenWrapInstruction.html) instruction.
-pub fn process_unwrap(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
-    if amount == 0 {
-        Err(TokenWrapError::ZeroWrapAmount)?
+/// Processes [`WrapConfidential`](enum.TokenWrapInstruction.html)
+/// instruction.
+pub fn process_wrap_confidential(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    // The owner of the recipient follows the accounts of `Wrap`
+    if accounts.len() < 18 {
+        Err(ProgramError::NotEnoughAccountKeys)?
     }
+    let (wrap_accounts, rest) = accounts.split_at(17);
+    let (recipient_owner, additional_accounts) = rest.split_first().unwrap();
 
-    let account_info_iter = &mut accounts.iter();
-
-    let unwrapped_escrow = next_account_info(account_info_iter)?;
-    let recipient_unwrapped_token = next_account_info(account_info_iter)?;
-    let wrapped_mint_authority = next_account_info(account_info_iter)?;
-    let unwrapped_mint = next_account_info(account_info_iter)?;
-    let wrapped_token_program = next_account_info(account_info_iter)?;
-    let unwrapped_token_program = next_account_info(account_info_iter)?;
-    let wrapped_token_account = next_account_info(account_info_iter)?;
-    let wrapped_mint = next_account_info(account_info_iter)?;
-    let transfer_authority = next_account_info(account_info_iter)?;
-    let additional_accounts = account_info_iter.as_slice();
-
-    // Validate accounts
-
-    let expected_wrapped_mint =
-        get_wrapped_mint_address(unwrapped_mint.key, wrapped_token_program.key);
-    if expected_wrapped_mint != *wrapped_mint.key {
-        Err(TokenWrapError::WrappedMintMismatch)?
-    }
+    let recipient_wrapped_token_account = &wrap_accounts[0];
+    let wrapped_mint = &wrap_accounts[1];
+    let wrapped_token_program = &wrap_accounts[4];
 
-    let (expected_authority, bump) = get_wrapped_mint_authority_with_seed(wrapped_mint.key);
-    if *wrapped_mint_authority.key != expected_authority {
-        Err(TokenWrapError::MintAuthorityMismatch)?
+    if *wrapped_token_program.key != spl_token_2022_interface::id() {
+        Err(ProgramError::IncorrectProgramId)?
     }
 
-    let expected_escrow = get_associated_token_address_with_program_id(
-        wrapped_mint_authority.key,
-        unwrapped_mint.key,
-        unwrapped_token_program.key,
-    );
-    if *unwrapped_escrow.key != expected_escrow {
-        Err(TokenWrapError::EscrowMismatch)?
-    }
+    let read_recipient_amount = || -> Result<u64, ProgramError> {
+        let recipient_data = recipient_wrapped_token_account.try_borrow_data()?;
+        let recipient = PodStateWithExtensions::<PodAccount>::unpack(&recipient_data)?;
+        recipient
+            .get_extension::<ConfidentialTransferAccount>()
+            .and_then(|extension| extension.valid_as_destination())
+            .map_err(|_| TokenWrapError::RecipientNotConfidential)?;
+        Ok(recipient.base.amount.into())
+    };
 
-    // Burn wrapped tokens
+    // Wrap into the public balance of the recipient
 
-    let multisig_signer_keys = extract_multisig_accounts(transfer_authority, additional_accounts)?
+    let balance_before = read_recipient_amount()?;
+    let wrap_accounts = wrap_accounts
         .iter()
-        .map(|a| a.key)
+        .chain(additional_accounts)
+        .cloned()
         .collect::<Vec<_>>();
+    process_wrap(program_id, &wrap_accounts, amount, false)?;
+    let minted_amount = read_recipient_amount()?
+        .checked_sub(balance_before)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
 
-    invoke(
-        &spl_token_2022_interface::instruction::burn(
+    // Move the minted tokens into the confidential pending balance
+
+    let decimals = {
+        let wrapped_mint_data = wrapped_mint.try_borrow_data()?;
+        PodStateWithExtensions::<PodMint>::unpack(&wrapped_mint_data)?
+            .base
+            .decimals
+    };
+
+    msg!(
+        "Depositing {} wrapped tokens into the confidential pending balance",
+        minted_amount
+    );
+    invoke(
+        &deposit(
             wrapped_token_program.key,
-            wrapped_token_account.key,
+            recipient_wrapped_token_account.key,
             wrapped_mint.key,
-            transfer_authority.key,
-            &multisig_signer_keys,
-            amount,
+            minted_amount,
+            decimals,
+            recipient_owner.key,
+            &[],
         )?,
-        &accounts[6..],
-    )?;
-
-    // Transfer unwrapped tokens from escrow to recipient
-
-    let unwrapped_mint_data = unwrapped_mint.try_borrow_data()?;
-    let unwrapped_mint_state = PodStateWithExtensions::<PodMint>::unpack(&unwrapped_mint_data)?;
-    let bump_seed = [bump];
-    let signer_seeds = get_wrapped_mint_authority_signer_seeds(wrapped_mint.key, &bump_seed);
-
-    invoke_transfer_checked(
-        unwrapped_token_program.key,
-        unwrapped_escrow.clone(),
-        unwrapped_mint.clone(),
-        recipient_unwrapped_token.clone(),
-        wrapped_mint_authority.clone(),
-        additional_accounts,
-        amount,
-        unwrapped_mint_state.base.decimals,
-        &[&signer_seeds],
+        &[
+            recipient_wrapped_token_account.clone(),
+            wrapped_mint.clone(),
+            recipient_owner.clone(),
+        ],
     )?;
 
     Ok(())
 }
 
-/// Processes [`CloseStuckEscrow`](enum.TokenWrapInstruction.html) instruction.
-pub fn process_close_stuck_escrow(accounts: &[AccountInfo]) -> ProgramResult {
-    let account_info_iter = &mut accounts.iter();
+/// Processes [`UnwrapConfidential`](enum.TokenWrapInstruction.html)
+pub fn process_unwrap_confidential(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    new_decryptable_available_balance: &PodAeCiphertext,
+) -> ProgramResult {
+    // The proof context state accounts follow the accounts of `Unwrap`
+    if accounts.len() < 18 {
+        Err(ProgramError::NotEnoughAccountKeys)?
+    }
+    let (unwrap_accounts, rest) = accounts.split_at(16);
+    let (proof_accounts, additional_accounts) = rest.split_at(2);
 
-    let escrow_account = next_account_info(account_info_iter)?;
-    let destination_account = next_account_info(account_info_iter)?;
-    let unwrapped_mint = next_account_info(account_info_iter)?;
-    let wrapped_mint = next_account_info(account_info_iter)?;
-    let wrapped_mint_authority = next_account_info(account_info_iter)?;
-    let _token_2022_program = next_account_info(account_info_iter)?;
+    let wrapped_token_program = &unwrap_accounts[4];
+    let wrapped_token_account = &unwrap_accounts[6];
+    let wrapped_mint = &unwrap_accounts[7];
+    let transfer_authority = &unwrap_accounts[8];
+    let equality_proof_context = &proof_accounts[0];
+    let range_proof_context = &proof_accounts[1];
 
-    // This instruction is only for spl-token-2022 accounts because only they
-    // can have extensions that lead to size changes.
-    if *escrow_account.owner != spl_token_2022_interface::id()
-        || unwrapped_mint.owner != &spl_token_2022_interface::id()
-    {
-        return Err(ProgramError::IncorrectProgramId);
+    if *wrapped_token_program.key != spl_token_2022_interface::id() {
+        Err(ProgramError::IncorrectProgramId)?
     }
 
-    let expected_wrapped_mint_pubkey =
-        get_wrapped_mint_address(unwrapped_mint.key, wrapped_mint.owner);
-    if *wrapped_mint.key != expected_wrapped_mint_pubkey {
-        Err(TokenWrapError::WrappedMintMismatch)?
-    }
+    // Withdraw from the confidential available balance
 
-    let (expected_authority, bump) = get_wrapped_mint_authority_with_seed(wrapped_mint.key);
-    if *wrapped_mint_authority.key != expected_authority {
-        Err(TokenWrapError::MintAuthorityMismatch)?
-    }
+    let decimals = {
+        let wrapped_mint_data = wrapped_mint.try_borrow_data()?;
+        PodStateWithExtensions::<PodMint>::unpack(&wrapped_mint_data)?
+            .base
+            .decimals
+    };
 
-    let expected_escrow_address = get_associated_token_address_with_program_id(
-        wrapped_mint_authority.key,
-        unwrapped_mint.key,
-        unwrapped_mint.owner,
+    let multisig_signer_keys = additional_accounts
+        .iter()
+        .map(|a| a.key)
+        .collect::<Vec<_>>();
+
+    msg!(
+        "Withdrawing {} wrapped tokens from the confidential available balance",
+        amount
     );
+    let mut withdraw_accounts = vec![
+        wrapped_token_account.clone(),
+        wrapped_mint.clone(),
+        equality_proof_context.clone(),
+        range_proof_context.clone(),
+        transfer_authority.clone(),
+    ];
+    withdraw_accounts.extend_from_slice(additional_accounts);
+    invoke(
+        &inner_withdraw(
+            wrapped_token_program.key,
+            wrapped_token_account.key,
+            wrapped_mint.key,
+            amount,
+            decimals,
+            new_decryptable_available_balance,
+            transfer_authority.key,
+            &multisig_signer_keys,
+            ProofLocation::ContextStateAccount(equality_proof_context.key),
+            ProofLocation::ContextStateAccount(range_proof_context.key),
+        )?,
+        &withdraw_accounts,
+    )?;
 
-    if *escrow_account.key != expected_escrow_address {
-        return Err(TokenWrapError::EscrowMismatch.into());
+    // Unwrap from the public balance
+
+    let unwrap_accounts = unwrap_accounts
+        .iter()
+        .chain(additional_accounts)
+        .cloned()
+        .collect::<Vec<_>>();
+    process_unwrap(program_id, &unwrap_accounts, amount, false, false)
+}
+
+/// Checks that the owner of the spent token account signed the permit in an
+/// ed25519 program instruction of the transaction, then consumes the nonce of
+/// the permit
+fn consume_permit(
+    program_id: &Pubkey,
+    permit_accounts: &[AccountInfo],
+    token_account: &AccountInfo,
+    permit: &Permit,
+) -> ProgramResult {
+    let account_info_iter = &mut permit_accounts.iter();
+
+    let owner_info = next_account_info(account_info_iter)?;
+    let permit_nonce_info = next_account_info(account_info_iter)?;
+    let instructions_sysvar = next_account_info(account_info_iter)?;
+
+    {
+        let token_account_data = token_account.try_borrow_data()?;
+        let token_account_state =
+            PodStateWithExtensions::<PodAccount>::unpack(&token_account_data)?;
+        if token_account_state.base.owner != *owner_info.key {
+            Err(TokenWrapError::PermitOwnerMismatch)?
+        }
     }
 
-    let escrow_data = escrow_account.try_borrow_data()?;
-    let escrow_state = PodStateWithExtensions::<PodAccount>::unpack(&escrow_data)?;
+    if Clock::get()?.unix_timestamp > i64::from(permit.expiry) {
+        Err(TokenWrapError::PermitExpired)?
+    }
 
-    if escrow_state.base.owner != *wrapped_mint_authority.key {
-        return Err(TokenWrapError::EscrowOwnerMismatch.into());
+    let (expected_nonce_address, bump) = get_permit_nonce_address_with_seed(owner_info.key);
+    if *permit_nonce_info.key != expected_nonce_address {
+        Err(TokenWrapError::PermitNonceMismatch)?
     }
 
-    // Closing only works when the token balance is zero
-    if u64::from(escrow_state.base.amount) != 0 {
-        return Err(ProgramError::InvalidAccountData);
+    let mut permit_nonce = if permit_nonce_info.data_is_empty() {
+        PermitNonce::zeroed()
+    } else {
+        if permit_nonce_info.owner != program_id {
+            Err(ProgramError::InvalidAccountOwner)?
+        }
+        PermitNonce::unpack_versioned(&permit_nonce_info.try_borrow_data()?)?
+    };
+    if permit_nonce.nonce != permit.nonce {
+        Err(TokenWrapError::InvalidPermitNonce)?
     }
 
-    // Ensure the account is in the initialized state
-    if escrow_state.base.state != (AccountState::Initialized as u8) {
-        return Err(ProgramError::InvalidAccountData);
+    if !has_ed25519_signature(instructions_sysvar, owner_info.key, &permit.message())? {
+        Err(TokenWrapError::PermitSignatureMissing)?
     }
 
-    let current_account_extensions = escrow_state.get_extension_types()?;
-    drop(escrow_data);
+    msg!(
+        "Consuming permit nonce {} of {}",
+        u64::from(permit.nonce),
+        owner_info.key
+    );
+    permit_nonce.nonce = u64::from(permit.nonce)
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        .into();
 
-    let mint_data = unwrapped_mint.try_borrow_data()?;
-    let mint_state = PodStateWithExtensions::<PodMint>::unpack(&mint_data)?;
-    let mut required_account_extensions = vec![];
-    try_for_each_required_init_account_extension(mint_state.get_tlv_data(), |extension_type| {
-        required_account_extensions.push(extension_type);
-        Ok(())
-    })?;
+    let bump_seed = [bump];
+    create_or_resize_pda(
+        program_id,
+        permit_nonce_info,
+        PermitNonce::LEN,
+        &get_permit_nonce_address_signer_seeds(owner_info.key, &bump_seed),
+    )?;
+    permit_nonce.pack_versioned(&mut permit_nonce_info.try_borrow_mut_data()?)
+}
 
-    // ATAs always have the ImmutableOwner extension
-    if !required_account_extensions.contains(&ExtensionType::ImmutableOwner) {
-        required_account_extensions.push(ExtensionType::ImmutableOwner);
+/// Processes [`WrapWithPermit`](enum.TokenWrapInstruction.html)
+/// instruction.
+pub fn process_wrap_with_permit(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    nonce: u64,
+    expiry: i64,
+) -> ProgramResult {
+    // The permit accounts follow the accounts of `Wrap`
+    if accounts.len() < 21 {
+        Err(ProgramError::NotEnoughAccountKeys)?
     }
+    let (wrap_accounts, rest) = accounts.split_at(17);
+    let (permit_accounts, additional_accounts) = rest.split_at(4);
 
-    // If the token account already shares the same extensions as the mint,
-    // it does not need to be re-created
-    let in_good_state = current_account_extensions.len() == required_account_extensions.len()
-        && required_account_extensions
-            .iter()
-            .all(|item| current_account_extensions.contains(item));
+    let recipient_wrapped_token_account = &wrap_accounts[0];
+    let unwrapped_token_account = &wrap_accounts[5];
+    let unwrapped_mint = &wrap_accounts[6];
+    let transfer_authority = &wrap_accounts[8];
 
-    if in_good_state {
-        return Err(TokenWrapError::EscrowInGoodState.into());
+    let (expected_delegate, bump) = get_permit_delegate_address_with_seed();
+    if *transfer_authority.key != expected_delegate {
+        Err(TokenWrapError::PermitDelegateMismatch)?
     }
 
-    // Close old escrow account
-    let bump_seed = [bump];
-    let signer_seeds = get_wrapped_mint_authority_signer_seeds(wrapped_mint.key, &bump_seed);
-
-    invoke_signed(
-        &spl_token_2022_interface::instruction::close_account(
-            escrow_account.owner,
-            escrow_account.key,
-            destination_account.key,
-            wrapped_mint_authority.key,
-            &[],
-        )?,
-        &[
-            escrow_account.clone(),
-            destination_account.clone(),
-            wrapped_mint_authority.clone(),
-        ],
-        &[&signer_seeds],
+    let permit = Permit {
+        program_id: *program_id,
+        action: PermitAction::Wrap as u8,
+        mint: *unwrapped_mint.key,
+        amount: amount.into(),
+        source: *unwrapped_token_account.key,
+        recipient: *recipient_wrapped_token_account.key,
+        nonce: nonce.into(),
+        expiry: expiry.into(),
+    };
+    consume_permit(
+        program_id,
+        permit_accounts,
+        unwrapped_token_account,
+        &permit,
     )?;
 
-    Ok(())
-}
+    // Wrap with the permit delegate as transfer authority
 
-type FieldExtractor = Vec<(Field, fn(&TokenMetadata) -> &str)>;
+    let bump_seed = [bump];
+    let signer_seeds = get_permit_delegate_address_signer_seeds(&bump_seed);
+    let wrap_accounts = wrap_accounts
+        .iter()
+        .chain(additional_accounts)
+        .cloned()
+        .collect::<Vec<_>>();
+    wrap_with_authority_seeds(program_id, &wrap_accounts, amount, false, &[&signer_seeds])
+}
 
-fn update_fields_if_changed<'a>(
-    token_program: &Pubkey,
-    mint: &AccountInfo<'a>,
-    authority: &AccountInfo<'a>,
-    fields: FieldExtractor,
-    wrapped_metadata: &TokenMetadata,
-    unwrapped_metadata: &TokenMetadata,
-    signer_seeds: &[&[&[u8]]],
+/// Processes [`UnwrapWithPermit`](enum.TokenWrapInstruction.html)
+/// instruction.
+pub fn process_unwrap_with_permit(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    nonce: u64,
+    expiry: i64,
 ) -> ProgramResult {
-    for (field, extractor) in fields {
-        let current_value = extractor(wrapped_metadata);
-        let new_value = extractor(unwrapped_metadata);
-        if current_value != new_value {
-            invoke_signed(
-                &update_field(
-                    token_program,
-                    mint.key,
-                    authority.key,
-                    field.clone(),
-                    new_value.to_string(),
-                ),
-                &[mint.clone(), authority.clone()],
-                signer_seeds,
-            )?;
-        }
+    // The permit accounts follow the accounts of `Unwrap`
+    if accounts.len() < 20 {
+        Err(ProgramError::NotEnoughAccountKeys)?
     }
-    Ok(())
+    let (unwrap_accounts, rest) = accounts.split_at(16);
+    let (permit_accounts, additional_accounts) = rest.split_at(4);
+
+    let recipient_unwrapped_token = &unwrap_accounts[1];
+    let wrapped_token_account = &unwrap_accounts[6];
+    let wrapped_mint = &unwrap_accounts[7];
+    let transfer_authority = &unwrap_accounts[8];
+
+    let (expected_delegate, bump) = get_permit_delegate_address_with_seed();
+    if *transfer_authority.key != expected_delegate {
+        Err(TokenWrapError::PermitDelegateMismatch)?
+    }
+
+    let permit = Permit {
+        program_id: *program_id,
+        action: PermitAction::Unwrap as u8,
+        mint: *wrapped_mint.key,
+        amount: amount.into(),
+        source: *wrapped_token_account.key,
+        recipient: *recipient_unwrapped_token.key,
+        nonce: nonce.into(),
+        expiry: expiry.into(),
+    };
+    consume_permit(program_id, permit_accounts, wrapped_token_account, &permit)?;
+
+    // Unwrap with the permit delegate as transfer authority
+
+    let bump_seed = [bump];
+    let signer_seeds = get_permit_delegate_address_signer_seeds(&bump_seed);
+    let unwrap_accounts = unwrap_accounts
+        .iter()
+        .chain(additional_accounts)
+        .cloned()
+        .collect::<Vec<_>>();
+    unwrap_with_authority_seeds(
+        program_id,
+        &unwrap_accounts,
+        amount,
+        false,
+        false,
+        &[&signer_seeds],
+    )
 }
 
-/// Processes [`SyncMetadataToToken2022`](enum.TokenWrapInstruction.html)
+/// Processes [`SetCrankWrapRecipient`](enum.TokenWrapInstruction.html)
 /// instruction.
-pub fn process_sync_metadata_to_token_2022(accounts: &[AccountInfo]) -> ProgramResult {
+pub fn process_set_crank_wrap_recipient(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    recipient: Pubkey,
+) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
-    let wrapped_mint_info = next_account_info(account_info_iter)?;
-    let wrapped_mint_authority_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let crank_wrap_delegate_info = next_account_info(account_info_iter)?;
     let unwrapped_mint_info = next_account_info(account_info_iter)?;
-    let token_program_info = next_account_info(account_info_iter)?;
-    let source_metadata_info = account_info_iter.next();
-    let owner_program_info = account_info_iter.next();
+    let _system_program_info = next_account_info(account_info_iter)?;
 
-    if *token_program_info.key != spl_token_2022_interface::id() {
-        return Err(ProgramError::IncorrectProgramId);
+    if !owner_info.is_signer {
+        Err(ProgramError::MissingRequiredSignature)?
     }
 
-    if *wrapped_mint_info.owner != spl_token_2022_interface::id() {
-        return Err(ProgramError::IncorrectProgramId);
+    let (expected_address, bump) =
+        get_crank_wrap_delegate_address_with_seed(owner_info.key, unwrapped_mint_info.key);
+    if *crank_wrap_delegate_info.key != expected_address {
+        Err(TokenWrapError::CrankWrapDelegateMismatch)?
+    }
+    if !crank_wrap_delegate_info.data_is_empty() && crank_wrap_delegate_info.owner != program_id {
+        Err(ProgramError::InvalidAccountOwner)?
     }
 
-    let (expected_wrapped_mint, _) = get_wrapped_mint_address_with_seed(
+    let bump_seed = [bump];
+    create_or_resize_pda(
+        program_id,
+        crank_wrap_delegate_info,
+        CrankWrapDelegate::LEN,
+        &get_crank_wrap_delegate_address_signer_seeds(
+            owner_info.key,
+            unwrapped_mint_info.key,
+            &bump_seed,
+        ),
+    )?;
+
+    msg!(
+        "Crank wraps of {} for {} go to {}",
         unwrapped_mint_info.key,
-        &spl_token_2022_interface::id(),
+        owner_info.key,
+        recipient
     );
-    if *wrapped_mint_info.key != expected_wrapped_mint {
-        return Err(TokenWrapError::WrappedMintMismatch.into());
+    CrankWrapDelegate { recipient }
+        .pack_versioned(&mut crank_wrap_delegate_info.try_borrow_mut_data()?)
+}
+
+/// Processes [`CrankWrap`](enum.TokenWrapInstruction.html) instruction.
+pub fn process_crank_wrap(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    if accounts.len() < 17 {
+        Err(ProgramError::NotEnoughAccountKeys)?
     }
-    let (expected_authority, authority_bump) =
-        get_wrapped_mint_authority_with_seed(wrapped_mint_info.key);
-    if *wrapped_mint_authority_info.key != expected_authority {
-        return Err(TokenWrapError::MintAuthorityMismatch.into());
+
+    let recipient_wrapped_token_account = &accounts[0];
+    let unwrapped_token_account = &accounts[5];
+    let unwrapped_mint = &accounts[6];
+    let transfer_authority = &accounts[8];
+
+    let (owner, delegate, delegated_amount, balance) = {
+        let token_account_data = unwrapped_token_account.try_borrow_data()?;
+        let token_account_state =
+            PodStateWithExtensions::<PodAccount>::unpack(&token_account_data)?;
+        (
+            token_account_state.base.owner,
+            token_account_state
+                .base
+                .delegate
+                .ok_or(TokenWrapError::CrankWrapNotApproved)?,
+            u64::from(token_account_state.base.delegated_amount),
+            u64::from(token_account_state.base.amount),
+        )
+    };
+
+    let (expected_delegate, bump) =
+        get_crank_wrap_delegate_address_with_seed(&owner, unwrapped_mint.key);
+    if *transfer_authority.key != expected_delegate {
+        Err(TokenWrapError::CrankWrapDelegateMismatch)?
+    }
+    if delegate != expected_delegate {
+        Err(TokenWrapError::CrankWrapNotApproved)?
     }
 
-    let unwrapped_metadata = extract_token_metadata(
-        unwrapped_mint_info,
-        source_metadata_info,
-        owner_program_info,
-    )?;
+    if transfer_authority.data_is_empty() {
+        Err(ProgramError::UninitializedAccount)?
+    }
+    if transfer_authority.owner != program_id {
+        Err(ProgramError::InvalidAccountOwner)?
+    }
+    let crank_wrap_delegate =
+        CrankWrapDelegate::unpack_versioned(&transfer_authority.try_borrow_data()?)?;
+    if crank_wrap_delegate.recipient != *recipient_wrapped_token_account.key {
+        Err(TokenWrapError::CrankWrapRecipientMismatch)?
+    }
 
-    let authority_bump_seed = [authority_bump];
-    let authority_signer_seeds =
-        get_wrapped_mint_authority_signer_seeds(wrapped_mint_info.key, &authority_bump_seed);
+    // Wrap all the delegated tokens still held by the account
+    let amount = delegated_amount.min(balance);
+    msg!("Crank wrapping {} tokens of {}", amount, owner);
 
-    let wrapped_mint_data = wrapped_mint_info.try_borrow_data()?;
-    let wrapped_mint_metadata = PodStateWithExtensions::<PodMint>::unpack(&wrapped_mint_data)?
-        .get_variable_len_extension::<TokenMetadata>()
-        .ok();
-    drop(wrapped_mint_data);
+    let bump_seed = [bump];
+    let signer_seeds =
+        get_crank_wrap_delegate_address_signer_seeds(&owner, unwrapped_mint.key, &bump_seed);
+    wrap_with_authority_seeds(program_id, accounts, amount, false, &[&signer_seeds])
+}
+
+/// Applies an update to the statistics account of a wrapped mint, creating it
+/// if it is uninitialized
+fn update_wrap_stats(
+    program_id: &Pubkey,
+    wrap_stats_info: &AccountInfo,
+    wrapped_mint: &Pubkey,
+    update: impl FnOnce(&mut WrapStats, u64) -> Option<()>,
+) -> ProgramResult {
+    let (expected_address, bump) = get_wrap_stats_address_with_seed(wrapped_mint);
+    if *wrap_stats_info.key != expected_address {
+        Err(TokenWrapError::WrapStatsMismatch)?
+    }
+
+    let mut stats = if wrap_stats_info.data_is_empty() {
+        WrapStats::zeroed()
+    } else {
+        if wrap_stats_info.owner != program_id {
+            Err(ProgramError::InvalidAccountOwner)?
+        }
+        WrapStats::unpack_versioned(&wrap_stats_info.try_borrow_data()?)?
+    };
+    update(&mut stats, Clock::get()?.slot).ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let bump_seed = [bump];
+    create_or_resize_pda(
+        program_id,
+        wrap_stats_info,
+        WrapStats::LEN,
+        &get_wrap_stats_address_signer_seeds(wrapped_mint, &bump_seed),
+    )?;
+    stats.pack_versioned(&mut wrap_stats_info.try_borrow_mut_data()?)
+}
+
+/// Records a pro-rata redemption in the pro-rata redemption PDA of a
+/// wrapped mint, creating it if needed
+fn update_pro_rata_redemption(
+    program_id: &Pubkey,
+    pro_rata_redemption_info: &AccountInfo,
+    wrapped_mint: &Pubkey,
+    update: impl FnOnce(&mut ProRataRedemption, u64) -> Option<()>,
+) -> ProgramResult {
+    let (expected_address, bump) = get_pro_rata_redemption_address_with_seed(wrapped_mint);
+    if *pro_rata_redemption_info.key != expected_address {
+        Err(TokenWrapError::ProRataRedemptionMismatch)?
+    }
+
+    let mut redemption = if pro_rata_redemption_info.data_is_empty() {
+        ProRataRedemption::zeroed()
+    } else {
+        if pro_rata_redemption_info.owner != program_id {
+            Err(ProgramError::InvalidAccountOwner)?
+        }
+        ProRataRedemption::unpack_versioned(&pro_rata_redemption_info.try_borrow_data()?)?
+    };
+    update(&mut redemption, Clock::get()?.slot).ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let bump_seed = [bump];
+    create_or_resize_pda(
+        program_id,
+        pro_rata_redemption_info,
+        ProRataRedemption::LEN,
+        &get_pro_rata_redemption_address_signer_seeds(wrapped_mint, &bump_seed),
+    )?;
+    redemption.pack_versioned(&mut pro_rata_redemption_info.try_borrow_mut_data()?)
+}
+
+/// Returns the balance of an escrow and the supply of the wrapped mint it backs
+fn read_escrow_backing(
+    escrow: &AccountInfo,
+    wrapped_mint: &AccountInfo,
+) -> Result<(u64, u64), ProgramError> {
+    let escrow_balance = {
+        let escrow_data = escrow.try_borrow_data()?;
+        PodStateWithExtensions::<PodAccount>::unpack(&escrow_data)?
+            .base
+            .amount
+    };
+    let wrapped_supply = {
+        let wrapped_mint_data = wrapped_mint.try_borrow_data()?;
+        PodStateWithExtensions::<PodMint>::unpack(&wrapped_mint_data)?
+            .base
+            .supply
+    };
+    Ok((escrow_balance.into(), wrapped_supply.into()))
+}
+
+/// Unwrapped tokens released for the wrapped tokens an unwrap burns, with the
+/// escrow backing they were computed from
+struct Redemption {
+    amount: u64,
+    escrow_balance: u64,
+    wrapped_supply: u64,
+}
+
+impl Redemption {
+    /// Returns true if the escrow is under-collateralized, in which case the
+    /// unwrap redeems pro rata
+    fn is_pro_rata(&self) -> bool {
+        self.escrow_balance < self.wrapped_supply
+    }
+}
+
+/// Computes the unwrapped tokens released for unwrapping `amount`. If the
+/// escrow is under-collateralized, the unwrap switches to pro-rata redemption,
+/// which requires the pro-rata redemption account.
+fn compute_redemption(
+    escrow: &AccountInfo,
+    wrapped_mint: &AccountInfo,
+    amount: u64,
+    pro_rata_redemption: Option<&AccountInfo>,
+) -> Result<Redemption, ProgramError> {
+    let (escrow_balance, wrapped_supply) = read_escrow_backing(escrow, wrapped_mint)?;
+    let mut redemption = Redemption {
+        amount,
+        escrow_balance,
+        wrapped_supply,
+    };
+    if !redemption.is_pro_rata() {
+        return Ok(redemption);
+    }
+
+    if pro_rata_redemption.is_none() {
+        Err(TokenWrapError::ProRataRedemptionRequired)?
+    }
+    redemption.amount =
+        ProRataRedemption::redemption_amount(amount, escrow_balance, wrapped_supply)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    if redemption.amount == 0 {
+        Err(TokenWrapError::RedemptionAmountZero)?
+    }
+    msg!(
+        "Escrow is under-collateralized: {} unwrapped for {} wrapped, redeeming {} for {} pro rata",
+        escrow_balance,
+        wrapped_supply,
+        redemption.amount,
+        amount
+    );
+    Ok(redemption)
+}
+
+/// Records an unwrap of `amount` in the pro-rata redemption account if it
+/// redeemed pro rata
+fn record_redemption(
+    program_id: &Pubkey,
+    pro_rata_redemption: Option<&AccountInfo>,
+    wrapped_mint: &Pubkey,
+    amount: u64,
+    redemption: &Redemption,
+) -> ProgramResult {
+    match pro_rata_redemption {
+        Some(pro_rata_redemption) if redemption.is_pro_rata() => update_pro_rata_redemption(
+            program_id,
+            pro_rata_redemption,
+            wrapped_mint,
+            |record, slot| {
+                record.record(
+                    amount,
+                    redemption.amount,
+                    redemption.escrow_balance,
+                    redemption.wrapped_supply,
+                    slot,
+                )
+            },
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Processes [`Unwrap`](enum.TokenWrapInstruction.html) instruction.
+pub fn process_unwrap(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    record_stats: bool,
+    pro_rata: bool,
+) -> ProgramResult {
+    unwrap_with_authority_seeds(program_id, accounts, amount, record_stats, pro_rata, &[])
+}
+
+/// Unwraps tokens with the accounts of `Unwrap`, signing the burn with the
+/// given seeds when the transfer authority is a PDA of the program
+fn unwrap_with_authority_seeds(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    record_stats: bool,
+    pro_rata: bool,
+    transfer_authority_signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    if amount == 0 {
+        Err(TokenWrapError::ZeroWrapAmount)?
+    }
+
+    let account_info_iter = &mut accounts.iter();
+
+    let unwrapped_escrow = next_account_info(account_info_iter)?;
+    let recipient_unwrapped_token = next_account_info(account_info_iter)?;
+    let wrapped_mint_authority = next_account_info(account_info_iter)?;
+    let unwrapped_mint = next_account_info(account_info_iter)?;
+    let wrapped_token_program = next_account_info(account_info_iter)?;
+    let unwrapped_token_program = next_account_info(account_info_iter)?;
+    let wrapped_token_account = next_account_info(account_info_iter)?;
+    let wrapped_mint = next_account_info(account_info_iter)?;
+    let transfer_authority = next_account_info(account_info_iter)?;
+    let wrap_limits = next_account_info(account_info_iter)?;
+    let allowlist = next_account_info(account_info_iter)?;
+    let allowlist_entry = next_account_info(account_info_iter)?;
+    let protocol_fee_config = next_account_info(account_info_iter)?;
+    let protocol_fee_override = next_account_info(account_info_iter)?;
+    let protocol_fee_vault = next_account_info(account_info_iter)?;
+    let flash_mint_guard = next_account_info(account_info_iter)?;
+    let wrap_stats = record_stats
+        .then(|| next_account_info(account_info_iter))
+        .transpose()?;
+    let pro_rata_redemption = pro_rata
+        .then(|| next_account_info(account_info_iter))
+        .transpose()?;
+    let additional_accounts = account_info_iter.as_slice();
+
+    // Validate accounts
+
+    let expected_wrapped_mint =
+        get_wrapped_mint_address(unwrapped_mint.key, wrapped_token_program.key);
+    if expected_wrapped_mint != *wrapped_mint.key {
+        Err(TokenWrapError::WrappedMintMismatch)?
+    }
+
+    let (expected_authority, bump) = get_wrapped_mint_authority_with_seed(wrapped_mint.key);
+    if *wrapped_mint_authority.key != expected_authority {
+        Err(TokenWrapError::MintAuthorityMismatch)?
+    }
+
+    let expected_escrow = get_associated_token_address_with_program_id(
+        wrapped_mint_authority.key,
+        unwrapped_mint.key,
+        unwrapped_token_program.key,
+    );
+    if *unwrapped_escrow.key != expected_escrow {
+        Err(TokenWrapError::EscrowMismatch)?
+    }
+
+    check_allowlist(
+        program_id,
+        allowlist,
+        allowlist_entry,
+        unwrapped_mint.key,
+        wrapped_token_account,
+    )?;
+
+    check_no_active_flash_mint(program_id, flash_mint_guard, wrapped_mint.key)?;
+
+    let redemption =
+        compute_redemption(unwrapped_escrow, wrapped_mint, amount, pro_rata_redemption)?;
+
+    // The protocol fee is taken from the tokens released from the escrow
+    let split = split_protocol_fee(
+        program_id,
+        protocol_fee_config,
+        protocol_fee_override,
+        protocol_fee_vault,
+        unwrapped_mint.key,
+        redemption.amount,
+        |fee| fee.unwrap_fee_basis_points.into(),
+    )?;
+
+    // Burn wrapped tokens
+
+    let multisig_signer_keys = extract_multisig_accounts(transfer_authority, additional_accounts)?
+        .iter()
+        .map(|a| a.key)
+        .collect::<Vec<_>>();
+
+    invoke_signed(
+        &spl_token_2022_interface::instruction::burn(
+            wrapped_token_program.key,
+            wrapped_token_account.key,
+            wrapped_mint.key,
+            transfer_authority.key,
+            &multisig_signer_keys,
+            amount,
+        )?,
+        &accounts[6..],
+        transfer_authority_signer_seeds,
+    )?;
+
+    // Transfer unwrapped tokens from escrow to recipient
+
+    let unwrapped_mint_data = unwrapped_mint.try_borrow_data()?;
+    let unwrapped_mint_state = PodStateWithExtensions::<PodMint>::unpack(&unwrapped_mint_data)?;
+    let bump_seed = [bump];
+    let signer_seeds = get_wrapped_mint_authority_signer_seeds(wrapped_mint.key, &bump_seed);
+
+    if u64::from(split.protocol_fee) > 0 {
+        invoke_transfer_checked(
+            unwrapped_token_program.key,
+            unwrapped_escrow.clone(),
+            unwrapped_mint.clone(),
+            protocol_fee_vault.clone(),
+            wrapped_mint_authority.clone(),
+            additional_accounts,
+            split.protocol_fee.into(),
+            unwrapped_mint_state.base.decimals,
+            &[&signer_seeds],
+        )?;
+    }
+
+    invoke_transfer_checked(
+        unwrapped_token_program.key,
+        unwrapped_escrow.clone(),
+        unwrapped_mint.clone(),
+        recipient_unwrapped_token.clone(),
+        wrapped_mint_authority.clone(),
+        additional_accounts,
+        split.amount.into(),
+        unwrapped_mint_state.base.decimals,
+        &[&signer_seeds],
+    )?;
+
+    record_unwrap_in_wrap_limits(program_id, wrap_limits, unwrapped_mint.key, amount)?;
+
+    if let Some(wrap_stats) = wrap_stats {
+        update_wrap_stats(program_id, wrap_stats, wrapped_mint.key, |stats, slot| {
+            stats.record_unwrap(amount, slot)
+        })?;
+    }
+
+    record_redemption(
+        program_id,
+        pro_rata_redemption,
+        wrapped_mint.key,
+        amount,
+        &redemption,
+    )?;
+
+    report_protocol_fee_split(&split);
+    Ok(())
+}
+
+/// Checks that the unwrapped mint is the native mint of the unwrapped token
+/// program
+fn check_native_mint(
+    unwrapped_mint: &AccountInfo,
+    unwrapped_token_program: &AccountInfo,
+) -> ProgramResult {
+    let native_mint = if *unwrapped_token_program.key == spl_token::id() {
+        spl_token::native_mint::id()
+    } else if *unwrapped_token_program.key == spl_token_2022_interface::id() {
+        spl_token_2022_interface::native_mint::id()
+    } else {
+        Err(ProgramError::IncorrectProgramId)?
+    };
+    if *unwrapped_mint.key != native_mint {
+        Err(TokenWrapError::NotNativeMint)?
+    }
+    Ok(())
+}
+
+/// Processes [`WrapSol`](enum.TokenWrapInstruction.html) instruction.
+pub fn process_wrap_sol(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    if amount == 0 {
+        Err(TokenWrapError::ZeroWrapAmount)?
+    }
+
+    let account_info_iter = &mut accounts.iter();
+
+    let recipient_wrapped_token_account = next_account_info(account_info_iter)?;
+    let wrapped_mint = next_account_info(account_info_iter)?;
+    let wrapped_mint_authority = next_account_info(account_info_iter)?;
+    let unwrapped_token_program = next_account_info(account_info_iter)?;
+    let wrapped_token_program = next_account_info(account_info_iter)?;
+    let native_mint = next_account_info(account_info_iter)?;
+    let unwrapped_escrow = next_account_info(account_info_iter)?;
+    let canonical_pointer = next_account_info(account_info_iter)?;
+    let scoped_canonical_pointer = next_account_info(account_info_iter)?;
+    let wrap_limits = next_account_info(account_info_iter)?;
+    let allowlist = next_account_info(account_info_iter)?;
+    let allowlist_entry = next_account_info(account_info_iter)?;
+    let protocol_fee_config = next_account_info(account_info_iter)?;
+    let protocol_fee_override = next_account_info(account_info_iter)?;
+    let protocol_fee_vault = next_account_info(account_info_iter)?;
+    let funding_account = next_account_info(account_info_iter)?;
+    let _system_program = next_account_info(account_info_iter)?;
+
+    // Validate accounts
+
+    check_native_mint(native_mint, unwrapped_token_program)?;
+
+    let expected_wrapped_mint =
+        get_wrapped_mint_address(native_mint.key, wrapped_token_program.key);
+    if expected_wrapped_mint != *wrapped_mint.key {
+        Err(TokenWrapError::WrappedMintMismatch)?
+    }
+
+    let (expected_authority, bump) = get_wrapped_mint_authority_with_seed(wrapped_mint.key);
+    if *wrapped_mint_authority.key != expected_authority {
+        Err(TokenWrapError::MintAuthorityMismatch)?
+    }
+
+    let expected_escrow = get_associated_token_address_with_program_id(
+        wrapped_mint_authority.key,
+        native_mint.key,
+        unwrapped_token_program.key,
+    );
+    if *unwrapped_escrow.key != expected_escrow {
+        Err(TokenWrapError::EscrowMismatch)?
+    }
+
+    {
+        let escrow_data = unwrapped_escrow.try_borrow_data()?;
+        let escrow_account = PodStateWithExtensions::<PodAccount>::unpack(&escrow_data)?;
+        if escrow_account.base.owner != expected_authority {
+            Err(TokenWrapError::EscrowOwnerMismatch)?
+        }
+    }
+
+    check_canonical_pointer(
+        program_id,
+        canonical_pointer,
+        scoped_canonical_pointer,
+        native_mint.key,
+        wrapped_token_program.key,
+    )?;
+
+    check_allowlist(
+        program_id,
+        allowlist,
+        allowlist_entry,
+        native_mint.key,
+        recipient_wrapped_token_account,
+    )?;
+
+    // The protocol fee is taken from the lamports deposited in the escrow
+    let split = split_protocol_fee(
+        program_id,
+        protocol_fee_config,
+        protocol_fee_override,
+        protocol_fee_vault,
+        native_mint.key,
+        amount,
+        |fee| fee.wrap_fee_basis_points.into(),
+    )?;
+    let mint_amount = u64::from(split.amount);
+
+    enforce_wrap_limits(
+        program_id,
+        wrap_limits,
+        native_mint.key,
+        mint_amount,
+        Clock::get()?.epoch,
+    )?;
+
+    // Deposit lamports into the escrow and sync its token balance
+
+    invoke(
+        &transfer(funding_account.key, unwrapped_escrow.key, amount),
+        &[funding_account.clone(), unwrapped_escrow.clone()],
+    )?;
+    invoke(
+        &sync_native(unwrapped_token_program.key, unwrapped_escrow.key)?,
+        core::slice::from_ref(unwrapped_escrow),
+    )?;
+
+    let bump_seed = [bump];
+    let signer_seeds = get_wrapped_mint_authority_signer_seeds(wrapped_mint.key, &bump_seed);
+
+    // Route the protocol fee from the escrow to the fee vault
+    if u64::from(split.protocol_fee) > 0 {
+        invoke_transfer_checked(
+            unwrapped_token_program.key,
+            unwrapped_escrow.clone(),
+            native_mint.clone(),
+            protocol_fee_vault.clone(),
+            wrapped_mint_authority.clone(),
+            &[],
+            split.protocol_fee.into(),
+            spl_token::native_mint::DECIMALS,
+            &[&signer_seeds],
+        )?;
+    }
+
+    // Mint wrapped tokens to recipient
+
+    invoke_signed(
+        &spl_token_2022_interface::instruction::mint_to(
+            wrapped_token_program.key,
+            wrapped_mint.key,
+            recipient_wrapped_token_account.key,
+            wrapped_mint_authority.key,
+            &[],
+            mint_amount,
+        )?,
+        &[
+            wrapped_mint.clone(),
+            recipient_wrapped_token_account.clone(),
+            wrapped_mint_authority.clone(),
+        ],
+        &[&signer_seeds],
+    )?;
+
+    report_protocol_fee_split(&split);
+    Ok(())
+}
+
+/// Processes [`UnwrapSol`](enum.TokenWrapInstruction.html) instruction.
+pub fn process_unwrap_sol(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    pro_rata: bool,
+) -> ProgramResult {
+    if amount == 0 {
+        Err(TokenWrapError::ZeroWrapAmount)?
+    }
+
+    let account_info_iter = &mut accounts.iter();
+
+    let unwrapped_escrow = next_account_info(account_info_iter)?;
+    let recipient = next_account_info(account_info_iter)?;
+    let wrapped_mint_authority = next_account_info(account_info_iter)?;
+    let native_mint = next_account_info(account_info_iter)?;
+    let wrapped_token_program = next_account_info(account_info_iter)?;
+    let unwrapped_token_program = next_account_info(account_info_iter)?;
+    let wrapped_token_account = next_account_info(account_info_iter)?;
+    let wrapped_mint = next_account_info(account_info_iter)?;
+    let transfer_authority = next_account_info(account_info_iter)?;
+    let wrap_limits = next_account_info(account_info_iter)?;
+    let allowlist = next_account_info(account_info_iter)?;
+    let allowlist_entry = next_account_info(account_info_iter)?;
+    let protocol_fee_config = next_account_info(account_info_iter)?;
+    let protocol_fee_override = next_account_info(account_info_iter)?;
+    let protocol_fee_vault = next_account_info(account_info_iter)?;
+    let flash_mint_guard = next_account_info(account_info_iter)?;
+    let native_unwrap_account = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let _system_program = next_account_info(account_info_iter)?;
+    let pro_rata_redemption = pro_rata
+        .then(|| next_account_info(account_info_iter))
+        .transpose()?;
+    let additional_accounts = account_info_iter.as_slice();
+
+    // Validate accounts
+
+    check_native_mint(native_mint, unwrapped_token_program)?;
+
+    let expected_wrapped_mint =
+        get_wrapped_mint_address(native_mint.key, wrapped_token_program.key);
+    if expected_wrapped_mint != *wrapped_mint.key {
+        Err(TokenWrapError::WrappedMintMismatch)?
+    }
+
+    let (expected_authority, bump) = get_wrapped_mint_authority_with_seed(wrapped_mint.key);
+    if *wrapped_mint_authority.key != expected_authority {
+        Err(TokenWrapError::MintAuthorityMismatch)?
+    }
+
+    let expected_escrow = get_associated_token_address_with_program_id(
+        wrapped_mint_authority.key,
+        native_mint.key,
+        unwrapped_token_program.key,
+    );
+    if *unwrapped_escrow.key != expected_escrow {
+        Err(TokenWrapError::EscrowMismatch)?
+    }
+
+    let (expected_native_unwrap_account, native_unwrap_bump) =
+        get_native_unwrap_account_address_with_seed(wrapped_mint.key);
+    if *native_unwrap_account.key != expected_native_unwrap_account {
+        Err(TokenWrapError::NativeUnwrapAccountMismatch)?
+    }
+
+    if !payer.is_signer {
+        Err(ProgramError::MissingRequiredSignature)?
+    }
+
+    check_allowlist(
+        program_id,
+        allowlist,
+        allowlist_entry,
+        native_mint.key,
+        wrapped_token_account,
+    )?;
+
+    check_no_active_flash_mint(program_id, flash_mint_guard, wrapped_mint.key)?;
+
+    let redemption =
+        compute_redemption(unwrapped_escrow, wrapped_mint, amount, pro_rata_redemption)?;
+
+    // The protocol fee is taken from the lamports released from the escrow
+    let split = split_protocol_fee(
+        program_id,
+        protocol_fee_config,
+        protocol_fee_override,
+        protocol_fee_vault,
+        native_mint.key,
+        redemption.amount,
+        |fee| fee.unwrap_fee_basis_points.into(),
+    )?;
+
+    // Burn wrapped tokens
+
+    let multisig_signers = extract_multisig_accounts(transfer_authority, additional_accounts)?;
+    let multisig_signer_keys = multisig_signers.iter().map(|a| a.key).collect::<Vec<_>>();
+
+    let mut burn_accounts = vec![
+        wrapped_token_account.clone(),
+        wrapped_mint.clone(),
+        transfer_authority.clone(),
+    ];
+    burn_accounts.extend(multisig_signers.into_iter().cloned());
+
+    invoke(
+        &spl_token_2022_interface::instruction::burn(
+            wrapped_token_program.key,
+            wrapped_token_account.key,
+            wrapped_mint.key,
+            transfer_authority.key,
+            &multisig_signer_keys,
+            amount,
+        )?,
+        &burn_accounts,
+    )?;
+
+    // Create the temporary native token account, funding its rent from the
+    // payer
+
+    let rent_required = Rent::get()?.minimum_balance(Account::LEN);
+    let rent_shortfall = rent_required.saturating_sub(native_unwrap_account.lamports());
+    if rent_shortfall > 0 {
+        invoke(
+            &transfer(payer.key, native_unwrap_account.key, rent_shortfall),
+            &[payer.clone(), native_unwrap_account.clone()],
+        )?;
+    }
+
+    let native_unwrap_bump_seed = [native_unwrap_bump];
+    let native_unwrap_signer_seeds =
+        get_native_unwrap_account_address_signer_seeds(wrapped_mint.key, &native_unwrap_bump_seed);
+    invoke_signed(
+        &allocate(native_unwrap_account.key, Account::LEN as u64),
+        core::slice::from_ref(native_unwrap_account),
+        &[&native_unwrap_signer_seeds],
+    )?;
+    invoke_signed(
+        &assign(native_unwrap_account.key, unwrapped_token_program.key),
+        core::slice::from_ref(native_unwrap_account),
+        &[&native_unwrap_signer_seeds],
+    )?;
+    invoke(
+        &initialize_account3(
+            unwrapped_token_program.key,
+            native_unwrap_account.key,
+            native_mint.key,
+            wrapped_mint_authority.key,
+        )?,
+        &[native_unwrap_account.clone(), native_mint.clone()],
+    )?;
+
+    // Move the unwrapped amount out of the escrow and close the temporary
+    // account to the payer, who forwards the amount to the recipient
+
+    let bump_seed = [bump];
+    let signer_seeds = get_wrapped_mint_authority_signer_seeds(wrapped_mint.key, &bump_seed);
+
+    if u64::from(split.protocol_fee) > 0 {
+        invoke_transfer_checked(
+            unwrapped_token_program.key,
+            unwrapped_escrow.clone(),
+            native_mint.clone(),
+            protocol_fee_vault.clone(),
+            wrapped_mint_authority.clone(),
+            &[],
+            split.protocol_fee.into(),
+            spl_token::native_mint::DECIMALS,
+            &[&signer_seeds],
+        )?;
+    }
+
+    invoke_transfer_checked(
+        unwrapped_token_program.key,
+        unwrapped_escrow.clone(),
+        native_mint.clone(),
+        native_unwrap_account.clone(),
+        wrapped_mint_authority.clone(),
+        &[],
+        split.amount.into(),
+        spl_token::native_mint::DECIMALS,
+        &[&signer_seeds],
+    )?;
+
+    invoke_signed(
+        &close_account(
+            unwrapped_token_program.key,
+            native_unwrap_account.key,
+            payer.key,
+            wrapped_mint_authority.key,
+            &[],
+        )?,
+        &[
+            native_unwrap_account.clone(),
+            payer.clone(),
+            wrapped_mint_authority.clone(),
+        ],
+        &[&signer_seeds],
+    )?;
+
+    if payer.key != recipient.key {
+        invoke(
+            &transfer(payer.key, recipient.key, split.amount.into()),
+            &[payer.clone(), recipient.clone()],
+        )?;
+    }
+
+    record_unwrap_in_wrap_limits(program_id, wrap_limits, native_mint.key, amount)?;
+
+    record_redemption(
+        program_id,
+        pro_rata_redemption,
+        wrapped_mint.key,
+        amount,
+        &redemption,
+    )?;
+
+    report_protocol_fee_split(&split);
+    Ok(())
+}
+
+/// Validates the escrow of a Token-2022 unwrapped mint and the wrapped mint
+/// authority that owns it, returning the bump seed of the authority
+fn validate_token_2022_escrow(
+    escrow_account: &AccountInfo,
+    unwrapped_mint: &AccountInfo,
+    wrapped_mint: &AccountInfo,
+    wrapped_mint_authority: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    // Only spl-token-2022 accounts can have extensions that lead to size
+    // changes.
+    if *escrow_account.owner != spl_token_2022_interface::id()
+        || unwrapped_mint.owner != &spl_token_2022_interface::id()
+    {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let expected_wrapped_mint_pubkey =
+        get_wrapped_mint_address(unwrapped_mint.key, wrapped_mint.owner);
+    if *wrapped_mint.key != expected_wrapped_mint_pubkey {
+        Err(TokenWrapError::WrappedMintMismatch)?
+    }
+
+    let (expected_authority, bump) = get_wrapped_mint_authority_with_seed(wrapped_mint.key);
+    if *wrapped_mint_authority.key != expected_authority {
+        Err(TokenWrapError::MintAuthorityMismatch)?
+    }
+
+    let expected_escrow_address = get_associated_token_address_with_program_id(
+        wrapped_mint_authority.key,
+        unwrapped_mint.key,
+        unwrapped_mint.owner,
+    );
+
+    if *escrow_account.key != expected_escrow_address {
+        return Err(TokenWrapError::EscrowMismatch.into());
+    }
+
+    Ok(bump)
+}
+
+/// Returns the account extensions that the escrow of a Token-2022 unwrapped
+/// mint must have
+fn required_escrow_extensions(
+    unwrapped_mint: &AccountInfo,
+) -> Result<Vec<ExtensionType>, ProgramError> {
+    let mint_data = unwrapped_mint.try_borrow_data()?;
+    let mint_state = PodStateWithExtensions::<PodMint>::unpack(&mint_data)?;
+    let mut required_account_extensions = vec![];
+    try_for_each_required_init_account_extension(mint_state.get_tlv_data(), |extension_type| {
+        required_account_extensions.push(extension_type);
+        Ok(())
+    })?;
+
+    // ATAs always have the ImmutableOwner extension
+    if !required_account_extensions.contains(&ExtensionType::ImmutableOwner) {
+        required_account_extensions.push(ExtensionType::ImmutableOwner);
+    }
+
+    Ok(required_account_extensions)
+}
+
+/// Processes [`CloseStuckEscrow`](enum.TokenWrapInstruction.html) instruction.
+pub fn process_close_stuck_escrow(accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let escrow_account = next_account_info(account_info_iter)?;
+    let destination_account = next_account_info(account_info_iter)?;
+    let unwrapped_mint = next_account_info(account_info_iter)?;
+    let wrapped_mint = next_account_info(account_info_iter)?;
+    let wrapped_mint_authority = next_account_info(account_info_iter)?;
+    let _token_2022_program = next_account_info(account_info_iter)?;
+
+    let bump = validate_token_2022_escrow(
+        escrow_account,
+        unwrapped_mint,
+        wrapped_mint,
+        wrapped_mint_authority,
+    )?;
+
+    let escrow_data = escrow_account.try_borrow_data()?;
+    let escrow_state = PodStateWithExtensions::<PodAccount>::unpack(&escrow_data)?;
+
+    if escrow_state.base.owner != *wrapped_mint_authority.key {
+        return Err(TokenWrapError::EscrowOwnerMismatch.into());
+    }
+
+    // Closing only works when the token balance is zero
+    if u64::from(escrow_state.base.amount) != 0 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Ensure the account is in the initialized state
+    if escrow_state.base.state != (AccountState::Initialized as u8) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let current_account_extensions = escrow_state.get_extension_types()?;
+    drop(escrow_data);
+
+    let required_account_extensions = required_escrow_extensions(unwrapped_mint)?;
+
+    // If the token account already shares the same extensions as the mint,
+    // it does not need to be re-created
+    let in_good_state = current_account_extensions.len() == required_account_extensions.len()
+        && required_account_extensions
+            .iter()
+            .all(|item| current_account_extensions.contains(item));
+
+    if in_good_state {
+        return Err(TokenWrapError::EscrowInGoodState.into());
+    }
+
+    // Close old escrow account
+    let bump_seed = [bump];
+    let signer_seeds = get_wrapped_mint_authority_signer_seeds(wrapped_mint.key, &bump_seed);
+
+    invoke_signed(
+        &close_account(
+            escrow_account.owner,
+            escrow_account.key,
+            destination_account.key,
+            wrapped_mint_authority.key,
+            &[],
+        )?,
+        &[
+            escrow_account.clone(),
+            destination_account.clone(),
+            wrapped_mint_authority.clone(),
+        ],
+        &[&signer_seeds],
+    )?;
+
+    Ok(())
+}
+
+/// Processes [`RepairEscrow`](enum.TokenWrapInstruction.html) instruction.
+pub fn process_repair_escrow(accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let escrow_account = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let unwrapped_mint = next_account_info(account_info_iter)?;
+    let wrapped_mint = next_account_info(account_info_iter)?;
+    let wrapped_mint_authority = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let _token_2022_program = next_account_info(account_info_iter)?;
+
+    let bump = validate_token_2022_escrow(
+        escrow_account,
+        unwrapped_mint,
+        wrapped_mint,
+        wrapped_mint_authority,
+    )?;
+
+    let escrow_data = escrow_account.try_borrow_data()?;
+    let escrow_state = PodStateWithExtensions::<PodAccount>::unpack(&escrow_data)?;
+
+    if escrow_state.base.owner != *wrapped_mint_authority.key {
+        return Err(TokenWrapError::EscrowOwnerMismatch.into());
+    }
+
+    let current_account_extensions = escrow_state.get_extension_types()?;
+    drop(escrow_data);
+
+    // Extensions the escrow has but the mint no longer requires are left in
+    // place, they do not prevent transfers
+    let missing_account_extensions: Vec<ExtensionType> =
+        required_escrow_extensions(unwrapped_mint)?
+            .into_iter()
+            .filter(|extension_type| !current_account_extensions.contains(extension_type))
+            .collect();
+
+    if missing_account_extensions.is_empty() {
+        return Err(TokenWrapError::EscrowInGoodState.into());
+    }
+
+    let bump_seed = [bump];
+    let signer_seeds = get_wrapped_mint_authority_signer_seeds(wrapped_mint.key, &bump_seed);
+
+    invoke_signed(
+        &reallocate(
+            escrow_account.owner,
+            escrow_account.key,
+            payer.key,
+            wrapped_mint_authority.key,
+            &[],
+            &missing_account_extensions,
+        )?,
+        &[
+            escrow_account.clone(),
+            payer.clone(),
+            system_program.clone(),
+            wrapped_mint_authority.clone(),
+        ],
+        &[&signer_seeds],
+    )?;
+
+    Ok(())
+}
+
+/// Processes [`SetEscrowSurplusPolicy`](enum.TokenWrapInstruction.html)
+/// instruction.
+pub fn process_set_escrow_surplus_policy(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    action: SurplusAction,
+    target: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let policy_authority_info = next_account_info(account_info_iter)?;
+    let policy_info = next_account_info(account_info_iter)?;
+    let unwrapped_mint_info = next_account_info(account_info_iter)?;
+    let wrapped_mint_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+
+    check_unwrapped_mint_authority(
+        policy_authority_info,
+        unwrapped_mint_info,
+        account_info_iter.as_slice(),
+    )?;
+
+    let expected_wrapped_mint =
+        get_wrapped_mint_address(unwrapped_mint_info.key, wrapped_mint_info.owner);
+    if *wrapped_mint_info.key != expected_wrapped_mint {
+        Err(TokenWrapError::WrappedMintMismatch)?
+    }
+
+    let (expected_policy, bump) =
+        get_escrow_surplus_policy_address_with_seed(wrapped_mint_info.key);
+    if *policy_info.key != expected_policy {
+        Err(TokenWrapError::EscrowSurplusPolicyMismatch)?
+    }
+    if !policy_info.data_is_empty() && policy_info.owner != program_id {
+        Err(ProgramError::InvalidAccountOwner)?
+    }
+
+    let bump_seed = [bump];
+    create_or_resize_pda(
+        program_id,
+        policy_info,
+        EscrowSurplusPolicy::LEN,
+        &get_escrow_surplus_policy_address_signer_seeds(wrapped_mint_info.key, &bump_seed),
+    )?;
+
+    msg!(
+        "Escrow surplus policy of {} set to {:?} for {}",
+        wrapped_mint_info.key,
+        action,
+        target
+    );
+    EscrowSurplusPolicy {
+        action: action as u8,
+        target,
+    }
+    .pack_versioned(&mut policy_info.try_borrow_mut_data()?)
+}
+
+/// Processes [`UpdateConfidentialConfig`](enum.TokenWrapInstruction.html)
+/// instruction.
+pub fn process_update_confidential_config(
+    accounts: &[AccountInfo],
+    auto_approve_new_accounts: bool,
+    auditor_elgamal_pubkey: Option<PodElGamalPubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let config_authority_info = next_account_info(account_info_iter)?;
+    let wrapped_mint_info = next_account_info(account_info_iter)?;
+    let wrapped_mint_authority_info = next_account_info(account_info_iter)?;
+    let unwrapped_mint_info = next_account_info(account_info_iter)?;
+    let wrapped_token_program_info = next_account_info(account_info_iter)?;
+
+    check_unwrapped_mint_authority(
+        config_authority_info,
+        unwrapped_mint_info,
+        account_info_iter.as_slice(),
+    )?;
+
+    if *wrapped_token_program_info.key != spl_token_2022_interface::id()
+        || *wrapped_mint_info.owner != spl_token_2022_interface::id()
+    {
+        Err(ProgramError::IncorrectProgramId)?
+    }
+
+    let expected_wrapped_mint =
+        get_wrapped_mint_address(unwrapped_mint_info.key, wrapped_token_program_info.key);
+    if *wrapped_mint_info.key != expected_wrapped_mint {
+        Err(TokenWrapError::WrappedMintMismatch)?
+    }
+
+    let (expected_authority, bump) = get_wrapped_mint_authority_with_seed(wrapped_mint_info.key);
+    if *wrapped_mint_authority_info.key != expected_authority {
+        Err(TokenWrapError::MintAuthorityMismatch)?
+    }
+
+    // Only mints that delegated their confidential transfer settings to the
+    // wrapped mint authority can be updated
+    {
+        let wrapped_mint_data = wrapped_mint_info.try_borrow_data()?;
+        let wrapped_mint = PodStateWithExtensions::<PodMint>::unpack(&wrapped_mint_data)?;
+        let confidential_transfer_authority = wrapped_mint
+            .get_extension::<ConfidentialTransferMint>()
+            .ok()
+            .and_then(|extension| Option::<Pubkey>::from(extension.authority));
+        if confidential_transfer_authority != Some(expected_authority) {
+            Err(TokenWrapError::ConfidentialConfigNotGoverned)?
+        }
+    }
+
+    let bump_seed = [bump];
+    let signer_seeds = get_wrapped_mint_authority_signer_seeds(wrapped_mint_info.key, &bump_seed);
+    invoke_signed(
+        &update_confidential_transfer_mint(
+            wrapped_token_program_info.key,
+            wrapped_mint_info.key,
+            wrapped_mint_authority_info.key,
+            &[],
+            auto_approve_new_accounts,
+            auditor_elgamal_pubkey,
+        )?,
+        &[
+            wrapped_mint_info.clone(),
+            wrapped_mint_authority_info.clone(),
+        ],
+        &[&signer_seeds],
+    )?;
+
+    msg!(
+        "Confidential transfer settings of {} updated, auto-approve: {}, auditor: {}",
+        wrapped_mint_info.key,
+        auto_approve_new_accounts,
+        auditor_elgamal_pubkey.is_some()
+    );
+    Ok(())
+}
+
+/// Processes [`SetWrappedMintAdmin`](enum.TokenWrapInstruction.html)
+/// instruction.
+pub fn process_set_wrapped_mint_admin(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    admin: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let unwrapped_mint_authority_info = next_account_info(account_info_iter)?;
+    let wrapped_mint_admin_info = next_account_info(account_info_iter)?;
+    let unwrapped_mint_info = next_account_info(account_info_iter)?;
+    let wrapped_mint_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+
+    check_unwrapped_mint_authority(
+        unwrapped_mint_authority_info,
+        unwrapped_mint_info,
+        account_info_iter.as_slice(),
+    )?;
+
+    let expected_wrapped_mint =
+        get_wrapped_mint_address(unwrapped_mint_info.key, wrapped_mint_info.owner);
+    if *wrapped_mint_info.key != expected_wrapped_mint {
+        Err(TokenWrapError::WrappedMintMismatch)?
+    }
+
+    let (expected_admin_address, bump) =
+        get_wrapped_mint_admin_address_with_seed(wrapped_mint_info.key);
+    if *wrapped_mint_admin_info.key != expected_admin_address {
+        Err(TokenWrapError::WrappedMintAdminMismatch)?
+    }
+    if !wrapped_mint_admin_info.data_is_empty() && wrapped_mint_admin_info.owner != program_id {
+        Err(ProgramError::InvalidAccountOwner)?
+    }
+
+    let bump_seed = [bump];
+    create_or_resize_pda(
+        program_id,
+        wrapped_mint_admin_info,
+        WrappedMintAdmin::LEN,
+        &get_wrapped_mint_admin_address_signer_seeds(wrapped_mint_info.key, &bump_seed),
+    )?;
+
+    msg!("Admin of {} set to {}", wrapped_mint_info.key, admin);
+    WrappedMintAdmin { admin }.pack_versioned(&mut wrapped_mint_admin_info.try_borrow_mut_data()?)
+}
+
+/// Processes [`ExecuteAdminAction`](enum.TokenWrapInstruction.html)
+/// instruction.
+pub fn process_execute_admin_action(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    action: AdminAction,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let admin_info = next_account_info(account_info_iter)?;
+    let wrapped_mint_info = next_account_info(account_info_iter)?;
+    let wrapped_mint_authority_info = next_account_info(account_info_iter)?;
+    let unwrapped_mint_info = next_account_info(account_info_iter)?;
+    let wrapped_mint_admin_info = next_account_info(account_info_iter)?;
+    let wrapped_token_program_info = next_account_info(account_info_iter)?;
+    let wrapped_token_account_info = match action {
+        AdminAction::FreezeAccount | AdminAction::ThawAccount => {
+            Some(next_account_info(account_info_iter)?)
+        }
+        _ => None,
+    };
+
+    // Validate accounts
+
+    let expected_wrapped_mint =
+        get_wrapped_mint_address(unwrapped_mint_info.key, wrapped_token_program_info.key);
+    if *wrapped_mint_info.key != expected_wrapped_mint {
+        Err(TokenWrapError::WrappedMintMismatch)?
+    }
+
+    let (expected_authority, bump) = get_wrapped_mint_authority_with_seed(wrapped_mint_info.key);
+    if *wrapped_mint_authority_info.key != expected_authority {
+        Err(TokenWrapError::MintAuthorityMismatch)?
+    }
+
+    if *wrapped_mint_admin_info.key
+        != get_wrapped_mint_admin_address_with_seed(wrapped_mint_info.key).0
+    {
+        Err(TokenWrapError::WrappedMintAdminMismatch)?
+    }
+
+    // The appointed admin acts alongside the unwrapped mint authority
+    let appointed_admin = if wrapped_mint_admin_info.owner == program_id
+        && !wrapped_mint_admin_info.data_is_empty()
+    {
+        WrappedMintAdmin::unpack_versioned(&wrapped_mint_admin_info.try_borrow_data()?)?.admin()
+    } else {
+        None
+    };
+    if appointed_admin == Some(*admin_info.key) {
+        check_pointer_authority_signature(admin_info, account_info_iter.as_slice())?;
+    } else {
+        check_unwrapped_mint_authority(
+            admin_info,
+            unwrapped_mint_info,
+            account_info_iter.as_slice(),
+        )?;
+    }
+
+    // Run the action as the wrapped mint authority
+
+    let token_program = wrapped_token_program_info.key;
+    let mint = wrapped_mint_info.key;
+    let authority = wrapped_mint_authority_info.key;
+    let (instruction, cpi_accounts) = match (action, wrapped_token_account_info) {
+        (AdminAction::FreezeAccount, Some(account_info)) => (
+            freeze_account(token_program, account_info.key, mint, authority, &[])?,
+            vec![
+                account_info.clone(),
+                wrapped_mint_info.clone(),
+                wrapped_mint_authority_info.clone(),
+            ],
+        ),
+        (AdminAction::ThawAccount, Some(account_info)) => (
+            thaw_account(token_program, account_info.key, mint, authority, &[])?,
+            vec![
+                account_info.clone(),
+                wrapped_mint_info.clone(),
+                wrapped_mint_authority_info.clone(),
+            ],
+        ),
+        (AdminAction::Pause, _) => (
+            pause(token_program, mint, authority, &[])?,
+            vec![
+                wrapped_mint_info.clone(),
+                wrapped_mint_authority_info.clone(),
+            ],
+        ),
+        (AdminAction::Resume, _) => (
+            resume(token_program, mint, authority, &[])?,
+            vec![
+                wrapped_mint_info.clone(),
+                wrapped_mint_authority_info.clone(),
+            ],
+        ),
+        (AdminAction::UpdateMetadataField { field, value }, _) => (
+            update_field(token_program, mint, authority, field, value),
+            vec![
+                wrapped_mint_info.clone(),
+                wrapped_mint_authority_info.clone(),
+            ],
+        ),
+        (
+            AdminAction::SetTransferFee {
+                transfer_fee_basis_points,
+                maximum_fee,
+            },
+            _,
+        ) => (
+            set_transfer_fee(
+                token_program,
+                mint,
+                authority,
+                &[],
+                transfer_fee_basis_points,
+                maximum_fee,
+            )?,
+            vec![
+                wrapped_mint_info.clone(),
+                wrapped_mint_authority_info.clone(),
+            ],
+        ),
+        _ => Err(ProgramError::NotEnoughAccountKeys)?,
+    };
+
+    let bump_seed = [bump];
+    let signer_seeds = get_wrapped_mint_authority_signer_seeds(wrapped_mint_info.key, &bump_seed);
+    invoke_signed(&instruction, &cpi_accounts, &[&signer_seeds])
+}
+
+/// Processes [`SetWrapLimits`](enum.TokenWrapInstruction.html) instruction.
+pub fn process_set_wrap_limits(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    unwrap_only: bool,
+    max_wrapped_supply: u64,
+    max_wrapped_per_epoch: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let unwrapped_mint_authority_info = next_account_info(account_info_iter)?;
+    let wrap_limits_info = next_account_info(account_info_iter)?;
+    let unwrapped_mint_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let spl_token_wrapped_mint_info = next_account_info(account_info_iter)?;
+    let token_2022_wrapped_mint_info = next_account_info(account_info_iter)?;
+
+    check_unwrapped_mint_authority(
+        unwrapped_mint_authority_info,
+        unwrapped_mint_info,
+        account_info_iter.as_slice(),
+    )?;
+
+    let (expected_address, bump) = get_wrap_limits_address_with_seed(unwrapped_mint_info.key);
+    if *wrap_limits_info.key != expected_address {
+        Err(TokenWrapError::WrapLimitsMismatch)?
+    }
+
+    // The total starts from the supply of both wrapped mints, so it covers
+    // tokens wrapped before the limits were set
+    let total_wrapped = read_wrapped_supply(
+        unwrapped_mint_info.key,
+        spl_token_wrapped_mint_info,
+        &spl_token::id(),
+    )?
+    .checked_add(read_wrapped_supply(
+        unwrapped_mint_info.key,
+        token_2022_wrapped_mint_info,
+        &spl_token_2022_interface::id(),
+    )?)
+    .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Keep the amount already wrapped in the current epoch
+    let mut limits = if wrap_limits_info.data_is_empty() {
+        WrapLimits::zeroed()
+    } else {
+        if wrap_limits_info.owner != program_id {
+            Err(ProgramError::InvalidAccountOwner)?
+        }
+        WrapLimits::unpack_versioned(&wrap_limits_info.try_borrow_data()?)?
+    };
+    limits.unwrap_only = unwrap_only.into();
+    limits.max_wrapped_supply = max_wrapped_supply.into();
+    limits.max_wrapped_per_epoch = max_wrapped_per_epoch.into();
+    limits.total_wrapped = total_wrapped.into();
+
+    let bump_seed = [bump];
+    create_or_resize_pda(
+        program_id,
+        wrap_limits_info,
+        WrapLimits::LEN,
+        &get_wrap_limits_address_signer_seeds(unwrapped_mint_info.key, &bump_seed),
+    )?;
+
+    msg!(
+        "Wrap limits of {} set: unwrap only {}, maximum supply {}, maximum per epoch {}, total \
+         wrapped {}",
+        unwrapped_mint_info.key,
+        unwrap_only,
+        max_wrapped_supply,
+        max_wrapped_per_epoch,
+        total_wrapped
+    );
+    limits.pack_versioned(&mut wrap_limits_info.try_borrow_mut_data()?)
+}
+
+/// Processes [`SetAllowlistMode`](enum.TokenWrapInstruction.html)
+/// instruction.
+pub fn process_set_allowlist_mode(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    enabled: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let unwrapped_mint_authority_info = next_account_info(account_info_iter)?;
+    let allowlist_info = next_account_info(account_info_iter)?;
+    let unwrapped_mint_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+
+    check_unwrapped_mint_authority(
+        unwrapped_mint_authority_info,
+        unwrapped_mint_info,
+        account_info_iter.as_slice(),
+    )?;
+
+    let (expected_address, bump) = get_allowlist_address_with_seed(unwrapped_mint_info.key);
+    if *allowlist_info.key != expected_address {
+        Err(TokenWrapError::AllowlistMismatch)?
+    }
+    if !allowlist_info.data_is_empty() && allowlist_info.owner != program_id {
+        Err(ProgramError::InvalidAccountOwner)?
+    }
+
+    let bump_seed = [bump];
+    create_or_resize_pda(
+        program_id,
+        allowlist_info,
+        Allowlist::LEN,
+        &get_allowlist_address_signer_seeds(unwrapped_mint_info.key, &bump_seed),
+    )?;
+
+    msg!(
+        "Allowlist mode of {} set to {}",
+        unwrapped_mint_info.key,
+        enabled
+    );
+    Allowlist {
+        enabled: enabled.into(),
+    }
+    .pack_versioned(&mut allowlist_info.try_borrow_mut_data()?)
+}
+
+/// Processes [`UpdateAllowlist`](enum.TokenWrapInstruction.html) instruction.
+pub fn process_update_allowlist(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    allowed: bool,
+    wallets: &[Pubkey],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let unwrapped_mint_authority_info = next_account_info(account_info_iter)?;
+    let unwrapped_mint_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let allowlist_entry_infos = wallets
+        .iter()
+        .map(|_| next_account_info(account_info_iter))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    check_unwrapped_mint_authority(
+        unwrapped_mint_authority_info,
+        unwrapped_mint_info,
+        account_info_iter.as_slice(),
+    )?;
+
+    for (wallet, allowlist_entry_info) in wallets.iter().zip(allowlist_entry_infos) {
+        let (expected_address, bump) =
+            get_allowlist_entry_address_with_seed(unwrapped_mint_info.key, wallet);
+        if *allowlist_entry_info.key != expected_address {
+            Err(TokenWrapError::AllowlistEntryMismatch)?
+        }
+        if !allowlist_entry_info.data_is_empty() && allowlist_entry_info.owner != program_id {
+            Err(ProgramError::InvalidAccountOwner)?
+        }
+
+        let bump_seed = [bump];
+        create_or_resize_pda(
+            program_id,
+            allowlist_entry_info,
+            AllowlistEntry::LEN,
+            &get_allowlist_entry_address_signer_seeds(unwrapped_mint_info.key, wallet, &bump_seed),
+        )?;
+        AllowlistEntry {
+            allowed: allowed.into(),
+        }
+        .pack_versioned(&mut allowlist_entry_info.try_borrow_mut_data()?)?;
+    }
+
+    msg!(
+        "{} {} wallets on the allowlist of {}",
+        if allowed { "Allowed" } else { "Disallowed" },
+        wallets.len(),
+        unwrapped_mint_info.key
+    );
+    Ok(())
+}
+
+/// Processes [`SetProtocolFeeConfig`](enum.TokenWrapInstruction.html)
+/// instruction.
+pub fn process_set_protocol_fee_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    wrap_fee_basis_points: u16,
+    unwrap_fee_basis_points: u16,
+    fee_destination: Pubkey,
+) -> ProgramResult {
+    if wrap_fee_basis_points > MAX_FEE_BASIS_POINTS
+        || unwrap_fee_basis_points > MAX_FEE_BASIS_POINTS
+    {
+        Err(TokenWrapError::InvalidFeeBasisPoints)?
+    }
+
+    let account_info_iter = &mut accounts.iter();
+    let upgrade_authority_info = next_account_info(account_info_iter)?;
+    let protocol_fee_config_info = next_account_info(account_info_iter)?;
+    let program_data_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+
+    check_upgrade_authority(program_id, upgrade_authority_info, program_data_info)?;
+
+    let (expected_address, bump) = get_protocol_fee_config_address_with_seed();
+    if *protocol_fee_config_info.key != expected_address {
+        Err(TokenWrapError::ProtocolFeeConfigMismatch)?
+    }
+    if !protocol_fee_config_info.data_is_empty() && protocol_fee_config_info.owner != program_id {
+        Err(ProgramError::InvalidAccountOwner)?
+    }
+
+    let bump_seed = [bump];
+    create_or_resize_pda(
+        program_id,
+        protocol_fee_config_info,
+        ProtocolFeeConfig::LEN,
+        &get_protocol_fee_config_address_signer_seeds(&bump_seed),
+    )?;
+
+    msg!(
+        "Protocol fee set to {} bps on wrap and {} bps on unwrap, paid to {}",
+        wrap_fee_basis_points,
+        unwrap_fee_basis_points,
+        fee_destination
+    );
+    ProtocolFeeConfig {
+        fee_destination,
+        wrap_fee_basis_points: wrap_fee_basis_points.into(),
+        unwrap_fee_basis_points: unwrap_fee_basis_points.into(),
+    }
+    .pack_versioned(&mut protocol_fee_config_info.try_borrow_mut_data()?)
+}
+
+/// Processes [`SetProtocolFeeOverride`](enum.TokenWrapInstruction.html)
+/// instruction.
+pub fn process_set_protocol_fee_override(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    enabled: bool,
+    wrap_fee_basis_points: u16,
+    unwrap_fee_basis_points: u16,
+) -> ProgramResult {
+    if wrap_fee_basis_points > MAX_FEE_BASIS_POINTS
+        || unwrap_fee_basis_points > MAX_FEE_BASIS_POINTS
+    {
+        Err(TokenWrapError::InvalidFeeBasisPoints)?
+    }
+
+    let account_info_iter = &mut accounts.iter();
+    let upgrade_authority_info = next_account_info(account_info_iter)?;
+    let protocol_fee_override_info = next_account_info(account_info_iter)?;
+    let unwrapped_mint_info = next_account_info(account_info_iter)?;
+    let program_data_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+
+    check_upgrade_authority(program_id, upgrade_authority_info, program_data_info)?;
+
+    if unwrapped_mint_info.owner != &spl_token::id()
+        && unwrapped_mint_info.owner != &spl_token_2022_interface::id()
+    {
+        Err(ProgramError::InvalidAccountOwner)?
+    }
+
+    let (expected_address, bump) =
+        get_protocol_fee_override_address_with_seed(unwrapped_mint_info.key);
+    if *protocol_fee_override_info.key != expected_address {
+        Err(TokenWrapError::ProtocolFeeOverrideMismatch)?
+    }
+    if !protocol_fee_override_info.data_is_empty() && protocol_fee_override_info.owner != program_id
+    {
+        Err(ProgramError::InvalidAccountOwner)?
+    }
+
+    let bump_seed = [bump];
+    create_or_resize_pda(
+        program_id,
+        protocol_fee_override_info,
+        ProtocolFeeOverride::LEN,
+        &get_protocol_fee_override_address_signer_seeds(unwrapped_mint_info.key, &bump_seed),
+    )?;
+
+    if enabled {
+        msg!(
+            "Protocol fee of {} overridden to {} bps on wrap and {} bps on unwrap",
+            unwrapped_mint_info.key,
+            wrap_fee_basis_points,
+            unwrap_fee_basis_points
+        );
+    } else {
+        msg!(
+            "Protocol fee override of {} disabled",
+            unwrapped_mint_info.key
+        );
+    }
+    ProtocolFeeOverride {
+        enabled: enabled.into(),
+        wrap_fee_basis_points: wrap_fee_basis_points.into(),
+        unwrap_fee_basis_points: unwrap_fee_basis_points.into(),
+    }
+    .pack_versioned(&mut protocol_fee_override_info.try_borrow_mut_data()?)
+}
+
+/// Rejects the operation while a flash mint of the wrapped mint awaits
+/// repayment, as the loan is not backed by the escrow. If the flash mint guard
+/// is uninitialized, no flash mint was ever taken.
+fn check_no_active_flash_mint(
+    program_id: &Pubkey,
+    flash_mint_guard_info: &AccountInfo,
+    wrapped_mint: &Pubkey,
+) -> ProgramResult {
+    let (expected_address, _) = get_flash_mint_guard_address_with_seed(wrapped_mint);
+    if *flash_mint_guard_info.key != expected_address {
+        Err(TokenWrapError::FlashMintGuardMismatch)?
+    }
+    if flash_mint_guard_info.data_is_empty() {
+        return Ok(());
+    }
+    if flash_mint_guard_info.owner != program_id {
+        Err(ProgramError::InvalidAccountOwner)?
+    }
+
+    if FlashMintGuard::unpack_versioned(&flash_mint_guard_info.try_borrow_data()?)?.is_active() {
+        Err(TokenWrapError::FlashMintActive)?
+    }
+    Ok(())
+}
+
+/// Returns true if a top-level instruction after the current one is a
+/// `FlashRepay` of the flash mint guard
+fn has_later_flash_repay(
+    program_id: &Pubkey,
+    instructions_sysvar: &AccountInfo,
+    flash_mint_guard: &Pubkey,
+) -> Result<bool, ProgramError> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let mut index = usize::from(current_index).saturating_add(1);
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions_sysvar) {
+        if instruction.program_id == *program_id
+            && matches!(
+                TokenWrapInstruction::unpack(&instruction.data),
+                Ok(TokenWrapInstruction::FlashRepay)
+            )
+            && instruction
+                .accounts
+                .get(3)
+                .is_some_and(|account| account.pubkey == *flash_mint_guard)
+        {
+            return Ok(true);
+        }
+        index = index.saturating_add(1);
+    }
+    Ok(false)
+}
+
+/// Processes [`FlashMint`](enum.TokenWrapInstruction.html) instruction.
+pub fn process_flash_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    if amount == 0 {
+        Err(TokenWrapError::ZeroWrapAmount)?
+    }
+
+    let account_info_iter = &mut accounts.iter();
+    let recipient_wrapped_token_account = next_account_info(account_info_iter)?;
+    let wrapped_mint = next_account_info(account_info_iter)?;
+    let wrapped_mint_authority = next_account_info(account_info_iter)?;
+    let unwrapped_mint = next_account_info(account_info_iter)?;
+    let unwrapped_escrow = next_account_info(account_info_iter)?;
+    let flash_mint_guard_info = next_account_info(account_info_iter)?;
+    let wrapped_token_program = next_account_info(account_info_iter)?;
+    let unwrapped_token_program = next_account_info(account_info_iter)?;
+    let instructions_sysvar = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let canonical_pointer = next_account_info(account_info_iter)?;
+    let scoped_canonical_pointer = next_account_info(account_info_iter)?;
+    let wrap_limits = next_account_info(account_info_iter)?;
+    let allowlist = next_account_info(account_info_iter)?;
+    let allowlist_entry = next_account_info(account_info_iter)?;
+
+    // Validate accounts
+
+    let expected_wrapped_mint =
+        get_wrapped_mint_address(unwrapped_mint.key, wrapped_token_program.key);
+    if *wrapped_mint.key != expected_wrapped_mint {
+        Err(TokenWrapError::WrappedMintMismatch)?
+    }
+
+    // A flash mint issues wrapped tokens like a wrap, so the same policies of
+    // the unwrapped mint apply to it
+
+    check_canonical_pointer(
+        program_id,
+        canonical_pointer,
+        scoped_canonical_pointer,
+        unwrapped_mint.key,
+        wrapped_token_program.key,
+    )?;
+
+    // The loan is burned again in the same transaction, so it is not recorded
+    check_wrap_limits(
+        program_id,
+        wrap_limits,
+        unwrapped_mint.key,
+        amount,
+        Clock::get()?.epoch,
+    )?;
+
+    check_allowlist(
+        program_id,
+        allowlist,
+        allowlist_entry,
+        unwrapped_mint.key,
+        recipient_wrapped_token_account,
+    )?;
+
+    let (expected_authority, bump) = get_wrapped_mint_authority_with_seed(wrapped_mint.key);
+    if *wrapped_mint_authority.key != expected_authority {
+        Err(TokenWrapError::MintAuthorityMismatch)?
+    }
+
+    let expected_escrow = get_associated_token_address_with_program_id(
+        wrapped_mint_authority.key,
+        unwrapped_mint.key,
+        unwrapped_token_program.key,
+    );
+    if *unwrapped_escrow.key != expected_escrow {
+        Err(TokenWrapError::EscrowMismatch)?
+    }
+
+    let (expected_guard_address, guard_bump) =
+        get_flash_mint_guard_address_with_seed(wrapped_mint.key);
+    if *flash_mint_guard_info.key != expected_guard_address {
+        Err(TokenWrapError::FlashMintGuardMismatch)?
+    }
+    if !flash_mint_guard_info.data_is_empty() && flash_mint_guard_info.owner != program_id {
+        Err(ProgramError::InvalidAccountOwner)?
+    }
+
+    // The loan must be repaid later in the same transaction
+
+    if !has_later_flash_repay(program_id, instructions_sysvar, flash_mint_guard_info.key)? {
+        Err(TokenWrapError::FlashRepayMissing)?
+    }
+
+    let mut guard = if flash_mint_guard_info.data_is_empty() {
+        FlashMintGuard::zeroed()
+    } else {
+        FlashMintGuard::unpack_versioned(&flash_mint_guard_info.try_borrow_data()?)?
+    };
+    if guard.is_active() {
+        Err(TokenWrapError::FlashMintActive)?
+    }
+
+    let fee = guard
+        .fee_for(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let (escrow_balance, wrapped_supply) = read_escrow_backing(unwrapped_escrow, wrapped_mint)?;
+
+    guard.active = true.into();
+    guard.unwrapped_escrow = *unwrapped_escrow.key;
+    guard.amount = amount.into();
+    guard.fee = fee.into();
+    guard.backing_deficit = wrapped_supply.saturating_sub(escrow_balance).into();
+
+    let guard_bump_seed = [guard_bump];
+    create_or_resize_pda(
+        program_id,
+        flash_mint_guard_info,
+        FlashMintGuard::LEN,
+        &get_flash_mint_guard_address_signer_seeds(wrapped_mint.key, &guard_bump_seed),
+    )?;
+    guard.pack_versioned(&mut flash_mint_guard_info.try_borrow_mut_data()?)?;
+
+    // Mint the loan
+
+    let bump_seed = [bump];
+    let signer_seeds = get_wrapped_mint_authority_signer_seeds(wrapped_mint.key, &bump_seed);
+    invoke_signed(
+        &spl_token_2022_interface::instruction::mint_to(
+            wrapped_token_program.key,
+            wrapped_mint.key,
+            recipient_wrapped_token_account.key,
+            wrapped_mint_authority.key,
+            &[],
+            amount,
+        )?,
+        &[
+            wrapped_mint.clone(),
+            recipient_wrapped_token_account.clone(),
+            wrapped_mint_authority.clone(),
+        ],
+        &[&signer_seeds],
+    )?;
+
+    msg!(
+        "Flash minted {} wrapped tokens to {}, repayable with a fee of {}",
+        amount,
+        recipient_wrapped_token_account.key,
+        fee
+    );
+    Ok(())
+}
+
+/// Processes [`FlashRepay`](enum.TokenWrapInstruction.html) instruction.
+pub fn process_flash_repay(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let wrapped_token_account = next_account_info(account_info_iter)?;
+    let wrapped_mint = next_account_info(account_info_iter)?;
+    let unwrapped_escrow = next_account_info(account_info_iter)?;
+    let flash_mint_guard_info = next_account_info(account_info_iter)?;
+    let wrapped_token_program = next_account_info(account_info_iter)?;
+    let transfer_authority = next_account_info(account_info_iter)?;
+    let additional_accounts = account_info_iter.as_slice();
+
+    // Validate accounts
+
+    if *flash_mint_guard_info.key != get_flash_mint_guard_address_with_seed(wrapped_mint.key).0 {
+        Err(TokenWrapError::FlashMintGuardMismatch)?
+    }
+    if flash_mint_guard_info.data_is_empty() {
+        Err(TokenWrapError::FlashMintNotActive)?
+    }
+    if flash_mint_guard_info.owner != program_id {
+        Err(ProgramError::InvalidAccountOwner)?
+    }
+
+    let guard = FlashMintGuard::unpack_versioned(&flash_mint_guard_info.try_borrow_data()?)?;
+    if !guard.is_active() {
+        Err(TokenWrapError::FlashMintNotActive)?
+    }
+    if *unwrapped_escrow.key != guard.unwrapped_escrow {
+        Err(TokenWrapError::EscrowMismatch)?
+    }
+    if wrapped_mint.owner != wrapped_token_program.key
+        || (*wrapped_token_program.key != spl_token::id()
+            && *wrapped_token_program.key != spl_token_2022_interface::id())
+    {
+        Err(ProgramError::IncorrectProgramId)?
+    }
+
+    // Burn the loan and its fee
+
+    let repay_amount = guard
+        .repay_amount()
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let multisig_signer_keys = extract_multisig_accounts(transfer_authority, additional_accounts)?
+        .iter()
+        .map(|a| a.key)
+        .collect::<Vec<_>>();
+
+    invoke(
+        &spl_token_2022_interface::instruction::burn(
+            wrapped_token_program.key,
+            wrapped_token_account.key,
+            wrapped_mint.key,
+            transfer_authority.key,
+            &multisig_signer_keys,
+            repay_amount,
+        )?,
+        accounts,
+    )?;
+
+    // The escrow must back the wrapped supply at least as well as before
+
+    let (escrow_balance, wrapped_supply) = read_escrow_backing(unwrapped_escrow, wrapped_mint)?;
+    if wrapped_supply.saturating_sub(escrow_balance) > u64::from(guard.backing_deficit) {
+        Err(TokenWrapError::EscrowBackingViolated)?
+    }
+
+    // Clear the loan, keeping the fee for later flash mints
+    FlashMintGuard {
+        fee_basis_points: guard.fee_basis_points,
+        ..FlashMintGuard::zeroed()
+    }
+    .pack_versioned(&mut flash_mint_guard_info.try_borrow_mut_data()?)?;
+
+    msg!(
+        "Flash mint of {} repaid with {} wrapped tokens",
+        wrapped_mint.key,
+        repay_amount
+    );
+    Ok(())
+}
+
+/// Processes [`SetFlashMintFee`](enum.TokenWrapInstruction.html) instruction.
+pub fn process_set_flash_mint_fee(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    fee_basis_points: u16,
+) -> ProgramResult {
+    if fee_basis_points > MAX_FEE_BASIS_POINTS {
+        Err(TokenWrapError::InvalidFeeBasisPoints)?
+    }
+
+    let account_info_iter = &mut accounts.iter();
+    let unwrapped_mint_authority_info = next_account_info(account_info_iter)?;
+    let flash_mint_guard_info = next_account_info(account_info_iter)?;
+    let unwrapped_mint_info = next_account_info(account_info_iter)?;
+    let wrapped_mint_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+
+    check_unwrapped_mint_authority(
+        unwrapped_mint_authority_info,
+        unwrapped_mint_info,
+        account_info_iter.as_slice(),
+    )?;
+
+    let expected_wrapped_mint =
+        get_wrapped_mint_address(unwrapped_mint_info.key, wrapped_mint_info.owner);
+    if *wrapped_mint_info.key != expected_wrapped_mint {
+        Err(TokenWrapError::WrappedMintMismatch)?
+    }
+
+    let (expected_guard_address, bump) =
+        get_flash_mint_guard_address_with_seed(wrapped_mint_info.key);
+    if *flash_mint_guard_info.key != expected_guard_address {
+        Err(TokenWrapError::FlashMintGuardMismatch)?
+    }
+    if !flash_mint_guard_info.data_is_empty() && flash_mint_guard_info.owner != program_id {
+        Err(ProgramError::InvalidAccountOwner)?
+    }
+
+    let mut guard = if flash_mint_guard_info.data_is_empty() {
+        FlashMintGuard::zeroed()
+    } else {
+        FlashMintGuard::unpack_versioned(&flash_mint_guard_info.try_borrow_data()?)?
+    };
+    guard.fee_basis_points = fee_basis_points.into();
+
+    let bump_seed = [bump];
+    create_or_resize_pda(
+        program_id,
+        flash_mint_guard_info,
+        FlashMintGuard::LEN,
+        &get_flash_mint_guard_address_signer_seeds(wrapped_mint_info.key, &bump_seed),
+    )?;
+
+    msg!(
+        "Flash mint fee of {} set to {} basis points",
+        wrapped_mint_info.key,
+        fee_basis_points
+    );
+    guard.pack_versioned(&mut flash_mint_guard_info.try_borrow_mut_data()?)
+}
+
+/// Processes [`ReconcileEscrow`](enum.TokenWrapInstruction.html) instruction.
+pub fn process_reconcile_escrow(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let escrow = next_account_info(account_info_iter)?;
+    let unwrapped_mint = next_account_info(account_info_iter)?;
+    let wrapped_mint = next_account_info(account_info_iter)?;
+    let wrapped_mint_authority = next_account_info(account_info_iter)?;
+    let policy_info = next_account_info(account_info_iter)?;
+    let destination = next_account_info(account_info_iter)?;
+    let unwrapped_token_program = next_account_info(account_info_iter)?;
+    let wrapped_token_program = next_account_info(account_info_iter)?;
+    let wrap_limits = next_account_info(account_info_iter)?;
+
+    // Validate accounts
+
+    let expected_wrapped_mint =
+        get_wrapped_mint_address(unwrapped_mint.key, wrapped_token_program.key);
+    if *wrapped_mint.key != expected_wrapped_mint {
+        Err(TokenWrapError::WrappedMintMismatch)?
+    }
+
+    let (expected_authority, bump) = get_wrapped_mint_authority_with_seed(wrapped_mint.key);
+    if *wrapped_mint_authority.key != expected_authority {
+        Err(TokenWrapError::MintAuthorityMismatch)?
+    }
+
+    let expected_escrow = get_associated_token_address_with_program_id(
+        wrapped_mint_authority.key,
+        unwrapped_mint.key,
+        unwrapped_token_program.key,
+    );
+    if *escrow.key != expected_escrow {
+        Err(TokenWrapError::EscrowMismatch)?
+    }
+
+    if *policy_info.key != get_escrow_surplus_policy_address_with_seed(wrapped_mint.key).0 {
+        Err(TokenWrapError::EscrowSurplusPolicyMismatch)?
+    }
+    if policy_info.owner != program_id {
+        Err(ProgramError::InvalidAccountOwner)?
+    }
+    let policy = EscrowSurplusPolicy::unpack_versioned(&policy_info.try_borrow_data()?)?;
+
+    let (escrow_balance, wrapped_supply) = read_escrow_backing(escrow, wrapped_mint)?;
+    let surplus = escrow_balance
+        .checked_sub(wrapped_supply)
+        .filter(|surplus| *surplus > 0)
+        .ok_or(TokenWrapError::NoEscrowSurplus)?;
+
+    // Apply the policy
+
+    let bump_seed = [bump];
+    let signer_seeds = get_wrapped_mint_authority_signer_seeds(wrapped_mint.key, &bump_seed);
+
+    match policy.action()? {
+        SurplusAction::MintToRecipient => {
+            if *destination.key != policy.target {
+                Err(TokenWrapError::SurplusRecipientMismatch)?
+            }
+
+            // Minting the surplus wraps it, so the wrap limits apply
+            enforce_wrap_limits(
+                program_id,
+                wrap_limits,
+                unwrapped_mint.key,
+                surplus,
+                Clock::get()?.epoch,
+            )?;
+
+            invoke_signed(
+                &spl_token_2022_interface::instruction::mint_to(
+                    wrapped_token_program.key,
+                    wrapped_mint.key,
+                    destination.key,
+                    wrapped_mint_authority.key,
+                    &[],
+                    surplus,
+                )?,
+                &[
+                    wrapped_mint.clone(),
+                    destination.clone(),
+                    wrapped_mint_authority.clone(),
+                ],
+                &[&signer_seeds],
+            )?;
+            msg!(
+                "Reconciled escrow surplus of {}: minted {} wrapped tokens to {}",
+                escrow.key,
+                surplus,
+                destination.key
+            );
+        }
+        SurplusAction::ReturnToClaimant => {
+            let claimant = next_account_info(account_info_iter)?;
+            if *claimant.key != policy.target {
+                Err(TokenWrapError::SurplusRecipientMismatch)?
+            }
+            if !claimant.is_signer {
+                Err(ProgramError::MissingRequiredSignature)?
+            }
+
+            let decimals = {
+                let unwrapped_mint_data = unwrapped_mint.try_borrow_data()?;
+                PodStateWithExtensions::<PodMint>::unpack(&unwrapped_mint_data)?
+                    .base
+                    .decimals
+            };
+            invoke_transfer_checked(
+                unwrapped_token_program.key,
+                escrow.clone(),
+                unwrapped_mint.clone(),
+                destination.clone(),
+                wrapped_mint_authority.clone(),
+                account_info_iter.as_slice(),
+                surplus,
+                decimals,
+                &[&signer_seeds],
+            )?;
+            msg!(
+                "Reconciled escrow surplus of {}: returned {} unwrapped tokens to {}",
+                escrow.key,
+                surplus,
+                destination.key
+            );
+        }
+    }
+
+    // The escrow must still back the whole wrapped supply
+
+    let (escrow_balance, wrapped_supply) = read_escrow_backing(escrow, wrapped_mint)?;
+    if escrow_balance < wrapped_supply {
+        Err(TokenWrapError::EscrowBackingViolated)?
+    }
+
+    Ok(())
+}
+
+type FieldExtractor = Vec<(Field, fn(&TokenMetadata) -> &str)>;
+
+fn update_fields_if_changed<'a>(
+    token_program: &Pubkey,
+    mint: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    fields: FieldExtractor,
+    wrapped_metadata: &TokenMetadata,
+    unwrapped_metadata: &TokenMetadata,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    for (field, extractor) in fields {
+        let current_value = extractor(wrapped_metadata);
+        let new_value = extractor(unwrapped_metadata);
+        if current_value != new_value {
+            invoke_signed(
+                &update_field(
+                    token_program,
+                    mint.key,
+                    authority.key,
+                    field.clone(),
+                    new_value.to_string(),
+                ),
+                &[mint.clone(), authority.clone()],
+                signer_seeds,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Processes [`SyncMetadataToToken2022`](enum.TokenWrapInstruction.html)
+/// instruction.
+pub fn process_sync_metadata_to_token_2022(accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let wrapped_mint_info = next_account_info(account_info_iter)?;
+    let wrapped_mint_authority_info = next_account_info(account_info_iter)?;
+    let unwrapped_mint_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let source_metadata_info = account_info_iter.next();
+    let owner_program_info = account_info_iter.next();
+
+    if *token_program_info.key != spl_token_2022_interface::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if *wrapped_mint_info.owner != spl_token_2022_interface::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let (expected_wrapped_mint, _) = get_wrapped_mint_address_with_seed(
+        unwrapped_mint_info.key,
+        &spl_token_2022_interface::id(),
+    );
+    if *wrapped_mint_info.key != expected_wrapped_mint {
+        return Err(TokenWrapError::WrappedMintMismatch.into());
+    }
+    let (expected_authority, authority_bump) =
+        get_wrapped_mint_authority_with_seed(wrapped_mint_info.key);
+    if *wrapped_mint_authority_info.key != expected_authority {
+        return Err(TokenWrapError::MintAuthorityMismatch.into());
+    }
+
+    let unwrapped_metadata = extract_token_metadata(
+        unwrapped_mint_info,
+        source_metadata_info,
+        owner_program_info,
+    )?;
+
+    let authority_bump_seed = [authority_bump];
+    let authority_signer_seeds =
+        get_wrapped_mint_authority_signer_seeds(wrapped_mint_info.key, &authority_bump_seed);
+
+    let wrapped_mint_data = wrapped_mint_info.try_borrow_data()?;
+    let wrapped_mint_metadata = PodStateWithExtensions::<PodMint>::unpack(&wrapped_mint_data)?
+        .get_variable_len_extension::<TokenMetadata>()
+        .ok();
+    drop(wrapped_mint_data);
 
     let cpi_accounts = [
         wrapped_mint_info.clone(),
         wrapped_mint_authority_info.clone(),
     ];
 
-    if let Some(wrapped_metadata) = wrapped_mint_metadata {
-        // The metadata extension is initialized. Update the fields that have changed.
-        // Note that mint and update authority cannot change as that is governed by
-        // token-wrap.
+    if let Some(wrapped_metadata) = wrapped_mint_metadata {
+        // The metadata extension is initialized. Update the fields that have changed.
+        // Note that mint and update authority cannot change as that is governed by
+        // token-wrap.
+
+        // --- Sync base fields ---
+        let base_fields: FieldExtractor = vec![
+            (Field::Name, |m| &m.name),
+            (Field::Symbol, |m| &m.symbol),
+            (Field::Uri, |m| &m.uri),
+        ];
+
+        update_fields_if_changed(
+            token_program_info.key,
+            wrapped_mint_info,
+            wrapped_mint_authority_info,
+            base_fields,
+            &wrapped_metadata,
+            &unwrapped_metadata,
+            &[&authority_signer_seeds],
+        )?;
+
+        // --- Sync additional metadata fields ---
+        let mut wrapped_meta_map: HashMap<String, String> =
+            wrapped_metadata.additional_metadata.into_iter().collect();
+
+        for (key, value) in &unwrapped_metadata.additional_metadata {
+            // Update if the key is not present or if the value is different
+            if wrapped_meta_map.get(key) != Some(value) {
+                invoke_signed(
+                    &update_field(
+                        token_program_info.key,
+                        wrapped_mint_info.key,
+                        wrapped_mint_authority_info.key,
+                        Field::Key(key.clone()),
+                        value.clone(),
+                    ),
+                    &cpi_accounts,
+                    &[&authority_signer_seeds],
+                )?;
+            }
+            // Remove the key from the map so we can track deletions
+            wrapped_meta_map.remove(key);
+        }
+
+        // Any keys remaining in the map no longer exist in the source, so remove them
+        for key in wrapped_meta_map.keys() {
+            invoke_signed(
+                &remove_key(
+                    token_program_info.key,
+                    wrapped_mint_info.key,
+                    wrapped_mint_authority_info.key,
+                    key.clone(),
+                    false,
+                ),
+                &cpi_accounts,
+                &[&authority_signer_seeds],
+            )?;
+        }
+    } else {
+        // The wrapped mint does not have the metadata extension. Initialize it with the
+        // fields from the unwrapped mint.
+        invoke_signed(
+            &initialize_token_metadata(
+                token_program_info.key,
+                wrapped_mint_info.key,
+                wrapped_mint_authority_info.key,
+                wrapped_mint_info.key,
+                wrapped_mint_authority_info.key,
+                unwrapped_metadata.name.clone(),
+                unwrapped_metadata.symbol.clone(),
+                unwrapped_metadata.uri.clone(),
+            ),
+            &cpi_accounts,
+            &[&authority_signer_seeds],
+        )?;
+
+        // After initializing, add the additional metadata fields.
+        for (key, value) in &unwrapped_metadata.additional_metadata {
+            invoke_signed(
+                &update_field(
+                    token_program_info.key,
+                    wrapped_mint_info.key,
+                    wrapped_mint_authority_info.key,
+                    Field::Key(key.clone()),
+                    value.clone(),
+                ),
+                &cpi_accounts,
+                &[&authority_signer_seeds],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Processes [`SyncMetadataToSplToken`](enum.TokenWrapInstruction.html)
+/// instruction.
+pub fn process_sync_metadata_to_spl_token(accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let metaplex_metadata_info = next_account_info(account_info_iter)?;
+    let wrapped_mint_authority_info = next_account_info(account_info_iter)?;
+    let wrapped_mint_info = next_account_info(account_info_iter)?;
+    let unwrapped_mint_info = next_account_info(account_info_iter)?;
+    let metaplex_program_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let rent_sysvar_info = next_account_info(account_info_iter)?;
+    let source_metadata_info = account_info_iter.next();
+    let owner_program_info = account_info_iter.next();
+
+    // ====== Validations ======
+
+    if *wrapped_mint_info.owner != spl_token::id() {
+        return Err(TokenWrapError::NoSyncingToToken2022.into());
+    }
+
+    let (expected_wrapped_mint, _) =
+        get_wrapped_mint_address_with_seed(unwrapped_mint_info.key, &spl_token::id());
+    if *wrapped_mint_info.key != expected_wrapped_mint {
+        return Err(TokenWrapError::WrappedMintMismatch.into());
+    }
+
+    let (expected_authority, authority_bump) =
+        get_wrapped_mint_authority_with_seed(wrapped_mint_info.key);
+    if *wrapped_mint_authority_info.key != expected_authority {
+        return Err(TokenWrapError::MintAuthorityMismatch.into());
+    }
+
+    let (expected_metaplex_metadata, _) = MetaplexMetadata::find_pda(wrapped_mint_info.key);
+    if *metaplex_metadata_info.key != expected_metaplex_metadata {
+        return Err(TokenWrapError::MetaplexMetadataMismatch.into());
+    }
+
+    if metaplex_program_info.key != &mpl_token_metadata::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // ====== Extract metadata from source ======
+
+    let unwrapped_metadata = extract_token_metadata(
+        unwrapped_mint_info,
+        source_metadata_info,
+        owner_program_info,
+    )?;
+
+    // ====== Upsert logic ======
+
+    let authority_bump_seed = [authority_bump];
+    let authority_signer_seeds =
+        get_wrapped_mint_authority_signer_seeds(wrapped_mint_info.key, &authority_bump_seed);
+
+    let new_metadata = token_2022_metadata_to_metaplex(&unwrapped_metadata)?;
+
+    // If the Metaplex metadata account is uninitialized, create a new one. The user
+    // is expected to have pre-funded the wrapped_mint_authority PDA. This program
+    // will use that PDA as the payer for the CPI.
+    if metaplex_metadata_info.data_is_empty() {
+        let create_ix = CreateMetadataAccountV3 {
+            metadata: *metaplex_metadata_info.key,
+            mint: *wrapped_mint_info.key,
+            mint_authority: *wrapped_mint_authority_info.key,
+            payer: *wrapped_mint_authority_info.key,
+            update_authority: (*wrapped_mint_authority_info.key, true),
+            system_program: *system_program_info.key,
+            rent: Some(*rent_sysvar_info.key),
+        }
+        .instruction(CreateMetadataAccountV3InstructionArgs {
+            data: new_metadata,
+            is_mutable: true,
+            collection_details: None,
+        });
+
+        invoke_signed(
+            &create_ix,
+            &[
+                metaplex_metadata_info.clone(),
+                wrapped_mint_info.clone(),
+                wrapped_mint_authority_info.clone(),
+                system_program_info.clone(),
+                rent_sysvar_info.clone(),
+            ],
+            &[&authority_signer_seeds],
+        )?;
+    } else {
+        // The Metaplex metadata account is initialized, so update the fields
+        let update_ix = UpdateMetadataAccountV2 {
+            metadata: *metaplex_metadata_info.key,
+            update_authority: *wrapped_mint_authority_info.key,
+        }
+        .instruction(UpdateMetadataAccountV2InstructionArgs {
+            data: Some(new_metadata),
+            // Cannot trust the additional metadata fields on token-2022 & external program case,
+            // so simplifying this and not updating it in any case
+            primary_sale_happened: None,
+            new_update_authority: None,
+            is_mutable: None,
+        });
+        invoke_signed(
+            &update_ix,
+            &[
+                metaplex_metadata_info.clone(),
+                wrapped_mint_authority_info.clone(),
+            ],
+            &[&authority_signer_seeds],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Resolves the update authority of the unwrapped mint's metadata, either a
+/// `TokenMetadata` or `Metaplex` update authority. Returns the accounts left
+/// over after the metadata accounts have been consumed.
+fn get_metadata_update_authority<'a, 'b>(
+    unwrapped_mint_info: &AccountInfo<'a>,
+    additional_accounts: &'b [AccountInfo<'a>],
+) -> Result<(Pubkey, &'b [AccountInfo<'a>]), ProgramError> {
+    let (source_metadata_info, remaining_accounts) = additional_accounts
+        .split_first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    // Metadata owned by a third-party program must be followed by that program
+    let (owner_program_info, remaining_accounts) = if source_metadata_info.owner
+        == &spl_token_2022_interface::id()
+        || source_metadata_info.owner == &mpl_token_metadata::ID
+    {
+        (None, remaining_accounts)
+    } else {
+        let (owner_program_info, remaining_accounts) = remaining_accounts
+            .split_first()
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        (Some(owner_program_info), remaining_accounts)
+    };
+
+    let token_metadata = extract_token_metadata(
+        unwrapped_mint_info,
+        Some(source_metadata_info),
+        owner_program_info,
+    )?;
+    let update_authority = Option::<Pubkey>::from(token_metadata.update_authority)
+        .ok_or(ProgramError::InvalidAccountData)
+        .inspect_err(|_| {
+            msg!("Cannot use metadata update authority for pointer if it is not set");
+        })?;
+
+    Ok((update_authority, remaining_accounts))
+}
+
+/// Validates that the pointer authority has signed, either directly or through
+/// the required number of signers of a token program multisig.
+fn check_pointer_authority_signature(
+    authority_info: &AccountInfo,
+    multisig_signer_infos: &[AccountInfo],
+) -> ProgramResult {
+    let owned_by_token_program = authority_info.owner == &spl_token::id()
+        || authority_info.owner == &spl_token_2022_interface::id();
+    if !owned_by_token_program || authority_info.data_len() != Multisig::LEN {
+        if !authority_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        return Ok(());
+    }
+
+    let multisig = Multisig::unpack(&authority_info.try_borrow_data()?)?;
+    let mut matched = [false; MAX_SIGNERS];
+    for signer_info in multisig_signer_infos {
+        for (position, key) in multisig.signers[..multisig.n as usize].iter().enumerate() {
+            if key == signer_info.key && !matched[position] {
+                if !signer_info.is_signer {
+                    return Err(ProgramError::MissingRequiredSignature);
+                }
+                matched[position] = true;
+            }
+        }
+    }
+    let num_signers = matched.iter().filter(|&&matched| matched).count();
+    if num_signers < usize::from(multisig.m) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    Ok(())
+}
+
+/// Validates that the authority may manage the settings of the unwrapped mint,
+/// such as its canonical pointer or escrow surplus policy.
+///
+/// The mint authority is used if set. If the mint authority has been revoked,
+/// the update authority of the mint's metadata is used instead.
+fn check_unwrapped_mint_authority<'a>(
+    authority_info: &AccountInfo<'a>,
+    unwrapped_mint_info: &AccountInfo<'a>,
+    additional_accounts: &[AccountInfo<'a>],
+) -> ProgramResult {
+    if unwrapped_mint_info.owner != &spl_token::id()
+        && unwrapped_mint_info.owner != &spl_token_2022_interface::id()
+    {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let mint_authority = {
+        let mint_data = unwrapped_mint_info.try_borrow_data()?;
+        let mint_state = PodStateWithExtensions::<PodMint>::unpack(&mint_data)?;
+        mint_state.base.mint_authority.ok_or(()).ok()
+    };
+
+    let (expected_authority, multisig_signer_infos) = match mint_authority {
+        Some(mint_authority) => (mint_authority, additional_accounts),
+        None => {
+            msg!("Unwrapped mint has no mint authority, using metadata update authority");
+            get_metadata_update_authority(unwrapped_mint_info, additional_accounts)?
+        }
+    };
+
+    if expected_authority != *authority_info.key {
+        return Err(ProgramError::IncorrectAuthority);
+    }
+
+    check_pointer_authority_signature(authority_info, multisig_signer_infos)
+}
+
+/// Validates that the authority signed and is the upgrade authority of the
+/// program, as recorded in its program data account
+fn check_upgrade_authority(
+    program_id: &Pubkey,
+    authority_info: &AccountInfo,
+    program_data_info: &AccountInfo,
+) -> ProgramResult {
+    if !authority_info.is_signer {
+        Err(ProgramError::MissingRequiredSignature)?
+    }
+    if *program_data_info.key != get_program_data_address(program_id) {
+        Err(TokenWrapError::ProgramDataMismatch)?
+    }
+    if program_data_info.owner != &bpf_loader_upgradeable::id() {
+        Err(ProgramError::InvalidAccountOwner)?
+    }
+
+    // The `ProgramData` state is the variant index 3, the last deployment slot
+    // and the optional upgrade authority
+    let upgrade_authority = {
+        let program_data = program_data_info.try_borrow_data()?;
+        let metadata = program_data
+            .get(..UpgradeableLoaderState::size_of_programdata_metadata())
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let (variant, rest) = metadata.split_at(4);
+        if variant != 3u32.to_le_bytes() {
+            Err(ProgramError::InvalidAccountData)?
+        }
+        match rest[8..].split_first() {
+            Some((1, authority)) => Some(Pubkey::new_from_array(authority.try_into().unwrap())),
+            _ => None,
+        }
+    };
+
+    if upgrade_authority != Some(*authority_info.key) {
+        msg!("Error: the program has no upgrade authority or it did not sign");
+        Err(ProgramError::IncorrectAuthority)?
+    }
+    Ok(())
+}
 
-        // --- Sync base fields ---
-        let base_fields: FieldExtractor = vec![
-            (Field::Name, |m| &m.name),
-            (Field::Symbol, |m| &m.symbol),
-            (Field::Uri, |m| &m.uri),
-        ];
+/// Validates the canonical pointer address for the unwrapped mint, optionally
+/// scoped to a wrapped token program
+fn check_canonical_pointer_address(
+    canonical_pointer_info: &AccountInfo,
+    unwrapped_mint: &Pubkey,
+    wrapped_token_program: Option<&Pubkey>,
+) -> Result<u8, ProgramError> {
+    let (expected_pointer_address, bump) = match wrapped_token_program {
+        Some(wrapped_token_program) => {
+            get_scoped_canonical_pointer_address_with_seed(unwrapped_mint, wrapped_token_program)
+        }
+        None => get_canonical_pointer_address_with_seed(unwrapped_mint),
+    };
+    if *canonical_pointer_info.key != expected_pointer_address {
+        msg!(
+            "Error: canonical pointer address {} does not match expected address {}",
+            canonical_pointer_info.key,
+            expected_pointer_address
+        );
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(bump)
+}
 
-        update_fields_if_changed(
-            token_program_info.key,
-            wrapped_mint_info,
-            wrapped_mint_authority_info,
-            base_fields,
-            &wrapped_metadata,
-            &unwrapped_metadata,
-            &[&authority_signer_seeds],
+/// Validates the pending canonical pointer address for the unwrapped mint,
+/// optionally scoped to a wrapped token program
+fn check_pending_canonical_pointer_address(
+    pending_pointer_info: &AccountInfo,
+    unwrapped_mint: &Pubkey,
+    wrapped_token_program: Option<&Pubkey>,
+) -> Result<u8, ProgramError> {
+    let (expected_pending_address, bump) = match wrapped_token_program {
+        Some(wrapped_token_program) => get_scoped_pending_canonical_pointer_address_with_seed(
+            unwrapped_mint,
+            wrapped_token_program,
+        ),
+        None => get_pending_canonical_pointer_address_with_seed(unwrapped_mint),
+    };
+    if *pending_pointer_info.key != expected_pending_address {
+        msg!(
+            "Error: pending canonical pointer address {} does not match expected address {}",
+            pending_pointer_info.key,
+            expected_pending_address
+        );
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(bump)
+}
+
+/// Reads the change delay of the unscoped canonical pointer, which also binds
+/// the scoped pointers of the mint. Zero if no pointer has been set.
+fn read_unscoped_change_delay(
+    program_id: &Pubkey,
+    canonical_pointer_info: &AccountInfo,
+    unwrapped_mint: &Pubkey,
+) -> Result<u64, ProgramError> {
+    check_canonical_pointer_address(canonical_pointer_info, unwrapped_mint, None)?;
+    Ok(read_canonical_pointer(program_id, canonical_pointer_info)?
+        .map(|pointer| u64::from(pointer.change_delay))
+        .unwrap_or(0))
+}
+
+/// Reads the current canonical pointer, if one has been set
+fn read_canonical_pointer(
+    program_id: &Pubkey,
+    canonical_pointer_info: &AccountInfo,
+) -> Result<Option<CanonicalDeploymentPointer>, ProgramError> {
+    if canonical_pointer_info.data_is_empty() {
+        return Ok(None);
+    }
+    if canonical_pointer_info.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    CanonicalDeploymentPointer::unpack(&canonical_pointer_info.try_borrow_data()?).map(Some)
+}
+
+/// Creates a PDA owned by this program with the given space, or grows it if it
+/// was created with an older, smaller layout. The account must already hold
+/// enough lamports to be rent-exempt.
+fn create_or_resize_pda(
+    program_id: &Pubkey,
+    account_info: &AccountInfo,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let rent_required = Rent::get()?.minimum_balance(space);
+    if account_info.lamports() < rent_required {
+        msg!(
+            "Error: {} requires pre-funding of {} lamports",
+            account_info.key,
+            rent_required
+        );
+        Err(ProgramError::AccountNotRentExempt)?
+    }
+
+    if account_info.data_is_empty() {
+        invoke_signed(
+            &allocate(account_info.key, space as u64),
+            core::slice::from_ref(account_info),
+            &[signer_seeds],
+        )?;
+        invoke_signed(
+            &assign(account_info.key, program_id),
+            core::slice::from_ref(account_info),
+            &[signer_seeds],
         )?;
+    } else if account_info.data_len() < space {
+        account_info.resize(space)?;
+    }
 
-        // --- Sync additional metadata fields ---
-        let mut wrapped_meta_map: HashMap<String, String> =
-            wrapped_metadata.additional_metadata.into_iter().collect();
+    Ok(())
+}
 
-        for (key, value) in &unwrapped_metadata.additional_metadata {
-            // Update if the key is not present or if the value is different
-            if wrapped_meta_map.get(key) != Some(value) {
-                invoke_signed(
-                    &update_field(
-                        token_program_info.key,
-                        wrapped_mint_info.key,
-                        wrapped_mint_authority_info.key,
-                        Field::Key(key.clone()),
-                        value.clone(),
-                    ),
-                    &cpi_accounts,
-                    &[&authority_signer_seeds],
-                )?;
-            }
-            // Remove the key from the map so we can track deletions
-            wrapped_meta_map.remove(key);
+/// Moves all lamports of a program-owned account to the destination and hands
+/// the account back to the system program, so it is garbage collected at the
+/// end of the transaction
+fn close_program_account(
+    account_info: &AccountInfo,
+    destination_info: &AccountInfo,
+) -> ProgramResult {
+    if account_info.key == destination_info.key {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let account_lamports = account_info.lamports();
+    **destination_info.try_borrow_mut_lamports()? = destination_info
+        .lamports()
+        .checked_add(account_lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **account_info.try_borrow_mut_lamports()? = 0;
+
+    account_info.try_borrow_mut_data()?.fill(0);
+    account_info.resize(0)?;
+    account_info.assign(&solana_system_interface::program::id());
+
+    Ok(())
+}
+
+/// Creates the canonical pointer if needed and writes the new state into it
+fn write_canonical_pointer(
+    program_id: &Pubkey,
+    canonical_pointer_info: &AccountInfo,
+    unwrapped_mint: &Pubkey,
+    wrapped_token_program: Option<&Pubkey>,
+    bump: u8,
+    pointer: &CanonicalDeploymentPointer,
+) -> ProgramResult {
+    let bump_seed = [bump];
+    let space = CanonicalDeploymentPointer::LEN;
+    match wrapped_token_program {
+        Some(wrapped_token_program) => create_or_resize_pda(
+            program_id,
+            canonical_pointer_info,
+            space,
+            &get_scoped_canonical_pointer_address_signer_seeds(
+                unwrapped_mint,
+                wrapped_token_program,
+                &bump_seed,
+            ),
+        )?,
+        None => create_or_resize_pda(
+            program_id,
+            canonical_pointer_info,
+            space,
+            &get_canonical_pointer_address_signer_seeds(unwrapped_mint, &bump_seed),
+        )?,
+    }
+
+    pointer.pack_versioned(&mut canonical_pointer_info.try_borrow_mut_data()?)
+}
+
+/// Processes [`SetCanonicalPointer`](enum.TokenWrapInstruction.html)
+/// instruction.
+pub fn process_set_canonical_pointer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_program_id: Pubkey,
+    enforce_on_wrap: bool,
+    change_delay: u64,
+    wrapped_token_program: Option<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pointer_authority_info = next_account_info(account_info_iter)?;
+    let canonical_pointer_info = next_account_info(account_info_iter)?;
+    let unwrapped_mint_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let unscoped_pointer_info = wrapped_token_program
+        .map(|_| next_account_info(account_info_iter))
+        .transpose()?;
+
+    check_unwrapped_mint_authority(
+        pointer_authority_info,
+        unwrapped_mint_info,
+        account_info_iter.as_slice(),
+    )?;
+
+    let bump = check_canonical_pointer_address(
+        canonical_pointer_info,
+        unwrapped_mint_info.key,
+        wrapped_token_program.as_ref(),
+    )?;
+
+    // A timelocked unscoped pointer also timelocks the scoped pointers
+    if let Some(unscoped_pointer_info) = unscoped_pointer_info {
+        if read_unscoped_change_delay(program_id, unscoped_pointer_info, unwrapped_mint_info.key)?
+            != 0
+        {
+            Err(TokenWrapError::CanonicalPointerTimelocked)?
         }
+    }
 
-        // Any keys remaining in the map no longer exist in the source, so remove them
-        for key in wrapped_meta_map.keys() {
-            invoke_signed(
-                &remove_key(
-                    token_program_info.key,
-                    wrapped_mint_info.key,
-                    wrapped_mint_authority_info.key,
-                    key.clone(),
-                    false,
-                ),
-                &cpi_accounts,
-                &[&authority_signer_seeds],
-            )?;
+    if read_canonical_pointer(program_id, canonical_pointer_info)?
+        .is_some_and(|pointer| pointer.is_timelocked())
+    {
+        Err(TokenWrapError::CanonicalPointerTimelocked)?
+    }
+
+    write_canonical_pointer(
+        program_id,
+        canonical_pointer_info,
+        unwrapped_mint_info.key,
+        wrapped_token_program.as_ref(),
+        bump,
+        &CanonicalDeploymentPointer {
+            program_id: new_program_id,
+            enforce_on_wrap: enforce_on_wrap.into(),
+            change_delay: change_delay.into(),
+        },
+    )
+}
+
+/// Processes [`CloseCanonicalPointer`](enum.TokenWrapInstruction.html)
+/// instruction.
+pub fn process_close_canonical_pointer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    wrapped_token_program: Option<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pointer_authority_info = next_account_info(account_info_iter)?;
+    let canonical_pointer_info = next_account_info(account_info_iter)?;
+    let unwrapped_mint_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let unscoped_pointer_info = wrapped_token_program
+        .map(|_| next_account_info(account_info_iter))
+        .transpose()?;
+
+    check_unwrapped_mint_authority(
+        pointer_authority_info,
+        unwrapped_mint_info,
+        account_info_iter.as_slice(),
+    )?;
+
+    check_canonical_pointer_address(
+        canonical_pointer_info,
+        unwrapped_mint_info.key,
+        wrapped_token_program.as_ref(),
+    )?;
+
+    if canonical_pointer_info.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    // A timelocked unscoped pointer also timelocks the scoped pointers
+    if let Some(unscoped_pointer_info) = unscoped_pointer_info {
+        if read_unscoped_change_delay(program_id, unscoped_pointer_info, unwrapped_mint_info.key)?
+            != 0
+        {
+            Err(TokenWrapError::CanonicalPointerTimelocked)?
         }
-    } else {
-        // The wrapped mint does not have the metadata extension. Initialize it with the
-        // fields from the unwrapped mint.
-        invoke_signed(
-            &initialize_token_metadata(
-                token_program_info.key,
-                wrapped_mint_info.key,
-                wrapped_mint_authority_info.key,
-                wrapped_mint_info.key,
-                wrapped_mint_authority_info.key,
-                unwrapped_metadata.name.clone(),
-                unwrapped_metadata.symbol.clone(),
-                unwrapped_metadata.uri.clone(),
-            ),
-            &cpi_accounts,
-            &[&authority_signer_seeds],
-        )?;
+    }
 
-        // After initializing, add the additional metadata fields.
-        for (key, value) in &unwrapped_metadata.additional_metadata {
-            invoke_signed(
-                &update_field(
-                    token_program_info.key,
-                    wrapped_mint_info.key,
-                    wrapped_mint_authority_info.key,
-                    Field::Key(key.clone()),
-                    value.clone(),
-                ),
-                &cpi_accounts,
-                &[&authority_signer_seeds],
-            )?;
+    if read_canonical_pointer(program_id, canonical_pointer_info)?
+        .is_some_and(|pointer| pointer.is_timelocked())
+    {
+        Err(TokenWrapError::CanonicalPointerTimelocked)?
+    }
+
+    close_program_account(canonical_pointer_info, destination_info)
+}
+
+/// Processes [`ProposeCanonicalPointer`](enum.TokenWrapInstruction.html)
+/// instruction.
+pub fn process_propose_canonical_pointer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_program_id: Pubkey,
+    enforce_on_wrap: bool,
+    change_delay: u64,
+    wrapped_token_program: Option<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pointer_authority_info = next_account_info(account_info_iter)?;
+    let canonical_pointer_info = next_account_info(account_info_iter)?;
+    let pending_pointer_info = next_account_info(account_info_iter)?;
+    let unwrapped_mint_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let unscoped_pointer_info = wrapped_token_program
+        .map(|_| next_account_info(account_info_iter))
+        .transpose()?;
+
+    check_unwrapped_mint_authority(
+        pointer_authority_info,
+        unwrapped_mint_info,
+        account_info_iter.as_slice(),
+    )?;
+
+    check_canonical_pointer_address(
+        canonical_pointer_info,
+        unwrapped_mint_info.key,
+        wrapped_token_program.as_ref(),
+    )?;
+    let bump = check_pending_canonical_pointer_address(
+        pending_pointer_info,
+        unwrapped_mint_info.key,
+        wrapped_token_program.as_ref(),
+    )?;
+
+    if !pending_pointer_info.data_is_empty() && pending_pointer_info.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    // Scoped pointers are held to at least the delay of the unscoped pointer
+    let unscoped_delay = match unscoped_pointer_info {
+        Some(unscoped_pointer_info) => {
+            read_unscoped_change_delay(program_id, unscoped_pointer_info, unwrapped_mint_info.key)?
         }
+        None => 0,
+    };
+    let current_delay = read_canonical_pointer(program_id, canonical_pointer_info)?
+        .map(|pointer| u64::from(pointer.change_delay))
+        .unwrap_or(0)
+        .max(unscoped_delay);
+    let change_delay = change_delay.max(unscoped_delay);
+
+    let clock = Clock::get()?;
+    let activation_timestamp = i64::try_from(current_delay)
+        .ok()
+        .and_then(|delay| clock.unix_timestamp.checked_add(delay))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let bump_seed = [bump];
+    let space = PendingCanonicalPointer::LEN;
+    match wrapped_token_program {
+        Some(wrapped_token_program) => create_or_resize_pda(
+            program_id,
+            pending_pointer_info,
+            space,
+            &get_scoped_pending_canonical_pointer_address_signer_seeds(
+                unwrapped_mint_info.key,
+                &wrapped_token_program,
+                &bump_seed,
+            ),
+        )?,
+        None => create_or_resize_pda(
+            program_id,
+            pending_pointer_info,
+            space,
+            &get_pending_canonical_pointer_address_signer_seeds(
+                unwrapped_mint_info.key,
+                &bump_seed,
+            ),
+        )?,
+    }
+
+    PendingCanonicalPointer {
+        pointer: CanonicalDeploymentPointer {
+            program_id: new_program_id,
+            enforce_on_wrap: enforce_on_wrap.into(),
+            change_delay: change_delay.into(),
+        },
+        proposed_slot: clock.slot.into(),
+        proposed_timestamp: clock.unix_timestamp.into(),
+        activation_timestamp: activation_timestamp.into(),
+    }
+    .pack_versioned(&mut pending_pointer_info.try_borrow_mut_data()?)?;
+
+    msg!(
+        "Canonical pointer change to {} can be accepted from {}",
+        new_program_id,
+        activation_timestamp
+    );
+
+    Ok(())
+}
+
+/// Processes [`AcceptCanonicalPointer`](enum.TokenWrapInstruction.html)
+/// instruction.
+pub fn process_accept_canonical_pointer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    wrapped_token_program: Option<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let canonical_pointer_info = next_account_info(account_info_iter)?;
+    let pending_pointer_info = next_account_info(account_info_iter)?;
+    let unwrapped_mint_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+
+    let bump = check_canonical_pointer_address(
+        canonical_pointer_info,
+        unwrapped_mint_info.key,
+        wrapped_token_program.as_ref(),
+    )?;
+    check_pending_canonical_pointer_address(
+        pending_pointer_info,
+        unwrapped_mint_info.key,
+        wrapped_token_program.as_ref(),
+    )?;
+
+    if pending_pointer_info.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let pending =
+        PendingCanonicalPointer::unpack_versioned(&pending_pointer_info.try_borrow_data()?)?;
+
+    let current_pointer = read_canonical_pointer(program_id, canonical_pointer_info)?;
+    if !pending.is_active(current_pointer.as_ref(), Clock::get()?.unix_timestamp) {
+        Err(TokenWrapError::PendingCanonicalPointerNotActive)?
     }
 
-    Ok(())
+    // The pending account's rent funds the pointer account
+    close_program_account(pending_pointer_info, canonical_pointer_info)?;
+
+    write_canonical_pointer(
+        program_id,
+        canonical_pointer_info,
+        unwrapped_mint_info.key,
+        wrapped_token_program.as_ref(),
+        bump,
+        &pending.pointer,
+    )
 }
 
-/// Processes [`SyncMetadataToSplToken`](enum.TokenWrapInstruction.html)
+/// Processes [`CancelCanonicalPointer`](enum.TokenWrapInstruction.html)
 /// instruction.
-pub fn process_sync_metadata_to_spl_token(accounts: &[AccountInfo]) -> ProgramResult {
+pub fn process_cancel_canonical_pointer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    wrapped_token_program: Option<Pubkey>,
+) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
-    let metaplex_metadata_info = next_account_info(account_info_iter)?;
-    let wrapped_mint_authority_info = next_account_info(account_info_iter)?;
-    let wrapped_mint_info = next_account_info(account_info_iter)?;
+    let pointer_authority_info = next_account_info(account_info_iter)?;
+    let pending_pointer_info = next_account_info(account_info_iter)?;
     let unwrapped_mint_info = next_account_info(account_info_iter)?;
-    let metaplex_program_info = next_account_info(account_info_iter)?;
-    let system_program_info = next_account_info(account_info_iter)?;
-    let rent_sysvar_info = next_account_info(account_info_iter)?;
-    let source_metadata_info = account_info_iter.next();
-    let owner_program_info = account_info_iter.next();
+    let destination_info = next_account_info(account_info_iter)?;
 
-    // ====== Validations ======
+    check_unwrapped_mint_authority(
+        pointer_authority_info,
+        unwrapped_mint_info,
+        account_info_iter.as_slice(),
+    )?;
 
-    if *wrapped_mint_info.owner != spl_token::id() {
-        return Err(TokenWrapError::NoSyncingToToken2022.into());
-    }
+    check_pending_canonical_pointer_address(
+        pending_pointer_info,
+        unwrapped_mint_info.key,
+        wrapped_token_program.as_ref(),
+    )?;
 
-    let (expected_wrapped_mint, _) =
-        get_wrapped_mint_address_with_seed(unwrapped_mint_info.key, &spl_token::id());
-    if *wrapped_mint_info.key != expected_wrapped_mint {
-        return Err(TokenWrapError::WrappedMintMismatch.into());
+    if pending_pointer_info.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner);
     }
 
-    let (expected_authority, authority_bump) =
-        get_wrapped_mint_authority_with_seed(wrapped_mint_info.key);
-    if *wrapped_mint_authority_info.key != expected_authority {
-        return Err(TokenWrapError::MintAuthorityMismatch.into());
-    }
+    close_program_account(pending_pointer_info, destination_info)
+}
 
-    let (expected_metaplex_metadata, _) = MetaplexMetadata::find_pda(wrapped_mint_info.key);
-    if *metaplex_metadata_info.key != expected_metaplex_metadata {
-        return Err(TokenWrapError::MetaplexMetadataMismatch.into());
+/// Rewrites a program-owned state account into the current layout. The
+/// account must already hold enough lamports for the larger size.
+fn migrate_state_account<T: VersionedState>(
+    program_id: &Pubkey,
+    account_info: &AccountInfo,
+    unpack: impl FnOnce(&[u8]) -> Result<T, ProgramError>,
+) -> ProgramResult {
+    let state = unpack(&account_info.try_borrow_data()?)?;
+    if account_info.data_len() == T::LEN {
+        msg!("Account already uses the current layout");
+        return Ok(());
     }
 
-    if metaplex_program_info.key != &mpl_token_metadata::ID {
-        return Err(ProgramError::IncorrectProgramId);
+    create_or_resize_pda(program_id, account_info, T::LEN, &[])?;
+    state.pack_versioned(&mut account_info.try_borrow_mut_data()?)
+}
+
+/// Reads a backpointer and fills in the provenance data that can be recovered
+/// from the mints. The customizer, creation slot and program version are
+/// unknown and left zeroed.
+fn backfill_backpointer(
+    data: &[u8],
+    wrapped_mint_info: &AccountInfo,
+    unwrapped_mint_info: &AccountInfo,
+) -> Result<Backpointer, ProgramError> {
+    let backpointer = Backpointer::unpack(data)?;
+    if backpointer.has_provenance() {
+        return Ok(backpointer);
     }
 
-    // ====== Extract metadata from source ======
+    if *unwrapped_mint_info.key != backpointer.unwrapped_mint {
+        Err(TokenWrapError::UnwrappedMintMismatch)?
+    }
+    if unwrapped_mint_info.owner != &spl_token::id()
+        && unwrapped_mint_info.owner != &spl_token_2022_interface::id()
+    {
+        Err(ProgramError::InvalidAccountOwner)?
+    }
 
-    let unwrapped_metadata = extract_token_metadata(
-        unwrapped_mint_info,
-        source_metadata_info,
-        owner_program_info,
-    )?;
+    // Deriving with the owner of the wrapped mint also validates that owner
+    let (wrapped_mint_address, wrapped_mint_bump) =
+        get_wrapped_mint_address_with_seed(unwrapped_mint_info.key, wrapped_mint_info.owner);
+    if *wrapped_mint_info.key != wrapped_mint_address {
+        Err(TokenWrapError::WrappedMintMismatch)?
+    }
 
-    // ====== Upsert logic ======
+    let (_, wrapped_mint_authority_bump) =
+        get_wrapped_mint_authority_with_seed(wrapped_mint_info.key);
+    let (_, backpointer_bump) =
+        get_wrapped_mint_backpointer_address_with_seed(wrapped_mint_info.key);
 
-    let authority_bump_seed = [authority_bump];
-    let authority_signer_seeds =
-        get_wrapped_mint_authority_signer_seeds(wrapped_mint_info.key, &authority_bump_seed);
+    Ok(Backpointer {
+        unwrapped_token_program: *unwrapped_mint_info.owner,
+        wrapped_token_program: *wrapped_mint_info.owner,
+        wrapped_mint_bump,
+        wrapped_mint_authority_bump,
+        backpointer_bump,
+        ..backpointer
+    })
+}
 
-    let new_metadata = token_2022_metadata_to_metaplex(&unwrapped_metadata)?;
+/// Processes [`MigrateState`](enum.TokenWrapInstruction.html) instruction.
+pub fn process_migrate_state(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let state_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let unwrapped_mint_info = next_account_info(account_info_iter).ok();
 
-    // If the Metaplex metadata account is uninitialized, create a new one. The user
-    // is expected to have pre-funded the wrapped_mint_authority PDA. This program
-    // will use that PDA as the payer for the CPI.
-    if metaplex_metadata_info.data_is_empty() {
-        let create_ix = CreateMetadataAccountV3 {
-            metadata: *metaplex_metadata_info.key,
-            mint: *wrapped_mint_info.key,
-            mint_authority: *wrapped_mint_authority_info.key,
-            payer: *wrapped_mint_authority_info.key,
-            update_authority: (*wrapped_mint_authority_info.key, true),
-            system_program: *system_program_info.key,
-            rent: Some(*rent_sysvar_info.key),
-        }
-        .instruction(CreateMetadataAccountV3InstructionArgs {
-            data: new_metadata,
-            is_mutable: true,
-            collection_details: None,
-        });
+    if state_info.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
 
-        invoke_signed(
-            &create_ix,
-            &[
-                metaplex_metadata_info.clone(),
-                wrapped_mint_info.clone(),
-                wrapped_mint_authority_info.clone(),
-                system_program_info.clone(),
-                rent_sysvar_info.clone(),
-            ],
-            &[&authority_signer_seeds],
-        )?;
+    // The type of a legacy account cannot be told from its data, so it is
+    // determined from the address derivation
+    let is_address = |(address, _): (Pubkey, u8)| address == *state_info.key;
+    if is_address(get_wrapped_mint_backpointer_address_with_seed(
+        mint_info.key,
+    )) {
+        let unwrapped_mint_info = unwrapped_mint_info.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        migrate_state_account(program_id, state_info, |data| {
+            backfill_backpointer(data, mint_info, unwrapped_mint_info)
+        })
+    } else if is_address(get_canonical_pointer_address_with_seed(mint_info.key)) {
+        migrate_state_account(program_id, state_info, CanonicalDeploymentPointer::unpack)
     } else {
-        // The Metaplex metadata account is initialized, so update the fields
-        let update_ix = UpdateMetadataAccountV2 {
-            metadata: *metaplex_metadata_info.key,
-            update_authority: *wrapped_mint_authority_info.key,
-        }
-        .instruction(UpdateMetadataAccountV2InstructionArgs {
-            data: Some(new_metadata),
-            // Cannot trust the additional metadata fields on token-2022 & external program case,
-            // so simplifying this and not updating it in any case
-            primary_sale_happened: None,
-            new_update_authority: None,
-            is_mutable: None,
-        });
-        invoke_signed(
-            &update_ix,
-            &[
-                metaplex_metadata_info.clone(),
-                wrapped_mint_authority_info.clone(),
-            ],
-            &[&authority_signer_seeds],
-        )?;
+        msg!(
+            "Error: {} is not a state account derived from {}",
+            state_info.key,
+            mint_info.key
+        );
+        Err(ProgramError::InvalidArgument)
     }
-
-    Ok(())
 }
 
-/// Processes [`SetCanonicalPointer`](enum.TokenWrapInstruction.html)
-/// instruction.
-pub fn process_set_canonical_pointer(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    new_program_id: Pubkey,
-) -> ProgramResult {
+/// Processes [`CloseWrappedMint`](enum.TokenWrapInstruction.html) instruction.
+pub fn process_close_wrapped_mint(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
-    let unwrapped_mint_authority_info = next_account_info(account_info_iter)?;
-    let canonical_pointer_info = next_account_info(account_info_iter)?;
-    let unwrapped_mint_info = next_account_info(account_info_iter)?;
-    let _system_program_info = next_account_info(account_info_iter)?;
 
-    if !unwrapped_mint_authority_info.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
-    }
+    let wrapped_mint = next_account_info(account_info_iter)?;
+    let backpointer_info = next_account_info(account_info_iter)?;
+    let destination = next_account_info(account_info_iter)?;
+    let unwrapped_mint = next_account_info(account_info_iter)?;
+    let escrow = next_account_info(account_info_iter)?;
+    let wrapped_mint_authority = next_account_info(account_info_iter)?;
+    let unwrapped_token_program = next_account_info(account_info_iter)?;
+    let wrapped_token_program = next_account_info(account_info_iter)?;
 
-    if unwrapped_mint_info.owner != &spl_token::id()
-        && unwrapped_mint_info.owner != &spl_token_2022_interface::id()
+    // Only Token-2022 mints can have a close authority
+    if *wrapped_mint.owner != spl_token_2022_interface::id()
+        || *wrapped_token_program.key != spl_token_2022_interface::id()
     {
-        return Err(ProgramError::InvalidAccountOwner);
+        Err(TokenWrapError::WrappedMintNotClosable)?
     }
 
-    let mint_data = unwrapped_mint_info.try_borrow_data()?;
-    let mint_state = PodStateWithExtensions::<PodMint>::unpack(&mint_data)?;
-    let mint_authority = mint_state
-        .base
-        .mint_authority
-        .ok_or(ProgramError::InvalidAccountData)
-        .inspect_err(|_| {
-            msg!("Cannot create/update pointer for unwrapped mint if does not have an authority");
-        })?;
+    // Validate the backpointer and the mints it links
 
-    if mint_authority != *unwrapped_mint_authority_info.key {
-        return Err(ProgramError::IncorrectAuthority);
+    let (expected_backpointer, _) =
+        get_wrapped_mint_backpointer_address_with_seed(wrapped_mint.key);
+    if *backpointer_info.key != expected_backpointer {
+        Err(TokenWrapError::BackpointerMismatch)?
+    }
+    if backpointer_info.owner != program_id {
+        Err(TokenWrapError::InvalidBackpointerOwner)?
+    }
+    let backpointer = Backpointer::unpack(&backpointer_info.try_borrow_data()?)?;
+    if backpointer.unwrapped_mint != *unwrapped_mint.key {
+        Err(TokenWrapError::UnwrappedMintMismatch)?
     }
 
-    let (expected_pointer_address, bump) =
-        get_canonical_pointer_address_with_seed(unwrapped_mint_info.key);
-    if *canonical_pointer_info.key != expected_pointer_address {
-        msg!(
-            "Error: canonical pointer address {} does not match expected address {}",
-            canonical_pointer_info.key,
-            expected_pointer_address
-        );
-        return Err(ProgramError::InvalidArgument);
+    let expected_wrapped_mint = get_wrapped_mint_address(unwrapped_mint.key, wrapped_mint.owner);
+    if *wrapped_mint.key != expected_wrapped_mint {
+        Err(TokenWrapError::WrappedMintMismatch)?
     }
 
-    // If pointer does not exist, initialize it
-    if canonical_pointer_info.data_is_empty() {
-        let space = mem::size_of::<CanonicalDeploymentPointer>();
-        let rent_required = Rent::get()?.minimum_balance(space);
+    let (expected_authority, bump) = get_wrapped_mint_authority_with_seed(wrapped_mint.key);
+    if *wrapped_mint_authority.key != expected_authority {
+        Err(TokenWrapError::MintAuthorityMismatch)?
+    }
 
-        if canonical_pointer_info.lamports() < rent_required {
-            msg!(
-                "Error: canonical pointer PDA requires pre-funding of {} lamports",
-                rent_required
-            );
-            Err(ProgramError::AccountNotRentExempt)?
+    // The unwrapped mint must be retired, otherwise anyone could keep closing
+    // wrapped mints that are still in use before their first wrap
+    if !unwrapped_mint.data_is_empty() {
+        Err(TokenWrapError::UnwrappedMintNotClosed)?
+    }
+
+    // Validate the escrow is empty or closed
+
+    if backpointer.has_provenance()
+        && backpointer.unwrapped_token_program != *unwrapped_token_program.key
+    {
+        Err(ProgramError::IncorrectProgramId)?
+    }
+    let expected_escrow = get_associated_token_address_with_program_id(
+        wrapped_mint_authority.key,
+        unwrapped_mint.key,
+        unwrapped_token_program.key,
+    );
+    if *escrow.key != expected_escrow {
+        Err(TokenWrapError::EscrowMismatch)?
+    }
+    if !escrow.data_is_empty() {
+        if escrow.owner != unwrapped_token_program.key {
+            Err(ProgramError::IncorrectProgramId)?
+        }
+        let escrow_data = escrow.try_borrow_data()?;
+        let escrow_state = PodStateWithExtensions::<PodAccount>::unpack(&escrow_data)?;
+        if u64::from(escrow_state.base.amount) != 0 {
+            Err(TokenWrapError::EscrowNotEmpty)?
         }
+    }
 
-        let bump_seed = [bump];
-        let signer_seeds =
-            get_canonical_pointer_address_signer_seeds(unwrapped_mint_info.key, &bump_seed);
-        invoke_signed(
-            &allocate(canonical_pointer_info.key, space as u64),
-            core::slice::from_ref(canonical_pointer_info),
-            &[&signer_seeds],
-        )?;
-        invoke_signed(
-            &assign(canonical_pointer_info.key, program_id),
-            core::slice::from_ref(canonical_pointer_info),
-            &[&signer_seeds],
-        )?;
+    // Validate the wrapped mint can be closed
+
+    {
+        let wrapped_mint_data = wrapped_mint.try_borrow_data()?;
+        let wrapped_mint_state = PodStateWithExtensions::<PodMint>::unpack(&wrapped_mint_data)?;
+        if u64::from(wrapped_mint_state.base.supply) != 0 {
+            Err(TokenWrapError::WrappedMintHasSupply)?
+        }
+        let close_authority = wrapped_mint_state
+            .get_extension::<MintCloseAuthority>()
+            .ok()
+            .and_then(|extension| Option::<Pubkey>::from(extension.close_authority));
+        if close_authority != Some(expected_authority) {
+            Err(TokenWrapError::WrappedMintNotClosable)?
+        }
     }
 
-    // Set data within canonical pointer PDA
+    // Close the wrapped mint and the backpointer
 
-    let mut pointer_data = canonical_pointer_info.try_borrow_mut_data()?;
-    let state = bytemuck::from_bytes_mut::<CanonicalDeploymentPointer>(&mut pointer_data);
-    state.program_id = new_program_id;
+    let bump_seed = [bump];
+    let signer_seeds = get_wrapped_mint_authority_signer_seeds(wrapped_mint.key, &bump_seed);
+    invoke_signed(
+        &close_account(
+            wrapped_token_program.key,
+            wrapped_mint.key,
+            destination.key,
+            wrapped_mint_authority.key,
+            &[],
+        )?,
+        &[
+            wrapped_mint.clone(),
+            destination.clone(),
+            wrapped_mint_authority.clone(),
+        ],
+        &[&signer_seeds],
+    )?;
 
-    Ok(())
+    close_program_account(backpointer_info, destination)
 }
 
 /// Instruction processor
@@ -891,19 +4533,34 @@ pub fn process_instruction(
     input: &[u8],
 ) -> ProgramResult {
     match TokenWrapInstruction::unpack(input)? {
-        TokenWrapInstruction::CreateMint { idempotent } => {
+        TokenWrapInstruction::CreateMint {
+            idempotent,
+            acknowledge_risky_extensions,
+        } => {
             // === DEVELOPER CUSTOMIZATION POINT ===
             // To use custom mint creation logic, update the mint customizer argument
             msg!("Instruction: CreateMint");
-            process_create_mint::<DefaultToken2022Customizer>(program_id, accounts, idempotent)
+            process_create_mint::<DefaultToken2022Customizer>(
+                program_id,
+                accounts,
+                idempotent,
+                acknowledge_risky_extensions,
+            )
         }
-        TokenWrapInstruction::Wrap { amount } => {
+        TokenWrapInstruction::Wrap {
+            amount,
+            record_stats,
+        } => {
             msg!("Instruction: Wrap");
-            process_wrap(accounts, amount)
+            process_wrap(program_id, accounts, amount, record_stats)
         }
-        TokenWrapInstruction::Unwrap { amount } => {
+        TokenWrapInstruction::Unwrap {
+            amount,
+            record_stats,
+            pro_rata,
+        } => {
             msg!("Instruction: Unwrap");
-            process_unwrap(accounts, amount)
+            process_unwrap(program_id, accounts, amount, record_stats, pro_rata)
         }
         TokenWrapInstruction::CloseStuckEscrow => {
             msg!("Instruction: CloseStuckEscrow");
@@ -919,9 +4576,206 @@ pub fn process_instruction(
         }
         TokenWrapInstruction::SetCanonicalPointer {
             program_id: new_program_id,
+            enforce_on_wrap,
+            change_delay,
+            wrapped_token_program,
         } => {
             msg!("Instruction: SetCanonicalPointer");
-            process_set_canonical_pointer(program_id, accounts, new_program_id)
+            process_set_canonical_pointer(
+                program_id,
+                accounts,
+                new_program_id,
+                enforce_on_wrap,
+                change_delay,
+                wrapped_token_program,
+            )
+        }
+        TokenWrapInstruction::CloseCanonicalPointer {
+            wrapped_token_program,
+        } => {
+            msg!("Instruction: CloseCanonicalPointer");
+            process_close_canonical_pointer(program_id, accounts, wrapped_token_program)
+        }
+        TokenWrapInstruction::ProposeCanonicalPointer {
+            program_id: new_program_id,
+            enforce_on_wrap,
+            change_delay,
+            wrapped_token_program,
+        } => {
+            msg!("Instruction: ProposeCanonicalPointer");
+            process_propose_canonical_pointer(
+                program_id,
+                accounts,
+                new_program_id,
+                enforce_on_wrap,
+                change_delay,
+                wrapped_token_program,
+            )
+        }
+        TokenWrapInstruction::AcceptCanonicalPointer {
+            wrapped_token_program,
+        } => {
+            msg!("Instruction: AcceptCanonicalPointer");
+            process_accept_canonical_pointer(program_id, accounts, wrapped_token_program)
+        }
+        TokenWrapInstruction::CancelCanonicalPointer {
+            wrapped_token_program,
+        } => {
+            msg!("Instruction: CancelCanonicalPointer");
+            process_cancel_canonical_pointer(program_id, accounts, wrapped_token_program)
+        }
+        TokenWrapInstruction::MigrateState => {
+            msg!("Instruction: MigrateState");
+            process_migrate_state(program_id, accounts)
+        }
+        TokenWrapInstruction::CloseWrappedMint => {
+            msg!("Instruction: CloseWrappedMint");
+            process_close_wrapped_mint(program_id, accounts)
+        }
+        TokenWrapInstruction::RepairEscrow => {
+            msg!("Instruction: RepairEscrow");
+            process_repair_escrow(accounts)
+        }
+        TokenWrapInstruction::SetEscrowSurplusPolicy { action, target } => {
+            msg!("Instruction: SetEscrowSurplusPolicy");
+            process_set_escrow_surplus_policy(program_id, accounts, action, target)
+        }
+        TokenWrapInstruction::ReconcileEscrow => {
+            msg!("Instruction: ReconcileEscrow");
+            process_reconcile_escrow(program_id, accounts)
+        }
+        TokenWrapInstruction::WrapSol { amount } => {
+            msg!("Instruction: WrapSol");
+            process_wrap_sol(program_id, accounts, amount)
+        }
+        TokenWrapInstruction::UnwrapSol { amount, pro_rata } => {
+            msg!("Instruction: UnwrapSol");
+            process_unwrap_sol(program_id, accounts, amount, pro_rata)
+        }
+        TokenWrapInstruction::WrapConfidential { amount } => {
+            msg!("Instruction: WrapConfidential");
+            process_wrap_confidential(program_id, accounts, amount)
+        }
+        TokenWrapInstruction::UnwrapConfidential {
+            amount,
+            new_decryptable_available_balance,
+        } => {
+            msg!("Instruction: UnwrapConfidential");
+            process_unwrap_confidential(
+                program_id,
+                accounts,
+                amount,
+                &new_decryptable_available_balance,
+            )
+        }
+        TokenWrapInstruction::UpdateConfidentialConfig {
+            auto_approve_new_accounts,
+            auditor_elgamal_pubkey,
+        } => {
+            msg!("Instruction: UpdateConfidentialConfig");
+            process_update_confidential_config(
+                accounts,
+                auto_approve_new_accounts,
+                auditor_elgamal_pubkey,
+            )
+        }
+        TokenWrapInstruction::SetWrappedMintAdmin { admin } => {
+            msg!("Instruction: SetWrappedMintAdmin");
+            process_set_wrapped_mint_admin(program_id, accounts, admin)
+        }
+        TokenWrapInstruction::ExecuteAdminAction { action } => {
+            msg!("Instruction: ExecuteAdminAction");
+            process_execute_admin_action(program_id, accounts, action)
+        }
+        TokenWrapInstruction::SetWrapLimits {
+            unwrap_only,
+            max_wrapped_supply,
+            max_wrapped_per_epoch,
+        } => {
+            msg!("Instruction: SetWrapLimits");
+            process_set_wrap_limits(
+                program_id,
+                accounts,
+                unwrap_only,
+                max_wrapped_supply,
+                max_wrapped_per_epoch,
+            )
+        }
+        TokenWrapInstruction::SetAllowlistMode { enabled } => {
+            msg!("Instruction: SetAllowlistMode");
+            process_set_allowlist_mode(program_id, accounts, enabled)
+        }
+        TokenWrapInstruction::UpdateAllowlist { allowed, wallets } => {
+            msg!("Instruction: UpdateAllowlist");
+            process_update_allowlist(program_id, accounts, allowed, &wallets)
+        }
+        TokenWrapInstruction::SetProtocolFeeConfig {
+            wrap_fee_basis_points,
+            unwrap_fee_basis_points,
+            fee_destination,
+        } => {
+            msg!("Instruction: SetProtocolFeeConfig");
+            process_set_protocol_fee_config(
+                program_id,
+                accounts,
+                wrap_fee_basis_points,
+                unwrap_fee_basis_points,
+                fee_destination,
+            )
+        }
+        TokenWrapInstruction::SetProtocolFeeOverride {
+            enabled,
+            wrap_fee_basis_points,
+            unwrap_fee_basis_points,
+        } => {
+            msg!("Instruction: SetProtocolFeeOverride");
+            process_set_protocol_fee_override(
+                program_id,
+                accounts,
+                enabled,
+                wrap_fee_basis_points,
+                unwrap_fee_basis_points,
+            )
+        }
+        TokenWrapInstruction::FlashMint { amount } => {
+            msg!("Instruction: FlashMint");
+            process_flash_mint(program_id, accounts, amount)
+        }
+        TokenWrapInstruction::FlashRepay => {
+            msg!("Instruction: FlashRepay");
+            process_flash_repay(program_id, accounts)
+        }
+        TokenWrapInstruction::SetFlashMintFee { fee_basis_points } => {
+            msg!("Instruction: SetFlashMintFee");
+            process_set_flash_mint_fee(program_id, accounts, fee_basis_points)
+        }
+        TokenWrapInstruction::WrapWithPermit {
+            amount,
+            nonce,
+            expiry,
+        } => {
+            msg!("Instruction: WrapWithPermit");
+            process_wrap_with_permit(program_id, accounts, amount, nonce, expiry)
+        }
+        TokenWrapInstruction::UnwrapWithPermit {
+            amount,
+            nonce,
+            expiry,
+        } => {
+            msg!("Instruction: UnwrapWithPermit");
+            process_unwrap_with_permit(program_id, accounts, amount, nonce, expiry)
+        }
+        TokenWrapInstruction::SetCrankWrapRecipient { recipient } => {
+            msg!("Instruction: SetCrankWrapRecipient");
+            process_set_crank_wrap_recipient(program_id, accounts, recipient)
+        }
+        TokenWrapInstruction::CrankWrap => {
+            msg!("Instruction: CrankWrap");
+            process_crank_wrap(program_id, accounts)
+        }
+        TokenWrapInstruction::RegisterWrappedMint => {
+            msg!("Instruction: RegisterWrappedMint");
+            process_register_wrapped_mint(program_id, accounts)
         }
     }
 }
@@ -1,10 +1,171 @@
 //! Program state
 
 use {
+    crate::mint_customizer::interface::CUSTOMIZER_ID_LEN,
     bytemuck::{Pod, Zeroable},
+    solana_program_error::ProgramError,
     solana_pubkey::Pubkey,
+    solana_zero_copy::unaligned::{Bool, I64, U128, U16, U64},
+    std::mem::size_of,
 };
 
+/// Identifies the type of state held by a program-owned account
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AccountDiscriminator {
+    /// [`Backpointer`]
+    Backpointer = 1,
+    /// [`CanonicalDeploymentPointer`]
+    CanonicalDeploymentPointer = 2,
+    /// [`PendingCanonicalPointer`]
+    PendingCanonicalPointer = 3,
+    /// [`WrappedMintRegistry`]
+    WrappedMintRegistry = 4,
+    /// Page of [`WrappedMintRegistryEntry`]
+    WrappedMintRegistryPage = 5,
+    /// List of [`WrappedMintIndexEntry`]
+    WrappedMintIndex = 6,
+    /// [`WrapStats`]
+    WrapStats = 7,
+    /// [`ProRataRedemption`]
+    ProRataRedemption = 8,
+    /// [`EscrowSurplusPolicy`]
+    EscrowSurplusPolicy = 9,
+    /// [`WrappedMintAdmin`]
+    WrappedMintAdmin = 10,
+    /// [`WrapLimits`]
+    WrapLimits = 11,
+    /// [`Allowlist`]
+    Allowlist = 12,
+    /// [`AllowlistEntry`]
+    AllowlistEntry = 13,
+    /// [`ProtocolFeeConfig`]
+    ProtocolFeeConfig = 14,
+    /// [`ProtocolFeeOverride`]
+    ProtocolFeeOverride = 15,
+    /// [`FlashMintGuard`]
+    FlashMintGuard = 16,
+    /// [`PermitNonce`]
+    PermitNonce = 17,
+    /// [`CrankWrapDelegate`]
+    CrankWrapDelegate = 18,
+}
+
+/// Header at the start of every program-owned state account.
+///
+/// Accounts created before the header was introduced hold the bare state.
+/// Readers accept these legacy layouts, and `MigrateState` rewrites them into
+/// the current one.
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct AccountHeader {
+    /// Type of the state following the header, see [`AccountDiscriminator`]
+    pub discriminator: u8,
+    /// Layout version of the state following the header
+    pub version: u8,
+}
+
+impl AccountHeader {
+    /// Size of the header
+    pub const LEN: usize = size_of::<Self>();
+}
+
+/// State stored in a program-owned account behind an [`AccountHeader`]
+pub trait VersionedState: Pod {
+    /// Type of the state
+    const DISCRIMINATOR: AccountDiscriminator;
+    /// Current layout version of the state
+    const VERSION: u8;
+    /// Size of an account holding the state, including the header
+    const LEN: usize = AccountHeader::LEN + size_of::<Self>();
+
+    /// Header written in front of the state
+    fn header() -> AccountHeader {
+        AccountHeader {
+            discriminator: Self::DISCRIMINATOR as u8,
+            version: Self::VERSION,
+        }
+    }
+
+    /// Reads the state from account data in the current layout, failing if
+    /// the header does not match
+    fn unpack_versioned(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let (header, state) = data.split_at(AccountHeader::LEN);
+        if bytemuck::bytes_of(&Self::header()) != header {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        bytemuck::try_from_bytes::<Self>(state)
+            .copied()
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Writes the header and the state into account data, which must be
+    /// exactly [`VersionedState::LEN`] bytes long
+    fn pack_versioned(&self, data: &mut [u8]) -> Result<(), ProgramError> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let (header, state) = data.split_at_mut(AccountHeader::LEN);
+        header.copy_from_slice(bytemuck::bytes_of(&Self::header()));
+        state.copy_from_slice(bytemuck::bytes_of(self));
+        Ok(())
+    }
+}
+
+/// Entry of a list stored in a program-owned account behind an
+/// [`AccountHeader`]. The account grows by one entry every time an entry is
+/// appended.
+pub trait VersionedListEntry: Pod {
+    /// Type of the list
+    const DISCRIMINATOR: AccountDiscriminator;
+    /// Current layout version of the list
+    const VERSION: u8;
+
+    /// Header written in front of the entries
+    fn header() -> AccountHeader {
+        AccountHeader {
+            discriminator: Self::DISCRIMINATOR as u8,
+            version: Self::VERSION,
+        }
+    }
+
+    /// Size of an account holding `count` entries, including the header
+    fn list_len(count: usize) -> usize {
+        AccountHeader::LEN.saturating_add(size_of::<Self>().saturating_mul(count))
+    }
+
+    /// Reads the entries from account data, failing if the header does not
+    /// match
+    fn unpack_list(data: &[u8]) -> Result<&[Self], ProgramError> {
+        if data.len() < AccountHeader::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let (header, entries) = data.split_at(AccountHeader::LEN);
+        if bytemuck::bytes_of(&Self::header()) != header {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        bytemuck::try_cast_slice(entries).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Writes the header and the entry at `index` into account data, which
+    /// must be exactly large enough to hold `index + 1` entries
+    fn pack_list_entry(&self, data: &mut [u8], index: usize) -> Result<(), ProgramError> {
+        if data.len() != Self::list_len(index.saturating_add(1)) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let offset = index
+            .checked_mul(size_of::<Self>())
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let (header, entries) = data.split_at_mut(AccountHeader::LEN);
+        header.copy_from_slice(bytemuck::bytes_of(&Self::header()));
+        entries[offset..].copy_from_slice(bytemuck::bytes_of(self));
+        Ok(())
+    }
+}
+
 /// Backpointer
 ///
 /// Since the backpointer account address is derived from the wrapped mint, it
@@ -12,15 +173,76 @@ use {
 ///
 /// Try to fetch the account at `get_wrapped_mint_backpointer_address`.
 ///  * if it doesn't exist, then the token is not wrapped
-///  * if it exists, read the data in the account as the unwrapped mint address
+///  * if it exists, read the account with [`Backpointer::unpack`] to get the
+///    unwrapped mint address
 ///
 /// With this info, clients can easily unwrap tokens, even if they don't know
 /// the origin.
+///
+/// The versioned layout also records how the wrapped mint was created.
+/// Backpointers in the original layout only store the unwrapped mint until
+/// they are backfilled with `MigrateState`, see [`Backpointer::has_provenance`].
 #[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
-#[repr(transparent)]
+#[repr(C)]
 pub struct Backpointer {
     /// Address that the wrapped mint is wrapping
     pub unwrapped_mint: Pubkey,
+    /// Token program that owns the unwrapped mint
+    pub unwrapped_token_program: Pubkey,
+    /// Token program that owns the wrapped mint
+    pub wrapped_token_program: Pubkey,
+    /// `MintCustomizer::ID` of the customizer that created the wrapped mint,
+    /// zeroed if the backpointer was backfilled
+    pub customizer_id: [u8; CUSTOMIZER_ID_LEN],
+    /// Slot in which the wrapped mint was created, zero if the backpointer
+    /// was backfilled
+    pub creation_slot: U64,
+    /// Bump seed of the wrapped mint address
+    pub wrapped_mint_bump: u8,
+    /// Bump seed of the wrapped mint authority address
+    pub wrapped_mint_authority_bump: u8,
+    /// Bump seed of the backpointer address
+    pub backpointer_bump: u8,
+    /// [`PROGRAM_VERSION`](crate::PROGRAM_VERSION) that created the wrapped
+    /// mint, zeroed if the backpointer was backfilled
+    pub program_version: [u8; 3],
+}
+
+impl VersionedState for Backpointer {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::Backpointer;
+    const VERSION: u8 = 1;
+}
+
+impl Backpointer {
+    /// Size of the original layout, which had no header
+    pub const LEGACY_LEN: usize = size_of::<Pubkey>();
+
+    /// Creates a backpointer without provenance data, as stored by the
+    /// original layout
+    pub fn without_provenance(unwrapped_mint: Pubkey) -> Self {
+        Self {
+            unwrapped_mint,
+            ..Self::zeroed()
+        }
+    }
+
+    /// Returns true if the backpointer records how the wrapped mint was
+    /// created, which is the case for every backpointer in the current layout
+    pub fn has_provenance(&self) -> bool {
+        self.wrapped_token_program != Pubkey::default()
+    }
+
+    /// Reads a backpointer from account data, accepting the original layout,
+    /// which is returned without provenance data
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEGACY_LEN {
+            return Self::unpack_versioned(data);
+        }
+        Ok(Self::without_provenance(Pubkey::new_from_array(
+            data.try_into()
+                .map_err(|_| ProgramError::InvalidAccountData)?,
+        )))
+    }
 }
 
 /// An on-chain pointer to a canonical token-wrap program deployment.
@@ -29,10 +251,689 @@ pub struct Backpointer {
 /// deployment of the token-wrap program is the "official" one for their mint.
 /// This guides users and apps especially when custom forks of the
 /// program exist.
+///
+/// If `enforce_on_wrap` is set and the pointer designates another deployment,
+/// `Wrap` is rejected in this deployment. `Unwrap` is never restricted so
+/// funds can always leave.
+///
+/// If `change_delay` is non-zero, the pointer can no longer be updated or
+/// closed directly. Changes must be proposed and can only be accepted once the
+/// delay has passed, see [`PendingCanonicalPointer`].
+///
+/// A missing (or closed) pointer means no deployment has been designated, so
+/// every deployment is treated as canonical.
 #[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
 #[repr(C)]
 pub struct CanonicalDeploymentPointer {
     /// The program ID of the canonical token-wrap deployment as determined by
     /// the unwrapped mint authority.
     pub program_id: Pubkey,
+    /// Whether non-canonical deployments must reject `Wrap`
+    pub enforce_on_wrap: Bool,
+    /// Minimum number of seconds between proposing and accepting a change
+    pub change_delay: U64,
+}
+
+impl CanonicalDeploymentPointer {
+    /// Size of the original pointer layout, which only stored the program id
+    pub const LEGACY_LEN: usize = 32;
+
+    /// Reads a pointer from account data, accepting the current layout as well
+    /// as the original layout. Legacy pointers are never enforced and have no
+    /// change delay.
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        match data.len() {
+            Self::LEGACY_LEN => Ok(Self {
+                program_id: Pubkey::new_from_array(
+                    data.try_into()
+                        .map_err(|_| ProgramError::InvalidAccountData)?,
+                ),
+                enforce_on_wrap: Bool(0),
+                change_delay: U64::from(0),
+            }),
+            _ => Self::unpack_versioned(data),
+        }
+    }
+
+    /// Returns true if `Wrap` must be rejected in the given deployment
+    pub fn blocks_wrap_in(&self, program_id: &Pubkey) -> bool {
+        bool::from(self.enforce_on_wrap) && self.program_id != *program_id
+    }
+
+    /// Returns true if changes must go through a timelocked proposal
+    pub fn is_timelocked(&self) -> bool {
+        u64::from(self.change_delay) != 0
+    }
+}
+
+impl VersionedState for CanonicalDeploymentPointer {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::CanonicalDeploymentPointer;
+    const VERSION: u8 = 1;
+}
+
+/// A proposed change to a [`CanonicalDeploymentPointer`].
+///
+/// Created by `ProposeCanonicalPointer` at
+/// `get_pending_canonical_pointer_address`. Clients should surface the
+/// pending pointer to users so they are warned before the switch. Once
+/// `activation_timestamp` has passed, anyone can apply it with
+/// `AcceptCanonicalPointer`. Until then, the pointer authority can withdraw it
+/// with `CancelCanonicalPointer`.
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct PendingCanonicalPointer {
+    /// The pointer that will replace the current one when accepted
+    pub pointer: CanonicalDeploymentPointer,
+    /// Slot in which the change was proposed
+    pub proposed_slot: U64,
+    /// Unix timestamp at which the change was proposed
+    pub proposed_timestamp: I64,
+    /// Unix timestamp from which the change can be accepted
+    pub activation_timestamp: I64,
+}
+
+impl VersionedState for PendingCanonicalPointer {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::PendingCanonicalPointer;
+    const VERSION: u8 = 1;
+}
+
+impl PendingCanonicalPointer {
+    /// Returns true if the change can be accepted at `unix_timestamp`.
+    ///
+    /// The delay of the current pointer is re-checked, so raising the delay
+    /// after a proposal was made also postpones that proposal.
+    pub fn is_active(
+        &self,
+        current_pointer: Option<&CanonicalDeploymentPointer>,
+        unix_timestamp: i64,
+    ) -> bool {
+        let current_delay = current_pointer
+            .map(|pointer| u64::from(pointer.change_delay))
+            .unwrap_or(0);
+        let earliest = i64::try_from(current_delay)
+            .ok()
+            .and_then(|delay| i64::from(self.proposed_timestamp).checked_add(delay))
+            .unwrap_or(i64::MAX)
+            .max(i64::from(self.activation_timestamp));
+        unix_timestamp >= earliest
+    }
+}
+
+/// Registry of every wrapped mint created by the program.
+///
+/// `CreateMint` appends a [`WrappedMintRegistryEntry`] for each new wrapped
+/// mint, and `RegisterWrappedMint` for each wrapped mint created before the
+/// registry. Entries are stored in pages of
+/// [`WrappedMintRegistry::ENTRIES_PER_PAGE`] at
+/// `get_wrapped_mint_registry_page_address(page_index)`, so clients can list
+/// every wrapped mint without scanning program accounts. The registry is
+/// append-only, entries are never removed.
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct WrappedMintRegistry {
+    /// Number of wrapped mints in the registry
+    pub entry_count: U64,
+}
+
+impl VersionedState for WrappedMintRegistry {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::WrappedMintRegistry;
+    const VERSION: u8 = 1;
+}
+
+impl WrappedMintRegistry {
+    /// Maximum number of entries stored in a single page
+    pub const ENTRIES_PER_PAGE: u64 = 100;
+
+    /// Number of pages holding at least one entry
+    pub fn page_count(&self) -> u64 {
+        u64::from(self.entry_count).div_ceil(Self::ENTRIES_PER_PAGE)
+    }
+
+    /// Index of the page the next entry is appended to
+    pub fn next_page_index(&self) -> u64 {
+        u64::from(self.entry_count) / Self::ENTRIES_PER_PAGE
+    }
+}
+
+/// A wrapped mint in a page of the [`WrappedMintRegistry`]
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct WrappedMintRegistryEntry {
+    /// Address that the wrapped mint is wrapping
+    pub unwrapped_mint: Pubkey,
+    /// Address of the wrapped mint
+    pub wrapped_mint: Pubkey,
+    /// Token program that owns the wrapped mint
+    pub wrapped_token_program: Pubkey,
+}
+
+impl VersionedListEntry for WrappedMintRegistryEntry {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::WrappedMintRegistryPage;
+    const VERSION: u8 = 1;
+}
+
+/// A wrapped variant of an unwrapped mint, stored at
+/// `get_wrapped_mint_index_address(unwrapped_mint)`. `CreateMint` and
+/// `RegisterWrappedMint` append an entry for each wrapped mint they record.
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct WrappedMintIndexEntry {
+    /// Address of the wrapped mint
+    pub wrapped_mint: Pubkey,
+    /// Token program that owns the wrapped mint
+    pub wrapped_token_program: Pubkey,
+}
+
+impl VersionedListEntry for WrappedMintIndexEntry {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::WrappedMintIndex;
+    const VERSION: u8 = 1;
+}
+
+/// Cumulative statistics of a wrapped mint, stored at
+/// `get_wrap_stats_address(wrapped_mint)`.
+///
+/// Recording statistics is opt-in for every `Wrap` and `Unwrap`, so callers
+/// that want to avoid the extra write lock can skip it. The totals only cover
+/// the operations that opted in. The account is created by the first operation
+/// recording statistics.
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct WrapStats {
+    /// Total amount of wrapped tokens minted by `Wrap`
+    pub total_wrapped: U128,
+    /// Total amount of wrapped tokens burned by `Unwrap`
+    pub total_unwrapped: U128,
+    /// Total transfer fees withheld from unwrapped tokens on `Wrap`
+    pub total_wrap_fees: U128,
+    /// Number of recorded `Wrap` operations
+    pub wrap_count: U64,
+    /// Number of recorded `Unwrap` operations
+    pub unwrap_count: U64,
+    /// Slot of the last recorded operation
+    pub last_activity_slot: U64,
+}
+
+impl VersionedState for WrapStats {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::WrapStats;
+    const VERSION: u8 = 1;
+}
+
+impl WrapStats {
+    /// Records a `Wrap` that minted `wrapped_amount` after withholding `fee`.
+    /// Returns `None` on overflow.
+    pub fn record_wrap(&mut self, wrapped_amount: u64, fee: u64, slot: u64) -> Option<()> {
+        self.total_wrapped = u128::from(self.total_wrapped)
+            .checked_add(wrapped_amount.into())?
+            .into();
+        self.total_wrap_fees = u128::from(self.total_wrap_fees)
+            .checked_add(fee.into())?
+            .into();
+        self.wrap_count = u64::from(self.wrap_count).checked_add(1)?.into();
+        self.last_activity_slot = slot.into();
+        Some(())
+    }
+
+    /// Records an `Unwrap` that burned `unwrapped_amount`. Returns `None` on
+    /// overflow.
+    pub fn record_unwrap(&mut self, unwrapped_amount: u64, slot: u64) -> Option<()> {
+        self.total_unwrapped = u128::from(self.total_unwrapped)
+            .checked_add(unwrapped_amount.into())?
+            .into();
+        self.unwrap_count = u64::from(self.unwrap_count).checked_add(1)?.into();
+        self.last_activity_slot = slot.into();
+        Some(())
+    }
+}
+
+/// Record of the pro-rata redemptions of a wrapped mint, stored at
+/// `get_pro_rata_redemption_address(wrapped_mint)`.
+///
+/// If the escrow holds fewer unwrapped tokens than the wrapped supply, e.g.
+/// after a permanent delegate moved escrowed tokens, `Unwrap` pays out
+/// `amount * escrow_balance / wrapped_supply` so that every holder shares the
+/// shortfall. Each of these `Unwrap` operations must update this account. It
+/// is created by the first one, which must pre-fund its rent, and stays as a
+/// record if the escrow is later re-collateralized.
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct ProRataRedemption {
+    /// Total amount of wrapped tokens burned by pro-rata `Unwrap` operations
+    pub total_burned: U128,
+    /// Total amount of unwrapped tokens paid out by pro-rata `Unwrap`
+    /// operations
+    pub total_paid: U128,
+    /// Number of pro-rata `Unwrap` operations
+    pub redemption_count: U64,
+    /// Slot of the first pro-rata `Unwrap`
+    pub first_redemption_slot: U64,
+    /// Slot of the last pro-rata `Unwrap`
+    pub last_redemption_slot: U64,
+    /// Escrow balance before the last pro-rata `Unwrap`
+    pub last_escrow_balance: U64,
+    /// Wrapped supply before the last pro-rata `Unwrap`
+    pub last_wrapped_supply: U64,
+}
+
+impl VersionedState for ProRataRedemption {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::ProRataRedemption;
+    const VERSION: u8 = 1;
+}
+
+impl ProRataRedemption {
+    /// Returns the unwrapped tokens paid for burning `amount` wrapped tokens
+    /// while the escrow holds `escrow_balance` for a supply of
+    /// `wrapped_supply`, rounded down. Returns `None` if the supply is zero.
+    pub fn redemption_amount(amount: u64, escrow_balance: u64, wrapped_supply: u64) -> Option<u64> {
+        u128::from(amount)
+            .checked_mul(escrow_balance.into())?
+            .checked_div(wrapped_supply.into())?
+            .try_into()
+            .ok()
+    }
+
+    /// Records a pro-rata `Unwrap` that burned `burned` wrapped tokens and
+    /// paid `paid` unwrapped tokens. Returns `None` on overflow.
+    pub fn record(
+        &mut self,
+        burned: u64,
+        paid: u64,
+        escrow_balance: u64,
+        wrapped_supply: u64,
+        slot: u64,
+    ) -> Option<()> {
+        self.total_burned = u128::from(self.total_burned)
+            .checked_add(burned.into())?
+            .into();
+        self.total_paid = u128::from(self.total_paid).checked_add(paid.into())?.into();
+        if u64::from(self.redemption_count) == 0 {
+            self.first_redemption_slot = slot.into();
+        }
+        self.redemption_count = u64::from(self.redemption_count).checked_add(1)?.into();
+        self.last_redemption_slot = slot.into();
+        self.last_escrow_balance = escrow_balance.into();
+        self.last_wrapped_supply = wrapped_supply.into();
+        Some(())
+    }
+}
+
+/// What `ReconcileEscrow` does with the surplus of an escrow
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SurplusAction {
+    /// Mint the surplus as wrapped tokens to the recipient token account
+    MintToRecipient = 1,
+    /// Transfer the surplus unwrapped tokens to a token account chosen by the
+    /// claimant authority
+    ReturnToClaimant = 2,
+}
+
+impl TryFrom<u8> for SurplusAction {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::MintToRecipient),
+            2 => Ok(Self::ReturnToClaimant),
+            _ => Err(ProgramError::InvalidArgument),
+        }
+    }
+}
+
+/// Policy for the surplus of the escrow of a wrapped mint, stored at
+/// `get_escrow_surplus_policy_address(wrapped_mint)`.
+///
+/// Unwrapped tokens sent straight to the escrow are not backed by wrapped
+/// supply. The unwrapped mint authority sets this policy, and
+/// `ReconcileEscrow` applies it to the surplus `escrow_balance -
+/// wrapped_supply`.
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct EscrowSurplusPolicy {
+    /// [`SurplusAction`] applied to the surplus
+    pub action: u8,
+    /// Wrapped token account receiving minted surplus for
+    /// [`SurplusAction::MintToRecipient`], or the authority that must sign
+    /// for [`SurplusAction::ReturnToClaimant`]
+    pub target: Pubkey,
+}
+
+impl VersionedState for EscrowSurplusPolicy {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::EscrowSurplusPolicy;
+    const VERSION: u8 = 1;
+}
+
+impl EscrowSurplusPolicy {
+    /// Returns the action applied to the surplus
+    pub fn action(&self) -> Result<SurplusAction, ProgramError> {
+        SurplusAction::try_from(self.action).map_err(|_| ProgramError::InvalidAccountData)
+    }
+}
+
+/// Admin of a wrapped mint, stored at `get_wrapped_mint_admin_address(wrapped_mint)`.
+///
+/// The unwrapped mint authority may always run administrative actions on the
+/// wrapped mint with `ExecuteAdminAction`. It can appoint this admin to run
+/// them as well.
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct WrappedMintAdmin {
+    /// The appointed admin, or the default pubkey if none is appointed
+    pub admin: Pubkey,
+}
+
+impl VersionedState for WrappedMintAdmin {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::WrappedMintAdmin;
+    const VERSION: u8 = 1;
+}
+
+impl WrappedMintAdmin {
+    /// Returns the appointed admin, if any
+    pub fn admin(&self) -> Option<Pubkey> {
+        (self.admin != Pubkey::default()).then_some(self.admin)
+    }
+}
+
+/// Limits on wrapping an unwrapped mint, stored at
+/// `get_wrap_limits_address(unwrapped_mint)`.
+///
+/// The unwrapped mint authority sets the limits with `SetWrapLimits`, and every
+/// `Wrap` of the unwrapped mint enforces them. `Unwrap` is never limited, so
+/// holders can always exit. A missing account means no limits.
+///
+/// The account tracks the total amount wrapped from the unwrapped mint across
+/// all of its wrapped mints: wraps add the minted amount and unwraps subtract
+/// the burned amount. Only operations passing this account are tracked, so the
+/// total starts at zero when the account is created.
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct WrapLimits {
+    /// Maximum of `total_wrapped` after minting wrapped tokens, or 0 for no
+    /// cap. The cap applies to all wrapped mints of the unwrapped mint
+    /// together.
+    pub max_wrapped_supply: U64,
+    /// Maximum amount of wrapped tokens minted by `Wrap` per epoch, or 0 for
+    /// no cap
+    pub max_wrapped_per_epoch: U64,
+    /// Whether `Wrap` is rejected entirely, leaving only `Unwrap`
+    pub unwrap_only: Bool,
+    /// Epoch of `wrapped_in_epoch`
+    pub epoch: U64,
+    /// Amount of wrapped tokens minted by `Wrap` in `epoch`
+    pub wrapped_in_epoch: U64,
+    /// Total amount of wrapped tokens outstanding across all wrapped mints of
+    /// the unwrapped mint. `SetWrapLimits` sets it to their supply, and tokens
+    /// burned other than by unwrapping stay counted until the limits are set
+    /// again.
+    pub total_wrapped: U64,
+}
+
+impl VersionedState for WrapLimits {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::WrapLimits;
+    const VERSION: u8 = 1;
+}
+
+impl WrapLimits {
+    /// Returns true if `Wrap` is rejected entirely
+    pub fn is_unwrap_only(&self) -> bool {
+        bool::from(self.unwrap_only)
+    }
+
+    /// Returns true if the total amount wrapped may grow to `new_total`
+    pub fn allows_total_wrapped(&self, new_total: u64) -> bool {
+        let cap = u64::from(self.max_wrapped_supply);
+        cap == 0 || new_total <= cap
+    }
+
+    /// Returns true if the amount wrapped per epoch is capped
+    pub fn has_epoch_cap(&self) -> bool {
+        u64::from(self.max_wrapped_per_epoch) != 0
+    }
+
+    /// Records a `Wrap` minting `amount` in `epoch`, starting a new count when
+    /// the epoch changed. Returns `None` if the per-epoch cap would be
+    /// exceeded.
+    pub fn record_wrap(&mut self, amount: u64, epoch: u64) -> Option<()> {
+        let wrapped_in_epoch = if u64::from(self.epoch) == epoch {
+            u64::from(self.wrapped_in_epoch)
+        } else {
+            0
+        };
+        let wrapped_in_epoch = wrapped_in_epoch.checked_add(amount)?;
+        if self.has_epoch_cap() && wrapped_in_epoch > u64::from(self.max_wrapped_per_epoch) {
+            return None;
+        }
+        let total_wrapped = u64::from(self.total_wrapped).checked_add(amount)?;
+        self.epoch = epoch.into();
+        self.wrapped_in_epoch = wrapped_in_epoch.into();
+        self.total_wrapped = total_wrapped.into();
+        Some(())
+    }
+
+    /// Records an `Unwrap` burning `amount`. The total stops at zero rather than
+    /// failing the unwrap.
+    pub fn record_unwrap(&mut self, amount: u64) {
+        self.total_wrapped = u64::from(self.total_wrapped).saturating_sub(amount).into();
+    }
+}
+
+/// Allowlist mode of an unwrapped mint, stored at
+/// `get_allowlist_address(unwrapped_mint)`.
+///
+/// The unwrapped mint authority enables the mode with `SetAllowlistMode` and
+/// manages the allowed wallets with `UpdateAllowlist`. While it is enabled,
+/// `Wrap` requires the owner of the recipient, and `Unwrap` the owner of the
+/// unwrapped wrapped token account, to be allowed by an [`AllowlistEntry`]. A
+/// missing account means the mode is disabled.
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct Allowlist {
+    /// Whether wrapping and unwrapping are restricted to allowed wallets
+    pub enabled: Bool,
+}
+
+impl VersionedState for Allowlist {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::Allowlist;
+    const VERSION: u8 = 1;
+}
+
+impl Allowlist {
+    /// Returns true if wrapping and unwrapping are restricted to allowed
+    /// wallets
+    pub fn is_enabled(&self) -> bool {
+        bool::from(self.enabled)
+    }
+}
+
+/// Entry of a wallet in the allowlist of an unwrapped mint, stored at
+/// `get_allowlist_entry_address(unwrapped_mint, wallet)`.
+///
+/// Removing a wallet keeps its entry, so it can be allowed again without
+/// funding its rent.
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct AllowlistEntry {
+    /// Whether the wallet is allowed
+    pub allowed: Bool,
+}
+
+impl VersionedState for AllowlistEntry {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::AllowlistEntry;
+    const VERSION: u8 = 1;
+}
+
+impl AllowlistEntry {
+    /// Returns true if the wallet is allowed
+    pub fn is_allowed(&self) -> bool {
+        bool::from(self.allowed)
+    }
+}
+
+/// Upper bound of a protocol fee, charging the whole amount
+pub const MAX_FEE_BASIS_POINTS: u16 = 10_000;
+
+/// Protocol fee charged by the deployment on wraps and unwraps, stored at
+/// `get_protocol_fee_config_address()`.
+///
+/// The program upgrade authority sets the fee with `SetProtocolFeeConfig`.
+/// `Wrap` and `Unwrap` route the fee portion of the unwrapped tokens from the
+/// escrow to a fee vault, a token account of the unwrapped mint owned by the
+/// fee destination. A missing account means no fee.
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct ProtocolFeeConfig {
+    /// Owner of the fee vaults
+    pub fee_destination: Pubkey,
+    /// Fee charged by `Wrap`, in basis points of the unwrapped tokens
+    /// deposited
+    pub wrap_fee_basis_points: U16,
+    /// Fee charged by `Unwrap`, in basis points of the unwrapped tokens
+    /// released
+    pub unwrap_fee_basis_points: U16,
+}
+
+impl VersionedState for ProtocolFeeConfig {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::ProtocolFeeConfig;
+    const VERSION: u8 = 1;
+}
+
+/// Protocol fee of an unwrapped mint replacing the one of the
+/// [`ProtocolFeeConfig`], stored at
+/// `get_protocol_fee_override_address(unwrapped_mint)`.
+///
+/// The program upgrade authority sets it with `SetProtocolFeeOverride`. A
+/// missing or disabled account means the fee of the config applies.
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct ProtocolFeeOverride {
+    /// Whether the override replaces the fee of the config
+    pub enabled: Bool,
+    /// Fee charged by `Wrap`, in basis points
+    pub wrap_fee_basis_points: U16,
+    /// Fee charged by `Unwrap`, in basis points
+    pub unwrap_fee_basis_points: U16,
+}
+
+impl VersionedState for ProtocolFeeOverride {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::ProtocolFeeOverride;
+    const VERSION: u8 = 1;
+}
+
+impl ProtocolFeeOverride {
+    /// Returns true if the override replaces the fee of the config
+    pub fn is_enabled(&self) -> bool {
+        bool::from(self.enabled)
+    }
+}
+
+/// Split of the unwrapped tokens of a `Wrap` or `Unwrap`, set as the return
+/// data of the instruction
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct ProtocolFeeSplit {
+    /// Unwrapped tokens routed to the fee vault
+    pub protocol_fee: U64,
+    /// Wrapped tokens minted by `Wrap`, or unwrapped tokens released to the
+    /// recipient by `Unwrap`
+    pub amount: U64,
+}
+
+impl ProtocolFeeSplit {
+    /// Splits `amount` unwrapped tokens into a protocol fee of `basis_points`,
+    /// rounded down, and the rest
+    pub fn new(amount: u64, basis_points: u16) -> Option<Self> {
+        let protocol_fee = u128::from(amount)
+            .checked_mul(u128::from(basis_points))?
+            .checked_div(u128::from(MAX_FEE_BASIS_POINTS))?;
+        let protocol_fee = u64::try_from(protocol_fee).ok()?;
+        Some(Self {
+            protocol_fee: protocol_fee.into(),
+            amount: amount.checked_sub(protocol_fee)?.into(),
+        })
+    }
+}
+
+/// Flash mint state of a wrapped mint, stored at
+/// `get_flash_mint_guard_address(wrapped_mint)`.
+///
+/// `FlashMint` marks the guard active and records the loan, and the matching
+/// `FlashRepay` clears it, so flash mints of a wrapped mint cannot be nested.
+/// The unwrapped mint authority sets the fee with `SetFlashMintFee`. A missing
+/// account means no fee.
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct FlashMintGuard {
+    /// Fee charged on flash mints, in basis points of the amount, rounded up
+    pub fee_basis_points: U16,
+    /// Whether a flash mint awaits its `FlashRepay`
+    pub active: Bool,
+    /// Escrow backing the wrapped mint during the active flash mint
+    pub unwrapped_escrow: Pubkey,
+    /// Amount of wrapped tokens minted by the active flash mint
+    pub amount: U64,
+    /// Fee the active flash mint must burn on top of `amount`
+    pub fee: U64,
+    /// Wrapped supply in excess of the escrow balance before the active flash
+    /// mint, which `FlashRepay` must not let grow
+    pub backing_deficit: U64,
+}
+
+impl VersionedState for FlashMintGuard {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::FlashMintGuard;
+    const VERSION: u8 = 1;
+}
+
+impl FlashMintGuard {
+    /// Returns true if a flash mint awaits its `FlashRepay`
+    pub fn is_active(&self) -> bool {
+        bool::from(self.active)
+    }
+
+    /// Returns the fee of flash minting `amount`, rounded up
+    pub fn fee_for(&self, amount: u64) -> Option<u64> {
+        let fee = u128::from(amount)
+            .checked_mul(u128::from(u16::from(self.fee_basis_points)))?
+            .checked_add(u128::from(MAX_FEE_BASIS_POINTS - 1))?
+            .checked_div(u128::from(MAX_FEE_BASIS_POINTS))?;
+        u64::try_from(fee).ok()
+    }
+
+    /// Returns the amount `FlashRepay` burns, the loan plus its fee
+    pub fn repay_amount(&self) -> Option<u64> {
+        u64::from(self.amount).checked_add(u64::from(self.fee))
+    }
+}
+
+/// Replay protection of the permits signed by an owner, stored at
+/// `get_permit_nonce_address(owner)`.
+///
+/// A permit is only accepted with the current nonce, which it then
+/// increments. A missing account means the next nonce is 0.
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct PermitNonce {
+    /// Nonce the next permit of the owner must carry
+    pub nonce: U64,
+}
+
+impl VersionedState for PermitNonce {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::PermitNonce;
+    const VERSION: u8 = 1;
+}
+
+/// Designated recipient of the cranked wraps of an owner's tokens of an
+/// unwrapped mint, stored at
+/// `get_crank_wrap_delegate_address(owner, unwrapped_mint)`.
+///
+/// The owner approves this PDA as delegate on their unwrapped token account,
+/// and `CrankWrap` wraps the delegated tokens into the recipient.
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct CrankWrapDelegate {
+    /// Wrapped token account receiving the cranked wraps
+    pub recipient: Pubkey,
+}
+
+impl VersionedState for CrankWrapDelegate {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::CrankWrapDelegate;
+    const VERSION: u8 = 1;
 }
@@ -0,0 +1,119 @@
+use {
+    crate::helpers::common::{init_mollusk, KeyedAccount},
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    spl_token_wrap::{
+        get_canonical_pointer_address, get_pending_canonical_pointer_address,
+        get_scoped_canonical_pointer_address, get_scoped_pending_canonical_pointer_address,
+    },
+};
+
+pub struct AcceptCanonicalPointerResult {
+    pub canonical_pointer: KeyedAccount,
+    pub pending_pointer: KeyedAccount,
+}
+
+pub struct AcceptCanonicalPointerBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    unwrapped_mint: KeyedAccount,
+    canonical_pointer: Option<KeyedAccount>,
+    pending_pointer: Option<KeyedAccount>,
+    wrapped_token_program: Option<Pubkey>,
+}
+
+impl<'a> AcceptCanonicalPointerBuilder<'a> {
+    pub fn new(unwrapped_mint: KeyedAccount) -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            unwrapped_mint,
+            canonical_pointer: None,
+            pending_pointer: None,
+            wrapped_token_program: None,
+        }
+    }
+
+    pub fn canonical_pointer(mut self, account: KeyedAccount) -> Self {
+        self.canonical_pointer = Some(account);
+        self
+    }
+
+    pub fn pending_pointer(mut self, account: KeyedAccount) -> Self {
+        self.pending_pointer = Some(account);
+        self
+    }
+
+    pub fn unix_timestamp(mut self, unix_timestamp: i64) -> Self {
+        self.mollusk.sysvars.clock.unix_timestamp = unix_timestamp;
+        self
+    }
+
+    pub fn wrapped_token_program(mut self, program_id: Pubkey) -> Self {
+        self.wrapped_token_program = Some(program_id);
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> AcceptCanonicalPointerResult {
+        let unwrapped_mint = &self.unwrapped_mint.key;
+        let (pointer_address, pending_pointer_address) = match &self.wrapped_token_program {
+            Some(wrapped_token_program) => (
+                get_scoped_canonical_pointer_address(unwrapped_mint, wrapped_token_program),
+                get_scoped_pending_canonical_pointer_address(unwrapped_mint, wrapped_token_program),
+            ),
+            None => (
+                get_canonical_pointer_address(unwrapped_mint),
+                get_pending_canonical_pointer_address(unwrapped_mint),
+            ),
+        };
+
+        let canonical_pointer = self.canonical_pointer.unwrap_or_else(|| KeyedAccount {
+            key: pointer_address,
+            account: Account::default(),
+        });
+
+        let pending_pointer = self.pending_pointer.unwrap_or_else(|| KeyedAccount {
+            key: pending_pointer_address,
+            account: Account::default(),
+        });
+
+        let instruction = spl_token_wrap::instruction::accept_canonical_pointer(
+            &spl_token_wrap::id(),
+            &canonical_pointer.key,
+            &pending_pointer.key,
+            &self.unwrapped_mint.key,
+            self.wrapped_token_program.as_ref(),
+        );
+
+        let accounts = &[
+            canonical_pointer.pair(),
+            pending_pointer.pair(),
+            self.unwrapped_mint.pair(),
+            keyed_account_for_system_program(),
+        ];
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, accounts, &self.checks);
+
+        let get_result_account = |key: &Pubkey| KeyedAccount {
+            key: *key,
+            account: result.get_account(key).unwrap().clone(),
+        };
+
+        AcceptCanonicalPointerResult {
+            canonical_pointer: get_result_account(&canonical_pointer.key),
+            pending_pointer: get_result_account(&pending_pointer.key),
+        }
+    }
+}
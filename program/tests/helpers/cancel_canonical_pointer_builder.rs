@@ -0,0 +1,135 @@
+use {
+    crate::helpers::{
+        common::{init_mollusk, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+    },
+    mollusk_svm::{result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    spl_token_wrap::get_pending_canonical_pointer_address,
+};
+
+pub struct CancelCanonicalPointerResult {
+    pub pending_pointer: KeyedAccount,
+    pub destination: KeyedAccount,
+}
+
+pub struct CancelCanonicalPointerBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    unwrapped_mint_authority: Option<Pubkey>,
+    is_authority_signer: bool,
+    pending_pointer: Option<KeyedAccount>,
+    unwrapped_mint: Option<KeyedAccount>,
+}
+
+impl Default for CancelCanonicalPointerBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            unwrapped_mint_authority: None,
+            is_authority_signer: true,
+            pending_pointer: None,
+            unwrapped_mint: None,
+        }
+    }
+}
+
+impl<'a> CancelCanonicalPointerBuilder<'a> {
+    pub fn unwrapped_mint_authority(mut self, key: Pubkey) -> Self {
+        self.unwrapped_mint_authority = Some(key);
+        self
+    }
+
+    pub fn authority_not_signer(mut self) -> Self {
+        self.is_authority_signer = false;
+        self
+    }
+
+    pub fn pending_pointer(mut self, account: KeyedAccount) -> Self {
+        self.pending_pointer = Some(account);
+        self
+    }
+
+    pub fn unwrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.unwrapped_mint = Some(account);
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> CancelCanonicalPointerResult {
+        let unwrapped_mint_authority_key = self
+            .unwrapped_mint_authority
+            .unwrap_or_else(Pubkey::new_unique);
+
+        let unwrapped_mint = self.unwrapped_mint.unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken)
+                .mint_authority(unwrapped_mint_authority_key)
+                .build()
+        });
+
+        let pending_pointer = self.pending_pointer.unwrap_or_else(|| KeyedAccount {
+            key: get_pending_canonical_pointer_address(&unwrapped_mint.key),
+            account: Account::default(),
+        });
+
+        let destination = KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        };
+
+        let unwrapped_mint_authority = KeyedAccount {
+            key: unwrapped_mint_authority_key,
+            account: Account::default(),
+        };
+
+        let mut instruction = spl_token_wrap::instruction::cancel_canonical_pointer(
+            &spl_token_wrap::id(),
+            &unwrapped_mint_authority.key,
+            &pending_pointer.key,
+            &unwrapped_mint.key,
+            None,
+            &destination.key,
+            None,
+            None,
+            &[],
+        );
+
+        // Allow testing with non-signer authority for negative test cases
+        if !self.is_authority_signer {
+            instruction.accounts[0].is_signer = false;
+        }
+
+        let accounts = &[
+            unwrapped_mint_authority.pair(),
+            pending_pointer.pair(),
+            unwrapped_mint.pair(),
+            destination.pair(),
+        ];
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, accounts, &self.checks);
+
+        CancelCanonicalPointerResult {
+            pending_pointer: KeyedAccount {
+                key: pending_pointer.key,
+                account: result.get_account(&pending_pointer.key).unwrap().clone(),
+            },
+            destination: KeyedAccount {
+                key: destination.key,
+                account: result.get_account(&destination.key).unwrap().clone(),
+            },
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use {
+    crate::helpers::{
+        common::{
+            init_mollusk, versioned_state_data, KeyedAccount, TokenProgram, TransferAuthority,
+        },
+        mint_builder::MintBuilder,
+    },
+    mollusk_svm::{result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        get_canonical_pointer_address,
+        state::{CanonicalDeploymentPointer, VersionedState},
+    },
+};
+
+pub struct CloseCanonicalPointerResult {
+    pub canonical_pointer: KeyedAccount,
+    pub destination: KeyedAccount,
+}
+
+pub struct CloseCanonicalPointerBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    unwrapped_mint_authority: Option<Pubkey>,
+    is_authority_signer: bool,
+    multisig: Option<TransferAuthority>,
+    source_metadata: Option<KeyedAccount>,
+    canonical_pointer: Option<KeyedAccount>,
+    unwrapped_mint: Option<KeyedAccount>,
+    destination: Option<KeyedAccount>,
+}
+
+impl Default for CloseCanonicalPointerBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            unwrapped_mint_authority: None,
+            is_authority_signer: true,
+            multisig: None,
+            source_metadata: None,
+            canonical_pointer: None,
+            unwrapped_mint: None,
+            destination: None,
+        }
+    }
+}
+
+impl<'a> CloseCanonicalPointerBuilder<'a> {
+    pub fn unwrapped_mint_authority(mut self, key: Pubkey) -> Self {
+        self.unwrapped_mint_authority = Some(key);
+        self
+    }
+
+    pub fn authority_not_signer(mut self) -> Self {
+        self.is_authority_signer = false;
+        self
+    }
+
+    pub fn multisig(mut self, multisig: TransferAuthority) -> Self {
+        self.multisig = Some(multisig);
+        self
+    }
+
+    pub fn source_metadata(mut self, account: KeyedAccount) -> Self {
+        self.source_metadata = Some(account);
+        self
+    }
+
+    pub fn canonical_pointer(mut self, account: KeyedAccount) -> Self {
+        self.canonical_pointer = Some(account);
+        self
+    }
+
+    pub fn unwrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.unwrapped_mint = Some(account);
+        self
+    }
+
+    pub fn destination(mut self, account: KeyedAccount) -> Self {
+        self.destination = Some(account);
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> CloseCanonicalPointerResult {
+        let unwrapped_mint_authority_key = self.unwrapped_mint_authority.unwrap_or_else(|| {
+            self.multisig
+                .as_ref()
+                .map(|multisig| multisig.keyed_account.key)
+                .unwrap_or_else(Pubkey::new_unique)
+        });
+
+        let unwrapped_mint = self.unwrapped_mint.unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken)
+                .mint_authority(unwrapped_mint_authority_key)
+                .build()
+        });
+
+        let canonical_pointer = self.canonical_pointer.unwrap_or_else(|| KeyedAccount {
+            key: get_canonical_pointer_address(&unwrapped_mint.key),
+            account: Account {
+                lamports: Rent::default().minimum_balance(CanonicalDeploymentPointer::LEN),
+                owner: spl_token_wrap::id(),
+                data: versioned_state_data(&CanonicalDeploymentPointer {
+                    program_id: Pubkey::new_unique(),
+                    enforce_on_wrap: true.into(),
+                    change_delay: 0.into(),
+                }),
+                ..Default::default()
+            },
+        });
+
+        let destination = self.destination.unwrap_or_else(|| KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        });
+
+        let unwrapped_mint_authority = self
+            .multisig
+            .as_ref()
+            .map(|multisig| multisig.keyed_account.clone())
+            .unwrap_or(KeyedAccount {
+                key: unwrapped_mint_authority_key,
+                account: Account::default(),
+            });
+        let multisig_signers = self
+            .multisig
+            .as_ref()
+            .map(|multisig| multisig.signers.clone())
+            .unwrap_or_default();
+
+        let mut instruction = spl_token_wrap::instruction::close_canonical_pointer(
+            &spl_token_wrap::id(),
+            &unwrapped_mint_authority.key,
+            &canonical_pointer.key,
+            &unwrapped_mint.key,
+            None,
+            None,
+            &destination.key,
+            self.source_metadata.as_ref().map(|account| &account.key),
+            None,
+            &multisig_signers.iter().collect::<Vec<_>>(),
+        );
+
+        // Allow testing with non-signer authority for negative test cases
+        if !self.is_authority_signer {
+            instruction.accounts[0].is_signer = false;
+        }
+
+        let mut accounts = vec![
+            unwrapped_mint_authority.pair(),
+            canonical_pointer.pair(),
+            unwrapped_mint.pair(),
+            destination.pair(),
+        ];
+        if let Some(source_metadata) = &self.source_metadata {
+            // A Token-2022 mint holding its own metadata is already provided
+            if source_metadata.key != unwrapped_mint.key {
+                accounts.push(source_metadata.pair());
+            }
+        }
+        for signer in &multisig_signers {
+            accounts.push((*signer, Account::default()));
+        }
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, &accounts, &self.checks);
+
+        CloseCanonicalPointerResult {
+            canonical_pointer: KeyedAccount {
+                key: canonical_pointer.key,
+                account: result.get_account(&canonical_pointer.key).unwrap().clone(),
+            },
+            destination: KeyedAccount {
+                key: destination.key,
+                account: result.get_account(&destination.key).unwrap().clone(),
+            },
+        }
+    }
+}
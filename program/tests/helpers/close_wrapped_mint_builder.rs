@@ -0,0 +1,189 @@
+use {
+    crate::helpers::{
+        common::{init_mollusk, versioned_state_data, KeyedAccount, TokenProgram},
+        extensions::MintExtension,
+        mint_builder::MintBuilder,
+    },
+    mollusk_svm::{result::Check, Mollusk},
+    mollusk_svm_programs_token::token2022,
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        get_escrow_address, get_wrapped_mint_address, get_wrapped_mint_authority,
+        get_wrapped_mint_backpointer_address,
+        instruction::close_wrapped_mint,
+        state::{Backpointer, VersionedState},
+    },
+};
+
+pub struct CloseWrappedMintResult {
+    pub wrapped_mint: KeyedAccount,
+    pub backpointer: KeyedAccount,
+    pub destination: KeyedAccount,
+}
+
+pub struct CloseWrappedMintBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    unwrapped_mint: Option<KeyedAccount>,
+    unwrapped_token_program: Option<TokenProgram>,
+    wrapped_mint: Option<KeyedAccount>,
+    wrapped_supply: u64,
+    backpointer: Option<KeyedAccount>,
+    escrow: Option<KeyedAccount>,
+    destination: Option<KeyedAccount>,
+}
+
+impl Default for CloseWrappedMintBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            unwrapped_mint: None,
+            unwrapped_token_program: None,
+            wrapped_mint: None,
+            wrapped_supply: 0,
+            backpointer: None,
+            escrow: None,
+            destination: None,
+        }
+    }
+}
+
+impl<'a> CloseWrappedMintBuilder<'a> {
+    /// Replaces the closed unwrapped mint
+    pub fn unwrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.unwrapped_mint = Some(account);
+        self
+    }
+
+    pub fn unwrapped_token_program(mut self, program: TokenProgram) -> Self {
+        self.unwrapped_token_program = Some(program);
+        self
+    }
+
+    pub fn wrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.wrapped_mint = Some(account);
+        self
+    }
+
+    pub fn wrapped_supply(mut self, supply: u64) -> Self {
+        self.wrapped_supply = supply;
+        self
+    }
+
+    pub fn backpointer(mut self, account: KeyedAccount) -> Self {
+        self.backpointer = Some(account);
+        self
+    }
+
+    /// Replaces the closed escrow
+    pub fn escrow(mut self, account: KeyedAccount) -> Self {
+        self.escrow = Some(account);
+        self
+    }
+
+    pub fn destination(mut self, account: KeyedAccount) -> Self {
+        self.destination = Some(account);
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> CloseWrappedMintResult {
+        let unwrapped_token_program = self
+            .unwrapped_token_program
+            .unwrap_or(TokenProgram::SplToken2022);
+
+        let unwrapped_mint = self.unwrapped_mint.unwrap_or_else(|| KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        });
+
+        let wrapped_mint_address =
+            get_wrapped_mint_address(&unwrapped_mint.key, &spl_token_2022_interface::id());
+        let wrapped_mint_authority = get_wrapped_mint_authority(&wrapped_mint_address);
+
+        let wrapped_mint = self.wrapped_mint.unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken2022)
+                .mint_key(wrapped_mint_address)
+                .mint_authority(wrapped_mint_authority)
+                .supply(self.wrapped_supply)
+                .with_extension(MintExtension::MintCloseAuthority(wrapped_mint_authority))
+                .build()
+        });
+
+        let backpointer = self.backpointer.unwrap_or_else(|| KeyedAccount {
+            key: get_wrapped_mint_backpointer_address(&wrapped_mint.key),
+            account: Account {
+                lamports: Rent::default().minimum_balance(Backpointer::LEN),
+                owner: spl_token_wrap::id(),
+                data: versioned_state_data(&Backpointer {
+                    unwrapped_token_program: unwrapped_token_program.id(),
+                    wrapped_token_program: spl_token_2022_interface::id(),
+                    ..Backpointer::without_provenance(unwrapped_mint.key)
+                }),
+                ..Default::default()
+            },
+        });
+
+        let escrow = self.escrow.unwrap_or_else(|| KeyedAccount {
+            key: get_escrow_address(
+                &unwrapped_mint.key,
+                &unwrapped_token_program.id(),
+                &spl_token_2022_interface::id(),
+            ),
+            account: Account::default(),
+        });
+
+        let destination = self.destination.unwrap_or_else(|| KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        });
+
+        let instruction = close_wrapped_mint(
+            &spl_token_wrap::id(),
+            &wrapped_mint.key,
+            &backpointer.key,
+            &destination.key,
+            &unwrapped_mint.key,
+            &escrow.key,
+            &wrapped_mint_authority,
+            &unwrapped_token_program.id(),
+        );
+
+        let accounts = &[
+            wrapped_mint.pair(),
+            backpointer.pair(),
+            destination.pair(),
+            unwrapped_mint.pair(),
+            escrow.pair(),
+            (wrapped_mint_authority, Account::default()),
+            unwrapped_token_program.keyed_account(),
+            token2022::keyed_account(),
+        ];
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, accounts, &self.checks);
+
+        let get_account = |key: &Pubkey| KeyedAccount {
+            key: *key,
+            account: result.get_account(key).unwrap().clone(),
+        };
+        CloseWrappedMintResult {
+            wrapped_mint: get_account(&wrapped_mint.key),
+            backpointer: get_account(&backpointer.key),
+            destination: get_account(&destination.key),
+        }
+    }
+}
@@ -2,10 +2,14 @@ use {
     mollusk_svm::Mollusk,
     mollusk_svm_programs_token,
     solana_account::Account,
+    solana_loader_v3_interface::{get_program_data_address, state::UpgradeableLoaderState},
     solana_program_pack::Pack,
     solana_pubkey::Pubkey,
     solana_rent::Rent,
+    solana_sdk_ids::bpf_loader_upgradeable,
     spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList},
+    spl_token_2022_interface::{extension::PodStateWithExtensions, pod::PodAccount},
+    spl_token_wrap::state::{VersionedListEntry, VersionedState},
     spl_transfer_hook_interface::{
         get_extra_account_metas_address, instruction::ExecuteInstruction,
     },
@@ -54,6 +58,52 @@ pub struct TransferAuthority {
     pub signers: Vec<Pubkey>,
 }
 
+/// Account data holding the state in the current, versioned layout
+pub fn versioned_state_data<T: VersionedState>(state: &T) -> Vec<u8> {
+    let mut data = vec![0; T::LEN];
+    state.pack_versioned(&mut data).unwrap();
+    data
+}
+
+/// Owner of the token account, or the default pubkey if the account is not a
+/// valid token account
+pub fn token_account_owner(account: &Account) -> Pubkey {
+    PodStateWithExtensions::<PodAccount>::unpack(&account.data)
+        .map(|state| state.base.owner)
+        .unwrap_or_default()
+}
+
+/// Program data account of the token-wrap program with the given upgrade
+/// authority, holding the serialized `ProgramData` state and no program
+pub fn program_data_account(upgrade_authority: Option<Pubkey>) -> KeyedAccount {
+    let mut data = vec![0; UpgradeableLoaderState::size_of_programdata_metadata()];
+    data[..4].copy_from_slice(&3u32.to_le_bytes());
+    if let Some(upgrade_authority) = upgrade_authority {
+        data[12] = 1;
+        data[13..].copy_from_slice(upgrade_authority.as_ref());
+    }
+    KeyedAccount {
+        key: get_program_data_address(&spl_token_wrap::id()),
+        account: Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: bpf_loader_upgradeable::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    }
+}
+
+/// Account data holding the entries of a list in the current layout
+pub fn list_data<T: VersionedListEntry>(entries: &[T]) -> Vec<u8> {
+    let mut data = vec![0; T::list_len(entries.len())];
+    for (index, entry) in entries.iter().enumerate() {
+        let len = T::list_len(index.checked_add(1).unwrap());
+        entry.pack_list_entry(&mut data[..len], index).unwrap();
+    }
+    data
+}
+
 pub fn init_mollusk() -> Mollusk {
     let mut mollusk = Mollusk::new(&spl_token_wrap::id(), "spl_token_wrap");
     mollusk_svm_programs_token::token::add_program(&mut mollusk);
@@ -0,0 +1,111 @@
+use {
+    crate::helpers::common::KeyedAccount,
+    bytemuck::Pod,
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    solana_zk_sdk::encryption::{
+        auth_encryption::AeKey,
+        elgamal::{ElGamalCiphertext, ElGamalKeypair},
+        pod::{
+            auth_encryption::PodAeCiphertext as SdkPodAeCiphertext,
+            elgamal::{
+                PodElGamalCiphertext as SdkPodElGamalCiphertext,
+                PodElGamalPubkey as SdkPodElGamalPubkey,
+            },
+        },
+    },
+    solana_zk_sdk_pod::encryption::auth_encryption::PodAeCiphertext,
+    spl_token_2022_interface::{
+        extension::{
+            confidential_transfer::ConfidentialTransferAccount, BaseStateWithExtensionsMut,
+            PodStateWithExtensionsMut,
+        },
+        pod::PodAccount,
+        solana_zk_elgamal_proof_interface::{
+            self,
+            proof_data::{
+                BatchedRangeProofU64Data, CiphertextCommitmentEqualityProofData, ZkProofData,
+            },
+            state::ProofContextState,
+        },
+    },
+    spl_token_confidential_transfer_proof_generation::withdraw::withdraw_proof_data,
+};
+
+/// A confidential available balance, with the keys that encrypt it
+pub struct ConfidentialBalance {
+    pub amount: u64,
+    pub elgamal_keypair: ElGamalKeypair,
+    pub aes_key: AeKey,
+    pub available_balance: ElGamalCiphertext,
+}
+
+impl ConfidentialBalance {
+    pub fn new(amount: u64) -> Self {
+        let elgamal_keypair = ElGamalKeypair::new_rand();
+        let available_balance = elgamal_keypair.pubkey().encrypt(amount);
+        Self {
+            amount,
+            elgamal_keypair,
+            aes_key: AeKey::new_rand(),
+            available_balance,
+        }
+    }
+
+    /// Writes the balance into the confidential transfer extension of a token
+    /// account
+    pub fn configure(&self, account: &mut Account) {
+        let mut state = PodStateWithExtensionsMut::<PodAccount>::unpack(&mut account.data).unwrap();
+        let extension = state
+            .get_extension_mut::<ConfidentialTransferAccount>()
+            .unwrap();
+        extension.elgamal_pubkey =
+            bytemuck::cast(SdkPodElGamalPubkey::from(*self.elgamal_keypair.pubkey()));
+        extension.available_balance =
+            bytemuck::cast(SdkPodElGamalCiphertext::from(self.available_balance));
+        extension.decryptable_available_balance = self.decryptable_balance(self.amount);
+    }
+
+    /// Encrypts a balance for the decryptable available balance
+    pub fn decryptable_balance(&self, amount: u64) -> PodAeCiphertext {
+        bytemuck::cast(SdkPodAeCiphertext::from(self.aes_key.encrypt(amount)))
+    }
+
+    /// Builds the equality and range proof context state accounts of a
+    /// withdraw
+    pub fn withdraw_proof_contexts(&self, withdraw_amount: u64) -> (KeyedAccount, KeyedAccount) {
+        let proof_data = withdraw_proof_data(
+            &self.available_balance,
+            self.amount,
+            withdraw_amount,
+            &self.elgamal_keypair,
+        )
+        .unwrap();
+        let equality_proof_data: CiphertextCommitmentEqualityProofData =
+            bytemuck::cast(proof_data.equality_proof_data);
+        let range_proof_data: BatchedRangeProofU64Data =
+            bytemuck::cast(proof_data.range_proof_data);
+        (
+            proof_context_account(&equality_proof_data),
+            proof_context_account(&range_proof_data),
+        )
+    }
+}
+
+fn proof_context_account<T: Pod + ZkProofData<U>, U: Pod>(proof_data: &T) -> KeyedAccount {
+    let data = ProofContextState::<U>::encode(
+        &Pubkey::new_unique(),
+        T::PROOF_TYPE,
+        proof_data.context_data(),
+    );
+    KeyedAccount {
+        key: Pubkey::new_unique(),
+        account: Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: solana_zk_elgamal_proof_interface::id(),
+            ..Default::default()
+        },
+    }
+}
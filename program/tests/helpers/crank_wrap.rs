@@ -0,0 +1,105 @@
+use {
+    crate::helpers::common::{versioned_state_data, KeyedAccount},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        get_crank_wrap_delegate_address,
+        state::{CrankWrapDelegate, VersionedState},
+    },
+};
+
+/// Owner of the unwrapped tokens who approved their crank wrap delegate, with
+/// the state of that delegate
+pub struct CrankWrapper {
+    pub owner: Pubkey,
+    pub approved_delegate: Option<Option<Pubkey>>,
+    pub delegated_amount: Option<u64>,
+    pub delegate_address: Option<Pubkey>,
+    pub delegate_account: Option<Account>,
+    pub designated_recipient: Option<Pubkey>,
+}
+
+impl Default for CrankWrapper {
+    fn default() -> Self {
+        Self {
+            owner: Pubkey::new_unique(),
+            approved_delegate: None,
+            delegated_amount: None,
+            delegate_address: None,
+            delegate_account: None,
+            designated_recipient: None,
+        }
+    }
+}
+
+impl CrankWrapper {
+    /// Delegate approved on the unwrapped token account, which defaults to
+    /// the crank wrap delegate of the owner
+    pub fn approved_delegate(mut self, delegate: Pubkey) -> Self {
+        self.approved_delegate = Some(Some(delegate));
+        self
+    }
+
+    /// Leaves the unwrapped token account without delegate
+    pub fn not_approved(mut self) -> Self {
+        self.approved_delegate = Some(None);
+        self
+    }
+
+    /// Amount approved to the delegate, which defaults to the wrap amount
+    pub fn delegated_amount(mut self, amount: u64) -> Self {
+        self.delegated_amount = Some(amount);
+        self
+    }
+
+    pub fn delegate_address(mut self, key: Pubkey) -> Self {
+        self.delegate_address = Some(key);
+        self
+    }
+
+    /// Starting state of the crank wrap delegate, which defaults to one
+    /// designating the recipient of the wrap
+    pub fn delegate_account(mut self, account: Account) -> Self {
+        self.delegate_account = Some(account);
+        self
+    }
+
+    /// Recipient designated in the default state of the crank wrap delegate
+    pub fn designated_recipient(mut self, recipient: Pubkey) -> Self {
+        self.designated_recipient = Some(recipient);
+        self
+    }
+
+    pub fn delegate_key(&self, unwrapped_mint: &Pubkey) -> Pubkey {
+        self.delegate_address
+            .unwrap_or_else(|| get_crank_wrap_delegate_address(&self.owner, unwrapped_mint))
+    }
+
+    /// Delegate approved on the unwrapped token account, if any
+    pub fn token_account_delegate(&self, unwrapped_mint: &Pubkey) -> Option<Pubkey> {
+        self.approved_delegate
+            .unwrap_or_else(|| Some(get_crank_wrap_delegate_address(&self.owner, unwrapped_mint)))
+    }
+
+    pub fn keyed_delegate(&self, unwrapped_mint: &Pubkey, recipient: &Pubkey) -> KeyedAccount {
+        KeyedAccount {
+            key: self.delegate_key(unwrapped_mint),
+            account: self.delegate_account.clone().unwrap_or_else(|| {
+                crank_wrap_delegate_account(&self.designated_recipient.unwrap_or(*recipient))
+            }),
+        }
+    }
+}
+
+/// Crank wrap delegate owned by the program, designating the recipient
+pub fn crank_wrap_delegate_account(recipient: &Pubkey) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(CrankWrapDelegate::LEN),
+        owner: spl_token_wrap::id(),
+        data: versioned_state_data(&CrankWrapDelegate {
+            recipient: *recipient,
+        }),
+        ..Default::default()
+    }
+}
@@ -7,7 +7,9 @@ use {
     solana_account::Account,
     solana_pubkey::Pubkey,
     spl_token_wrap::{
-        get_wrapped_mint_address, get_wrapped_mint_backpointer_address, instruction::create_mint,
+        get_wrapped_mint_address, get_wrapped_mint_backpointer_address,
+        get_wrapped_mint_index_address, get_wrapped_mint_registry_address,
+        get_wrapped_mint_registry_page_address, instruction::create_mint,
     },
 };
 
@@ -15,6 +17,9 @@ pub struct CreateMintResult {
     pub unwrapped_mint: KeyedAccount,
     pub wrapped_mint: KeyedAccount,
     pub wrapped_backpointer: KeyedAccount,
+    pub registry: KeyedAccount,
+    pub registry_page: KeyedAccount,
+    pub wrapped_mint_index: KeyedAccount,
 }
 
 pub struct CreateMintBuilder<'a> {
@@ -29,7 +34,11 @@ pub struct CreateMintBuilder<'a> {
     backpointer_addr: Option<Pubkey>,
     backpointer_account: Option<Account>,
     freeze_authority: Option<Pubkey>,
+    registry_account: Option<Account>,
+    registry_page: Option<KeyedAccount>,
+    wrapped_mint_index_account: Option<Account>,
     idempotent: bool,
+    acknowledge_risky_extensions: bool,
     checks: Vec<Check<'a>>,
 }
 
@@ -47,7 +56,11 @@ impl Default for CreateMintBuilder<'_> {
             backpointer_addr: None,
             backpointer_account: None,
             freeze_authority: None,
+            registry_account: None,
+            registry_page: None,
+            wrapped_mint_index_account: None,
             idempotent: false,
+            acknowledge_risky_extensions: false,
             checks: vec![],
         }
     }
@@ -98,6 +111,11 @@ impl<'a> CreateMintBuilder<'a> {
         self
     }
 
+    pub fn acknowledge_risky_extensions(mut self) -> Self {
+        self.acknowledge_risky_extensions = true;
+        self
+    }
+
     pub fn unwrapped_mint_account(mut self, account: Account) -> Self {
         self.unwrapped_mint_account = Some(account);
         self
@@ -108,6 +126,26 @@ impl<'a> CreateMintBuilder<'a> {
         self
     }
 
+    pub fn registry_account(mut self, account: Account) -> Self {
+        self.registry_account = Some(account);
+        self
+    }
+
+    pub fn registry_page(mut self, page: KeyedAccount) -> Self {
+        self.registry_page = Some(page);
+        self
+    }
+
+    pub fn wrapped_mint_index_account(mut self, account: Account) -> Self {
+        self.wrapped_mint_index_account = Some(account);
+        self
+    }
+
+    pub fn slot(mut self, slot: u64) -> Self {
+        self.mollusk.sysvars.clock.slot = slot;
+        self
+    }
+
     pub fn check(mut self, check: Check<'a>) -> Self {
         self.checks.push(check);
         self
@@ -149,13 +187,38 @@ impl<'a> CreateMintBuilder<'a> {
             ..Default::default()
         });
 
+        let funded_account = Account {
+            lamports: 100_000_000,
+            ..Default::default()
+        };
+        let registry = KeyedAccount {
+            key: get_wrapped_mint_registry_address(),
+            account: self
+                .registry_account
+                .unwrap_or_else(|| funded_account.clone()),
+        };
+        let registry_page = self.registry_page.unwrap_or_else(|| KeyedAccount {
+            key: get_wrapped_mint_registry_page_address(0),
+            account: funded_account.clone(),
+        });
+        let wrapped_mint_index = KeyedAccount {
+            key: get_wrapped_mint_index_address(&unwrapped_mint_addr),
+            account: self
+                .wrapped_mint_index_account
+                .unwrap_or_else(|| funded_account.clone()),
+        };
+
         let instruction = create_mint(
             &spl_token_wrap::id(),
             &wrapped_mint_addr,
             &wrapped_backpointer_address,
             &unwrapped_mint_addr,
             &wrapped_token_program_id,
+            &registry.key,
+            &registry_page.key,
+            &wrapped_mint_index.key,
             self.idempotent,
+            self.acknowledge_risky_extensions,
         );
 
         let mut keyed_token_program = match self.wrapped_token_program {
@@ -170,6 +233,9 @@ impl<'a> CreateMintBuilder<'a> {
             (unwrapped_mint_addr, unwrapped_mint_account),
             keyed_account_for_system_program(),
             keyed_token_program,
+            registry.pair(),
+            registry_page.pair(),
+            wrapped_mint_index.pair(),
         ];
 
         if self.checks.is_empty() {
@@ -196,6 +262,18 @@ impl<'a> CreateMintBuilder<'a> {
                     .unwrap()
                     .clone(),
             },
+            registry: KeyedAccount {
+                key: registry.key,
+                account: result.get_account(&registry.key).unwrap().clone(),
+            },
+            registry_page: KeyedAccount {
+                key: registry_page.key,
+                account: result.get_account(&registry_page.key).unwrap().clone(),
+            },
+            wrapped_mint_index: KeyedAccount {
+                key: wrapped_mint_index.key,
+                account: result.get_account(&wrapped_mint_index.key).unwrap().clone(),
+            },
         }
     }
 }
@@ -0,0 +1,243 @@
+use {
+    crate::helpers::{
+        common::{init_mollusk, versioned_state_data, KeyedAccount, TokenProgram},
+        extensions::MintExtension,
+        mint_builder::MintBuilder,
+        token_account_builder::TokenAccountBuilder,
+    },
+    mollusk_svm::{result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        get_wrapped_mint_address, get_wrapped_mint_admin_address, get_wrapped_mint_authority,
+        instruction::{execute_admin_action, AdminAction},
+        state::WrappedMintAdmin,
+    },
+};
+
+pub struct ExecuteAdminActionResult {
+    pub wrapped_mint: KeyedAccount,
+    pub wrapped_token_account: Option<KeyedAccount>,
+}
+
+pub struct ExecuteAdminActionBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    action: AdminAction,
+    admin: Option<Pubkey>,
+    is_admin_signer: bool,
+    appointed_admin: Option<Pubkey>,
+    unwrapped_mint_authority: Option<Pubkey>,
+    unwrapped_mint: Option<KeyedAccount>,
+    wrapped_mint: Option<KeyedAccount>,
+    wrapped_mint_admin_address: Option<Pubkey>,
+    wrapped_token_account: Option<KeyedAccount>,
+}
+
+impl Default for ExecuteAdminActionBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            action: AdminAction::Pause,
+            admin: None,
+            is_admin_signer: true,
+            appointed_admin: None,
+            unwrapped_mint_authority: None,
+            unwrapped_mint: None,
+            wrapped_mint: None,
+            wrapped_mint_admin_address: None,
+            wrapped_token_account: None,
+        }
+    }
+}
+
+impl<'a> ExecuteAdminActionBuilder<'a> {
+    pub fn action(mut self, action: AdminAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Signer of the action, defaults to the unwrapped mint authority
+    pub fn admin(mut self, key: Pubkey) -> Self {
+        self.admin = Some(key);
+        self
+    }
+
+    pub fn admin_not_signer(mut self) -> Self {
+        self.is_admin_signer = false;
+        self
+    }
+
+    /// Admin stored in the `WrappedMintAdmin` account, which is not created
+    /// otherwise
+    pub fn appointed_admin(mut self, key: Pubkey) -> Self {
+        self.appointed_admin = Some(key);
+        self
+    }
+
+    pub fn unwrapped_mint_authority(mut self, key: Pubkey) -> Self {
+        self.unwrapped_mint_authority = Some(key);
+        self
+    }
+
+    pub fn unwrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.unwrapped_mint = Some(account);
+        self
+    }
+
+    pub fn wrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.wrapped_mint = Some(account);
+        self
+    }
+
+    pub fn wrapped_mint_admin_address(mut self, key: Pubkey) -> Self {
+        self.wrapped_mint_admin_address = Some(key);
+        self
+    }
+
+    pub fn wrapped_token_account(mut self, account: KeyedAccount) -> Self {
+        self.wrapped_token_account = Some(account);
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    /// Default wrapped mint, whose freeze, pause, metadata and transfer fee
+    /// authorities are all the wrapped mint authority
+    pub fn default_wrapped_mint(unwrapped_mint: &Pubkey) -> KeyedAccount {
+        let wrapped_mint_address =
+            get_wrapped_mint_address(unwrapped_mint, &spl_token_2022_interface::id());
+        let wrapped_mint_authority = get_wrapped_mint_authority(&wrapped_mint_address);
+        MintBuilder::new()
+            .token_program(TokenProgram::SplToken2022)
+            .mint_key(wrapped_mint_address)
+            .mint_authority(wrapped_mint_authority)
+            .freeze_authority(wrapped_mint_authority)
+            .lamports(1_000_000_000)
+            .with_extension(MintExtension::PausableWithAuthority(wrapped_mint_authority))
+            .with_extension(MintExtension::TransferFeeConfigWithAuthority(
+                wrapped_mint_authority,
+            ))
+            .with_extension(MintExtension::MetadataPointer {
+                metadata_address: Some(wrapped_mint_address),
+            })
+            .with_extension(MintExtension::TokenMetadata {
+                name: "Wrapped".to_string(),
+                symbol: "WRP".to_string(),
+                uri: "https://example.com/wrapped.json".to_string(),
+                additional_metadata: vec![],
+            })
+            .build()
+    }
+
+    pub fn execute(mut self) -> ExecuteAdminActionResult {
+        let unwrapped_mint_authority = self
+            .unwrapped_mint_authority
+            .unwrap_or_else(Pubkey::new_unique);
+
+        let unwrapped_mint = self.unwrapped_mint.unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken)
+                .mint_authority(unwrapped_mint_authority)
+                .build()
+        });
+
+        let wrapped_mint = self
+            .wrapped_mint
+            .unwrap_or_else(|| Self::default_wrapped_mint(&unwrapped_mint.key));
+
+        let wrapped_token_program = if wrapped_mint.account.owner == spl_token::id() {
+            TokenProgram::SplToken
+        } else {
+            TokenProgram::SplToken2022
+        };
+
+        let wrapped_mint_admin_address = self
+            .wrapped_mint_admin_address
+            .unwrap_or_else(|| get_wrapped_mint_admin_address(&wrapped_mint.key));
+        let wrapped_mint_admin_account = match self.appointed_admin {
+            Some(admin) => {
+                let data = versioned_state_data(&WrappedMintAdmin { admin });
+                Account {
+                    lamports: Rent::default().minimum_balance(data.len()),
+                    data,
+                    owner: spl_token_wrap::id(),
+                    ..Default::default()
+                }
+            }
+            None => Account::default(),
+        };
+
+        let wrapped_token_account = match self.action {
+            AdminAction::FreezeAccount | AdminAction::ThawAccount => {
+                Some(self.wrapped_token_account.unwrap_or_else(|| {
+                    TokenAccountBuilder::default()
+                        .token_program(wrapped_token_program)
+                        .mint(wrapped_mint.clone())
+                        .owner(Pubkey::new_unique())
+                        .build()
+                }))
+            }
+            _ => None,
+        };
+
+        let admin = self.admin.unwrap_or(unwrapped_mint_authority);
+        let wrapped_mint_authority = get_wrapped_mint_authority(&wrapped_mint.key);
+
+        let mut instruction = execute_admin_action(
+            &spl_token_wrap::id(),
+            &admin,
+            &wrapped_mint.key,
+            &wrapped_mint_authority,
+            &unwrapped_mint.key,
+            &wrapped_mint_admin_address,
+            &wrapped_token_program.id(),
+            wrapped_token_account.as_ref().map(|account| &account.key),
+            self.action,
+            None,
+            None,
+            &[],
+        );
+
+        // Allow testing with non-signer admin for negative test cases
+        if !self.is_admin_signer {
+            instruction.accounts[0].is_signer = false;
+        }
+
+        let mut accounts = vec![
+            (admin, Account::default()),
+            wrapped_mint.pair(),
+            (wrapped_mint_authority, Account::default()),
+            unwrapped_mint.pair(),
+            (wrapped_mint_admin_address, wrapped_mint_admin_account),
+            wrapped_token_program.keyed_account(),
+        ];
+        if let Some(wrapped_token_account) = &wrapped_token_account {
+            accounts.push(wrapped_token_account.pair());
+        }
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, &accounts, &self.checks);
+
+        ExecuteAdminActionResult {
+            wrapped_mint: KeyedAccount {
+                key: wrapped_mint.key,
+                account: result.get_account(&wrapped_mint.key).unwrap().clone(),
+            },
+            wrapped_token_account: wrapped_token_account.map(|account| KeyedAccount {
+                key: account.key,
+                account: result.get_account(&account.key).unwrap().clone(),
+            }),
+        }
+    }
+}
@@ -5,18 +5,25 @@ use {
     solana_zero_copy::unaligned::U64,
     spl_token_2022_interface::{
         extension::{
-            confidential_transfer::ConfidentialTransferMint,
+            confidential_transfer::{
+                ConfidentialTransferAccount, ConfidentialTransferMint,
+                DEFAULT_MAXIMUM_PENDING_BALANCE_CREDIT_COUNTER,
+            },
+            cpi_guard::CpiGuard,
+            default_account_state::DefaultAccountState,
             immutable_owner::ImmutableOwner,
             metadata_pointer::MetadataPointer,
             mint_close_authority::MintCloseAuthority,
             non_transferable::{NonTransferable, NonTransferableAccount},
+            pausable::PausableConfig,
+            permanent_delegate::PermanentDelegate,
             transfer_fee::{TransferFee, TransferFeeAmount, TransferFeeConfig},
             transfer_hook::{TransferHook, TransferHookAccount},
             AccountType, BaseStateWithExtensionsMut, ExtensionType, Length,
             PodStateWithExtensionsMut,
         },
         pod::{PodAccount, PodMint},
-        state::{Account, Mint},
+        state::{Account, AccountState, Mint},
     },
     spl_token_metadata_interface::state::TokenMetadata,
     spl_token_wrap::get_wrapped_mint_authority,
@@ -26,10 +33,16 @@ use {
 #[derive(Clone, Debug)]
 pub enum MintExtension {
     ConfidentialTransfer,
+    ConfidentialTransferWithAuthority(Pubkey),
     TransferHook,
     TransferFeeConfig,
+    TransferFeeConfigWithAuthority(Pubkey),
     MintCloseAuthority(Pubkey),
     NonTransferable,
+    PermanentDelegate(Pubkey),
+    Pausable,
+    PausableWithAuthority(Pubkey),
+    DefaultAccountState(AccountState),
     TokenMetadata {
         name: String,
         symbol: String,
@@ -45,10 +58,20 @@ impl MintExtension {
     pub fn extension_type(&self) -> ExtensionType {
         match self {
             MintExtension::TransferHook => ExtensionType::TransferHook,
-            MintExtension::TransferFeeConfig => ExtensionType::TransferFeeConfig,
+            MintExtension::TransferFeeConfig | MintExtension::TransferFeeConfigWithAuthority(_) => {
+                ExtensionType::TransferFeeConfig
+            }
             MintExtension::MintCloseAuthority(_) => ExtensionType::MintCloseAuthority,
-            MintExtension::ConfidentialTransfer => ExtensionType::ConfidentialTransferMint,
+            MintExtension::ConfidentialTransfer
+            | MintExtension::ConfidentialTransferWithAuthority(_) => {
+                ExtensionType::ConfidentialTransferMint
+            }
             MintExtension::NonTransferable => ExtensionType::NonTransferable,
+            MintExtension::PermanentDelegate(_) => ExtensionType::PermanentDelegate,
+            MintExtension::Pausable | MintExtension::PausableWithAuthority(_) => {
+                ExtensionType::Pausable
+            }
+            MintExtension::DefaultAccountState(_) => ExtensionType::DefaultAccountState,
             MintExtension::TokenMetadata { .. } => ExtensionType::TokenMetadata,
             MintExtension::MetadataPointer { .. } => ExtensionType::MetadataPointer,
         }
@@ -69,28 +92,10 @@ pub fn init_mint_extensions(
                     MaybeNull::<Pubkey>::try_from(Some(test_transfer_hook::id())).unwrap();
             }
             MintExtension::TransferFeeConfig => {
-                let extension = state.init_extension::<TransferFeeConfig>(false).unwrap();
-                *extension = TransferFeeConfig {
-                    transfer_fee_config_authority: MaybeNull::<Pubkey>::try_from(Some(
-                        Pubkey::new_unique(),
-                    ))
-                    .unwrap(),
-                    withdraw_withheld_authority: MaybeNull::<Pubkey>::try_from(Some(
-                        Pubkey::new_unique(),
-                    ))
-                    .unwrap(),
-                    withheld_amount: U64::from(0),
-                    older_transfer_fee: TransferFee {
-                        epoch: 0.into(),
-                        maximum_fee: 50_000.into(),
-                        transfer_fee_basis_points: 100.into(),
-                    },
-                    newer_transfer_fee: TransferFee {
-                        epoch: 0.into(),
-                        maximum_fee: 50_000.into(),
-                        transfer_fee_basis_points: 100.into(),
-                    },
-                };
+                init_transfer_fee_config(state, Pubkey::new_unique());
+            }
+            MintExtension::TransferFeeConfigWithAuthority(authority) => {
+                init_transfer_fee_config(state, *authority);
             }
             MintExtension::MintCloseAuthority(authority) => {
                 let extension = state.init_extension::<MintCloseAuthority>(false).unwrap();
@@ -102,9 +107,33 @@ pub fn init_mint_extensions(
                     .init_extension::<ConfidentialTransferMint>(false)
                     .unwrap();
             }
+            MintExtension::ConfidentialTransferWithAuthority(authority) => {
+                let extension = state
+                    .init_extension::<ConfidentialTransferMint>(false)
+                    .unwrap();
+                extension.authority = MaybeNull::<Pubkey>::try_from(Some(*authority)).unwrap();
+                extension.auto_approve_new_accounts = true.into();
+            }
             MintExtension::NonTransferable => {
                 state.init_extension::<NonTransferable>(false).unwrap();
             }
+            MintExtension::PermanentDelegate(delegate) => {
+                let extension = state.init_extension::<PermanentDelegate>(false).unwrap();
+                extension.delegate = MaybeNull::<Pubkey>::try_from(Some(*delegate)).unwrap();
+            }
+            MintExtension::Pausable => {
+                let extension = state.init_extension::<PausableConfig>(false).unwrap();
+                extension.authority =
+                    MaybeNull::<Pubkey>::try_from(Some(Pubkey::new_unique())).unwrap();
+            }
+            MintExtension::PausableWithAuthority(authority) => {
+                let extension = state.init_extension::<PausableConfig>(false).unwrap();
+                extension.authority = MaybeNull::<Pubkey>::try_from(Some(*authority)).unwrap();
+            }
+            MintExtension::DefaultAccountState(account_state) => {
+                let extension = state.init_extension::<DefaultAccountState>(false).unwrap();
+                extension.state = *account_state as u8;
+            }
             MintExtension::TokenMetadata {
                 name,
                 symbol,
@@ -132,6 +161,33 @@ pub fn init_mint_extensions(
     }
 }
 
+/// Initialize a transfer fee config of 1%, capped at 50,000
+fn init_transfer_fee_config(
+    state: &mut PodStateWithExtensionsMut<PodMint>,
+    transfer_fee_config_authority: Pubkey,
+) {
+    let extension = state.init_extension::<TransferFeeConfig>(false).unwrap();
+    *extension = TransferFeeConfig {
+        transfer_fee_config_authority: MaybeNull::<Pubkey>::try_from(Some(
+            transfer_fee_config_authority,
+        ))
+        .unwrap(),
+        withdraw_withheld_authority: MaybeNull::<Pubkey>::try_from(Some(Pubkey::new_unique()))
+            .unwrap(),
+        withheld_amount: U64::from(0),
+        older_transfer_fee: TransferFee {
+            epoch: 0.into(),
+            maximum_fee: 50_000.into(),
+            transfer_fee_basis_points: 100.into(),
+        },
+        newer_transfer_fee: TransferFee {
+            epoch: 0.into(),
+            maximum_fee: 50_000.into(),
+            transfer_fee_basis_points: 100.into(),
+        },
+    };
+}
+
 /// Initialize extensions for a token account
 pub fn init_token_account_extensions(
     state: &mut PodStateWithExtensionsMut<PodAccount>,
@@ -156,6 +212,22 @@ pub fn init_token_account_extensions(
                     .init_extension::<NonTransferableAccount>(true)
                     .unwrap();
             }
+            ExtensionType::ConfidentialTransferAccount => {
+                // Approved and accepting deposits, with empty balances
+                let extension = state
+                    .init_extension::<ConfidentialTransferAccount>(true)
+                    .unwrap();
+                extension.approved = true.into();
+                extension.allow_confidential_credits = true.into();
+                extension.allow_non_confidential_credits = true.into();
+                extension.maximum_pending_balance_credit_counter =
+                    DEFAULT_MAXIMUM_PENDING_BALANCE_CREDIT_COUNTER.into();
+            }
+            ExtensionType::CpiGuard => {
+                // Enabled, so that the owner cannot transfer within a CPI
+                let extension = state.init_extension::<CpiGuard>(true).unwrap();
+                extension.lock_cpi = true.into();
+            }
             _ => unimplemented!(),
         }
     }
@@ -0,0 +1,338 @@
+use {
+    crate::helpers::{
+        common::{init_mollusk, versioned_state_data, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+        token_account_builder::TokenAccountBuilder,
+    },
+    bytemuck::Zeroable,
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
+    solana_account::Account,
+    solana_instruction::Instruction,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        get_allowlist_address, get_allowlist_entry_address, get_canonical_pointer_address,
+        get_escrow_address, get_flash_mint_guard_address, get_scoped_canonical_pointer_address,
+        get_wrap_limits_address, get_wrapped_mint_address, get_wrapped_mint_authority,
+        instruction::{flash_mint, flash_repay},
+        state::{FlashMintGuard, VersionedState},
+    },
+};
+
+pub struct FlashMintResult {
+    pub recipient_wrapped_token_account: KeyedAccount,
+    pub wrapped_mint: KeyedAccount,
+    pub unwrapped_escrow: KeyedAccount,
+    pub flash_mint_guard: KeyedAccount,
+    pub wrap_limits: KeyedAccount,
+}
+
+pub struct FlashMintBuilder<'a> {
+    mollusk: Mollusk,
+    flash_mint_checks: Vec<Check<'a>>,
+    flash_repay_checks: Vec<Check<'a>>,
+    wrapped_token_program: TokenProgram,
+    repay_token_program: Option<TokenProgram>,
+    amount: u64,
+    escrow_amount: u64,
+    borrower_balance: u64,
+    fee_basis_points: Option<u16>,
+    flash_mint_guard: Option<KeyedAccount>,
+    unwrapped_mint: Option<KeyedAccount>,
+    canonical_pointer: Option<KeyedAccount>,
+    wrap_limits: Option<KeyedAccount>,
+    allowlist: Option<Account>,
+    allowlist_entry: Option<Account>,
+    flash_mint: bool,
+    repay: bool,
+    nested: bool,
+}
+
+impl Default for FlashMintBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            flash_mint_checks: vec![],
+            flash_repay_checks: vec![],
+            wrapped_token_program: TokenProgram::SplToken2022,
+            repay_token_program: None,
+            amount: 10_000,
+            escrow_amount: 100_000,
+            borrower_balance: 0,
+            fee_basis_points: None,
+            flash_mint_guard: None,
+            unwrapped_mint: None,
+            canonical_pointer: None,
+            wrap_limits: None,
+            allowlist: None,
+            allowlist_entry: None,
+            flash_mint: true,
+            repay: true,
+            nested: false,
+        }
+    }
+}
+
+impl<'a> FlashMintBuilder<'a> {
+    pub fn wrapped_token_program(mut self, program: TokenProgram) -> Self {
+        self.wrapped_token_program = program;
+        self
+    }
+
+    /// Token program passed to `FlashRepay` in place of the wrapped token
+    /// program
+    pub fn repay_token_program(mut self, program: TokenProgram) -> Self {
+        self.repay_token_program = Some(program);
+        self
+    }
+
+    pub fn amount(mut self, amount: u64) -> Self {
+        self.amount = amount;
+        self
+    }
+
+    /// Wrapped tokens the borrower already holds, e.g. to pay the fee
+    pub fn borrower_balance(mut self, amount: u64) -> Self {
+        self.borrower_balance = amount;
+        self
+    }
+
+    /// Starts from a guard with the fee set by `SetFlashMintFee`
+    pub fn fee_basis_points(mut self, fee_basis_points: u16) -> Self {
+        self.fee_basis_points = Some(fee_basis_points);
+        self
+    }
+
+    pub fn flash_mint_guard(mut self, account: KeyedAccount) -> Self {
+        self.flash_mint_guard = Some(account);
+        self
+    }
+
+    /// Unwrapped mint, which must be an SPL Token mint
+    pub fn unwrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.unwrapped_mint = Some(account);
+        self
+    }
+
+    pub fn canonical_pointer(mut self, account: KeyedAccount) -> Self {
+        self.canonical_pointer = Some(account);
+        self
+    }
+
+    pub fn wrap_limits(mut self, account: KeyedAccount) -> Self {
+        self.wrap_limits = Some(account);
+        self
+    }
+
+    /// Enforces the allowlist of the unwrapped mint, starting from the given
+    /// state
+    pub fn allowlist(mut self, account: Account) -> Self {
+        self.allowlist = Some(account);
+        self
+    }
+
+    /// Entry of the borrower on the allowlist
+    pub fn allowlist_entry(mut self, account: Account) -> Self {
+        self.allowlist_entry = Some(account);
+        self
+    }
+
+    /// Leaves the `FlashMint` out of the transaction
+    pub fn without_flash_mint(mut self) -> Self {
+        self.flash_mint = false;
+        self
+    }
+
+    /// Leaves the `FlashRepay` out of the transaction
+    pub fn without_repay(mut self) -> Self {
+        self.repay = false;
+        self
+    }
+
+    /// Runs a second `FlashMint` before the `FlashRepay` of the first
+    pub fn nested(mut self) -> Self {
+        self.nested = true;
+        self
+    }
+
+    /// Adds a check of the last `FlashMint`
+    pub fn flash_mint_check(mut self, check: Check<'a>) -> Self {
+        self.flash_mint_checks.push(check);
+        self
+    }
+
+    pub fn flash_repay_check(mut self, check: Check<'a>) -> Self {
+        self.flash_repay_checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> FlashMintResult {
+        let unwrapped_token_program = TokenProgram::SplToken;
+        let unwrapped_mint = self.unwrapped_mint.clone().unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(unwrapped_token_program)
+                .mint_authority(Pubkey::new_unique())
+                .build()
+        });
+
+        let wrapped_mint_address =
+            get_wrapped_mint_address(&unwrapped_mint.key, &self.wrapped_token_program.id());
+        let wrapped_mint_authority = get_wrapped_mint_authority(&wrapped_mint_address);
+        let wrapped_mint = MintBuilder::new()
+            .token_program(self.wrapped_token_program)
+            .mint_key(wrapped_mint_address)
+            .mint_authority(wrapped_mint_authority)
+            .supply(self.escrow_amount)
+            .build();
+
+        let unwrapped_escrow = TokenAccountBuilder::new()
+            .token_program(unwrapped_token_program)
+            .mint(unwrapped_mint.clone())
+            .owner(wrapped_mint_authority)
+            .amount(self.escrow_amount)
+            .account_key(get_escrow_address(
+                &unwrapped_mint.key,
+                &unwrapped_token_program.id(),
+                &self.wrapped_token_program.id(),
+            ))
+            .build();
+
+        let borrower = Pubkey::new_unique();
+        let recipient = TokenAccountBuilder::new()
+            .token_program(self.wrapped_token_program)
+            .mint(wrapped_mint.clone())
+            .owner(borrower)
+            .amount(self.borrower_balance)
+            .build();
+
+        let flash_mint_guard = self
+            .flash_mint_guard
+            .clone()
+            .unwrap_or_else(|| KeyedAccount {
+                key: get_flash_mint_guard_address(&wrapped_mint.key),
+                account: match self.fee_basis_points {
+                    Some(fee_basis_points) => Account {
+                        lamports: Rent::default().minimum_balance(FlashMintGuard::LEN),
+                        owner: spl_token_wrap::id(),
+                        data: versioned_state_data(&FlashMintGuard {
+                            fee_basis_points: fee_basis_points.into(),
+                            ..FlashMintGuard::zeroed()
+                        }),
+                        ..Default::default()
+                    },
+                    None => Account {
+                        lamports: Rent::default().minimum_balance(FlashMintGuard::LEN),
+                        ..Default::default()
+                    },
+                },
+            });
+
+        let canonical_pointer = self.canonical_pointer.clone().unwrap_or(KeyedAccount {
+            key: get_canonical_pointer_address(&unwrapped_mint.key),
+            account: Account::default(),
+        });
+        let scoped_canonical_pointer = KeyedAccount {
+            key: get_scoped_canonical_pointer_address(
+                &unwrapped_mint.key,
+                &self.wrapped_token_program.id(),
+            ),
+            account: Account::default(),
+        };
+        let wrap_limits = self.wrap_limits.clone().unwrap_or(KeyedAccount {
+            key: get_wrap_limits_address(&unwrapped_mint.key),
+            account: Account::default(),
+        });
+        let allowlist = KeyedAccount {
+            key: get_allowlist_address(&unwrapped_mint.key),
+            account: self.allowlist.clone().unwrap_or_default(),
+        };
+        let allowlist_entry = KeyedAccount {
+            key: get_allowlist_entry_address(&unwrapped_mint.key, &borrower),
+            account: self.allowlist_entry.clone().unwrap_or_default(),
+        };
+
+        let flash_mint_instruction = flash_mint(
+            &spl_token_wrap::id(),
+            &recipient.key,
+            &wrapped_mint.key,
+            &wrapped_mint_authority,
+            &unwrapped_mint.key,
+            &unwrapped_escrow.key,
+            &flash_mint_guard.key,
+            &self.wrapped_token_program.id(),
+            &unwrapped_token_program.id(),
+            &canonical_pointer.key,
+            &scoped_canonical_pointer.key,
+            &wrap_limits.key,
+            &allowlist.key,
+            &allowlist_entry.key,
+            self.amount,
+        );
+        let flash_repay_instruction = flash_repay(
+            &spl_token_wrap::id(),
+            &recipient.key,
+            &wrapped_mint.key,
+            &unwrapped_escrow.key,
+            &flash_mint_guard.key,
+            &self
+                .repay_token_program
+                .unwrap_or(self.wrapped_token_program)
+                .id(),
+            &borrower,
+            &[],
+        );
+
+        let accounts = &[
+            recipient.pair(),
+            wrapped_mint.pair(),
+            (wrapped_mint_authority, Account::default()),
+            unwrapped_mint.pair(),
+            unwrapped_escrow.pair(),
+            flash_mint_guard.pair(),
+            self.wrapped_token_program.keyed_account(),
+            unwrapped_token_program.keyed_account(),
+            keyed_account_for_system_program(),
+            canonical_pointer.pair(),
+            scoped_canonical_pointer.pair(),
+            wrap_limits.pair(),
+            allowlist.pair(),
+            allowlist_entry.pair(),
+            (borrower, Account::default()),
+        ];
+
+        if self.flash_mint_checks.is_empty() {
+            self.flash_mint_checks.push(Check::success());
+        }
+        if self.flash_repay_checks.is_empty() {
+            self.flash_repay_checks.push(Check::success());
+        }
+
+        let success_checks = [Check::success()];
+        let mut instructions: Vec<(&Instruction, &[Check])> = vec![];
+        if self.nested {
+            instructions.push((&flash_mint_instruction, &success_checks));
+        }
+        if self.flash_mint {
+            instructions.push((&flash_mint_instruction, &self.flash_mint_checks));
+        }
+        if self.repay {
+            instructions.push((&flash_repay_instruction, &self.flash_repay_checks));
+        }
+
+        let result = self
+            .mollusk
+            .process_and_validate_instruction_chain(&instructions, accounts);
+
+        let get_account = |key: &Pubkey| KeyedAccount {
+            key: *key,
+            account: result.get_account(key).unwrap().clone(),
+        };
+        FlashMintResult {
+            recipient_wrapped_token_account: get_account(&recipient.key),
+            wrapped_mint: get_account(&wrapped_mint.key),
+            unwrapped_escrow: get_account(&unwrapped_escrow.key),
+            flash_mint_guard: get_account(&flash_mint_guard.key),
+            wrap_limits: get_account(&wrap_limits.key),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use {
+    crate::helpers::common::{init_mollusk, KeyedAccount},
+    mollusk_svm::{result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    spl_token_wrap::instruction::{migrate_backpointer, migrate_state},
+};
+
+pub struct MigrateStateBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    state_account: KeyedAccount,
+    mint: Pubkey,
+    mint_owner: Pubkey,
+    unwrapped_mint: Option<KeyedAccount>,
+}
+
+impl<'a> MigrateStateBuilder<'a> {
+    pub fn new(state_account: KeyedAccount, mint: Pubkey) -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            state_account,
+            mint,
+            mint_owner: Pubkey::default(),
+            unwrapped_mint: None,
+        }
+    }
+
+    pub fn mint_owner(mut self, owner: Pubkey) -> Self {
+        self.mint_owner = owner;
+        self
+    }
+
+    pub fn unwrapped_mint(mut self, unwrapped_mint: KeyedAccount) -> Self {
+        self.unwrapped_mint = Some(unwrapped_mint);
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> KeyedAccount {
+        let mint_account = Account {
+            owner: self.mint_owner,
+            ..Account::default()
+        };
+        let mut accounts = vec![self.state_account.pair(), (self.mint, mint_account)];
+
+        let instruction = match &self.unwrapped_mint {
+            Some(unwrapped_mint) => {
+                accounts.push(unwrapped_mint.pair());
+                migrate_backpointer(
+                    &spl_token_wrap::id(),
+                    &self.state_account.key,
+                    &self.mint,
+                    &unwrapped_mint.key,
+                )
+            }
+            None => migrate_state(&spl_token_wrap::id(), &self.state_account.key, &self.mint),
+        };
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, &accounts, &self.checks);
+
+        KeyedAccount {
+            key: self.state_account.key,
+            account: result.get_account(&self.state_account.key).unwrap().clone(),
+        }
+    }
+}
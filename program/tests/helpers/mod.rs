@@ -1,12 +1,38 @@
+pub mod accept_canonical_pointer_builder;
+pub mod cancel_canonical_pointer_builder;
+pub mod close_canonical_pointer_builder;
 pub mod close_stuck_escrow_builder;
+pub mod close_wrapped_mint_builder;
 pub mod common;
+pub mod confidential;
+pub mod crank_wrap;
 pub mod create_mint_builder;
+pub mod execute_admin_action_builder;
 pub mod extensions;
+pub mod flash_mint_builder;
 pub mod metadata;
+pub mod migrate_state_builder;
 pub mod mint_builder;
+pub mod permit;
+pub mod propose_canonical_pointer_builder;
+pub mod reconcile_escrow_builder;
+pub mod register_wrapped_mint_builder;
+pub mod repair_escrow_builder;
+pub mod set_allowlist_mode_builder;
 pub mod set_canonical_pointer_builder;
+pub mod set_crank_wrap_recipient_builder;
+pub mod set_escrow_surplus_policy_builder;
+pub mod set_flash_mint_fee_builder;
+pub mod set_protocol_fee_config_builder;
+pub mod set_protocol_fee_override_builder;
+pub mod set_wrap_limits_builder;
+pub mod set_wrapped_mint_admin_builder;
 pub mod sync_to_spl_token_builder;
 pub mod sync_to_token_2022_builder;
 pub mod token_account_builder;
 pub mod unwrap_builder;
+pub mod unwrap_sol_builder;
+pub mod update_allowlist_builder;
+pub mod update_confidential_config_builder;
 pub mod wrap_builder;
+pub mod wrap_sol_builder;
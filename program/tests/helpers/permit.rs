@@ -0,0 +1,154 @@
+use {
+    solana_account::Account,
+    solana_ed25519_program::new_ed25519_instruction_with_signature,
+    solana_instruction::Instruction,
+    solana_keypair::Keypair,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    solana_signer::Signer,
+    spl_token_wrap::{
+        get_permit_delegate_address, get_permit_nonce_address,
+        permit::{Permit, PermitAction},
+        state::{PermitNonce, VersionedState},
+    },
+};
+
+/// Owner signing a permit off-chain, with the terms of the permit
+pub struct PermitSigner {
+    pub owner: Keypair,
+    pub delegate: Pubkey,
+    pub nonce: u64,
+    pub expiry: i64,
+    pub permit_nonce: Account,
+    pub permit_nonce_address: Option<Pubkey>,
+    pub signed_amount: Option<u64>,
+    pub signed_source: Option<Pubkey>,
+    pub signed_recipient: Option<Pubkey>,
+    pub signer: Option<Keypair>,
+    pub with_signature: bool,
+}
+
+impl Default for PermitSigner {
+    fn default() -> Self {
+        Self {
+            owner: Keypair::new(),
+            delegate: get_permit_delegate_address(),
+            nonce: 0,
+            expiry: i64::MAX,
+            permit_nonce: Account {
+                lamports: Rent::default().minimum_balance(PermitNonce::LEN),
+                ..Default::default()
+            },
+            permit_nonce_address: None,
+            signed_amount: None,
+            signed_source: None,
+            signed_recipient: None,
+            signer: None,
+            with_signature: true,
+        }
+    }
+}
+
+impl PermitSigner {
+    pub fn delegate(mut self, delegate: Pubkey) -> Self {
+        self.delegate = delegate;
+        self
+    }
+
+    pub fn nonce(mut self, nonce: u64) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    pub fn expiry(mut self, expiry: i64) -> Self {
+        self.expiry = expiry;
+        self
+    }
+
+    /// Starting state of the nonce account of the owner, which defaults to
+    /// an uninitialized account pre-funded for rent
+    pub fn permit_nonce(mut self, account: Account) -> Self {
+        self.permit_nonce = account;
+        self
+    }
+
+    pub fn permit_nonce_address(mut self, key: Pubkey) -> Self {
+        self.permit_nonce_address = Some(key);
+        self
+    }
+
+    /// Signs a permit for another amount than the one in the instruction
+    pub fn signed_amount(mut self, amount: u64) -> Self {
+        self.signed_amount = Some(amount);
+        self
+    }
+
+    /// Signs a permit spending from another token account than the one in the
+    /// instruction
+    pub fn signed_source(mut self, source: Pubkey) -> Self {
+        self.signed_source = Some(source);
+        self
+    }
+
+    /// Signs a permit for another recipient than the one in the instruction,
+    /// as if a relayer swapped the recipient
+    pub fn signed_recipient(mut self, recipient: Pubkey) -> Self {
+        self.signed_recipient = Some(recipient);
+        self
+    }
+
+    /// Signs the permit with another key than the owner
+    pub fn signer(mut self, signer: Keypair) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Leaves the ed25519 program instruction out of the transaction
+    pub fn without_signature(mut self) -> Self {
+        self.with_signature = false;
+        self
+    }
+
+    pub fn permit_nonce_key(&self) -> Pubkey {
+        self.permit_nonce_address
+            .unwrap_or_else(|| get_permit_nonce_address(&self.owner.pubkey()))
+    }
+
+    /// Builds the instructions sysvar of a transaction verifying the signed
+    /// permit with the ed25519 program before the permit instruction
+    pub fn instructions_sysvar(
+        &self,
+        action: PermitAction,
+        mint: &Pubkey,
+        amount: u64,
+        source: &Pubkey,
+        recipient: &Pubkey,
+        permit_instruction: &Instruction,
+    ) -> (Pubkey, Account) {
+        let message = Permit {
+            program_id: spl_token_wrap::id(),
+            action: action as u8,
+            mint: *mint,
+            amount: self.signed_amount.unwrap_or(amount).into(),
+            source: self.signed_source.unwrap_or(*source),
+            recipient: self.signed_recipient.unwrap_or(*recipient),
+            nonce: self.nonce.into(),
+            expiry: self.expiry.into(),
+        }
+        .message();
+        let signer = self.signer.as_ref().unwrap_or(&self.owner);
+        let signature = signer.sign_message(&message);
+        let ed25519_instruction = new_ed25519_instruction_with_signature(
+            &message,
+            signature.as_ref().try_into().unwrap(),
+            &signer.pubkey().to_bytes(),
+        );
+
+        let instructions = if self.with_signature {
+            vec![ed25519_instruction, permit_instruction.clone()]
+        } else {
+            vec![permit_instruction.clone()]
+        };
+        mollusk_svm::instructions_sysvar::keyed_account(instructions.iter())
+    }
+}
@@ -0,0 +1,222 @@
+use {
+    crate::helpers::{
+        common::{init_mollusk, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+    },
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        get_canonical_pointer_address, get_pending_canonical_pointer_address,
+        get_scoped_canonical_pointer_address, get_scoped_pending_canonical_pointer_address,
+        state::{PendingCanonicalPointer, VersionedState},
+    },
+};
+
+pub struct ProposeCanonicalPointerResult {
+    pub pending_pointer: KeyedAccount,
+}
+
+pub struct ProposeCanonicalPointerBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    unwrapped_mint_authority: Option<Pubkey>,
+    is_authority_signer: bool,
+    canonical_pointer: Option<KeyedAccount>,
+    pending_pointer: Option<KeyedAccount>,
+    unwrapped_mint: Option<KeyedAccount>,
+    new_program_id: Option<Pubkey>,
+    enforce_on_wrap: bool,
+    change_delay: u64,
+    wrapped_token_program: Option<Pubkey>,
+    unscoped_canonical_pointer: Option<KeyedAccount>,
+}
+
+impl Default for ProposeCanonicalPointerBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            unwrapped_mint_authority: None,
+            is_authority_signer: true,
+            canonical_pointer: None,
+            pending_pointer: None,
+            unwrapped_mint: None,
+            new_program_id: None,
+            enforce_on_wrap: false,
+            change_delay: 0,
+            wrapped_token_program: None,
+            unscoped_canonical_pointer: None,
+        }
+    }
+}
+
+impl<'a> ProposeCanonicalPointerBuilder<'a> {
+    pub fn unwrapped_mint_authority(mut self, key: Pubkey) -> Self {
+        self.unwrapped_mint_authority = Some(key);
+        self
+    }
+
+    pub fn authority_not_signer(mut self) -> Self {
+        self.is_authority_signer = false;
+        self
+    }
+
+    pub fn canonical_pointer(mut self, account: KeyedAccount) -> Self {
+        self.canonical_pointer = Some(account);
+        self
+    }
+
+    pub fn pending_pointer(mut self, account: KeyedAccount) -> Self {
+        self.pending_pointer = Some(account);
+        self
+    }
+
+    pub fn unwrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.unwrapped_mint = Some(account);
+        self
+    }
+
+    pub fn new_program_id(mut self, program_id: Pubkey) -> Self {
+        self.new_program_id = Some(program_id);
+        self
+    }
+
+    pub fn enforce_on_wrap(mut self) -> Self {
+        self.enforce_on_wrap = true;
+        self
+    }
+
+    pub fn change_delay(mut self, change_delay: u64) -> Self {
+        self.change_delay = change_delay;
+        self
+    }
+
+    pub fn unix_timestamp(mut self, unix_timestamp: i64) -> Self {
+        self.mollusk.sysvars.clock.unix_timestamp = unix_timestamp;
+        self
+    }
+
+    pub fn wrapped_token_program(mut self, program_id: Pubkey) -> Self {
+        self.wrapped_token_program = Some(program_id);
+        self
+    }
+
+    /// Unscoped pointer passed alongside a scoped pointer, which defaults to
+    /// an uninitialized account
+    pub fn unscoped_canonical_pointer(mut self, account: KeyedAccount) -> Self {
+        self.unscoped_canonical_pointer = Some(account);
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> ProposeCanonicalPointerResult {
+        let unwrapped_mint_authority_key = self
+            .unwrapped_mint_authority
+            .unwrap_or_else(Pubkey::new_unique);
+
+        let unwrapped_mint = self.unwrapped_mint.unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken)
+                .mint_authority(unwrapped_mint_authority_key)
+                .build()
+        });
+
+        let (pointer_address, pending_pointer_address) = match &self.wrapped_token_program {
+            Some(wrapped_token_program) => (
+                get_scoped_canonical_pointer_address(&unwrapped_mint.key, wrapped_token_program),
+                get_scoped_pending_canonical_pointer_address(
+                    &unwrapped_mint.key,
+                    wrapped_token_program,
+                ),
+            ),
+            None => (
+                get_canonical_pointer_address(&unwrapped_mint.key),
+                get_pending_canonical_pointer_address(&unwrapped_mint.key),
+            ),
+        };
+
+        let canonical_pointer = self.canonical_pointer.unwrap_or_else(|| KeyedAccount {
+            key: pointer_address,
+            account: Account::default(),
+        });
+
+        let pending_pointer = self.pending_pointer.unwrap_or_else(|| KeyedAccount {
+            key: pending_pointer_address,
+            account: Account {
+                lamports: Rent::default().minimum_balance(PendingCanonicalPointer::LEN),
+                ..Default::default()
+            },
+        });
+
+        let unscoped_canonical_pointer = self.wrapped_token_program.map(|_| {
+            self.unscoped_canonical_pointer
+                .clone()
+                .unwrap_or_else(|| KeyedAccount {
+                    key: get_canonical_pointer_address(&unwrapped_mint.key),
+                    account: Account::default(),
+                })
+        });
+
+        let new_program_id = self.new_program_id.unwrap_or_else(Pubkey::new_unique);
+
+        let unwrapped_mint_authority = KeyedAccount {
+            key: unwrapped_mint_authority_key,
+            account: Account::default(),
+        };
+
+        let mut instruction = spl_token_wrap::instruction::propose_canonical_pointer(
+            &spl_token_wrap::id(),
+            &unwrapped_mint_authority.key,
+            &canonical_pointer.key,
+            &pending_pointer.key,
+            &unwrapped_mint.key,
+            self.wrapped_token_program.as_ref(),
+            unscoped_canonical_pointer
+                .as_ref()
+                .map(|account| &account.key),
+            &new_program_id,
+            self.enforce_on_wrap,
+            self.change_delay,
+            None,
+            None,
+            &[],
+        );
+
+        // Allow testing with non-signer authority for negative test cases
+        if !self.is_authority_signer {
+            instruction.accounts[0].is_signer = false;
+        }
+
+        let mut accounts = vec![
+            unwrapped_mint_authority.pair(),
+            canonical_pointer.pair(),
+            pending_pointer.pair(),
+            unwrapped_mint.pair(),
+            keyed_account_for_system_program(),
+        ];
+        if let Some(unscoped_canonical_pointer) = &unscoped_canonical_pointer {
+            accounts.push(unscoped_canonical_pointer.pair());
+        }
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, &accounts, &self.checks);
+
+        ProposeCanonicalPointerResult {
+            pending_pointer: KeyedAccount {
+                key: pending_pointer.key,
+                account: result.get_account(&pending_pointer.key).unwrap().clone(),
+            },
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use {
+    crate::helpers::{
+        common::{init_mollusk, versioned_state_data, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+        token_account_builder::TokenAccountBuilder,
+    },
+    mollusk_svm::{result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        get_escrow_address, get_escrow_surplus_policy_address, get_wrap_limits_address,
+        get_wrapped_mint_address, get_wrapped_mint_authority,
+        instruction::reconcile_escrow,
+        state::{EscrowSurplusPolicy, SurplusAction, VersionedState},
+    },
+};
+
+pub struct ReconcileEscrowResult {
+    pub escrow: KeyedAccount,
+    pub wrapped_mint: KeyedAccount,
+    pub destination: KeyedAccount,
+    pub wrap_limits: KeyedAccount,
+}
+
+pub struct ReconcileEscrowBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    unwrapped_token_program: TokenProgram,
+    wrapped_token_program: TokenProgram,
+    escrow_amount: u64,
+    wrapped_supply: u64,
+    action: SurplusAction,
+    policy_target: Option<Pubkey>,
+    policy: Option<KeyedAccount>,
+    is_policy_initialized: bool,
+    destination: Option<KeyedAccount>,
+    wrap_limits: Option<Account>,
+    is_claimant_signer: bool,
+}
+
+impl Default for ReconcileEscrowBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            unwrapped_token_program: TokenProgram::SplToken,
+            wrapped_token_program: TokenProgram::SplToken2022,
+            escrow_amount: 150_000,
+            wrapped_supply: 100_000,
+            action: SurplusAction::MintToRecipient,
+            policy_target: None,
+            policy: None,
+            is_policy_initialized: true,
+            destination: None,
+            wrap_limits: None,
+            is_claimant_signer: true,
+        }
+    }
+}
+
+impl<'a> ReconcileEscrowBuilder<'a> {
+    pub fn unwrapped_token_program(mut self, program: TokenProgram) -> Self {
+        self.unwrapped_token_program = program;
+        self
+    }
+
+    pub fn wrapped_token_program(mut self, program: TokenProgram) -> Self {
+        self.wrapped_token_program = program;
+        self
+    }
+
+    pub fn escrow_amount(mut self, amount: u64) -> Self {
+        self.escrow_amount = amount;
+        self
+    }
+
+    pub fn wrapped_supply(mut self, supply: u64) -> Self {
+        self.wrapped_supply = supply;
+        self
+    }
+
+    /// Uses a policy returning the surplus to a claimant instead of minting it
+    pub fn return_to_claimant(mut self) -> Self {
+        self.action = SurplusAction::ReturnToClaimant;
+        self
+    }
+
+    /// Replaces the recipient or claimant stored in the policy
+    pub fn policy_target(mut self, key: Pubkey) -> Self {
+        self.policy_target = Some(key);
+        self
+    }
+
+    pub fn policy(mut self, account: KeyedAccount) -> Self {
+        self.policy = Some(account);
+        self
+    }
+
+    /// Leaves the policy account at the expected address uninitialized
+    pub fn uninitialized_policy(mut self) -> Self {
+        self.is_policy_initialized = false;
+        self
+    }
+
+    pub fn destination(mut self, account: KeyedAccount) -> Self {
+        self.destination = Some(account);
+        self
+    }
+
+    pub fn claimant_not_signer(mut self) -> Self {
+        self.is_claimant_signer = false;
+        self
+    }
+
+    /// Sets the wrap limits of the unwrapped mint, starting from the given
+    /// account state
+    pub fn wrap_limits(mut self, account: Account) -> Self {
+        self.wrap_limits = Some(account);
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> ReconcileEscrowResult {
+        let unwrapped_mint = MintBuilder::new()
+            .token_program(self.unwrapped_token_program)
+            .mint_authority(Pubkey::new_unique())
+            .build();
+
+        let wrapped_mint_address =
+            get_wrapped_mint_address(&unwrapped_mint.key, &self.wrapped_token_program.id());
+        let wrapped_mint_authority = get_wrapped_mint_authority(&wrapped_mint_address);
+        let wrapped_mint = MintBuilder::new()
+            .token_program(self.wrapped_token_program)
+            .mint_key(wrapped_mint_address)
+            .mint_authority(wrapped_mint_authority)
+            .supply(self.wrapped_supply)
+            .build();
+
+        let escrow = TokenAccountBuilder::new()
+            .token_program(self.unwrapped_token_program)
+            .mint(unwrapped_mint.clone())
+            .owner(wrapped_mint_authority)
+            .amount(self.escrow_amount)
+            .account_key(get_escrow_address(
+                &unwrapped_mint.key,
+                &self.unwrapped_token_program.id(),
+                &self.wrapped_token_program.id(),
+            ))
+            .build();
+
+        let claimant = Pubkey::new_unique();
+        let destination = self
+            .destination
+            .clone()
+            .unwrap_or_else(|| match self.action {
+                SurplusAction::MintToRecipient => TokenAccountBuilder::new()
+                    .token_program(self.wrapped_token_program)
+                    .mint(wrapped_mint.clone())
+                    .owner(Pubkey::new_unique())
+                    .build(),
+                SurplusAction::ReturnToClaimant => TokenAccountBuilder::new()
+                    .token_program(self.unwrapped_token_program)
+                    .mint(unwrapped_mint.clone())
+                    .owner(claimant)
+                    .amount(0)
+                    .build(),
+            });
+
+        let policy_target = self.policy_target.unwrap_or(match self.action {
+            SurplusAction::MintToRecipient => destination.key,
+            SurplusAction::ReturnToClaimant => claimant,
+        });
+        let policy = self.policy.clone().unwrap_or_else(|| KeyedAccount {
+            key: get_escrow_surplus_policy_address(&wrapped_mint.key),
+            account: if self.is_policy_initialized {
+                Account {
+                    lamports: Rent::default().minimum_balance(EscrowSurplusPolicy::LEN),
+                    owner: spl_token_wrap::id(),
+                    data: versioned_state_data(&EscrowSurplusPolicy {
+                        action: self.action as u8,
+                        target: policy_target,
+                    }),
+                    ..Default::default()
+                }
+            } else {
+                Account::default()
+            },
+        });
+
+        let wrap_limits = KeyedAccount {
+            key: get_wrap_limits_address(&unwrapped_mint.key),
+            account: self.wrap_limits.clone().unwrap_or_default(),
+        };
+
+        let claimant = (self.action == SurplusAction::ReturnToClaimant).then_some(claimant);
+        let mut instruction = reconcile_escrow(
+            &spl_token_wrap::id(),
+            &escrow.key,
+            &unwrapped_mint.key,
+            &wrapped_mint.key,
+            &wrapped_mint_authority,
+            &policy.key,
+            &destination.key,
+            &self.unwrapped_token_program.id(),
+            &self.wrapped_token_program.id(),
+            &wrap_limits.key,
+            claimant.as_ref(),
+        );
+
+        // Allow testing with non-signer claimant for negative test cases
+        if !self.is_claimant_signer {
+            instruction.accounts[9].is_signer = false;
+        }
+
+        let mut accounts = vec![
+            escrow.pair(),
+            unwrapped_mint.pair(),
+            wrapped_mint.pair(),
+            (wrapped_mint_authority, Account::default()),
+            policy.pair(),
+            destination.pair(),
+            self.unwrapped_token_program.keyed_account(),
+            self.wrapped_token_program.keyed_account(),
+            wrap_limits.pair(),
+        ];
+        if let Some(claimant) = claimant {
+            accounts.push((claimant, Account::default()));
+        }
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, &accounts, &self.checks);
+
+        let get_account = |key: &Pubkey| KeyedAccount {
+            key: *key,
+            account: result.get_account(key).unwrap().clone(),
+        };
+        ReconcileEscrowResult {
+            escrow: get_account(&escrow.key),
+            wrapped_mint: get_account(&wrapped_mint.key),
+            destination: get_account(&destination.key),
+            wrap_limits: get_account(&wrap_limits.key),
+        }
+    }
+}
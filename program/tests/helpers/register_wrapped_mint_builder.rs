@@ -0,0 +1,184 @@
+use {
+    crate::helpers::{
+        common::{init_mollusk, versioned_state_data, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+    },
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        get_wrapped_mint_address, get_wrapped_mint_authority, get_wrapped_mint_backpointer_address,
+        get_wrapped_mint_index_address, get_wrapped_mint_registry_address,
+        get_wrapped_mint_registry_page_address,
+        instruction::register_wrapped_mint,
+        state::{Backpointer, VersionedState},
+    },
+};
+
+pub struct RegisterWrappedMintResult {
+    pub unwrapped_mint: Pubkey,
+    pub wrapped_mint: KeyedAccount,
+    pub registry: KeyedAccount,
+    pub registry_page: KeyedAccount,
+    pub wrapped_mint_index: KeyedAccount,
+}
+
+pub struct RegisterWrappedMintBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    unwrapped_mint_addr: Option<Pubkey>,
+    wrapped_token_program: TokenProgram,
+    wrapped_mint: Option<KeyedAccount>,
+    backpointer: Option<KeyedAccount>,
+    registry_account: Option<Account>,
+    registry_page: Option<KeyedAccount>,
+    wrapped_mint_index_account: Option<Account>,
+}
+
+impl Default for RegisterWrappedMintBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            unwrapped_mint_addr: None,
+            wrapped_token_program: TokenProgram::SplToken2022,
+            wrapped_mint: None,
+            backpointer: None,
+            registry_account: None,
+            registry_page: None,
+            wrapped_mint_index_account: None,
+        }
+    }
+}
+
+impl<'a> RegisterWrappedMintBuilder<'a> {
+    pub fn unwrapped_mint_addr(mut self, key: Pubkey) -> Self {
+        self.unwrapped_mint_addr = Some(key);
+        self
+    }
+
+    pub fn wrapped_token_program(mut self, program: TokenProgram) -> Self {
+        self.wrapped_token_program = program;
+        self
+    }
+
+    pub fn wrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.wrapped_mint = Some(account);
+        self
+    }
+
+    pub fn backpointer(mut self, account: KeyedAccount) -> Self {
+        self.backpointer = Some(account);
+        self
+    }
+
+    pub fn registry_account(mut self, account: Account) -> Self {
+        self.registry_account = Some(account);
+        self
+    }
+
+    pub fn registry_page(mut self, page: KeyedAccount) -> Self {
+        self.registry_page = Some(page);
+        self
+    }
+
+    pub fn wrapped_mint_index_account(mut self, account: Account) -> Self {
+        self.wrapped_mint_index_account = Some(account);
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> RegisterWrappedMintResult {
+        let unwrapped_mint = self.unwrapped_mint_addr.unwrap_or_else(Pubkey::new_unique);
+        let wrapped_token_program_id = self.wrapped_token_program.id();
+
+        let wrapped_mint = self.wrapped_mint.unwrap_or_else(|| {
+            let wrapped_mint_address =
+                get_wrapped_mint_address(&unwrapped_mint, &wrapped_token_program_id);
+            MintBuilder::new()
+                .token_program(self.wrapped_token_program)
+                .mint_key(wrapped_mint_address)
+                .mint_authority(get_wrapped_mint_authority(&wrapped_mint_address))
+                .build()
+        });
+
+        // Mints created before the registry have no provenance data
+        let backpointer = self.backpointer.unwrap_or_else(|| KeyedAccount {
+            key: get_wrapped_mint_backpointer_address(&wrapped_mint.key),
+            account: Account {
+                lamports: Rent::default().minimum_balance(Backpointer::LEN),
+                owner: spl_token_wrap::id(),
+                data: versioned_state_data(&Backpointer::without_provenance(unwrapped_mint)),
+                ..Default::default()
+            },
+        });
+
+        let funded_account = Account {
+            lamports: 100_000_000,
+            ..Default::default()
+        };
+        let registry = KeyedAccount {
+            key: get_wrapped_mint_registry_address(),
+            account: self
+                .registry_account
+                .unwrap_or_else(|| funded_account.clone()),
+        };
+        let registry_page = self.registry_page.unwrap_or_else(|| KeyedAccount {
+            key: get_wrapped_mint_registry_page_address(0),
+            account: funded_account.clone(),
+        });
+        let wrapped_mint_index = KeyedAccount {
+            key: get_wrapped_mint_index_address(&unwrapped_mint),
+            account: self
+                .wrapped_mint_index_account
+                .unwrap_or_else(|| funded_account.clone()),
+        };
+
+        let instruction = register_wrapped_mint(
+            &spl_token_wrap::id(),
+            &wrapped_mint.key,
+            &backpointer.key,
+            &unwrapped_mint,
+            &wrapped_token_program_id,
+            &registry.key,
+            &registry_page.key,
+            &wrapped_mint_index.key,
+        );
+
+        let accounts = &[
+            wrapped_mint.pair(),
+            backpointer.pair(),
+            (unwrapped_mint, Account::default()),
+            keyed_account_for_system_program(),
+            self.wrapped_token_program.keyed_account(),
+            registry.pair(),
+            registry_page.pair(),
+            wrapped_mint_index.pair(),
+        ];
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, accounts, &self.checks);
+
+        let get_account = |key: &Pubkey| KeyedAccount {
+            key: *key,
+            account: result.get_account(key).unwrap().clone(),
+        };
+        RegisterWrappedMintResult {
+            unwrapped_mint,
+            wrapped_mint: get_account(&wrapped_mint.key),
+            registry: get_account(&registry.key),
+            registry_page: get_account(&registry_page.key),
+            wrapped_mint_index: get_account(&wrapped_mint_index.key),
+        }
+    }
+}
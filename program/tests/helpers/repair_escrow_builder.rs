@@ -0,0 +1,156 @@
+use {
+    crate::helpers::{
+        common::{init_mollusk, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+        token_account_builder::TokenAccountBuilder,
+    },
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
+    mollusk_svm_programs_token::token2022,
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    spl_token_wrap::{
+        get_escrow_address, get_wrapped_mint_address, get_wrapped_mint_authority, instruction,
+    },
+};
+
+pub struct RepairEscrowResult {
+    pub escrow: KeyedAccount,
+    pub payer: KeyedAccount,
+}
+
+pub struct RepairEscrowBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    escrow_account: Option<KeyedAccount>,
+    payer: Option<KeyedAccount>,
+    unwrapped_mint: Option<KeyedAccount>,
+    wrapped_mint: Option<KeyedAccount>,
+    wrapped_mint_authority: Option<Pubkey>,
+}
+
+impl Default for RepairEscrowBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            escrow_account: None,
+            payer: None,
+            unwrapped_mint: None,
+            wrapped_mint: None,
+            wrapped_mint_authority: None,
+        }
+    }
+}
+
+impl<'a> RepairEscrowBuilder<'a> {
+    pub fn escrow_account(mut self, account: KeyedAccount) -> Self {
+        self.escrow_account = Some(account);
+        self
+    }
+
+    pub fn payer(mut self, account: KeyedAccount) -> Self {
+        self.payer = Some(account);
+        self
+    }
+
+    pub fn unwrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.unwrapped_mint = Some(account);
+        self
+    }
+
+    pub fn wrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.wrapped_mint = Some(account);
+        self
+    }
+
+    pub fn wrapped_mint_authority(mut self, authority: Pubkey) -> Self {
+        self.wrapped_mint_authority = Some(authority);
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> RepairEscrowResult {
+        let unwrapped_mint = self.unwrapped_mint.unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken2022)
+                .mint_authority(Pubkey::new_unique())
+                .build()
+        });
+
+        let wrapped_mint = self.wrapped_mint.unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken2022)
+                .mint_key(get_wrapped_mint_address(
+                    &unwrapped_mint.key,
+                    &spl_token_2022_interface::id(),
+                ))
+                .mint_authority(Pubkey::new_unique())
+                .build()
+        });
+
+        let wrapped_mint_authority = self
+            .wrapped_mint_authority
+            .unwrap_or_else(|| get_wrapped_mint_authority(&wrapped_mint.key));
+
+        let escrow_account = self.escrow_account.unwrap_or_else(|| {
+            TokenAccountBuilder::new()
+                .token_program(TokenProgram::SplToken2022)
+                .mint(unwrapped_mint.clone())
+                .owner(get_wrapped_mint_authority(&wrapped_mint.key))
+                .account_key(get_escrow_address(
+                    &unwrapped_mint.key,
+                    &spl_token_2022_interface::id(),
+                    &wrapped_mint.account.owner,
+                ))
+                .build()
+        });
+
+        let payer = self.payer.unwrap_or_else(|| KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account {
+                lamports: 1_000_000_000,
+                ..Default::default()
+            },
+        });
+
+        let instruction = instruction::repair_escrow(
+            &spl_token_wrap::id(),
+            &escrow_account.key,
+            &payer.key,
+            &unwrapped_mint.key,
+            &wrapped_mint.key,
+            &wrapped_mint_authority,
+        );
+
+        let accounts = &[
+            escrow_account.pair(),
+            payer.pair(),
+            unwrapped_mint.pair(),
+            wrapped_mint.pair(),
+            (wrapped_mint_authority, Account::default()),
+            keyed_account_for_system_program(),
+            token2022::keyed_account(),
+        ];
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, accounts, &self.checks);
+
+        let get_account = |key: &Pubkey| KeyedAccount {
+            key: *key,
+            account: result.get_account(key).unwrap().clone(),
+        };
+        RepairEscrowResult {
+            escrow: get_account(&escrow_account.key),
+            payer: get_account(&payer.key),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use {
+    crate::helpers::{
+        common::{init_mollusk, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+    },
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        get_allowlist_address,
+        instruction::set_allowlist_mode,
+        state::{Allowlist, VersionedState},
+    },
+};
+
+pub struct SetAllowlistModeResult {
+    pub allowlist: KeyedAccount,
+}
+
+pub struct SetAllowlistModeBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    unwrapped_mint_authority: Option<Pubkey>,
+    is_authority_signer: bool,
+    unwrapped_mint: Option<KeyedAccount>,
+    allowlist: Option<KeyedAccount>,
+    enabled: bool,
+}
+
+impl Default for SetAllowlistModeBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            unwrapped_mint_authority: None,
+            is_authority_signer: true,
+            unwrapped_mint: None,
+            allowlist: None,
+            enabled: true,
+        }
+    }
+}
+
+impl<'a> SetAllowlistModeBuilder<'a> {
+    pub fn unwrapped_mint_authority(mut self, key: Pubkey) -> Self {
+        self.unwrapped_mint_authority = Some(key);
+        self
+    }
+
+    pub fn authority_not_signer(mut self) -> Self {
+        self.is_authority_signer = false;
+        self
+    }
+
+    pub fn unwrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.unwrapped_mint = Some(account);
+        self
+    }
+
+    pub fn allowlist(mut self, account: KeyedAccount) -> Self {
+        self.allowlist = Some(account);
+        self
+    }
+
+    pub fn disabled(mut self) -> Self {
+        self.enabled = false;
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> SetAllowlistModeResult {
+        let unwrapped_mint_authority = self
+            .unwrapped_mint_authority
+            .unwrap_or_else(Pubkey::new_unique);
+
+        let unwrapped_mint = self.unwrapped_mint.unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken)
+                .mint_authority(unwrapped_mint_authority)
+                .build()
+        });
+
+        let allowlist = self.allowlist.unwrap_or_else(|| KeyedAccount {
+            key: get_allowlist_address(&unwrapped_mint.key),
+            account: Account {
+                lamports: Rent::default().minimum_balance(Allowlist::LEN),
+                ..Default::default()
+            },
+        });
+
+        let mut instruction = set_allowlist_mode(
+            &spl_token_wrap::id(),
+            &unwrapped_mint_authority,
+            &allowlist.key,
+            &unwrapped_mint.key,
+            self.enabled,
+            None,
+            None,
+            &[],
+        );
+
+        // Allow testing with non-signer authority for negative test cases
+        if !self.is_authority_signer {
+            instruction.accounts[0].is_signer = false;
+        }
+
+        let accounts = &[
+            (unwrapped_mint_authority, Account::default()),
+            allowlist.pair(),
+            unwrapped_mint.pair(),
+            keyed_account_for_system_program(),
+        ];
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, accounts, &self.checks);
+
+        SetAllowlistModeResult {
+            allowlist: KeyedAccount {
+                key: allowlist.key,
+                account: result.get_account(&allowlist.key).unwrap().clone(),
+            },
+        }
+    }
+}
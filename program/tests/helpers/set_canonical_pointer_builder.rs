@@ -1,13 +1,16 @@
 use {
     crate::helpers::{
-        common::{init_mollusk, KeyedAccount, TokenProgram},
+        common::{init_mollusk, KeyedAccount, TokenProgram, TransferAuthority},
         mint_builder::MintBuilder,
     },
     mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
     solana_account::Account,
     solana_pubkey::Pubkey,
     solana_rent::Rent,
-    spl_token_wrap::get_canonical_pointer_address,
+    spl_token_wrap::{
+        get_canonical_pointer_address, get_scoped_canonical_pointer_address,
+        state::{CanonicalDeploymentPointer, VersionedState},
+    },
 };
 
 pub struct SetCanonicalPointerResult {
@@ -19,9 +22,15 @@ pub struct SetCanonicalPointerBuilder<'a> {
     checks: Vec<Check<'a>>,
     unwrapped_mint_authority: Option<Pubkey>,
     is_authority_signer: bool,
+    multisig: Option<TransferAuthority>,
+    source_metadata: Option<KeyedAccount>,
     canonical_pointer: Option<KeyedAccount>,
     unwrapped_mint: Option<KeyedAccount>,
     new_program_id: Option<Pubkey>,
+    enforce_on_wrap: bool,
+    change_delay: u64,
+    wrapped_token_program: Option<Pubkey>,
+    unscoped_canonical_pointer: Option<KeyedAccount>,
 }
 
 impl Default for SetCanonicalPointerBuilder<'_> {
@@ -31,9 +40,15 @@ impl Default for SetCanonicalPointerBuilder<'_> {
             checks: vec![],
             unwrapped_mint_authority: None,
             is_authority_signer: true,
+            multisig: None,
+            source_metadata: None,
             canonical_pointer: None,
             unwrapped_mint: None,
             new_program_id: None,
+            enforce_on_wrap: false,
+            change_delay: 0,
+            wrapped_token_program: None,
+            unscoped_canonical_pointer: None,
         }
     }
 }
@@ -49,6 +64,16 @@ impl<'a> SetCanonicalPointerBuilder<'a> {
         self
     }
 
+    pub fn multisig(mut self, multisig: TransferAuthority) -> Self {
+        self.multisig = Some(multisig);
+        self
+    }
+
+    pub fn source_metadata(mut self, account: KeyedAccount) -> Self {
+        self.source_metadata = Some(account);
+        self
+    }
+
     pub fn canonical_pointer(mut self, account: KeyedAccount) -> Self {
         self.canonical_pointer = Some(account);
         self
@@ -64,15 +89,40 @@ impl<'a> SetCanonicalPointerBuilder<'a> {
         self
     }
 
+    pub fn enforce_on_wrap(mut self) -> Self {
+        self.enforce_on_wrap = true;
+        self
+    }
+
+    pub fn change_delay(mut self, change_delay: u64) -> Self {
+        self.change_delay = change_delay;
+        self
+    }
+
+    pub fn wrapped_token_program(mut self, program_id: Pubkey) -> Self {
+        self.wrapped_token_program = Some(program_id);
+        self
+    }
+
+    /// Unscoped pointer passed alongside a scoped pointer, which defaults to
+    /// an uninitialized account
+    pub fn unscoped_canonical_pointer(mut self, account: KeyedAccount) -> Self {
+        self.unscoped_canonical_pointer = Some(account);
+        self
+    }
+
     pub fn check(mut self, check: Check<'a>) -> Self {
         self.checks.push(check);
         self
     }
 
     pub fn execute(mut self) -> SetCanonicalPointerResult {
-        let unwrapped_mint_authority_key = self
-            .unwrapped_mint_authority
-            .unwrap_or_else(Pubkey::new_unique);
+        let unwrapped_mint_authority_key = self.unwrapped_mint_authority.unwrap_or_else(|| {
+            self.multisig
+                .as_ref()
+                .map(|multisig| multisig.keyed_account.key)
+                .unwrap_or_else(Pubkey::new_unique)
+        });
 
         let unwrapped_mint = self.unwrapped_mint.unwrap_or_else(|| {
             MintBuilder::new()
@@ -81,31 +131,61 @@ impl<'a> SetCanonicalPointerBuilder<'a> {
                 .build()
         });
 
-        let expected_pointer_address = get_canonical_pointer_address(&unwrapped_mint.key);
+        let expected_pointer_address = match &self.wrapped_token_program {
+            Some(wrapped_token_program) => {
+                get_scoped_canonical_pointer_address(&unwrapped_mint.key, wrapped_token_program)
+            }
+            None => get_canonical_pointer_address(&unwrapped_mint.key),
+        };
 
         let canonical_pointer = self.canonical_pointer.unwrap_or_else(|| KeyedAccount {
             key: expected_pointer_address,
             account: Account {
-                lamports: Rent::default().minimum_balance(std::mem::size_of::<
-                    spl_token_wrap::state::CanonicalDeploymentPointer,
-                >()),
+                lamports: Rent::default().minimum_balance(CanonicalDeploymentPointer::LEN),
                 ..Default::default()
             },
         });
 
+        let unscoped_canonical_pointer = self.wrapped_token_program.map(|_| {
+            self.unscoped_canonical_pointer
+                .clone()
+                .unwrap_or_else(|| KeyedAccount {
+                    key: get_canonical_pointer_address(&unwrapped_mint.key),
+                    account: Account::default(),
+                })
+        });
+
         let new_program_id = self.new_program_id.unwrap_or_else(Pubkey::new_unique);
 
-        let unwrapped_mint_authority = KeyedAccount {
-            key: unwrapped_mint_authority_key,
-            account: Account::default(),
-        };
+        let unwrapped_mint_authority = self
+            .multisig
+            .as_ref()
+            .map(|multisig| multisig.keyed_account.clone())
+            .unwrap_or(KeyedAccount {
+                key: unwrapped_mint_authority_key,
+                account: Account::default(),
+            });
+        let multisig_signers = self
+            .multisig
+            .as_ref()
+            .map(|multisig| multisig.signers.clone())
+            .unwrap_or_default();
 
         let mut instruction = spl_token_wrap::instruction::set_canonical_pointer(
             &spl_token_wrap::id(),
             &unwrapped_mint_authority.key,
             &canonical_pointer.key,
             &unwrapped_mint.key,
+            self.wrapped_token_program.as_ref(),
+            unscoped_canonical_pointer
+                .as_ref()
+                .map(|account| &account.key),
             &new_program_id,
+            self.enforce_on_wrap,
+            self.change_delay,
+            self.source_metadata.as_ref().map(|account| &account.key),
+            None,
+            &multisig_signers.iter().collect::<Vec<_>>(),
         );
 
         // Allow testing with non-signer authority for negative test cases
@@ -113,12 +193,24 @@ impl<'a> SetCanonicalPointerBuilder<'a> {
             instruction.accounts[0].is_signer = false;
         }
 
-        let accounts = &[
+        let mut accounts = vec![
             unwrapped_mint_authority.pair(),
             canonical_pointer.pair(),
             unwrapped_mint.pair(),
             keyed_account_for_system_program(),
         ];
+        if let Some(unscoped_canonical_pointer) = &unscoped_canonical_pointer {
+            accounts.push(unscoped_canonical_pointer.pair());
+        }
+        if let Some(source_metadata) = &self.source_metadata {
+            // A Token-2022 mint holding its own metadata is already provided
+            if source_metadata.key != unwrapped_mint.key {
+                accounts.push(source_metadata.pair());
+            }
+        }
+        for signer in &multisig_signers {
+            accounts.push((*signer, Account::default()));
+        }
 
         if self.checks.is_empty() {
             self.checks.push(Check::success());
@@ -126,7 +218,7 @@ impl<'a> SetCanonicalPointerBuilder<'a> {
 
         let result =
             self.mollusk
-                .process_and_validate_instruction(&instruction, accounts, &self.checks);
+                .process_and_validate_instruction(&instruction, &accounts, &self.checks);
 
         SetCanonicalPointerResult {
             canonical_pointer: KeyedAccount {
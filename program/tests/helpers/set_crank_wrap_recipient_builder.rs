@@ -0,0 +1,132 @@
+use {
+    crate::helpers::{
+        common::{init_mollusk, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+    },
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        get_crank_wrap_delegate_address,
+        instruction::set_crank_wrap_recipient,
+        state::{CrankWrapDelegate, VersionedState},
+    },
+};
+
+pub struct SetCrankWrapRecipientResult {
+    pub crank_wrap_delegate: KeyedAccount,
+}
+
+pub struct SetCrankWrapRecipientBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    owner: Option<Pubkey>,
+    is_owner_signer: bool,
+    unwrapped_mint: Option<KeyedAccount>,
+    crank_wrap_delegate: Option<KeyedAccount>,
+    recipient: Option<Pubkey>,
+}
+
+impl Default for SetCrankWrapRecipientBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            owner: None,
+            is_owner_signer: true,
+            unwrapped_mint: None,
+            crank_wrap_delegate: None,
+            recipient: None,
+        }
+    }
+}
+
+impl<'a> SetCrankWrapRecipientBuilder<'a> {
+    pub fn owner(mut self, key: Pubkey) -> Self {
+        self.owner = Some(key);
+        self
+    }
+
+    pub fn owner_not_signer(mut self) -> Self {
+        self.is_owner_signer = false;
+        self
+    }
+
+    pub fn unwrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.unwrapped_mint = Some(account);
+        self
+    }
+
+    pub fn crank_wrap_delegate(mut self, account: KeyedAccount) -> Self {
+        self.crank_wrap_delegate = Some(account);
+        self
+    }
+
+    pub fn recipient(mut self, key: Pubkey) -> Self {
+        self.recipient = Some(key);
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> SetCrankWrapRecipientResult {
+        let owner = self.owner.unwrap_or_else(Pubkey::new_unique);
+
+        let unwrapped_mint = self.unwrapped_mint.unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken)
+                .mint_authority(Pubkey::new_unique())
+                .build()
+        });
+
+        let crank_wrap_delegate = self.crank_wrap_delegate.unwrap_or_else(|| KeyedAccount {
+            key: get_crank_wrap_delegate_address(&owner, &unwrapped_mint.key),
+            account: Account {
+                lamports: Rent::default().minimum_balance(CrankWrapDelegate::LEN),
+                ..Default::default()
+            },
+        });
+
+        let mut instruction = set_crank_wrap_recipient(
+            &spl_token_wrap::id(),
+            &owner,
+            &crank_wrap_delegate.key,
+            &unwrapped_mint.key,
+            &self.recipient.unwrap_or_else(Pubkey::new_unique),
+        );
+
+        // Allow testing with non-signer owner for negative test cases
+        if !self.is_owner_signer {
+            instruction.accounts[0].is_signer = false;
+        }
+
+        let accounts = &[
+            (owner, Account::default()),
+            crank_wrap_delegate.pair(),
+            unwrapped_mint.pair(),
+            keyed_account_for_system_program(),
+        ];
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, accounts, &self.checks);
+
+        SetCrankWrapRecipientResult {
+            crank_wrap_delegate: KeyedAccount {
+                key: crank_wrap_delegate.key,
+                account: result
+                    .get_account(&crank_wrap_delegate.key)
+                    .unwrap()
+                    .clone(),
+            },
+        }
+    }
+}
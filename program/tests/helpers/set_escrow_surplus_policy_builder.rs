@@ -0,0 +1,161 @@
+use {
+    crate::helpers::{
+        common::{init_mollusk, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+    },
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        get_escrow_surplus_policy_address, get_wrapped_mint_address,
+        instruction::set_escrow_surplus_policy,
+        state::{EscrowSurplusPolicy, SurplusAction, VersionedState},
+    },
+};
+
+pub struct SetEscrowSurplusPolicyResult {
+    pub policy: KeyedAccount,
+}
+
+pub struct SetEscrowSurplusPolicyBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    unwrapped_mint_authority: Option<Pubkey>,
+    is_authority_signer: bool,
+    unwrapped_mint: Option<KeyedAccount>,
+    wrapped_mint: Option<KeyedAccount>,
+    policy: Option<KeyedAccount>,
+    action: SurplusAction,
+    target: Option<Pubkey>,
+}
+
+impl Default for SetEscrowSurplusPolicyBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            unwrapped_mint_authority: None,
+            is_authority_signer: true,
+            unwrapped_mint: None,
+            wrapped_mint: None,
+            policy: None,
+            action: SurplusAction::MintToRecipient,
+            target: None,
+        }
+    }
+}
+
+impl<'a> SetEscrowSurplusPolicyBuilder<'a> {
+    pub fn unwrapped_mint_authority(mut self, key: Pubkey) -> Self {
+        self.unwrapped_mint_authority = Some(key);
+        self
+    }
+
+    pub fn authority_not_signer(mut self) -> Self {
+        self.is_authority_signer = false;
+        self
+    }
+
+    pub fn unwrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.unwrapped_mint = Some(account);
+        self
+    }
+
+    pub fn wrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.wrapped_mint = Some(account);
+        self
+    }
+
+    pub fn policy(mut self, account: KeyedAccount) -> Self {
+        self.policy = Some(account);
+        self
+    }
+
+    pub fn action(mut self, action: SurplusAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    pub fn target(mut self, key: Pubkey) -> Self {
+        self.target = Some(key);
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> SetEscrowSurplusPolicyResult {
+        let unwrapped_mint_authority = self
+            .unwrapped_mint_authority
+            .unwrap_or_else(Pubkey::new_unique);
+
+        let unwrapped_mint = self.unwrapped_mint.unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken)
+                .mint_authority(unwrapped_mint_authority)
+                .build()
+        });
+
+        let wrapped_mint = self.wrapped_mint.unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken2022)
+                .mint_key(get_wrapped_mint_address(
+                    &unwrapped_mint.key,
+                    &spl_token_2022_interface::id(),
+                ))
+                .build()
+        });
+
+        let policy = self.policy.unwrap_or_else(|| KeyedAccount {
+            key: get_escrow_surplus_policy_address(&wrapped_mint.key),
+            account: Account {
+                lamports: Rent::default().minimum_balance(EscrowSurplusPolicy::LEN),
+                ..Default::default()
+            },
+        });
+
+        let mut instruction = set_escrow_surplus_policy(
+            &spl_token_wrap::id(),
+            &unwrapped_mint_authority,
+            &policy.key,
+            &unwrapped_mint.key,
+            &wrapped_mint.key,
+            self.action,
+            &self.target.unwrap_or_else(Pubkey::new_unique),
+            None,
+            None,
+            &[],
+        );
+
+        // Allow testing with non-signer authority for negative test cases
+        if !self.is_authority_signer {
+            instruction.accounts[0].is_signer = false;
+        }
+
+        let accounts = &[
+            (unwrapped_mint_authority, Account::default()),
+            policy.pair(),
+            unwrapped_mint.pair(),
+            wrapped_mint.pair(),
+            keyed_account_for_system_program(),
+        ];
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, accounts, &self.checks);
+
+        SetEscrowSurplusPolicyResult {
+            policy: KeyedAccount {
+                key: policy.key,
+                account: result.get_account(&policy.key).unwrap().clone(),
+            },
+        }
+    }
+}
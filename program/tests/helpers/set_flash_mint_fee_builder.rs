@@ -0,0 +1,153 @@
+use {
+    crate::helpers::{
+        common::{init_mollusk, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+    },
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        get_flash_mint_guard_address, get_wrapped_mint_address,
+        instruction::set_flash_mint_fee,
+        state::{FlashMintGuard, VersionedState},
+    },
+};
+
+pub struct SetFlashMintFeeResult {
+    pub flash_mint_guard: KeyedAccount,
+}
+
+pub struct SetFlashMintFeeBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    unwrapped_mint_authority: Option<Pubkey>,
+    is_authority_signer: bool,
+    unwrapped_mint: Option<KeyedAccount>,
+    wrapped_mint: Option<KeyedAccount>,
+    flash_mint_guard: Option<KeyedAccount>,
+    fee_basis_points: u16,
+}
+
+impl Default for SetFlashMintFeeBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            unwrapped_mint_authority: None,
+            is_authority_signer: true,
+            unwrapped_mint: None,
+            wrapped_mint: None,
+            flash_mint_guard: None,
+            fee_basis_points: 30,
+        }
+    }
+}
+
+impl<'a> SetFlashMintFeeBuilder<'a> {
+    pub fn unwrapped_mint_authority(mut self, key: Pubkey) -> Self {
+        self.unwrapped_mint_authority = Some(key);
+        self
+    }
+
+    pub fn authority_not_signer(mut self) -> Self {
+        self.is_authority_signer = false;
+        self
+    }
+
+    pub fn unwrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.unwrapped_mint = Some(account);
+        self
+    }
+
+    pub fn wrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.wrapped_mint = Some(account);
+        self
+    }
+
+    pub fn flash_mint_guard(mut self, account: KeyedAccount) -> Self {
+        self.flash_mint_guard = Some(account);
+        self
+    }
+
+    pub fn fee_basis_points(mut self, fee_basis_points: u16) -> Self {
+        self.fee_basis_points = fee_basis_points;
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> SetFlashMintFeeResult {
+        let unwrapped_mint_authority = self
+            .unwrapped_mint_authority
+            .unwrap_or_else(Pubkey::new_unique);
+
+        let unwrapped_mint = self.unwrapped_mint.unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken)
+                .mint_authority(unwrapped_mint_authority)
+                .build()
+        });
+
+        let wrapped_mint = self.wrapped_mint.unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken2022)
+                .mint_key(get_wrapped_mint_address(
+                    &unwrapped_mint.key,
+                    &spl_token_2022_interface::id(),
+                ))
+                .build()
+        });
+
+        let flash_mint_guard = self.flash_mint_guard.unwrap_or_else(|| KeyedAccount {
+            key: get_flash_mint_guard_address(&wrapped_mint.key),
+            account: Account {
+                lamports: Rent::default().minimum_balance(FlashMintGuard::LEN),
+                ..Default::default()
+            },
+        });
+
+        let mut instruction = set_flash_mint_fee(
+            &spl_token_wrap::id(),
+            &unwrapped_mint_authority,
+            &flash_mint_guard.key,
+            &unwrapped_mint.key,
+            &wrapped_mint.key,
+            self.fee_basis_points,
+            None,
+            None,
+            &[],
+        );
+
+        // Allow testing with non-signer authority for negative test cases
+        if !self.is_authority_signer {
+            instruction.accounts[0].is_signer = false;
+        }
+
+        let accounts = &[
+            (unwrapped_mint_authority, Account::default()),
+            flash_mint_guard.pair(),
+            unwrapped_mint.pair(),
+            wrapped_mint.pair(),
+            keyed_account_for_system_program(),
+        ];
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, accounts, &self.checks);
+
+        SetFlashMintFeeResult {
+            flash_mint_guard: KeyedAccount {
+                key: flash_mint_guard.key,
+                account: result.get_account(&flash_mint_guard.key).unwrap().clone(),
+            },
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use {
+    crate::helpers::common::{init_mollusk, program_data_account, KeyedAccount},
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        get_protocol_fee_config_address,
+        instruction::set_protocol_fee_config,
+        state::{ProtocolFeeConfig, VersionedState},
+    },
+};
+
+pub struct SetProtocolFeeConfigResult {
+    pub protocol_fee_config: KeyedAccount,
+}
+
+pub struct SetProtocolFeeConfigBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    upgrade_authority: Option<Pubkey>,
+    is_authority_signer: bool,
+    program_data: Option<KeyedAccount>,
+    protocol_fee_config: Option<KeyedAccount>,
+    wrap_fee_basis_points: u16,
+    unwrap_fee_basis_points: u16,
+    fee_destination: Option<Pubkey>,
+}
+
+impl Default for SetProtocolFeeConfigBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            upgrade_authority: None,
+            is_authority_signer: true,
+            program_data: None,
+            protocol_fee_config: None,
+            wrap_fee_basis_points: 0,
+            unwrap_fee_basis_points: 0,
+            fee_destination: None,
+        }
+    }
+}
+
+impl<'a> SetProtocolFeeConfigBuilder<'a> {
+    pub fn upgrade_authority(mut self, key: Pubkey) -> Self {
+        self.upgrade_authority = Some(key);
+        self
+    }
+
+    pub fn authority_not_signer(mut self) -> Self {
+        self.is_authority_signer = false;
+        self
+    }
+
+    /// Program data account, defaults to one whose upgrade authority is the
+    /// signing authority
+    pub fn program_data(mut self, account: KeyedAccount) -> Self {
+        self.program_data = Some(account);
+        self
+    }
+
+    pub fn protocol_fee_config(mut self, account: KeyedAccount) -> Self {
+        self.protocol_fee_config = Some(account);
+        self
+    }
+
+    pub fn fees(mut self, wrap_fee_basis_points: u16, unwrap_fee_basis_points: u16) -> Self {
+        self.wrap_fee_basis_points = wrap_fee_basis_points;
+        self.unwrap_fee_basis_points = unwrap_fee_basis_points;
+        self
+    }
+
+    pub fn fee_destination(mut self, key: Pubkey) -> Self {
+        self.fee_destination = Some(key);
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> SetProtocolFeeConfigResult {
+        let upgrade_authority = self.upgrade_authority.unwrap_or_else(Pubkey::new_unique);
+
+        let program_data = self
+            .program_data
+            .unwrap_or_else(|| program_data_account(Some(upgrade_authority)));
+
+        let protocol_fee_config = self.protocol_fee_config.unwrap_or_else(|| KeyedAccount {
+            key: get_protocol_fee_config_address(),
+            account: Account {
+                lamports: Rent::default().minimum_balance(ProtocolFeeConfig::LEN),
+                ..Default::default()
+            },
+        });
+
+        let mut instruction = set_protocol_fee_config(
+            &spl_token_wrap::id(),
+            &upgrade_authority,
+            &protocol_fee_config.key,
+            &program_data.key,
+            self.wrap_fee_basis_points,
+            self.unwrap_fee_basis_points,
+            &self.fee_destination.unwrap_or_else(Pubkey::new_unique),
+        );
+
+        // Allow testing with non-signer authority for negative test cases
+        if !self.is_authority_signer {
+            instruction.accounts[0].is_signer = false;
+        }
+
+        let accounts = &[
+            (upgrade_authority, Account::default()),
+            protocol_fee_config.pair(),
+            program_data.pair(),
+            keyed_account_for_system_program(),
+        ];
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, accounts, &self.checks);
+
+        SetProtocolFeeConfigResult {
+            protocol_fee_config: KeyedAccount {
+                key: protocol_fee_config.key,
+                account: result
+                    .get_account(&protocol_fee_config.key)
+                    .unwrap()
+                    .clone(),
+            },
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use {
+    crate::helpers::{
+        common::{init_mollusk, program_data_account, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+    },
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        get_protocol_fee_override_address,
+        instruction::set_protocol_fee_override,
+        state::{ProtocolFeeOverride, VersionedState},
+    },
+};
+
+pub struct SetProtocolFeeOverrideResult {
+    pub protocol_fee_override: KeyedAccount,
+}
+
+pub struct SetProtocolFeeOverrideBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    upgrade_authority: Option<Pubkey>,
+    program_data: Option<KeyedAccount>,
+    unwrapped_mint: Option<KeyedAccount>,
+    protocol_fee_override: Option<KeyedAccount>,
+    enabled: bool,
+    wrap_fee_basis_points: u16,
+    unwrap_fee_basis_points: u16,
+}
+
+impl Default for SetProtocolFeeOverrideBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            upgrade_authority: None,
+            program_data: None,
+            unwrapped_mint: None,
+            protocol_fee_override: None,
+            enabled: true,
+            wrap_fee_basis_points: 0,
+            unwrap_fee_basis_points: 0,
+        }
+    }
+}
+
+impl<'a> SetProtocolFeeOverrideBuilder<'a> {
+    pub fn upgrade_authority(mut self, key: Pubkey) -> Self {
+        self.upgrade_authority = Some(key);
+        self
+    }
+
+    /// Program data account, defaults to one whose upgrade authority is the
+    /// signing authority
+    pub fn program_data(mut self, account: KeyedAccount) -> Self {
+        self.program_data = Some(account);
+        self
+    }
+
+    pub fn unwrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.unwrapped_mint = Some(account);
+        self
+    }
+
+    pub fn protocol_fee_override(mut self, account: KeyedAccount) -> Self {
+        self.protocol_fee_override = Some(account);
+        self
+    }
+
+    pub fn fees(mut self, wrap_fee_basis_points: u16, unwrap_fee_basis_points: u16) -> Self {
+        self.wrap_fee_basis_points = wrap_fee_basis_points;
+        self.unwrap_fee_basis_points = unwrap_fee_basis_points;
+        self
+    }
+
+    pub fn disabled(mut self) -> Self {
+        self.enabled = false;
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> SetProtocolFeeOverrideResult {
+        let upgrade_authority = self.upgrade_authority.unwrap_or_else(Pubkey::new_unique);
+
+        let program_data = self
+            .program_data
+            .unwrap_or_else(|| program_data_account(Some(upgrade_authority)));
+
+        let unwrapped_mint = self.unwrapped_mint.unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken)
+                .mint_authority(Pubkey::new_unique())
+                .build()
+        });
+
+        let protocol_fee_override = self.protocol_fee_override.unwrap_or_else(|| KeyedAccount {
+            key: get_protocol_fee_override_address(&unwrapped_mint.key),
+            account: Account {
+                lamports: Rent::default().minimum_balance(ProtocolFeeOverride::LEN),
+                ..Default::default()
+            },
+        });
+
+        let instruction = set_protocol_fee_override(
+            &spl_token_wrap::id(),
+            &upgrade_authority,
+            &protocol_fee_override.key,
+            &unwrapped_mint.key,
+            &program_data.key,
+            self.enabled,
+            self.wrap_fee_basis_points,
+            self.unwrap_fee_basis_points,
+        );
+
+        let accounts = &[
+            (upgrade_authority, Account::default()),
+            protocol_fee_override.pair(),
+            unwrapped_mint.pair(),
+            program_data.pair(),
+            keyed_account_for_system_program(),
+        ];
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, accounts, &self.checks);
+
+        SetProtocolFeeOverrideResult {
+            protocol_fee_override: KeyedAccount {
+                key: protocol_fee_override.key,
+                account: result
+                    .get_account(&protocol_fee_override.key)
+                    .unwrap()
+                    .clone(),
+            },
+        }
+    }
+}
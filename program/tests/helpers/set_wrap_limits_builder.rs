@@ -0,0 +1,197 @@
+use {
+    crate::helpers::{
+        common::{init_mollusk, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+    },
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        get_wrap_limits_address, get_wrapped_mint_address,
+        instruction::set_wrap_limits,
+        state::{VersionedState, WrapLimits},
+    },
+};
+
+pub struct SetWrapLimitsResult {
+    pub wrap_limits: KeyedAccount,
+}
+
+pub struct SetWrapLimitsBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    unwrapped_mint_authority: Option<Pubkey>,
+    is_authority_signer: bool,
+    unwrapped_mint: Option<KeyedAccount>,
+    wrap_limits: Option<KeyedAccount>,
+    spl_token_wrapped_mint: Option<KeyedAccount>,
+    spl_token_wrapped_supply: Option<u64>,
+    token_2022_wrapped_supply: Option<u64>,
+    unwrap_only: bool,
+    max_wrapped_supply: u64,
+    max_wrapped_per_epoch: u64,
+}
+
+impl Default for SetWrapLimitsBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            unwrapped_mint_authority: None,
+            is_authority_signer: true,
+            unwrapped_mint: None,
+            wrap_limits: None,
+            spl_token_wrapped_mint: None,
+            spl_token_wrapped_supply: None,
+            token_2022_wrapped_supply: None,
+            unwrap_only: false,
+            max_wrapped_supply: 0,
+            max_wrapped_per_epoch: 0,
+        }
+    }
+}
+
+impl<'a> SetWrapLimitsBuilder<'a> {
+    pub fn unwrapped_mint_authority(mut self, key: Pubkey) -> Self {
+        self.unwrapped_mint_authority = Some(key);
+        self
+    }
+
+    pub fn authority_not_signer(mut self) -> Self {
+        self.is_authority_signer = false;
+        self
+    }
+
+    pub fn unwrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.unwrapped_mint = Some(account);
+        self
+    }
+
+    pub fn wrap_limits(mut self, account: KeyedAccount) -> Self {
+        self.wrap_limits = Some(account);
+        self
+    }
+
+    /// Replaces the wrapped mint under SPL Token
+    pub fn spl_token_wrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.spl_token_wrapped_mint = Some(account);
+        self
+    }
+
+    /// Creates the wrapped mint under the given token program with the given
+    /// supply. Wrapped mints are uninitialized otherwise.
+    pub fn wrapped_supply(mut self, token_program: TokenProgram, supply: u64) -> Self {
+        match token_program {
+            TokenProgram::SplToken => self.spl_token_wrapped_supply = Some(supply),
+            TokenProgram::SplToken2022 => self.token_2022_wrapped_supply = Some(supply),
+        }
+        self
+    }
+
+    pub fn unwrap_only(mut self) -> Self {
+        self.unwrap_only = true;
+        self
+    }
+
+    pub fn max_wrapped_supply(mut self, amount: u64) -> Self {
+        self.max_wrapped_supply = amount;
+        self
+    }
+
+    pub fn max_wrapped_per_epoch(mut self, amount: u64) -> Self {
+        self.max_wrapped_per_epoch = amount;
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> SetWrapLimitsResult {
+        let unwrapped_mint_authority = self
+            .unwrapped_mint_authority
+            .unwrap_or_else(Pubkey::new_unique);
+
+        let unwrapped_mint = self.unwrapped_mint.unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken)
+                .mint_authority(unwrapped_mint_authority)
+                .build()
+        });
+
+        let wrap_limits = self.wrap_limits.unwrap_or_else(|| KeyedAccount {
+            key: get_wrap_limits_address(&unwrapped_mint.key),
+            account: Account {
+                lamports: Rent::default().minimum_balance(WrapLimits::LEN),
+                ..Default::default()
+            },
+        });
+
+        let wrapped_mint = |token_program: TokenProgram, supply: Option<u64>| {
+            let key = get_wrapped_mint_address(&unwrapped_mint.key, &token_program.id());
+            match supply {
+                Some(supply) => MintBuilder::new()
+                    .token_program(token_program)
+                    .mint_key(key)
+                    .supply(supply)
+                    .build(),
+                None => KeyedAccount {
+                    key,
+                    account: Account::default(),
+                },
+            }
+        };
+        let spl_token_wrapped_mint = self
+            .spl_token_wrapped_mint
+            .clone()
+            .unwrap_or_else(|| wrapped_mint(TokenProgram::SplToken, self.spl_token_wrapped_supply));
+        let token_2022_wrapped_mint =
+            wrapped_mint(TokenProgram::SplToken2022, self.token_2022_wrapped_supply);
+
+        let mut instruction = set_wrap_limits(
+            &spl_token_wrap::id(),
+            &unwrapped_mint_authority,
+            &wrap_limits.key,
+            &unwrapped_mint.key,
+            &spl_token_wrapped_mint.key,
+            &token_2022_wrapped_mint.key,
+            self.unwrap_only,
+            self.max_wrapped_supply,
+            self.max_wrapped_per_epoch,
+            None,
+            None,
+            &[],
+        );
+
+        // Allow testing with non-signer authority for negative test cases
+        if !self.is_authority_signer {
+            instruction.accounts[0].is_signer = false;
+        }
+
+        let accounts = &[
+            (unwrapped_mint_authority, Account::default()),
+            wrap_limits.pair(),
+            unwrapped_mint.pair(),
+            keyed_account_for_system_program(),
+            spl_token_wrapped_mint.pair(),
+            token_2022_wrapped_mint.pair(),
+        ];
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, accounts, &self.checks);
+
+        SetWrapLimitsResult {
+            wrap_limits: KeyedAccount {
+                key: wrap_limits.key,
+                account: result.get_account(&wrap_limits.key).unwrap().clone(),
+            },
+        }
+    }
+}
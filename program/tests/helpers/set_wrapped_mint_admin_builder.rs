@@ -0,0 +1,153 @@
+use {
+    crate::helpers::{
+        common::{init_mollusk, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+    },
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        get_wrapped_mint_address, get_wrapped_mint_admin_address,
+        instruction::set_wrapped_mint_admin,
+        state::{VersionedState, WrappedMintAdmin},
+    },
+};
+
+pub struct SetWrappedMintAdminResult {
+    pub wrapped_mint_admin: KeyedAccount,
+}
+
+pub struct SetWrappedMintAdminBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    unwrapped_mint_authority: Option<Pubkey>,
+    is_authority_signer: bool,
+    unwrapped_mint: Option<KeyedAccount>,
+    wrapped_mint: Option<KeyedAccount>,
+    wrapped_mint_admin: Option<KeyedAccount>,
+    admin: Pubkey,
+}
+
+impl Default for SetWrappedMintAdminBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            unwrapped_mint_authority: None,
+            is_authority_signer: true,
+            unwrapped_mint: None,
+            wrapped_mint: None,
+            wrapped_mint_admin: None,
+            admin: Pubkey::new_unique(),
+        }
+    }
+}
+
+impl<'a> SetWrappedMintAdminBuilder<'a> {
+    pub fn unwrapped_mint_authority(mut self, key: Pubkey) -> Self {
+        self.unwrapped_mint_authority = Some(key);
+        self
+    }
+
+    pub fn authority_not_signer(mut self) -> Self {
+        self.is_authority_signer = false;
+        self
+    }
+
+    pub fn unwrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.unwrapped_mint = Some(account);
+        self
+    }
+
+    pub fn wrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.wrapped_mint = Some(account);
+        self
+    }
+
+    pub fn wrapped_mint_admin(mut self, account: KeyedAccount) -> Self {
+        self.wrapped_mint_admin = Some(account);
+        self
+    }
+
+    pub fn admin(mut self, admin: Pubkey) -> Self {
+        self.admin = admin;
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> SetWrappedMintAdminResult {
+        let unwrapped_mint_authority = self
+            .unwrapped_mint_authority
+            .unwrap_or_else(Pubkey::new_unique);
+
+        let unwrapped_mint = self.unwrapped_mint.unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken)
+                .mint_authority(unwrapped_mint_authority)
+                .build()
+        });
+
+        let wrapped_mint = self.wrapped_mint.unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken2022)
+                .mint_key(get_wrapped_mint_address(
+                    &unwrapped_mint.key,
+                    &spl_token_2022_interface::id(),
+                ))
+                .build()
+        });
+
+        let wrapped_mint_admin = self.wrapped_mint_admin.unwrap_or_else(|| KeyedAccount {
+            key: get_wrapped_mint_admin_address(&wrapped_mint.key),
+            account: Account {
+                lamports: Rent::default().minimum_balance(WrappedMintAdmin::LEN),
+                ..Default::default()
+            },
+        });
+
+        let mut instruction = set_wrapped_mint_admin(
+            &spl_token_wrap::id(),
+            &unwrapped_mint_authority,
+            &wrapped_mint_admin.key,
+            &unwrapped_mint.key,
+            &wrapped_mint.key,
+            &self.admin,
+            None,
+            None,
+            &[],
+        );
+
+        // Allow testing with non-signer authority for negative test cases
+        if !self.is_authority_signer {
+            instruction.accounts[0].is_signer = false;
+        }
+
+        let accounts = &[
+            (unwrapped_mint_authority, Account::default()),
+            wrapped_mint_admin.pair(),
+            unwrapped_mint.pair(),
+            wrapped_mint.pair(),
+            keyed_account_for_system_program(),
+        ];
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, accounts, &self.checks);
+
+        SetWrappedMintAdminResult {
+            wrapped_mint_admin: KeyedAccount {
+                key: wrapped_mint_admin.key,
+                account: result.get_account(&wrapped_mint_admin.key).unwrap().clone(),
+            },
+        }
+    }
+}
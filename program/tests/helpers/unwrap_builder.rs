@@ -1,25 +1,39 @@
 use {
     crate::helpers::{
-        common::{init_mollusk, KeyedAccount, TokenProgram, TransferAuthority},
+        common::{
+            init_mollusk, token_account_owner, KeyedAccount, TokenProgram, TransferAuthority,
+        },
+        confidential::ConfidentialBalance,
+        extensions::MintExtension,
         mint_builder::MintBuilder,
+        permit::PermitSigner,
         token_account_builder::TokenAccountBuilder,
     },
-    mollusk_svm::{result::Check, Mollusk},
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
     solana_account::Account,
     solana_instruction::AccountMeta,
     solana_pubkey::Pubkey,
+    solana_signer::Signer,
     spl_token_2022_interface::{
         extension::{
             transfer_fee::TransferFeeConfig,
             BaseStateWithExtensions,
-            ExtensionType::{ImmutableOwner, TransferFeeConfig as TransferFeeConfigExt},
+            ExtensionType::{
+                ConfidentialTransferAccount, ImmutableOwner,
+                TransferFeeConfig as TransferFeeConfigExt,
+            },
             PodStateWithExtensions,
         },
         pod::PodMint,
     },
     spl_token_wrap::{
-        get_escrow_address, get_wrapped_mint_address, get_wrapped_mint_authority,
-        instruction::unwrap,
+        get_allowlist_address, get_allowlist_entry_address, get_escrow_address,
+        get_flash_mint_guard_address, get_pro_rata_redemption_address,
+        get_protocol_fee_config_address, get_protocol_fee_override_address,
+        get_wrap_limits_address, get_wrap_stats_address, get_wrapped_mint_address,
+        get_wrapped_mint_authority,
+        instruction::{unwrap, unwrap_confidential, unwrap_with_permit},
+        permit::PermitAction,
     },
 };
 
@@ -40,6 +54,22 @@ pub struct UnwrapBuilder<'a> {
     unwrapped_escrow_account: Option<KeyedAccount>,
     extra_accounts: Vec<KeyedAccount>,
     recipient_token_account: Option<KeyedAccount>,
+    wrap_limits: Option<KeyedAccount>,
+    allowlist: Option<Account>,
+    allowlist_entry: Option<Account>,
+    allowlist_entry_address: Option<Pubkey>,
+    protocol_fee_config: Option<Account>,
+    protocol_fee_override: Option<Account>,
+    protocol_fee_vault: Option<KeyedAccount>,
+    flash_mint_guard: Option<Account>,
+    wrap_stats: Option<Account>,
+    pro_rata_redemption: Option<Account>,
+    pro_rata_redemption_address: Option<Pubkey>,
+    wrapped_supply: Option<u64>,
+    confidential_balance: Option<u64>,
+    confidential_proof_amount: Option<u64>,
+    permit: Option<PermitSigner>,
+    policy_accounts: bool,
 }
 
 impl Default for UnwrapBuilder<'_> {
@@ -61,6 +91,22 @@ impl Default for UnwrapBuilder<'_> {
             unwrapped_escrow_account: None,
             extra_accounts: vec![],
             recipient_token_account: None,
+            wrap_limits: None,
+            allowlist: None,
+            allowlist_entry: None,
+            allowlist_entry_address: None,
+            protocol_fee_config: None,
+            protocol_fee_override: None,
+            protocol_fee_vault: None,
+            flash_mint_guard: None,
+            wrap_stats: None,
+            pro_rata_redemption: None,
+            pro_rata_redemption_address: None,
+            wrapped_supply: None,
+            confidential_balance: None,
+            confidential_proof_amount: None,
+            permit: None,
+            policy_accounts: true,
         }
     }
 }
@@ -134,6 +180,115 @@ impl<'a> UnwrapBuilder<'a> {
         self
     }
 
+    /// Enforces the allowlist of the unwrapped mint, starting from the given
+    /// account state
+    pub fn wrap_limits(mut self, account: KeyedAccount) -> Self {
+        self.wrap_limits = Some(account);
+        self
+    }
+
+    pub fn allowlist(mut self, account: Account) -> Self {
+        self.allowlist = Some(account);
+        self
+    }
+
+    /// Allowlist entry of the wrapped token account owner, starting from the given account state
+    pub fn allowlist_entry(mut self, account: Account) -> Self {
+        self.allowlist_entry = Some(account);
+        self
+    }
+
+    pub fn allowlist_entry_address(mut self, key: Pubkey) -> Self {
+        self.allowlist_entry_address = Some(key);
+        self
+    }
+
+    /// Charges the protocol fee of the deployment, starting from the given
+    /// account state
+    pub fn protocol_fee_config(mut self, account: Account) -> Self {
+        self.protocol_fee_config = Some(account);
+        self
+    }
+
+    /// Overrides the protocol fee of the unwrapped mint, starting from the
+    /// given account state
+    pub fn protocol_fee_override(mut self, account: Account) -> Self {
+        self.protocol_fee_override = Some(account);
+        self
+    }
+
+    pub fn protocol_fee_vault(mut self, account: KeyedAccount) -> Self {
+        self.protocol_fee_vault = Some(account);
+        self
+    }
+
+    /// Sets the flash mint guard of the wrapped mint, as left by a `FlashMint`
+    pub fn flash_mint_guard(mut self, account: Account) -> Self {
+        self.flash_mint_guard = Some(account);
+        self
+    }
+
+    /// Leaves out the policy accounts, as in the baseline account layout
+    pub fn without_policy_accounts(mut self) -> Self {
+        self.policy_accounts = false;
+        self
+    }
+
+    /// Records the unwrap in the statistics account, starting from the given
+    /// account state
+    pub fn wrap_stats(mut self, account: Account) -> Self {
+        self.wrap_stats = Some(account);
+        self
+    }
+
+    /// Allows a pro-rata redemption, starting from the given account state
+    pub fn pro_rata_redemption(mut self, account: Account) -> Self {
+        self.pro_rata_redemption = Some(account);
+        self
+    }
+
+    pub fn pro_rata_redemption_address(mut self, key: Pubkey) -> Self {
+        self.pro_rata_redemption_address = Some(key);
+        self
+    }
+
+    /// Supply of the default wrapped mint, defaults to the escrow balance
+    pub fn wrapped_supply(mut self, supply: u64) -> Self {
+        self.wrapped_supply = Some(supply);
+        self
+    }
+
+    /// Unwraps with `UnwrapConfidential` from a confidential available
+    /// balance of the given amount
+    pub fn confidential(mut self, balance: u64) -> Self {
+        self.confidential_balance = Some(balance);
+        self
+    }
+
+    /// Generates the withdraw proofs for a different amount than the one
+    /// unwrapped
+    pub fn confidential_proof_amount(mut self, amount: u64) -> Self {
+        self.confidential_proof_amount = Some(amount);
+        self
+    }
+
+    /// Unwraps with `UnwrapWithPermit` from a token account of the permit
+    /// owner that approved the permit delegate
+    pub fn permit(mut self, permit: PermitSigner) -> Self {
+        self.permit = Some(permit);
+        self
+    }
+
+    pub fn unix_timestamp(mut self, unix_timestamp: i64) -> Self {
+        self.mollusk.sysvars.clock.unix_timestamp = unix_timestamp;
+        self
+    }
+
+    pub fn slot(mut self, slot: u64) -> Self {
+        self.mollusk.sysvars.clock.slot = slot;
+        self
+    }
+
     pub fn add_extra_account(mut self, keyed_account: KeyedAccount) -> Self {
         self.extra_accounts.push(keyed_account);
         self
@@ -144,6 +299,10 @@ impl<'a> UnwrapBuilder<'a> {
         self
     }
 
+    fn escrow_amount(&self) -> u64 {
+        self.escrow_starting_amount.unwrap_or(100_000)
+    }
+
     fn mint_has_transfer_fees(&self, mint: &KeyedAccount) -> bool {
         if let Ok(mint_state) = PodStateWithExtensions::<PodMint>::unpack(&mint.account.data) {
             mint_state.get_extension::<TransferFeeConfig>().is_ok()
@@ -160,13 +319,18 @@ impl<'a> UnwrapBuilder<'a> {
         let wrapped_mint_addr = get_wrapped_mint_address(&unwrapped_mint_addr, &token_program.id());
         let mint_authority = get_wrapped_mint_authority(&wrapped_mint_addr);
 
-        self.wrapped_mint.clone().unwrap_or(KeyedAccount {
-            key: wrapped_mint_addr,
-            account: MintBuilder::new()
+        self.wrapped_mint.clone().unwrap_or_else(|| {
+            let mut builder = MintBuilder::new()
                 .token_program(token_program)
                 .mint_authority(mint_authority)
-                .build()
-                .account,
+                .supply(self.wrapped_supply.unwrap_or(self.escrow_amount()));
+            if self.confidential_balance.is_some() && token_program == TokenProgram::SplToken2022 {
+                builder = builder.with_extension(MintExtension::ConfidentialTransfer);
+            }
+            KeyedAccount {
+                key: wrapped_mint_addr,
+                account: builder.build().account,
+            }
         })
     }
 
@@ -200,13 +364,35 @@ impl<'a> UnwrapBuilder<'a> {
             .wrapped_mint_authority
             .unwrap_or_else(|| get_wrapped_mint_authority(&wrapped_mint.key));
 
-        // Setup wrapped token account to be unwrapped
-        let wrapped_token_account = TokenAccountBuilder::new()
+        // Setup wrapped token account to be unwrapped, from its confidential
+        // balance if requested
+        let confidential_balance = self.confidential_balance.map(ConfidentialBalance::new);
+        let mut builder = TokenAccountBuilder::new()
             .token_program(wrapped_token_program)
             .mint(wrapped_mint.clone())
-            .owner(transfer_authority.keyed_account.key)
-            .amount(self.wrapped_token_starting_amount.unwrap_or(unwrap_amount))
-            .build();
+            .amount(self.wrapped_token_starting_amount.unwrap_or(
+                if confidential_balance.is_some() {
+                    0
+                } else {
+                    unwrap_amount
+                },
+            ));
+        builder = match &self.permit {
+            Some(permit) => builder
+                .owner(permit.owner.pubkey())
+                .delegate(permit.delegate)
+                .delegated_amount(unwrap_amount),
+            None => builder.owner(transfer_authority.keyed_account.key),
+        };
+        let configure_confidential =
+            confidential_balance.is_some() && wrapped_token_program == TokenProgram::SplToken2022;
+        if configure_confidential {
+            builder = builder.with_extension(ConfidentialTransferAccount);
+        }
+        let mut wrapped_token_account = builder.build();
+        if let (Some(balance), true) = (&confidential_balance, configure_confidential) {
+            balance.configure(&mut wrapped_token_account.account);
+        }
 
         // Setup escrow account
         let escrow = self.unwrapped_escrow_account.clone().unwrap_or_else(|| {
@@ -222,7 +408,7 @@ impl<'a> UnwrapBuilder<'a> {
                     self.unwrapped_escrow_owner
                         .unwrap_or(wrapped_mint_authority),
                 )
-                .amount(self.escrow_starting_amount.unwrap_or(100_000))
+                .amount(self.escrow_amount())
                 .account_key(escrow_addr);
 
             // Only add extensions for SPL Token 2022
@@ -257,6 +443,56 @@ impl<'a> UnwrapBuilder<'a> {
             builder.build()
         });
 
+        let wrap_limits = self.wrap_limits.clone().unwrap_or(KeyedAccount {
+            key: get_wrap_limits_address(&unwrapped_mint.key),
+            account: Account::default(),
+        });
+        let allowlist = KeyedAccount {
+            key: get_allowlist_address(&unwrapped_mint.key),
+            account: self.allowlist.clone().unwrap_or_default(),
+        };
+        let allowlist_entry = KeyedAccount {
+            key: self.allowlist_entry_address.unwrap_or_else(|| {
+                get_allowlist_entry_address(
+                    &unwrapped_mint.key,
+                    &token_account_owner(&wrapped_token_account.account),
+                )
+            }),
+            account: self.allowlist_entry.clone().unwrap_or_default(),
+        };
+
+        let protocol_fee_config = KeyedAccount {
+            key: get_protocol_fee_config_address(),
+            account: self.protocol_fee_config.clone().unwrap_or_default(),
+        };
+        let protocol_fee_override = KeyedAccount {
+            key: get_protocol_fee_override_address(&unwrapped_mint.key),
+            account: self.protocol_fee_override.clone().unwrap_or_default(),
+        };
+        let protocol_fee_vault = self.protocol_fee_vault.clone().unwrap_or(KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        });
+        let flash_mint_guard = KeyedAccount {
+            key: get_flash_mint_guard_address(&wrapped_mint.key),
+            account: self.flash_mint_guard.clone().unwrap_or_default(),
+        };
+
+        let wrap_stats = self.wrap_stats.clone().map(|account| KeyedAccount {
+            key: get_wrap_stats_address(&wrapped_mint.key),
+            account,
+        });
+
+        let pro_rata_redemption = self
+            .pro_rata_redemption
+            .clone()
+            .map(|account| KeyedAccount {
+                key: self
+                    .pro_rata_redemption_address
+                    .unwrap_or_else(|| get_pro_rata_redemption_address(&wrapped_mint.key)),
+                account,
+            });
+
         let mut instruction = unwrap(
             &spl_token_wrap::id(),
             &escrow.key,
@@ -268,6 +504,17 @@ impl<'a> UnwrapBuilder<'a> {
             &wrapped_token_account.key,
             &wrapped_mint.key,
             &transfer_authority.keyed_account.key,
+            &wrap_limits.key,
+            &allowlist.key,
+            &allowlist_entry.key,
+            &protocol_fee_config.key,
+            &protocol_fee_override.key,
+            &protocol_fee_vault.key,
+            &flash_mint_guard.key,
+            wrap_stats.as_ref().map(|stats| &stats.key),
+            pro_rata_redemption
+                .as_ref()
+                .map(|redemption| &redemption.key),
             &transfer_authority.signers.iter().collect::<Vec<_>>(),
             unwrap_amount,
         );
@@ -282,12 +529,111 @@ impl<'a> UnwrapBuilder<'a> {
             wrapped_token_account.pair(),
             wrapped_mint.pair(),
             transfer_authority.keyed_account.pair(),
+            wrap_limits.pair(),
+            allowlist.pair(),
+            allowlist_entry.pair(),
+            protocol_fee_config.pair(),
+            protocol_fee_override.pair(),
+            protocol_fee_vault.pair(),
+            flash_mint_guard.pair(),
         ];
 
+        // Baseline layout, which ends the fixed accounts at the transfer
+        // authority
+        if !self.policy_accounts {
+            instruction.accounts.drain(9..16);
+            accounts.drain(9..16);
+        }
+
+        if let Some(balance) = &confidential_balance {
+            let (equality_proof_context, range_proof_context) = balance
+                .withdraw_proof_contexts(self.confidential_proof_amount.unwrap_or(unwrap_amount));
+            let remaining_balance = balance.amount.saturating_sub(unwrap_amount);
+            instruction = unwrap_confidential(
+                &spl_token_wrap::id(),
+                &escrow.key,
+                &recipient.key,
+                &wrapped_mint_authority,
+                &unwrapped_mint.key,
+                &unwrapped_token_program.id(),
+                &wrapped_token_account.key,
+                &wrapped_mint.key,
+                &transfer_authority.keyed_account.key,
+                &wrap_limits.key,
+                &allowlist.key,
+                &allowlist_entry.key,
+                &protocol_fee_config.key,
+                &protocol_fee_override.key,
+                &protocol_fee_vault.key,
+                &flash_mint_guard.key,
+                &equality_proof_context.key,
+                &range_proof_context.key,
+                &transfer_authority.signers.iter().collect::<Vec<_>>(),
+                unwrap_amount,
+                balance.decryptable_balance(remaining_balance),
+            );
+            instruction.accounts[4].pubkey = wrapped_token_program.id();
+            accounts.push(equality_proof_context.pair());
+            accounts.push(range_proof_context.pair());
+        }
+
+        if let Some(wrap_stats) = &wrap_stats {
+            accounts.push(wrap_stats.pair());
+        }
+
+        if let Some(pro_rata_redemption) = &pro_rata_redemption {
+            accounts.push(pro_rata_redemption.pair());
+        }
+
         for signer_key in &transfer_authority.signers {
             accounts.push((*signer_key, Account::default()));
         }
 
+        let permit_nonce = self.permit.as_ref().map(|permit| KeyedAccount {
+            key: permit.permit_nonce_key(),
+            account: permit.permit_nonce.clone(),
+        });
+        if let (Some(permit), Some(permit_nonce)) = (&self.permit, &permit_nonce) {
+            instruction = unwrap_with_permit(
+                &spl_token_wrap::id(),
+                &escrow.key,
+                &recipient.key,
+                &wrapped_mint_authority,
+                &unwrapped_mint.key,
+                &wrapped_token_program.id(),
+                &unwrapped_token_program.id(),
+                &wrapped_token_account.key,
+                &wrapped_mint.key,
+                &wrap_limits.key,
+                &allowlist.key,
+                &allowlist_entry.key,
+                &protocol_fee_config.key,
+                &protocol_fee_override.key,
+                &protocol_fee_vault.key,
+                &flash_mint_guard.key,
+                &permit.delegate,
+                &permit.owner.pubkey(),
+                &permit_nonce.key,
+                unwrap_amount,
+                permit.nonce,
+                permit.expiry,
+            );
+            accounts[8] = (permit.delegate, Account::default());
+            accounts.extend([
+                (permit.owner.pubkey(), Account::default()),
+                permit_nonce.pair(),
+                permit.instructions_sysvar(
+                    PermitAction::Unwrap,
+                    &wrapped_mint.key,
+                    unwrap_amount,
+                    &wrapped_token_account.key,
+                    &recipient.key,
+                    &instruction,
+                ),
+                keyed_account_for_system_program(),
+            ]);
+        }
+
         for extra_account in &self.extra_accounts {
             instruction
                 .accounts
@@ -323,6 +669,27 @@ impl<'a> UnwrapBuilder<'a> {
                 key: recipient.key,
                 account: result.get_account(&recipient.key).unwrap().clone(),
             },
+            protocol_fee_vault: KeyedAccount {
+                key: protocol_fee_vault.key,
+                account: result.get_account(&protocol_fee_vault.key).unwrap().clone(),
+            },
+            wrap_limits: KeyedAccount {
+                key: wrap_limits.key,
+                account: result.get_account(&wrap_limits.key).unwrap().clone(),
+            },
+            wrap_stats: wrap_stats.map(|stats| KeyedAccount {
+                key: stats.key,
+                account: result.get_account(&stats.key).unwrap().clone(),
+            }),
+            pro_rata_redemption: pro_rata_redemption.map(|redemption| KeyedAccount {
+                key: redemption.key,
+                account: result.get_account(&redemption.key).unwrap().clone(),
+            }),
+            confidential_balance,
+            permit_nonce: permit_nonce.map(|permit_nonce| KeyedAccount {
+                key: permit_nonce.key,
+                account: result.get_account(&permit_nonce.key).unwrap().clone(),
+            }),
             extra_accounts: self
                 .extra_accounts
                 .iter()
@@ -340,5 +707,11 @@ pub struct UnwrapResult {
     pub unwrapped_escrow: KeyedAccount,
     pub wrapped_mint: KeyedAccount,
     pub recipient_unwrapped_token: KeyedAccount,
+    pub protocol_fee_vault: KeyedAccount,
+    pub wrap_limits: KeyedAccount,
+    pub wrap_stats: Option<KeyedAccount>,
+    pub pro_rata_redemption: Option<KeyedAccount>,
+    pub confidential_balance: Option<ConfidentialBalance>,
+    pub permit_nonce: Option<KeyedAccount>,
     pub extra_accounts: Vec<KeyedAccount>,
 }
@@ -0,0 +1,381 @@
+use {
+    crate::helpers::{
+        common::{
+            init_mollusk, token_account_owner, KeyedAccount, TokenProgram, TransferAuthority,
+        },
+        mint_builder::MintBuilder,
+        token_account_builder::TokenAccountBuilder,
+        wrap_sol_builder::{native_mint, native_token_account, NATIVE_MINT_DECIMALS},
+    },
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    spl_token_wrap::{
+        get_allowlist_address, get_allowlist_entry_address, get_escrow_address,
+        get_flash_mint_guard_address, get_native_unwrap_account_address,
+        get_pro_rata_redemption_address, get_protocol_fee_config_address,
+        get_protocol_fee_override_address, get_wrap_limits_address, get_wrapped_mint_address,
+        get_wrapped_mint_authority, instruction::unwrap_sol,
+    },
+};
+
+pub struct UnwrapSolResult {
+    pub unwrapped_escrow: KeyedAccount,
+    pub wrapped_mint: KeyedAccount,
+    pub wrapped_token_account: KeyedAccount,
+    pub recipient: KeyedAccount,
+    pub native_unwrap_account: KeyedAccount,
+    pub payer: KeyedAccount,
+    pub wrap_limits: KeyedAccount,
+    pub protocol_fee_vault: KeyedAccount,
+    pub pro_rata_redemption: Option<KeyedAccount>,
+}
+
+pub struct UnwrapSolBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    unwrap_amount: u64,
+    escrow_starting_amount: u64,
+    unwrapped_token_program: TokenProgram,
+    wrapped_token_program: TokenProgram,
+    unwrapped_mint: Option<KeyedAccount>,
+    transfer_authority: Option<TransferAuthority>,
+    wrap_limits: Option<KeyedAccount>,
+    allowlist: Option<Account>,
+    allowlist_entry: Option<Account>,
+    allowlist_entry_address: Option<Pubkey>,
+    protocol_fee_config: Option<Account>,
+    protocol_fee_override: Option<Account>,
+    protocol_fee_vault: Option<KeyedAccount>,
+    native_unwrap_account: Option<KeyedAccount>,
+    recipient: Option<KeyedAccount>,
+    pro_rata_redemption: Option<Account>,
+    wrapped_supply: Option<u64>,
+    payer_lamports: u64,
+    payer_is_recipient: bool,
+}
+
+impl Default for UnwrapSolBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            unwrap_amount: 1_000_000,
+            escrow_starting_amount: 5_000_000,
+            unwrapped_token_program: TokenProgram::SplToken,
+            wrapped_token_program: TokenProgram::SplToken2022,
+            unwrapped_mint: None,
+            transfer_authority: None,
+            wrap_limits: None,
+            allowlist: None,
+            allowlist_entry: None,
+            allowlist_entry_address: None,
+            protocol_fee_config: None,
+            protocol_fee_override: None,
+            protocol_fee_vault: None,
+            native_unwrap_account: None,
+            recipient: None,
+            pro_rata_redemption: None,
+            wrapped_supply: None,
+            payer_lamports: 1_000_000_000,
+            payer_is_recipient: false,
+        }
+    }
+}
+
+impl<'a> UnwrapSolBuilder<'a> {
+    pub fn unwrap_amount(mut self, amount: u64) -> Self {
+        self.unwrap_amount = amount;
+        self
+    }
+
+    pub fn escrow_starting_amount(mut self, amount: u64) -> Self {
+        self.escrow_starting_amount = amount;
+        self
+    }
+
+    pub fn unwrapped_token_program(mut self, program: TokenProgram) -> Self {
+        self.unwrapped_token_program = program;
+        self
+    }
+
+    pub fn wrapped_token_program(mut self, program: TokenProgram) -> Self {
+        self.wrapped_token_program = program;
+        self
+    }
+
+    /// Replaces the native mint
+    pub fn unwrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.unwrapped_mint = Some(account);
+        self
+    }
+
+    pub fn transfer_authority(mut self, auth: TransferAuthority) -> Self {
+        self.transfer_authority = Some(auth);
+        self
+    }
+
+    pub fn wrap_limits(mut self, account: KeyedAccount) -> Self {
+        self.wrap_limits = Some(account);
+        self
+    }
+
+    /// Enforces the allowlist of the unwrapped mint, starting from the given
+    /// account state
+    pub fn allowlist(mut self, account: Account) -> Self {
+        self.allowlist = Some(account);
+        self
+    }
+
+    /// Allowlist entry of the wrapped token account owner, starting from the given account state
+    pub fn allowlist_entry(mut self, account: Account) -> Self {
+        self.allowlist_entry = Some(account);
+        self
+    }
+
+    pub fn allowlist_entry_address(mut self, key: Pubkey) -> Self {
+        self.allowlist_entry_address = Some(key);
+        self
+    }
+
+    /// Charges the protocol fee of the deployment, starting from the given
+    /// account state
+    pub fn protocol_fee_config(mut self, account: Account) -> Self {
+        self.protocol_fee_config = Some(account);
+        self
+    }
+
+    /// Overrides the protocol fee of the native mint, starting from the given
+    /// account state
+    pub fn protocol_fee_override(mut self, account: Account) -> Self {
+        self.protocol_fee_override = Some(account);
+        self
+    }
+
+    pub fn protocol_fee_vault(mut self, account: KeyedAccount) -> Self {
+        self.protocol_fee_vault = Some(account);
+        self
+    }
+
+    /// Provides the pro-rata redemption account, starting from the given
+    /// account state
+    pub fn pro_rata_redemption(mut self, account: Account) -> Self {
+        self.pro_rata_redemption = Some(account);
+        self
+    }
+
+    /// Supply of the wrapped mint, defaults to the escrow amount
+    pub fn wrapped_supply(mut self, supply: u64) -> Self {
+        self.wrapped_supply = Some(supply);
+        self
+    }
+
+    pub fn native_unwrap_account(mut self, account: KeyedAccount) -> Self {
+        self.native_unwrap_account = Some(account);
+        self
+    }
+
+    pub fn recipient(mut self, account: KeyedAccount) -> Self {
+        self.recipient = Some(account);
+        self
+    }
+
+    pub fn payer_lamports(mut self, lamports: u64) -> Self {
+        self.payer_lamports = lamports;
+        self
+    }
+
+    /// Pays the unwrapped lamports out to the payer
+    pub fn payer_is_recipient(mut self) -> Self {
+        self.payer_is_recipient = true;
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> UnwrapSolResult {
+        let unwrapped_mint = self
+            .unwrapped_mint
+            .clone()
+            .unwrap_or_else(|| native_mint(self.unwrapped_token_program));
+
+        let wrapped_mint_address =
+            get_wrapped_mint_address(&unwrapped_mint.key, &self.wrapped_token_program.id());
+        let wrapped_mint_authority = get_wrapped_mint_authority(&wrapped_mint_address);
+        let wrapped_mint = MintBuilder::new()
+            .token_program(self.wrapped_token_program)
+            .mint_authority(wrapped_mint_authority)
+            .supply(self.wrapped_supply.unwrap_or(self.escrow_starting_amount))
+            .decimals(NATIVE_MINT_DECIMALS)
+            .mint_key(wrapped_mint_address)
+            .build();
+
+        let unwrapped_escrow = native_token_account(
+            self.unwrapped_token_program,
+            unwrapped_mint.clone(),
+            wrapped_mint_authority,
+            get_escrow_address(
+                &unwrapped_mint.key,
+                &self.unwrapped_token_program.id(),
+                &self.wrapped_token_program.id(),
+            ),
+            self.escrow_starting_amount,
+        );
+
+        let transfer_authority = self.transfer_authority.clone().unwrap_or_default();
+        let wrapped_token_account = TokenAccountBuilder::new()
+            .token_program(self.wrapped_token_program)
+            .mint(wrapped_mint.clone())
+            .owner(transfer_authority.keyed_account.key)
+            .amount(self.escrow_starting_amount)
+            .build();
+
+        let wrap_limits = self.wrap_limits.clone().unwrap_or(KeyedAccount {
+            key: get_wrap_limits_address(&unwrapped_mint.key),
+            account: Account::default(),
+        });
+        let allowlist = KeyedAccount {
+            key: get_allowlist_address(&unwrapped_mint.key),
+            account: self.allowlist.clone().unwrap_or_default(),
+        };
+        let allowlist_entry = KeyedAccount {
+            key: self.allowlist_entry_address.unwrap_or_else(|| {
+                get_allowlist_entry_address(
+                    &unwrapped_mint.key,
+                    &token_account_owner(&wrapped_token_account.account),
+                )
+            }),
+            account: self.allowlist_entry.clone().unwrap_or_default(),
+        };
+
+        let protocol_fee_config = KeyedAccount {
+            key: get_protocol_fee_config_address(),
+            account: self.protocol_fee_config.clone().unwrap_or_default(),
+        };
+        let protocol_fee_override = KeyedAccount {
+            key: get_protocol_fee_override_address(&unwrapped_mint.key),
+            account: self.protocol_fee_override.clone().unwrap_or_default(),
+        };
+        let protocol_fee_vault = self.protocol_fee_vault.clone().unwrap_or(KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        });
+
+        let native_unwrap_account = self.native_unwrap_account.clone().unwrap_or(KeyedAccount {
+            key: get_native_unwrap_account_address(&wrapped_mint.key),
+            account: Account::default(),
+        });
+
+        let pro_rata_redemption = self
+            .pro_rata_redemption
+            .clone()
+            .map(|account| KeyedAccount {
+                key: get_pro_rata_redemption_address(&wrapped_mint.key),
+                account,
+            });
+
+        let payer = KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account {
+                lamports: self.payer_lamports,
+                ..Default::default()
+            },
+        };
+
+        let recipient = if self.payer_is_recipient {
+            payer.clone()
+        } else {
+            self.recipient.clone().unwrap_or(KeyedAccount {
+                key: Pubkey::new_unique(),
+                account: Account::default(),
+            })
+        };
+
+        let instruction = unwrap_sol(
+            &spl_token_wrap::id(),
+            &unwrapped_escrow.key,
+            &recipient.key,
+            &wrapped_mint_authority,
+            &unwrapped_mint.key,
+            &self.wrapped_token_program.id(),
+            &self.unwrapped_token_program.id(),
+            &wrapped_token_account.key,
+            &wrapped_mint.key,
+            &transfer_authority.keyed_account.key,
+            &wrap_limits.key,
+            &allowlist.key,
+            &allowlist_entry.key,
+            &protocol_fee_config.key,
+            &protocol_fee_override.key,
+            &protocol_fee_vault.key,
+            &get_flash_mint_guard_address(&wrapped_mint.key),
+            &native_unwrap_account.key,
+            &payer.key,
+            pro_rata_redemption
+                .as_ref()
+                .map(|redemption| &redemption.key),
+            &transfer_authority.signers.iter().collect::<Vec<_>>(),
+            self.unwrap_amount,
+        );
+
+        let mut accounts = vec![
+            unwrapped_escrow.pair(),
+            recipient.pair(),
+            (wrapped_mint_authority, Account::default()),
+            unwrapped_mint.pair(),
+            self.wrapped_token_program.keyed_account(),
+            self.unwrapped_token_program.keyed_account(),
+            wrapped_token_account.pair(),
+            wrapped_mint.pair(),
+            transfer_authority.keyed_account.pair(),
+            wrap_limits.pair(),
+            allowlist.pair(),
+            allowlist_entry.pair(),
+            protocol_fee_config.pair(),
+            protocol_fee_override.pair(),
+            protocol_fee_vault.pair(),
+            (
+                get_flash_mint_guard_address(&wrapped_mint.key),
+                Account::default(),
+            ),
+            native_unwrap_account.pair(),
+        ];
+        if !self.payer_is_recipient {
+            accounts.push(payer.pair());
+        }
+        accounts.push(keyed_account_for_system_program());
+        if let Some(pro_rata_redemption) = &pro_rata_redemption {
+            accounts.push(pro_rata_redemption.pair());
+        }
+        for signer_key in &transfer_authority.signers {
+            accounts.push((*signer_key, Account::default()));
+        }
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, &accounts, &self.checks);
+
+        let get_account = |key: &Pubkey| KeyedAccount {
+            key: *key,
+            account: result.get_account(key).unwrap().clone(),
+        };
+        UnwrapSolResult {
+            unwrapped_escrow: get_account(&unwrapped_escrow.key),
+            wrapped_mint: get_account(&wrapped_mint.key),
+            wrapped_token_account: get_account(&wrapped_token_account.key),
+            recipient: get_account(&recipient.key),
+            native_unwrap_account: get_account(&native_unwrap_account.key),
+            payer: get_account(&payer.key),
+            wrap_limits: get_account(&wrap_limits.key),
+            protocol_fee_vault: get_account(&protocol_fee_vault.key),
+            pro_rata_redemption: pro_rata_redemption.map(|redemption| get_account(&redemption.key)),
+        }
+    }
+}
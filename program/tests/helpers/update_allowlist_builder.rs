@@ -0,0 +1,155 @@
+use {
+    crate::helpers::{
+        common::{init_mollusk, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+    },
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        get_allowlist_entry_address,
+        instruction::update_allowlist,
+        state::{AllowlistEntry, VersionedState},
+    },
+};
+
+pub struct UpdateAllowlistResult {
+    pub allowlist_entries: Vec<KeyedAccount>,
+}
+
+pub struct UpdateAllowlistBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    unwrapped_mint_authority: Option<Pubkey>,
+    is_authority_signer: bool,
+    unwrapped_mint: Option<KeyedAccount>,
+    wallets: Vec<Pubkey>,
+    allowlist_entries: Vec<(Pubkey, KeyedAccount)>,
+    allowed: bool,
+}
+
+impl Default for UpdateAllowlistBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            unwrapped_mint_authority: None,
+            is_authority_signer: true,
+            unwrapped_mint: None,
+            wallets: vec![],
+            allowlist_entries: vec![],
+            allowed: true,
+        }
+    }
+}
+
+impl<'a> UpdateAllowlistBuilder<'a> {
+    pub fn unwrapped_mint_authority(mut self, key: Pubkey) -> Self {
+        self.unwrapped_mint_authority = Some(key);
+        self
+    }
+
+    pub fn authority_not_signer(mut self) -> Self {
+        self.is_authority_signer = false;
+        self
+    }
+
+    pub fn unwrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.unwrapped_mint = Some(account);
+        self
+    }
+
+    /// Updates the wallet, with an uninitialized entry at its address
+    pub fn wallet(mut self, wallet: Pubkey) -> Self {
+        self.wallets.push(wallet);
+        self
+    }
+
+    /// Updates the wallet, starting from the given entry account
+    pub fn allowlist_entry(mut self, wallet: Pubkey, account: KeyedAccount) -> Self {
+        self.allowlist_entries.push((wallet, account));
+        self
+    }
+
+    pub fn disallow(mut self) -> Self {
+        self.allowed = false;
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> UpdateAllowlistResult {
+        let unwrapped_mint_authority = self
+            .unwrapped_mint_authority
+            .unwrap_or_else(Pubkey::new_unique);
+
+        let unwrapped_mint = self.unwrapped_mint.unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken)
+                .mint_authority(unwrapped_mint_authority)
+                .build()
+        });
+
+        let mut allowlist_entries = self.allowlist_entries;
+        for wallet in &self.wallets {
+            allowlist_entries.push((
+                *wallet,
+                KeyedAccount {
+                    key: get_allowlist_entry_address(&unwrapped_mint.key, wallet),
+                    account: Account {
+                        lamports: Rent::default().minimum_balance(AllowlistEntry::LEN),
+                        ..Default::default()
+                    },
+                },
+            ));
+        }
+
+        let mut instruction = update_allowlist(
+            &spl_token_wrap::id(),
+            &unwrapped_mint_authority,
+            &unwrapped_mint.key,
+            self.allowed,
+            &allowlist_entries
+                .iter()
+                .map(|(wallet, entry)| (*wallet, entry.key))
+                .collect::<Vec<_>>(),
+            None,
+            None,
+            &[],
+        );
+
+        // Allow testing with non-signer authority for negative test cases
+        if !self.is_authority_signer {
+            instruction.accounts[0].is_signer = false;
+        }
+
+        let mut accounts = vec![
+            (unwrapped_mint_authority, Account::default()),
+            unwrapped_mint.pair(),
+            keyed_account_for_system_program(),
+        ];
+        accounts.extend(allowlist_entries.iter().map(|(_, entry)| entry.pair()));
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, &accounts, &self.checks);
+
+        UpdateAllowlistResult {
+            allowlist_entries: allowlist_entries
+                .iter()
+                .map(|(_, entry)| KeyedAccount {
+                    key: entry.key,
+                    account: result.get_account(&entry.key).unwrap().clone(),
+                })
+                .collect(),
+        }
+    }
+}
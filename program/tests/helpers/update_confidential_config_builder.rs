@@ -0,0 +1,162 @@
+use {
+    crate::helpers::{
+        common::{init_mollusk, KeyedAccount, TokenProgram},
+        extensions::MintExtension,
+        mint_builder::MintBuilder,
+    },
+    mollusk_svm::{result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    solana_zk_sdk_pod::encryption::elgamal::PodElGamalPubkey,
+    spl_token_wrap::{
+        get_wrapped_mint_address, get_wrapped_mint_authority,
+        instruction::update_confidential_config,
+    },
+};
+
+pub struct UpdateConfidentialConfigResult {
+    pub wrapped_mint: KeyedAccount,
+}
+
+pub struct UpdateConfidentialConfigBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    unwrapped_mint_authority: Option<Pubkey>,
+    is_authority_signer: bool,
+    unwrapped_mint: Option<KeyedAccount>,
+    wrapped_mint: Option<KeyedAccount>,
+    wrapped_mint_authority: Option<Pubkey>,
+    auto_approve_new_accounts: bool,
+    auditor_elgamal_pubkey: Option<PodElGamalPubkey>,
+}
+
+impl Default for UpdateConfidentialConfigBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            unwrapped_mint_authority: None,
+            is_authority_signer: true,
+            unwrapped_mint: None,
+            wrapped_mint: None,
+            wrapped_mint_authority: None,
+            auto_approve_new_accounts: true,
+            auditor_elgamal_pubkey: None,
+        }
+    }
+}
+
+impl<'a> UpdateConfidentialConfigBuilder<'a> {
+    pub fn unwrapped_mint_authority(mut self, key: Pubkey) -> Self {
+        self.unwrapped_mint_authority = Some(key);
+        self
+    }
+
+    pub fn authority_not_signer(mut self) -> Self {
+        self.is_authority_signer = false;
+        self
+    }
+
+    pub fn unwrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.unwrapped_mint = Some(account);
+        self
+    }
+
+    pub fn wrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.wrapped_mint = Some(account);
+        self
+    }
+
+    pub fn wrapped_mint_authority(mut self, key: Pubkey) -> Self {
+        self.wrapped_mint_authority = Some(key);
+        self
+    }
+
+    pub fn auto_approve_new_accounts(mut self, auto_approve_new_accounts: bool) -> Self {
+        self.auto_approve_new_accounts = auto_approve_new_accounts;
+        self
+    }
+
+    pub fn auditor_elgamal_pubkey(mut self, auditor_elgamal_pubkey: PodElGamalPubkey) -> Self {
+        self.auditor_elgamal_pubkey = Some(auditor_elgamal_pubkey);
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> UpdateConfidentialConfigResult {
+        let unwrapped_mint_authority = self
+            .unwrapped_mint_authority
+            .unwrap_or_else(Pubkey::new_unique);
+
+        let unwrapped_mint = self.unwrapped_mint.unwrap_or_else(|| {
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken)
+                .mint_authority(unwrapped_mint_authority)
+                .build()
+        });
+
+        // By default, the confidential transfer settings are governed by the
+        // wrapped mint authority
+        let wrapped_mint = self.wrapped_mint.unwrap_or_else(|| {
+            let wrapped_mint_address =
+                get_wrapped_mint_address(&unwrapped_mint.key, &spl_token_2022_interface::id());
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken2022)
+                .mint_key(wrapped_mint_address)
+                .mint_authority(get_wrapped_mint_authority(&wrapped_mint_address))
+                .with_extension(MintExtension::ConfidentialTransferWithAuthority(
+                    get_wrapped_mint_authority(&wrapped_mint_address),
+                ))
+                .build()
+        });
+
+        let wrapped_mint_authority = self
+            .wrapped_mint_authority
+            .unwrap_or_else(|| get_wrapped_mint_authority(&wrapped_mint.key));
+
+        let mut instruction = update_confidential_config(
+            &spl_token_wrap::id(),
+            &unwrapped_mint_authority,
+            &wrapped_mint.key,
+            &wrapped_mint_authority,
+            &unwrapped_mint.key,
+            self.auto_approve_new_accounts,
+            self.auditor_elgamal_pubkey,
+            None,
+            None,
+            &[],
+        );
+
+        // Allow testing with non-signer authority for negative test cases
+        if !self.is_authority_signer {
+            instruction.accounts[0].is_signer = false;
+        }
+
+        let accounts = &[
+            (unwrapped_mint_authority, Account::default()),
+            wrapped_mint.pair(),
+            (wrapped_mint_authority, Account::default()),
+            unwrapped_mint.pair(),
+            TokenProgram::SplToken2022.keyed_account(),
+        ];
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, accounts, &self.checks);
+
+        UpdateConfidentialConfigResult {
+            wrapped_mint: KeyedAccount {
+                key: wrapped_mint.key,
+                account: result.get_account(&wrapped_mint.key).unwrap().clone(),
+            },
+        }
+    }
+}
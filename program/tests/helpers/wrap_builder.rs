@@ -1,16 +1,27 @@
 use {
     crate::helpers::{
-        common::{init_mollusk, KeyedAccount, TokenProgram, TransferAuthority},
+        common::{
+            init_mollusk, token_account_owner, KeyedAccount, TokenProgram, TransferAuthority,
+        },
+        crank_wrap::CrankWrapper,
+        extensions::MintExtension,
         mint_builder::MintBuilder,
+        permit::PermitSigner,
         token_account_builder::TokenAccountBuilder,
     },
-    mollusk_svm::{result::Check, Mollusk},
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
     solana_account::Account,
     solana_instruction::AccountMeta,
     solana_pubkey::Pubkey,
-    spl_token_2022_interface::extension::ExtensionType::ImmutableOwner,
+    solana_signer::Signer,
+    spl_token_2022_interface::extension::ExtensionType::{self, ImmutableOwner},
     spl_token_wrap::{
-        get_escrow_address, get_wrapped_mint_address, get_wrapped_mint_authority, instruction::wrap,
+        get_allowlist_address, get_allowlist_entry_address, get_canonical_pointer_address,
+        get_escrow_address, get_protocol_fee_config_address, get_protocol_fee_override_address,
+        get_scoped_canonical_pointer_address, get_wrap_limits_address, get_wrap_stats_address,
+        get_wrapped_mint_address, get_wrapped_mint_authority,
+        instruction::{crank_wrap, wrap, wrap_confidential, wrap_with_permit},
+        permit::PermitAction,
     },
 };
 
@@ -32,6 +43,22 @@ pub struct WrapBuilder<'a> {
     transfer_authority: Option<TransferAuthority>,
     extra_accounts: Vec<KeyedAccount>,
     unwrapped_token_account: Option<KeyedAccount>,
+    canonical_pointer: Option<KeyedAccount>,
+    scoped_canonical_pointer: Option<KeyedAccount>,
+    wrap_limits: Option<KeyedAccount>,
+    allowlist: Option<Account>,
+    allowlist_entry: Option<Account>,
+    allowlist_entry_address: Option<Pubkey>,
+    protocol_fee_config: Option<Account>,
+    protocol_fee_override: Option<Account>,
+    protocol_fee_vault: Option<KeyedAccount>,
+    wrap_stats: Option<Account>,
+    wrap_stats_address: Option<Pubkey>,
+    confidential_recipient: Option<Option<Vec<ExtensionType>>>,
+    permit: Option<PermitSigner>,
+    crank: Option<CrankWrapper>,
+    unwrapped_token_extensions: Vec<ExtensionType>,
+    policy_accounts: bool,
 }
 
 impl Default for WrapBuilder<'_> {
@@ -54,6 +81,22 @@ impl Default for WrapBuilder<'_> {
             transfer_authority: None,
             extra_accounts: vec![],
             unwrapped_token_account: None,
+            canonical_pointer: None,
+            scoped_canonical_pointer: None,
+            wrap_limits: None,
+            allowlist: None,
+            allowlist_entry: None,
+            allowlist_entry_address: None,
+            protocol_fee_config: None,
+            protocol_fee_override: None,
+            protocol_fee_vault: None,
+            wrap_stats: None,
+            wrap_stats_address: None,
+            confidential_recipient: None,
+            permit: None,
+            crank: None,
+            unwrapped_token_extensions: vec![],
+            policy_accounts: true,
         }
     }
 }
@@ -124,6 +167,127 @@ impl<'a> WrapBuilder<'a> {
         self
     }
 
+    pub fn canonical_pointer(mut self, account: KeyedAccount) -> Self {
+        self.canonical_pointer = Some(account);
+        self
+    }
+
+    pub fn scoped_canonical_pointer(mut self, account: KeyedAccount) -> Self {
+        self.scoped_canonical_pointer = Some(account);
+        self
+    }
+
+    pub fn wrap_limits(mut self, account: KeyedAccount) -> Self {
+        self.wrap_limits = Some(account);
+        self
+    }
+
+    /// Enforces the allowlist of the unwrapped mint, starting from the given
+    /// account state
+    pub fn allowlist(mut self, account: Account) -> Self {
+        self.allowlist = Some(account);
+        self
+    }
+
+    /// Allowlist entry of the recipient owner, starting from the given account state
+    pub fn allowlist_entry(mut self, account: Account) -> Self {
+        self.allowlist_entry = Some(account);
+        self
+    }
+
+    pub fn allowlist_entry_address(mut self, key: Pubkey) -> Self {
+        self.allowlist_entry_address = Some(key);
+        self
+    }
+
+    /// Charges the protocol fee of the deployment, starting from the given
+    /// account state
+    pub fn protocol_fee_config(mut self, account: Account) -> Self {
+        self.protocol_fee_config = Some(account);
+        self
+    }
+
+    /// Overrides the protocol fee of the unwrapped mint, starting from the
+    /// given account state
+    pub fn protocol_fee_override(mut self, account: Account) -> Self {
+        self.protocol_fee_override = Some(account);
+        self
+    }
+
+    pub fn protocol_fee_vault(mut self, account: KeyedAccount) -> Self {
+        self.protocol_fee_vault = Some(account);
+        self
+    }
+
+    /// Leaves out the policy accounts, as in the baseline account layout
+    pub fn without_policy_accounts(mut self) -> Self {
+        self.policy_accounts = false;
+        self
+    }
+
+    /// Records the wrap in the statistics account, starting from the given
+    /// account state
+    pub fn wrap_stats(mut self, account: Account) -> Self {
+        self.wrap_stats = Some(account);
+        self
+    }
+
+    pub fn wrap_stats_address(mut self, key: Pubkey) -> Self {
+        self.wrap_stats_address = Some(key);
+        self
+    }
+
+    /// Wraps with `WrapConfidential` into a recipient configured for
+    /// confidential transfers
+    pub fn confidential(mut self) -> Self {
+        self.confidential_recipient = Some(None);
+        self
+    }
+
+    /// Wraps with `WrapConfidential` into a recipient with the given
+    /// extensions
+    pub fn confidential_with_recipient_extensions(
+        mut self,
+        extensions: Vec<ExtensionType>,
+    ) -> Self {
+        self.confidential_recipient = Some(Some(extensions));
+        self
+    }
+
+    /// Wraps with `WrapWithPermit` from a token account of the permit owner
+    /// that approved the permit delegate
+    pub fn permit(mut self, permit: PermitSigner) -> Self {
+        self.permit = Some(permit);
+        self
+    }
+
+    /// Wraps with `CrankWrap` from a token account of the crank wrapper owner
+    /// that approved their crank wrap delegate
+    pub fn crank(mut self, crank: CrankWrapper) -> Self {
+        self.crank = Some(crank);
+        self
+    }
+
+    pub fn unwrapped_token_extension(mut self, extension: ExtensionType) -> Self {
+        self.unwrapped_token_extensions.push(extension);
+        self
+    }
+
+    pub fn unix_timestamp(mut self, unix_timestamp: i64) -> Self {
+        self.mollusk.sysvars.clock.unix_timestamp = unix_timestamp;
+        self
+    }
+
+    pub fn slot(mut self, slot: u64) -> Self {
+        self.mollusk.sysvars.clock.slot = slot;
+        self
+    }
+
+    pub fn epoch(mut self, epoch: u64) -> Self {
+        self.mollusk.sysvars.clock.epoch = epoch;
+        self
+    }
+
     pub fn add_extra_account(mut self, keyed_account: KeyedAccount) -> Self {
         self.extra_accounts.push(keyed_account);
         self
@@ -142,13 +306,16 @@ impl<'a> WrapBuilder<'a> {
         let wrapped_mint_addr = get_wrapped_mint_address(&unwrapped_mint_addr, &token_program.id());
         let mint_authority = get_wrapped_mint_authority(&wrapped_mint_addr);
 
+        let mut mint_builder = MintBuilder::new()
+            .token_program(token_program)
+            .mint_authority(mint_authority);
+        if self.confidential_recipient.is_some() {
+            mint_builder = mint_builder.with_extension(MintExtension::ConfidentialTransfer);
+        }
+
         self.wrapped_mint.clone().unwrap_or(KeyedAccount {
             key: wrapped_mint_addr,
-            account: MintBuilder::new()
-                .token_program(token_program)
-                .mint_authority(mint_authority)
-                .build()
-                .account,
+            account: mint_builder.build().account,
         })
     }
 
@@ -171,13 +338,31 @@ impl<'a> WrapBuilder<'a> {
         let wrap_amount = self.wrap_amount.unwrap_or(500);
 
         let unwrapped_token_account = self.unwrapped_token_account.clone().unwrap_or_else(|| {
-            TokenAccountBuilder::new()
+            let mut builder = TokenAccountBuilder::new()
                 .token_program(unwrapped_token_program)
                 .mint(unwrapped_mint.clone())
-                .owner(unwrapped_token_account_authority.keyed_account.key)
                 .amount(self.unwrapped_token_starting_amount.unwrap_or(wrap_amount))
-                .lamports(100_000_000)
-                .build()
+                .lamports(100_000_000);
+            for extension in &self.unwrapped_token_extensions {
+                builder = builder.with_extension(*extension);
+            }
+            match (&self.permit, &self.crank) {
+                (Some(permit), _) => builder
+                    .owner(permit.owner.pubkey())
+                    .delegate(permit.delegate)
+                    .delegated_amount(wrap_amount),
+                (None, Some(crank)) => {
+                    builder = builder
+                        .owner(crank.owner)
+                        .delegated_amount(crank.delegated_amount.unwrap_or(wrap_amount));
+                    match crank.token_account_delegate(&unwrapped_mint.key) {
+                        Some(delegate) => builder.delegate(delegate),
+                        None => builder,
+                    }
+                }
+                (None, None) => builder.owner(unwrapped_token_account_authority.keyed_account.key),
+            }
+            .build()
         });
 
         let wrapped_token_program = self
@@ -213,13 +398,21 @@ impl<'a> WrapBuilder<'a> {
 
         let recipient = self.recipient.clone().unwrap_or_else(|| {
             let recipient_addr = Pubkey::new_unique();
-            TokenAccountBuilder::new()
+            let mut builder = TokenAccountBuilder::new()
                 .token_program(wrapped_token_program)
                 .mint(wrapped_mint.clone())
                 .owner(recipient_addr)
                 .amount(self.recipient_starting_amount.unwrap_or(0))
-                .account_key(recipient_addr)
-                .build()
+                .account_key(recipient_addr);
+            if let Some(extensions) = &self.confidential_recipient {
+                let extensions = extensions
+                    .clone()
+                    .unwrap_or(vec![ExtensionType::ConfidentialTransferAccount]);
+                for extension in extensions {
+                    builder = builder.with_extension(extension);
+                }
+            }
+            builder.build()
         });
 
         let unwrapped_escrow_address = self.unwrapped_escrow_addr.unwrap_or(get_escrow_address(
@@ -228,6 +421,61 @@ impl<'a> WrapBuilder<'a> {
             &wrapped_token_program.id(),
         ));
 
+        let canonical_pointer = self.canonical_pointer.clone().unwrap_or(KeyedAccount {
+            key: get_canonical_pointer_address(&unwrapped_mint.key),
+            account: Account::default(),
+        });
+
+        let scoped_canonical_pointer =
+            self.scoped_canonical_pointer
+                .clone()
+                .unwrap_or(KeyedAccount {
+                    key: get_scoped_canonical_pointer_address(
+                        &unwrapped_mint.key,
+                        &wrapped_token_program.id(),
+                    ),
+                    account: Account::default(),
+                });
+
+        let wrap_limits = self.wrap_limits.clone().unwrap_or(KeyedAccount {
+            key: get_wrap_limits_address(&unwrapped_mint.key),
+            account: Account::default(),
+        });
+
+        let allowlist = KeyedAccount {
+            key: get_allowlist_address(&unwrapped_mint.key),
+            account: self.allowlist.clone().unwrap_or_default(),
+        };
+        let allowlist_entry = KeyedAccount {
+            key: self.allowlist_entry_address.unwrap_or_else(|| {
+                get_allowlist_entry_address(
+                    &unwrapped_mint.key,
+                    &token_account_owner(&recipient.account),
+                )
+            }),
+            account: self.allowlist_entry.clone().unwrap_or_default(),
+        };
+
+        let protocol_fee_config = KeyedAccount {
+            key: get_protocol_fee_config_address(),
+            account: self.protocol_fee_config.clone().unwrap_or_default(),
+        };
+        let protocol_fee_override = KeyedAccount {
+            key: get_protocol_fee_override_address(&unwrapped_mint.key),
+            account: self.protocol_fee_override.clone().unwrap_or_default(),
+        };
+        let protocol_fee_vault = self.protocol_fee_vault.clone().unwrap_or(KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        });
+
+        let wrap_stats = self.wrap_stats.clone().map(|account| KeyedAccount {
+            key: self
+                .wrap_stats_address
+                .unwrap_or_else(|| get_wrap_stats_address(&wrapped_mint.key)),
+            account,
+        });
+
         let mut instruction = wrap(
             &spl_token_wrap::id(),
             &recipient.key,
@@ -238,7 +486,16 @@ impl<'a> WrapBuilder<'a> {
             &unwrapped_token_account.key,
             &unwrapped_mint.key,
             &unwrapped_escrow_address,
+            &canonical_pointer.key,
+            &scoped_canonical_pointer.key,
+            &wrap_limits.key,
+            &allowlist.key,
+            &allowlist_entry.key,
+            &protocol_fee_config.key,
+            &protocol_fee_override.key,
+            &protocol_fee_vault.key,
             &unwrapped_token_account_authority.keyed_account.key,
+            wrap_stats.as_ref().map(|stats| &stats.key),
             &unwrapped_token_account_authority
                 .signers
                 .iter()
@@ -260,12 +517,131 @@ impl<'a> WrapBuilder<'a> {
                     .unwrap_or(unwrapped_escrow_account),
             ),
             unwrapped_token_account_authority.keyed_account.pair(),
+            canonical_pointer.pair(),
+            scoped_canonical_pointer.pair(),
+            wrap_limits.pair(),
+            allowlist.pair(),
+            allowlist_entry.pair(),
+            protocol_fee_config.pair(),
+            protocol_fee_override.pair(),
+            protocol_fee_vault.pair(),
         ];
 
+        // Baseline layout, which ends the fixed accounts at the transfer
+        // authority
+        if !self.policy_accounts {
+            instruction.accounts.drain(9..17);
+            accounts.drain(9..17);
+        }
+
+        if let Some(wrap_stats) = &wrap_stats {
+            accounts.push(wrap_stats.pair());
+        }
+
+        // The recipient is its own owner, so it is already provided
+        if self.confidential_recipient.is_some() {
+            instruction = wrap_confidential(
+                &spl_token_wrap::id(),
+                &recipient.key,
+                &recipient.key,
+                &wrapped_mint.key,
+                &wrapped_mint_authority,
+                &unwrapped_token_program.id(),
+                &unwrapped_token_account.key,
+                &unwrapped_mint.key,
+                &unwrapped_escrow_address,
+                &canonical_pointer.key,
+                &scoped_canonical_pointer.key,
+                &wrap_limits.key,
+                &allowlist.key,
+                &allowlist_entry.key,
+                &protocol_fee_config.key,
+                &protocol_fee_override.key,
+                &protocol_fee_vault.key,
+                &unwrapped_token_account_authority.keyed_account.key,
+                &unwrapped_token_account_authority
+                    .signers
+                    .iter()
+                    .collect::<Vec<_>>(),
+                wrap_amount,
+            );
+        }
+
         for signer_key in &unwrapped_token_account_authority.signers {
             accounts.push((*signer_key, Account::default()));
         }
 
+        let permit_nonce = self.permit.as_ref().map(|permit| KeyedAccount {
+            key: permit.permit_nonce_key(),
+            account: permit.permit_nonce.clone(),
+        });
+        if let (Some(permit), Some(permit_nonce)) = (&self.permit, &permit_nonce) {
+            instruction = wrap_with_permit(
+                &spl_token_wrap::id(),
+                &recipient.key,
+                &wrapped_mint.key,
+                &wrapped_mint_authority,
+                &unwrapped_token_program.id(),
+                &wrapped_token_program.id(),
+                &unwrapped_token_account.key,
+                &unwrapped_mint.key,
+                &unwrapped_escrow_address,
+                &canonical_pointer.key,
+                &scoped_canonical_pointer.key,
+                &wrap_limits.key,
+                &allowlist.key,
+                &allowlist_entry.key,
+                &protocol_fee_config.key,
+                &protocol_fee_override.key,
+                &protocol_fee_vault.key,
+                &permit.delegate,
+                &permit.owner.pubkey(),
+                &permit_nonce.key,
+                wrap_amount,
+                permit.nonce,
+                permit.expiry,
+            );
+            accounts[8] = (permit.delegate, Account::default());
+            accounts.extend([
+                (permit.owner.pubkey(), Account::default()),
+                permit_nonce.pair(),
+                permit.instructions_sysvar(
+                    PermitAction::Wrap,
+                    &unwrapped_mint.key,
+                    wrap_amount,
+                    &unwrapped_token_account.key,
+                    &recipient.key,
+                    &instruction,
+                ),
+                keyed_account_for_system_program(),
+            ]);
+        }
+
+        if let Some(crank) = &self.crank {
+            let crank_wrap_delegate = crank.keyed_delegate(&unwrapped_mint.key, &recipient.key);
+            instruction = crank_wrap(
+                &spl_token_wrap::id(),
+                &recipient.key,
+                &wrapped_mint.key,
+                &wrapped_mint_authority,
+                &unwrapped_token_program.id(),
+                &wrapped_token_program.id(),
+                &unwrapped_token_account.key,
+                &unwrapped_mint.key,
+                &unwrapped_escrow_address,
+                &canonical_pointer.key,
+                &scoped_canonical_pointer.key,
+                &wrap_limits.key,
+                &allowlist.key,
+                &allowlist_entry.key,
+                &protocol_fee_config.key,
+                &protocol_fee_override.key,
+                &protocol_fee_vault.key,
+                &crank_wrap_delegate.key,
+            );
+            accounts[8] = crank_wrap_delegate.pair();
+        }
+
         for extra_account in &self.extra_accounts {
             instruction
                 .accounts
@@ -304,6 +680,22 @@ impl<'a> WrapBuilder<'a> {
                 key: recipient.key,
                 account: result.get_account(&recipient.key).unwrap().clone(),
             },
+            protocol_fee_vault: KeyedAccount {
+                key: protocol_fee_vault.key,
+                account: result.get_account(&protocol_fee_vault.key).unwrap().clone(),
+            },
+            wrap_limits: KeyedAccount {
+                key: wrap_limits.key,
+                account: result.get_account(&wrap_limits.key).unwrap().clone(),
+            },
+            wrap_stats: wrap_stats.map(|stats| KeyedAccount {
+                key: stats.key,
+                account: result.get_account(&stats.key).unwrap().clone(),
+            }),
+            permit_nonce: permit_nonce.map(|permit_nonce| KeyedAccount {
+                key: permit_nonce.key,
+                account: result.get_account(&permit_nonce.key).unwrap().clone(),
+            }),
             extra_accounts: self
                 .extra_accounts
                 .iter()
@@ -321,5 +713,9 @@ pub struct WrapResult {
     pub unwrapped_escrow: KeyedAccount,
     pub wrapped_mint: KeyedAccount,
     pub recipient_wrapped_token: KeyedAccount,
+    pub protocol_fee_vault: KeyedAccount,
+    pub wrap_limits: KeyedAccount,
+    pub wrap_stats: Option<KeyedAccount>,
+    pub permit_nonce: Option<KeyedAccount>,
     pub extra_accounts: Vec<KeyedAccount>,
 }
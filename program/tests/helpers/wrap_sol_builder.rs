@@ -0,0 +1,356 @@
+use {
+    crate::helpers::{
+        common::{init_mollusk, token_account_owner, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+        token_account_builder::TokenAccountBuilder,
+    },
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_2022_interface::{
+        extension::ExtensionType::{self, ImmutableOwner},
+        pod::PodAccount,
+    },
+    spl_token_wrap::{
+        get_allowlist_address, get_allowlist_entry_address, get_canonical_pointer_address,
+        get_escrow_address, get_protocol_fee_config_address, get_protocol_fee_override_address,
+        get_scoped_canonical_pointer_address, get_wrap_limits_address, get_wrapped_mint_address,
+        get_wrapped_mint_authority, instruction::wrap_sol,
+    },
+};
+
+pub const NATIVE_MINT_DECIMALS: u8 = 9;
+
+/// Builds the native mint of a token program
+pub fn native_mint(token_program: TokenProgram) -> KeyedAccount {
+    let mint_key = match token_program {
+        TokenProgram::SplToken => spl_token::native_mint::id(),
+        TokenProgram::SplToken2022 => spl_token_2022_interface::native_mint::id(),
+    };
+    MintBuilder::new()
+        .token_program(token_program)
+        .no_mint_authority()
+        .supply(0)
+        .decimals(NATIVE_MINT_DECIMALS)
+        .mint_key(mint_key)
+        .build()
+}
+
+/// Builds a native token account holding `amount` lamports above its rent
+pub fn native_token_account(
+    token_program: TokenProgram,
+    mint: KeyedAccount,
+    owner: Pubkey,
+    key: Pubkey,
+    amount: u64,
+) -> KeyedAccount {
+    let extensions = match token_program {
+        TokenProgram::SplToken => vec![],
+        TokenProgram::SplToken2022 => vec![ImmutableOwner],
+    };
+    let rent = Rent::default().minimum_balance(
+        ExtensionType::try_calculate_account_len::<PodAccount>(&extensions).unwrap(),
+    );
+    let mut builder = TokenAccountBuilder::new()
+        .token_program(token_program)
+        .mint(mint)
+        .owner(owner)
+        .account_key(key);
+    for extension in extensions {
+        builder = builder.with_extension(extension);
+    }
+    builder
+        .amount(amount)
+        .native_balance(rent)
+        .lamports(rent.checked_add(amount).unwrap())
+        .build()
+}
+
+pub struct WrapSolResult {
+    pub unwrapped_escrow: KeyedAccount,
+    pub wrapped_mint: KeyedAccount,
+    pub recipient_wrapped_token: KeyedAccount,
+    pub funding_account: KeyedAccount,
+    pub protocol_fee_vault: KeyedAccount,
+}
+
+pub struct WrapSolBuilder<'a> {
+    mollusk: Mollusk,
+    checks: Vec<Check<'a>>,
+    wrap_amount: u64,
+    escrow_starting_amount: u64,
+    unwrapped_token_program: TokenProgram,
+    wrapped_token_program: TokenProgram,
+    unwrapped_mint: Option<KeyedAccount>,
+    unwrapped_escrow: Option<KeyedAccount>,
+    wrapped_mint_authority: Option<Pubkey>,
+    wrap_limits: Option<Account>,
+    allowlist: Option<Account>,
+    allowlist_entry: Option<Account>,
+    allowlist_entry_address: Option<Pubkey>,
+    protocol_fee_config: Option<Account>,
+    protocol_fee_override: Option<Account>,
+    protocol_fee_vault: Option<KeyedAccount>,
+    funding_lamports: u64,
+}
+
+impl Default for WrapSolBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            mollusk: init_mollusk(),
+            checks: vec![],
+            wrap_amount: 1_000_000,
+            escrow_starting_amount: 0,
+            unwrapped_token_program: TokenProgram::SplToken,
+            wrapped_token_program: TokenProgram::SplToken2022,
+            unwrapped_mint: None,
+            unwrapped_escrow: None,
+            wrapped_mint_authority: None,
+            wrap_limits: None,
+            allowlist: None,
+            allowlist_entry: None,
+            allowlist_entry_address: None,
+            protocol_fee_config: None,
+            protocol_fee_override: None,
+            protocol_fee_vault: None,
+            funding_lamports: 1_000_000_000,
+        }
+    }
+}
+
+impl<'a> WrapSolBuilder<'a> {
+    pub fn wrap_amount(mut self, amount: u64) -> Self {
+        self.wrap_amount = amount;
+        self
+    }
+
+    pub fn escrow_starting_amount(mut self, amount: u64) -> Self {
+        self.escrow_starting_amount = amount;
+        self
+    }
+
+    pub fn unwrapped_token_program(mut self, program: TokenProgram) -> Self {
+        self.unwrapped_token_program = program;
+        self
+    }
+
+    pub fn wrapped_token_program(mut self, program: TokenProgram) -> Self {
+        self.wrapped_token_program = program;
+        self
+    }
+
+    /// Replaces the native mint
+    pub fn unwrapped_mint(mut self, account: KeyedAccount) -> Self {
+        self.unwrapped_mint = Some(account);
+        self
+    }
+
+    pub fn unwrapped_escrow(mut self, account: KeyedAccount) -> Self {
+        self.unwrapped_escrow = Some(account);
+        self
+    }
+
+    pub fn wrapped_mint_authority(mut self, key: Pubkey) -> Self {
+        self.wrapped_mint_authority = Some(key);
+        self
+    }
+
+    pub fn wrap_limits(mut self, account: Account) -> Self {
+        self.wrap_limits = Some(account);
+        self
+    }
+
+    /// Enforces the allowlist of the unwrapped mint, starting from the given
+    /// account state
+    pub fn allowlist(mut self, account: Account) -> Self {
+        self.allowlist = Some(account);
+        self
+    }
+
+    /// Allowlist entry of the recipient owner, starting from the given account state
+    pub fn allowlist_entry(mut self, account: Account) -> Self {
+        self.allowlist_entry = Some(account);
+        self
+    }
+
+    pub fn allowlist_entry_address(mut self, key: Pubkey) -> Self {
+        self.allowlist_entry_address = Some(key);
+        self
+    }
+
+    /// Charges the protocol fee of the deployment, starting from the given
+    /// account state
+    pub fn protocol_fee_config(mut self, account: Account) -> Self {
+        self.protocol_fee_config = Some(account);
+        self
+    }
+
+    /// Overrides the protocol fee of the native mint, starting from the given
+    /// account state
+    pub fn protocol_fee_override(mut self, account: Account) -> Self {
+        self.protocol_fee_override = Some(account);
+        self
+    }
+
+    pub fn protocol_fee_vault(mut self, account: KeyedAccount) -> Self {
+        self.protocol_fee_vault = Some(account);
+        self
+    }
+
+    pub fn funding_lamports(mut self, lamports: u64) -> Self {
+        self.funding_lamports = lamports;
+        self
+    }
+
+    pub fn check(mut self, check: Check<'a>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn execute(mut self) -> WrapSolResult {
+        let unwrapped_mint = self
+            .unwrapped_mint
+            .clone()
+            .unwrap_or_else(|| native_mint(self.unwrapped_token_program));
+
+        let wrapped_mint_address =
+            get_wrapped_mint_address(&unwrapped_mint.key, &self.wrapped_token_program.id());
+        let wrapped_mint_authority = self
+            .wrapped_mint_authority
+            .unwrap_or_else(|| get_wrapped_mint_authority(&wrapped_mint_address));
+        let wrapped_mint = MintBuilder::new()
+            .token_program(self.wrapped_token_program)
+            .mint_authority(get_wrapped_mint_authority(&wrapped_mint_address))
+            .supply(self.escrow_starting_amount)
+            .decimals(NATIVE_MINT_DECIMALS)
+            .mint_key(wrapped_mint_address)
+            .build();
+
+        let unwrapped_escrow = self.unwrapped_escrow.clone().unwrap_or_else(|| {
+            native_token_account(
+                self.unwrapped_token_program,
+                unwrapped_mint.clone(),
+                get_wrapped_mint_authority(&wrapped_mint_address),
+                get_escrow_address(
+                    &unwrapped_mint.key,
+                    &self.unwrapped_token_program.id(),
+                    &self.wrapped_token_program.id(),
+                ),
+                self.escrow_starting_amount,
+            )
+        });
+
+        let recipient = TokenAccountBuilder::new()
+            .token_program(self.wrapped_token_program)
+            .mint(wrapped_mint.clone())
+            .build();
+
+        let canonical_pointer = get_canonical_pointer_address(&unwrapped_mint.key);
+        let scoped_canonical_pointer = get_scoped_canonical_pointer_address(
+            &unwrapped_mint.key,
+            &self.wrapped_token_program.id(),
+        );
+
+        let wrap_limits = KeyedAccount {
+            key: get_wrap_limits_address(&unwrapped_mint.key),
+            account: self.wrap_limits.clone().unwrap_or_default(),
+        };
+
+        let allowlist = KeyedAccount {
+            key: get_allowlist_address(&unwrapped_mint.key),
+            account: self.allowlist.clone().unwrap_or_default(),
+        };
+        let allowlist_entry = KeyedAccount {
+            key: self.allowlist_entry_address.unwrap_or_else(|| {
+                get_allowlist_entry_address(
+                    &unwrapped_mint.key,
+                    &token_account_owner(&recipient.account),
+                )
+            }),
+            account: self.allowlist_entry.clone().unwrap_or_default(),
+        };
+
+        let protocol_fee_config = KeyedAccount {
+            key: get_protocol_fee_config_address(),
+            account: self.protocol_fee_config.clone().unwrap_or_default(),
+        };
+        let protocol_fee_override = KeyedAccount {
+            key: get_protocol_fee_override_address(&unwrapped_mint.key),
+            account: self.protocol_fee_override.clone().unwrap_or_default(),
+        };
+        let protocol_fee_vault = self.protocol_fee_vault.clone().unwrap_or(KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        });
+
+        let funding_account = KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account {
+                lamports: self.funding_lamports,
+                ..Default::default()
+            },
+        };
+
+        let instruction = wrap_sol(
+            &spl_token_wrap::id(),
+            &recipient.key,
+            &wrapped_mint.key,
+            &wrapped_mint_authority,
+            &self.unwrapped_token_program.id(),
+            &self.wrapped_token_program.id(),
+            &unwrapped_mint.key,
+            &unwrapped_escrow.key,
+            &canonical_pointer,
+            &scoped_canonical_pointer,
+            &wrap_limits.key,
+            &allowlist.key,
+            &allowlist_entry.key,
+            &protocol_fee_config.key,
+            &protocol_fee_override.key,
+            &protocol_fee_vault.key,
+            &funding_account.key,
+            self.wrap_amount,
+        );
+
+        let accounts = &[
+            recipient.pair(),
+            wrapped_mint.pair(),
+            (wrapped_mint_authority, Account::default()),
+            self.unwrapped_token_program.keyed_account(),
+            self.wrapped_token_program.keyed_account(),
+            unwrapped_mint.pair(),
+            unwrapped_escrow.pair(),
+            (canonical_pointer, Account::default()),
+            (scoped_canonical_pointer, Account::default()),
+            wrap_limits.pair(),
+            allowlist.pair(),
+            allowlist_entry.pair(),
+            protocol_fee_config.pair(),
+            protocol_fee_override.pair(),
+            protocol_fee_vault.pair(),
+            funding_account.pair(),
+            keyed_account_for_system_program(),
+        ];
+
+        if self.checks.is_empty() {
+            self.checks.push(Check::success());
+        }
+
+        let result =
+            self.mollusk
+                .process_and_validate_instruction(&instruction, accounts, &self.checks);
+
+        let get_account = |key: &Pubkey| KeyedAccount {
+            key: *key,
+            account: result.get_account(key).unwrap().clone(),
+        };
+        WrapSolResult {
+            unwrapped_escrow: get_account(&unwrapped_escrow.key),
+            wrapped_mint: get_account(&wrapped_mint.key),
+            recipient_wrapped_token: get_account(&recipient.key),
+            funding_account: get_account(&funding_account.key),
+            protocol_fee_vault: get_account(&protocol_fee_vault.key),
+        }
+    }
+}
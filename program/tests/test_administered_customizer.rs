@@ -0,0 +1,165 @@
+pub mod helpers;
+
+use {
+    crate::helpers::{
+        common::{KeyedAccount, TokenProgram},
+        create_mint_builder::CreateMintBuilder,
+        execute_admin_action_builder::ExecuteAdminActionBuilder,
+        mint_builder::MintBuilder,
+        token_account_builder::TokenAccountBuilder,
+    },
+    solana_account_info::AccountInfo,
+    solana_pubkey::Pubkey,
+    spl_token_2022_interface::{
+        extension::{
+            metadata_pointer::MetadataPointer, mint_close_authority::MintCloseAuthority,
+            pausable::PausableConfig, transfer_fee::TransferFeeConfig, BaseStateWithExtensions,
+            PodStateWithExtensions,
+        },
+        pod::{PodAccount, PodMint},
+        state::AccountState,
+    },
+    spl_token_wrap::{
+        get_wrapped_mint_address, get_wrapped_mint_authority,
+        instruction::AdminAction,
+        mint_customizer::{administered::AdministeredMintCustomizer, interface::MintCustomizer},
+    },
+};
+
+fn pod_mint(wrapped_mint: &KeyedAccount) -> PodStateWithExtensions<'_, PodMint> {
+    PodStateWithExtensions::<PodMint>::unpack(&wrapped_mint.account.data).unwrap()
+}
+
+#[test]
+fn test_administered_customizer_freeze_authority() {
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .freeze_authority(Pubkey::new_unique())
+        .decimals(6)
+        .build();
+
+    let mut lamports = unwrapped_mint.account.lamports;
+    let mut data = unwrapped_mint.account.data.clone();
+    let unwrapped_mint_info = AccountInfo::new(
+        &unwrapped_mint.key,
+        false,
+        false,
+        &mut lamports,
+        &mut data,
+        &unwrapped_mint.account.owner,
+        false,
+    );
+
+    // The unwrapped freeze authority is replaced by the wrapped mint authority
+    let (freeze_authority, decimals) =
+        AdministeredMintCustomizer::get_freeze_auth_and_decimals(&unwrapped_mint_info).unwrap();
+    let wrapped_mint_address =
+        get_wrapped_mint_address(&unwrapped_mint.key, &spl_token_2022_interface::id());
+    assert_eq!(
+        freeze_authority.unwrap(),
+        get_wrapped_mint_authority(&wrapped_mint_address)
+    );
+    assert_eq!(decimals, 6);
+}
+
+#[test]
+// To test, adjust the processor function:
+// process_create_mint::<AdministeredMintCustomizer>(program_id, accounts, idempotent)
+#[ignore]
+fn test_execute_admin_action_on_administered_mint() {
+    let result = CreateMintBuilder::default()
+        .unwrapped_token_program(TokenProgram::SplToken)
+        .wrapped_token_program(TokenProgram::SplToken2022)
+        .freeze_authority(Pubkey::new_unique())
+        .execute();
+
+    let wrapped_mint_authority = get_wrapped_mint_authority(&result.wrapped_mint.key);
+    {
+        let mint_state = pod_mint(&result.wrapped_mint);
+        assert_eq!(
+            mint_state.base.freeze_authority.ok_or(()).unwrap(),
+            wrapped_mint_authority
+        );
+
+        let pausable = mint_state.get_extension::<PausableConfig>().unwrap();
+        assert_eq!(
+            Option::<Pubkey>::from(pausable.authority).unwrap(),
+            wrapped_mint_authority
+        );
+
+        let transfer_fee = mint_state.get_extension::<TransferFeeConfig>().unwrap();
+        assert_eq!(
+            Option::<Pubkey>::from(transfer_fee.transfer_fee_config_authority).unwrap(),
+            wrapped_mint_authority
+        );
+        assert_eq!(u64::from(transfer_fee.newer_transfer_fee.maximum_fee), 0);
+
+        let metadata_pointer = mint_state.get_extension::<MetadataPointer>().unwrap();
+        assert_eq!(
+            Option::<Pubkey>::from(metadata_pointer.authority).unwrap(),
+            wrapped_mint_authority
+        );
+
+        let close_authority = mint_state.get_extension::<MintCloseAuthority>().unwrap();
+        assert_eq!(
+            Option::<Pubkey>::from(close_authority.close_authority).unwrap(),
+            wrapped_mint_authority
+        );
+    }
+
+    // Each admin action succeeds on the mint built by the customizer
+    let admin = Pubkey::new_unique();
+    let paused = ExecuteAdminActionBuilder::default()
+        .unwrapped_mint(result.unwrapped_mint.clone())
+        .wrapped_mint(result.wrapped_mint.clone())
+        .appointed_admin(admin)
+        .admin(admin)
+        .action(AdminAction::Pause)
+        .execute();
+    let pausable = *pod_mint(&paused.wrapped_mint)
+        .get_extension::<PausableConfig>()
+        .unwrap();
+    assert!(bool::from(pausable.paused));
+
+    let with_fee = ExecuteAdminActionBuilder::default()
+        .unwrapped_mint(result.unwrapped_mint.clone())
+        .wrapped_mint(result.wrapped_mint.clone())
+        .appointed_admin(admin)
+        .admin(admin)
+        .action(AdminAction::SetTransferFee {
+            transfer_fee_basis_points: 25,
+            maximum_fee: 1_000,
+        })
+        .execute();
+    let transfer_fee = *pod_mint(&with_fee.wrapped_mint)
+        .get_extension::<TransferFeeConfig>()
+        .unwrap();
+    assert_eq!(
+        u16::from(transfer_fee.newer_transfer_fee.transfer_fee_basis_points),
+        25
+    );
+    assert_eq!(
+        u64::from(transfer_fee.newer_transfer_fee.maximum_fee),
+        1_000
+    );
+
+    let token_account = TokenAccountBuilder::default()
+        .token_program(TokenProgram::SplToken2022)
+        .mint(result.wrapped_mint.clone())
+        .owner(Pubkey::new_unique())
+        .build();
+    let frozen = ExecuteAdminActionBuilder::default()
+        .unwrapped_mint(result.unwrapped_mint)
+        .wrapped_mint(result.wrapped_mint)
+        .wrapped_token_account(token_account)
+        .appointed_admin(admin)
+        .admin(admin)
+        .action(AdminAction::FreezeAccount)
+        .execute();
+    let frozen_account = frozen.wrapped_token_account.unwrap();
+    let account_state = PodStateWithExtensions::<PodAccount>::unpack(&frozen_account.account.data)
+        .unwrap()
+        .base
+        .state;
+    assert_eq!(account_state, AccountState::Frozen as u8);
+}
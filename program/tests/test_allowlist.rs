@@ -0,0 +1,271 @@
+use {
+    crate::helpers::{
+        common::{versioned_state_data, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+        set_allowlist_mode_builder::SetAllowlistModeBuilder,
+        unwrap_builder::UnwrapBuilder,
+        unwrap_sol_builder::UnwrapSolBuilder,
+        update_allowlist_builder::UpdateAllowlistBuilder,
+        wrap_builder::WrapBuilder,
+        wrap_sol_builder::WrapSolBuilder,
+    },
+    mollusk_svm::result::Check,
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        error::TokenWrapError,
+        get_allowlist_entry_address,
+        state::{Allowlist, AllowlistEntry, VersionedState},
+    },
+};
+
+pub mod helpers;
+
+fn allowlist_account(enabled: bool) -> Account {
+    state_account(&Allowlist {
+        enabled: enabled.into(),
+    })
+}
+
+fn allowlist_entry_account(allowed: bool) -> Account {
+    state_account(&AllowlistEntry {
+        allowed: allowed.into(),
+    })
+}
+
+fn state_account<T: VersionedState>(state: &T) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(T::LEN),
+        owner: spl_token_wrap::id(),
+        data: versioned_state_data(state),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_set_allowlist_mode() {
+    let authority = Pubkey::new_unique();
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(authority)
+        .build();
+
+    let result = SetAllowlistModeBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(unwrapped_mint.clone())
+        .execute();
+
+    assert_eq!(result.allowlist.account.owner, spl_token_wrap::id());
+    assert!(Allowlist::unpack_versioned(&result.allowlist.account.data)
+        .unwrap()
+        .is_enabled());
+
+    // Disabling keeps the account around
+    let result = SetAllowlistModeBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(unwrapped_mint)
+        .allowlist(result.allowlist)
+        .disabled()
+        .execute();
+
+    assert!(!Allowlist::unpack_versioned(&result.allowlist.account.data)
+        .unwrap()
+        .is_enabled());
+}
+
+#[test]
+fn test_set_allowlist_mode_fails_incorrect_authority() {
+    SetAllowlistModeBuilder::default()
+        .unwrapped_mint(
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken)
+                .mint_authority(Pubkey::new_unique())
+                .build(),
+        )
+        .check(Check::err(ProgramError::IncorrectAuthority))
+        .execute();
+}
+
+#[test]
+fn test_set_allowlist_mode_fails_authority_not_signer() {
+    SetAllowlistModeBuilder::default()
+        .authority_not_signer()
+        .check(Check::err(ProgramError::MissingRequiredSignature))
+        .execute();
+}
+
+#[test]
+fn test_set_allowlist_mode_fails_incorrect_address() {
+    SetAllowlistModeBuilder::default()
+        .allowlist(KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        })
+        .check(Check::err(TokenWrapError::AllowlistMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_update_allowlist_in_bulk() {
+    let authority = Pubkey::new_unique();
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(authority)
+        .build();
+    let wallets = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let removed_wallet = Pubkey::new_unique();
+
+    let result = UpdateAllowlistBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(unwrapped_mint.clone())
+        .wallet(wallets[0])
+        .wallet(wallets[1])
+        .execute();
+
+    for (wallet, entry) in wallets.iter().zip(&result.allowlist_entries) {
+        assert_eq!(
+            entry.key,
+            get_allowlist_entry_address(&unwrapped_mint.key, wallet)
+        );
+        assert_eq!(entry.account.owner, spl_token_wrap::id());
+        assert!(AllowlistEntry::unpack_versioned(&entry.account.data)
+            .unwrap()
+            .is_allowed());
+    }
+
+    // Removing a wallet keeps its entry, disallowed
+    let result = UpdateAllowlistBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(unwrapped_mint.clone())
+        .allowlist_entry(
+            removed_wallet,
+            KeyedAccount {
+                key: get_allowlist_entry_address(&unwrapped_mint.key, &removed_wallet),
+                account: allowlist_entry_account(true),
+            },
+        )
+        .disallow()
+        .execute();
+
+    assert!(
+        !AllowlistEntry::unpack_versioned(&result.allowlist_entries[0].account.data)
+            .unwrap()
+            .is_allowed()
+    );
+}
+
+#[test]
+fn test_update_allowlist_fails_incorrect_authority() {
+    UpdateAllowlistBuilder::default()
+        .unwrapped_mint(
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken)
+                .mint_authority(Pubkey::new_unique())
+                .build(),
+        )
+        .wallet(Pubkey::new_unique())
+        .check(Check::err(ProgramError::IncorrectAuthority))
+        .execute();
+}
+
+#[test]
+fn test_update_allowlist_fails_incorrect_entry_address() {
+    UpdateAllowlistBuilder::default()
+        .allowlist_entry(
+            Pubkey::new_unique(),
+            KeyedAccount {
+                key: Pubkey::new_unique(),
+                account: Account::default(),
+            },
+        )
+        .check(Check::err(TokenWrapError::AllowlistEntryMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_allowlisted_wallet() {
+    WrapBuilder::default()
+        .allowlist(allowlist_account(true))
+        .allowlist_entry(allowlist_entry_account(true))
+        .execute();
+}
+
+#[test]
+fn test_wrap_fails_not_allowlisted() {
+    WrapBuilder::default()
+        .allowlist(allowlist_account(true))
+        .check(Check::err(TokenWrapError::NotAllowlisted.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_fails_removed_from_allowlist() {
+    WrapBuilder::default()
+        .allowlist(allowlist_account(true))
+        .allowlist_entry(allowlist_entry_account(false))
+        .check(Check::err(TokenWrapError::NotAllowlisted.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_ignores_allowlist_when_disabled() {
+    WrapBuilder::default()
+        .allowlist(allowlist_account(false))
+        .execute();
+}
+
+#[test]
+fn test_wrap_fails_incorrect_allowlist_entry_address() {
+    WrapBuilder::default()
+        .allowlist(allowlist_account(true))
+        .allowlist_entry(allowlist_entry_account(true))
+        .allowlist_entry_address(Pubkey::new_unique())
+        .check(Check::err(TokenWrapError::AllowlistEntryMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_fails_allowlist_not_owned_by_program() {
+    let mut allowlist = allowlist_account(true);
+    allowlist.owner = Pubkey::new_unique();
+
+    WrapBuilder::default()
+        .allowlist(allowlist)
+        .check(Check::err(ProgramError::InvalidAccountOwner))
+        .execute();
+}
+
+#[test]
+fn test_wrap_sol_fails_not_allowlisted() {
+    WrapSolBuilder::default()
+        .allowlist(allowlist_account(true))
+        .check(Check::err(TokenWrapError::NotAllowlisted.into()))
+        .execute();
+}
+
+#[test]
+fn test_unwrap_allowlisted_wallet() {
+    UnwrapBuilder::default()
+        .allowlist(allowlist_account(true))
+        .allowlist_entry(allowlist_entry_account(true))
+        .execute();
+}
+
+#[test]
+fn test_unwrap_fails_not_allowlisted() {
+    UnwrapBuilder::default()
+        .allowlist(allowlist_account(true))
+        .check(Check::err(TokenWrapError::NotAllowlisted.into()))
+        .execute();
+}
+
+#[test]
+fn test_unwrap_sol_fails_not_allowlisted() {
+    UnwrapSolBuilder::default()
+        .allowlist(allowlist_account(true))
+        .allowlist_entry(allowlist_entry_account(false))
+        .check(Check::err(TokenWrapError::NotAllowlisted.into()))
+        .execute();
+}
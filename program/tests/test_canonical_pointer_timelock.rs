@@ -0,0 +1,291 @@
+use {
+    crate::helpers::{
+        accept_canonical_pointer_builder::AcceptCanonicalPointerBuilder,
+        cancel_canonical_pointer_builder::CancelCanonicalPointerBuilder,
+        common::{versioned_state_data, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+        propose_canonical_pointer_builder::ProposeCanonicalPointerBuilder,
+    },
+    mollusk_svm::result::Check,
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        error::TokenWrapError,
+        get_canonical_pointer_address, get_pending_canonical_pointer_address,
+        state::{CanonicalDeploymentPointer, PendingCanonicalPointer, VersionedState},
+    },
+};
+
+pub mod helpers;
+
+const PROPOSED_AT: i64 = 1_000;
+const CHANGE_DELAY: u64 = 3_600;
+
+fn pointer_account(unwrapped_mint: &Pubkey, change_delay: u64) -> KeyedAccount {
+    KeyedAccount {
+        key: get_canonical_pointer_address(unwrapped_mint),
+        account: Account {
+            lamports: Rent::default().minimum_balance(CanonicalDeploymentPointer::LEN),
+            owner: spl_token_wrap::id(),
+            data: versioned_state_data(&CanonicalDeploymentPointer {
+                program_id: Pubkey::new_unique(),
+                enforce_on_wrap: false.into(),
+                change_delay: change_delay.into(),
+            }),
+            ..Default::default()
+        },
+    }
+}
+
+fn pending_account(
+    unwrapped_mint: &Pubkey,
+    program_id: Pubkey,
+    activation_timestamp: i64,
+) -> KeyedAccount {
+    KeyedAccount {
+        key: get_pending_canonical_pointer_address(unwrapped_mint),
+        account: Account {
+            lamports: Rent::default().minimum_balance(PendingCanonicalPointer::LEN),
+            owner: spl_token_wrap::id(),
+            data: versioned_state_data(&PendingCanonicalPointer {
+                pointer: CanonicalDeploymentPointer {
+                    program_id,
+                    enforce_on_wrap: true.into(),
+                    change_delay: 0.into(),
+                },
+                proposed_slot: 0.into(),
+                proposed_timestamp: PROPOSED_AT.into(),
+                activation_timestamp: activation_timestamp.into(),
+            }),
+            ..Default::default()
+        },
+    }
+}
+
+fn unwrapped_mint_with_authority(authority: Pubkey) -> KeyedAccount {
+    MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(authority)
+        .build()
+}
+
+#[test]
+fn test_fail_propose_missing_authority_signature() {
+    ProposeCanonicalPointerBuilder::default()
+        .authority_not_signer()
+        .check(Check::err(ProgramError::MissingRequiredSignature))
+        .execute();
+}
+
+#[test]
+fn test_fail_propose_incorrect_pending_address() {
+    let authority = Pubkey::new_unique();
+    let mint = unwrapped_mint_with_authority(authority);
+
+    ProposeCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(mint)
+        .pending_pointer(KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        })
+        .check(Check::err(ProgramError::InvalidArgument))
+        .execute();
+}
+
+#[test]
+fn test_success_propose_without_pointer_is_immediately_active() {
+    let authority = Pubkey::new_unique();
+    let mint = unwrapped_mint_with_authority(authority);
+    let new_program_id = Pubkey::new_unique();
+
+    let result = ProposeCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(mint)
+        .new_program_id(new_program_id)
+        .enforce_on_wrap()
+        .change_delay(CHANGE_DELAY)
+        .unix_timestamp(PROPOSED_AT)
+        .execute();
+
+    assert_eq!(result.pending_pointer.account.owner, spl_token_wrap::id());
+    let pending =
+        PendingCanonicalPointer::unpack_versioned(&result.pending_pointer.account.data).unwrap();
+    assert_eq!(pending.pointer.program_id, new_program_id);
+    assert!(bool::from(pending.pointer.enforce_on_wrap));
+    assert_eq!(u64::from(pending.pointer.change_delay), CHANGE_DELAY);
+    assert_eq!(i64::from(pending.proposed_timestamp), PROPOSED_AT);
+    assert_eq!(i64::from(pending.activation_timestamp), PROPOSED_AT);
+}
+
+#[test]
+fn test_success_propose_uses_current_change_delay() {
+    let authority = Pubkey::new_unique();
+    let mint = unwrapped_mint_with_authority(authority);
+    let pointer = pointer_account(&mint.key, CHANGE_DELAY);
+
+    let result = ProposeCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(mint)
+        .canonical_pointer(pointer)
+        .unix_timestamp(PROPOSED_AT)
+        .execute();
+
+    let pending =
+        PendingCanonicalPointer::unpack_versioned(&result.pending_pointer.account.data).unwrap();
+    assert_eq!(
+        i64::from(pending.activation_timestamp),
+        PROPOSED_AT + CHANGE_DELAY as i64
+    );
+}
+
+#[test]
+fn test_success_propose_scoped_inherits_unscoped_change_delay() {
+    let authority = Pubkey::new_unique();
+    let mint = unwrapped_mint_with_authority(authority);
+    let unscoped_pointer = pointer_account(&mint.key, CHANGE_DELAY);
+
+    let result = ProposeCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(mint)
+        .wrapped_token_program(spl_token_2022_interface::id())
+        .unscoped_canonical_pointer(unscoped_pointer)
+        .change_delay(60)
+        .unix_timestamp(PROPOSED_AT)
+        .execute();
+
+    let pending =
+        PendingCanonicalPointer::unpack_versioned(&result.pending_pointer.account.data).unwrap();
+    assert_eq!(
+        i64::from(pending.activation_timestamp),
+        PROPOSED_AT + CHANGE_DELAY as i64
+    );
+    assert_eq!(u64::from(pending.pointer.change_delay), CHANGE_DELAY);
+}
+
+#[test]
+fn test_fail_accept_before_activation() {
+    let mint = unwrapped_mint_with_authority(Pubkey::new_unique());
+    let pointer = pointer_account(&mint.key, CHANGE_DELAY);
+    let activation = PROPOSED_AT + CHANGE_DELAY as i64;
+    let pending = pending_account(&mint.key, Pubkey::new_unique(), activation);
+
+    AcceptCanonicalPointerBuilder::new(mint)
+        .canonical_pointer(pointer)
+        .pending_pointer(pending)
+        .unix_timestamp(activation - 1)
+        .check(Check::err(
+            TokenWrapError::PendingCanonicalPointerNotActive.into(),
+        ))
+        .execute();
+}
+
+#[test]
+fn test_fail_accept_after_delay_was_raised() {
+    let mint = unwrapped_mint_with_authority(Pubkey::new_unique());
+    // Proposed while no delay applied, then the delay was raised
+    let pointer = pointer_account(&mint.key, CHANGE_DELAY);
+    let pending = pending_account(&mint.key, Pubkey::new_unique(), PROPOSED_AT);
+
+    AcceptCanonicalPointerBuilder::new(mint)
+        .canonical_pointer(pointer)
+        .pending_pointer(pending)
+        .unix_timestamp(PROPOSED_AT + 1)
+        .check(Check::err(
+            TokenWrapError::PendingCanonicalPointerNotActive.into(),
+        ))
+        .execute();
+}
+
+#[test]
+fn test_fail_accept_invalid_pending_owner() {
+    let mint = unwrapped_mint_with_authority(Pubkey::new_unique());
+    let mut pending = pending_account(&mint.key, Pubkey::new_unique(), PROPOSED_AT);
+    pending.account.owner = Pubkey::new_unique();
+
+    AcceptCanonicalPointerBuilder::new(mint)
+        .pending_pointer(pending)
+        .unix_timestamp(PROPOSED_AT)
+        .check(Check::err(ProgramError::InvalidAccountOwner))
+        .execute();
+}
+
+#[test]
+fn test_success_accept_updates_pointer() {
+    let mint = unwrapped_mint_with_authority(Pubkey::new_unique());
+    let pointer = pointer_account(&mint.key, CHANGE_DELAY);
+    let pointer_lamports = pointer.account.lamports;
+    let new_program_id = Pubkey::new_unique();
+    let activation = PROPOSED_AT + CHANGE_DELAY as i64;
+    let pending = pending_account(&mint.key, new_program_id, activation);
+    let pending_lamports = pending.account.lamports;
+
+    let result = AcceptCanonicalPointerBuilder::new(mint)
+        .canonical_pointer(pointer)
+        .pending_pointer(pending)
+        .unix_timestamp(activation)
+        .execute();
+
+    let pointer_data =
+        CanonicalDeploymentPointer::unpack(&result.canonical_pointer.account.data).unwrap();
+    assert_eq!(pointer_data.program_id, new_program_id);
+    assert!(bool::from(pointer_data.enforce_on_wrap));
+    assert!(!pointer_data.is_timelocked());
+    assert_eq!(
+        result.canonical_pointer.account.lamports,
+        pointer_lamports + pending_lamports
+    );
+
+    assert_eq!(result.pending_pointer.account.lamports, 0);
+    assert!(result.pending_pointer.account.data.is_empty());
+}
+
+#[test]
+fn test_success_accept_creates_pointer() {
+    let mint = unwrapped_mint_with_authority(Pubkey::new_unique());
+    let new_program_id = Pubkey::new_unique();
+    let pending = pending_account(&mint.key, new_program_id, PROPOSED_AT);
+
+    let result = AcceptCanonicalPointerBuilder::new(mint)
+        .pending_pointer(pending)
+        .unix_timestamp(PROPOSED_AT)
+        .execute();
+
+    assert_eq!(result.canonical_pointer.account.owner, spl_token_wrap::id());
+    let pointer_data =
+        CanonicalDeploymentPointer::unpack(&result.canonical_pointer.account.data).unwrap();
+    assert_eq!(pointer_data.program_id, new_program_id);
+}
+
+#[test]
+fn test_fail_cancel_incorrect_authority() {
+    let mint = unwrapped_mint_with_authority(Pubkey::new_unique());
+    let pending = pending_account(&mint.key, Pubkey::new_unique(), PROPOSED_AT);
+
+    CancelCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(Pubkey::new_unique())
+        .unwrapped_mint(mint)
+        .pending_pointer(pending)
+        .check(Check::err(ProgramError::IncorrectAuthority))
+        .execute();
+}
+
+#[test]
+fn test_success_cancel() {
+    let authority = Pubkey::new_unique();
+    let mint = unwrapped_mint_with_authority(authority);
+    let pending = pending_account(&mint.key, Pubkey::new_unique(), PROPOSED_AT);
+    let pending_lamports = pending.account.lamports;
+
+    let result = CancelCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(mint)
+        .pending_pointer(pending)
+        .execute();
+
+    assert_eq!(result.pending_pointer.account.lamports, 0);
+    assert!(result.pending_pointer.account.data.is_empty());
+    assert_eq!(result.destination.account.lamports, pending_lamports);
+}
@@ -0,0 +1,193 @@
+use {
+    crate::helpers::{
+        close_canonical_pointer_builder::CloseCanonicalPointerBuilder,
+        common::{setup_multisig, versioned_state_data, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+    },
+    mollusk_svm::result::Check,
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        error::TokenWrapError,
+        get_canonical_pointer_address,
+        state::{CanonicalDeploymentPointer, VersionedState},
+    },
+};
+
+pub mod helpers;
+
+fn pointer_account(unwrapped_mint: &Pubkey, owner: Pubkey) -> KeyedAccount {
+    timelocked_pointer_account(unwrapped_mint, owner, 0)
+}
+
+fn timelocked_pointer_account(
+    unwrapped_mint: &Pubkey,
+    owner: Pubkey,
+    change_delay: u64,
+) -> KeyedAccount {
+    KeyedAccount {
+        key: get_canonical_pointer_address(unwrapped_mint),
+        account: Account {
+            lamports: Rent::default().minimum_balance(CanonicalDeploymentPointer::LEN),
+            owner,
+            data: versioned_state_data(&CanonicalDeploymentPointer {
+                program_id: Pubkey::new_unique(),
+                enforce_on_wrap: false.into(),
+                change_delay: change_delay.into(),
+            }),
+            ..Default::default()
+        },
+    }
+}
+
+#[test]
+fn test_fail_missing_authority_signature() {
+    CloseCanonicalPointerBuilder::default()
+        .authority_not_signer()
+        .check(Check::err(ProgramError::MissingRequiredSignature))
+        .execute();
+}
+
+#[test]
+fn test_fail_incorrect_authority() {
+    let mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(Pubkey::new_unique())
+        .build();
+
+    CloseCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(Pubkey::new_unique())
+        .unwrapped_mint(mint)
+        .check(Check::err(ProgramError::IncorrectAuthority))
+        .execute();
+}
+
+#[test]
+fn test_fail_incorrect_pointer_address() {
+    let authority = Pubkey::new_unique();
+    let mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(authority)
+        .build();
+    let mut pointer = pointer_account(&mint.key, spl_token_wrap::id());
+    pointer.key = Pubkey::new_unique();
+
+    CloseCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(mint)
+        .canonical_pointer(pointer)
+        .check(Check::err(ProgramError::InvalidArgument))
+        .execute();
+}
+
+#[test]
+fn test_fail_pointer_not_owned_by_program() {
+    let authority = Pubkey::new_unique();
+    let mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(authority)
+        .build();
+    let pointer = pointer_account(&mint.key, Pubkey::new_unique());
+
+    CloseCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(mint)
+        .canonical_pointer(pointer)
+        .check(Check::err(ProgramError::InvalidAccountOwner))
+        .execute();
+}
+
+#[test]
+fn test_success_close_pointer() {
+    let authority = Pubkey::new_unique();
+    let mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(authority)
+        .build();
+    let pointer = pointer_account(&mint.key, spl_token_wrap::id());
+    let pointer_lamports = pointer.account.lamports;
+    let destination = KeyedAccount {
+        key: Pubkey::new_unique(),
+        account: Account {
+            lamports: 1_000,
+            ..Default::default()
+        },
+    };
+
+    let result = CloseCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(mint)
+        .canonical_pointer(pointer)
+        .destination(destination)
+        .execute();
+
+    assert_eq!(result.canonical_pointer.account.lamports, 0);
+    assert!(result.canonical_pointer.account.data.is_empty());
+    assert_eq!(
+        result.canonical_pointer.account.owner,
+        solana_system_interface::program::id()
+    );
+    assert_eq!(
+        result.destination.account.lamports,
+        1_000 + pointer_lamports
+    );
+}
+
+#[test]
+fn test_success_close_legacy_pointer() {
+    let authority = Pubkey::new_unique();
+    let mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(authority)
+        .build();
+    let legacy_pointer = KeyedAccount {
+        key: get_canonical_pointer_address(&mint.key),
+        account: Account {
+            lamports: Rent::default().minimum_balance(CanonicalDeploymentPointer::LEGACY_LEN),
+            owner: spl_token_wrap::id(),
+            data: Pubkey::new_unique().to_bytes().to_vec(),
+            ..Default::default()
+        },
+    };
+
+    let result = CloseCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(mint)
+        .canonical_pointer(legacy_pointer)
+        .execute();
+
+    assert_eq!(result.canonical_pointer.account.lamports, 0);
+    assert!(result.canonical_pointer.account.data.is_empty());
+}
+
+#[test]
+fn test_success_close_with_multisig_authority() {
+    let multisig = setup_multisig(TokenProgram::SplToken2022);
+
+    let result = CloseCanonicalPointerBuilder::default()
+        .multisig(multisig)
+        .execute();
+
+    assert_eq!(result.canonical_pointer.account.lamports, 0);
+}
+
+#[test]
+fn test_fail_close_timelocked_pointer() {
+    let authority = Pubkey::new_unique();
+    let mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(authority)
+        .build();
+    let pointer = timelocked_pointer_account(&mint.key, spl_token_wrap::id(), 60);
+
+    CloseCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(mint)
+        .canonical_pointer(pointer)
+        .check(Check::err(
+            TokenWrapError::CanonicalPointerTimelocked.into(),
+        ))
+        .execute();
+}
@@ -0,0 +1,291 @@
+use {
+    crate::helpers::{
+        close_wrapped_mint_builder::CloseWrappedMintBuilder,
+        common::{versioned_state_data, KeyedAccount, TokenProgram},
+        extensions::MintExtension,
+        mint_builder::MintBuilder,
+        token_account_builder::TokenAccountBuilder,
+    },
+    mollusk_svm::result::Check,
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        error::TokenWrapError,
+        get_escrow_address, get_wrapped_mint_address, get_wrapped_mint_authority,
+        get_wrapped_mint_backpointer_address,
+        state::{Backpointer, VersionedState},
+    },
+};
+
+pub mod helpers;
+
+fn closed_unwrapped_mint() -> KeyedAccount {
+    KeyedAccount {
+        key: Pubkey::new_unique(),
+        account: Account::default(),
+    }
+}
+
+fn wrapped_mint_address(unwrapped_mint: &KeyedAccount) -> Pubkey {
+    get_wrapped_mint_address(&unwrapped_mint.key, &spl_token_2022_interface::id())
+}
+
+#[test]
+fn test_close_wrapped_mint_success() {
+    let destination = KeyedAccount {
+        key: Pubkey::new_unique(),
+        account: Account {
+            lamports: 1_000,
+            ..Default::default()
+        },
+    };
+    let result = CloseWrappedMintBuilder::default()
+        .destination(destination)
+        .execute();
+
+    assert_eq!(result.wrapped_mint.account.lamports, 0);
+    assert_eq!(result.backpointer.account.lamports, 0);
+    assert!(result.backpointer.account.data.is_empty());
+    assert_eq!(
+        result.backpointer.account.owner,
+        solana_system_interface::program::id()
+    );
+
+    let mint_rent = Rent::default().minimum_balance(
+        MintBuilder::new()
+            .token_program(TokenProgram::SplToken2022)
+            .with_extension(MintExtension::MintCloseAuthority(Pubkey::new_unique()))
+            .build()
+            .account
+            .data
+            .len(),
+    );
+    let backpointer_rent = Rent::default().minimum_balance(Backpointer::LEN);
+    assert!(result.destination.account.lamports >= 1_000 + mint_rent + backpointer_rent);
+}
+
+#[test]
+fn test_close_wrapped_mint_with_empty_escrow() {
+    let unwrapped_mint = closed_unwrapped_mint();
+    let wrapped_mint_authority = get_wrapped_mint_authority(&wrapped_mint_address(&unwrapped_mint));
+    let escrow_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .mint_key(unwrapped_mint.key)
+        .build();
+    let escrow = TokenAccountBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .mint(escrow_mint)
+        .owner(wrapped_mint_authority)
+        .amount(0)
+        .account_key(get_escrow_address(
+            &unwrapped_mint.key,
+            &spl_token_2022_interface::id(),
+            &spl_token_2022_interface::id(),
+        ))
+        .build();
+
+    CloseWrappedMintBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .escrow(escrow)
+        .execute();
+}
+
+#[test]
+fn test_close_wrapped_mint_escrow_not_empty() {
+    let unwrapped_mint = closed_unwrapped_mint();
+    let wrapped_mint_authority = get_wrapped_mint_authority(&wrapped_mint_address(&unwrapped_mint));
+    let escrow_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_key(unwrapped_mint.key)
+        .build();
+    let escrow = TokenAccountBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint(escrow_mint)
+        .owner(wrapped_mint_authority)
+        .amount(1)
+        .account_key(get_escrow_address(
+            &unwrapped_mint.key,
+            &spl_token::id(),
+            &spl_token_2022_interface::id(),
+        ))
+        .build();
+
+    CloseWrappedMintBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .unwrapped_token_program(TokenProgram::SplToken)
+        .escrow(escrow)
+        .check(Check::err(TokenWrapError::EscrowNotEmpty.into()))
+        .execute();
+}
+
+#[test]
+fn test_close_wrapped_mint_escrow_mismatch() {
+    let unwrapped_mint = closed_unwrapped_mint();
+    // Escrow of the other unwrapped token program
+    let escrow = KeyedAccount {
+        key: get_escrow_address(
+            &unwrapped_mint.key,
+            &spl_token::id(),
+            &spl_token_2022_interface::id(),
+        ),
+        account: Account::default(),
+    };
+
+    CloseWrappedMintBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .escrow(escrow)
+        .check(Check::err(TokenWrapError::EscrowMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_close_wrapped_mint_unwrapped_token_program_mismatch() {
+    let unwrapped_mint = closed_unwrapped_mint();
+    let backpointer = KeyedAccount {
+        key: get_wrapped_mint_backpointer_address(&wrapped_mint_address(&unwrapped_mint)),
+        account: Account {
+            lamports: Rent::default().minimum_balance(Backpointer::LEN),
+            owner: spl_token_wrap::id(),
+            data: versioned_state_data(&Backpointer {
+                unwrapped_token_program: spl_token_2022_interface::id(),
+                wrapped_token_program: spl_token_2022_interface::id(),
+                ..Backpointer::without_provenance(unwrapped_mint.key)
+            }),
+            ..Default::default()
+        },
+    };
+
+    CloseWrappedMintBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .unwrapped_token_program(TokenProgram::SplToken)
+        .backpointer(backpointer)
+        .check(Check::err(ProgramError::IncorrectProgramId))
+        .execute();
+}
+
+#[test]
+fn test_close_wrapped_mint_has_supply() {
+    CloseWrappedMintBuilder::default()
+        .wrapped_supply(1)
+        .check(Check::err(TokenWrapError::WrappedMintHasSupply.into()))
+        .execute();
+}
+
+#[test]
+fn test_close_wrapped_mint_unwrapped_mint_not_closed() {
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .build();
+
+    CloseWrappedMintBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .check(Check::err(TokenWrapError::UnwrappedMintNotClosed.into()))
+        .execute();
+}
+
+#[test]
+fn test_close_wrapped_mint_without_close_authority() {
+    let unwrapped_mint = closed_unwrapped_mint();
+    let wrapped_mint_address = wrapped_mint_address(&unwrapped_mint);
+    let wrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .mint_key(wrapped_mint_address)
+        .mint_authority(get_wrapped_mint_authority(&wrapped_mint_address))
+        .supply(0)
+        .build();
+
+    CloseWrappedMintBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .wrapped_mint(wrapped_mint)
+        .check(Check::err(TokenWrapError::WrappedMintNotClosable.into()))
+        .execute();
+}
+
+#[test]
+fn test_close_wrapped_mint_other_close_authority() {
+    let unwrapped_mint = closed_unwrapped_mint();
+    let wrapped_mint_address = wrapped_mint_address(&unwrapped_mint);
+    let wrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .mint_key(wrapped_mint_address)
+        .mint_authority(get_wrapped_mint_authority(&wrapped_mint_address))
+        .supply(0)
+        .with_extension(MintExtension::MintCloseAuthority(Pubkey::new_unique()))
+        .build();
+
+    CloseWrappedMintBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .wrapped_mint(wrapped_mint)
+        .check(Check::err(TokenWrapError::WrappedMintNotClosable.into()))
+        .execute();
+}
+
+#[test]
+fn test_close_wrapped_mint_backpointer_mismatch() {
+    let backpointer = KeyedAccount {
+        key: Pubkey::new_unique(),
+        account: Account::default(),
+    };
+
+    CloseWrappedMintBuilder::default()
+        .backpointer(backpointer)
+        .check(Check::err(TokenWrapError::BackpointerMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_close_wrapped_mint_unwrapped_mint_mismatch() {
+    let unwrapped_mint = closed_unwrapped_mint();
+    let backpointer = KeyedAccount {
+        key: get_wrapped_mint_backpointer_address(&wrapped_mint_address(&unwrapped_mint)),
+        account: Account {
+            lamports: Rent::default().minimum_balance(Backpointer::LEN),
+            owner: spl_token_wrap::id(),
+            data: versioned_state_data(&Backpointer::without_provenance(Pubkey::new_unique())),
+            ..Default::default()
+        },
+    };
+
+    CloseWrappedMintBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .backpointer(backpointer)
+        .check(Check::err(TokenWrapError::UnwrappedMintMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_close_wrapped_mint_legacy_backpointer() {
+    let unwrapped_mint = closed_unwrapped_mint();
+    let backpointer = KeyedAccount {
+        key: get_wrapped_mint_backpointer_address(&wrapped_mint_address(&unwrapped_mint)),
+        account: Account {
+            lamports: Rent::default().minimum_balance(Backpointer::LEGACY_LEN),
+            owner: spl_token_wrap::id(),
+            data: unwrapped_mint.key.to_bytes().to_vec(),
+            ..Default::default()
+        },
+    };
+
+    let result = CloseWrappedMintBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .backpointer(backpointer)
+        .execute();
+    assert_eq!(result.backpointer.account.lamports, 0);
+}
+
+#[test]
+fn test_close_wrapped_mint_spl_token_not_closable() {
+    let unwrapped_mint = closed_unwrapped_mint();
+    let wrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_key(wrapped_mint_address(&unwrapped_mint))
+        .build();
+
+    CloseWrappedMintBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .wrapped_mint(wrapped_mint)
+        .check(Check::err(TokenWrapError::WrappedMintNotClosable.into()))
+        .execute();
+}
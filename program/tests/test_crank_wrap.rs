@@ -0,0 +1,253 @@
+use {
+    crate::helpers::{
+        common::{KeyedAccount, TokenProgram},
+        crank_wrap::{crank_wrap_delegate_account, CrankWrapper},
+        set_crank_wrap_recipient_builder::SetCrankWrapRecipientBuilder,
+        wrap_builder::WrapBuilder,
+    },
+    mollusk_svm::result::Check,
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    spl_token_2022_interface::{
+        error::TokenError,
+        extension::{ExtensionType, PodStateWithExtensions},
+        pod::PodAccount,
+    },
+    spl_token_wrap::{
+        error::TokenWrapError,
+        get_crank_wrap_delegate_address,
+        state::{CrankWrapDelegate, VersionedState},
+    },
+    test_case::test_case,
+};
+
+pub mod helpers;
+
+fn token_amount(account: &KeyedAccount) -> u64 {
+    PodStateWithExtensions::<PodAccount>::unpack(&account.account.data)
+        .unwrap()
+        .base
+        .amount
+        .into()
+}
+
+#[test]
+fn test_set_crank_wrap_recipient() {
+    let owner = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+
+    let result = SetCrankWrapRecipientBuilder::default()
+        .owner(owner)
+        .recipient(recipient)
+        .execute();
+
+    assert_eq!(
+        result.crank_wrap_delegate.account.owner,
+        spl_token_wrap::id()
+    );
+    let crank_wrap_delegate =
+        CrankWrapDelegate::unpack_versioned(&result.crank_wrap_delegate.account.data).unwrap();
+    assert_eq!(crank_wrap_delegate.recipient, recipient);
+}
+
+#[test]
+fn test_set_crank_wrap_recipient_updates_existing() {
+    let owner = Pubkey::new_unique();
+    let unwrapped_mint = KeyedAccount {
+        key: Pubkey::new_unique(),
+        account: Account::default(),
+    };
+    let recipient = Pubkey::new_unique();
+
+    let result = SetCrankWrapRecipientBuilder::default()
+        .owner(owner)
+        .unwrapped_mint(unwrapped_mint.clone())
+        .crank_wrap_delegate(KeyedAccount {
+            key: get_crank_wrap_delegate_address(&owner, &unwrapped_mint.key),
+            account: crank_wrap_delegate_account(&Pubkey::new_unique()),
+        })
+        .recipient(recipient)
+        .execute();
+
+    let crank_wrap_delegate =
+        CrankWrapDelegate::unpack_versioned(&result.crank_wrap_delegate.account.data).unwrap();
+    assert_eq!(crank_wrap_delegate.recipient, recipient);
+}
+
+#[test]
+fn test_set_crank_wrap_recipient_fails_owner_not_signer() {
+    SetCrankWrapRecipientBuilder::default()
+        .owner_not_signer()
+        .check(Check::err(ProgramError::MissingRequiredSignature))
+        .execute();
+}
+
+#[test]
+fn test_set_crank_wrap_recipient_fails_incorrect_address() {
+    SetCrankWrapRecipientBuilder::default()
+        .crank_wrap_delegate(KeyedAccount::default())
+        .check(Check::err(TokenWrapError::CrankWrapDelegateMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_set_crank_wrap_recipient_fails_not_owned_by_program() {
+    let owner = Pubkey::new_unique();
+    let unwrapped_mint = KeyedAccount {
+        key: Pubkey::new_unique(),
+        account: Account::default(),
+    };
+    let mut account = crank_wrap_delegate_account(&Pubkey::new_unique());
+    account.owner = Pubkey::new_unique();
+
+    SetCrankWrapRecipientBuilder::default()
+        .owner(owner)
+        .unwrapped_mint(unwrapped_mint.clone())
+        .crank_wrap_delegate(KeyedAccount {
+            key: get_crank_wrap_delegate_address(&owner, &unwrapped_mint.key),
+            account,
+        })
+        .check(Check::err(ProgramError::InvalidAccountOwner))
+        .execute();
+}
+
+#[test_case(TokenProgram::SplToken, TokenProgram::SplToken2022)]
+#[test_case(TokenProgram::SplToken2022, TokenProgram::SplToken)]
+fn test_crank_wrap(unwrapped_token_program: TokenProgram, wrapped_token_program: TokenProgram) {
+    let wrap_amount = 5_000;
+
+    let result = WrapBuilder::default()
+        .unwrapped_token_program(unwrapped_token_program)
+        .wrapped_token_program(wrapped_token_program)
+        .wrap_amount(wrap_amount)
+        .crank(CrankWrapper::default())
+        .execute();
+
+    assert_eq!(token_amount(&result.unwrapped_token), 0);
+    assert_eq!(token_amount(&result.unwrapped_escrow), wrap_amount);
+    assert_eq!(token_amount(&result.recipient_wrapped_token), wrap_amount);
+
+    // The crank used up the approval of the delegate
+    let unwrapped_token =
+        PodStateWithExtensions::<PodAccount>::unpack(&result.unwrapped_token.account.data).unwrap();
+    assert_eq!(u64::from(unwrapped_token.base.delegated_amount), 0);
+}
+
+#[test]
+fn test_wrap_fails_from_cpi_guarded_account() {
+    WrapBuilder::default()
+        .unwrapped_token_program(TokenProgram::SplToken2022)
+        .unwrapped_token_extension(ExtensionType::CpiGuard)
+        .check(Check::err(TokenError::CpiGuardTransferBlocked.into()))
+        .execute();
+}
+
+#[test]
+fn test_crank_wrap_from_cpi_guarded_account() {
+    let wrap_amount = 5_000;
+
+    let result = WrapBuilder::default()
+        .unwrapped_token_program(TokenProgram::SplToken2022)
+        .unwrapped_token_extension(ExtensionType::CpiGuard)
+        .wrap_amount(wrap_amount)
+        .crank(CrankWrapper::default())
+        .execute();
+
+    assert_eq!(token_amount(&result.unwrapped_token), 0);
+    assert_eq!(token_amount(&result.recipient_wrapped_token), wrap_amount);
+}
+
+#[test]
+fn test_crank_wrap_capped_by_balance() {
+    let result = WrapBuilder::default()
+        .unwrapped_token_starting_amount(400)
+        .crank(CrankWrapper::default().delegated_amount(1_000))
+        .execute();
+
+    assert_eq!(token_amount(&result.unwrapped_token), 0);
+    assert_eq!(token_amount(&result.recipient_wrapped_token), 400);
+
+    // The rest of the approval remains for later inflows
+    let unwrapped_token =
+        PodStateWithExtensions::<PodAccount>::unpack(&result.unwrapped_token.account.data).unwrap();
+    assert_eq!(u64::from(unwrapped_token.base.delegated_amount), 600);
+}
+
+#[test]
+fn test_crank_wrap_partial_approval() {
+    let result = WrapBuilder::default()
+        .unwrapped_token_starting_amount(1_000)
+        .crank(CrankWrapper::default().delegated_amount(300))
+        .execute();
+
+    assert_eq!(token_amount(&result.unwrapped_token), 700);
+    assert_eq!(token_amount(&result.recipient_wrapped_token), 300);
+}
+
+#[test]
+fn test_crank_wrap_fails_nothing_delegated() {
+    WrapBuilder::default()
+        .crank(CrankWrapper::default().delegated_amount(0))
+        .check(Check::err(TokenWrapError::ZeroWrapAmount.into()))
+        .execute();
+}
+
+#[test]
+fn test_crank_wrap_fails_not_approved() {
+    WrapBuilder::default()
+        .crank(CrankWrapper::default().not_approved())
+        .check(Check::err(TokenWrapError::CrankWrapNotApproved.into()))
+        .execute();
+}
+
+#[test]
+fn test_crank_wrap_fails_other_delegate_approved() {
+    WrapBuilder::default()
+        .crank(CrankWrapper::default().approved_delegate(Pubkey::new_unique()))
+        .check(Check::err(TokenWrapError::CrankWrapNotApproved.into()))
+        .execute();
+}
+
+#[test]
+fn test_crank_wrap_fails_incorrect_delegate_address() {
+    let delegate = Pubkey::new_unique();
+
+    WrapBuilder::default()
+        .crank(
+            CrankWrapper::default()
+                .approved_delegate(delegate)
+                .delegate_address(delegate),
+        )
+        .check(Check::err(TokenWrapError::CrankWrapDelegateMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_crank_wrap_fails_other_recipient() {
+    WrapBuilder::default()
+        .crank(CrankWrapper::default().designated_recipient(Pubkey::new_unique()))
+        .check(Check::err(
+            TokenWrapError::CrankWrapRecipientMismatch.into(),
+        ))
+        .execute();
+}
+
+#[test]
+fn test_crank_wrap_fails_recipient_not_set() {
+    WrapBuilder::default()
+        .crank(CrankWrapper::default().delegate_account(Account::default()))
+        .check(Check::err(ProgramError::UninitializedAccount))
+        .execute();
+}
+
+#[test]
+fn test_crank_wrap_fails_delegate_not_owned_by_program() {
+    let mut account = crank_wrap_delegate_account(&Pubkey::new_unique());
+    account.owner = Pubkey::new_unique();
+
+    WrapBuilder::default()
+        .crank(CrankWrapper::default().delegate_account(account))
+        .check(Check::err(ProgramError::InvalidAccountOwner))
+        .execute();
+}
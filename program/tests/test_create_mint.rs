@@ -16,19 +16,25 @@ use {
         extension::{
             confidential_transfer::ConfidentialTransferMint,
             metadata_pointer::MetadataPointer,
+            mint_close_authority::MintCloseAuthority,
             BaseStateWithExtensions,
             ExtensionType::{
                 ConfidentialTransferMint as ConfidentialTransferMintExt,
-                MetadataPointer as MetadataPointerExt,
+                MetadataPointer as MetadataPointerExt, MintCloseAuthority as MintCloseAuthorityExt,
             },
             PodStateWithExtensions,
         },
         pod::PodMint,
-        state::Mint,
+        state::{AccountState, Mint},
     },
     spl_token_wrap::{
-        error::TokenWrapError, get_wrapped_mint_address, get_wrapped_mint_authority,
-        state::Backpointer,
+        error::TokenWrapError,
+        get_wrapped_mint_address, get_wrapped_mint_authority,
+        mint_customizer::{
+            default_token_2022::DefaultToken2022Customizer, interface::MintCustomizer,
+        },
+        state::{Backpointer, VersionedState},
+        PROGRAM_VERSION,
     },
     test_case::test_case,
 };
@@ -138,7 +144,7 @@ fn test_create_mint_insufficient_funds() {
 fn test_create_mint_backpointer_insufficient_funds() {
     // Calculate minimum rent for Backpointer account
     let rent = Rent::default(); // Using default rent for test
-    let backpointer_space = std::mem::size_of::<Backpointer>();
+    let backpointer_space = Backpointer::LEN;
     let backpointer_rent_required = rent.minimum_balance(backpointer_space);
 
     // Create wrapped_backpointer_account with insufficient lamports
@@ -204,6 +210,7 @@ fn test_successful_spl_token_to_spl_token_2022() {
         .unwrapped_token_program(TokenProgram::SplToken)
         .wrapped_token_program(TokenProgram::SplToken2022)
         .freeze_authority(freeze_authority)
+        .slot(42)
         .execute();
 
     // Assert state of resulting wrapped mint account
@@ -230,9 +237,45 @@ fn test_successful_spl_token_to_spl_token_2022() {
         result.wrapped_backpointer.account.owner,
         spl_token_wrap::id()
     );
-    let backpointer =
-        bytemuck::from_bytes::<Backpointer>(&result.wrapped_backpointer.account.data[..]);
+    let backpointer = Backpointer::unpack(&result.wrapped_backpointer.account.data[..]).unwrap();
     assert_eq!(backpointer.unwrapped_mint, result.unwrapped_mint.key);
+
+    // Assert provenance recorded in the backpointer
+
+    assert!(backpointer.has_provenance());
+    assert_eq!(backpointer.unwrapped_token_program, spl_token::id());
+    assert_eq!(
+        backpointer.wrapped_token_program,
+        spl_token_2022_interface::id()
+    );
+    assert_eq!(backpointer.customizer_id, DefaultToken2022Customizer::ID);
+    assert_eq!(u64::from(backpointer.creation_slot), 42);
+    assert_eq!(backpointer.program_version, PROGRAM_VERSION);
+
+    let (wrapped_mint_address, wrapped_mint_bump) = Pubkey::find_program_address(
+        &[
+            b"mint",
+            result.unwrapped_mint.key.as_ref(),
+            spl_token_2022_interface::id().as_ref(),
+        ],
+        &spl_token_wrap::id(),
+    );
+    assert_eq!(wrapped_mint_address, result.wrapped_mint.key);
+    assert_eq!(backpointer.wrapped_mint_bump, wrapped_mint_bump);
+    let (_, wrapped_mint_authority_bump) = Pubkey::find_program_address(
+        &[b"authority", result.wrapped_mint.key.as_ref()],
+        &spl_token_wrap::id(),
+    );
+    assert_eq!(
+        backpointer.wrapped_mint_authority_bump,
+        wrapped_mint_authority_bump
+    );
+    let (backpointer_address, backpointer_bump) = Pubkey::find_program_address(
+        &[b"backpointer", result.wrapped_mint.key.as_ref()],
+        &spl_token_wrap::id(),
+    );
+    assert_eq!(backpointer_address, result.wrapped_backpointer.key);
+    assert_eq!(backpointer.backpointer_bump, backpointer_bump);
 }
 
 #[test]
@@ -278,15 +321,19 @@ fn test_successful_spl_token_2022_to_spl_token() {
         spl_token_wrap::id()
     );
 
-    let backpointer =
-        bytemuck::from_bytes::<Backpointer>(&result.wrapped_backpointer.account.data[..]);
+    let backpointer = Backpointer::unpack(&result.wrapped_backpointer.account.data[..]).unwrap();
     assert_eq!(backpointer.unwrapped_mint, unwrapped_mint_address);
+    assert_eq!(
+        backpointer.unwrapped_token_program,
+        spl_token_2022_interface::id()
+    );
+    assert_eq!(backpointer.wrapped_token_program, wrapped_token_program_id);
 }
 
 #[test_case(MintExtension::ConfidentialTransfer)]
-#[test_case(MintExtension::TransferHook)]
 #[test_case(MintExtension::TransferFeeConfig)]
 #[test_case(MintExtension::MintCloseAuthority(Pubkey::new_unique()))]
+#[test_case(MintExtension::DefaultAccountState(AccountState::Initialized))]
 fn test_create_mint_from_extended_mint(extension: MintExtension) {
     let unwrapped_mint = MintBuilder::new()
         .token_program(TokenProgram::SplToken2022)
@@ -321,8 +368,7 @@ fn test_create_mint_from_extended_mint(extension: MintExtension) {
         result.wrapped_backpointer.account.owner,
         spl_token_wrap::id()
     );
-    let backpointer =
-        bytemuck::from_bytes::<Backpointer>(&result.wrapped_backpointer.account.data[..]);
+    let backpointer = Backpointer::unpack(&result.wrapped_backpointer.account.data[..]).unwrap();
     assert_eq!(backpointer.unwrapped_mint, unwrapped_mint.key);
 }
 
@@ -363,9 +409,10 @@ fn test_customizer_applies_all_default_extensions() {
         PodStateWithExtensions::<PodMint>::unpack(&result.wrapped_mint.account.data).unwrap();
 
     let extensions = wrapped_mint_state.get_extension_types().unwrap();
-    assert_eq!(extensions.len(), 2);
+    assert_eq!(extensions.len(), 3);
     assert!(extensions.contains(&ConfidentialTransferMintExt));
     assert!(extensions.contains(&MetadataPointerExt));
+    assert!(extensions.contains(&MintCloseAuthorityExt));
 
     assert!(wrapped_mint_state
         .get_extension::<ConfidentialTransferMint>()
@@ -384,4 +431,13 @@ fn test_customizer_applies_all_default_extensions() {
         Option::<Pubkey>::from(pointer_ext.metadata_address).unwrap(),
         result.wrapped_mint.key
     );
+
+    // Verify MintCloseAuthority content
+    let close_authority_ext = wrapped_mint_state
+        .get_extension::<MintCloseAuthority>()
+        .unwrap();
+    assert_eq!(
+        Option::<Pubkey>::from(close_authority_ext.close_authority).unwrap(),
+        expected_mint_authority
+    );
 }
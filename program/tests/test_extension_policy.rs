@@ -0,0 +1,145 @@
+use {
+    crate::helpers::{
+        common::TokenProgram, create_mint_builder::CreateMintBuilder, extensions::MintExtension,
+        mint_builder::MintBuilder,
+    },
+    mollusk_svm::result::Check,
+    solana_pubkey::Pubkey,
+    spl_token_2022_interface::{extension::ExtensionType, state::AccountState},
+    spl_token_wrap::{
+        error::TokenWrapError,
+        extension_policy::{analyze_mint_extensions, ExtensionFinding, ExtensionRisk},
+    },
+    test_case::test_case,
+};
+
+pub mod helpers;
+
+fn create_mint_builder<'a>(extensions: &[MintExtension]) -> CreateMintBuilder<'a> {
+    let mut mint_builder = MintBuilder::new().token_program(TokenProgram::SplToken2022);
+    for extension in extensions {
+        mint_builder = mint_builder.with_extension(extension.clone());
+    }
+    let unwrapped_mint = mint_builder.build();
+
+    CreateMintBuilder::default()
+        .unwrapped_mint_account(unwrapped_mint.account)
+        .unwrapped_mint_addr(unwrapped_mint.key)
+        .unwrapped_token_program(TokenProgram::SplToken2022)
+        .wrapped_token_program(TokenProgram::SplToken2022)
+}
+
+#[test_case(MintExtension::PermanentDelegate(Pubkey::new_unique()))]
+#[test_case(MintExtension::TransferHook)]
+#[test_case(MintExtension::Pausable)]
+fn test_risky_extension_requires_acknowledgement(extension: MintExtension) {
+    create_mint_builder(&[extension])
+        .check(Check::err(
+            TokenWrapError::RiskyExtensionNotAcknowledged.into(),
+        ))
+        .execute();
+}
+
+#[test_case(MintExtension::PermanentDelegate(Pubkey::new_unique()))]
+#[test_case(MintExtension::TransferHook)]
+#[test_case(MintExtension::Pausable)]
+fn test_risky_extension_acknowledged(extension: MintExtension) {
+    create_mint_builder(&[extension])
+        .acknowledge_risky_extensions()
+        .execute();
+}
+
+#[test]
+fn test_non_transferable_rejected() {
+    create_mint_builder(&[MintExtension::NonTransferable])
+        .acknowledge_risky_extensions()
+        .check(Check::err(
+            TokenWrapError::UnwrappedMintNonTransferable.into(),
+        ))
+        .execute();
+}
+
+#[test]
+fn test_default_frozen_rejected() {
+    create_mint_builder(&[MintExtension::DefaultAccountState(AccountState::Frozen)])
+        .acknowledge_risky_extensions()
+        .check(Check::err(
+            TokenWrapError::UnwrappedMintDefaultFrozen.into(),
+        ))
+        .execute();
+}
+
+#[test]
+fn test_rejection_takes_precedence_over_acknowledgement() {
+    create_mint_builder(&[
+        MintExtension::PermanentDelegate(Pubkey::new_unique()),
+        MintExtension::NonTransferable,
+    ])
+    .acknowledge_risky_extensions()
+    .check(Check::err(
+        TokenWrapError::UnwrappedMintNonTransferable.into(),
+    ))
+    .execute();
+}
+
+#[test]
+fn test_spl_token_mint_not_checked() {
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .build();
+
+    CreateMintBuilder::default()
+        .unwrapped_mint_account(unwrapped_mint.account)
+        .unwrapped_mint_addr(unwrapped_mint.key)
+        .execute();
+}
+
+#[test]
+fn test_idempotent_create_skips_policy() {
+    let result = create_mint_builder(&[MintExtension::Pausable])
+        .acknowledge_risky_extensions()
+        .execute();
+
+    // Already created, the policy is not evaluated again
+    create_mint_builder(&[MintExtension::Pausable])
+        .unwrapped_mint_addr(result.unwrapped_mint.key)
+        .wrapped_mint_account(result.wrapped_mint.account)
+        .backpointer_account(result.wrapped_backpointer.account)
+        .idempotent()
+        .execute();
+}
+
+#[test]
+fn test_analyze_mint_extensions() {
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .with_extension(MintExtension::TransferFeeConfig)
+        .with_extension(MintExtension::PermanentDelegate(Pubkey::new_unique()))
+        .with_extension(MintExtension::DefaultAccountState(
+            AccountState::Initialized,
+        ))
+        .with_extension(MintExtension::NonTransferable)
+        .build();
+
+    let findings = analyze_mint_extensions(&unwrapped_mint.account.data).unwrap();
+    assert_eq!(
+        findings,
+        vec![
+            ExtensionFinding {
+                extension_type: ExtensionType::PermanentDelegate,
+                risk: ExtensionRisk::Risky,
+            },
+            ExtensionFinding {
+                extension_type: ExtensionType::NonTransferable,
+                risk: ExtensionRisk::Rejected(TokenWrapError::UnwrappedMintNonTransferable),
+            },
+        ]
+    );
+
+    let spl_token_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .build();
+    assert!(analyze_mint_extensions(&spl_token_mint.account.data)
+        .unwrap()
+        .is_empty());
+}
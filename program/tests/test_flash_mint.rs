@@ -0,0 +1,332 @@
+use {
+    crate::helpers::{
+        common::{versioned_state_data, KeyedAccount, TokenProgram},
+        flash_mint_builder::FlashMintBuilder,
+        mint_builder::MintBuilder,
+        set_flash_mint_fee_builder::SetFlashMintFeeBuilder,
+    },
+    bytemuck::Zeroable,
+    mollusk_svm::result::Check,
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_2022_interface::{
+        error::TokenError,
+        extension::PodStateWithExtensions,
+        pod::{PodAccount, PodMint},
+    },
+    spl_token_wrap::{
+        error::TokenWrapError,
+        get_canonical_pointer_address, get_wrap_limits_address,
+        state::{
+            Allowlist, AllowlistEntry, CanonicalDeploymentPointer, FlashMintGuard, VersionedState,
+            WrapLimits,
+        },
+    },
+    test_case::test_case,
+};
+
+pub mod helpers;
+
+fn token_amount(account: &KeyedAccount) -> u64 {
+    PodStateWithExtensions::<PodAccount>::unpack(&account.account.data)
+        .unwrap()
+        .base
+        .amount
+        .into()
+}
+
+fn unwrapped_mint() -> KeyedAccount {
+    MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(Pubkey::new_unique())
+        .build()
+}
+
+fn state_account<T: VersionedState>(state: &T) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(T::LEN),
+        owner: spl_token_wrap::id(),
+        data: versioned_state_data(state),
+        ..Default::default()
+    }
+}
+
+fn mint_supply(account: &KeyedAccount) -> u64 {
+    PodStateWithExtensions::<PodMint>::unpack(&account.account.data)
+        .unwrap()
+        .base
+        .supply
+        .into()
+}
+
+#[test_case(TokenProgram::SplToken)]
+#[test_case(TokenProgram::SplToken2022)]
+fn test_flash_mint_and_repay(wrapped_token_program: TokenProgram) {
+    let result = FlashMintBuilder::default()
+        .wrapped_token_program(wrapped_token_program)
+        .amount(50_000)
+        .execute();
+
+    assert_eq!(token_amount(&result.recipient_wrapped_token_account), 0);
+    assert_eq!(mint_supply(&result.wrapped_mint), 100_000);
+    assert_eq!(token_amount(&result.unwrapped_escrow), 100_000);
+
+    // The guard is created on the first flash mint and cleared by the repay
+    assert_eq!(result.flash_mint_guard.account.owner, spl_token_wrap::id());
+    let guard = FlashMintGuard::unpack_versioned(&result.flash_mint_guard.account.data).unwrap();
+    assert!(!guard.is_active());
+    assert_eq!(u64::from(guard.amount), 0);
+}
+
+#[test]
+fn test_flash_mint_burns_fee() {
+    let result = FlashMintBuilder::default()
+        .fee_basis_points(30)
+        .amount(10_000)
+        .borrower_balance(100)
+        .execute();
+
+    // The fee is burned, leaving the escrow with a surplus
+    assert_eq!(token_amount(&result.recipient_wrapped_token_account), 70);
+    assert_eq!(mint_supply(&result.wrapped_mint), 99_970);
+    assert_eq!(token_amount(&result.unwrapped_escrow), 100_000);
+
+    let guard = FlashMintGuard::unpack_versioned(&result.flash_mint_guard.account.data).unwrap();
+    assert_eq!(u16::from(guard.fee_basis_points), 30);
+    assert_eq!(u64::from(guard.fee), 0);
+}
+
+#[test]
+fn test_flash_mint_fee_rounds_up() {
+    let result = FlashMintBuilder::default()
+        .fee_basis_points(30)
+        .amount(1)
+        .borrower_balance(1)
+        .execute();
+
+    assert_eq!(token_amount(&result.recipient_wrapped_token_account), 0);
+}
+
+#[test]
+fn test_flash_repay_fails_without_fee_balance() {
+    FlashMintBuilder::default()
+        .fee_basis_points(30)
+        .flash_repay_check(Check::err(TokenError::InsufficientFunds.into()))
+        .execute();
+}
+
+#[test]
+fn test_flash_mint_fails_without_repay() {
+    FlashMintBuilder::default()
+        .without_repay()
+        .flash_mint_check(Check::err(TokenWrapError::FlashRepayMissing.into()))
+        .execute();
+}
+
+#[test]
+fn test_flash_mint_fails_nested() {
+    FlashMintBuilder::default()
+        .nested()
+        .flash_mint_check(Check::err(TokenWrapError::FlashMintActive.into()))
+        .execute();
+}
+
+#[test]
+fn test_flash_mint_fails_zero_amount() {
+    FlashMintBuilder::default()
+        .amount(0)
+        .flash_mint_check(Check::err(TokenWrapError::ZeroWrapAmount.into()))
+        .execute();
+}
+
+#[test]
+fn test_flash_mint_fails_incorrect_guard_address() {
+    FlashMintBuilder::default()
+        .flash_mint_guard(KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        })
+        .flash_mint_check(Check::err(TokenWrapError::FlashMintGuardMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_flash_mint_rejected_by_enforced_pointer_to_other_deployment() {
+    let unwrapped_mint = unwrapped_mint();
+    let pointer = state_account(&CanonicalDeploymentPointer {
+        program_id: Pubkey::new_unique(),
+        enforce_on_wrap: true.into(),
+        change_delay: 0.into(),
+    });
+
+    FlashMintBuilder::default()
+        .unwrapped_mint(unwrapped_mint.clone())
+        .canonical_pointer(KeyedAccount {
+            key: get_canonical_pointer_address(&unwrapped_mint.key),
+            account: pointer,
+        })
+        .flash_mint_check(Check::err(TokenWrapError::NonCanonicalDeployment.into()))
+        .execute();
+}
+
+#[test]
+fn test_flash_mint_fails_unwrap_only() {
+    let unwrapped_mint = unwrapped_mint();
+    let limits = state_account(&WrapLimits {
+        unwrap_only: true.into(),
+        ..WrapLimits::zeroed()
+    });
+
+    FlashMintBuilder::default()
+        .unwrapped_mint(unwrapped_mint.clone())
+        .wrap_limits(KeyedAccount {
+            key: get_wrap_limits_address(&unwrapped_mint.key),
+            account: limits,
+        })
+        .flash_mint_check(Check::err(TokenWrapError::UnwrapOnly.into()))
+        .execute();
+}
+
+#[test]
+fn test_flash_mint_fails_over_wrapped_supply_cap() {
+    let unwrapped_mint = unwrapped_mint();
+    let limits = state_account(&WrapLimits {
+        max_wrapped_supply: 105_000.into(),
+        total_wrapped: 100_000.into(),
+        ..WrapLimits::zeroed()
+    });
+
+    FlashMintBuilder::default()
+        .unwrapped_mint(unwrapped_mint.clone())
+        .wrap_limits(KeyedAccount {
+            key: get_wrap_limits_address(&unwrapped_mint.key),
+            account: limits,
+        })
+        .flash_mint_check(Check::err(TokenWrapError::WrappedSupplyCapExceeded.into()))
+        .execute();
+}
+
+#[test]
+fn test_flash_mint_does_not_record_wrap_limits() {
+    let unwrapped_mint = unwrapped_mint();
+    let limits = state_account(&WrapLimits {
+        max_wrapped_supply: 200_000.into(),
+        total_wrapped: 100_000.into(),
+        ..WrapLimits::zeroed()
+    });
+
+    let result = FlashMintBuilder::default()
+        .unwrapped_mint(unwrapped_mint.clone())
+        .wrap_limits(KeyedAccount {
+            key: get_wrap_limits_address(&unwrapped_mint.key),
+            account: limits.clone(),
+        })
+        .execute();
+
+    assert_eq!(result.wrap_limits.account.data, limits.data);
+}
+
+#[test]
+fn test_flash_mint_allowlisted_borrower() {
+    let result = FlashMintBuilder::default()
+        .allowlist(state_account(&Allowlist {
+            enabled: true.into(),
+        }))
+        .allowlist_entry(state_account(&AllowlistEntry {
+            allowed: true.into(),
+        }))
+        .execute();
+
+    assert_eq!(token_amount(&result.recipient_wrapped_token_account), 0);
+}
+
+#[test]
+fn test_flash_mint_fails_not_allowlisted() {
+    FlashMintBuilder::default()
+        .allowlist(state_account(&Allowlist {
+            enabled: true.into(),
+        }))
+        .flash_mint_check(Check::err(TokenWrapError::NotAllowlisted.into()))
+        .execute();
+}
+
+#[test]
+fn test_flash_repay_fails_without_flash_mint() {
+    FlashMintBuilder::default()
+        .without_flash_mint()
+        .flash_repay_check(Check::err(TokenWrapError::FlashMintNotActive.into()))
+        .execute();
+}
+
+#[test]
+fn test_flash_repay_fails_incorrect_token_program() {
+    FlashMintBuilder::default()
+        .repay_token_program(TokenProgram::SplToken)
+        .flash_repay_check(Check::err(ProgramError::IncorrectProgramId))
+        .execute();
+}
+
+#[test]
+fn test_set_flash_mint_fee() {
+    let authority = Pubkey::new_unique();
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(authority)
+        .build();
+
+    let result = SetFlashMintFeeBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(unwrapped_mint.clone())
+        .fee_basis_points(30)
+        .execute();
+
+    assert_eq!(result.flash_mint_guard.account.owner, spl_token_wrap::id());
+    let guard = FlashMintGuard::unpack_versioned(&result.flash_mint_guard.account.data).unwrap();
+    assert_eq!(u16::from(guard.fee_basis_points), 30);
+    assert!(!guard.is_active());
+
+    // Updating the fee keeps the account
+    let result = SetFlashMintFeeBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(unwrapped_mint)
+        .flash_mint_guard(result.flash_mint_guard)
+        .fee_basis_points(0)
+        .execute();
+
+    let guard = FlashMintGuard::unpack_versioned(&result.flash_mint_guard.account.data).unwrap();
+    assert_eq!(u16::from(guard.fee_basis_points), 0);
+}
+
+#[test]
+fn test_set_flash_mint_fee_fails_incorrect_authority() {
+    SetFlashMintFeeBuilder::default()
+        .unwrapped_mint(
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken)
+                .mint_authority(Pubkey::new_unique())
+                .build(),
+        )
+        .check(Check::err(ProgramError::IncorrectAuthority))
+        .execute();
+}
+
+#[test]
+fn test_set_flash_mint_fee_fails_invalid_fee() {
+    SetFlashMintFeeBuilder::default()
+        .fee_basis_points(10_001)
+        .check(Check::err(TokenWrapError::InvalidFeeBasisPoints.into()))
+        .execute();
+}
+
+#[test]
+fn test_set_flash_mint_fee_fails_incorrect_address() {
+    SetFlashMintFeeBuilder::default()
+        .flash_mint_guard(KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        })
+        .check(Check::err(TokenWrapError::FlashMintGuardMismatch.into()))
+        .execute();
+}
@@ -1,40 +1,146 @@
-use {solana_pubkey::Pubkey, spl_token_wrap::instruction::TokenWrapInstruction};
+use {
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_zk_sdk_pod::encryption::{auth_encryption::PodAeCiphertext, elgamal::PodElGamalPubkey},
+    spl_token_metadata_interface::state::Field,
+    spl_token_wrap::{
+        instruction::{AdminAction, TokenWrapInstruction},
+        state::SurplusAction,
+    },
+};
 
 #[test]
 fn test_pack_unpack_create_mint() {
-    let instruction = TokenWrapInstruction::CreateMint { idempotent: true };
+    let instruction = TokenWrapInstruction::CreateMint {
+        idempotent: true,
+        acknowledge_risky_extensions: false,
+    };
     let packed = instruction.pack();
     assert_eq!(packed, vec![0, 1]);
 
     let unpacked = TokenWrapInstruction::unpack(&packed).unwrap();
     assert_eq!(unpacked, instruction);
 
-    let instruction = TokenWrapInstruction::CreateMint { idempotent: false };
+    let instruction = TokenWrapInstruction::CreateMint {
+        idempotent: false,
+        acknowledge_risky_extensions: false,
+    };
     let packed = instruction.pack();
     assert_eq!(packed, vec![0, 0]);
 
     let unpacked = TokenWrapInstruction::unpack(&packed).unwrap();
     assert_eq!(unpacked, instruction);
+
+    let instruction = TokenWrapInstruction::CreateMint {
+        idempotent: false,
+        acknowledge_risky_extensions: true,
+    };
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![0, 0, 1]);
+
+    let unpacked = TokenWrapInstruction::unpack(&packed).unwrap();
+    assert_eq!(unpacked, instruction);
+
+    // An explicit false acknowledgement is accepted
+    let unpacked = TokenWrapInstruction::unpack(&[0, 1, 0]).unwrap();
+    assert_eq!(
+        unpacked,
+        TokenWrapInstruction::CreateMint {
+            idempotent: true,
+            acknowledge_risky_extensions: false,
+        }
+    );
 }
 
 #[test]
 fn test_pack_unpack_wrap() {
-    let instruction = TokenWrapInstruction::Wrap { amount: 42 };
+    let instruction = TokenWrapInstruction::Wrap {
+        amount: 42,
+        record_stats: false,
+    };
     let packed = instruction.pack();
     assert_eq!(packed, vec![1, 42, 0, 0, 0, 0, 0, 0, 0]);
 
     let unpacked = TokenWrapInstruction::unpack(&packed).unwrap();
     assert_eq!(unpacked, instruction);
+
+    let instruction = TokenWrapInstruction::Wrap {
+        amount: 42,
+        record_stats: true,
+    };
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![1, 42, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+    let unpacked = TokenWrapInstruction::unpack(&packed).unwrap();
+    assert_eq!(unpacked, instruction);
+
+    // Explicitly disabled statistics
+    assert_eq!(
+        TokenWrapInstruction::unpack(&[1, 42, 0, 0, 0, 0, 0, 0, 0, 0]).unwrap(),
+        TokenWrapInstruction::Wrap {
+            amount: 42,
+            record_stats: false,
+        }
+    );
+
+    // No further trailing bytes
+    assert_eq!(
+        TokenWrapInstruction::unpack(&[1, 42, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+        Err(ProgramError::InvalidInstructionData)
+    );
 }
 
 #[test]
 fn test_pack_unpack_unwrap() {
-    let instruction = TokenWrapInstruction::Unwrap { amount: 100 };
+    let instruction = TokenWrapInstruction::Unwrap {
+        amount: 100,
+        record_stats: false,
+        pro_rata: false,
+    };
     let packed = instruction.pack();
     assert_eq!(packed, vec![2, 100, 0, 0, 0, 0, 0, 0, 0]);
 
     let unpacked = TokenWrapInstruction::unpack(&packed).unwrap();
     assert_eq!(unpacked, instruction);
+
+    let instruction = TokenWrapInstruction::Unwrap {
+        amount: 100,
+        record_stats: true,
+        pro_rata: false,
+    };
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![2, 100, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+    let unpacked = TokenWrapInstruction::unpack(&packed).unwrap();
+    assert_eq!(unpacked, instruction);
+
+    // Pro-rata redemption without statistics keeps the statistics byte
+    let instruction = TokenWrapInstruction::Unwrap {
+        amount: 100,
+        record_stats: false,
+        pro_rata: true,
+    };
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![2, 100, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+    let unpacked = TokenWrapInstruction::unpack(&packed).unwrap();
+    assert_eq!(unpacked, instruction);
+
+    // Explicitly disabled pro-rata redemption
+    assert_eq!(
+        TokenWrapInstruction::unpack(&[2, 100, 0, 0, 0, 0, 0, 0, 0, 1, 0]).unwrap(),
+        TokenWrapInstruction::Unwrap {
+            amount: 100,
+            record_stats: true,
+            pro_rata: false,
+        }
+    );
+
+    // No further trailing bytes
+    assert_eq!(
+        TokenWrapInstruction::unpack(&[2, 100, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+        Err(ProgramError::InvalidInstructionData)
+    );
 }
 
 #[test]
@@ -42,12 +148,446 @@ fn test_pack_unpack_set_canonical_pointer() {
     let canonical_program_id = Pubkey::new_unique();
     let instruction = TokenWrapInstruction::SetCanonicalPointer {
         program_id: canonical_program_id,
+        enforce_on_wrap: true,
+        change_delay: 3_600,
+        wrapped_token_program: None,
+    };
+    let packed = instruction.pack();
+    assert_eq!(packed.len(), 42);
+    let unpacked = TokenWrapInstruction::unpack(&packed).unwrap();
+    assert_eq!(unpacked, instruction);
+
+    // Legacy encoding without the enforcement flag
+    let mut legacy = vec![6];
+    legacy.extend_from_slice(canonical_program_id.as_ref());
+    assert_eq!(
+        TokenWrapInstruction::unpack(&legacy).unwrap(),
+        TokenWrapInstruction::SetCanonicalPointer {
+            program_id: canonical_program_id,
+            enforce_on_wrap: false,
+            change_delay: 0,
+            wrapped_token_program: None,
+        }
+    );
+
+    // The enforcement flag requires the change delay
+    let mut untimelocked = legacy.clone();
+    untimelocked.push(1);
+    assert!(TokenWrapInstruction::unpack(&untimelocked).is_err());
+
+    // Partial change delay
+    untimelocked.extend_from_slice(&[0; 4]);
+    assert!(TokenWrapInstruction::unpack(&untimelocked).is_err());
+
+    // Invalid enforcement flag
+    let mut invalid_flag = legacy.clone();
+    invalid_flag.push(2);
+    invalid_flag.extend_from_slice(&[0; 8]);
+    assert!(TokenWrapInstruction::unpack(&invalid_flag).is_err());
+
+    // Scoped to a wrapped token program
+    let instruction = TokenWrapInstruction::SetCanonicalPointer {
+        program_id: canonical_program_id,
+        enforce_on_wrap: false,
+        change_delay: 0,
+        wrapped_token_program: Some(spl_token_2022_interface::id()),
+    };
+    let packed = instruction.pack();
+    assert_eq!(packed.len(), 74);
+    assert_eq!(&packed[42..], spl_token_2022_interface::id().as_ref());
+    let unpacked = TokenWrapInstruction::unpack(&packed).unwrap();
+    assert_eq!(unpacked, instruction);
+
+    // The scope requires every other field
+    let mut partial = packed[..34].to_vec();
+    partial.extend_from_slice(spl_token_2022_interface::id().as_ref());
+    assert!(TokenWrapInstruction::unpack(&partial).is_err());
+}
+
+#[test]
+fn test_pack_unpack_close_canonical_pointer() {
+    let instruction = TokenWrapInstruction::CloseCanonicalPointer {
+        wrapped_token_program: None,
     };
     let packed = instruction.pack();
+    assert_eq!(packed, vec![7]);
+
+    let unpacked = TokenWrapInstruction::unpack(&packed).unwrap();
+    assert_eq!(unpacked, instruction);
+
+    let instruction = TokenWrapInstruction::CloseCanonicalPointer {
+        wrapped_token_program: Some(spl_token::id()),
+    };
+    let packed = instruction.pack();
+    assert_eq!(packed.len(), 33);
+
+    let unpacked = TokenWrapInstruction::unpack(&packed).unwrap();
+    assert_eq!(unpacked, instruction);
+}
+
+#[test]
+fn test_pack_unpack_propose_canonical_pointer() {
+    let instruction = TokenWrapInstruction::ProposeCanonicalPointer {
+        program_id: Pubkey::new_unique(),
+        enforce_on_wrap: false,
+        change_delay: 86_400,
+        wrapped_token_program: None,
+    };
+    let packed = instruction.pack();
+    assert_eq!(packed[0], 8);
+    assert_eq!(packed.len(), 42);
+
+    let unpacked = TokenWrapInstruction::unpack(&packed).unwrap();
+    assert_eq!(unpacked, instruction);
+
+    // All fields are required
+    assert!(TokenWrapInstruction::unpack(&packed[..33]).is_err());
+
+    let instruction = TokenWrapInstruction::ProposeCanonicalPointer {
+        program_id: Pubkey::new_unique(),
+        enforce_on_wrap: true,
+        change_delay: 0,
+        wrapped_token_program: Some(spl_token::id()),
+    };
+    let packed = instruction.pack();
+    assert_eq!(packed.len(), 74);
+
     let unpacked = TokenWrapInstruction::unpack(&packed).unwrap();
     assert_eq!(unpacked, instruction);
 }
 
+#[test]
+fn test_pack_unpack_accept_and_cancel_canonical_pointer() {
+    let instruction = TokenWrapInstruction::AcceptCanonicalPointer {
+        wrapped_token_program: None,
+    };
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![9]);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+
+    let instruction = TokenWrapInstruction::CancelCanonicalPointer {
+        wrapped_token_program: None,
+    };
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![10]);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+
+    let instruction = TokenWrapInstruction::AcceptCanonicalPointer {
+        wrapped_token_program: Some(spl_token_2022_interface::id()),
+    };
+    let packed = instruction.pack();
+    assert_eq!(packed.len(), 33);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_migrate_state() {
+    let instruction = TokenWrapInstruction::MigrateState;
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![11]);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_close_wrapped_mint() {
+    let instruction = TokenWrapInstruction::CloseWrappedMint;
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![12]);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_repair_escrow() {
+    let instruction = TokenWrapInstruction::RepairEscrow;
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![13]);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_set_escrow_surplus_policy() {
+    let target = Pubkey::new_unique();
+    let instruction = TokenWrapInstruction::SetEscrowSurplusPolicy {
+        action: SurplusAction::ReturnToClaimant,
+        target,
+    };
+    let packed = instruction.pack();
+    let mut expected = vec![14, 2];
+    expected.extend_from_slice(target.as_ref());
+    assert_eq!(packed, expected);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_reconcile_escrow() {
+    let instruction = TokenWrapInstruction::ReconcileEscrow;
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![15]);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_wrap_sol() {
+    let instruction = TokenWrapInstruction::WrapSol { amount: 42 };
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![16, 42, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_unwrap_sol() {
+    let instruction = TokenWrapInstruction::UnwrapSol {
+        amount: 42,
+        pro_rata: false,
+    };
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![17, 42, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+
+    let instruction = TokenWrapInstruction::UnwrapSol {
+        amount: 42,
+        pro_rata: true,
+    };
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![17, 42, 0, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_wrap_confidential() {
+    let instruction = TokenWrapInstruction::WrapConfidential { amount: 42 };
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![18, 42, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_unwrap_confidential() {
+    let instruction = TokenWrapInstruction::UnwrapConfidential {
+        amount: 42,
+        new_decryptable_available_balance: PodAeCiphertext([7; 36]),
+    };
+    let packed = instruction.pack();
+    let mut expected = vec![19, 42, 0, 0, 0, 0, 0, 0, 0];
+    expected.extend_from_slice(&[7; 36]);
+    assert_eq!(packed, expected);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_update_confidential_config() {
+    let instruction = TokenWrapInstruction::UpdateConfidentialConfig {
+        auto_approve_new_accounts: false,
+        auditor_elgamal_pubkey: Some(PodElGamalPubkey([7; 32])),
+    };
+    let packed = instruction.pack();
+    let mut expected = vec![20, 0];
+    expected.extend_from_slice(&[7; 32]);
+    assert_eq!(packed, expected);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+
+    // An all-zero auditor key means no auditor
+    let instruction = TokenWrapInstruction::UpdateConfidentialConfig {
+        auto_approve_new_accounts: true,
+        auditor_elgamal_pubkey: None,
+    };
+    let packed = instruction.pack();
+    let mut expected = vec![20, 1];
+    expected.extend_from_slice(&[0; 32]);
+    assert_eq!(packed, expected);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_set_wrapped_mint_admin() {
+    let admin = Pubkey::new_unique();
+    let instruction = TokenWrapInstruction::SetWrappedMintAdmin { admin };
+    let packed = instruction.pack();
+    let mut expected = vec![21];
+    expected.extend_from_slice(admin.as_ref());
+    assert_eq!(packed, expected);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_execute_admin_action() {
+    for (action, expected) in [
+        (AdminAction::FreezeAccount, vec![22, 0]),
+        (AdminAction::ThawAccount, vec![22, 1]),
+        (AdminAction::Pause, vec![22, 2]),
+        (AdminAction::Resume, vec![22, 3]),
+        (
+            AdminAction::UpdateMetadataField {
+                field: Field::Name,
+                value: "ab".to_string(),
+            },
+            vec![22, 4, 0, 2, 0, 0, 0, b'a', b'b'],
+        ),
+        (
+            AdminAction::UpdateMetadataField {
+                field: Field::Key("k".to_string()),
+                value: String::new(),
+            },
+            vec![22, 4, 3, 1, 0, 0, 0, b'k', 0, 0, 0, 0],
+        ),
+        (
+            AdminAction::SetTransferFee {
+                transfer_fee_basis_points: 25,
+                maximum_fee: 1_000,
+            },
+            vec![22, 5, 25, 0, 232, 3, 0, 0, 0, 0, 0, 0],
+        ),
+    ] {
+        let instruction = TokenWrapInstruction::ExecuteAdminAction { action };
+        let packed = instruction.pack();
+        assert_eq!(packed, expected);
+        assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+    }
+}
+
+#[test]
+fn test_pack_unpack_set_wrap_limits() {
+    let instruction = TokenWrapInstruction::SetWrapLimits {
+        unwrap_only: true,
+        max_wrapped_supply: 1_000,
+        max_wrapped_per_epoch: 5,
+    };
+    let packed = instruction.pack();
+    let mut expected = vec![23, 1];
+    expected.extend_from_slice(&1_000u64.to_le_bytes());
+    expected.extend_from_slice(&5u64.to_le_bytes());
+    assert_eq!(packed, expected);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_set_allowlist_mode() {
+    for enabled in [false, true] {
+        let instruction = TokenWrapInstruction::SetAllowlistMode { enabled };
+        let packed = instruction.pack();
+        assert_eq!(packed, vec![24, enabled as u8]);
+        assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+    }
+}
+
+#[test]
+fn test_pack_unpack_update_allowlist() {
+    let wallets = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+    let instruction = TokenWrapInstruction::UpdateAllowlist {
+        allowed: true,
+        wallets: wallets.clone(),
+    };
+    let packed = instruction.pack();
+    let mut expected = vec![25, 1];
+    for wallet in &wallets {
+        expected.extend_from_slice(wallet.as_ref());
+    }
+    assert_eq!(packed, expected);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_set_protocol_fee_config() {
+    let fee_destination = Pubkey::new_unique();
+    let instruction = TokenWrapInstruction::SetProtocolFeeConfig {
+        wrap_fee_basis_points: 25,
+        unwrap_fee_basis_points: 10_000,
+        fee_destination,
+    };
+    let packed = instruction.pack();
+    let mut expected = vec![26, 25, 0, 16, 39];
+    expected.extend_from_slice(fee_destination.as_ref());
+    assert_eq!(packed, expected);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_set_protocol_fee_override() {
+    let instruction = TokenWrapInstruction::SetProtocolFeeOverride {
+        enabled: true,
+        wrap_fee_basis_points: 0,
+        unwrap_fee_basis_points: 300,
+    };
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![27, 1, 0, 0, 44, 1]);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_flash_mint() {
+    let instruction = TokenWrapInstruction::FlashMint { amount: 500 };
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![28, 244, 1, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+
+    let instruction = TokenWrapInstruction::FlashRepay;
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![29]);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_set_flash_mint_fee() {
+    let instruction = TokenWrapInstruction::SetFlashMintFee {
+        fee_basis_points: 300,
+    };
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![30, 44, 1]);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_wrap_and_unwrap_with_permit() {
+    let instruction = TokenWrapInstruction::WrapWithPermit {
+        amount: 500,
+        nonce: 3,
+        expiry: -1,
+    };
+    let packed = instruction.pack();
+    let mut expected = vec![31, 244, 1, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0];
+    expected.extend_from_slice(&[0xff; 8]);
+    assert_eq!(packed, expected);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+
+    let instruction = TokenWrapInstruction::UnwrapWithPermit {
+        amount: 500,
+        nonce: 3,
+        expiry: 1_000,
+    };
+    let packed = instruction.pack();
+    let mut expected = vec![32, 244, 1, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0];
+    expected.extend_from_slice(&1_000i64.to_le_bytes());
+    assert_eq!(packed, expected);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_set_crank_wrap_recipient() {
+    let recipient = Pubkey::new_unique();
+    let instruction = TokenWrapInstruction::SetCrankWrapRecipient { recipient };
+    let packed = instruction.pack();
+    let mut expected = vec![33];
+    expected.extend_from_slice(recipient.as_ref());
+    assert_eq!(packed, expected);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_crank_wrap() {
+    let instruction = TokenWrapInstruction::CrankWrap;
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![34]);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
+#[test]
+fn test_pack_unpack_register_wrapped_mint() {
+    let instruction = TokenWrapInstruction::RegisterWrappedMint;
+    let packed = instruction.pack();
+    assert_eq!(packed, vec![35]);
+    assert_eq!(TokenWrapInstruction::unpack(&packed).unwrap(), instruction);
+}
+
 #[test]
 fn test_unpack_invalid_data() {
     assert!(TokenWrapInstruction::unpack(&[]).is_err());
@@ -55,6 +595,63 @@ fn test_unpack_invalid_data() {
     assert!(TokenWrapInstruction::unpack(&[0]).is_err());
     assert!(TokenWrapInstruction::unpack(&[1, 0, 0, 0]).is_err());
     assert!(TokenWrapInstruction::unpack(&[2, 0, 0, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[1, 0, 0, 0, 0, 0, 0, 0, 0, 2]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[2, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[2, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 2]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[2, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 0]).is_err());
     assert!(TokenWrapInstruction::unpack(&[0]).is_err());
-    assert!(TokenWrapInstruction::unpack(&[0, 1, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[0, 1, 2]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[0, 1, 1, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[7, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[9, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[10, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[11, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[14, 1]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[&[14, 3][..], &[0; 32]].concat()).is_err());
+    assert!(TokenWrapInstruction::unpack(&[15, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[16, 0, 0, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[16, 0, 0, 0, 0, 0, 0, 0, 0, 1]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[17]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[17, 0, 0, 0, 0, 0, 0, 0, 0, 2]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[17, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[18, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[19, 0, 0, 0, 0, 0, 0, 0, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[&[19][..], &[0; 45]].concat()).is_err());
+    assert!(TokenWrapInstruction::unpack(&[20, 1]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[&[20, 2][..], &[0; 32]].concat()).is_err());
+    assert!(TokenWrapInstruction::unpack(&[&[20, 1][..], &[0; 33]].concat()).is_err());
+    assert!(TokenWrapInstruction::unpack(&[21, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[22]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[22, 0, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[22, 4, 4, 0, 0, 0, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[22, 4, 0, 2, 0, 0, 0, b'a']).is_err());
+    assert!(TokenWrapInstruction::unpack(&[22, 4, 0, 1, 0, 0, 0, 0xff]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[22, 4, 0, 0, 0, 0, 0, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[22, 5, 0, 0, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[22, 6]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[23, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[&[23, 2][..], &[0; 16]].concat()).is_err());
+    assert!(TokenWrapInstruction::unpack(&[&[23, 0][..], &[0; 17]].concat()).is_err());
+    assert!(TokenWrapInstruction::unpack(&[24]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[24, 2]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[24, 1, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[25, 1]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[&[25, 2][..], &[0; 32]].concat()).is_err());
+    assert!(TokenWrapInstruction::unpack(&[&[25, 1][..], &[0; 33]].concat()).is_err());
+    assert!(TokenWrapInstruction::unpack(&[26, 0, 0, 0, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[&[26][..], &[0; 37]].concat()).is_err());
+    assert!(TokenWrapInstruction::unpack(&[27, 1, 0, 0, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[27, 2, 0, 0, 0, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[28, 0, 0, 0, 0, 0, 0, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[29, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[30, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[30, 0, 0, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[&[31][..], &[0; 16]].concat()).is_err());
+    assert!(TokenWrapInstruction::unpack(&[&[31][..], &[0; 25]].concat()).is_err());
+    assert!(TokenWrapInstruction::unpack(&[&[32][..], &[0; 23]].concat()).is_err());
+    assert!(TokenWrapInstruction::unpack(&[&[33][..], &[0; 31]].concat()).is_err());
+    assert!(TokenWrapInstruction::unpack(&[34, 0]).is_err());
+    assert!(TokenWrapInstruction::unpack(&[35, 0]).is_err());
 }
@@ -0,0 +1,342 @@
+use {
+    crate::helpers::{
+        common::{versioned_state_data, KeyedAccount},
+        migrate_state_builder::MigrateStateBuilder,
+    },
+    bytemuck::Zeroable,
+    mollusk_svm::result::Check,
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        error::TokenWrapError,
+        get_canonical_pointer_address, get_pending_canonical_pointer_address,
+        get_wrapped_mint_address, get_wrapped_mint_backpointer_address,
+        mint_customizer::interface::CUSTOMIZER_ID_LEN,
+        state::{
+            AccountDiscriminator, AccountHeader, Backpointer, CanonicalDeploymentPointer,
+            PendingCanonicalPointer, VersionedState,
+        },
+    },
+};
+
+pub mod helpers;
+
+fn legacy_account(key: Pubkey, data: Vec<u8>, funded_len: usize) -> KeyedAccount {
+    KeyedAccount {
+        key,
+        account: Account {
+            lamports: Rent::default().minimum_balance(funded_len),
+            owner: spl_token_wrap::id(),
+            data,
+            ..Default::default()
+        },
+    }
+}
+
+fn assert_header<T: VersionedState>(data: &[u8]) {
+    assert_eq!(data.len(), T::LEN);
+    let header = bytemuck::from_bytes::<AccountHeader>(&data[..AccountHeader::LEN]);
+    assert_eq!(header.discriminator, T::DISCRIMINATOR as u8);
+    assert_eq!(header.version, T::VERSION);
+}
+
+struct LegacyBackpointer {
+    backpointer: KeyedAccount,
+    wrapped_mint: Pubkey,
+    unwrapped_mint: KeyedAccount,
+}
+
+fn legacy_backpointer(
+    data: impl FnOnce(&Pubkey) -> Vec<u8>,
+    funded_len: usize,
+) -> LegacyBackpointer {
+    let unwrapped_mint = KeyedAccount {
+        key: Pubkey::new_unique(),
+        account: Account {
+            owner: spl_token::id(),
+            ..Default::default()
+        },
+    };
+    let wrapped_mint =
+        get_wrapped_mint_address(&unwrapped_mint.key, &spl_token_2022_interface::id());
+    LegacyBackpointer {
+        backpointer: legacy_account(
+            get_wrapped_mint_backpointer_address(&wrapped_mint),
+            data(&unwrapped_mint.key),
+            funded_len,
+        ),
+        wrapped_mint,
+        unwrapped_mint,
+    }
+}
+
+fn migrate_backpointer_builder<'a>(legacy: LegacyBackpointer) -> MigrateStateBuilder<'a> {
+    MigrateStateBuilder::new(legacy.backpointer, legacy.wrapped_mint)
+        .mint_owner(spl_token_2022_interface::id())
+        .unwrapped_mint(legacy.unwrapped_mint)
+}
+
+fn assert_backfilled(data: &[u8], legacy_wrapped_mint: &Pubkey, unwrapped_mint: &Pubkey) {
+    assert_header::<Backpointer>(data);
+    let backpointer = Backpointer::unpack(data).unwrap();
+    assert!(backpointer.has_provenance());
+    assert_eq!(backpointer.unwrapped_mint, *unwrapped_mint);
+    assert_eq!(backpointer.unwrapped_token_program, spl_token::id());
+    assert_eq!(
+        backpointer.wrapped_token_program,
+        spl_token_2022_interface::id()
+    );
+
+    let (_, wrapped_mint_bump) = Pubkey::find_program_address(
+        &[
+            b"mint",
+            unwrapped_mint.as_ref(),
+            spl_token_2022_interface::id().as_ref(),
+        ],
+        &spl_token_wrap::id(),
+    );
+    assert_eq!(backpointer.wrapped_mint_bump, wrapped_mint_bump);
+    let (_, wrapped_mint_authority_bump) = Pubkey::find_program_address(
+        &[b"authority", legacy_wrapped_mint.as_ref()],
+        &spl_token_wrap::id(),
+    );
+    assert_eq!(
+        backpointer.wrapped_mint_authority_bump,
+        wrapped_mint_authority_bump
+    );
+    let (_, backpointer_bump) = Pubkey::find_program_address(
+        &[b"backpointer", legacy_wrapped_mint.as_ref()],
+        &spl_token_wrap::id(),
+    );
+    assert_eq!(backpointer.backpointer_bump, backpointer_bump);
+
+    // Unknown for existing wrapped mints
+    assert_eq!(backpointer.customizer_id, [0; CUSTOMIZER_ID_LEN]);
+    assert_eq!(u64::from(backpointer.creation_slot), 0);
+    assert_eq!(backpointer.program_version, [0; 3]);
+}
+
+#[test]
+fn test_migrate_legacy_backpointer() {
+    let legacy = legacy_backpointer(
+        |unwrapped_mint| unwrapped_mint.to_bytes().to_vec(),
+        Backpointer::LEN,
+    );
+    let wrapped_mint = legacy.wrapped_mint;
+    let unwrapped_mint = legacy.unwrapped_mint.key;
+
+    let result = migrate_backpointer_builder(legacy).execute();
+
+    assert_backfilled(&result.account.data, &wrapped_mint, &unwrapped_mint);
+}
+
+#[test]
+fn test_fail_migrate_unreleased_backpointer_layout() {
+    // Header followed by only the unwrapped mint, never deployed
+    let legacy = legacy_backpointer(
+        |unwrapped_mint| {
+            let mut data = vec![
+                AccountDiscriminator::Backpointer as u8,
+                Backpointer::VERSION,
+            ];
+            data.extend_from_slice(unwrapped_mint.as_ref());
+            data
+        },
+        Backpointer::LEN,
+    );
+
+    migrate_backpointer_builder(legacy)
+        .check(Check::err(ProgramError::InvalidAccountData))
+        .execute();
+}
+
+#[test]
+fn test_fail_migrate_backpointer_without_unwrapped_mint() {
+    let legacy = legacy_backpointer(
+        |unwrapped_mint| unwrapped_mint.to_bytes().to_vec(),
+        Backpointer::LEN,
+    );
+
+    MigrateStateBuilder::new(legacy.backpointer, legacy.wrapped_mint)
+        .mint_owner(spl_token_2022_interface::id())
+        .check(Check::err(ProgramError::NotEnoughAccountKeys))
+        .execute();
+}
+
+#[test]
+fn test_fail_migrate_backpointer_unwrapped_mint_mismatch() {
+    let mut legacy = legacy_backpointer(
+        |unwrapped_mint| unwrapped_mint.to_bytes().to_vec(),
+        Backpointer::LEN,
+    );
+    legacy.backpointer.account.data = Pubkey::new_unique().to_bytes().to_vec();
+
+    migrate_backpointer_builder(legacy)
+        .check(Check::err(TokenWrapError::UnwrappedMintMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_fail_migrate_backpointer_wrong_wrapped_token_program() {
+    let legacy = legacy_backpointer(
+        |unwrapped_mint| unwrapped_mint.to_bytes().to_vec(),
+        Backpointer::LEN,
+    );
+
+    // The wrapped mint was derived for Token-2022
+    migrate_backpointer_builder(legacy)
+        .mint_owner(spl_token::id())
+        .check(Check::err(TokenWrapError::WrappedMintMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_fail_migrate_backpointer_invalid_unwrapped_mint_owner() {
+    let mut legacy = legacy_backpointer(
+        |unwrapped_mint| unwrapped_mint.to_bytes().to_vec(),
+        Backpointer::LEN,
+    );
+    legacy.unwrapped_mint.account.owner = Pubkey::new_unique();
+
+    migrate_backpointer_builder(legacy)
+        .check(Check::err(ProgramError::InvalidAccountOwner))
+        .execute();
+}
+
+#[test]
+fn test_migrate_legacy_canonical_pointer() {
+    let unwrapped_mint = Pubkey::new_unique();
+    let canonical_program_id = Pubkey::new_unique();
+    let pointer = legacy_account(
+        get_canonical_pointer_address(&unwrapped_mint),
+        canonical_program_id.to_bytes().to_vec(),
+        CanonicalDeploymentPointer::LEN,
+    );
+
+    let result = MigrateStateBuilder::new(pointer, unwrapped_mint).execute();
+
+    assert_header::<CanonicalDeploymentPointer>(&result.account.data);
+    let pointer = CanonicalDeploymentPointer::unpack(&result.account.data).unwrap();
+    assert_eq!(pointer.program_id, canonical_program_id);
+    assert!(!bool::from(pointer.enforce_on_wrap));
+    assert_eq!(u64::from(pointer.change_delay), 0);
+}
+
+#[test]
+fn test_fail_migrate_unversioned_canonical_pointer() {
+    let unwrapped_mint = Pubkey::new_unique();
+    let state = CanonicalDeploymentPointer {
+        program_id: Pubkey::new_unique(),
+        enforce_on_wrap: true.into(),
+        change_delay: 3_600.into(),
+    };
+    let pointer = legacy_account(
+        get_canonical_pointer_address(&unwrapped_mint),
+        bytemuck::bytes_of(&state).to_vec(),
+        CanonicalDeploymentPointer::LEN,
+    );
+
+    MigrateStateBuilder::new(pointer, unwrapped_mint)
+        .check(Check::err(ProgramError::InvalidAccountData))
+        .execute();
+}
+
+#[test]
+fn test_fail_migrate_pending_canonical_pointer() {
+    let unwrapped_mint = Pubkey::new_unique();
+    let pending = legacy_account(
+        get_pending_canonical_pointer_address(&unwrapped_mint),
+        versioned_state_data(&PendingCanonicalPointer::zeroed()),
+        PendingCanonicalPointer::LEN,
+    );
+
+    // Pending pointers have always had the current layout
+    MigrateStateBuilder::new(pending, unwrapped_mint)
+        .check(Check::err(ProgramError::InvalidArgument))
+        .execute();
+}
+
+#[test]
+fn test_migrate_current_layout_is_noop() {
+    let legacy = legacy_backpointer(
+        |unwrapped_mint| {
+            versioned_state_data(&Backpointer {
+                unwrapped_mint: *unwrapped_mint,
+                unwrapped_token_program: spl_token::id(),
+                wrapped_token_program: spl_token_2022_interface::id(),
+                customizer_id: [7; CUSTOMIZER_ID_LEN],
+                creation_slot: 100.into(),
+                wrapped_mint_bump: 255,
+                wrapped_mint_authority_bump: 254,
+                backpointer_bump: 253,
+                program_version: [1, 0, 0],
+            })
+        },
+        Backpointer::LEN,
+    );
+    let data = legacy.backpointer.account.data.clone();
+
+    let result = migrate_backpointer_builder(legacy).execute();
+    assert_eq!(result.account.data, data);
+}
+
+#[test]
+fn test_fail_migrate_insufficient_funds() {
+    let legacy = legacy_backpointer(
+        |unwrapped_mint| unwrapped_mint.to_bytes().to_vec(),
+        Backpointer::LEGACY_LEN,
+    );
+
+    migrate_backpointer_builder(legacy)
+        .check(Check::err(ProgramError::AccountNotRentExempt))
+        .execute();
+}
+
+#[test]
+fn test_fail_migrate_wrong_owner() {
+    let mut legacy = legacy_backpointer(
+        |unwrapped_mint| unwrapped_mint.to_bytes().to_vec(),
+        Backpointer::LEN,
+    );
+    legacy.backpointer.account.owner = Pubkey::new_unique();
+
+    migrate_backpointer_builder(legacy)
+        .check(Check::err(ProgramError::InvalidAccountOwner))
+        .execute();
+}
+
+#[test]
+fn test_fail_migrate_unrelated_account() {
+    let unwrapped_mint = Pubkey::new_unique();
+    let account = legacy_account(
+        Pubkey::new_unique(),
+        Pubkey::new_unique().to_bytes().to_vec(),
+        CanonicalDeploymentPointer::LEN,
+    );
+
+    MigrateStateBuilder::new(account, unwrapped_mint)
+        .check(Check::err(ProgramError::InvalidArgument))
+        .execute();
+}
+
+#[test]
+fn test_fail_migrate_mismatched_discriminator() {
+    let unwrapped_mint = Pubkey::new_unique();
+    let mut data = versioned_state_data(&CanonicalDeploymentPointer {
+        program_id: Pubkey::new_unique(),
+        enforce_on_wrap: false.into(),
+        change_delay: 0.into(),
+    });
+    data[0] = AccountDiscriminator::Backpointer as u8;
+    let pointer = legacy_account(
+        get_canonical_pointer_address(&unwrapped_mint),
+        data,
+        CanonicalDeploymentPointer::LEN,
+    );
+
+    MigrateStateBuilder::new(pointer, unwrapped_mint)
+        .check(Check::err(ProgramError::InvalidAccountData))
+        .execute();
+}
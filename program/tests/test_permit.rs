@@ -0,0 +1,306 @@
+use {
+    crate::helpers::{
+        common::{versioned_state_data, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+        permit::PermitSigner,
+        token_account_builder::TokenAccountBuilder,
+        unwrap_builder::UnwrapBuilder,
+        wrap_builder::WrapBuilder,
+    },
+    mollusk_svm::result::Check,
+    solana_account::Account,
+    solana_keypair::Keypair,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_2022_interface::{extension::PodStateWithExtensions, pod::PodAccount},
+    spl_token_wrap::{
+        error::TokenWrapError,
+        state::{PermitNonce, VersionedState},
+    },
+    test_case::test_case,
+};
+
+pub mod helpers;
+
+fn permit_nonce_account(nonce: u64) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(PermitNonce::LEN),
+        owner: spl_token_wrap::id(),
+        data: versioned_state_data(&PermitNonce {
+            nonce: nonce.into(),
+        }),
+        ..Default::default()
+    }
+}
+
+fn next_nonce(permit_nonce: &KeyedAccount) -> u64 {
+    PermitNonce::unpack_versioned(&permit_nonce.account.data)
+        .unwrap()
+        .nonce
+        .into()
+}
+
+fn token_amount(account: &KeyedAccount) -> u64 {
+    PodStateWithExtensions::<PodAccount>::unpack(&account.account.data)
+        .unwrap()
+        .base
+        .amount
+        .into()
+}
+
+#[test_case(TokenProgram::SplToken, TokenProgram::SplToken2022)]
+#[test_case(TokenProgram::SplToken2022, TokenProgram::SplToken)]
+fn test_wrap_with_permit(
+    unwrapped_token_program: TokenProgram,
+    wrapped_token_program: TokenProgram,
+) {
+    let wrap_amount = 5_000;
+
+    let result = WrapBuilder::default()
+        .unwrapped_token_program(unwrapped_token_program)
+        .wrapped_token_program(wrapped_token_program)
+        .wrap_amount(wrap_amount)
+        .permit(PermitSigner::default())
+        .execute();
+
+    assert_eq!(token_amount(&result.unwrapped_token), 0);
+    assert_eq!(token_amount(&result.unwrapped_escrow), wrap_amount);
+    assert_eq!(token_amount(&result.recipient_wrapped_token), wrap_amount);
+
+    // The permit used up the approval of the delegate
+    let unwrapped_token =
+        PodStateWithExtensions::<PodAccount>::unpack(&result.unwrapped_token.account.data).unwrap();
+    assert_eq!(u64::from(unwrapped_token.base.delegated_amount), 0);
+
+    let permit_nonce = result.permit_nonce.unwrap();
+    assert_eq!(permit_nonce.account.owner, spl_token_wrap::id());
+    assert_eq!(next_nonce(&permit_nonce), 1);
+}
+
+#[test]
+fn test_wrap_with_permit_consumes_next_nonce() {
+    let result = WrapBuilder::default()
+        .permit(
+            PermitSigner::default()
+                .nonce(3)
+                .permit_nonce(permit_nonce_account(3)),
+        )
+        .execute();
+
+    assert_eq!(next_nonce(&result.permit_nonce.unwrap()), 4);
+}
+
+#[test]
+fn test_wrap_with_permit_fails_replayed_nonce() {
+    WrapBuilder::default()
+        .permit(
+            PermitSigner::default()
+                .nonce(0)
+                .permit_nonce(permit_nonce_account(1)),
+        )
+        .check(Check::err(TokenWrapError::InvalidPermitNonce.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_with_permit_fails_expired() {
+    WrapBuilder::default()
+        .unix_timestamp(1_000)
+        .permit(PermitSigner::default().expiry(999))
+        .check(Check::err(TokenWrapError::PermitExpired.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_with_permit_at_expiry() {
+    WrapBuilder::default()
+        .unix_timestamp(1_000)
+        .permit(PermitSigner::default().expiry(1_000))
+        .execute();
+}
+
+#[test]
+fn test_wrap_with_permit_fails_missing_signature() {
+    WrapBuilder::default()
+        .permit(PermitSigner::default().without_signature())
+        .check(Check::err(TokenWrapError::PermitSignatureMissing.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_with_permit_fails_signed_by_other_key() {
+    WrapBuilder::default()
+        .permit(PermitSigner::default().signer(Keypair::new()))
+        .check(Check::err(TokenWrapError::PermitSignatureMissing.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_with_permit_fails_signed_for_other_amount() {
+    WrapBuilder::default()
+        .wrap_amount(5_000)
+        .unwrapped_token_starting_amount(10_000)
+        .permit(PermitSigner::default().signed_amount(4_000))
+        .check(Check::err(TokenWrapError::PermitSignatureMissing.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_with_permit_fails_recipient_swapped_by_relayer() {
+    WrapBuilder::default()
+        .permit(PermitSigner::default().signed_recipient(Pubkey::new_unique()))
+        .check(Check::err(TokenWrapError::PermitSignatureMissing.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_with_permit_fails_signed_for_other_source() {
+    WrapBuilder::default()
+        .permit(PermitSigner::default().signed_source(Pubkey::new_unique()))
+        .check(Check::err(TokenWrapError::PermitSignatureMissing.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_with_permit_fails_incorrect_delegate() {
+    WrapBuilder::default()
+        .permit(PermitSigner::default().delegate(Pubkey::new_unique()))
+        .check(Check::err(TokenWrapError::PermitDelegateMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_with_permit_fails_incorrect_nonce_address() {
+    WrapBuilder::default()
+        .permit(PermitSigner::default().permit_nonce_address(Pubkey::new_unique()))
+        .check(Check::err(TokenWrapError::PermitNonceMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_with_permit_fails_nonce_not_owned_by_program() {
+    let mut permit_nonce = permit_nonce_account(0);
+    permit_nonce.owner = Pubkey::new_unique();
+
+    WrapBuilder::default()
+        .permit(PermitSigner::default().permit_nonce(permit_nonce))
+        .check(Check::err(ProgramError::InvalidAccountOwner))
+        .execute();
+}
+
+#[test]
+fn test_wrap_with_permit_fails_token_account_of_other_owner() {
+    let unwrapped_mint = KeyedAccount {
+        key: Pubkey::new_unique(),
+        account: MintBuilder::new()
+            .token_program(TokenProgram::SplToken)
+            .mint_authority(Pubkey::new_unique())
+            .build()
+            .account,
+    };
+    let permit = PermitSigner::default();
+    let unwrapped_token_account = TokenAccountBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint(unwrapped_mint.clone())
+        .owner(Pubkey::new_unique())
+        .delegate(permit.delegate)
+        .delegated_amount(500)
+        .amount(500)
+        .build();
+
+    WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .unwrapped_token_account(unwrapped_token_account)
+        .permit(permit)
+        .check(Check::err(TokenWrapError::PermitOwnerMismatch.into()))
+        .execute();
+}
+
+#[test_case(TokenProgram::SplToken2022, TokenProgram::SplToken)]
+#[test_case(TokenProgram::SplToken, TokenProgram::SplToken2022)]
+fn test_unwrap_with_permit(
+    wrapped_token_program: TokenProgram,
+    unwrapped_token_program: TokenProgram,
+) {
+    let unwrap_amount = 5_000;
+    let escrow_starting_amount = 100_000;
+
+    let result = UnwrapBuilder::default()
+        .wrapped_token_program(wrapped_token_program)
+        .unwrapped_token_program(unwrapped_token_program)
+        .escrow_starting_amount(escrow_starting_amount)
+        .unwrap_amount(unwrap_amount)
+        .permit(PermitSigner::default())
+        .execute();
+
+    assert_eq!(token_amount(&result.wrapped_token_account), 0);
+    assert_eq!(
+        token_amount(&result.unwrapped_escrow),
+        escrow_starting_amount - unwrap_amount
+    );
+    assert_eq!(
+        token_amount(&result.recipient_unwrapped_token),
+        unwrap_amount
+    );
+
+    let wrapped_token =
+        PodStateWithExtensions::<PodAccount>::unpack(&result.wrapped_token_account.account.data)
+            .unwrap();
+    assert_eq!(u64::from(wrapped_token.base.delegated_amount), 0);
+
+    assert_eq!(next_nonce(&result.permit_nonce.unwrap()), 1);
+}
+
+#[test]
+fn test_unwrap_with_permit_fails_replayed_nonce() {
+    UnwrapBuilder::default()
+        .permit(
+            PermitSigner::default()
+                .nonce(4)
+                .permit_nonce(permit_nonce_account(5)),
+        )
+        .check(Check::err(TokenWrapError::InvalidPermitNonce.into()))
+        .execute();
+}
+
+#[test]
+fn test_unwrap_with_permit_fails_signed_by_other_key() {
+    UnwrapBuilder::default()
+        .permit(PermitSigner::default().signer(Keypair::new()))
+        .check(Check::err(TokenWrapError::PermitSignatureMissing.into()))
+        .execute();
+}
+
+#[test]
+fn test_unwrap_with_permit_fails_recipient_swapped_by_relayer() {
+    UnwrapBuilder::default()
+        .permit(PermitSigner::default().signed_recipient(Pubkey::new_unique()))
+        .check(Check::err(TokenWrapError::PermitSignatureMissing.into()))
+        .execute();
+}
+
+#[test]
+fn test_unwrap_with_permit_fails_signed_for_other_source() {
+    UnwrapBuilder::default()
+        .permit(PermitSigner::default().signed_source(Pubkey::new_unique()))
+        .check(Check::err(TokenWrapError::PermitSignatureMissing.into()))
+        .execute();
+}
+
+#[test]
+fn test_unwrap_with_permit_fails_expired() {
+    UnwrapBuilder::default()
+        .unix_timestamp(1_000)
+        .permit(PermitSigner::default().expiry(0))
+        .check(Check::err(TokenWrapError::PermitExpired.into()))
+        .execute();
+}
+
+#[test]
+fn test_unwrap_with_permit_fails_incorrect_delegate() {
+    UnwrapBuilder::default()
+        .permit(PermitSigner::default().delegate(Pubkey::new_unique()))
+        .check(Check::err(TokenWrapError::PermitDelegateMismatch.into()))
+        .execute();
+}
@@ -0,0 +1,239 @@
+use {
+    crate::helpers::{
+        common::{versioned_state_data, KeyedAccount},
+        unwrap_builder::{UnwrapBuilder, UnwrapResult},
+        unwrap_sol_builder::UnwrapSolBuilder,
+    },
+    bytemuck::Zeroable,
+    mollusk_svm::result::Check,
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_2022_interface::{
+        extension::PodStateWithExtensions,
+        pod::{PodAccount, PodMint},
+    },
+    spl_token_wrap::{
+        error::TokenWrapError,
+        get_pro_rata_redemption_address,
+        state::{ProRataRedemption, VersionedState},
+    },
+};
+
+pub mod helpers;
+
+fn funded_redemption_account() -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(ProRataRedemption::LEN),
+        ..Default::default()
+    }
+}
+
+fn unpack_redemption(account: &KeyedAccount) -> ProRataRedemption {
+    ProRataRedemption::unpack_versioned(&account.account.data).unwrap()
+}
+
+fn token_amount(account: &KeyedAccount) -> u64 {
+    PodStateWithExtensions::<PodAccount>::unpack(&account.account.data)
+        .unwrap()
+        .base
+        .amount
+        .into()
+}
+
+fn mint_supply(account: &KeyedAccount) -> u64 {
+    PodStateWithExtensions::<PodMint>::unpack(&account.account.data)
+        .unwrap()
+        .base
+        .supply
+        .into()
+}
+
+fn assert_balances(result: &UnwrapResult, escrow: u64, supply: u64, recipient: u64) {
+    assert_eq!(token_amount(&result.unwrapped_escrow), escrow);
+    assert_eq!(mint_supply(&result.wrapped_mint), supply);
+    assert_eq!(token_amount(&result.recipient_unwrapped_token), recipient);
+}
+
+#[test]
+fn test_unwrap_pro_rata_when_under_collateralized() {
+    let result = UnwrapBuilder::default()
+        .escrow_starting_amount(60_000)
+        .wrapped_supply(100_000)
+        .unwrap_amount(10_000)
+        .slot(42)
+        .pro_rata_redemption(funded_redemption_account())
+        .execute();
+
+    // 10_000 * 60_000 / 100_000
+    assert_balances(&result, 54_000, 90_000, 6_000);
+
+    let pro_rata_redemption = result.pro_rata_redemption.unwrap();
+    assert_eq!(
+        pro_rata_redemption.key,
+        get_pro_rata_redemption_address(&result.wrapped_mint.key)
+    );
+    assert_eq!(pro_rata_redemption.account.owner, spl_token_wrap::id());
+
+    let redemption = unpack_redemption(&pro_rata_redemption);
+    assert_eq!(u128::from(redemption.total_burned), 10_000);
+    assert_eq!(u128::from(redemption.total_paid), 6_000);
+    assert_eq!(u64::from(redemption.redemption_count), 1);
+    assert_eq!(u64::from(redemption.first_redemption_slot), 42);
+    assert_eq!(u64::from(redemption.last_redemption_slot), 42);
+    assert_eq!(u64::from(redemption.last_escrow_balance), 60_000);
+    assert_eq!(u64::from(redemption.last_wrapped_supply), 100_000);
+}
+
+#[test]
+fn test_unwrap_pro_rata_rounds_down() {
+    let result = UnwrapBuilder::default()
+        .escrow_starting_amount(2)
+        .wrapped_supply(3)
+        .unwrap_amount(2)
+        .pro_rata_redemption(funded_redemption_account())
+        .execute();
+
+    // 2 * 2 / 3
+    assert_balances(&result, 1, 1, 1);
+}
+
+#[test]
+fn test_unwrap_pro_rata_accumulates() {
+    let existing = ProRataRedemption {
+        total_burned: 1_000.into(),
+        total_paid: 500.into(),
+        redemption_count: 2.into(),
+        first_redemption_slot: 5.into(),
+        last_redemption_slot: 6.into(),
+        last_escrow_balance: 51_000.into(),
+        last_wrapped_supply: 101_000.into(),
+    };
+
+    let result = UnwrapBuilder::default()
+        .escrow_starting_amount(50_000)
+        .wrapped_supply(100_000)
+        .unwrap_amount(4_000)
+        .slot(9)
+        .pro_rata_redemption(Account {
+            lamports: Rent::default().minimum_balance(ProRataRedemption::LEN),
+            owner: spl_token_wrap::id(),
+            data: versioned_state_data(&existing),
+            ..Default::default()
+        })
+        .execute();
+
+    let redemption = unpack_redemption(&result.pro_rata_redemption.unwrap());
+    assert_eq!(u128::from(redemption.total_burned), 5_000);
+    assert_eq!(u128::from(redemption.total_paid), 2_500);
+    assert_eq!(u64::from(redemption.redemption_count), 3);
+    assert_eq!(u64::from(redemption.first_redemption_slot), 5);
+    assert_eq!(u64::from(redemption.last_redemption_slot), 9);
+    assert_eq!(u64::from(redemption.last_escrow_balance), 50_000);
+    assert_eq!(u64::from(redemption.last_wrapped_supply), 100_000);
+}
+
+#[test]
+fn test_unwrap_requires_pro_rata_redemption_account() {
+    UnwrapBuilder::default()
+        .escrow_starting_amount(60_000)
+        .wrapped_supply(100_000)
+        .unwrap_amount(10_000)
+        .check(Check::err(TokenWrapError::ProRataRedemptionRequired.into()))
+        .execute();
+}
+
+#[test]
+fn test_unwrap_sol_pro_rata_when_under_collateralized() {
+    let result = UnwrapSolBuilder::default()
+        .escrow_starting_amount(5_000_000)
+        .wrapped_supply(10_000_000)
+        .unwrap_amount(2_000_000)
+        .pro_rata_redemption(funded_redemption_account())
+        .execute();
+
+    // 2_000_000 * 5_000_000 / 10_000_000
+    assert_eq!(result.recipient.account.lamports, 1_000_000);
+    assert_eq!(mint_supply(&result.wrapped_mint), 8_000_000);
+    assert_eq!(token_amount(&result.unwrapped_escrow), 4_000_000);
+
+    let redemption = unpack_redemption(&result.pro_rata_redemption.unwrap());
+    assert_eq!(u128::from(redemption.total_burned), 2_000_000);
+    assert_eq!(u128::from(redemption.total_paid), 1_000_000);
+    assert_eq!(u64::from(redemption.last_escrow_balance), 5_000_000);
+    assert_eq!(u64::from(redemption.last_wrapped_supply), 10_000_000);
+}
+
+#[test]
+fn test_unwrap_sol_requires_pro_rata_redemption_account() {
+    UnwrapSolBuilder::default()
+        .escrow_starting_amount(5_000_000)
+        .wrapped_supply(10_000_000)
+        .unwrap_amount(2_000_000)
+        .check(Check::err(TokenWrapError::ProRataRedemptionRequired.into()))
+        .execute();
+}
+
+#[test]
+fn test_unwrap_fully_collateralized_ignores_pro_rata_redemption_account() {
+    let result = UnwrapBuilder::default()
+        .escrow_starting_amount(100_000)
+        .wrapped_supply(100_000)
+        .unwrap_amount(10_000)
+        .pro_rata_redemption(funded_redemption_account())
+        .execute();
+
+    assert_balances(&result, 90_000, 90_000, 10_000);
+    assert!(result.pro_rata_redemption.unwrap().account.data.is_empty());
+}
+
+#[test]
+fn test_unwrap_over_collateralized_pays_in_full() {
+    let result = UnwrapBuilder::default()
+        .escrow_starting_amount(150_000)
+        .wrapped_supply(100_000)
+        .unwrap_amount(10_000)
+        .execute();
+
+    assert_balances(&result, 140_000, 90_000, 10_000);
+}
+
+#[test]
+fn test_unwrap_pro_rata_amount_zero() {
+    UnwrapBuilder::default()
+        .escrow_starting_amount(1)
+        .wrapped_supply(100_000)
+        .unwrap_amount(10)
+        .pro_rata_redemption(funded_redemption_account())
+        .check(Check::err(TokenWrapError::RedemptionAmountZero.into()))
+        .execute();
+}
+
+#[test]
+fn test_unwrap_pro_rata_redemption_mismatch() {
+    UnwrapBuilder::default()
+        .escrow_starting_amount(60_000)
+        .wrapped_supply(100_000)
+        .unwrap_amount(10_000)
+        .pro_rata_redemption(funded_redemption_account())
+        .pro_rata_redemption_address(get_pro_rata_redemption_address(&Pubkey::new_unique()))
+        .check(Check::err(TokenWrapError::ProRataRedemptionMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_unwrap_pro_rata_redemption_wrong_owner() {
+    UnwrapBuilder::default()
+        .escrow_starting_amount(60_000)
+        .wrapped_supply(100_000)
+        .unwrap_amount(10_000)
+        .pro_rata_redemption(Account {
+            lamports: Rent::default().minimum_balance(ProRataRedemption::LEN),
+            owner: Pubkey::new_unique(),
+            data: versioned_state_data(&ProRataRedemption::zeroed()),
+            ..Default::default()
+        })
+        .check(Check::err(ProgramError::InvalidAccountOwner))
+        .execute();
+}
@@ -0,0 +1,415 @@
+use {
+    crate::helpers::{
+        common::{program_data_account, versioned_state_data, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+        set_protocol_fee_config_builder::SetProtocolFeeConfigBuilder,
+        set_protocol_fee_override_builder::SetProtocolFeeOverrideBuilder,
+        token_account_builder::TokenAccountBuilder,
+        unwrap_builder::UnwrapBuilder,
+        unwrap_sol_builder::UnwrapSolBuilder,
+        wrap_builder::WrapBuilder,
+        wrap_sol_builder::{native_mint, native_token_account, WrapSolBuilder},
+    },
+    mollusk_svm::result::Check,
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_2022_interface::{extension::PodStateWithExtensions, pod::PodAccount},
+    spl_token_wrap::{
+        error::TokenWrapError,
+        state::{ProtocolFeeConfig, ProtocolFeeOverride, ProtocolFeeSplit, VersionedState},
+    },
+};
+
+pub mod helpers;
+
+fn protocol_fee_config_account(
+    fee_destination: Pubkey,
+    wrap_fee_basis_points: u16,
+    unwrap_fee_basis_points: u16,
+) -> Account {
+    state_account(&ProtocolFeeConfig {
+        fee_destination,
+        wrap_fee_basis_points: wrap_fee_basis_points.into(),
+        unwrap_fee_basis_points: unwrap_fee_basis_points.into(),
+    })
+}
+
+fn protocol_fee_override_account(
+    enabled: bool,
+    wrap_fee_basis_points: u16,
+    unwrap_fee_basis_points: u16,
+) -> Account {
+    state_account(&ProtocolFeeOverride {
+        enabled: enabled.into(),
+        wrap_fee_basis_points: wrap_fee_basis_points.into(),
+        unwrap_fee_basis_points: unwrap_fee_basis_points.into(),
+    })
+}
+
+fn state_account<T: VersionedState>(state: &T) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(T::LEN),
+        owner: spl_token_wrap::id(),
+        data: versioned_state_data(state),
+        ..Default::default()
+    }
+}
+
+fn token_amount(account: &KeyedAccount) -> u64 {
+    PodStateWithExtensions::<PodAccount>::unpack(&account.account.data)
+        .unwrap()
+        .base
+        .amount
+        .into()
+}
+
+fn unwrapped_mint_and_vault(fee_destination: Pubkey) -> (KeyedAccount, KeyedAccount) {
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(Pubkey::new_unique())
+        .build();
+    let fee_vault = TokenAccountBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint(unwrapped_mint.clone())
+        .owner(fee_destination)
+        .build();
+    (unwrapped_mint, fee_vault)
+}
+
+/// Wrapped SOL account of the fee destination
+fn native_fee_vault(fee_destination: Pubkey) -> KeyedAccount {
+    native_token_account(
+        TokenProgram::SplToken,
+        native_mint(TokenProgram::SplToken),
+        fee_destination,
+        Pubkey::new_unique(),
+        0,
+    )
+}
+
+#[test]
+fn test_set_protocol_fee_config() {
+    let upgrade_authority = Pubkey::new_unique();
+    let fee_destination = Pubkey::new_unique();
+
+    let result = SetProtocolFeeConfigBuilder::default()
+        .upgrade_authority(upgrade_authority)
+        .fees(25, 50)
+        .fee_destination(fee_destination)
+        .execute();
+
+    let config =
+        ProtocolFeeConfig::unpack_versioned(&result.protocol_fee_config.account.data).unwrap();
+    assert_eq!(
+        result.protocol_fee_config.account.owner,
+        spl_token_wrap::id()
+    );
+    assert_eq!(config.fee_destination, fee_destination);
+    assert_eq!(u16::from(config.wrap_fee_basis_points), 25);
+    assert_eq!(u16::from(config.unwrap_fee_basis_points), 50);
+
+    // Updating keeps the account around
+    let result = SetProtocolFeeConfigBuilder::default()
+        .upgrade_authority(upgrade_authority)
+        .protocol_fee_config(result.protocol_fee_config)
+        .fees(0, 0)
+        .fee_destination(fee_destination)
+        .execute();
+
+    let config =
+        ProtocolFeeConfig::unpack_versioned(&result.protocol_fee_config.account.data).unwrap();
+    assert_eq!(u16::from(config.wrap_fee_basis_points), 0);
+    assert_eq!(u16::from(config.unwrap_fee_basis_points), 0);
+}
+
+#[test]
+fn test_set_protocol_fee_config_fails_incorrect_authority() {
+    SetProtocolFeeConfigBuilder::default()
+        .program_data(program_data_account(Some(Pubkey::new_unique())))
+        .check(Check::err(ProgramError::IncorrectAuthority))
+        .execute();
+}
+
+#[test]
+fn test_set_protocol_fee_config_fails_immutable_program() {
+    SetProtocolFeeConfigBuilder::default()
+        .program_data(program_data_account(None))
+        .check(Check::err(ProgramError::IncorrectAuthority))
+        .execute();
+}
+
+#[test]
+fn test_set_protocol_fee_config_fails_authority_not_signer() {
+    SetProtocolFeeConfigBuilder::default()
+        .authority_not_signer()
+        .check(Check::err(ProgramError::MissingRequiredSignature))
+        .execute();
+}
+
+#[test]
+fn test_set_protocol_fee_config_fails_incorrect_program_data() {
+    let upgrade_authority = Pubkey::new_unique();
+    let mut program_data = program_data_account(Some(upgrade_authority));
+    program_data.key = Pubkey::new_unique();
+
+    SetProtocolFeeConfigBuilder::default()
+        .upgrade_authority(upgrade_authority)
+        .program_data(program_data)
+        .check(Check::err(TokenWrapError::ProgramDataMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_set_protocol_fee_config_fails_invalid_basis_points() {
+    SetProtocolFeeConfigBuilder::default()
+        .fees(10_001, 0)
+        .check(Check::err(TokenWrapError::InvalidFeeBasisPoints.into()))
+        .execute();
+}
+
+#[test]
+fn test_set_protocol_fee_override() {
+    let upgrade_authority = Pubkey::new_unique();
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(Pubkey::new_unique())
+        .build();
+
+    let result = SetProtocolFeeOverrideBuilder::default()
+        .upgrade_authority(upgrade_authority)
+        .unwrapped_mint(unwrapped_mint.clone())
+        .fees(0, 10)
+        .execute();
+
+    let fee_override =
+        ProtocolFeeOverride::unpack_versioned(&result.protocol_fee_override.account.data).unwrap();
+    assert!(fee_override.is_enabled());
+    assert_eq!(u16::from(fee_override.unwrap_fee_basis_points), 10);
+
+    let result = SetProtocolFeeOverrideBuilder::default()
+        .upgrade_authority(upgrade_authority)
+        .unwrapped_mint(unwrapped_mint)
+        .protocol_fee_override(result.protocol_fee_override)
+        .disabled()
+        .execute();
+
+    assert!(
+        !ProtocolFeeOverride::unpack_versioned(&result.protocol_fee_override.account.data)
+            .unwrap()
+            .is_enabled()
+    );
+}
+
+#[test]
+fn test_set_protocol_fee_override_fails_incorrect_authority() {
+    SetProtocolFeeOverrideBuilder::default()
+        .program_data(program_data_account(Some(Pubkey::new_unique())))
+        .check(Check::err(ProgramError::IncorrectAuthority))
+        .execute();
+}
+
+#[test]
+fn test_set_protocol_fee_override_fails_incorrect_address() {
+    SetProtocolFeeOverrideBuilder::default()
+        .protocol_fee_override(KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        })
+        .check(Check::err(
+            TokenWrapError::ProtocolFeeOverrideMismatch.into(),
+        ))
+        .execute();
+}
+
+#[test]
+fn test_wrap_charges_protocol_fee() {
+    let fee_destination = Pubkey::new_unique();
+    let (unwrapped_mint, fee_vault) = unwrapped_mint_and_vault(fee_destination);
+    let split = ProtocolFeeSplit::new(10_000, 100).unwrap();
+
+    let result = WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .wrap_amount(10_000)
+        .protocol_fee_config(protocol_fee_config_account(fee_destination, 100, 0))
+        .protocol_fee_vault(fee_vault)
+        .check(Check::success())
+        .check(Check::return_data(bytemuck::bytes_of(&split)))
+        .execute();
+
+    assert_eq!(token_amount(&result.protocol_fee_vault), 100);
+    assert_eq!(token_amount(&result.unwrapped_escrow), 9_900);
+    assert_eq!(token_amount(&result.recipient_wrapped_token), 9_900);
+}
+
+#[test]
+fn test_wrap_protocol_fee_override() {
+    let fee_destination = Pubkey::new_unique();
+    let (unwrapped_mint, fee_vault) = unwrapped_mint_and_vault(fee_destination);
+
+    let result = WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .wrap_amount(10_000)
+        .protocol_fee_config(protocol_fee_config_account(fee_destination, 100, 0))
+        .protocol_fee_override(protocol_fee_override_account(true, 5, 0))
+        .protocol_fee_vault(fee_vault)
+        .execute();
+
+    assert_eq!(token_amount(&result.protocol_fee_vault), 5);
+    assert_eq!(token_amount(&result.recipient_wrapped_token), 9_995);
+}
+
+#[test]
+fn test_wrap_ignores_disabled_protocol_fee_override() {
+    let fee_destination = Pubkey::new_unique();
+    let (unwrapped_mint, fee_vault) = unwrapped_mint_and_vault(fee_destination);
+
+    let result = WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .wrap_amount(10_000)
+        .protocol_fee_config(protocol_fee_config_account(fee_destination, 100, 0))
+        .protocol_fee_override(protocol_fee_override_account(false, 0, 0))
+        .protocol_fee_vault(fee_vault)
+        .execute();
+
+    assert_eq!(token_amount(&result.protocol_fee_vault), 100);
+}
+
+#[test]
+fn test_wrap_fails_incorrect_fee_vault_owner() {
+    let (unwrapped_mint, fee_vault) = unwrapped_mint_and_vault(Pubkey::new_unique());
+
+    WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .protocol_fee_config(protocol_fee_config_account(Pubkey::new_unique(), 100, 0))
+        .protocol_fee_vault(fee_vault)
+        .check(Check::err(TokenWrapError::FeeVaultMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_fails_protocol_fee_config_not_owned_by_program() {
+    let mut config = protocol_fee_config_account(Pubkey::new_unique(), 100, 0);
+    config.owner = Pubkey::new_unique();
+
+    WrapBuilder::default()
+        .protocol_fee_config(config)
+        .check(Check::err(ProgramError::InvalidAccountOwner))
+        .execute();
+}
+
+#[test]
+fn test_wrap_without_fee_ignores_fee_vault() {
+    let split = ProtocolFeeSplit::new(500, 0).unwrap();
+
+    WrapBuilder::default()
+        .wrap_amount(500)
+        .protocol_fee_config(protocol_fee_config_account(Pubkey::new_unique(), 0, 100))
+        .check(Check::success())
+        .check(Check::return_data(bytemuck::bytes_of(&split)))
+        .execute();
+}
+
+#[test]
+fn test_unwrap_charges_protocol_fee() {
+    let fee_destination = Pubkey::new_unique();
+    let (unwrapped_mint, fee_vault) = unwrapped_mint_and_vault(fee_destination);
+    let split = ProtocolFeeSplit::new(2_000, 50).unwrap();
+
+    let result = UnwrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .escrow_starting_amount(100_000)
+        .unwrap_amount(2_000)
+        .protocol_fee_config(protocol_fee_config_account(fee_destination, 0, 50))
+        .protocol_fee_vault(fee_vault)
+        .check(Check::success())
+        .check(Check::return_data(bytemuck::bytes_of(&split)))
+        .execute();
+
+    assert_eq!(token_amount(&result.protocol_fee_vault), 10);
+    assert_eq!(token_amount(&result.recipient_unwrapped_token), 1_990);
+    assert_eq!(token_amount(&result.unwrapped_escrow), 98_000);
+}
+
+#[test]
+fn test_unwrap_fails_incorrect_fee_vault_mint() {
+    let fee_destination = Pubkey::new_unique();
+    let (_, fee_vault) = unwrapped_mint_and_vault(fee_destination);
+
+    UnwrapBuilder::default()
+        .protocol_fee_config(protocol_fee_config_account(fee_destination, 0, 50))
+        .protocol_fee_vault(fee_vault)
+        .check(Check::err(TokenWrapError::FeeVaultMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_sol_charges_protocol_fee() {
+    let fee_destination = Pubkey::new_unique();
+    let split = ProtocolFeeSplit::new(1_000_000, 100).unwrap();
+
+    let result = WrapSolBuilder::default()
+        .wrap_amount(1_000_000)
+        .protocol_fee_config(protocol_fee_config_account(fee_destination, 100, 0))
+        .protocol_fee_vault(native_fee_vault(fee_destination))
+        .check(Check::success())
+        .check(Check::return_data(bytemuck::bytes_of(&split)))
+        .execute();
+
+    assert_eq!(token_amount(&result.protocol_fee_vault), 10_000);
+    assert_eq!(token_amount(&result.unwrapped_escrow), 990_000);
+    assert_eq!(token_amount(&result.recipient_wrapped_token), 990_000);
+}
+
+#[test]
+fn test_wrap_sol_fails_incorrect_fee_vault_owner() {
+    WrapSolBuilder::default()
+        .protocol_fee_config(protocol_fee_config_account(Pubkey::new_unique(), 100, 0))
+        .protocol_fee_vault(native_fee_vault(Pubkey::new_unique()))
+        .check(Check::err(TokenWrapError::FeeVaultMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_unwrap_sol_charges_protocol_fee() {
+    let fee_destination = Pubkey::new_unique();
+    let split = ProtocolFeeSplit::new(1_000_000, 50).unwrap();
+
+    let result = UnwrapSolBuilder::default()
+        .escrow_starting_amount(5_000_000)
+        .unwrap_amount(1_000_000)
+        .protocol_fee_config(protocol_fee_config_account(fee_destination, 0, 50))
+        .protocol_fee_vault(native_fee_vault(fee_destination))
+        .check(Check::success())
+        .check(Check::return_data(bytemuck::bytes_of(&split)))
+        .execute();
+
+    assert_eq!(token_amount(&result.protocol_fee_vault), 5_000);
+    assert_eq!(result.recipient.account.lamports, 995_000);
+    assert_eq!(token_amount(&result.unwrapped_escrow), 4_000_000);
+}
+
+#[test]
+fn test_unwrap_sol_protocol_fee_override() {
+    let fee_destination = Pubkey::new_unique();
+
+    let result = UnwrapSolBuilder::default()
+        .unwrap_amount(1_000_000)
+        .protocol_fee_config(protocol_fee_config_account(fee_destination, 0, 50))
+        .protocol_fee_override(protocol_fee_override_account(true, 0, 10))
+        .protocol_fee_vault(native_fee_vault(fee_destination))
+        .execute();
+
+    assert_eq!(token_amount(&result.protocol_fee_vault), 1_000);
+    assert_eq!(result.recipient.account.lamports, 999_000);
+}
+
+#[test]
+fn test_unwrap_sol_fails_incorrect_fee_vault_owner() {
+    UnwrapSolBuilder::default()
+        .protocol_fee_config(protocol_fee_config_account(Pubkey::new_unique(), 0, 50))
+        .protocol_fee_vault(native_fee_vault(Pubkey::new_unique()))
+        .check(Check::err(TokenWrapError::FeeVaultMismatch.into()))
+        .execute();
+}
@@ -0,0 +1,196 @@
+use {
+    crate::helpers::{
+        common::{KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+        reconcile_escrow_builder::ReconcileEscrowBuilder,
+        set_escrow_surplus_policy_builder::SetEscrowSurplusPolicyBuilder,
+    },
+    mollusk_svm::result::Check,
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_2022_interface::{
+        extension::PodStateWithExtensions,
+        pod::{PodAccount, PodMint},
+    },
+    spl_token_wrap::{
+        error::TokenWrapError,
+        get_escrow_surplus_policy_address,
+        state::{EscrowSurplusPolicy, SurplusAction, VersionedState},
+    },
+};
+
+pub mod helpers;
+
+fn token_amount(account: &KeyedAccount) -> u64 {
+    PodStateWithExtensions::<PodAccount>::unpack(&account.account.data)
+        .unwrap()
+        .base
+        .amount
+        .into()
+}
+
+fn mint_supply(account: &KeyedAccount) -> u64 {
+    PodStateWithExtensions::<PodMint>::unpack(&account.account.data)
+        .unwrap()
+        .base
+        .supply
+        .into()
+}
+
+#[test]
+fn test_set_escrow_surplus_policy() {
+    let target = Pubkey::new_unique();
+    let result = SetEscrowSurplusPolicyBuilder::default()
+        .action(SurplusAction::ReturnToClaimant)
+        .target(target)
+        .execute();
+
+    assert_eq!(result.policy.account.owner, spl_token_wrap::id());
+    let policy = EscrowSurplusPolicy::unpack_versioned(&result.policy.account.data).unwrap();
+    assert_eq!(policy.action().unwrap(), SurplusAction::ReturnToClaimant);
+    assert_eq!(policy.target, target);
+}
+
+#[test]
+fn test_set_escrow_surplus_policy_fails_incorrect_authority() {
+    let mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(Pubkey::new_unique())
+        .build();
+
+    SetEscrowSurplusPolicyBuilder::default()
+        .unwrapped_mint(mint)
+        .check(Check::err(ProgramError::IncorrectAuthority))
+        .execute();
+}
+
+#[test]
+fn test_set_escrow_surplus_policy_fails_missing_signature() {
+    SetEscrowSurplusPolicyBuilder::default()
+        .authority_not_signer()
+        .check(Check::err(ProgramError::MissingRequiredSignature))
+        .execute();
+}
+
+#[test]
+fn test_set_escrow_surplus_policy_fails_wrapped_mint_mismatch() {
+    let wrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .build();
+
+    SetEscrowSurplusPolicyBuilder::default()
+        .wrapped_mint(wrapped_mint)
+        .check(Check::err(TokenWrapError::WrappedMintMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_set_escrow_surplus_policy_fails_policy_mismatch() {
+    SetEscrowSurplusPolicyBuilder::default()
+        .policy(KeyedAccount {
+            key: get_escrow_surplus_policy_address(&Pubkey::new_unique()),
+            account: Account {
+                lamports: Rent::default().minimum_balance(EscrowSurplusPolicy::LEN),
+                ..Default::default()
+            },
+        })
+        .check(Check::err(
+            TokenWrapError::EscrowSurplusPolicyMismatch.into(),
+        ))
+        .execute();
+}
+
+#[test]
+fn test_reconcile_escrow_mints_surplus_to_recipient() {
+    let result = ReconcileEscrowBuilder::default()
+        .escrow_amount(150_000)
+        .wrapped_supply(100_000)
+        .execute();
+
+    assert_eq!(token_amount(&result.destination), 50_000);
+    assert_eq!(mint_supply(&result.wrapped_mint), 150_000);
+    assert_eq!(token_amount(&result.escrow), 150_000);
+}
+
+#[test]
+fn test_reconcile_escrow_returns_surplus_to_claimant() {
+    let result = ReconcileEscrowBuilder::default()
+        .unwrapped_token_program(TokenProgram::SplToken2022)
+        .wrapped_token_program(TokenProgram::SplToken)
+        .escrow_amount(150_000)
+        .wrapped_supply(100_000)
+        .return_to_claimant()
+        .execute();
+
+    assert_eq!(token_amount(&result.destination), 50_000);
+    assert_eq!(mint_supply(&result.wrapped_mint), 100_000);
+    assert_eq!(token_amount(&result.escrow), 100_000);
+}
+
+#[test]
+fn test_reconcile_escrow_fails_without_surplus() {
+    ReconcileEscrowBuilder::default()
+        .escrow_amount(100_000)
+        .wrapped_supply(100_000)
+        .check(Check::err(TokenWrapError::NoEscrowSurplus.into()))
+        .execute();
+}
+
+#[test]
+fn test_reconcile_escrow_fails_under_collateralized() {
+    ReconcileEscrowBuilder::default()
+        .escrow_amount(50_000)
+        .wrapped_supply(100_000)
+        .return_to_claimant()
+        .check(Check::err(TokenWrapError::NoEscrowSurplus.into()))
+        .execute();
+}
+
+#[test]
+fn test_reconcile_escrow_fails_recipient_mismatch() {
+    ReconcileEscrowBuilder::default()
+        .policy_target(Pubkey::new_unique())
+        .check(Check::err(TokenWrapError::SurplusRecipientMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_reconcile_escrow_fails_claimant_mismatch() {
+    ReconcileEscrowBuilder::default()
+        .return_to_claimant()
+        .policy_target(Pubkey::new_unique())
+        .check(Check::err(TokenWrapError::SurplusRecipientMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_reconcile_escrow_fails_claimant_not_signer() {
+    ReconcileEscrowBuilder::default()
+        .return_to_claimant()
+        .claimant_not_signer()
+        .check(Check::err(ProgramError::MissingRequiredSignature))
+        .execute();
+}
+
+#[test]
+fn test_reconcile_escrow_fails_policy_mismatch() {
+    ReconcileEscrowBuilder::default()
+        .policy(KeyedAccount {
+            key: get_escrow_surplus_policy_address(&Pubkey::new_unique()),
+            account: Account::default(),
+        })
+        .check(Check::err(
+            TokenWrapError::EscrowSurplusPolicyMismatch.into(),
+        ))
+        .execute();
+}
+
+#[test]
+fn test_reconcile_escrow_fails_without_policy() {
+    ReconcileEscrowBuilder::default()
+        .uninitialized_policy()
+        .check(Check::err(ProgramError::InvalidAccountOwner))
+        .execute();
+}
@@ -0,0 +1,193 @@
+use {
+    crate::helpers::{
+        common::{KeyedAccount, TokenProgram},
+        extensions::MintExtension::TransferHook,
+        mint_builder::MintBuilder,
+        repair_escrow_builder::RepairEscrowBuilder,
+        token_account_builder::TokenAccountBuilder,
+    },
+    mollusk_svm::result::Check,
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_2022_interface::{
+        extension::{
+            BaseStateWithExtensions,
+            ExtensionType::{self, ImmutableOwner, TransferHookAccount},
+            PodStateWithExtensions,
+        },
+        pod::PodAccount,
+        state::Account as TokenAccount,
+    },
+    spl_token_wrap::{
+        error::TokenWrapError, get_escrow_address, get_wrapped_mint_address,
+        get_wrapped_mint_authority,
+    },
+};
+
+pub mod helpers;
+
+struct Setup {
+    unwrapped_mint: KeyedAccount,
+    wrapped_mint: KeyedAccount,
+    escrow_address: Pubkey,
+    wrapped_mint_authority: Pubkey,
+}
+
+fn setup_transfer_hook_mint() -> Setup {
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .mint_authority(Pubkey::new_unique())
+        .with_extension(TransferHook)
+        .build();
+    let wrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .mint_key(get_wrapped_mint_address(
+            &unwrapped_mint.key,
+            &spl_token_2022_interface::id(),
+        ))
+        .build();
+    let escrow_address = get_escrow_address(
+        &unwrapped_mint.key,
+        &spl_token_2022_interface::id(),
+        &spl_token_2022_interface::id(),
+    );
+    let wrapped_mint_authority = get_wrapped_mint_authority(&wrapped_mint.key);
+    Setup {
+        unwrapped_mint,
+        wrapped_mint,
+        escrow_address,
+        wrapped_mint_authority,
+    }
+}
+
+#[test]
+fn test_repair_escrow_with_balance() {
+    let setup = setup_transfer_hook_mint();
+
+    // Escrow created before the mint required the transfer hook extension
+    let escrow = TokenAccountBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .mint(setup.unwrapped_mint.clone())
+        .owner(setup.wrapped_mint_authority)
+        .amount(1_000)
+        .with_extension(ImmutableOwner)
+        .account_key(setup.escrow_address)
+        .build();
+    let initial_payer_lamports = 1_000_000_000;
+
+    let result = RepairEscrowBuilder::default()
+        .unwrapped_mint(setup.unwrapped_mint)
+        .wrapped_mint(setup.wrapped_mint)
+        .escrow_account(escrow)
+        .payer(KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account {
+                lamports: initial_payer_lamports,
+                ..Default::default()
+            },
+        })
+        .execute();
+
+    let expected_len = ExtensionType::try_calculate_account_len::<TokenAccount>(&[
+        ImmutableOwner,
+        TransferHookAccount,
+    ])
+    .unwrap();
+    assert_eq!(result.escrow.account.data.len(), expected_len);
+    assert!(result.escrow.account.lamports >= Rent::default().minimum_balance(expected_len));
+    assert!(result.payer.account.lamports < initial_payer_lamports);
+
+    // Funds and ownership are preserved. Reallocate only allocates the space
+    // for the missing extensions.
+    let state = PodStateWithExtensions::<PodAccount>::unpack(&result.escrow.account.data).unwrap();
+    assert_eq!(u64::from(state.base.amount), 1_000);
+    assert_eq!(state.base.owner, setup.wrapped_mint_authority);
+    assert_eq!(state.get_extension_types().unwrap(), vec![ImmutableOwner]);
+}
+
+#[test]
+fn test_repair_escrow_adds_immutable_owner() {
+    let result = RepairEscrowBuilder::default().execute();
+
+    let expected_len =
+        ExtensionType::try_calculate_account_len::<TokenAccount>(&[ImmutableOwner]).unwrap();
+    assert_eq!(result.escrow.account.data.len(), expected_len);
+}
+
+#[test]
+fn test_repair_escrow_fails_when_in_good_state() {
+    let setup = setup_transfer_hook_mint();
+    let escrow = TokenAccountBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .mint(setup.unwrapped_mint.clone())
+        .owner(setup.wrapped_mint_authority)
+        .amount(1_000)
+        .with_extension(ImmutableOwner)
+        .with_extension(TransferHookAccount)
+        .account_key(setup.escrow_address)
+        .build();
+
+    RepairEscrowBuilder::default()
+        .unwrapped_mint(setup.unwrapped_mint)
+        .wrapped_mint(setup.wrapped_mint)
+        .escrow_account(escrow)
+        .check(Check::err(TokenWrapError::EscrowInGoodState.into()))
+        .execute();
+}
+
+#[test]
+fn test_repair_escrow_fails_for_spl_token_mint() {
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .build();
+
+    RepairEscrowBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .check(Check::err(ProgramError::IncorrectProgramId))
+        .execute();
+}
+
+#[test]
+fn test_repair_escrow_fails_wrapped_mint_authority_mismatch() {
+    RepairEscrowBuilder::default()
+        .wrapped_mint_authority(Pubkey::new_unique())
+        .check(Check::err(TokenWrapError::MintAuthorityMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_repair_escrow_fails_escrow_mismatch() {
+    let setup = setup_transfer_hook_mint();
+    let escrow = TokenAccountBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .mint(setup.unwrapped_mint.clone())
+        .owner(setup.wrapped_mint_authority)
+        .build();
+
+    RepairEscrowBuilder::default()
+        .unwrapped_mint(setup.unwrapped_mint)
+        .wrapped_mint(setup.wrapped_mint)
+        .escrow_account(escrow)
+        .check(Check::err(TokenWrapError::EscrowMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_repair_escrow_fails_escrow_owner_mismatch() {
+    let setup = setup_transfer_hook_mint();
+    let escrow = TokenAccountBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .mint(setup.unwrapped_mint.clone())
+        .owner(Pubkey::new_unique())
+        .account_key(setup.escrow_address)
+        .build();
+
+    RepairEscrowBuilder::default()
+        .unwrapped_mint(setup.unwrapped_mint)
+        .wrapped_mint(setup.wrapped_mint)
+        .escrow_account(escrow)
+        .check(Check::err(TokenWrapError::EscrowOwnerMismatch.into()))
+        .execute();
+}
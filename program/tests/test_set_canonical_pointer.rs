@@ -1,15 +1,22 @@
 use {
     crate::helpers::{
-        common::{KeyedAccount, TokenProgram},
+        common::{setup_multisig, versioned_state_data, KeyedAccount, TokenProgram},
+        extensions::MintExtension,
         mint_builder::MintBuilder,
         set_canonical_pointer_builder::SetCanonicalPointerBuilder,
     },
     mollusk_svm::result::Check,
+    mpl_token_metadata::{accounts::Metadata as MetaplexMetadata, types::Key},
     solana_account::Account,
     solana_program_error::ProgramError,
     solana_pubkey::Pubkey,
     solana_rent::Rent,
-    spl_token_wrap::{get_canonical_pointer_address, state::CanonicalDeploymentPointer},
+    spl_token_wrap::{
+        error::TokenWrapError,
+        get_canonical_pointer_address, get_scoped_canonical_pointer_address,
+        get_wrapped_mint_authority,
+        state::{CanonicalDeploymentPointer, VersionedState},
+    },
 };
 
 pub mod helpers;
@@ -46,9 +53,10 @@ fn test_fail_mint_has_no_authority() {
         .no_mint_authority()
         .build();
 
+    // Without a mint authority, the metadata account is required
     SetCanonicalPointerBuilder::default()
         .unwrapped_mint(mint_without_authority)
-        .check(Check::err(ProgramError::InvalidAccountData))
+        .check(Check::err(ProgramError::NotEnoughAccountKeys))
         .execute();
 }
 
@@ -99,9 +107,7 @@ fn test_fail_insufficient_funds_for_new_pointer() {
     let pointer_account_not_rent_exempt = KeyedAccount {
         key: pointer_address,
         account: Account {
-            lamports: Rent::default()
-                .minimum_balance(std::mem::size_of::<CanonicalDeploymentPointer>())
-                - 1,
+            lamports: Rent::default().minimum_balance(CanonicalDeploymentPointer::LEN) - 1,
             ..Default::default()
         },
     };
@@ -126,8 +132,7 @@ fn test_success_create_new_pointer() {
     let pointer_account_uninitialized = KeyedAccount {
         key: pointer_address,
         account: Account {
-            lamports: Rent::default()
-                .minimum_balance(std::mem::size_of::<CanonicalDeploymentPointer>()),
+            lamports: Rent::default().minimum_balance(CanonicalDeploymentPointer::LEN),
             ..Default::default()
         },
     };
@@ -142,8 +147,9 @@ fn test_success_create_new_pointer() {
     // Check account state
     assert_eq!(result.canonical_pointer.account.owner, spl_token_wrap::id());
     let pointer_data =
-        bytemuck::from_bytes::<CanonicalDeploymentPointer>(&result.canonical_pointer.account.data);
+        CanonicalDeploymentPointer::unpack(&result.canonical_pointer.account.data).unwrap();
     assert_eq!(pointer_data.program_id, new_program_id);
+    assert!(!bool::from(pointer_data.enforce_on_wrap));
 }
 
 #[test]
@@ -160,13 +166,13 @@ fn test_success_update_existing_pointer() {
     let pointer_account_initialized = KeyedAccount {
         key: pointer_address,
         account: Account {
-            lamports: Rent::default()
-                .minimum_balance(std::mem::size_of::<CanonicalDeploymentPointer>()),
+            lamports: Rent::default().minimum_balance(CanonicalDeploymentPointer::LEN),
             owner: spl_token_wrap::id(),
-            data: bytemuck::bytes_of(&CanonicalDeploymentPointer {
+            data: versioned_state_data(&CanonicalDeploymentPointer {
                 program_id: old_program_id,
-            })
-            .to_vec(),
+                enforce_on_wrap: false.into(),
+                change_delay: 0.into(),
+            }),
             ..Default::default()
         },
     };
@@ -181,6 +187,369 @@ fn test_success_update_existing_pointer() {
     // Check account state
     assert_eq!(result.canonical_pointer.account.owner, spl_token_wrap::id());
     let pointer_data =
-        bytemuck::from_bytes::<CanonicalDeploymentPointer>(&result.canonical_pointer.account.data);
+        CanonicalDeploymentPointer::unpack(&result.canonical_pointer.account.data).unwrap();
+    assert_eq!(pointer_data.program_id, new_program_id);
+    assert!(!bool::from(pointer_data.enforce_on_wrap));
+}
+
+#[test]
+fn test_success_create_enforced_pointer() {
+    let authority = Pubkey::new_unique();
+    let mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(authority)
+        .build();
+    let new_program_id = Pubkey::new_unique();
+
+    let result = SetCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(mint)
+        .new_program_id(new_program_id)
+        .enforce_on_wrap()
+        .execute();
+
+    let pointer_data =
+        CanonicalDeploymentPointer::unpack(&result.canonical_pointer.account.data).unwrap();
+    assert_eq!(pointer_data.program_id, new_program_id);
+    assert!(bool::from(pointer_data.enforce_on_wrap));
+}
+
+#[test]
+fn test_success_create_scoped_pointer() {
+    let authority = Pubkey::new_unique();
+    let mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(authority)
+        .build();
+    let new_program_id = Pubkey::new_unique();
+    let mint_address = mint.key;
+
+    let result = SetCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(mint)
+        .wrapped_token_program(spl_token_2022_interface::id())
+        .new_program_id(new_program_id)
+        .enforce_on_wrap()
+        .execute();
+
+    assert_eq!(
+        result.canonical_pointer.key,
+        get_scoped_canonical_pointer_address(&mint_address, &spl_token_2022_interface::id())
+    );
+    assert_ne!(
+        result.canonical_pointer.key,
+        get_canonical_pointer_address(&mint_address)
+    );
+    assert_eq!(result.canonical_pointer.account.owner, spl_token_wrap::id());
+    let pointer_data =
+        CanonicalDeploymentPointer::unpack(&result.canonical_pointer.account.data).unwrap();
+    assert_eq!(pointer_data.program_id, new_program_id);
+    assert!(bool::from(pointer_data.enforce_on_wrap));
+}
+
+#[test]
+fn test_fail_scoped_pointer_at_unscoped_address() {
+    let authority = Pubkey::new_unique();
+    let mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(authority)
+        .build();
+    let unscoped_pointer = KeyedAccount {
+        key: get_canonical_pointer_address(&mint.key),
+        account: Account {
+            lamports: Rent::default().minimum_balance(CanonicalDeploymentPointer::LEN),
+            ..Default::default()
+        },
+    };
+
+    SetCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(mint)
+        .wrapped_token_program(spl_token::id())
+        .canonical_pointer(unscoped_pointer)
+        .check(Check::err(ProgramError::InvalidArgument))
+        .execute();
+}
+
+fn legacy_pointer_account(
+    unwrapped_mint: &Pubkey,
+    program_id: Pubkey,
+    lamports: u64,
+) -> KeyedAccount {
+    KeyedAccount {
+        key: get_canonical_pointer_address(unwrapped_mint),
+        account: Account {
+            lamports,
+            owner: spl_token_wrap::id(),
+            data: program_id.to_bytes().to_vec(),
+            ..Default::default()
+        },
+    }
+}
+
+#[test]
+fn test_fail_legacy_pointer_insufficient_funds_for_resize() {
+    let authority = Pubkey::new_unique();
+    let mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(authority)
+        .build();
+    let legacy_pointer = legacy_pointer_account(
+        &mint.key,
+        Pubkey::new_unique(),
+        Rent::default().minimum_balance(CanonicalDeploymentPointer::LEGACY_LEN),
+    );
+
+    SetCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(mint)
+        .canonical_pointer(legacy_pointer)
+        .check(Check::err(ProgramError::AccountNotRentExempt))
+        .execute();
+}
+
+#[test]
+fn test_success_update_legacy_pointer() {
+    let authority = Pubkey::new_unique();
+    let mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(authority)
+        .build();
+    let new_program_id = Pubkey::new_unique();
+    let legacy_pointer = legacy_pointer_account(
+        &mint.key,
+        Pubkey::new_unique(),
+        Rent::default().minimum_balance(CanonicalDeploymentPointer::LEN),
+    );
+
+    let result = SetCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(mint)
+        .canonical_pointer(legacy_pointer)
+        .new_program_id(new_program_id)
+        .enforce_on_wrap()
+        .execute();
+
+    assert_eq!(
+        result.canonical_pointer.account.data.len(),
+        CanonicalDeploymentPointer::LEN
+    );
+    let pointer_data =
+        CanonicalDeploymentPointer::unpack(&result.canonical_pointer.account.data).unwrap();
     assert_eq!(pointer_data.program_id, new_program_id);
+    assert!(bool::from(pointer_data.enforce_on_wrap));
+}
+
+#[test]
+fn test_success_multisig_authority() {
+    let multisig = setup_multisig(TokenProgram::SplToken);
+    let new_program_id = Pubkey::new_unique();
+
+    let result = SetCanonicalPointerBuilder::default()
+        .multisig(multisig)
+        .new_program_id(new_program_id)
+        .execute();
+
+    let pointer_data =
+        CanonicalDeploymentPointer::unpack(&result.canonical_pointer.account.data).unwrap();
+    assert_eq!(pointer_data.program_id, new_program_id);
+}
+
+#[test]
+fn test_fail_multisig_not_enough_signers() {
+    let mut multisig = setup_multisig(TokenProgram::SplToken2022);
+    multisig.signers.truncate(1);
+
+    SetCanonicalPointerBuilder::default()
+        .multisig(multisig)
+        .check(Check::err(ProgramError::MissingRequiredSignature))
+        .execute();
+}
+
+fn metaplex_metadata_account(unwrapped_mint: &Pubkey, update_authority: Pubkey) -> KeyedAccount {
+    let metaplex_metadata = MetaplexMetadata {
+        key: Key::MetadataV1,
+        update_authority,
+        mint: *unwrapped_mint,
+        name: "Metaplex".to_string(),
+        symbol: "MPL".to_string(),
+        uri: "uri.metaplex".to_string(),
+        seller_fee_basis_points: 0,
+        creators: None,
+        primary_sale_happened: false,
+        is_mutable: true,
+        edition_nonce: None,
+        token_standard: None,
+        collection: None,
+        uses: None,
+        collection_details: None,
+        programmable_config: None,
+    };
+    KeyedAccount {
+        key: MetaplexMetadata::find_pda(unwrapped_mint).0,
+        account: Account {
+            owner: mpl_token_metadata::ID,
+            lamports: 1_000_000_000,
+            data: borsh::to_vec(&metaplex_metadata).unwrap(),
+            ..Default::default()
+        },
+    }
+}
+
+#[test]
+fn test_success_metaplex_update_authority_without_mint_authority() {
+    let update_authority = Pubkey::new_unique();
+    let mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .no_mint_authority()
+        .build();
+    let metaplex_metadata = metaplex_metadata_account(&mint.key, update_authority);
+    let new_program_id = Pubkey::new_unique();
+
+    let result = SetCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(update_authority)
+        .unwrapped_mint(mint)
+        .source_metadata(metaplex_metadata)
+        .new_program_id(new_program_id)
+        .execute();
+
+    let pointer_data =
+        CanonicalDeploymentPointer::unpack(&result.canonical_pointer.account.data).unwrap();
+    assert_eq!(pointer_data.program_id, new_program_id);
+}
+
+#[test]
+fn test_fail_metaplex_update_authority_mismatch() {
+    let mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .no_mint_authority()
+        .build();
+    let metaplex_metadata = metaplex_metadata_account(&mint.key, Pubkey::new_unique());
+
+    SetCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(Pubkey::new_unique())
+        .unwrapped_mint(mint)
+        .source_metadata(metaplex_metadata)
+        .check(Check::err(ProgramError::IncorrectAuthority))
+        .execute();
+}
+
+#[test]
+fn test_success_token_metadata_update_authority_without_mint_authority() {
+    let mint_key = Pubkey::new_unique();
+    let mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .mint_key(mint_key)
+        .no_mint_authority()
+        .with_extension(MintExtension::MetadataPointer {
+            metadata_address: Some(mint_key),
+        })
+        .with_extension(MintExtension::TokenMetadata {
+            name: "Token".to_string(),
+            symbol: "TKN".to_string(),
+            uri: "uri.token".to_string(),
+            additional_metadata: vec![],
+        })
+        .build();
+    // The mint holds its own metadata, so it is passed as the metadata account
+    let source_metadata = mint.clone();
+    let new_program_id = Pubkey::new_unique();
+
+    let result = SetCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(get_wrapped_mint_authority(&mint_key))
+        .unwrapped_mint(mint)
+        .source_metadata(source_metadata)
+        .new_program_id(new_program_id)
+        .execute();
+
+    let pointer_data =
+        CanonicalDeploymentPointer::unpack(&result.canonical_pointer.account.data).unwrap();
+    assert_eq!(pointer_data.program_id, new_program_id);
+}
+
+#[test]
+fn test_fail_update_timelocked_pointer() {
+    let authority = Pubkey::new_unique();
+    let mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(authority)
+        .build();
+    let pointer_account_timelocked = KeyedAccount {
+        key: get_canonical_pointer_address(&mint.key),
+        account: Account {
+            lamports: Rent::default().minimum_balance(CanonicalDeploymentPointer::LEN),
+            owner: spl_token_wrap::id(),
+            data: versioned_state_data(&CanonicalDeploymentPointer {
+                program_id: Pubkey::new_unique(),
+                enforce_on_wrap: false.into(),
+                change_delay: 60.into(),
+            }),
+            ..Default::default()
+        },
+    };
+
+    SetCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(mint)
+        .canonical_pointer(pointer_account_timelocked)
+        .check(Check::err(
+            TokenWrapError::CanonicalPointerTimelocked.into(),
+        ))
+        .execute();
+}
+
+#[test]
+fn test_success_create_timelocked_pointer() {
+    let result = SetCanonicalPointerBuilder::default()
+        .change_delay(60)
+        .execute();
+
+    let pointer_data =
+        CanonicalDeploymentPointer::unpack(&result.canonical_pointer.account.data).unwrap();
+    assert!(pointer_data.is_timelocked());
+    assert_eq!(u64::from(pointer_data.change_delay), 60);
+}
+
+#[test]
+fn test_fail_set_scoped_pointer_under_timelocked_unscoped_pointer() {
+    let authority = Pubkey::new_unique();
+    let mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(authority)
+        .build();
+    let unscoped_pointer_timelocked = KeyedAccount {
+        key: get_canonical_pointer_address(&mint.key),
+        account: Account {
+            lamports: Rent::default().minimum_balance(CanonicalDeploymentPointer::LEN),
+            owner: spl_token_wrap::id(),
+            data: versioned_state_data(&CanonicalDeploymentPointer {
+                program_id: Pubkey::new_unique(),
+                enforce_on_wrap: true.into(),
+                change_delay: 60.into(),
+            }),
+            ..Default::default()
+        },
+    };
+
+    SetCanonicalPointerBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(mint)
+        .wrapped_token_program(spl_token_2022_interface::id())
+        .unscoped_canonical_pointer(unscoped_pointer_timelocked)
+        .check(Check::err(
+            TokenWrapError::CanonicalPointerTimelocked.into(),
+        ))
+        .execute();
+}
+
+#[test]
+fn test_fail_scoped_pointer_incorrect_unscoped_pointer_address() {
+    SetCanonicalPointerBuilder::default()
+        .wrapped_token_program(spl_token_2022_interface::id())
+        .unscoped_canonical_pointer(KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        })
+        .check(Check::err(ProgramError::InvalidArgument))
+        .execute();
 }
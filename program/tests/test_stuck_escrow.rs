@@ -33,8 +33,17 @@ use {
         state::{AccountState, Mint},
     },
     spl_token_wrap::{
-        error::TokenWrapError, get_escrow_address, get_wrapped_mint_address,
-        get_wrapped_mint_authority, get_wrapped_mint_backpointer_address, state::Backpointer,
+        error::TokenWrapError,
+        get_allowlist_address, get_allowlist_entry_address, get_canonical_pointer_address,
+        get_escrow_address, get_protocol_fee_config_address, get_protocol_fee_override_address,
+        get_scoped_canonical_pointer_address, get_wrap_limits_address, get_wrapped_mint_address,
+        get_wrapped_mint_authority, get_wrapped_mint_backpointer_address,
+        get_wrapped_mint_index_address, get_wrapped_mint_registry_address,
+        get_wrapped_mint_registry_page_address,
+        state::{
+            Backpointer, VersionedListEntry, VersionedState, WrappedMintIndexEntry,
+            WrappedMintRegistry, WrappedMintRegistryEntry,
+        },
     },
 };
 
@@ -452,6 +461,10 @@ fn test_end_to_end_close_mint_case() {
         &backpointer_address,
         &unwrapped_mint.key,
         &spl_token_2022_interface::id(),
+        &get_wrapped_mint_registry_address(),
+        &get_wrapped_mint_registry_page_address(0),
+        &get_wrapped_mint_index_address(&unwrapped_mint.key),
+        false,
         false,
     );
 
@@ -545,7 +558,16 @@ fn test_end_to_end_close_mint_case() {
         &unwrapped_token_account_addr,
         &unwrapped_mint.key,
         &escrow_address,
+        &get_canonical_pointer_address(&unwrapped_mint.key),
+        &get_scoped_canonical_pointer_address(&unwrapped_mint.key, &spl_token_2022_interface::id()),
+        &get_wrap_limits_address(&unwrapped_mint.key),
+        &get_allowlist_address(&unwrapped_mint.key),
+        &get_allowlist_entry_address(&unwrapped_mint.key, &payer),
+        &get_protocol_fee_config_address(),
+        &get_protocol_fee_override_address(&unwrapped_mint.key),
+        &escrow_address,
         &payer,
+        None,
         &[],
         wrap_amount,
     );
@@ -568,6 +590,7 @@ fn test_end_to_end_close_mint_case() {
                     ExtensionType::try_calculate_account_len::<Mint>(&[
                         ConfidentialTransferMint,
                         MetadataPointer,
+                        ExtensionType::MintCloseAuthority,
                     ])
                     .unwrap(),
                 ),
@@ -577,10 +600,7 @@ fn test_end_to_end_close_mint_case() {
         (
             backpointer_address,
             Account {
-                lamports: mollusk
-                    .sysvars
-                    .rent
-                    .minimum_balance(std::mem::size_of::<Backpointer>()),
+                lamports: mollusk.sysvars.rent.minimum_balance(Backpointer::LEN),
                 ..Default::default()
             },
         ),
@@ -592,6 +612,57 @@ fn test_end_to_end_close_mint_case() {
         mollusk_svm_programs_token::associated_token::keyed_account(),
         (recipient_wrapped_addr, Account::default()),
         (unwrapped_token_account_addr, unwrapped_token_account),
+        (
+            get_canonical_pointer_address(&unwrapped_mint.key),
+            Account::default(),
+        ),
+        (
+            get_wrap_limits_address(&unwrapped_mint.key),
+            Account::default(),
+        ),
+        (
+            get_allowlist_address(&unwrapped_mint.key),
+            Account::default(),
+        ),
+        (
+            get_allowlist_entry_address(&unwrapped_mint.key, &payer),
+            Account::default(),
+        ),
+        (get_protocol_fee_config_address(), Account::default()),
+        (
+            get_protocol_fee_override_address(&unwrapped_mint.key),
+            Account::default(),
+        ),
+        (
+            get_wrapped_mint_registry_address(),
+            Account {
+                lamports: mollusk
+                    .sysvars
+                    .rent
+                    .minimum_balance(WrappedMintRegistry::LEN),
+                ..Default::default()
+            },
+        ),
+        (
+            get_wrapped_mint_registry_page_address(0),
+            Account {
+                lamports: mollusk
+                    .sysvars
+                    .rent
+                    .minimum_balance(WrappedMintRegistryEntry::list_len(1)),
+                ..Default::default()
+            },
+        ),
+        (
+            get_wrapped_mint_index_address(&unwrapped_mint.key),
+            Account {
+                lamports: mollusk
+                    .sysvars
+                    .rent
+                    .minimum_balance(WrappedMintIndexEntry::list_len(1)),
+                ..Default::default()
+            },
+        ),
     ];
 
     let success_check = [Check::success()];
@@ -1,8 +1,8 @@
 use {
     crate::helpers::{
         common::{
-            setup_counter, setup_multisig, setup_validation_state_account, KeyedAccount,
-            TokenProgram, DEFAULT_MINT_SUPPLY,
+            setup_counter, setup_multisig, setup_validation_state_account, versioned_state_data,
+            KeyedAccount, TokenProgram,
         },
         create_mint_builder::CreateMintBuilder,
         extensions::MintExtension::{
@@ -12,9 +12,13 @@ use {
         token_account_builder::TokenAccountBuilder,
         unwrap_builder::{UnwrapBuilder, UnwrapResult},
     },
+    bytemuck::Zeroable,
     helpers::common::TransferAuthority,
     mollusk_svm::{program::create_program_account_loader_v3, result::Check},
+    solana_account::Account,
+    solana_program_error::ProgramError,
     solana_pubkey::Pubkey,
+    solana_rent::Rent,
     spl_token_2022_interface::{
         error::TokenError,
         extension::{
@@ -26,8 +30,9 @@ use {
         pod::{PodAccount, PodMint},
     },
     spl_token_wrap::{
-        error::TokenWrapError, get_escrow_address, get_wrapped_mint_address,
-        get_wrapped_mint_authority,
+        error::TokenWrapError,
+        get_escrow_address, get_wrapped_mint_address, get_wrapped_mint_authority,
+        state::{FlashMintGuard, VersionedState},
     },
 };
 
@@ -107,12 +112,12 @@ fn assert_unwrap_result(
             .into()
     );
 
-    // Verify wrapped mint supply decreased
+    // Verify wrapped mint supply decreased, starting fully collateralized
     let mint = PodStateWithExtensions::<PodMint>::unpack(&unwrap_result.wrapped_mint.account.data)
         .unwrap();
     assert_eq!(
         u64::from(mint.base.supply),
-        DEFAULT_MINT_SUPPLY.checked_sub(unwrap_amount).unwrap()
+        escrow_starting_amount.checked_sub(unwrap_amount).unwrap()
     );
 
     // Verify escrow was debited
@@ -244,6 +249,39 @@ fn test_unwrap_with_spl_token_multisig() {
     );
 }
 
+#[test]
+fn test_fail_unwrap_with_baseline_accounts() {
+    UnwrapBuilder::default()
+        .without_policy_accounts()
+        .escrow_starting_amount(30_000)
+        .wrapped_token_starting_amount(30_000)
+        .unwrap_amount(12_000)
+        .check(Check::err(ProgramError::NotEnoughAccountKeys))
+        .execute();
+}
+
+#[test]
+fn test_fail_unwrap_during_flash_mint() {
+    let guard = FlashMintGuard {
+        active: true.into(),
+        amount: 10_000.into(),
+        ..FlashMintGuard::zeroed()
+    };
+
+    UnwrapBuilder::default()
+        .escrow_starting_amount(30_000)
+        .wrapped_token_starting_amount(30_000)
+        .unwrap_amount(12_000)
+        .flash_mint_guard(Account {
+            lamports: Rent::default().minimum_balance(FlashMintGuard::LEN),
+            owner: spl_token_wrap::id(),
+            data: versioned_state_data(&guard),
+            ..Default::default()
+        })
+        .check(Check::err(TokenWrapError::FlashMintActive.into()))
+        .execute();
+}
+
 #[test]
 fn test_unwrap_with_spl_token_2022_multisig() {
     let multisig = setup_multisig(TokenProgram::SplToken2022);
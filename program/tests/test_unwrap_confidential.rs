@@ -0,0 +1,148 @@
+use {
+    crate::helpers::{
+        common::{setup_multisig, TokenProgram},
+        unwrap_builder::{UnwrapBuilder, UnwrapResult},
+    },
+    mollusk_svm::result::Check,
+    solana_program_error::ProgramError,
+    solana_zk_sdk::encryption::{
+        auth_encryption::AeCiphertext,
+        pod::{
+            auth_encryption::PodAeCiphertext as SdkPodAeCiphertext,
+            elgamal::PodElGamalCiphertext as SdkPodElGamalCiphertext,
+        },
+    },
+    spl_token_2022_interface::{
+        error::TokenError,
+        extension::{
+            confidential_transfer::ConfidentialTransferAccount, BaseStateWithExtensions,
+            PodStateWithExtensions,
+        },
+        pod::{PodAccount, PodMint},
+    },
+};
+
+pub mod helpers;
+
+const ESCROW_STARTING_AMOUNT: u64 = 100_000;
+
+fn assert_unwrap_confidential_result(
+    confidential_balance: u64,
+    unwrap_amount: u64,
+    unwrap_result: &UnwrapResult,
+) {
+    // The withdrawn tokens were burned from the public balance
+    let wrapped_token = PodStateWithExtensions::<PodAccount>::unpack(
+        &unwrap_result.wrapped_token_account.account.data,
+    )
+    .unwrap();
+    assert_eq!(u64::from(wrapped_token.base.amount), 0);
+
+    let wrapped_mint =
+        PodStateWithExtensions::<PodMint>::unpack(&unwrap_result.wrapped_mint.account.data)
+            .unwrap();
+    assert_eq!(
+        u64::from(wrapped_mint.base.supply),
+        ESCROW_STARTING_AMOUNT.checked_sub(unwrap_amount).unwrap()
+    );
+
+    // The escrow released the unwrapped tokens to the recipient
+    let escrow =
+        PodStateWithExtensions::<PodAccount>::unpack(&unwrap_result.unwrapped_escrow.account.data)
+            .unwrap();
+    assert_eq!(
+        u64::from(escrow.base.amount),
+        ESCROW_STARTING_AMOUNT.checked_sub(unwrap_amount).unwrap()
+    );
+
+    let recipient = PodStateWithExtensions::<PodAccount>::unpack(
+        &unwrap_result.recipient_unwrapped_token.account.data,
+    )
+    .unwrap();
+    assert_eq!(u64::from(recipient.base.amount), unwrap_amount);
+
+    // The confidential available balance holds the remainder
+    let balance = unwrap_result.confidential_balance.as_ref().unwrap();
+    let confidential_account = wrapped_token
+        .get_extension::<ConfidentialTransferAccount>()
+        .unwrap();
+    let decryptable_balance: AeCiphertext =
+        bytemuck::cast::<_, SdkPodAeCiphertext>(confidential_account.decryptable_available_balance)
+            .try_into()
+            .unwrap();
+    assert_eq!(
+        balance.aes_key.decrypt(&decryptable_balance),
+        Some(confidential_balance.checked_sub(unwrap_amount).unwrap())
+    );
+    assert_ne!(
+        confidential_account.available_balance,
+        bytemuck::cast(SdkPodElGamalCiphertext::from(balance.available_balance))
+    );
+}
+
+#[test]
+fn test_unwrap_confidential() {
+    let confidential_balance = 5_000;
+    let unwrap_amount = 2_000;
+
+    let unwrap_result = UnwrapBuilder::default()
+        .unwrapped_token_program(TokenProgram::SplToken)
+        .escrow_starting_amount(ESCROW_STARTING_AMOUNT)
+        .unwrap_amount(unwrap_amount)
+        .confidential(confidential_balance)
+        .execute();
+
+    assert_unwrap_confidential_result(confidential_balance, unwrap_amount, &unwrap_result);
+}
+
+#[test]
+fn test_unwrap_confidential_entire_balance() {
+    let confidential_balance = 5_000;
+
+    let unwrap_result = UnwrapBuilder::default()
+        .unwrapped_token_program(TokenProgram::SplToken2022)
+        .escrow_starting_amount(ESCROW_STARTING_AMOUNT)
+        .unwrap_amount(confidential_balance)
+        .confidential(confidential_balance)
+        .execute();
+
+    assert_unwrap_confidential_result(confidential_balance, confidential_balance, &unwrap_result);
+}
+
+#[test]
+fn test_unwrap_confidential_with_multisig() {
+    let confidential_balance = 5_000;
+    let unwrap_amount = 2_000;
+
+    let unwrap_result = UnwrapBuilder::default()
+        .transfer_authority(setup_multisig(TokenProgram::SplToken2022))
+        .escrow_starting_amount(ESCROW_STARTING_AMOUNT)
+        .unwrap_amount(unwrap_amount)
+        .confidential(confidential_balance)
+        .execute();
+
+    assert_unwrap_confidential_result(confidential_balance, unwrap_amount, &unwrap_result);
+}
+
+#[test]
+fn test_unwrap_confidential_fails_proof_for_other_amount() {
+    UnwrapBuilder::default()
+        .escrow_starting_amount(ESCROW_STARTING_AMOUNT)
+        .unwrap_amount(2_000)
+        .confidential(5_000)
+        .confidential_proof_amount(1_000)
+        .check(Check::err(
+            TokenError::ConfidentialTransferBalanceMismatch.into(),
+        ))
+        .execute();
+}
+
+#[test]
+fn test_unwrap_confidential_fails_spl_token_wrapped_mint() {
+    UnwrapBuilder::default()
+        .wrapped_token_program(TokenProgram::SplToken)
+        .unwrap_amount(2_000)
+        .confidential(5_000)
+        .check(Check::err(ProgramError::IncorrectProgramId))
+        .execute();
+}
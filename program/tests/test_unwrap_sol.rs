@@ -0,0 +1,194 @@
+use {
+    crate::helpers::{
+        common::{setup_multisig, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+        unwrap_sol_builder::{UnwrapSolBuilder, UnwrapSolResult},
+    },
+    mollusk_svm::result::Check,
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+    spl_token_2022_interface::{
+        extension::PodStateWithExtensions,
+        pod::{PodAccount, PodMint},
+    },
+    spl_token_wrap::{
+        error::TokenWrapError, get_native_unwrap_account_address, get_wrapped_mint_address,
+    },
+};
+
+pub mod helpers;
+
+fn token_amount(account: &KeyedAccount) -> u64 {
+    PodStateWithExtensions::<PodAccount>::unpack(&account.account.data)
+        .unwrap()
+        .base
+        .amount
+        .into()
+}
+
+fn mint_supply(account: &KeyedAccount) -> u64 {
+    PodStateWithExtensions::<PodMint>::unpack(&account.account.data)
+        .unwrap()
+        .base
+        .supply
+        .into()
+}
+
+fn assert_unwrap_sol_result(result: &UnwrapSolResult, escrow_starting_amount: u64, amount: u64) {
+    // Wrapped tokens were burned
+    assert_eq!(
+        token_amount(&result.wrapped_token_account),
+        escrow_starting_amount.checked_sub(amount).unwrap()
+    );
+    assert_eq!(
+        mint_supply(&result.wrapped_mint),
+        escrow_starting_amount.checked_sub(amount).unwrap()
+    );
+
+    // The escrow released the unwrapped amount
+    assert_eq!(
+        token_amount(&result.unwrapped_escrow),
+        escrow_starting_amount.checked_sub(amount).unwrap()
+    );
+
+    // The temporary account was closed
+    assert_eq!(result.native_unwrap_account.account.lamports, 0);
+}
+
+#[test]
+fn test_unwrap_sol_spl_token_native_mint() {
+    let escrow_starting_amount = 5_000_000;
+    let unwrap_amount = 1_250_000;
+    let payer_lamports = 1_000_000_000;
+
+    let result = UnwrapSolBuilder::default()
+        .unwrapped_token_program(TokenProgram::SplToken)
+        .wrapped_token_program(TokenProgram::SplToken2022)
+        .escrow_starting_amount(escrow_starting_amount)
+        .payer_lamports(payer_lamports)
+        .unwrap_amount(unwrap_amount)
+        .execute();
+
+    assert_unwrap_sol_result(&result, escrow_starting_amount, unwrap_amount);
+
+    // The recipient received the lamports, the payer got the rent back
+    assert_eq!(result.recipient.account.lamports, unwrap_amount);
+    assert_eq!(result.payer.account.lamports, payer_lamports);
+}
+
+#[test]
+fn test_unwrap_sol_token_2022_native_mint() {
+    let escrow_starting_amount = 5_000_000;
+    let unwrap_amount = 5_000_000;
+
+    let result = UnwrapSolBuilder::default()
+        .unwrapped_token_program(TokenProgram::SplToken2022)
+        .wrapped_token_program(TokenProgram::SplToken)
+        .escrow_starting_amount(escrow_starting_amount)
+        .unwrap_amount(unwrap_amount)
+        .execute();
+
+    assert_unwrap_sol_result(&result, escrow_starting_amount, unwrap_amount);
+    assert_eq!(result.recipient.account.lamports, unwrap_amount);
+}
+
+#[test]
+fn test_unwrap_sol_to_payer() {
+    let escrow_starting_amount = 5_000_000;
+    let unwrap_amount = 1_000_000;
+    let payer_lamports = 1_000_000_000;
+
+    let result = UnwrapSolBuilder::default()
+        .escrow_starting_amount(escrow_starting_amount)
+        .payer_lamports(payer_lamports)
+        .payer_is_recipient()
+        .unwrap_amount(unwrap_amount)
+        .execute();
+
+    assert_unwrap_sol_result(&result, escrow_starting_amount, unwrap_amount);
+    assert_eq!(
+        result.payer.account.lamports,
+        payer_lamports + unwrap_amount
+    );
+}
+
+#[test]
+fn test_unwrap_sol_with_multisig() {
+    let escrow_starting_amount = 5_000_000;
+    let unwrap_amount = 1_000_000;
+
+    let result = UnwrapSolBuilder::default()
+        .wrapped_token_program(TokenProgram::SplToken2022)
+        .transfer_authority(setup_multisig(TokenProgram::SplToken2022))
+        .escrow_starting_amount(escrow_starting_amount)
+        .unwrap_amount(unwrap_amount)
+        .execute();
+
+    assert_unwrap_sol_result(&result, escrow_starting_amount, unwrap_amount);
+}
+
+#[test]
+fn test_unwrap_sol_with_funded_native_unwrap_account() {
+    let unwrap_amount = 1_000_000;
+    let payer_lamports = 1_000_000_000;
+    let stray_lamports = 5_000;
+
+    let wrapped_mint_address = get_wrapped_mint_address(
+        &spl_token::native_mint::id(),
+        &spl_token_2022_interface::id(),
+    );
+
+    // Lamports sent to the temporary account beforehand go to the payer
+    let result = UnwrapSolBuilder::default()
+        .native_unwrap_account(KeyedAccount {
+            key: get_native_unwrap_account_address(&wrapped_mint_address),
+            account: Account {
+                lamports: stray_lamports,
+                ..Default::default()
+            },
+        })
+        .payer_lamports(payer_lamports)
+        .unwrap_amount(unwrap_amount)
+        .execute();
+
+    assert_eq!(result.recipient.account.lamports, unwrap_amount);
+    assert_eq!(
+        result.payer.account.lamports,
+        payer_lamports + stray_lamports
+    );
+    assert_eq!(result.native_unwrap_account.account.lamports, 0);
+}
+
+#[test]
+fn test_unwrap_sol_fails_zero_amount() {
+    UnwrapSolBuilder::default()
+        .unwrap_amount(0)
+        .check(Check::err(TokenWrapError::ZeroWrapAmount.into()))
+        .execute();
+}
+
+#[test]
+fn test_unwrap_sol_fails_not_native_mint() {
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_key(Pubkey::new_unique())
+        .build();
+
+    UnwrapSolBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .check(Check::err(TokenWrapError::NotNativeMint.into()))
+        .execute();
+}
+
+#[test]
+fn test_unwrap_sol_fails_native_unwrap_account_mismatch() {
+    UnwrapSolBuilder::default()
+        .native_unwrap_account(KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        })
+        .check(Check::err(
+            TokenWrapError::NativeUnwrapAccountMismatch.into(),
+        ))
+        .execute();
+}
@@ -0,0 +1,182 @@
+use {
+    crate::helpers::{
+        common::TokenProgram, create_mint_builder::CreateMintBuilder, extensions::MintExtension,
+        mint_builder::MintBuilder,
+        update_confidential_config_builder::UpdateConfidentialConfigBuilder,
+    },
+    mollusk_svm::result::Check,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_zero_copy::unaligned::Bool,
+    solana_zk_sdk::encryption::{
+        elgamal::ElGamalKeypair, pod::elgamal::PodElGamalPubkey as SdkPodElGamalPubkey,
+    },
+    solana_zk_sdk_pod::encryption::elgamal::PodElGamalPubkey,
+    spl_token_2022_interface::{
+        extension::{
+            confidential_transfer::ConfidentialTransferMint, BaseStateWithExtensions,
+            PodStateWithExtensions,
+        },
+        pod::PodMint,
+    },
+    spl_token_wrap::{error::TokenWrapError, get_wrapped_mint_address, get_wrapped_mint_authority},
+};
+
+pub mod helpers;
+
+fn new_auditor_elgamal_pubkey() -> PodElGamalPubkey {
+    bytemuck::cast(SdkPodElGamalPubkey::from(
+        *ElGamalKeypair::new_rand().pubkey(),
+    ))
+}
+
+fn confidential_transfer_mint(data: &[u8]) -> ConfidentialTransferMint {
+    *PodStateWithExtensions::<PodMint>::unpack(data)
+        .unwrap()
+        .get_extension::<ConfidentialTransferMint>()
+        .unwrap()
+}
+
+#[test]
+fn test_update_confidential_config() {
+    let auditor_elgamal_pubkey = new_auditor_elgamal_pubkey();
+    let result = UpdateConfidentialConfigBuilder::default()
+        .auto_approve_new_accounts(false)
+        .auditor_elgamal_pubkey(auditor_elgamal_pubkey)
+        .execute();
+
+    let extension = confidential_transfer_mint(&result.wrapped_mint.account.data);
+    assert_eq!(
+        Option::<Pubkey>::from(extension.authority).unwrap(),
+        get_wrapped_mint_authority(&result.wrapped_mint.key)
+    );
+    assert_eq!(extension.auto_approve_new_accounts, Bool::from_bool(false));
+    assert_eq!(
+        Option::<PodElGamalPubkey>::from(extension.auditor_elgamal_pubkey).unwrap(),
+        auditor_elgamal_pubkey
+    );
+}
+
+#[test]
+fn test_update_confidential_config_without_auditor() {
+    let result = UpdateConfidentialConfigBuilder::default()
+        .auto_approve_new_accounts(true)
+        .execute();
+
+    let extension = confidential_transfer_mint(&result.wrapped_mint.account.data);
+    assert_eq!(extension.auto_approve_new_accounts, Bool::from_bool(true));
+    assert!(Option::<PodElGamalPubkey>::from(extension.auditor_elgamal_pubkey).is_none());
+}
+
+#[test]
+fn test_update_confidential_config_fails_incorrect_authority() {
+    let mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(Pubkey::new_unique())
+        .build();
+
+    UpdateConfidentialConfigBuilder::default()
+        .unwrapped_mint(mint)
+        .check(Check::err(ProgramError::IncorrectAuthority))
+        .execute();
+}
+
+#[test]
+fn test_update_confidential_config_fails_missing_signature() {
+    UpdateConfidentialConfigBuilder::default()
+        .authority_not_signer()
+        .check(Check::err(ProgramError::MissingRequiredSignature))
+        .execute();
+}
+
+#[test]
+fn test_update_confidential_config_fails_wrapped_mint_mismatch() {
+    let wrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .with_extension(MintExtension::ConfidentialTransfer)
+        .build();
+
+    UpdateConfidentialConfigBuilder::default()
+        .wrapped_mint(wrapped_mint)
+        .check(Check::err(TokenWrapError::WrappedMintMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_update_confidential_config_fails_wrapped_mint_authority_mismatch() {
+    UpdateConfidentialConfigBuilder::default()
+        .wrapped_mint_authority(Pubkey::new_unique())
+        .check(Check::err(TokenWrapError::MintAuthorityMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_update_confidential_config_fails_spl_token_wrapped_mint() {
+    let unwrapped_mint_authority = Pubkey::new_unique();
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(unwrapped_mint_authority)
+        .build();
+    let wrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_key(get_wrapped_mint_address(
+            &unwrapped_mint.key,
+            &TokenProgram::SplToken.id(),
+        ))
+        .build();
+
+    UpdateConfidentialConfigBuilder::default()
+        .unwrapped_mint_authority(unwrapped_mint_authority)
+        .unwrapped_mint(unwrapped_mint)
+        .wrapped_mint(wrapped_mint)
+        .check(Check::err(ProgramError::IncorrectProgramId))
+        .execute();
+}
+
+#[test]
+fn test_update_confidential_config_fails_immutable_config() {
+    let unwrapped_mint_authority = Pubkey::new_unique();
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(unwrapped_mint_authority)
+        .build();
+
+    // Confidential transfer settings without an authority, as set up by the
+    // default customizer
+    let wrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .mint_key(get_wrapped_mint_address(
+            &unwrapped_mint.key,
+            &TokenProgram::SplToken2022.id(),
+        ))
+        .with_extension(MintExtension::ConfidentialTransfer)
+        .build();
+
+    UpdateConfidentialConfigBuilder::default()
+        .unwrapped_mint_authority(unwrapped_mint_authority)
+        .unwrapped_mint(unwrapped_mint)
+        .wrapped_mint(wrapped_mint)
+        .check(Check::err(
+            TokenWrapError::ConfidentialConfigNotGoverned.into(),
+        ))
+        .execute();
+}
+
+#[test]
+// To test, adjust the processor function:
+// process_create_mint::<GovernedConfidentialMintCustomizer>(program_id, accounts, idempotent)
+#[ignore]
+fn test_create_mint_with_governed_confidential_customizer() {
+    let result = CreateMintBuilder::default()
+        .unwrapped_token_program(TokenProgram::SplToken)
+        .wrapped_token_program(TokenProgram::SplToken2022)
+        .execute();
+
+    let extension = confidential_transfer_mint(&result.wrapped_mint.account.data);
+    assert_eq!(
+        Option::<Pubkey>::from(extension.authority).unwrap(),
+        get_wrapped_mint_authority(&result.wrapped_mint.key)
+    );
+    assert_eq!(extension.auto_approve_new_accounts, Bool::from_bool(true));
+    assert!(Option::<PodElGamalPubkey>::from(extension.auditor_elgamal_pubkey).is_none());
+}
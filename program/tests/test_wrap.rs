@@ -1,8 +1,8 @@
 use {
     crate::helpers::{
         common::{
-            setup_counter, setup_multisig, setup_validation_state_account, KeyedAccount,
-            TokenProgram, DEFAULT_MINT_SUPPLY,
+            setup_counter, setup_multisig, setup_validation_state_account, versioned_state_data,
+            KeyedAccount, TokenProgram, DEFAULT_MINT_SUPPLY,
         },
         create_mint_builder::CreateMintBuilder,
         extensions::MintExtension::{
@@ -18,6 +18,7 @@ use {
     solana_program_error::ProgramError,
     solana_program_pack::Pack,
     solana_pubkey::Pubkey,
+    solana_rent::Rent,
     spl_token_2022_interface::{
         extension::{
             transfer_fee::{TransferFeeAmount, TransferFeeConfig},
@@ -29,7 +30,12 @@ use {
         },
         pod::{PodAccount, PodMint},
     },
-    spl_token_wrap::{error::TokenWrapError, get_wrapped_mint_address, get_wrapped_mint_authority},
+    spl_token_wrap::{
+        error::TokenWrapError,
+        get_canonical_pointer_address, get_scoped_canonical_pointer_address,
+        get_wrapped_mint_address, get_wrapped_mint_authority,
+        state::{AccountDiscriminator, CanonicalDeploymentPointer},
+    },
 };
 
 pub mod helpers;
@@ -85,6 +91,228 @@ fn test_incorrect_escrow_owner() {
         .execute();
 }
 
+fn canonical_pointer_account(
+    unwrapped_mint: &Pubkey,
+    canonical_program_id: Pubkey,
+    enforce_on_wrap: bool,
+) -> KeyedAccount {
+    let data = versioned_state_data(&CanonicalDeploymentPointer {
+        program_id: canonical_program_id,
+        enforce_on_wrap: enforce_on_wrap.into(),
+        change_delay: 0.into(),
+    });
+    KeyedAccount {
+        key: get_canonical_pointer_address(unwrapped_mint),
+        account: Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            owner: spl_token_wrap::id(),
+            data,
+            ..Default::default()
+        },
+    }
+}
+
+#[test]
+fn test_incorrect_canonical_pointer_address() {
+    WrapBuilder::default()
+        .canonical_pointer(KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        })
+        .check(Check::err(ProgramError::InvalidArgument))
+        .execute();
+}
+
+#[test]
+fn test_canonical_pointer_invalid_owner() {
+    let unwrapped_mint = MintBuilder::new().build();
+    let mut pointer = canonical_pointer_account(&unwrapped_mint.key, Pubkey::new_unique(), true);
+    pointer.account.owner = Pubkey::new_unique();
+
+    WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .canonical_pointer(pointer)
+        .check(Check::err(ProgramError::InvalidAccountOwner))
+        .execute();
+}
+
+#[test]
+fn test_wrap_rejected_by_enforced_pointer_to_other_deployment() {
+    let unwrapped_mint = MintBuilder::new().build();
+    let pointer = canonical_pointer_account(&unwrapped_mint.key, Pubkey::new_unique(), true);
+
+    WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .canonical_pointer(pointer)
+        .check(Check::err(TokenWrapError::NonCanonicalDeployment.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_allowed_with_enforced_pointer_to_this_deployment() {
+    let wrap_amount = 1_000;
+    let unwrapped_mint = MintBuilder::new().build();
+    let pointer = canonical_pointer_account(&unwrapped_mint.key, spl_token_wrap::id(), true);
+
+    let wrap_result = WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .canonical_pointer(pointer)
+        .wrap_amount(wrap_amount)
+        .execute();
+
+    assert_wrap_result(0, wrap_amount, &wrap_result);
+}
+
+#[test]
+fn test_wrap_allowed_with_unenforced_pointer_to_other_deployment() {
+    let wrap_amount = 1_000;
+    let unwrapped_mint = MintBuilder::new().build();
+    let pointer = canonical_pointer_account(&unwrapped_mint.key, Pubkey::new_unique(), false);
+
+    let wrap_result = WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .canonical_pointer(pointer)
+        .wrap_amount(wrap_amount)
+        .execute();
+
+    assert_wrap_result(0, wrap_amount, &wrap_result);
+}
+
+#[test]
+fn test_wrap_allowed_with_legacy_pointer() {
+    let wrap_amount = 1_000;
+    let unwrapped_mint = MintBuilder::new().build();
+    let mut pointer = canonical_pointer_account(&unwrapped_mint.key, Pubkey::new_unique(), true);
+    pointer.account.data = Pubkey::new_unique().to_bytes().to_vec();
+
+    let wrap_result = WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .canonical_pointer(pointer)
+        .wrap_amount(wrap_amount)
+        .execute();
+
+    assert_wrap_result(0, wrap_amount, &wrap_result);
+}
+
+#[test]
+fn test_wrap_rejects_unversioned_pointer() {
+    let unwrapped_mint = MintBuilder::new().build();
+    let mut pointer = canonical_pointer_account(&unwrapped_mint.key, Pubkey::new_unique(), true);
+    // Only the original 32-byte layout is read without an account header
+    pointer.account.data = bytemuck::bytes_of(&CanonicalDeploymentPointer {
+        program_id: Pubkey::new_unique(),
+        enforce_on_wrap: true.into(),
+        change_delay: 0.into(),
+    })
+    .to_vec();
+
+    WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .canonical_pointer(pointer)
+        .check(Check::err(ProgramError::InvalidAccountData))
+        .execute();
+}
+
+#[test]
+fn test_wrap_rejects_pointer_with_wrong_discriminator() {
+    let unwrapped_mint = MintBuilder::new().build();
+    let mut pointer = canonical_pointer_account(&unwrapped_mint.key, Pubkey::new_unique(), true);
+    pointer.account.data[0] = AccountDiscriminator::PendingCanonicalPointer as u8;
+
+    WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .canonical_pointer(pointer)
+        .check(Check::err(ProgramError::InvalidAccountData))
+        .execute();
+}
+
+fn scoped_canonical_pointer_account(
+    unwrapped_mint: &Pubkey,
+    wrapped_token_program: &Pubkey,
+    canonical_program_id: Pubkey,
+    enforce_on_wrap: bool,
+) -> KeyedAccount {
+    let mut pointer =
+        canonical_pointer_account(unwrapped_mint, canonical_program_id, enforce_on_wrap);
+    pointer.key = get_scoped_canonical_pointer_address(unwrapped_mint, wrapped_token_program);
+    pointer
+}
+
+#[test]
+fn test_incorrect_scoped_canonical_pointer_address() {
+    let unwrapped_mint = MintBuilder::new().build();
+    // Pointer scoped to the other wrapped token program
+    let pointer = scoped_canonical_pointer_account(
+        &unwrapped_mint.key,
+        &spl_token::id(),
+        spl_token_wrap::id(),
+        true,
+    );
+
+    WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .wrapped_token_program(TokenProgram::SplToken2022)
+        .scoped_canonical_pointer(pointer)
+        .check(Check::err(ProgramError::InvalidArgument))
+        .execute();
+}
+
+#[test]
+fn test_wrap_rejected_by_enforced_scoped_pointer() {
+    let unwrapped_mint = MintBuilder::new().build();
+    let pointer = scoped_canonical_pointer_account(
+        &unwrapped_mint.key,
+        &spl_token_2022_interface::id(),
+        Pubkey::new_unique(),
+        true,
+    );
+
+    WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .wrapped_token_program(TokenProgram::SplToken2022)
+        .scoped_canonical_pointer(pointer)
+        .check(Check::err(TokenWrapError::NonCanonicalDeployment.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_scoped_pointer_overrides_unscoped_pointer() {
+    let wrap_amount = 1_000;
+    let unwrapped_mint = MintBuilder::new().build();
+    let pointer = canonical_pointer_account(&unwrapped_mint.key, Pubkey::new_unique(), true);
+    let scoped_pointer = scoped_canonical_pointer_account(
+        &unwrapped_mint.key,
+        &spl_token_2022_interface::id(),
+        spl_token_wrap::id(),
+        true,
+    );
+
+    let wrap_result = WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .wrapped_token_program(TokenProgram::SplToken2022)
+        .canonical_pointer(pointer)
+        .scoped_canonical_pointer(scoped_pointer)
+        .wrap_amount(wrap_amount)
+        .execute();
+
+    assert_wrap_result(0, wrap_amount, &wrap_result);
+}
+
+#[test]
+fn test_wrap_falls_back_to_unscoped_pointer() {
+    let unwrapped_mint = MintBuilder::new().build();
+    let pointer = canonical_pointer_account(&unwrapped_mint.key, Pubkey::new_unique(), true);
+
+    // No pointer is scoped to the wrapped token program, so the unscoped
+    // pointer applies
+    WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .wrapped_token_program(TokenProgram::SplToken2022)
+        .canonical_pointer(pointer)
+        .check(Check::err(TokenWrapError::NonCanonicalDeployment.into()))
+        .execute();
+}
+
 fn assert_wrap_result(starting_amount: u64, wrap_amount: u64, wrap_result: &WrapResult) {
     // Verify the unwrapped tokens were transferred to escrow
     let escrow_token =
@@ -223,6 +451,15 @@ fn test_wrap_with_token_2022_multisig() {
     assert_wrap_result(starting_amount, wrap_amount, &wrap_result);
 }
 
+#[test]
+fn test_fail_wrap_with_baseline_accounts() {
+    WrapBuilder::default()
+        .without_policy_accounts()
+        .wrap_amount(750)
+        .check(Check::err(ProgramError::NotEnoughAccountKeys))
+        .execute();
+}
+
 #[test]
 fn test_wrap_with_transfer_hook() {
     let hook_program_id = test_transfer_hook::id();
@@ -0,0 +1,107 @@
+use {
+    crate::helpers::{
+        common::{setup_multisig, TokenProgram, DEFAULT_MINT_SUPPLY},
+        wrap_builder::{WrapBuilder, WrapResult},
+    },
+    mollusk_svm::result::Check,
+    solana_zk_sdk_pod::encryption::elgamal::PodElGamalCiphertext,
+    spl_token_2022_interface::{
+        extension::{
+            confidential_transfer::ConfidentialTransferAccount, BaseStateWithExtensions,
+            ExtensionType::ImmutableOwner, PodStateWithExtensions,
+        },
+        pod::{PodAccount, PodMint},
+    },
+    spl_token_wrap::error::TokenWrapError,
+};
+
+pub mod helpers;
+
+fn assert_wrap_confidential_result(
+    starting_amount: u64,
+    wrap_amount: u64,
+    wrap_result: &WrapResult,
+) {
+    // The unwrapped tokens are escrowed as with a plain wrap
+    let escrow_token =
+        PodStateWithExtensions::<PodAccount>::unpack(&wrap_result.unwrapped_escrow.account.data)
+            .unwrap();
+    assert_eq!(u64::from(escrow_token.base.amount), wrap_amount);
+
+    let mint =
+        PodStateWithExtensions::<PodMint>::unpack(&wrap_result.wrapped_mint.account.data).unwrap();
+    assert_eq!(
+        u64::from(mint.base.supply),
+        DEFAULT_MINT_SUPPLY.checked_add(wrap_amount).unwrap()
+    );
+
+    // The minted tokens went into the pending balance instead of the public
+    // balance
+    let recipient_token = PodStateWithExtensions::<PodAccount>::unpack(
+        &wrap_result.recipient_wrapped_token.account.data,
+    )
+    .unwrap();
+    assert_eq!(u64::from(recipient_token.base.amount), starting_amount);
+
+    let confidential_account = recipient_token
+        .get_extension::<ConfidentialTransferAccount>()
+        .unwrap();
+    assert_eq!(
+        u64::from(confidential_account.pending_balance_credit_counter),
+        1
+    );
+    assert_ne!(
+        confidential_account.pending_balance_lo,
+        PodElGamalCiphertext::default()
+    );
+}
+
+#[test]
+fn test_wrap_confidential() {
+    let wrap_amount = 5_000;
+
+    let wrap_result = WrapBuilder::default()
+        .unwrapped_token_program(TokenProgram::SplToken)
+        .wrapped_token_program(TokenProgram::SplToken2022)
+        .wrap_amount(wrap_amount)
+        .confidential()
+        .execute();
+
+    assert_wrap_confidential_result(0, wrap_amount, &wrap_result);
+}
+
+#[test]
+fn test_wrap_confidential_keeps_public_balance() {
+    let starting_amount = 1_000;
+    let wrap_amount = 5_000;
+
+    let wrap_result = WrapBuilder::default()
+        .unwrapped_token_program(TokenProgram::SplToken2022)
+        .recipient_starting_amount(starting_amount)
+        .wrap_amount(wrap_amount)
+        .confidential()
+        .execute();
+
+    assert_wrap_confidential_result(starting_amount, wrap_amount, &wrap_result);
+}
+
+#[test]
+fn test_wrap_confidential_with_multisig() {
+    let wrap_amount = 5_000;
+
+    let wrap_result = WrapBuilder::default()
+        .transfer_authority(setup_multisig(TokenProgram::SplToken))
+        .wrap_amount(wrap_amount)
+        .confidential()
+        .execute();
+
+    assert_wrap_confidential_result(0, wrap_amount, &wrap_result);
+}
+
+#[test]
+fn test_wrap_confidential_fails_recipient_not_configured() {
+    WrapBuilder::default()
+        .confidential_with_recipient_extensions(vec![ImmutableOwner])
+        .check(Check::err(TokenWrapError::RecipientNotConfidential.into()))
+        .execute();
+}
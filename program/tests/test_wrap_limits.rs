@@ -0,0 +1,466 @@
+use {
+    crate::helpers::{
+        common::{versioned_state_data, KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+        reconcile_escrow_builder::ReconcileEscrowBuilder,
+        set_wrap_limits_builder::SetWrapLimitsBuilder,
+        unwrap_builder::UnwrapBuilder,
+        unwrap_sol_builder::UnwrapSolBuilder,
+        wrap_builder::WrapBuilder,
+        wrap_sol_builder::{native_mint, WrapSolBuilder},
+    },
+    bytemuck::Zeroable,
+    mollusk_svm::result::Check,
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        error::TokenWrapError,
+        get_wrap_limits_address,
+        state::{VersionedState, WrapLimits},
+    },
+};
+
+pub mod helpers;
+
+fn unwrapped_mint() -> KeyedAccount {
+    MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(Pubkey::new_unique())
+        .build()
+}
+
+fn limits_account(limits: &WrapLimits) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(WrapLimits::LEN),
+        owner: spl_token_wrap::id(),
+        data: versioned_state_data(limits),
+        ..Default::default()
+    }
+}
+
+fn keyed_limits_account(unwrapped_mint: &KeyedAccount, limits: &WrapLimits) -> KeyedAccount {
+    KeyedAccount {
+        key: get_wrap_limits_address(&unwrapped_mint.key),
+        account: limits_account(limits),
+    }
+}
+
+fn unpack_limits(account: &KeyedAccount) -> WrapLimits {
+    WrapLimits::unpack_versioned(&account.account.data).unwrap()
+}
+
+#[test]
+fn test_set_wrap_limits() {
+    let result = SetWrapLimitsBuilder::default()
+        .max_wrapped_supply(1_000)
+        .max_wrapped_per_epoch(100)
+        .execute();
+
+    assert_eq!(result.wrap_limits.account.owner, spl_token_wrap::id());
+    let limits = unpack_limits(&result.wrap_limits);
+    assert!(!limits.is_unwrap_only());
+    assert_eq!(u64::from(limits.max_wrapped_supply), 1_000);
+    assert_eq!(u64::from(limits.max_wrapped_per_epoch), 100);
+}
+
+#[test]
+fn test_set_wrap_limits_keeps_epoch_count() {
+    let authority = Pubkey::new_unique();
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(authority)
+        .build();
+    let limits = WrapLimits {
+        max_wrapped_per_epoch: 100.into(),
+        epoch: 7.into(),
+        wrapped_in_epoch: 60.into(),
+        total_wrapped: 400.into(),
+        ..WrapLimits::zeroed()
+    };
+
+    let result = SetWrapLimitsBuilder::default()
+        .unwrapped_mint_authority(authority)
+        .unwrapped_mint(unwrapped_mint.clone())
+        .wrap_limits(keyed_limits_account(&unwrapped_mint, &limits))
+        .wrapped_supply(TokenProgram::SplToken2022, 350)
+        .unwrap_only()
+        .execute();
+
+    let limits = unpack_limits(&result.wrap_limits);
+    assert!(limits.is_unwrap_only());
+    assert_eq!(u64::from(limits.max_wrapped_per_epoch), 0);
+    assert_eq!(u64::from(limits.epoch), 7);
+    assert_eq!(u64::from(limits.wrapped_in_epoch), 60);
+    // The total is reset to the wrapped supply
+    assert_eq!(u64::from(limits.total_wrapped), 350);
+}
+
+#[test]
+fn test_set_wrap_limits_seeds_total_from_wrapped_supply() {
+    let result = SetWrapLimitsBuilder::default()
+        .wrapped_supply(TokenProgram::SplToken, 300)
+        .wrapped_supply(TokenProgram::SplToken2022, 200)
+        .max_wrapped_supply(1_000)
+        .execute();
+
+    let limits = unpack_limits(&result.wrap_limits);
+    assert_eq!(u64::from(limits.total_wrapped), 500);
+
+    // Tokens wrapped before the limits were set count towards the cap
+    let unwrapped_mint = unwrapped_mint();
+    WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint.clone())
+        .wrap_limits(keyed_limits_account(&unwrapped_mint, &limits))
+        .wrap_amount(501)
+        .check(Check::err(TokenWrapError::WrappedSupplyCapExceeded.into()))
+        .execute();
+}
+
+#[test]
+fn test_set_wrap_limits_fails_incorrect_wrapped_mint() {
+    SetWrapLimitsBuilder::default()
+        .spl_token_wrapped_mint(KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        })
+        .check(Check::err(TokenWrapError::WrappedMintMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_set_wrap_limits_fails_incorrect_authority() {
+    SetWrapLimitsBuilder::default()
+        .unwrapped_mint(unwrapped_mint())
+        .check(Check::err(ProgramError::IncorrectAuthority))
+        .execute();
+}
+
+#[test]
+fn test_set_wrap_limits_fails_authority_not_signer() {
+    SetWrapLimitsBuilder::default()
+        .authority_not_signer()
+        .check(Check::err(ProgramError::MissingRequiredSignature))
+        .execute();
+}
+
+#[test]
+fn test_set_wrap_limits_fails_incorrect_address() {
+    SetWrapLimitsBuilder::default()
+        .wrap_limits(KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        })
+        .check(Check::err(TokenWrapError::WrapLimitsMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_records_epoch_count() {
+    let unwrapped_mint = unwrapped_mint();
+    let limits = WrapLimits {
+        max_wrapped_per_epoch: 1_000.into(),
+        ..WrapLimits::zeroed()
+    };
+
+    let result = WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint.clone())
+        .wrap_limits(keyed_limits_account(&unwrapped_mint, &limits))
+        .epoch(3)
+        .wrap_amount(400)
+        .execute();
+
+    let limits = unpack_limits(&result.wrap_limits);
+    assert_eq!(u64::from(limits.epoch), 3);
+    assert_eq!(u64::from(limits.wrapped_in_epoch), 400);
+}
+
+#[test]
+fn test_wrap_fails_unwrap_only() {
+    let unwrapped_mint = unwrapped_mint();
+    let limits = WrapLimits {
+        unwrap_only: true.into(),
+        ..WrapLimits::zeroed()
+    };
+
+    WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint.clone())
+        .wrap_limits(keyed_limits_account(&unwrapped_mint, &limits))
+        .check(Check::err(TokenWrapError::UnwrapOnly.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_supply_cap() {
+    let unwrapped_mint = unwrapped_mint();
+    let wrap_amount = 500;
+    let limits = WrapLimits {
+        max_wrapped_supply: (1_000 + wrap_amount).into(),
+        total_wrapped: 1_000.into(),
+        ..WrapLimits::zeroed()
+    };
+
+    // Wrapping up to the cap succeeds
+    let result = WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint.clone())
+        .wrap_limits(keyed_limits_account(&unwrapped_mint, &limits))
+        .wrap_amount(wrap_amount)
+        .execute();
+    assert_eq!(
+        u64::from(unpack_limits(&result.wrap_limits).total_wrapped),
+        1_000 + wrap_amount
+    );
+
+    // Wrapping past it fails
+    WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint.clone())
+        .wrap_limits(keyed_limits_account(&unwrapped_mint, &limits))
+        .wrap_amount(wrap_amount + 1)
+        .check(Check::err(TokenWrapError::WrappedSupplyCapExceeded.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_supply_cap_spans_wrapped_mints() {
+    let unwrapped_mint = unwrapped_mint();
+    let limits = WrapLimits {
+        max_wrapped_supply: 1_000.into(),
+        ..WrapLimits::zeroed()
+    };
+
+    // Wrap into the Token-2022 wrapped mint
+    let result = WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint.clone())
+        .wrapped_token_program(TokenProgram::SplToken2022)
+        .wrap_limits(keyed_limits_account(&unwrapped_mint, &limits))
+        .wrap_amount(600)
+        .execute();
+
+    // The SPL Token wrapped mint only has the rest of the cap left, although
+    // its own supply is far below it
+    WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint.clone())
+        .wrapped_token_program(TokenProgram::SplToken)
+        .wrap_limits(result.wrap_limits.clone())
+        .wrap_amount(401)
+        .check(Check::err(TokenWrapError::WrappedSupplyCapExceeded.into()))
+        .execute();
+
+    let result = WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint.clone())
+        .wrapped_token_program(TokenProgram::SplToken)
+        .wrap_limits(result.wrap_limits)
+        .wrap_amount(400)
+        .execute();
+    assert_eq!(
+        u64::from(unpack_limits(&result.wrap_limits).total_wrapped),
+        1_000
+    );
+}
+
+#[test]
+fn test_unwrap_reduces_total_wrapped() {
+    let unwrapped_mint = unwrapped_mint();
+    let limits = WrapLimits {
+        max_wrapped_supply: 1_000.into(),
+        total_wrapped: 1_000.into(),
+        ..WrapLimits::zeroed()
+    };
+
+    let result = UnwrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint.clone())
+        .wrap_limits(keyed_limits_account(&unwrapped_mint, &limits))
+        .unwrap_amount(300)
+        .execute();
+    assert_eq!(
+        u64::from(unpack_limits(&result.wrap_limits).total_wrapped),
+        700
+    );
+
+    // The unwrapped amount can be wrapped again
+    WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint.clone())
+        .wrap_limits(result.wrap_limits)
+        .wrap_amount(300)
+        .execute();
+}
+
+#[test]
+fn test_unwrap_total_wrapped_stops_at_zero() {
+    let unwrapped_mint = unwrapped_mint();
+    let limits = WrapLimits {
+        total_wrapped: 100.into(),
+        ..WrapLimits::zeroed()
+    };
+
+    // The total never fails an unwrap
+    let result = UnwrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint.clone())
+        .wrap_limits(keyed_limits_account(&unwrapped_mint, &limits))
+        .unwrap_amount(300)
+        .execute();
+    assert_eq!(
+        u64::from(unpack_limits(&result.wrap_limits).total_wrapped),
+        0
+    );
+}
+
+#[test]
+fn test_unwrap_allowed_when_unwrap_only() {
+    let unwrapped_mint = unwrapped_mint();
+    let limits = WrapLimits {
+        unwrap_only: true.into(),
+        total_wrapped: 500.into(),
+        ..WrapLimits::zeroed()
+    };
+
+    let result = UnwrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint.clone())
+        .wrap_limits(keyed_limits_account(&unwrapped_mint, &limits))
+        .unwrap_amount(500)
+        .execute();
+    assert_eq!(
+        u64::from(unpack_limits(&result.wrap_limits).total_wrapped),
+        0
+    );
+}
+
+#[test]
+fn test_unwrap_fails_incorrect_limits_address() {
+    UnwrapBuilder::default()
+        .wrap_limits(KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        })
+        .check(Check::err(TokenWrapError::WrapLimitsMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_unwrap_sol_reduces_total_wrapped() {
+    let unwrapped_mint = native_mint(TokenProgram::SplToken);
+    let limits = WrapLimits {
+        total_wrapped: 3_000_000.into(),
+        ..WrapLimits::zeroed()
+    };
+
+    let result = UnwrapSolBuilder::default()
+        .wrap_limits(keyed_limits_account(&unwrapped_mint, &limits))
+        .unwrap_amount(1_000_000)
+        .execute();
+    assert_eq!(
+        u64::from(unpack_limits(&result.wrap_limits).total_wrapped),
+        2_000_000
+    );
+}
+
+#[test]
+fn test_wrap_fails_epoch_cap() {
+    let unwrapped_mint = unwrapped_mint();
+    let limits = WrapLimits {
+        max_wrapped_per_epoch: 1_000.into(),
+        epoch: 3.into(),
+        wrapped_in_epoch: 800.into(),
+        ..WrapLimits::zeroed()
+    };
+
+    WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint.clone())
+        .wrap_limits(keyed_limits_account(&unwrapped_mint, &limits))
+        .epoch(3)
+        .wrap_amount(201)
+        .check(Check::err(TokenWrapError::EpochWrapCapExceeded.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_epoch_cap_resets_in_new_epoch() {
+    let unwrapped_mint = unwrapped_mint();
+    let limits = WrapLimits {
+        max_wrapped_per_epoch: 1_000.into(),
+        epoch: 3.into(),
+        wrapped_in_epoch: 800.into(),
+        ..WrapLimits::zeroed()
+    };
+
+    let result = WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint.clone())
+        .wrap_limits(keyed_limits_account(&unwrapped_mint, &limits))
+        .epoch(4)
+        .wrap_amount(900)
+        .execute();
+
+    let limits = unpack_limits(&result.wrap_limits);
+    assert_eq!(u64::from(limits.epoch), 4);
+    assert_eq!(u64::from(limits.wrapped_in_epoch), 900);
+}
+
+#[test]
+fn test_wrap_fails_incorrect_limits_address() {
+    WrapBuilder::default()
+        .wrap_limits(KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account::default(),
+        })
+        .check(Check::err(TokenWrapError::WrapLimitsMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_fails_limits_not_owned_by_program() {
+    let unwrapped_mint = unwrapped_mint();
+    let mut limits = keyed_limits_account(&unwrapped_mint, &WrapLimits::zeroed());
+    limits.account.owner = Pubkey::new_unique();
+
+    WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .wrap_limits(limits)
+        .check(Check::err(ProgramError::InvalidAccountOwner))
+        .execute();
+}
+
+#[test]
+fn test_wrap_sol_fails_unwrap_only() {
+    let limits = WrapLimits {
+        unwrap_only: true.into(),
+        ..WrapLimits::zeroed()
+    };
+
+    WrapSolBuilder::default()
+        .unwrapped_mint(native_mint(TokenProgram::SplToken))
+        .wrap_limits(limits_account(&limits))
+        .check(Check::err(TokenWrapError::UnwrapOnly.into()))
+        .execute();
+}
+
+#[test]
+fn test_reconcile_escrow_records_total_wrapped() {
+    let limits = WrapLimits {
+        max_wrapped_supply: 150_000.into(),
+        total_wrapped: 100_000.into(),
+        ..WrapLimits::zeroed()
+    };
+
+    let result = ReconcileEscrowBuilder::default()
+        .wrap_limits(limits_account(&limits))
+        .execute();
+    assert_eq!(
+        u64::from(unpack_limits(&result.wrap_limits).total_wrapped),
+        150_000
+    );
+}
+
+#[test]
+fn test_reconcile_escrow_fails_supply_cap() {
+    let limits = WrapLimits {
+        max_wrapped_supply: 120_000.into(),
+        total_wrapped: 100_000.into(),
+        ..WrapLimits::zeroed()
+    };
+
+    ReconcileEscrowBuilder::default()
+        .wrap_limits(limits_account(&limits))
+        .check(Check::err(TokenWrapError::WrappedSupplyCapExceeded.into()))
+        .execute();
+}
@@ -0,0 +1,141 @@
+use {
+    crate::helpers::{
+        common::{KeyedAccount, TokenProgram},
+        mint_builder::MintBuilder,
+        wrap_sol_builder::{native_mint, WrapSolBuilder, WrapSolResult},
+    },
+    mollusk_svm::result::Check,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    spl_token_2022_interface::{
+        extension::PodStateWithExtensions,
+        pod::{PodAccount, PodMint},
+    },
+    spl_token_wrap::error::TokenWrapError,
+};
+
+pub mod helpers;
+
+fn token_amount(account: &KeyedAccount) -> u64 {
+    PodStateWithExtensions::<PodAccount>::unpack(&account.account.data)
+        .unwrap()
+        .base
+        .amount
+        .into()
+}
+
+fn mint_supply(account: &KeyedAccount) -> u64 {
+    PodStateWithExtensions::<PodMint>::unpack(&account.account.data)
+        .unwrap()
+        .base
+        .supply
+        .into()
+}
+
+fn assert_wrap_sol_result(
+    result: &WrapSolResult,
+    escrow_starting_amount: u64,
+    wrap_amount: u64,
+    funding_lamports: u64,
+) {
+    // Lamports moved into the escrow and were synced to its token balance
+    assert_eq!(
+        token_amount(&result.unwrapped_escrow),
+        escrow_starting_amount.checked_add(wrap_amount).unwrap()
+    );
+    assert_eq!(
+        result.funding_account.account.lamports,
+        funding_lamports.checked_sub(wrap_amount).unwrap()
+    );
+
+    // Wrapped tokens were minted 1:1
+    assert_eq!(token_amount(&result.recipient_wrapped_token), wrap_amount);
+    assert_eq!(
+        mint_supply(&result.wrapped_mint),
+        escrow_starting_amount.checked_add(wrap_amount).unwrap()
+    );
+}
+
+#[test]
+fn test_wrap_sol_spl_token_native_mint() {
+    let wrap_amount = 2_500_000;
+    let escrow_starting_amount = 10_000;
+    let funding_lamports = 1_000_000_000;
+
+    let result = WrapSolBuilder::default()
+        .unwrapped_token_program(TokenProgram::SplToken)
+        .wrapped_token_program(TokenProgram::SplToken2022)
+        .escrow_starting_amount(escrow_starting_amount)
+        .funding_lamports(funding_lamports)
+        .wrap_amount(wrap_amount)
+        .execute();
+
+    assert_wrap_sol_result(
+        &result,
+        escrow_starting_amount,
+        wrap_amount,
+        funding_lamports,
+    );
+}
+
+#[test]
+fn test_wrap_sol_token_2022_native_mint() {
+    let wrap_amount = 2_500_000;
+    let funding_lamports = 1_000_000_000;
+
+    let result = WrapSolBuilder::default()
+        .unwrapped_token_program(TokenProgram::SplToken2022)
+        .wrapped_token_program(TokenProgram::SplToken)
+        .funding_lamports(funding_lamports)
+        .wrap_amount(wrap_amount)
+        .execute();
+
+    assert_wrap_sol_result(&result, 0, wrap_amount, funding_lamports);
+}
+
+#[test]
+fn test_wrap_sol_fails_zero_amount() {
+    WrapSolBuilder::default()
+        .wrap_amount(0)
+        .check(Check::err(TokenWrapError::ZeroWrapAmount.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_sol_fails_not_native_mint() {
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_key(Pubkey::new_unique())
+        .build();
+
+    WrapSolBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .check(Check::err(TokenWrapError::NotNativeMint.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_sol_fails_native_mint_of_other_program() {
+    WrapSolBuilder::default()
+        .unwrapped_token_program(TokenProgram::SplToken2022)
+        .unwrapped_mint(native_mint(TokenProgram::SplToken))
+        .check(Check::err(TokenWrapError::NotNativeMint.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_sol_fails_wrapped_mint_authority_mismatch() {
+    WrapSolBuilder::default()
+        .wrapped_mint_authority(Pubkey::new_unique())
+        .check(Check::err(TokenWrapError::MintAuthorityMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_wrap_sol_fails_insufficient_lamports() {
+    WrapSolBuilder::default()
+        .funding_lamports(1_000)
+        .wrap_amount(2_000)
+        .check(Check::err(ProgramError::Custom(1)))
+        .execute();
+}
@@ -0,0 +1,262 @@
+use {
+    crate::helpers::{
+        common::{setup_multisig, versioned_state_data, KeyedAccount, TokenProgram},
+        extensions::MintExtension::TransferFeeConfig as MintTransferFeeConfig,
+        mint_builder::MintBuilder,
+        token_account_builder::TokenAccountBuilder,
+        unwrap_builder::UnwrapBuilder,
+        wrap_builder::WrapBuilder,
+    },
+    bytemuck::Zeroable,
+    helpers::common::TransferAuthority,
+    mollusk_svm::result::Check,
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_2022_interface::{
+        extension::{
+            transfer_fee::TransferFeeConfig,
+            BaseStateWithExtensions,
+            ExtensionType::{ImmutableOwner, TransferFeeConfig as TransferFeeExt},
+            PodStateWithExtensions,
+        },
+        pod::PodMint,
+    },
+    spl_token_wrap::{
+        error::TokenWrapError,
+        get_wrap_stats_address, get_wrapped_mint_address, get_wrapped_mint_authority,
+        state::{VersionedState, WrapStats},
+    },
+};
+
+pub mod helpers;
+
+fn funded_stats_account() -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(WrapStats::LEN),
+        ..Default::default()
+    }
+}
+
+fn stats_account(stats: &WrapStats) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(WrapStats::LEN),
+        owner: spl_token_wrap::id(),
+        data: versioned_state_data(stats),
+        ..Default::default()
+    }
+}
+
+fn unpack_stats(account: &KeyedAccount) -> WrapStats {
+    WrapStats::unpack_versioned(&account.account.data).unwrap()
+}
+
+#[test]
+fn test_wrap_without_stats() {
+    let result = WrapBuilder::default().wrap_amount(500).execute();
+    assert!(result.wrap_stats.is_none());
+}
+
+#[test]
+fn test_wrap_creates_stats() {
+    let result = WrapBuilder::default()
+        .wrap_amount(500)
+        .slot(42)
+        .wrap_stats(funded_stats_account())
+        .execute();
+
+    let wrap_stats = result.wrap_stats.unwrap();
+    assert_eq!(
+        wrap_stats.key,
+        get_wrap_stats_address(&result.wrapped_mint.key)
+    );
+    assert_eq!(wrap_stats.account.owner, spl_token_wrap::id());
+
+    let stats = unpack_stats(&wrap_stats);
+    assert_eq!(u128::from(stats.total_wrapped), 500);
+    assert_eq!(u128::from(stats.total_unwrapped), 0);
+    assert_eq!(u128::from(stats.total_wrap_fees), 0);
+    assert_eq!(u64::from(stats.wrap_count), 1);
+    assert_eq!(u64::from(stats.unwrap_count), 0);
+    assert_eq!(u64::from(stats.last_activity_slot), 42);
+}
+
+#[test]
+fn test_wrap_stats_not_funded() {
+    WrapBuilder::default()
+        .wrap_stats(Account::default())
+        .check(Check::err(ProgramError::AccountNotRentExempt))
+        .execute();
+}
+
+#[test]
+fn test_wrap_stats_accumulate() {
+    let existing = WrapStats {
+        total_wrapped: 1_000u128.into(),
+        total_unwrapped: 300u128.into(),
+        total_wrap_fees: 7u128.into(),
+        wrap_count: 3.into(),
+        unwrap_count: 1.into(),
+        last_activity_slot: 10.into(),
+    };
+
+    let result = WrapBuilder::default()
+        .wrap_amount(500)
+        .slot(20)
+        .wrap_stats(stats_account(&existing))
+        .execute();
+
+    let stats = unpack_stats(&result.wrap_stats.unwrap());
+    assert_eq!(u128::from(stats.total_wrapped), 1_500);
+    assert_eq!(u128::from(stats.total_unwrapped), 300);
+    assert_eq!(u128::from(stats.total_wrap_fees), 7);
+    assert_eq!(u64::from(stats.wrap_count), 4);
+    assert_eq!(u64::from(stats.unwrap_count), 1);
+    assert_eq!(u64::from(stats.last_activity_slot), 20);
+}
+
+#[test]
+fn test_wrap_stats_records_transfer_fee() {
+    let wrap_amount = 500_000;
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .with_extension(MintTransferFeeConfig)
+        .build();
+    let transfer_authority = KeyedAccount::default();
+    let unwrapped_token_account = TokenAccountBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .mint(unwrapped_mint.clone())
+        .owner(transfer_authority.key)
+        .amount(wrap_amount)
+        .with_extension(TransferFeeExt)
+        .build();
+    let wrapped_mint_address =
+        get_wrapped_mint_address(&unwrapped_mint.key, &spl_token_2022_interface::id());
+    let escrow = TokenAccountBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .mint(unwrapped_mint.clone())
+        .owner(get_wrapped_mint_authority(&wrapped_mint_address))
+        .amount(0)
+        .with_extension(TransferFeeExt)
+        .with_extension(ImmutableOwner)
+        .build()
+        .account;
+
+    let result = WrapBuilder::default()
+        .unwrapped_mint(unwrapped_mint.clone())
+        .unwrapped_token_account(unwrapped_token_account)
+        .unwrapped_escrow_account(escrow)
+        .transfer_authority(TransferAuthority {
+            keyed_account: transfer_authority,
+            signers: vec![],
+        })
+        .unwrapped_token_program(TokenProgram::SplToken2022)
+        .wrapped_token_program(TokenProgram::SplToken2022)
+        .wrap_amount(wrap_amount)
+        .wrap_stats(funded_stats_account())
+        .execute();
+
+    let fee = PodStateWithExtensions::<PodMint>::unpack(&unwrapped_mint.account.data)
+        .unwrap()
+        .get_extension::<TransferFeeConfig>()
+        .unwrap()
+        .calculate_epoch_fee(0, wrap_amount)
+        .unwrap();
+    assert!(fee > 0);
+
+    let stats = unpack_stats(&result.wrap_stats.unwrap());
+    assert_eq!(
+        u128::from(stats.total_wrapped),
+        u128::from(wrap_amount - fee)
+    );
+    assert_eq!(u128::from(stats.total_wrap_fees), u128::from(fee));
+}
+
+#[test]
+fn test_wrap_stats_with_multisig() {
+    let result = WrapBuilder::default()
+        .transfer_authority(setup_multisig(TokenProgram::SplToken))
+        .wrap_amount(500)
+        .wrap_stats(funded_stats_account())
+        .execute();
+
+    let stats = unpack_stats(&result.wrap_stats.unwrap());
+    assert_eq!(u128::from(stats.total_wrapped), 500);
+}
+
+#[test]
+fn test_wrap_stats_overflow() {
+    let existing = WrapStats {
+        wrap_count: u64::MAX.into(),
+        ..WrapStats::zeroed()
+    };
+
+    WrapBuilder::default()
+        .wrap_stats(stats_account(&existing))
+        .check(Check::err(ProgramError::ArithmeticOverflow))
+        .execute();
+}
+
+#[test]
+fn test_wrap_stats_wrong_owner() {
+    let mut account = stats_account(&WrapStats::zeroed());
+    account.owner = Pubkey::new_unique();
+
+    WrapBuilder::default()
+        .wrap_stats(account)
+        .check(Check::err(ProgramError::InvalidAccountOwner))
+        .execute();
+}
+
+#[test]
+fn test_unwrap_creates_stats() {
+    let result = UnwrapBuilder::default()
+        .unwrap_amount(300)
+        .slot(7)
+        .wrap_stats(funded_stats_account())
+        .execute();
+
+    let wrap_stats = result.wrap_stats.unwrap();
+    assert_eq!(
+        wrap_stats.key,
+        get_wrap_stats_address(&result.wrapped_mint.key)
+    );
+
+    let stats = unpack_stats(&wrap_stats);
+    assert_eq!(u128::from(stats.total_wrapped), 0);
+    assert_eq!(u128::from(stats.total_unwrapped), 300);
+    assert_eq!(u64::from(stats.wrap_count), 0);
+    assert_eq!(u64::from(stats.unwrap_count), 1);
+    assert_eq!(u64::from(stats.last_activity_slot), 7);
+}
+
+#[test]
+fn test_unwrap_stats_with_multisig() {
+    let existing = WrapStats {
+        total_wrapped: 1_000u128.into(),
+        wrap_count: 1.into(),
+        ..WrapStats::zeroed()
+    };
+
+    let result = UnwrapBuilder::default()
+        .transfer_authority(setup_multisig(TokenProgram::SplToken2022))
+        .unwrap_amount(400)
+        .wrap_stats(stats_account(&existing))
+        .execute();
+
+    let stats = unpack_stats(&result.wrap_stats.unwrap());
+    assert_eq!(u128::from(stats.total_wrapped), 1_000);
+    assert_eq!(u128::from(stats.total_unwrapped), 400);
+    assert_eq!(u64::from(stats.unwrap_count), 1);
+}
+
+#[test]
+fn test_wrap_stats_mismatch() {
+    // Statistics account of another wrapped mint
+    WrapBuilder::default()
+        .wrap_stats(funded_stats_account())
+        .wrap_stats_address(get_wrap_stats_address(&Pubkey::new_unique()))
+        .check(Check::err(TokenWrapError::WrapStatsMismatch.into()))
+        .execute();
+}
@@ -0,0 +1,306 @@
+use {
+    crate::helpers::{
+        common::{KeyedAccount, TokenProgram},
+        execute_admin_action_builder::ExecuteAdminActionBuilder,
+        mint_builder::MintBuilder,
+        set_wrapped_mint_admin_builder::SetWrappedMintAdminBuilder,
+        token_account_builder::TokenAccountBuilder,
+    },
+    mollusk_svm::result::Check,
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_2022_interface::{
+        error::TokenError,
+        extension::{
+            pausable::PausableConfig, transfer_fee::TransferFeeConfig, BaseStateWithExtensions,
+            PodStateWithExtensions,
+        },
+        pod::{PodAccount, PodMint},
+        state::AccountState,
+    },
+    spl_token_metadata_interface::state::{Field, TokenMetadata},
+    spl_token_wrap::{
+        error::TokenWrapError,
+        get_wrapped_mint_address, get_wrapped_mint_admin_address,
+        instruction::AdminAction,
+        state::{VersionedState, WrappedMintAdmin},
+    },
+};
+
+pub mod helpers;
+
+fn is_paused(wrapped_mint: &KeyedAccount) -> bool {
+    let mint = PodStateWithExtensions::<PodMint>::unpack(&wrapped_mint.account.data).unwrap();
+    bool::from(mint.get_extension::<PausableConfig>().unwrap().paused)
+}
+
+fn account_state(token_account: &KeyedAccount) -> u8 {
+    PodStateWithExtensions::<PodAccount>::unpack(&token_account.account.data)
+        .unwrap()
+        .base
+        .state
+}
+
+#[test]
+fn test_set_wrapped_mint_admin() {
+    let admin = Pubkey::new_unique();
+    let result = SetWrappedMintAdminBuilder::default().admin(admin).execute();
+
+    assert_eq!(
+        result.wrapped_mint_admin.account.owner,
+        spl_token_wrap::id()
+    );
+    let state =
+        WrappedMintAdmin::unpack_versioned(&result.wrapped_mint_admin.account.data).unwrap();
+    assert_eq!(state.admin(), Some(admin));
+}
+
+#[test]
+fn test_set_wrapped_mint_admin_removes_admin() {
+    let result = SetWrappedMintAdminBuilder::default()
+        .admin(Pubkey::default())
+        .execute();
+
+    let state =
+        WrappedMintAdmin::unpack_versioned(&result.wrapped_mint_admin.account.data).unwrap();
+    assert_eq!(state.admin(), None);
+}
+
+#[test]
+fn test_set_wrapped_mint_admin_fails_incorrect_authority() {
+    let mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(Pubkey::new_unique())
+        .build();
+
+    SetWrappedMintAdminBuilder::default()
+        .unwrapped_mint(mint)
+        .check(Check::err(ProgramError::IncorrectAuthority))
+        .execute();
+}
+
+#[test]
+fn test_set_wrapped_mint_admin_fails_missing_signature() {
+    SetWrappedMintAdminBuilder::default()
+        .authority_not_signer()
+        .check(Check::err(ProgramError::MissingRequiredSignature))
+        .execute();
+}
+
+#[test]
+fn test_set_wrapped_mint_admin_fails_wrapped_mint_mismatch() {
+    let wrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .build();
+
+    SetWrappedMintAdminBuilder::default()
+        .wrapped_mint(wrapped_mint)
+        .check(Check::err(TokenWrapError::WrappedMintMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_set_wrapped_mint_admin_fails_admin_address_mismatch() {
+    SetWrappedMintAdminBuilder::default()
+        .wrapped_mint_admin(KeyedAccount {
+            key: get_wrapped_mint_admin_address(&Pubkey::new_unique()),
+            account: Account {
+                lamports: Rent::default().minimum_balance(WrappedMintAdmin::LEN),
+                ..Default::default()
+            },
+        })
+        .check(Check::err(TokenWrapError::WrappedMintAdminMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_execute_admin_action_pause_by_unwrapped_mint_authority() {
+    let result = ExecuteAdminActionBuilder::default()
+        .action(AdminAction::Pause)
+        .execute();
+
+    assert!(is_paused(&result.wrapped_mint));
+}
+
+#[test]
+fn test_execute_admin_action_pause_and_resume_by_appointed_admin() {
+    let admin = Pubkey::new_unique();
+    let result = ExecuteAdminActionBuilder::default()
+        .appointed_admin(admin)
+        .admin(admin)
+        .action(AdminAction::Pause)
+        .execute();
+    assert!(is_paused(&result.wrapped_mint));
+
+    let unwrapped_mint_authority = Pubkey::new_unique();
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(unwrapped_mint_authority)
+        .build();
+    let paused_wrapped_mint = ExecuteAdminActionBuilder::default()
+        .unwrapped_mint_authority(unwrapped_mint_authority)
+        .unwrapped_mint(unwrapped_mint.clone())
+        .action(AdminAction::Pause)
+        .execute()
+        .wrapped_mint;
+
+    let result = ExecuteAdminActionBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .wrapped_mint(paused_wrapped_mint)
+        .appointed_admin(admin)
+        .admin(admin)
+        .action(AdminAction::Resume)
+        .execute();
+    assert!(!is_paused(&result.wrapped_mint));
+}
+
+#[test]
+fn test_execute_admin_action_freeze_and_thaw() {
+    let admin = Pubkey::new_unique();
+    let result = ExecuteAdminActionBuilder::default()
+        .appointed_admin(admin)
+        .admin(admin)
+        .action(AdminAction::FreezeAccount)
+        .execute();
+    assert_eq!(
+        account_state(&result.wrapped_token_account.unwrap()),
+        AccountState::Frozen as u8
+    );
+
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(Pubkey::new_unique())
+        .build();
+    let wrapped_mint = ExecuteAdminActionBuilder::default_wrapped_mint(&unwrapped_mint.key);
+    let frozen_account = TokenAccountBuilder::default()
+        .token_program(TokenProgram::SplToken2022)
+        .mint(wrapped_mint.clone())
+        .state(AccountState::Frozen)
+        .build();
+
+    let result = ExecuteAdminActionBuilder::default()
+        .unwrapped_mint(unwrapped_mint)
+        .wrapped_mint(wrapped_mint)
+        .wrapped_token_account(frozen_account)
+        .appointed_admin(admin)
+        .admin(admin)
+        .action(AdminAction::ThawAccount)
+        .execute();
+    assert_eq!(
+        account_state(&result.wrapped_token_account.unwrap()),
+        AccountState::Initialized as u8
+    );
+}
+
+#[test]
+fn test_execute_admin_action_update_metadata_field() {
+    let result = ExecuteAdminActionBuilder::default()
+        .action(AdminAction::UpdateMetadataField {
+            field: Field::Name,
+            value: "Wrapped Token With A Longer Name".to_string(),
+        })
+        .execute();
+
+    let mint =
+        PodStateWithExtensions::<PodMint>::unpack(&result.wrapped_mint.account.data).unwrap();
+    let metadata = mint.get_variable_len_extension::<TokenMetadata>().unwrap();
+    assert_eq!(metadata.name, "Wrapped Token With A Longer Name");
+}
+
+#[test]
+fn test_execute_admin_action_set_transfer_fee() {
+    let result = ExecuteAdminActionBuilder::default()
+        .action(AdminAction::SetTransferFee {
+            transfer_fee_basis_points: 25,
+            maximum_fee: 1_000,
+        })
+        .execute();
+
+    let mint =
+        PodStateWithExtensions::<PodMint>::unpack(&result.wrapped_mint.account.data).unwrap();
+    let config = mint.get_extension::<TransferFeeConfig>().unwrap();
+    assert_eq!(
+        u16::from(config.newer_transfer_fee.transfer_fee_basis_points),
+        25
+    );
+    assert_eq!(u64::from(config.newer_transfer_fee.maximum_fee), 1_000);
+}
+
+#[test]
+fn test_execute_admin_action_fails_unauthorized_admin() {
+    ExecuteAdminActionBuilder::default()
+        .appointed_admin(Pubkey::new_unique())
+        .admin(Pubkey::new_unique())
+        .check(Check::err(ProgramError::IncorrectAuthority))
+        .execute();
+}
+
+#[test]
+fn test_execute_admin_action_fails_removed_admin() {
+    ExecuteAdminActionBuilder::default()
+        .appointed_admin(Pubkey::default())
+        .admin(Pubkey::default())
+        .check(Check::err(ProgramError::IncorrectAuthority))
+        .execute();
+}
+
+#[test]
+fn test_execute_admin_action_fails_appointed_admin_not_signer() {
+    let admin = Pubkey::new_unique();
+    ExecuteAdminActionBuilder::default()
+        .appointed_admin(admin)
+        .admin(admin)
+        .admin_not_signer()
+        .check(Check::err(ProgramError::MissingRequiredSignature))
+        .execute();
+}
+
+#[test]
+fn test_execute_admin_action_fails_admin_address_mismatch() {
+    ExecuteAdminActionBuilder::default()
+        .wrapped_mint_admin_address(Pubkey::new_unique())
+        .check(Check::err(TokenWrapError::WrappedMintAdminMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_execute_admin_action_fails_wrapped_mint_mismatch() {
+    ExecuteAdminActionBuilder::default()
+        .wrapped_mint(
+            MintBuilder::new()
+                .token_program(TokenProgram::SplToken2022)
+                .build(),
+        )
+        .check(Check::err(TokenWrapError::WrappedMintMismatch.into()))
+        .execute();
+}
+
+#[test]
+fn test_execute_admin_action_fails_without_freeze_authority() {
+    let unwrapped_mint_authority = Pubkey::new_unique();
+    let unwrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken)
+        .mint_authority(unwrapped_mint_authority)
+        .build();
+
+    // The freeze authority is copied from the unwrapped mint, so the wrapped
+    // mint authority cannot freeze
+    let wrapped_mint = MintBuilder::new()
+        .token_program(TokenProgram::SplToken2022)
+        .mint_key(get_wrapped_mint_address(
+            &unwrapped_mint.key,
+            &TokenProgram::SplToken2022.id(),
+        ))
+        .freeze_authority(Pubkey::new_unique())
+        .build();
+
+    ExecuteAdminActionBuilder::default()
+        .unwrapped_mint_authority(unwrapped_mint_authority)
+        .unwrapped_mint(unwrapped_mint)
+        .wrapped_mint(wrapped_mint)
+        .action(AdminAction::FreezeAccount)
+        .check(Check::err(TokenError::OwnerMismatch.into()))
+        .execute();
+}
@@ -0,0 +1,329 @@
+use {
+    crate::helpers::{
+        common::{list_data, versioned_state_data, KeyedAccount, TokenProgram},
+        create_mint_builder::CreateMintBuilder,
+        register_wrapped_mint_builder::RegisterWrappedMintBuilder,
+    },
+    mollusk_svm::result::Check,
+    solana_account::Account,
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    spl_token_wrap::{
+        error::TokenWrapError,
+        get_wrapped_mint_address, get_wrapped_mint_backpointer_address,
+        get_wrapped_mint_registry_page_address,
+        state::{
+            Backpointer, VersionedListEntry, VersionedState, WrappedMintIndexEntry,
+            WrappedMintRegistry, WrappedMintRegistryEntry,
+        },
+    },
+};
+
+pub mod helpers;
+
+fn random_entries(count: usize) -> Vec<WrappedMintRegistryEntry> {
+    (0..count)
+        .map(|_| WrappedMintRegistryEntry {
+            unwrapped_mint: Pubkey::new_unique(),
+            wrapped_mint: Pubkey::new_unique(),
+            wrapped_token_program: spl_token::id(),
+        })
+        .collect()
+}
+
+fn registry_account(entry_count: u64) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(WrappedMintRegistry::LEN),
+        owner: spl_token_wrap::id(),
+        data: versioned_state_data(&WrappedMintRegistry {
+            entry_count: entry_count.into(),
+        }),
+        ..Default::default()
+    }
+}
+
+fn list_account<T: VersionedListEntry>(entries: &[T]) -> Account {
+    Account {
+        lamports: Rent::default()
+            .minimum_balance(T::list_len(entries.len().checked_add(1).unwrap())),
+        owner: spl_token_wrap::id(),
+        data: list_data(entries),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_create_mint_creates_registry() {
+    let result = CreateMintBuilder::default()
+        .wrapped_token_program(TokenProgram::SplToken2022)
+        .execute();
+
+    let expected_entry = WrappedMintRegistryEntry {
+        unwrapped_mint: result.unwrapped_mint.key,
+        wrapped_mint: result.wrapped_mint.key,
+        wrapped_token_program: spl_token_2022_interface::id(),
+    };
+
+    assert_eq!(result.registry.account.owner, spl_token_wrap::id());
+    let registry = WrappedMintRegistry::unpack_versioned(&result.registry.account.data).unwrap();
+    assert_eq!(u64::from(registry.entry_count), 1);
+    assert_eq!(registry.page_count(), 1);
+
+    assert_eq!(
+        result.registry_page.key,
+        get_wrapped_mint_registry_page_address(0)
+    );
+    assert_eq!(
+        WrappedMintRegistryEntry::unpack_list(&result.registry_page.account.data).unwrap(),
+        &[expected_entry]
+    );
+
+    assert_eq!(
+        WrappedMintIndexEntry::unpack_list(&result.wrapped_mint_index.account.data).unwrap(),
+        &[WrappedMintIndexEntry {
+            wrapped_mint: result.wrapped_mint.key,
+            wrapped_token_program: spl_token_2022_interface::id(),
+        }]
+    );
+}
+
+#[test]
+fn test_create_mint_appends_to_registry() {
+    let unwrapped_mint = Pubkey::new_unique();
+    let existing_entries = random_entries(5);
+    let existing_variant = WrappedMintIndexEntry {
+        wrapped_mint: get_wrapped_mint_address(&unwrapped_mint, &spl_token::id()),
+        wrapped_token_program: spl_token::id(),
+    };
+
+    let result = CreateMintBuilder::default()
+        .unwrapped_mint_addr(unwrapped_mint)
+        .wrapped_token_program(TokenProgram::SplToken2022)
+        .registry_account(registry_account(5))
+        .registry_page(KeyedAccount {
+            key: get_wrapped_mint_registry_page_address(0),
+            account: list_account(&existing_entries),
+        })
+        .wrapped_mint_index_account(list_account(&[existing_variant]))
+        .execute();
+
+    let registry = WrappedMintRegistry::unpack_versioned(&result.registry.account.data).unwrap();
+    assert_eq!(u64::from(registry.entry_count), 6);
+
+    let entries =
+        WrappedMintRegistryEntry::unpack_list(&result.registry_page.account.data).unwrap();
+    assert_eq!(entries.len(), 6);
+    assert_eq!(&entries[..5], &existing_entries[..]);
+    assert_eq!(entries[5].unwrapped_mint, unwrapped_mint);
+    assert_eq!(entries[5].wrapped_mint, result.wrapped_mint.key);
+
+    let variants =
+        WrappedMintIndexEntry::unpack_list(&result.wrapped_mint_index.account.data).unwrap();
+    assert_eq!(variants.len(), 2);
+    assert_eq!(variants[0], existing_variant);
+    assert_eq!(variants[1].wrapped_mint, result.wrapped_mint.key);
+    assert_eq!(
+        variants[1].wrapped_token_program,
+        spl_token_2022_interface::id()
+    );
+}
+
+#[test]
+fn test_create_mint_starts_new_registry_page() {
+    let entry_count = WrappedMintRegistry::ENTRIES_PER_PAGE;
+    let full_page = KeyedAccount {
+        key: get_wrapped_mint_registry_page_address(0),
+        account: list_account(&random_entries(entry_count as usize)),
+    };
+
+    // The first page is full
+    CreateMintBuilder::default()
+        .registry_account(registry_account(entry_count))
+        .registry_page(full_page)
+        .check(Check::err(
+            TokenWrapError::WrappedMintRegistryPageMismatch.into(),
+        ))
+        .execute();
+
+    let result = CreateMintBuilder::default()
+        .registry_account(registry_account(entry_count))
+        .registry_page(KeyedAccount {
+            key: get_wrapped_mint_registry_page_address(1),
+            account: Account {
+                lamports: 100_000_000,
+                ..Default::default()
+            },
+        })
+        .execute();
+
+    let registry = WrappedMintRegistry::unpack_versioned(&result.registry.account.data).unwrap();
+    assert_eq!(u64::from(registry.entry_count), entry_count + 1);
+    assert_eq!(registry.page_count(), 2);
+    assert_eq!(registry.next_page_index(), 1);
+    assert_eq!(
+        WrappedMintRegistryEntry::unpack_list(&result.registry_page.account.data)
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_fail_create_mint_wrong_registry_page() {
+    CreateMintBuilder::default()
+        .registry_page(KeyedAccount {
+            key: Pubkey::new_unique(),
+            account: Account {
+                lamports: 100_000_000,
+                ..Default::default()
+            },
+        })
+        .check(Check::err(
+            TokenWrapError::WrappedMintRegistryPageMismatch.into(),
+        ))
+        .execute();
+}
+
+#[test]
+fn test_fail_create_mint_registry_not_funded() {
+    // The page was funded for its current size only
+    let mut page = list_account(&random_entries(3));
+    page.lamports = Rent::default().minimum_balance(page.data.len());
+
+    CreateMintBuilder::default()
+        .registry_account(registry_account(3))
+        .registry_page(KeyedAccount {
+            key: get_wrapped_mint_registry_page_address(0),
+            account: page,
+        })
+        .check(Check::err(ProgramError::AccountNotRentExempt))
+        .execute();
+}
+
+#[test]
+fn test_fail_create_mint_registry_page_out_of_sync() {
+    // The registry counts more entries than the page holds
+    CreateMintBuilder::default()
+        .registry_account(registry_account(4))
+        .registry_page(KeyedAccount {
+            key: get_wrapped_mint_registry_page_address(0),
+            account: list_account(&random_entries(3)),
+        })
+        .check(Check::err(ProgramError::InvalidAccountData))
+        .execute();
+}
+
+#[test]
+fn test_idempotent_create_mint_does_not_register_again() {
+    let result = CreateMintBuilder::default()
+        .idempotent()
+        .wrapped_mint_account(Account {
+            data: vec![1; 10],
+            owner: spl_token_2022_interface::id(),
+            ..Account::default()
+        })
+        .backpointer_account(Account {
+            owner: spl_token_wrap::id(),
+            ..Account::default()
+        })
+        .execute();
+
+    assert!(result.registry.account.data.is_empty());
+    assert!(result.registry_page.account.data.is_empty());
+    assert!(result.wrapped_mint_index.account.data.is_empty());
+}
+
+#[test]
+fn test_register_wrapped_mint_backfills_registry() {
+    let existing_entries = random_entries(5);
+
+    let result = RegisterWrappedMintBuilder::default()
+        .wrapped_token_program(TokenProgram::SplToken)
+        .registry_account(registry_account(5))
+        .registry_page(KeyedAccount {
+            key: get_wrapped_mint_registry_page_address(0),
+            account: list_account(&existing_entries),
+        })
+        .execute();
+
+    let registry = WrappedMintRegistry::unpack_versioned(&result.registry.account.data).unwrap();
+    assert_eq!(u64::from(registry.entry_count), 6);
+
+    let entries =
+        WrappedMintRegistryEntry::unpack_list(&result.registry_page.account.data).unwrap();
+    assert_eq!(&entries[..5], &existing_entries[..]);
+    assert_eq!(
+        entries[5],
+        WrappedMintRegistryEntry {
+            unwrapped_mint: result.unwrapped_mint,
+            wrapped_mint: result.wrapped_mint.key,
+            wrapped_token_program: spl_token::id(),
+        }
+    );
+
+    assert_eq!(
+        WrappedMintIndexEntry::unpack_list(&result.wrapped_mint_index.account.data).unwrap(),
+        &[WrappedMintIndexEntry {
+            wrapped_mint: result.wrapped_mint.key,
+            wrapped_token_program: spl_token::id(),
+        }]
+    );
+}
+
+#[test]
+fn test_fail_register_wrapped_mint_already_registered() {
+    let unwrapped_mint = Pubkey::new_unique();
+    let registered = WrappedMintIndexEntry {
+        wrapped_mint: get_wrapped_mint_address(&unwrapped_mint, &spl_token_2022_interface::id()),
+        wrapped_token_program: spl_token_2022_interface::id(),
+    };
+
+    RegisterWrappedMintBuilder::default()
+        .unwrapped_mint_addr(unwrapped_mint)
+        .registry_account(registry_account(1))
+        .registry_page(KeyedAccount {
+            key: get_wrapped_mint_registry_page_address(0),
+            account: list_account(&random_entries(1)),
+        })
+        .wrapped_mint_index_account(list_account(&[registered]))
+        .check(Check::err(
+            TokenWrapError::WrappedMintAlreadyRegistered.into(),
+        ))
+        .execute();
+}
+
+#[test]
+fn test_fail_register_wrapped_mint_not_created() {
+    let unwrapped_mint = Pubkey::new_unique();
+
+    RegisterWrappedMintBuilder::default()
+        .unwrapped_mint_addr(unwrapped_mint)
+        .wrapped_mint(KeyedAccount {
+            key: get_wrapped_mint_address(&unwrapped_mint, &spl_token_2022_interface::id()),
+            account: Account::default(),
+        })
+        .check(Check::err(ProgramError::UninitializedAccount))
+        .execute();
+}
+
+#[test]
+fn test_fail_register_wrapped_mint_backpointer_mismatch() {
+    let unwrapped_mint = Pubkey::new_unique();
+    let wrapped_mint = get_wrapped_mint_address(&unwrapped_mint, &spl_token_2022_interface::id());
+
+    // The backpointer links another unwrapped mint
+    RegisterWrappedMintBuilder::default()
+        .unwrapped_mint_addr(unwrapped_mint)
+        .backpointer(KeyedAccount {
+            key: get_wrapped_mint_backpointer_address(&wrapped_mint),
+            account: Account {
+                lamports: Rent::default().minimum_balance(Backpointer::LEN),
+                owner: spl_token_wrap::id(),
+                data: versioned_state_data(&Backpointer::without_provenance(Pubkey::new_unique())),
+                ..Default::default()
+            },
+        })
+        .check(Check::err(TokenWrapError::UnwrappedMintMismatch.into()))
+        .execute();
+}